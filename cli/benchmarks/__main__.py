@@ -0,0 +1,98 @@
+"""Benchmarks for the import, sync dedup and backfill hot paths.
+
+Run from cli/:
+
+    uv run python -m benchmarks                 # full datasets, 5 runs each
+    uv run python -m benchmarks --quick         # a tenth of the rows, 1 run (CI)
+    uv run python -m benchmarks --filter import --save baseline.json
+    uv run python -m benchmarks --baseline baseline.json
+
+Every run gets a fresh temp DuckDB and TREELINE_DIR, so settings.json and
+the user's database are never read. Setup isn't timed. With --baseline, a
+bench whose median is more than --threshold slower than the baseline's
+fails the run (exit code 1).
+
+The arrow to JSON conversion behind the app's execute_query is benchmarked
+in Rust: `cargo bench -- --quick` in ui/src-tauri.
+"""
+
+import argparse
+import asyncio
+import json
+import os
+import statistics
+import sys
+import tempfile
+import time
+from pathlib import Path
+from typing import Dict, List
+
+from benchmarks.benches import BENCHES, Bench
+
+QUICK_SCALE = 10
+FULL_RUNS = 5
+DEFAULT_THRESHOLD = 0.2
+
+
+async def _time_once(bench: Bench, scale: int) -> float:
+    with tempfile.TemporaryDirectory() as tmp:
+        workdir = Path(tmp)
+        os.environ["TREELINE_DIR"] = str(workdir)
+        run = await bench.setup(workdir, scale)
+        started = time.perf_counter()
+        await run()
+        return time.perf_counter() - started
+
+
+def _run(bench: Bench, scale: int, runs: int) -> Dict[str, float]:
+    timings = [asyncio.run(_time_once(bench, scale)) for _ in range(runs)]
+    return {"min": min(timings), "median": statistics.median(timings), "runs": runs}
+
+
+def main(argv: List[str] | None = None) -> int:
+    parser = argparse.ArgumentParser(prog="python -m benchmarks", description=__doc__.split("\n")[0])
+    parser.add_argument("--quick", action="store_true", help="A tenth of the rows, one run each")
+    parser.add_argument("--filter", default="", help="Only benches whose name contains this")
+    parser.add_argument("--runs", type=int, help=f"Runs per bench (default {FULL_RUNS}, 1 with --quick)")
+    parser.add_argument("--save", type=Path, help="Write the results as JSON")
+    parser.add_argument("--baseline", type=Path, help="Compare with results saved by --save")
+    parser.add_argument(
+        "--threshold",
+        type=float,
+        default=DEFAULT_THRESHOLD,
+        help=f"Slowdown against --baseline that fails the run (default {DEFAULT_THRESHOLD * 100:.0f}%%)",
+    )
+    args = parser.parse_args(argv)
+
+    scale = QUICK_SCALE if args.quick else 1
+    runs = args.runs or (1 if args.quick else FULL_RUNS)
+    baseline = json.loads(args.baseline.read_text()) if args.baseline else {}
+
+    results = {}
+    regressions = []
+    for bench in BENCHES:
+        if args.filter not in bench.name:
+            continue
+        result = _run(bench, scale, runs)
+        result["scale"] = scale
+        results[bench.name] = result
+
+        line = f"{bench.name:<40} median {result['median']:8.3f}s  min {result['min']:8.3f}s"
+        previous = baseline.get(bench.name)
+        if previous and previous.get("scale") == scale:
+            change = result["median"] / previous["median"] - 1
+            line += f"  {change:+.1%} vs baseline"
+            if change > args.threshold:
+                regressions.append(bench.name)
+        print(line, flush=True)
+
+    if args.save:
+        args.save.write_text(json.dumps(results, indent=2) + "\n")
+    if regressions:
+        print(f"Slower than the baseline by more than {args.threshold:.0%}: {', '.join(regressions)}")
+        return 1
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
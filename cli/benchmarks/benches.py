@@ -0,0 +1,141 @@
+"""The benchmarks: each sets up a temp DuckDB, then the timed part runs once.
+
+setup(workdir, scale) builds the database and inputs and returns the
+coroutine function to time. scale divides the dataset sizes (10 with
+--quick), so a quick run exercises the same code on a tenth of the rows.
+"""
+
+from dataclasses import dataclass
+from datetime import timedelta
+from decimal import Decimal
+from pathlib import Path
+from typing import Any, Awaitable, Callable
+
+from treeline.app.account_service import AccountService
+from treeline.app.backfill_service import BackfillService
+from treeline.app.import_service import ImportService
+from treeline.app.integration_service import IntegrationService
+from treeline.app.preferences_service import PreferencesService
+from treeline.app.sync_service import SyncContext, SyncService
+from treeline.app.warning_service import WarningService
+from treeline.infra.csv import CSVProvider
+from treeline.infra.demo import DemoDataProvider
+
+from tests.support.datasets import (
+    CSV_COLUMN_MAPPING,
+    DATASET_START,
+    make_accounts,
+    make_balance_snapshot,
+    make_transactions,
+    seed_database,
+    write_csv,
+)
+
+Run = Callable[[], Awaitable[Any]]
+
+
+@dataclass(frozen=True)
+class Bench:
+    name: str
+    description: str
+    setup: Callable[[Path, int], Awaitable[Run]]
+
+
+async def _setup_import(workdir: Path, scale: int) -> Run:
+    """100k CSV rows into an account already holding the first 50k of them."""
+    (account,) = make_accounts(1)
+    rows = make_transactions(account, 100_000 // scale)
+    repository = await seed_database(workdir / "treeline.duckdb", [account])
+    service = ImportService(repository, {"csv": CSVProvider()})
+
+    seeded = await service.import_transactions(
+        "csv",
+        account.id,
+        {
+            "file_path": str(write_csv(workdir / "seed.csv", rows[: len(rows) // 2])),
+            "column_mapping": CSV_COLUMN_MAPPING,
+        },
+    )
+    assert seeded.success, seeded.error
+    options = {
+        "file_path": str(write_csv(workdir / "statement.csv", rows)),
+        "column_mapping": CSV_COLUMN_MAPPING,
+    }
+
+    async def run() -> Any:
+        result = await service.import_transactions("csv", account.id, options)
+        assert result.success, result.error
+        assert result.data["skipped"] == len(rows) // 2
+        return result
+
+    return run
+
+
+async def _setup_sync_dedup(workdir: Path, scale: int) -> Run:
+    """A dry-run sync of 20k provider transactions, half of them already stored."""
+    (account,) = make_accounts(1, provider="demo")
+    provider_id = account.external_ids["demo"]
+    stored = make_transactions(account, 10_000 // scale, seed=1, provider="demo")
+    fetched = stored + make_transactions(account, 10_000 // scale, seed=2, provider="demo")
+    repository = await seed_database(workdir / "treeline.duckdb", [account], stored)
+    service = SyncService(
+        {"demo": DemoDataProvider()},
+        repository,
+        AccountService(repository),
+        IntegrationService(repository),
+        PreferencesService(),
+        WarningService(repository),
+    )
+    data = {"transactions": [(provider_id, tx) for tx in fetched], "errors": []}
+
+    async def run() -> Any:
+        result = await service.sync_transactions(
+            "demo", accounts=[account], fetched=data, context=SyncContext(dry_run=True)
+        )
+        assert result.success, result.error
+        assert result.data["stats"]["skipped"] == len(stored)
+        return result
+
+    return run
+
+
+async def _setup_backfill(workdir: Path, scale: int) -> Run:
+    """Balance history for 10 accounts over 365 days of transactions."""
+    days = max(365 // scale, 1)
+    accounts = make_accounts(10)
+    transactions = [
+        tx for account in accounts for tx in make_transactions(account, days * 3, days=days)
+    ]
+    last_day = DATASET_START + timedelta(days=days - 1)
+    balances = [make_balance_snapshot(a, Decimal("2500.00"), last_day) for a in accounts]
+    repository = await seed_database(
+        workdir / "treeline.duckdb", accounts, transactions, balances
+    )
+    service = BackfillService(repository)
+
+    async def run() -> Any:
+        result = await service.backfill_balances()
+        assert result.success, result.error
+        assert result.data["snapshots_created"] > 0
+        return result
+
+    return run
+
+
+BENCHES = [
+    Bench(
+        "bench_import_100k_rows",
+        "CSV parse, fingerprints and dedup against 50k stored rows",
+        _setup_import,
+    ),
+    Bench(
+        "bench_sync_dedup_20k",
+        "Sync mapping, fingerprints and partition_new against 10k stored rows",
+        _setup_sync_dedup,
+    ),
+    Bench(
+        "bench_backfill_10_accounts_365_days",
+        "Backfill of daily balances from transactions, snapshots written",
+        _setup_backfill,
+    ),
+]
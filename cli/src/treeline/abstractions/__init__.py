@@ -3,10 +3,13 @@
 from treeline.abstractions.backup import BackupStorageProvider
 from treeline.abstractions.data import DataAggregationProvider, IntegrationProvider
 from treeline.abstractions.db import Repository
+from treeline.abstractions.write_lock import WriteLock, write_phase
 
 __all__ = [
     "BackupStorageProvider",
     "DataAggregationProvider",
     "IntegrationProvider",
     "Repository",
+    "WriteLock",
+    "write_phase",
 ]
@@ -4,7 +4,7 @@ from abc import ABC, abstractmethod
 from datetime import datetime
 from typing import Any, Dict, List
 
-from treeline.domain import Account, Result
+from treeline.domain import Account, Fail, Ok, Result
 
 
 class DataAggregationProvider(ABC):
@@ -23,6 +23,19 @@ class DataAggregationProvider(ABC):
     def can_get_balances(self) -> bool:
         pass
 
+    @property
+    def requires_network(self) -> bool:
+        """Whether this provider talks to a remote service (skipped while offline)."""
+        return False
+
+    @property
+    def tags_are_categories(self) -> bool:
+        """Whether transaction tags hold the provider's own category names.
+
+        Sync translates these through the category map (see treeline.category_map).
+        """
+        return False
+
     @abstractmethod
     async def get_accounts(
         self,
@@ -49,6 +62,80 @@ class DataAggregationProvider(ABC):
     ) -> Result[List[Account]]:
         pass
 
+    async def health_check(
+        self, provider_settings: Dict[str, Any] = {}
+    ) -> Result[Dict[str, Any]]:
+        """Check that the provider is reachable with these settings, without syncing.
+
+        Returns Ok({"status": "ok", "account_count": N}) on success. Failures
+        set context["kind"] to a short machine-readable error kind.
+
+        The default implementation calls get_accounts; providers with a
+        cheaper or more specific check should override it.
+        """
+        result = await self.get_accounts(provider_settings=provider_settings)
+        if not result.success:
+            return Fail(result.error, context={"kind": "error", **(result.context or {})})
+
+        accounts = result.data
+        if isinstance(accounts, dict):
+            accounts = accounts.get("accounts", [])
+        return Ok({"status": "ok", "account_count": len(accounts or [])})
+
+
+    async def fetch(
+        self,
+        start_date: datetime,
+        end_date: datetime,
+        provider_settings: Dict[str, Any] = {},
+    ) -> Result[Dict[str, Any]]:
+        """Fetch everything a sync needs: accounts and transactions in the date range.
+
+        Returns Ok({"accounts": [...], "transactions": [...], "errors": [...],
+        "balance_dates": {provider_account_id: date}}). Transactions are not
+        filtered by account; the caller maps and filters them.
+
+        The default implementation calls get_accounts and then get_transactions;
+        providers whose API returns both in one response should override it.
+        """
+        accounts: List[Account] = []
+        errors: List[str] = []
+        balance_dates: Dict[str, Any] = {}
+        if self.can_get_accounts:
+            accounts_result = await self.get_accounts(provider_settings=provider_settings)
+            if not accounts_result.success:
+                return accounts_result
+            data = accounts_result.data or []
+            if isinstance(data, dict):
+                accounts = data.get("accounts", [])
+                errors.extend(data.get("errors", []))
+                balance_dates = data.get("balance_dates", {})
+            else:
+                accounts = data
+
+        transactions: List[Any] = []
+        if self.can_get_transactions:
+            transactions_result = await self.get_transactions(
+                start_date, end_date, provider_settings=provider_settings
+            )
+            if not transactions_result.success:
+                return transactions_result
+            data = transactions_result.data or []
+            if isinstance(data, dict):
+                transactions = data.get("transactions", [])
+                errors.extend(data.get("errors", []))
+            else:
+                transactions = data
+
+        return Ok(
+            {
+                "accounts": accounts,
+                "transactions": transactions,
+                "errors": errors,
+                "balance_dates": balance_dates,
+            }
+        )
+
 
 class IntegrationProvider(ABC):
     @abstractmethod
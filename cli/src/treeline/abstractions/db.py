@@ -1,10 +1,29 @@
 """Database repository abstraction."""
 
 from abc import ABC, abstractmethod
-from typing import Any, Dict, List
+from datetime import date, datetime
+from decimal import Decimal
+from pathlib import Path
+from typing import Any, Callable, Dict, List, Tuple
 from uuid import UUID
 
-from treeline.domain import Account, BalanceSnapshot, Result, Transaction
+from treeline.domain import (
+    Account,
+    Attachment,
+    BalanceSnapshot,
+    DuplicateCandidate,
+    DuplicateStatus,
+    ImportRecord,
+    PlannedTransaction,
+    ProviderWarning,
+    Result,
+    Transaction,
+    TransactionStatus,
+    UnmappedTransaction,
+)
+
+# Rewrites one row (column name -> value) while copying a table
+RowTransform = Callable[[Dict[str, Any]], Dict[str, Any]]
 
 
 class Repository(ABC):
@@ -19,13 +38,71 @@ class Repository(ABC):
         pass
 
     @abstractmethod
-    async def add_account(self, account: Account) -> Result[Account]:
+    async def get_data_version(self) -> Result[Dict[str, Any]]:
+        """Get the data version recorded in the database.
+
+        Returns:
+            Result containing dict with:
+              - "data_version": int | None - version stored in sys_meta (None for new/legacy DBs)
+              - "min_app_version": str | None - treeline version that introduced that data version
+              - "supported_data_version": int - highest data version this build understands
+        """
+        pass
+
+    @abstractmethod
+    async def get_view_columns(self) -> Result[Dict[str, List[str]]]:
+        """Get the columns of every view in the database.
+
+        Returns:
+            Result containing dict of view name -> column names, in column order
+        """
+        pass
+
+    @abstractmethod
+    async def repair_views(self) -> Result[List[str]]:
+        """Recreate the views from the migrations' latest definitions.
+
+        Only the CREATE OR REPLACE VIEW statements are re-run; tables and
+        data are untouched.
+
+        Returns:
+            Result containing the names of the recreated views
+        """
+        pass
+
+    @abstractmethod
+    async def get_migration_status(self) -> Result[Dict[str, Any]]:
+        """Get the schema migrations applied to the database (read-only).
+
+        Returns:
+            Result containing dict with:
+              - "applied": int - number of applied migrations (0 for a new DB)
+              - "latest": str | None - name of the most recent migration
+        """
+        pass
+
+    @abstractmethod
+    async def add_account(
+        self, account: Account, opening_balance: BalanceSnapshot | None = None
+    ) -> Result[Account]:
+        """Add an account, and its opening balance snapshot if given.
+
+        The two are written together: if either fails, neither is saved.
+        """
         pass
 
     @abstractmethod
     async def add_transaction(self, transaction: Transaction) -> Result[Transaction]:
         pass
 
+    @abstractmethod
+    async def update_transaction(self, transaction: Transaction) -> Result[Transaction]:
+        """Save a transaction's amount, description, dates and external_ids.
+
+        Fails if no non-deleted transaction has that ID.
+        """
+        pass
+
     @abstractmethod
     async def add_balance(self, balance: BalanceSnapshot) -> Result[BalanceSnapshot]:
         pass
@@ -48,12 +125,24 @@ class Repository(ABC):
     ) -> Result[List[BalanceSnapshot]]:
         pass
 
+    @abstractmethod
+    async def update_balance_snapshot(
+        self, snapshot_id: UUID, balance: Decimal
+    ) -> Result[BalanceSnapshot]:
+        """Change a snapshot's balance, keeping created_at and bumping updated_at."""
+        pass
+
     @abstractmethod
     async def update_account_by_id(self, account: Account) -> Result[Account]:
         pass
 
     @abstractmethod
     async def get_accounts(self) -> Result[List[Account]]:
+        """Get all accounts.
+
+        Rows that can't be read are skipped; the result's
+        context["row_errors"] then lists them by table and primary key.
+        """
         pass
 
     @abstractmethod
@@ -61,37 +150,133 @@ class Repository(ABC):
         pass
 
     @abstractmethod
-    async def get_account_by_external_id(self, external_id: str) -> Result[Account]:
-        pass
+    async def get_accounts_by_external_ids(
+        self, provider_key: str, ids: List[str]
+    ) -> Result[List[Account]]:
+        """Get accounts whose external ID for provider_key is in ids."""
+
+    @abstractmethod
+    async def list_external_id_keys(self, table: str) -> Result[List[str]]:
+        """Distinct external_ids keys in use by "accounts" or "transactions"."""
 
     @abstractmethod
     async def get_transactions_by_external_ids(
-        self, external_ids: List[Dict[str, str]]
+        self, provider_key: str, ids: List[str]
     ) -> Result[List[Transaction]]:
+        """Get transactions whose external ID for provider_key is in ids.
+
+        Only the given provider's key is matched, so an ID from one provider
+        never matches another provider's ID or a fingerprint.
+        """
         pass
 
     @abstractmethod
     async def get_balance_snapshots(
         self, account_id: UUID | None = None, date: str | None = None
     ) -> Result[List[BalanceSnapshot]]:
+        """Get balance snapshots, optionally for one account and/or day.
+
+        Unreadable rows are skipped and reported in context["row_errors"].
+        """
+        pass
+
+    @abstractmethod
+    async def get_latest_balance_snapshots(
+        self, cutoffs: Dict[UUID, date]
+    ) -> Result[Dict[UUID, BalanceSnapshot]]:
+        """Get each account's most recent snapshot dated before its cutoff date.
+
+        One query for all accounts. Accounts with no earlier snapshot are
+        left out of the result.
+        """
         pass
 
     @abstractmethod
-    async def execute_query(self, sql: str) -> Result[Dict[str, Any]]:
+    async def get_transaction_overlap(
+        self, account_a: UUID, account_b: UUID
+    ) -> Result[Dict[str, int]]:
+        """Count transactions two accounts have in common.
+
+        Stored fingerprints include the account ID, so transactions are
+        matched on date and amount instead (each transaction matches at most
+        once). Deleted transactions and split children are not counted.
+
+        Returns:
+            Result containing dict with a and b (transactions counted per
+            account) and shared
+        """
+        pass
+
+    @abstractmethod
+    async def get_balance_as_of(self, account_id: UUID, as_of: date) -> Result[Dict[str, Any]]:
+        """Get an account's end-of-day balance on a date.
+
+        Uses a snapshot on that date when there is one, otherwise reconstructs
+        from the nearest snapshot and the transactions in between.
+
+        Returns dict with balance (None when unknown), status ("snapshot",
+        "reconstructed" or "unknown"), reconstructed, anchor (the snapshot used)
+        and transactions_applied.
+        """
+        pass
+
+    @abstractmethod
+    async def get_balances_as_of(
+        self, account_ids: List[UUID], dates: List[date]
+    ) -> Result[Dict[UUID, Dict[date, Dict[str, Any]]]]:
+        """Get end-of-day balances for several accounts on several dates at once.
+
+        Returns dict of account ID to dict of date to the same dict
+        get_balance_as_of returns.
+        """
+        pass
+
+    @abstractmethod
+    async def execute_query(
+        self, sql: str, timeout_secs: float | None = None
+    ) -> Result[Dict[str, Any]]:
         """
         Execute SQL query and return structured results.
 
         Args:
             sql: SQL query to execute
+            timeout_secs: Cancel the query after this many seconds (None or 0 =
+                unlimited). A cancelled query fails with context {"cancelled": True}.
 
         Returns:
             Result containing dict with:
               - "columns": List[str] - column names
+              - "column_types": List[str] - DuckDB type codes ("JSON" for JSON columns)
               - "rows": List[tuple] - result rows
               - "row_count": int - number of rows
         """
         pass
 
+    @abstractmethod
+    async def copy_query_to_file(
+        self,
+        sql: str,
+        output_path: Path,
+        file_format: str,
+        overwrite: bool = False,
+        timeout_secs: float | None = None,
+    ) -> Result[Dict[str, Any]]:
+        """
+        Write a read-only query's result to a file (DuckDB COPY ... TO).
+
+        Args:
+            sql: SELECT/WITH query
+            output_path: File to write; its directory must exist
+            file_format: One of COPY_FORMATS (parquet, csv or json)
+            overwrite: Replace output_path if it exists (fails otherwise)
+            timeout_secs: As for execute_query
+
+        Returns:
+            Result containing dict with "output" (str), "format" and "rows"
+            (the row count written)
+        """
+        pass
+
     @abstractmethod
     async def execute_write_query(self, sql: str) -> Result[None]:
         """
@@ -162,6 +347,17 @@ class Repository(ABC):
         """
         pass
 
+    @abstractmethod
+    async def bulk_update_transaction_tags(
+        self, tags_by_id: Dict[UUID, List[str]]
+    ) -> Result[int]:
+        """Replace the tags of several transactions in one transaction.
+
+        Returns:
+            Result containing the number of transactions updated
+        """
+        pass
+
     @abstractmethod
     async def get_date_range_info(self) -> Result[Dict[str, Any]]:
         """
@@ -174,13 +370,14 @@ class Repository(ABC):
 
     @abstractmethod
     async def get_transaction_counts_by_fingerprint(
-        self, fingerprints: List[str]
+        self, fingerprints: List[str], exclude_import_id: UUID | None = None
     ) -> Result[Dict[str, int]]:
         """
         Get count of existing transactions for each fingerprint.
 
         Args:
             fingerprints: List of fingerprint strings to check
+            exclude_import_id: Leave out rows written by this file import
 
         Returns:
             Result containing dict mapping fingerprint -> count
@@ -193,6 +390,7 @@ class Repository(ABC):
         self,
         account_id: UUID,
         order_by: str = "transaction_date DESC",
+        leaves_only: bool = False,
     ) -> Result[List[Transaction]]:
         """
         Get all transactions for a specific account.
@@ -200,12 +398,170 @@ class Repository(ABC):
         Args:
             account_id: Account ID to filter by
             order_by: SQL order clause (default: transaction_date DESC)
+            leaves_only: Only the rows the transactions view counts, as
+                get_transactions_in_range returns them
 
         Returns:
-            Result containing list of Transaction objects ordered as specified
+            Result containing list of Transaction objects ordered as specified;
+            unreadable rows are skipped and reported in context["row_errors"]
+        """
+        pass
+
+    @abstractmethod
+    async def get_all_transactions(
+        self, include_deleted: bool = False
+    ) -> Result[List[Transaction]]:
+        """Get all transactions, ordered by account, date and creation time."""
+        pass
+
+    @abstractmethod
+    async def get_transactions_in_range(
+        self, start_date: date, end_date: date
+    ) -> Result[List[Transaction]]:
+        """Get non-deleted transactions dated within [start_date, end_date].
+
+        Split parents that still have live children are left out wherever the
+        children are dated, as in the transactions view, so totals over any
+        period count a split once.
+        """
+        pass
+
+    @abstractmethod
+    async def count_transactions_added(self, since: datetime, until: datetime) -> Result[int]:
+        """Count non-deleted transactions added (synced or imported) in [since, until)."""
+        pass
+
+    @abstractmethod
+    async def get_transactions_filtered(
+        self,
+        account_id: UUID | None = None,
+        status: TransactionStatus | None = None,
+        start_date: date | None = None,
+        end_date: date | None = None,
+        search: str | None = None,
+        limit: int | None = None,
+    ) -> Result[List[Transaction]]:
+        """Get non-deleted transactions matching all given filters, newest first.
+
+        search matches descriptions containing the text, ignoring case.
+        """
+        pass
+
+    @abstractmethod
+    async def rebuild_search_index(self) -> Result[int]:
+        """Recompute the lowercased description search column for every transaction.
+
+        Returns how many rows were missing or stale.
+        """
+        pass
+
+    @abstractmethod
+    async def get_earliest_activity_dates(self) -> Result[Dict[UUID, date]]:
+        """Get each account's earliest transaction or balance snapshot date.
+
+        Deleted transactions are ignored; accounts with no activity are absent.
+        """
+        pass
+
+    @abstractmethod
+    async def scan_transactions_without_account(self, limit: int = 5) -> Result[Dict[str, Any]]:
+        """Find transactions whose account doesn't exist.
+
+        This and the other scan_ methods return dict with count (offending
+        rows) and ids (the first limit of them, sorted).
         """
         pass
 
+    @abstractmethod
+    async def scan_duplicate_fingerprints(self, limit: int = 5) -> Result[Dict[str, Any]]:
+        """Find non-deleted transactions that share a fingerprint with another."""
+        pass
+
+    @abstractmethod
+    async def scan_malformed_json(self, limit: int = 5) -> Result[Dict[str, Any]]:
+        """Find rows whose external_ids isn't a JSON object or whose tags contain NULL."""
+        pass
+
+    @abstractmethod
+    async def scan_dangling_parent_ids(self, limit: int = 5) -> Result[Dict[str, Any]]:
+        """Find transactions whose parent is missing or the transaction itself."""
+        pass
+
+    @abstractmethod
+    async def scan_snapshots_without_account(self, limit: int = 5) -> Result[Dict[str, Any]]:
+        """Find balance snapshots whose account doesn't exist."""
+        pass
+
+    @abstractmethod
+    async def get_missing_migrations(self) -> Result[List[str]]:
+        """Get the migrations shipped with this build that haven't been recorded as applied."""
+        pass
+
+    @abstractmethod
+    async def repair_malformed_json(self) -> Result[int]:
+        """Reset malformed external_ids to {} and drop NULL tags. Returns rows changed."""
+        pass
+
+    @abstractmethod
+    async def clear_dangling_parent_ids(self) -> Result[int]:
+        """Clear parent IDs that are missing or self-referential. Returns rows changed."""
+        pass
+
+    @abstractmethod
+    async def update_transactions_status(
+        self, transaction_ids: List[UUID], status: TransactionStatus
+    ) -> Result[int]:
+        """Set the status of many transactions. Returns how many were updated."""
+        pass
+
+    @abstractmethod
+    async def delete_transactions(self, transaction_ids: List[UUID]) -> Result[int]:
+        """Soft-delete transactions by setting deleted_at. Returns how many were deleted."""
+        pass
+
+    @abstractmethod
+    async def restore_transactions(self, transaction_ids: List[UUID]) -> Result[int]:
+        """Clear deleted_at on soft-deleted transactions. Returns how many were restored."""
+        pass
+
+    @abstractmethod
+    async def update_transaction_external_ids(
+        self, external_ids_by_id: Dict[UUID, Dict[str, str]]
+    ) -> Result[int]:
+        """Replace external_ids for many transactions in a single DB transaction."""
+        pass
+
+    @abstractmethod
+    async def get_transaction_by_id(self, transaction_id: UUID) -> Result[Transaction]:
+        """Get a single transaction by ID, including soft-deleted ones."""
+        pass
+
+    @abstractmethod
+    async def find_transaction_ids_by_prefix(
+        self, prefix: str, limit: int = 10
+    ) -> Result[List[UUID]]:
+        """Find transaction IDs starting with the given prefix (for short ID lookup)."""
+        pass
+
+    @abstractmethod
+    async def get_transactions_by_parent_id(
+        self, parent_transaction_id: UUID
+    ) -> Result[List[Transaction]]:
+        """Get the split children of a transaction."""
+        pass
+
+    @abstractmethod
+    async def get_children(self, parent_id: UUID) -> Result[List[Transaction]]:
+        """Get the non-deleted split parts of a transaction (empty if it isn't split)."""
+        pass
+
+    @abstractmethod
+    async def get_transactions_by_fingerprint(
+        self, fingerprint: str
+    ) -> Result[List[Transaction]]:
+        """Get non-deleted transactions with the given fingerprint."""
+        pass
+
     @abstractmethod
     async def upsert_integration(
         self, integration_name: str, integration_options: Dict[str, Any]
@@ -220,12 +576,173 @@ class Repository(ABC):
     async def delete_integration(self, integration_name: str) -> Result[None]:
         pass
 
+    @abstractmethod
+    async def get_budget_actuals(self, month: str) -> Result[List[Dict[str, Any]]]:
+        """Get spending for each tag-based expense category of a month (YYYY-MM).
+
+        Each dict has category_id, name, expected, actual and alerted_actual
+        (the level sync last alerted at, or None).
+        """
+        pass
+
+    @abstractmethod
+    async def set_budget_alert_levels(
+        self, levels: Dict[str, Decimal | None]
+    ) -> Result[None]:
+        """Set alerted_actual for budget categories by ID (None clears it)."""
+        pass
+
     @abstractmethod
     async def get_integration_settings(
         self, integration_name: str
     ) -> Result[Dict[str, Any]]:
         pass
 
+    @abstractmethod
+    async def record_provider_warnings(
+        self, integration_name: str, messages: List[str]
+    ) -> Result[None]:
+        """Persist provider warnings from a sync run.
+
+        Identical unresolved messages bump last_seen instead of creating a new row.
+        An empty messages list resolves all open warnings for the integration.
+        """
+        pass
+
+    @abstractmethod
+    async def list_provider_warnings(
+        self, include_resolved: bool = False
+    ) -> Result[List[ProviderWarning]]:
+        pass
+
+    @abstractmethod
+    async def resolve_provider_warning(self, warning_id: UUID) -> Result[None]:
+        pass
+
+    @abstractmethod
+    async def add_unmapped_transactions(
+        self, integration_name: str, items: List[Tuple[str, Transaction]]
+    ) -> Result[None]:
+        """Quarantine (provider_account_id, transaction) pairs that sync couldn't map.
+
+        Pairs already quarantined under the same provider transaction ID are ignored.
+        """
+        pass
+
+    @abstractmethod
+    async def get_unmapped_transactions(
+        self, provider_account_id: str | None = None
+    ) -> Result[List[UnmappedTransaction]]:
+        pass
+
+    @abstractmethod
+    async def adopt_unmapped_transactions(
+        self, provider_account_id: str, transactions: List[Transaction]
+    ) -> Result[int]:
+        """Insert transactions and clear the provider account's quarantine in one DB transaction."""
+        pass
+
+    @abstractmethod
+    async def record_import(self, record: ImportRecord) -> Result[None]:
+        """Record a file import, or update the counts of one already recorded."""
+        pass
+
+    @abstractmethod
+    async def get_import_progress(self, import_id: UUID) -> Result[Dict[str, int]]:
+        """Rows written so far by a file import: {"rows": n, "last_line": highest CSV line}."""
+        pass
+
+    @abstractmethod
+    async def delete_import(self, import_id: UUID) -> Result[int]:
+        """Delete a file import's record and every transaction it wrote; returns the row count."""
+        pass
+
+    @abstractmethod
+    async def get_import_by_file_hash(
+        self, file_sha256: str, account_id: UUID
+    ) -> Result[ImportRecord | None]:
+        """Get the most recent import of a file (by content hash) into an account."""
+        pass
+
+    @abstractmethod
+    async def list_imports(self) -> Result[List[ImportRecord]]:
+        pass
+
+    @abstractmethod
+    async def add_attachment(self, attachment: Attachment) -> Result[None]:
+        pass
+
+    @abstractmethod
+    async def get_attachments(self, transaction_id: UUID) -> Result[List[Attachment]]:
+        """Get a transaction's attachments, oldest first."""
+        pass
+
+    @abstractmethod
+    async def get_attachment(self, attachment_id: UUID) -> Result[Attachment | None]:
+        pass
+
+    @abstractmethod
+    async def delete_attachment(self, attachment_id: UUID) -> Result[None]:
+        pass
+
+    @abstractmethod
+    async def get_dangling_attachments(self) -> Result[List[Attachment]]:
+        """Get attachments whose transaction is soft-deleted or no longer exists."""
+        pass
+
+    @abstractmethod
+    async def upsert_planned_transaction(self, planned: PlannedTransaction) -> Result[None]:
+        """Add a planned transaction, or replace the one with its ID."""
+        pass
+
+    @abstractmethod
+    async def list_planned_transactions(self) -> Result[List[PlannedTransaction]]:
+        """Get every planned transaction, soonest due first."""
+        pass
+
+    @abstractmethod
+    async def delete_planned_transaction(self, planned_id: UUID) -> Result[None]:
+        pass
+
+    @abstractmethod
+    async def find_duplicate_transaction_pairs(self, since: date) -> Result[List[Tuple[UUID, UUID]]]:
+        """Pairs (keep, duplicate) of non-deleted transactions in one account
+        sharing a fingerprint, dated on or after since. The earliest stored
+        transaction of each group is kept."""
+        pass
+
+    @abstractmethod
+    async def add_duplicate_candidates(self, candidates: List[DuplicateCandidate]) -> Result[int]:
+        """Queue candidates for review, skipping pairs already recorded (either
+        way round, in any status); returns how many were added."""
+        pass
+
+    @abstractmethod
+    async def list_duplicate_candidates(
+        self, status: DuplicateStatus | None = None
+    ) -> Result[List[DuplicateCandidate]]:
+        """Get duplicate candidates, oldest first, optionally only those in one status."""
+        pass
+
+    @abstractmethod
+    async def set_duplicate_candidate_status(
+        self, candidate_id: UUID, status: DuplicateStatus
+    ) -> Result[None]:
+        pass
+
+    @abstractmethod
+    async def merge_duplicate_transaction(
+        self,
+        candidate_id: UUID,
+        keep_id: UUID,
+        duplicate_id: UUID,
+        external_ids: Dict[str, str],
+        tags: List[str],
+    ) -> Result[None]:
+        """Give the kept transaction the merged external_ids and tags, soft-delete
+        the duplicate and mark the candidate merged, in one DB transaction."""
+        pass
+
     @abstractmethod
     async def compact(self) -> Result[Dict[str, Any]]:
         """Compact the database to reclaim space from deleted rows.
@@ -240,3 +757,21 @@ class Repository(ABC):
         """
         pass
 
+    @abstractmethod
+    async def copy_to_database(
+        self, output_path: Path, tables: Dict[str, RowTransform | None]
+    ) -> Result[Dict[str, int]]:
+        """Copy tables into a new, unencrypted database, transforming each row.
+
+        The new database gets the full schema, so tables not listed exist
+        there but are empty. JSON columns reach transforms as strings.
+
+        Args:
+            output_path: Database file to create; must not exist
+            tables: Table name -> transform for each row, or None to copy
+                rows unchanged
+
+        Returns:
+            Result containing dict of table name -> rows copied
+        """
+        pass
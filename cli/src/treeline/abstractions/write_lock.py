@@ -0,0 +1,52 @@
+"""Write lock abstraction."""
+
+from abc import ABC, abstractmethod
+from contextlib import asynccontextmanager
+from typing import AsyncIterator
+
+from treeline.domain import Ok, Result
+
+
+class WriteLock(ABC):
+    """Lock held while an import, sync or backfill checks for duplicates and writes.
+
+    Serializes write phases across processes, so two of them can't each take
+    their dedupe snapshot before either inserts. Implementations are
+    reentrant within a process, so a sync's nested write phases don't wait
+    on each other.
+    """
+
+    @abstractmethod
+    async def acquire(self, operation: str) -> Result[None]:
+        """Take the lock, waiting for another process to release it.
+
+        Gives up with context kind "write_locked" and the holder.
+        """
+        pass
+
+    @abstractmethod
+    def release(self) -> None:
+        """Release the lock once the outermost holder in this process is done."""
+        pass
+
+    @asynccontextmanager
+    async def held(self, operation: str) -> AsyncIterator[Result[None]]:
+        """Hold the lock for a block; yields the result of acquire."""
+        result = await self.acquire(operation)
+        try:
+            yield result
+        finally:
+            if result.success:
+                self.release()
+
+
+@asynccontextmanager
+async def write_phase(
+    lock: WriteLock | None, operation: str, skip: bool = False
+) -> AsyncIterator[Result[None]]:
+    """Hold lock for a write phase; a no-op without a lock or with skip (dry runs)."""
+    if lock is None or skip:
+        yield Ok(None)
+        return
+    async with lock.held(operation) as result:
+        yield result
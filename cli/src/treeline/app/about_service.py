@@ -0,0 +1,94 @@
+"""Service for build and environment details, for `tl about` and bug reports."""
+
+import platform
+from pathlib import Path
+from typing import Any, Callable, Dict
+
+from treeline.abstractions import Repository
+from treeline.config import get_settings_path
+from treeline.domain import Ok, Result
+from treeline.utils import get_app_version, get_build_info
+
+
+class AboutService:
+    """Service for build and environment details.
+
+    Only paths, versions and counts are reported. Nothing is read from
+    settings or sys_integrations, so access URLs, tokens and passwords
+    can't end up in a pasted bug report.
+    """
+
+    def __init__(
+        self,
+        repository: Callable[[], Repository],
+        db_path: str,
+        supported_data_version: int,
+        demo_mode: bool,
+        encrypted: bool,
+        locked: bool,
+    ):
+        """Initialize the service.
+
+        Args:
+            repository: Returns the repository; only called when the
+                database exists and isn't locked
+            db_path: Path to the database file
+            supported_data_version: Highest data version this build understands
+            demo_mode: Whether the CLI is using the demo database
+            encrypted: Whether the database is encrypted
+            locked: Whether opening it would need a password prompt
+        """
+        self._repository = repository
+        self.db_path = Path(db_path)
+        self.supported_data_version = supported_data_version
+        self.demo_mode = demo_mode
+        self.encrypted = encrypted
+        self.locked = locked
+
+    async def about(self) -> Result[Dict[str, Any]]:
+        """Collect version, build, database and platform details.
+
+        Returns:
+            Result containing dict with version, git_commit, build_date,
+            python, os, arch, demo_mode, config_path and database (path,
+            backend, exists, size_bytes, encrypted, data_version,
+            supported_data_version, migrations_applied, latest_migration).
+            Database fields that need a connection are None when the
+            database doesn't exist or is encrypted without a password set.
+        """
+        settings_path = get_settings_path()
+        exists = self.db_path.exists()
+        database: Dict[str, Any] = {
+            "path": str(self.db_path),
+            "backend": "duckdb",
+            "exists": exists,
+            "size_bytes": self.db_path.stat().st_size if exists else None,
+            "encrypted": self.encrypted,
+            "data_version": None,
+            "supported_data_version": self.supported_data_version,
+            "migrations_applied": None,
+            "latest_migration": None,
+        }
+
+        if exists and not self.locked:
+            repository = self._repository()
+            version_result = await repository.get_data_version()
+            if version_result.success:
+                database["data_version"] = version_result.data["data_version"]
+            migrations_result = await repository.get_migration_status()
+            if migrations_result.success:
+                database["migrations_applied"] = migrations_result.data["applied"]
+                database["latest_migration"] = migrations_result.data["latest"]
+
+        return Ok(
+            {
+                "version": get_app_version(),
+                **get_build_info(),
+                "python": platform.python_version(),
+                "os": f"{platform.system()} {platform.release()}",
+                "arch": platform.machine(),
+                "demo_mode": self.demo_mode,
+                "config_path": str(settings_path) if settings_path.exists() else None,
+                "database": database,
+            }
+        )
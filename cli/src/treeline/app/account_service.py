@@ -1,12 +1,121 @@
 """Service for account operations."""
 
-from datetime import datetime, timezone, date
+import re
+import unicodedata
+from datetime import datetime, time, timezone, date
 from decimal import Decimal
-from typing import List
+from typing import Any, Dict, List, Tuple
 from uuid import UUID, uuid4
 
 from treeline.abstractions import Repository
-from treeline.domain import Account, BalanceSnapshot, Result
+from treeline.app.transaction_service import MIN_ID_PREFIX_LENGTH, find_by_external_id
+from treeline.domain import (
+    MAX_AMOUNT,
+    Account,
+    BalanceSnapshot,
+    Fail,
+    Ok,
+    Result,
+    amount_in_range,
+    normalize_tags,
+)
+
+
+# Account types accepted by tl accounts set-type; reports treat credit and
+# loan as liabilities (see classifier.LIABILITY_ACCOUNT_TYPES)
+ACCOUNT_TYPES = ("checking", "savings", "credit", "investment", "loan", "other")
+
+
+def parse_account_type(value: str) -> Result[str]:
+    """Normalize an account type, failing on anything not in ACCOUNT_TYPES."""
+    account_type = (value or "").strip().lower()
+    if account_type not in ACCOUNT_TYPES:
+        return Fail(
+            f"Invalid account type: {value!r} (expected one of {', '.join(ACCOUNT_TYPES)})"
+        )
+    return Ok(account_type)
+
+
+def _fold(text: str) -> str:
+    """Normalize a name for case-insensitive comparison (unicode-aware)."""
+    return " ".join(unicodedata.normalize("NFKC", text).casefold().split())
+
+
+def _describe(account: Account) -> str:
+    label = account.name
+    if account.nickname and account.nickname != account.name:
+        label += f" ({account.nickname})"
+    return f"{label} - {account.id}"
+
+
+def match_account(identifier: str, accounts: List[Account]) -> Result[Account]:
+    """Pick the account a user meant by a name or nickname.
+
+    Names and nicknames are compared case-insensitively (Unicode case
+    folding, whitespace collapsed; punctuation must match). A unique exact
+    match wins over prefix matches; otherwise a unique prefix match wins.
+    Ambiguous identifiers fail with the candidates and their IDs.
+    """
+    query = _fold(identifier or "")
+    if not query:
+        return Fail("Account name cannot be empty")
+
+    def names(account: Account) -> List[str]:
+        return [_fold(n) for n in (account.name, account.nickname) if n]
+
+    exact = [a for a in accounts if query in names(a)]
+    candidates = exact or [
+        a for a in accounts if any(n.startswith(query) for n in names(a))
+    ]
+
+    if not candidates:
+        return Fail(f"No account matches '{identifier}'")
+    if len(candidates) > 1:
+        listing = "\n".join(f"  {_describe(a)}" for a in candidates)
+        return Fail(
+            f"'{identifier}' matches {len(candidates)} accounts:\n{listing}\n"
+            "Use a longer name or --account-id"
+        )
+    return Result(success=True, data=candidates[0])
+
+
+def account_sort_key(account: Account) -> Tuple[str, str]:
+    """Sort key for listing accounts: name (case-insensitive), then ID."""
+    return (_fold(account.name), str(account.id))
+
+
+def institution_sort_key(account: Account) -> Tuple[bool, str, str, str]:
+    """Sort key grouping accounts by institution, then name.
+
+    Accounts with no institution (manual accounts, mostly) come last.
+    """
+    institution = _fold(account.institution_name or "")
+    return (not institution, institution, *account_sort_key(account))
+
+
+def short_id(account_id: UUID | str) -> str:
+    """First block of a UUID, e.g. "3f2a9c1e...", for output that has no name."""
+    return f"{str(account_id)[:8]}..."
+
+
+class AccountDirectory:
+    """Account names by ID for human-facing output.
+
+    Built once per command from get_accounts() so output that only has an
+    account_id (warnings, orphans, report rows) can show a name without its
+    own lookup. IDs with no account fall back to short_id().
+    """
+
+    def __init__(self, accounts: List[Account]):
+        self._names = {account.id: account.nickname or account.name for account in accounts}
+
+    def name_for(self, account_id: UUID | str) -> str:
+        """Nickname or name of an account, or its shortened ID if unknown."""
+        try:
+            key = account_id if isinstance(account_id, UUID) else UUID(str(account_id))
+        except ValueError:
+            return str(account_id)
+        return self._names.get(key) or short_id(key)
 
 
 class AccountService:
@@ -19,31 +128,98 @@ class AccountService:
         """Get all accounts."""
         return await self.repository.get_accounts()
 
+    async def directory(self) -> AccountDirectory:
+        """Account names by ID for labelling command output.
+
+        Labels are cosmetic, so a failed lookup gives an empty directory
+        (every ID shortened) rather than failing the command.
+        """
+        result = await self.repository.get_accounts()
+        return AccountDirectory(result.data or [] if result.success else [])
+
+    async def resolve_account(self, identifier: str) -> Result[Account]:
+        """Resolve an account from a UUID, name, or nickname.
+
+        A string that parses as a UUID is looked up by ID only; anything else
+        is matched against names and nicknames (see match_account).
+        """
+        try:
+            account_id = UUID(identifier.strip())
+        except (ValueError, AttributeError):
+            account_id = None
+
+        if account_id is not None:
+            result = await self.repository.get_account_by_id(account_id)
+            if not result.success or result.data is None:
+                return Fail(f"Account not found: {account_id}")
+            return result
+
+        accounts_result = await self.repository.get_accounts()
+        if not accounts_result.success:
+            return accounts_result
+        return match_account(identifier, accounts_result.data or [])
+
     async def create_account(
         self,
         name: str,
-        account_type: str,
+        account_type: str | None,
         institution: str | None = None,
         currency: str = "USD",
         balance: Decimal | None = None,
+        nickname: str | None = None,
+        opening_balance: Decimal | None = None,
+        as_of: date | None = None,
     ) -> Result[Account]:
         """Create a new account.
 
         Args:
-            name: Account name
+            name: Account name (must not be empty)
             account_type: Account type (no validation - accepts any string)
             institution: Optional institution name
-            currency: Currency code (default: USD)
+            currency: ISO 4217 currency code (default: USD)
             balance: Optional account balance
+            nickname: Optional display nickname
+            opening_balance: Optional opening balance, recorded as a manual snapshot
+            as_of: Date of the opening balance (defaults to today, cannot be in the future)
 
         Returns:
-            Result containing the created Account
+            Result containing the created Account. Invalid arguments fail
+            with context {"field": ...} naming the argument.
         """
+        if not name or not name.strip():
+            return Fail("Account name cannot be empty", context={"field": "name"})
+
+        currency = (currency or "").strip().upper()
+        if not re.fullmatch(r"[A-Z]{3}", currency):
+            return Fail(
+                f"Invalid currency code: {currency!r} (expected 3 letters, e.g. USD)",
+                context={"field": "currency"},
+            )
+
+        if as_of is not None and as_of > date.today():
+            return Fail(
+                f"Opening balance date cannot be in the future: {as_of}",
+                context={"field": "as_of"},
+            )
+
+        if opening_balance is not None and not opening_balance.is_finite():
+            return Fail(
+                f"Opening balance must be a number: {opening_balance}",
+                context={"field": "opening_balance"},
+            )
+
+        if opening_balance is not None and not amount_in_range(opening_balance):
+            return Fail(
+                f"Opening balance is too large: {opening_balance} (the largest is ±{MAX_AMOUNT})",
+                context={"field": "opening_balance"},
+            )
+
         # Create new account with generated UUID and timestamps
         now = datetime.now(timezone.utc)
         account = Account(
             id=uuid4(),
-            name=name,
+            name=name.strip(),
+            nickname=nickname,
             account_type=account_type,
             institution_name=institution,
             currency=currency,
@@ -53,13 +229,69 @@ class AccountService:
             updated_at=now,
         )
 
-        # Add to repository
-        add_result = await self.repository.add_account(account)
-        if not add_result.success:
-            return add_result
+        # The opening balance is a manual snapshot at midnight on as_of, as
+        # add_balance_snapshot records it, saved together with the account
+        snapshot = None
+        if opening_balance is not None:
+            snapshot = BalanceSnapshot(
+                id=uuid4(),
+                account_id=account.id,
+                balance=opening_balance,
+                snapshot_time=datetime.combine(as_of or date.today(), datetime.min.time()),
+                created_at=now,
+                updated_at=now,
+                source="manual",
+            )
+
+        return await self.repository.add_account(account, opening_balance=snapshot)
 
-        # Return the created account
-        return Result(success=True, data=account)
+    async def resolve_account_id(self, id_or_prefix: str) -> Result[Account]:
+        """Find an account by full ID or unambiguous ID prefix.
+
+        An ambiguous prefix fails with context {"matches": [...]} listing the
+        matching IDs.
+        """
+        value = (id_or_prefix or "").strip().lower()
+        try:
+            account_id = UUID(value)
+        except ValueError:
+            account_id = None
+        if account_id is not None:
+            result = await self.repository.get_account_by_id(account_id)
+            if not result.success or result.data is None:
+                return Fail(f"Account not found: {account_id}")
+            return result
+
+        if len(value) < MIN_ID_PREFIX_LENGTH:
+            return Fail(f"Account ID prefix must be at least {MIN_ID_PREFIX_LENGTH} characters")
+        accounts_result = await self.repository.get_accounts()
+        if not accounts_result.success:
+            return accounts_result
+        matches = [a for a in accounts_result.data or [] if str(a.id).startswith(value)]
+        if not matches:
+            return Fail(f"No account matches ID: {id_or_prefix}")
+        if len(matches) > 1:
+            return Fail(
+                f"Ambiguous account ID prefix: {id_or_prefix}",
+                context={"matches": [str(a.id) for a in matches]},
+            )
+        return Ok(matches[0])
+
+    async def find_by_external_id(
+        self, provider: str, value: str
+    ) -> Result[List[Tuple[Account, str]]]:
+        """Find accounts by a provider's external ID.
+
+        provider is an external_ids key such as simplefin, or "any". Returns
+        (account, matched key) pairs; see transaction_service.find_by_external_id.
+        """
+        return await find_by_external_id(
+            self.repository,
+            "accounts",
+            provider,
+            value,
+            self.repository.get_accounts_by_external_ids,
+        )
 
     async def update_account_type(
         self, account_id: UUID, account_type: str
@@ -68,15 +300,19 @@ class AccountService:
 
         Args:
             account_id: UUID of account to update
-            account_type: New account type (no validation - accepts any string)
+            account_type: New account type, one of ACCOUNT_TYPES (case-insensitive)
 
         Returns:
             Result containing the updated Account
         """
+        type_result = parse_account_type(account_type)
+        if not type_result.success:
+            return type_result
+
         # Get existing account
         get_result = await self.repository.get_account_by_id(account_id)
-        if not get_result.success:
-            return get_result
+        if not get_result.success or get_result.data is None:
+            return Fail(f"Account not found: {account_id}")
 
         existing_account = get_result.data
 
@@ -84,7 +320,7 @@ class AccountService:
         # Note: Account is frozen (immutable), so we use model_copy
         now = datetime.now(timezone.utc)
         updated_account = existing_account.model_copy(
-            update={"account_type": account_type, "updated_at": now}
+            update={"account_type": type_result.data, "updated_at": now}
         )
 
         # Update in repository
@@ -95,6 +331,131 @@ class AccountService:
         # Return the updated account
         return Result(success=True, data=updated_account)
 
+    async def set_account_dates(
+        self, account_id: UUID, dates: Dict[str, date | None]
+    ) -> Result[Dict[str, Any]]:
+        """Set or clear an account's opened_at and closed_at.
+
+        Args:
+            account_id: UUID of account to update
+            dates: Only the keys to change ("opened_at", "closed_at"); None
+                clears a date
+
+        Returns:
+            Result containing dict with the updated account and warnings.
+            Transactions after closed_at don't block closing the account;
+            they are counted in a warning instead.
+        """
+        unknown = set(dates) - {"opened_at", "closed_at"}
+        if unknown:
+            return Fail(f"Unknown account date: {', '.join(sorted(unknown))}")
+
+        get_result = await self.repository.get_account_by_id(account_id)
+        if not get_result.success:
+            return get_result
+        account = get_result.data
+
+        opened_at = dates.get("opened_at", account.opened_at)
+        closed_at = dates.get("closed_at", account.closed_at)
+        if opened_at and closed_at and closed_at < opened_at:
+            return Fail(f"Closed date {closed_at} is before opened date {opened_at}")
+
+        updated = account.model_copy(
+            update={
+                "opened_at": opened_at,
+                "closed_at": closed_at,
+                "updated_at": datetime.now(timezone.utc),
+            }
+        )
+        update_result = await self.repository.update_account_by_id(updated)
+        if not update_result.success:
+            return update_result
+
+        warnings = []
+        if closed_at is not None:
+            tx_result = await self.repository.get_transactions_by_account(account_id)
+            if not tx_result.success:
+                return tx_result
+            after = [
+                tx
+                for tx in tx_result.data or []
+                if tx.deleted_at is None and tx.transaction_date > closed_at
+            ]
+            if after:
+                warnings.append(
+                    f"{len(after)} transaction(s) dated after the closed date {closed_at} "
+                    f"(latest {max(tx.transaction_date for tx in after)})"
+                )
+
+        return Ok({"account": updated, "warnings": warnings})
+
+    async def set_nickname(self, account_id: UUID, nickname: str | None) -> Result[Account]:
+        """Set or clear (None) the name an account is shown under.
+
+        Sync never overwrites a nickname, so it outlasts provider renames.
+        """
+        if nickname is not None:
+            nickname = nickname.strip()
+            if not nickname:
+                return Fail("Nickname cannot be empty (use --clear to remove it)")
+
+        get_result = await self.repository.get_account_by_id(account_id)
+        if not get_result.success or get_result.data is None:
+            return Fail(f"Account not found: {account_id}")
+
+        updated = get_result.data.model_copy(
+            update={"nickname": nickname, "updated_at": datetime.now(timezone.utc)}
+        )
+        update_result = await self.repository.update_account_by_id(updated)
+        if not update_result.success:
+            return update_result
+        return Ok(updated)
+
+    async def set_archived(self, account_id: UUID, archived: bool) -> Result[Account]:
+        """Archive or unarchive an account.
+
+        An archived account is left out of status totals, balance backfills
+        and sync; its transactions stay queryable. Archiving an account that
+        is already archived keeps the original archived_at.
+        """
+        get_result = await self.repository.get_account_by_id(account_id)
+        if not get_result.success or get_result.data is None:
+            return Fail(f"Account not found: {account_id}")
+
+        account = get_result.data
+        if archived == (account.archived_at is not None):
+            return Ok(account)
+
+        now = datetime.now(timezone.utc)
+        updated = account.model_copy(
+            update={"archived_at": now if archived else None, "updated_at": now}
+        )
+        update_result = await self.repository.update_account_by_id(updated)
+        if not update_result.success:
+            return update_result
+        return Ok(updated)
+
+    async def set_default_tags(self, account_id: UUID, tags: List[str]) -> Result[Account]:
+        """Replace the tags added to new transactions in an account.
+
+        Tags are normalized; an empty list clears them. Transactions already
+        stored keep their tags (see MaintenanceService.apply_default_tags).
+        """
+        get_result = await self.repository.get_account_by_id(account_id)
+        if not get_result.success:
+            return get_result
+
+        updated = get_result.data.model_copy(
+            update={
+                "default_tags": tuple(normalize_tags(tags)),
+                "updated_at": datetime.now(timezone.utc),
+            }
+        )
+        update_result = await self.repository.update_account_by_id(updated)
+        if not update_result.success:
+            return update_result
+        return Ok(updated)
+
     async def add_balance_snapshot(
         self,
         account_id: UUID,
@@ -113,6 +474,9 @@ class AccountService:
         Returns:
             Result containing the created BalanceSnapshot or error if duplicate
         """
+        if not balance.is_finite():
+            return Fail(f"Balance must be a number: {balance}")
+
         # Verify account exists
         account_result = await self.repository.get_account_by_id(account_id)
         if not account_result.success:
@@ -169,3 +533,174 @@ class AccountService:
 
         # Return the created snapshot
         return Result(success=True, data=balance_snapshot)
+
+    async def add_balance_snapshots(
+        self,
+        accounts: List[Account],
+        snapshot_date: date | None = None,
+        source: str | None = None,
+    ) -> Result[List[BalanceSnapshot]]:
+        """Add balance snapshots for several pre-validated accounts at once.
+
+        Same deduplication as add_balance_snapshot, but existing snapshots for the
+        date are fetched with a single query and new ones are written in bulk.
+        Accounts without a balance are ignored.
+
+        Args:
+            accounts: Accounts known to exist (e.g., just upserted by sync)
+            snapshot_date: Date for the snapshots (defaults to today)
+            source: Source of the snapshots ('sync', 'manual', 'backfill')
+
+        Returns:
+            Result containing the BalanceSnapshots that were created
+        """
+        accounts = [acc for acc in accounts if acc.balance is not None]
+        if not accounts:
+            return Result(success=True, data=[])
+
+        if snapshot_date is None:
+            snapshot_date = date.today()
+        snapshot_time = datetime.combine(snapshot_date, datetime.min.time())
+
+        existing_result = await self.repository.get_balance_snapshots(
+            date=snapshot_date.isoformat()
+        )
+        if not existing_result.success:
+            return Result(success=False, error="Failed to check for existing snapshots")
+
+        existing_by_account: Dict[UUID, List[Decimal]] = {}
+        for snapshot in existing_result.data or []:
+            existing_by_account.setdefault(snapshot.account_id, []).append(snapshot.balance)
+
+        now = datetime.now(timezone.utc)
+        new_snapshots = []
+        for account in accounts:
+            has_same_balance = any(
+                abs(existing - account.balance) < Decimal("0.01")
+                for existing in existing_by_account.get(account.id, [])
+            )
+            if has_same_balance:
+                continue
+
+            new_snapshots.append(
+                BalanceSnapshot(
+                    id=uuid4(),
+                    account_id=account.id,
+                    balance=account.balance,
+                    snapshot_time=snapshot_time,
+                    created_at=now,
+                    updated_at=now,
+                    source=source,
+                )
+            )
+
+        if not new_snapshots:
+            return Result(success=True, data=[])
+
+        return await self.repository.bulk_add_balances(new_snapshots)
+
+    async def add_month_end_snapshots(
+        self,
+        month_end: date,
+        accounts: List[Account] | None = None,
+        dry_run: bool = False,
+    ) -> Result[List[BalanceSnapshot]]:
+        """Fill in a snapshot on a month-end for accounts that have none that day.
+
+        The balance comes from get_balance_as_of, so it is reconstructed from
+        the nearest snapshot and the transactions in between. Accounts with
+        any snapshot on month_end are left alone, which makes this safe to
+        run on every sync; archived accounts and accounts with no data before
+        month_end (balance unknown) are skipped.
+
+        Args:
+            month_end: Date for the snapshots (end of day)
+            accounts: Accounts already loaded by the caller (fetched if None)
+            dry_run: Work out the snapshots without writing them
+
+        Returns:
+            Result containing the BalanceSnapshots created (or that would be)
+        """
+        if accounts is None:
+            accounts_result = await self.repository.get_accounts()
+            if not accounts_result.success:
+                return accounts_result
+            accounts = accounts_result.data or []
+
+        existing_result = await self.repository.get_balance_snapshots(date=month_end.isoformat())
+        if not existing_result.success:
+            return Result(success=False, error="Failed to check for existing snapshots")
+        covered = {snapshot.account_id for snapshot in existing_result.data or []}
+
+        now = datetime.now(timezone.utc)
+        new_snapshots = []
+        for account in accounts:
+            if account.id in covered or account.archived_at is not None:
+                continue
+            balance_result = await self.repository.get_balance_as_of(account.id, month_end)
+            if not balance_result.success:
+                return balance_result
+            balance = balance_result.data["balance"]
+            if balance is None:
+                continue
+
+            new_snapshots.append(
+                BalanceSnapshot(
+                    id=uuid4(),
+                    account_id=account.id,
+                    balance=balance,
+                    snapshot_time=datetime.combine(month_end, time(23, 59, 59)),
+                    created_at=now,
+                    updated_at=now,
+                    source="auto",
+                )
+            )
+
+        if not new_snapshots or dry_run:
+            return Result(success=True, data=new_snapshots)
+
+        return await self.repository.bulk_add_balances(new_snapshots)
+
+    async def update_balance_snapshot(
+        self, snapshot_id: UUID, balance: Decimal
+    ) -> Result[BalanceSnapshot]:
+        """Correct the balance of an existing snapshot.
+
+        The snapshot keeps its date, source and created_at; updated_at
+        records when it was edited.
+        """
+        return await self.repository.update_balance_snapshot(snapshot_id, balance)
+
+    async def get_balances_as_of(
+        self, as_of: date, account_id: UUID | None = None
+    ) -> Result[List[Dict]]:
+        """Get end-of-day balances on a date for one account or all accounts.
+
+        Each entry is the repository's balance_as_of result plus
+        account_name. Accounts with no data at that date have balance None
+        (status "unknown") rather than zero.
+        """
+        accounts_result = await self.repository.get_accounts()
+        if not accounts_result.success:
+            return accounts_result
+
+        accounts = accounts_result.data or []
+        if account_id is not None:
+            accounts = [a for a in accounts if a.id == account_id]
+            if not accounts:
+                return Fail(f"Account not found: {account_id}")
+
+        balances = []
+        for account in accounts:
+            balance_result = await self.repository.get_balance_as_of(account.id, as_of)
+            if not balance_result.success:
+                return balance_result
+            balances.append(
+                {
+                    "account_name": account.nickname or account.name,
+                    "currency": account.currency,
+                    **balance_result.data,
+                }
+            )
+
+        return Result(success=True, data=balances)
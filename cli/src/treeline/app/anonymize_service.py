@@ -0,0 +1,343 @@
+"""Service for building anonymized copies of the database for bug reports."""
+
+import hashlib
+import json
+import secrets
+from decimal import ROUND_HALF_UP, Decimal
+from pathlib import Path
+from typing import Any, Callable, Dict, Iterable, List, Set
+
+from treeline.abstractions import Repository
+from treeline.domain import (
+    Fail,
+    Ok,
+    Result,
+    Transaction,
+    assign_fingerprints,
+    is_system_table,
+    normalize_fingerprint_description,
+)
+
+# Tables copied into the anonymized database. Everything else (integration
+# settings, provider warnings, attachments, unmapped sync data, planned
+# transactions, the duplicate review queue, rules and saved queries, community
+# plugin tables) is left out.
+COPIED_TABLES = (
+    "sys_accounts",
+    "sys_transactions",
+    "sys_balance_snapshots",
+    "sys_imports",
+    "sys_plugin_budget_categories",
+    "sys_plugin_budget_rollovers",
+    "sys_plugin_accounts_overrides",
+)
+
+# Written by the migrations in the new database rather than copied
+SCHEMA_TABLES = ("sys_migrations", "sys_meta")
+
+# Amounts are scaled by a factor in [1 - AMOUNT_JITTER, 1 + AMOUNT_JITTER]
+AMOUNT_JITTER = Decimal("0.20")
+
+# The verification pass fails if any original description shares a
+# substring this long with an anonymized one
+LEAK_SUBSTRING_LENGTH = 5
+
+# Fake merchant names are two pseudo-words built from these syllables
+_SYLLABLES = [c + v for c in "bdfgklmnprstvz" for v in "aeiou"]
+
+CENT = Decimal("0.01")
+
+
+def _ngrams(text: str, n: int = LEAK_SUBSTRING_LENGTH) -> Set[str]:
+    text = text.lower()
+    return {text[i : i + n] for i in range(len(text) - n + 1)}
+
+
+class _Scrambler:
+    """Deterministic replacements for one anonymization, keyed by a secret seed."""
+
+    def __init__(self, seed: str, original_descriptions: Iterable[str | None]):
+        self.seed = seed
+        self._original_ngrams: Set[str] = set()
+        for description in original_descriptions:
+            self._original_ngrams |= _ngrams(description or "")
+        self._merchants: Dict[str, str] = {}
+        self._used_merchants: Set[str] = set()
+
+    def _digest(self, *parts: Any) -> bytes:
+        text = "|".join([self.seed, *(str(part) for part in parts)])
+        return hashlib.sha256(text.encode()).digest()
+
+    def rehash(self, kind: str, value: str) -> str:
+        """Replace an identifier with a keyed hash (same input, same output)."""
+        return self._digest("id", kind, value).hex()[:16]
+
+    def merchant(self, description: str | None) -> str | None:
+        """Fake merchant for a description.
+
+        Descriptions that fingerprint alike get the same merchant, so dedup
+        behaves the same on the copy. Names that would share a substring
+        with any original description are skipped.
+        """
+        if description is None:
+            return None
+        key = normalize_fingerprint_description(description)
+        if key in self._merchants:
+            return self._merchants[key]
+
+        attempt = 0
+        while True:
+            digest = self._digest("merchant", key, attempt)
+            syllables = [_SYLLABLES[b % len(_SYLLABLES)] for b in digest[:4]]
+            name = f"{syllables[0]}{syllables[1]} {syllables[2]}{syllables[3]}".title()
+            if name not in self._used_merchants and not (_ngrams(name) & self._original_ngrams):
+                break
+            attempt += 1
+
+        self._merchants[key] = name
+        self._used_merchants.add(name)
+        return name
+
+    def amount(self, value: Decimal | float | None) -> Decimal | None:
+        """Scale an amount by up to AMOUNT_JITTER, keeping its sign and cents precision.
+
+        The factor depends only on the value, so equal amounts stay equal.
+        """
+        if value is None:
+            return None
+        value = Decimal(str(value))
+        if value == 0:
+            return value
+        digest = self._digest("amount", f"{value:.2f}")
+        fraction = Decimal(int.from_bytes(digest[:4], "big")) / Decimal(0xFFFFFFFF)
+        factor = 1 - AMOUNT_JITTER + 2 * AMOUNT_JITTER * fraction
+        scaled = max((abs(value) * factor).quantize(CENT, rounding=ROUND_HALF_UP), CENT)
+        return scaled if value > 0 else -scaled
+
+    def external_ids(self, external_ids: Dict[str, str]) -> Dict[str, str]:
+        """Re-hash provider IDs; fingerprints are left for the caller to recompute."""
+        return {
+            key: value if key == "fingerprint_version" else self.rehash(key, value)
+            for key, value in external_ids.items()
+            if key != "fingerprint"
+        }
+
+
+class AnonymizeService:
+    """Service for anonymized copies of the database to attach to bug reports.
+
+    The copy keeps the data's shape (accounts, dates, signs, rough amounts,
+    which rows are duplicates of which) while replacing everything that
+    identifies the user or their finances.
+    """
+
+    def __init__(
+        self,
+        repository: Repository,
+        open_database: Callable[[Path], Repository],
+        schema_metadata: Dict[str, Dict[str, Any]],
+    ):
+        """Initialize the service.
+
+        Args:
+            repository: Repository for the database being anonymized
+            open_database: Opens a repository for the anonymized copy (used
+                by the verification pass)
+            schema_metadata: Table descriptions by name; the system tables
+                not copied are reported as dropped
+        """
+        self.repository = repository
+        self.open_database = open_database
+        self.schema_metadata = schema_metadata
+
+    async def anonymize(self, output_path: Path, seed: str | None = None) -> Result[Dict[str, Any]]:
+        """Write an anonymized copy of the database to output_path.
+
+        - Descriptions become fake merchants (seeded hash of the normalized
+          description)
+        - Account names and institutions become generic labels
+        - Amounts and balances are perturbed within +/-20%, keeping sign
+        - External IDs and import file hashes are re-hashed
+        - Fingerprints are recomputed so dedup behaves the same
+        - Integration settings and other free-text tables are dropped
+        - Dates are preserved
+
+        Args:
+            output_path: Database file to create; must not exist
+            seed: Secret for the hashes; random when omitted. Anyone with the
+                seed can test guesses against the fake merchants, so it is
+                never part of the output.
+
+        Returns:
+            Result containing dict with output, tables (rows copied per
+            table), dropped_tables, and counts of accounts, institutions,
+            descriptions, transactions, amounts and external_ids transformed
+        """
+        output_path = Path(output_path).expanduser()
+        if output_path.exists():
+            return Fail(f"{output_path} already exists")
+        seed = seed or secrets.token_hex(16)
+
+        accounts_result = await self.repository.get_accounts()
+        if not accounts_result.success:
+            return accounts_result
+        transactions_result = await self.repository.get_all_transactions(include_deleted=True)
+        if not transactions_result.success:
+            return transactions_result
+
+        accounts = sorted(accounts_result.data or [], key=lambda a: (a.created_at, str(a.id)))
+        transactions = transactions_result.data or []
+        scrambler = _Scrambler(seed, (tx.description for tx in transactions))
+        counts = {"amounts": 0, "external_ids": 0}
+
+        account_labels = {str(a.id): f"Account {i}" for i, a in enumerate(accounts, start=1)}
+        institution_labels: Dict[str, str] = {}
+        for account in accounts:
+            if account.institution_name and account.institution_name not in institution_labels:
+                institution_labels[account.institution_name] = (
+                    f"Institution {len(institution_labels) + 1}"
+                )
+
+        anonymized = {str(tx.id): tx for tx in self._anonymize_transactions(transactions, scrambler)}
+
+        def amount(value: Any) -> Decimal | None:
+            if value is not None:
+                counts["amounts"] += 1
+            return scrambler.amount(value)
+
+        def count_external_ids(value: str | None) -> Dict[str, str]:
+            ids = json.loads(value or "{}")
+            counts["external_ids"] += len(scrambler.external_ids(ids))
+            return ids
+
+        def external_ids(value: str | None) -> str:
+            return json.dumps(scrambler.external_ids(count_external_ids(value)))
+
+        def account_row(row: Dict[str, Any]) -> Dict[str, Any]:
+            return {
+                **row,
+                "name": account_labels.get(row["account_id"], "Account"),
+                "nickname": None,
+                "institution_name": institution_labels.get(row["institution_name"]),
+                "institution_url": None,
+                "institution_domain": None,
+                "balance": amount(row["balance"]),
+                "external_ids": external_ids(row["external_ids"]),
+            }
+
+        def transaction_row(row: Dict[str, Any]) -> Dict[str, Any]:
+            tx = anonymized[row["transaction_id"]]
+            counts["amounts"] += 1
+            count_external_ids(row["external_ids"])
+            return {
+                **row,
+                "description": tx.description,
+                "description_search": tx.description.lower() if tx.description else None,
+                "amount": tx.amount,
+                "external_ids": json.dumps(dict(tx.external_ids)),
+            }
+
+        def snapshot_row(row: Dict[str, Any]) -> Dict[str, Any]:
+            return {**row, "balance": amount(row["balance"])}
+
+        def import_row(row: Dict[str, Any]) -> Dict[str, Any]:
+            suffix = Path(row["file_name"] or "").suffix
+            return {
+                **row,
+                "file_name": f"import-{scrambler.rehash('import', row['import_id'])[:8]}{suffix}",
+                "file_sha256": row["file_sha256"] and scrambler.rehash("file", row["file_sha256"]),
+            }
+
+        def budget_row(row: Dict[str, Any]) -> Dict[str, Any]:
+            return {
+                **row,
+                "expected": amount(row["expected"]),
+                "alerted_actual": amount(row["alerted_actual"]),
+            }
+
+        def rollover_row(row: Dict[str, Any]) -> Dict[str, Any]:
+            return {**row, "amount": amount(row["amount"])}
+
+        transforms = {
+            "sys_accounts": account_row,
+            "sys_transactions": transaction_row,
+            "sys_balance_snapshots": snapshot_row,
+            "sys_imports": import_row,
+            "sys_plugin_budget_categories": budget_row,
+            "sys_plugin_budget_rollovers": rollover_row,
+            "sys_plugin_accounts_overrides": None,
+        }
+        copy_result = await self.repository.copy_to_database(output_path, transforms)
+        if not copy_result.success:
+            return copy_result
+
+        verify_result = await self._verify(output_path, transactions)
+        if not verify_result.success:
+            output_path.unlink(missing_ok=True)
+            return verify_result
+
+        return Ok(
+            {
+                "output": str(output_path),
+                "tables": copy_result.data,
+                "dropped_tables": sorted(
+                    name
+                    for name in self.schema_metadata
+                    if is_system_table(name)
+                    and name not in COPIED_TABLES
+                    and name not in SCHEMA_TABLES
+                ),
+                "accounts": len(account_labels),
+                "institutions": len(institution_labels),
+                "descriptions": len({tx.description for tx in transactions if tx.description}),
+                "merchants": len({tx.description for tx in anonymized.values() if tx.description}),
+                "transactions": len(transactions),
+                "amounts": counts["amounts"],
+                "external_ids": counts["external_ids"],
+            }
+        )
+
+    def _anonymize_transactions(
+        self, transactions: List[Transaction], scrambler: _Scrambler
+    ) -> List[Transaction]:
+        """Scramble transactions, then fingerprint them with the version each had."""
+        by_version: Dict[int, List[Transaction]] = {}
+        for tx in transactions:
+            scrambled = tx.model_copy(
+                update={
+                    "description": scrambler.merchant(tx.description),
+                    "amount": scrambler.amount(tx.amount),
+                    "external_ids": scrambler.external_ids(dict(tx.external_ids)),
+                }
+            )
+            by_version.setdefault(tx.fingerprint_version, []).append(scrambled)
+
+        # Same order as refingerprint: identical rows are indexed per account
+        # in (date, created_at) order, which get_all_transactions returns
+        return [
+            fingerprinted
+            for version, group in sorted(by_version.items())
+            for fingerprinted in assign_fingerprints(group, version)
+        ]
+
+    async def _verify(self, output_path: Path, transactions: List[Transaction]) -> Result:
+        """Check that no original description text survives in the copy."""
+        copy = self.open_database(output_path)
+        result = await copy.execute_query(
+            "SELECT description, description_search FROM sys_transactions"
+        )
+        if not result.success:
+            return Fail(f"Failed to verify {output_path}: {result.error}")
+
+        written: Set[str] = set()
+        for row in result.data["rows"]:
+            for text in row:
+                written |= _ngrams(text or "")
+        # The error doesn't quote the leaked text, since it may end up in a bug report
+        for tx in transactions:
+            if _ngrams(tx.description or "") & written:
+                return Fail(
+                    "Verification failed: text from an original description is "
+                    "still in the anonymized copy, so it was deleted"
+                )
+        return Ok()
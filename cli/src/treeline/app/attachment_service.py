@@ -0,0 +1,206 @@
+"""Service for files attached to transactions (receipts, invoices)."""
+
+import hashlib
+import shutil
+from datetime import datetime, timezone
+from pathlib import Path
+from typing import Any, Dict, List
+from uuid import UUID, uuid4
+
+from treeline.abstractions import Repository
+from treeline.app.transaction_service import TransactionService
+from treeline.domain import Attachment, Fail, Ok, Result
+
+# Detached and pruned files are moved here instead of being deleted
+TRASH_DIR_NAME = ".trash"
+
+HASH_CHUNK_SIZE = 64 * 1024
+
+
+def _file_sha256(path: Path) -> str:
+    digest = hashlib.sha256()
+    with open(path, "rb") as f:
+        for chunk in iter(lambda: f.read(HASH_CHUNK_SIZE), b""):
+            digest.update(chunk)
+    return digest.hexdigest()
+
+
+class AttachmentService:
+    """Service for attaching files to transactions.
+
+    Files are copied to <attachments_dir>/<yyyy>/<uuid>-<filename>, where
+    yyyy is the transaction's year. Removing an attachment moves its file to
+    <attachments_dir>/.trash rather than deleting it.
+    """
+
+    def __init__(
+        self,
+        repository: Repository,
+        transaction_service: TransactionService,
+        attachments_dir: Path,
+        max_bytes: int,
+        allowed_extensions: List[str],
+    ):
+        """Initialize the service.
+
+        Args:
+            repository: Repository for attachment rows
+            transaction_service: Used to resolve transaction ID prefixes
+            attachments_dir: Directory the files are copied into
+            max_bytes: Largest file accepted
+            allowed_extensions: Accepted extensions, lowercase without the dot
+        """
+        self.repository = repository
+        self.transaction_service = transaction_service
+        self.attachments_dir = attachments_dir
+        self.max_bytes = max_bytes
+        self.allowed_extensions = allowed_extensions
+
+    @property
+    def trash_dir(self) -> Path:
+        return self.attachments_dir / TRASH_DIR_NAME
+
+    def path_for(self, attachment: Attachment) -> Path:
+        """Absolute path of an attachment's stored file."""
+        return self.attachments_dir / attachment.stored_path
+
+    async def attach(self, transaction_id: str, file_path: str) -> Result[Attachment]:
+        """Copy a file into the attachments directory and link it to a transaction.
+
+        Args:
+            transaction_id: Transaction ID or unique ID prefix
+            file_path: File to attach
+        """
+        source = Path(file_path).expanduser()
+        if not source.is_file():
+            return Fail(f"File not found: {file_path}")
+
+        extension = source.suffix.lower().lstrip(".")
+        if extension not in self.allowed_extensions:
+            return Fail(
+                f"Can't attach .{extension or '(none)'} files "
+                f"(allowed: {', '.join(self.allowed_extensions)})"
+            )
+
+        size = source.stat().st_size
+        if size > self.max_bytes:
+            return Fail(
+                f"{source.name} is {size / (1024 * 1024):.1f} MB; "
+                f"attachments are limited to {self.max_bytes / (1024 * 1024):.0f} MB"
+            )
+
+        id_result = await self.transaction_service.resolve_transaction_id(transaction_id)
+        if not id_result.success:
+            return id_result
+        tx_result = await self.repository.get_transaction_by_id(id_result.data)
+        if not tx_result.success:
+            return tx_result
+        transaction = tx_result.data
+        if transaction.deleted_at:
+            return Fail(f"Transaction {transaction.id} is deleted")
+
+        attachment_id = uuid4()
+        stored_path = f"{transaction.transaction_date.year}/{attachment_id}-{source.name}"
+        destination = self.attachments_dir / stored_path
+        try:
+            destination.parent.mkdir(parents=True, exist_ok=True)
+            shutil.copy2(source, destination)
+            sha256 = _file_sha256(destination)
+        except OSError as e:
+            destination.unlink(missing_ok=True)
+            return Fail(f"Failed to copy {source.name}: {e}")
+
+        attachment = Attachment(
+            id=attachment_id,
+            transaction_id=transaction.id,
+            filename=source.name,
+            stored_path=stored_path,
+            sha256=sha256,
+            size_bytes=size,
+            created_at=datetime.now(timezone.utc),
+        )
+        add_result = await self.repository.add_attachment(attachment)
+        if not add_result.success:
+            destination.unlink(missing_ok=True)
+            return add_result
+        return Ok(attachment)
+
+    async def list_attachments(self, transaction_id: str) -> Result[List[Attachment]]:
+        """List a transaction's attachments, by transaction ID or unique ID prefix."""
+        id_result = await self.transaction_service.resolve_transaction_id(transaction_id)
+        if not id_result.success:
+            return id_result
+        return await self.repository.get_attachments(id_result.data)
+
+    async def get_attachment(self, attachment_id: str) -> Result[Attachment]:
+        """Get an attachment by its full ID."""
+        try:
+            parsed_id = UUID(attachment_id.strip())
+        except ValueError:
+            return Fail(f"Invalid attachment ID: {attachment_id}")
+
+        result = await self.repository.get_attachment(parsed_id)
+        if not result.success:
+            return result
+        if result.data is None:
+            return Fail(f"Attachment not found: {attachment_id}")
+        return Ok(result.data)
+
+    async def detach(self, attachment_id: str) -> Result[Dict[str, Any]]:
+        """Remove an attachment, moving its file to the trash directory.
+
+        Returns:
+            Result containing dict with attachment and trash_path (None if
+            the file was already missing)
+        """
+        result = await self.get_attachment(attachment_id)
+        if not result.success:
+            return result
+        attachment = result.data
+
+        move_result = self._move_to_trash(attachment)
+        if not move_result.success:
+            return move_result
+        trash_path = move_result.data
+
+        delete_result = await self.repository.delete_attachment(attachment.id)
+        if not delete_result.success:
+            # Put the file back so the row still points at it
+            if trash_path:
+                shutil.move(str(trash_path), str(self.path_for(attachment)))
+            return delete_result
+
+        return Ok({"attachment": attachment, "trash_path": trash_path})
+
+    async def prune(self, dry_run: bool = False) -> Result[Dict[str, Any]]:
+        """Remove attachments whose transaction is deleted or no longer exists.
+
+        Returns:
+            Result containing dict with attachments (those pruned, or that
+            would be with dry_run) and dry_run
+        """
+        dangling_result = await self.repository.get_dangling_attachments()
+        if not dangling_result.success:
+            return dangling_result
+
+        attachments = dangling_result.data
+        if not dry_run:
+            for attachment in attachments:
+                detach_result = await self.detach(str(attachment.id))
+                if not detach_result.success:
+                    return detach_result
+
+        return Ok({"attachments": attachments, "dry_run": dry_run})
+
+    def _move_to_trash(self, attachment: Attachment) -> Result[Path | None]:
+        source = self.path_for(attachment)
+        if not source.exists():
+            return Ok(None)
+
+        trash_path = self.trash_dir / Path(attachment.stored_path).name
+        try:
+            self.trash_dir.mkdir(parents=True, exist_ok=True)
+            shutil.move(str(source), str(trash_path))
+        except OSError as e:
+            return Fail(f"Failed to move {attachment.filename} to the trash: {e}")
+        return Ok(trash_path)
@@ -1,12 +1,51 @@
 """Service for backfilling historical data."""
 
-from datetime import datetime, timezone
+from collections import defaultdict
+from datetime import date, datetime, timezone
 from decimal import Decimal
-from typing import Any, Dict, List
+from typing import Any, Dict, List, Tuple
 from uuid import UUID, uuid4
 
 from treeline.abstractions import Repository
-from treeline.domain import BalanceSnapshot, Ok, Fail, Result
+from treeline.app.account_service import short_id
+from treeline.domain import (
+    BalanceSnapshot,
+    Fail,
+    Ok,
+    Result,
+    Transaction,
+    balance_from_anchor,
+)
+
+
+def end_of_day_balances(
+    anchor_balance: Decimal, anchor_date: date, transactions: List[Transaction]
+) -> List[Tuple[date, Decimal]]:
+    """End-of-day balance on each day with transactions, newest first.
+
+    Steps out from the anchor one transaction day at a time, backward and
+    forward, with balance_from_anchor. The anchor day itself is left out.
+    """
+    by_day: Dict[date, List[Tuple[date, Decimal]]] = defaultdict(list)
+    for tx in transactions:
+        by_day[tx.transaction_date].append((tx.transaction_date, tx.amount))
+
+    balances: List[Tuple[date, Decimal]] = []
+    later = sorted(day for day in by_day if day > anchor_date)
+    balance, known = anchor_balance, anchor_date
+    for day in later:
+        balance, _ = balance_from_anchor(balance, known, day, by_day[day])
+        balances.append((day, balance))
+        known = day
+    balances.reverse()
+
+    earlier = sorted((day for day in by_day if day < anchor_date), reverse=True)
+    balance, known = anchor_balance, anchor_date
+    for day in earlier:
+        balance, _ = balance_from_anchor(balance, known, day, by_day.get(known, []))
+        balances.append((day, balance))
+        known = day
+    return balances
 
 
 class BackfillService:
@@ -21,23 +60,34 @@ class BackfillService:
         days: int | None = None,
         dry_run: bool = False,
         verbose: bool = False,
+        force_zero: bool = False,
     ) -> Result[Dict[str, Any]]:
         """Calculate historical balance snapshots from transactions.
 
-        Walks backward from the latest balance snapshot using transaction history.
-        Only creates snapshots for dates that don't already have one.
+        Walks out from the latest balance snapshot using transaction history,
+        or from the provider's balance as of today when there are no snapshots.
+        Each day with transactions gets its end-of-day balance, computed the
+        same way as get_balance_as_of. Only creates snapshots for dates that
+        don't already have one.
+
+        Accounts with neither are skipped and listed in accounts_skipped: an
+        unknown balance isn't zero, and walking back from zero would chart
+        made-up history. Archived accounts are skipped and listed there too.
 
         Args:
             account_ids: Specific accounts (None = all accounts)
             days: Limit to last N days of history
             dry_run: Preview without saving
             verbose: Detailed output
+            force_zero: Walk back from a balance of zero as of today for
+                accounts with no known balance, instead of skipping them
 
         Returns:
             Result with stats: {
                 "accounts_processed": int,
                 "snapshots_created": int,
                 "snapshots_skipped": int,
+                "accounts_skipped": List[{"account_id", "account", "reason"}],
                 "warnings": List[str],
                 "verbose_logs": List[str],
                 "dry_run": bool
@@ -56,7 +106,7 @@ class BackfillService:
                 accounts = [a for a in all_accounts if a.id in account_ids]
                 if not accounts:
                     return Fail(
-                        f"No accounts found matching IDs: {', '.join(str(id) for id in account_ids)}"
+                        f"No accounts found matching IDs: {', '.join(short_id(id) for id in account_ids)}"
                     )
             else:
                 accounts = all_accounts
@@ -65,10 +115,21 @@ class BackfillService:
             accounts_processed = 0
             total_snapshots_created = 0
             total_snapshots_skipped = 0
+            accounts_skipped: List[Dict[str, str]] = []
             warnings: List[str] = []
             verbose_logs: List[str] = []
 
             for account in accounts:
+                if account.archived_at is not None:
+                    accounts_skipped.append(
+                        {
+                            "account_id": str(account.id),
+                            "account": account.nickname or account.name,
+                            "reason": "Archived (tl accounts unarchive to include it)",
+                        }
+                    )
+                    continue
+
                 accounts_processed += 1
 
                 # Get latest balance snapshot (required)
@@ -82,17 +143,27 @@ class BackfillService:
                     continue
 
                 existing_snapshots = snapshots_result.data
-                if not existing_snapshots:
-                    warnings.append(
-                        f"Account {account.name}: No balance snapshots found - cannot backfill without starting point"
+                if existing_snapshots:
+                    # Start from the latest snapshot
+                    latest_snapshot = max(existing_snapshots, key=lambda s: s.snapshot_time)
+                    starting_balance = latest_snapshot.balance
+                    starting_date = latest_snapshot.snapshot_time.date()
+                elif account.balance is not None:
+                    starting_balance = account.balance
+                    starting_date = date.today()
+                elif force_zero:
+                    starting_balance = Decimal("0.00")
+                    starting_date = date.today()
+                else:
+                    accounts_skipped.append(
+                        {
+                            "account_id": str(account.id),
+                            "account": account.nickname or account.name,
+                            "reason": "No balance snapshots or provider balance to start from",
+                        }
                     )
                     continue
 
-                # Find latest snapshot
-                latest_snapshot = max(existing_snapshots, key=lambda s: s.snapshot_time)
-                starting_balance = latest_snapshot.balance
-                starting_date = latest_snapshot.snapshot_time.date()
-
                 if verbose:
                     from treeline.app.preferences_service import format_currency
 
@@ -100,33 +171,28 @@ class BackfillService:
                         f"Account {account.name}: Starting from {format_currency(starting_balance, account.currency)} on {starting_date}"
                     )
 
-                # Get transactions for this account (ordered DESC by date)
+                # The rows the transactions view counts: no deleted
+                # transactions, and split parents give way to their parts
                 transactions_result = await self.repository.get_transactions_by_account(
-                    account.id, order_by="transaction_date DESC"
+                    account.id, order_by="transaction_date DESC", leaves_only=True
                 )
                 if not transactions_result.success:
                     warnings.append(
                         f"Account {account.name}: Failed to get transactions - {transactions_result.error}"
                     )
                     continue
-
                 transactions = transactions_result.data
 
                 # Build set of dates that already have snapshots
                 existing_dates = {s.snapshot_time.date() for s in existing_snapshots}
 
-                # Walk backward through transactions
-                current_balance = starting_balance
                 snapshots_to_create: List[BalanceSnapshot] = []
-
-                for transaction in transactions:
-                    tx_date = transaction.transaction_date
-
+                for tx_date, balance in end_of_day_balances(
+                    starting_balance, starting_date, transactions
+                ):
                     # Skip if beyond days limit
-                    if days is not None:
-                        days_ago = (starting_date - tx_date).days
-                        if days_ago > days:
-                            break
+                    if days is not None and (starting_date - tx_date).days > days:
+                        continue
 
                     # Skip if this date already has a snapshot (preserve real data)
                     if tx_date in existing_dates:
@@ -137,16 +203,11 @@ class BackfillService:
                             )
                         continue
 
-                    # Calculate balance before this transaction
-                    # If debit (negative), balance was higher before
-                    # If credit (positive), balance was lower before
-                    balance_before = current_balance - transaction.amount
-
                     # Create snapshot for this date (end of day)
                     snapshot = BalanceSnapshot(
                         id=uuid4(),
                         account_id=account.id,
-                        balance=Decimal(str(balance_before)),
+                        balance=balance,
                         snapshot_time=datetime.combine(
                             tx_date, datetime.max.time()
                         ).replace(tzinfo=timezone.utc),
@@ -155,15 +216,12 @@ class BackfillService:
                         source="backfill",
                     )
                     snapshots_to_create.append(snapshot)
-                    existing_dates.add(tx_date)  # Mark as processed
-
-                    current_balance = balance_before
 
                     if verbose:
                         from treeline.app.preferences_service import format_currency
 
                         verbose_logs.append(
-                            f"Account {account.name}: {tx_date} = {format_currency(balance_before, account.currency)} (tx: {format_currency(transaction.amount, account.currency)})"
+                            f"Account {account.name}: {tx_date} = {format_currency(balance, account.currency)}"
                         )
 
                 # Insert snapshots (unless dry-run)
@@ -197,6 +255,7 @@ class BackfillService:
                     "accounts_processed": accounts_processed,
                     "snapshots_created": total_snapshots_created,
                     "snapshots_skipped": total_snapshots_skipped,
+                    "accounts_skipped": accounts_skipped,
                     "warnings": warnings,
                     "verbose_logs": verbose_logs,
                     "dry_run": dry_run,
@@ -0,0 +1,110 @@
+"""Service for budget status and the over-budget alerts raised by sync."""
+
+from dataclasses import dataclass
+from datetime import date
+from decimal import Decimal
+from typing import Any, Dict, List, Tuple
+
+from treeline.abstractions import Repository
+from treeline.app.preferences_service import format_currency
+from treeline.domain import Result
+
+
+@dataclass(frozen=True)
+class BudgetAlert:
+    """An expense category that a sync pushed (further) over its expected amount."""
+
+    category_id: str
+    category: str
+    month: str
+    expected: Decimal
+    actual: Decimal
+    over_by: Decimal
+    sync_delta: Decimal  # Spending added by this sync
+
+
+def find_budget_alerts(
+    month: str, before: List[Dict[str, Any]], after: List[Dict[str, Any]]
+) -> Tuple[List[BudgetAlert], Dict[str, Decimal | None]]:
+    """Compare budget status before and after a sync.
+
+    A category alerts when it is over its expected amount, this sync added
+    spending to it, and spending is past the level it last alerted at, so
+    a category that stays over only alerts again when it gets worse.
+    Categories back under budget have their alert level cleared so a later
+    crossing alerts again.
+
+    Args:
+        month: Budget month (YYYY-MM)
+        before: BudgetService.status() entries before the sync
+        after: BudgetService.status() entries after the sync
+
+    Returns:
+        (alerts, alert levels to record by category ID)
+    """
+    actual_before = {entry["category_id"]: entry["actual"] for entry in before}
+
+    alerts = []
+    levels: Dict[str, Decimal | None] = {}
+    for entry in after:
+        category_id = entry["category_id"]
+        expected, actual, alerted = entry["expected"], entry["actual"], entry["alerted_actual"]
+
+        if actual <= expected:
+            if alerted is not None:
+                levels[category_id] = None
+            continue
+
+        previous = actual_before.get(category_id, Decimal("0.00"))
+        if actual <= previous or (alerted is not None and actual <= alerted):
+            continue
+
+        alerts.append(
+            BudgetAlert(
+                category_id=category_id,
+                category=entry["name"],
+                month=month,
+                expected=expected,
+                actual=actual,
+                over_by=actual - expected,
+                sync_delta=actual - previous,
+            )
+        )
+        levels[category_id] = actual
+
+    return alerts, levels
+
+
+def describe_budget_alert(alert: BudgetAlert, currency: str, locale: str | None = None) -> str:
+    """e.g. "groceries: $612 of $500 (over by $112, +$87 from this sync)"."""
+
+    def money(amount: Decimal) -> str:
+        return format_currency(amount, currency, decimal_places=0, locale=locale)
+
+    return (
+        f"{alert.category}: {money(alert.actual)} of {money(alert.expected)} "
+        f"(over by {money(alert.over_by)}, +{money(alert.sync_delta)} from this sync)"
+    )
+
+
+class BudgetService:
+    """Service for budget status and the over-budget alerts raised by sync."""
+
+    def __init__(self, repository: Repository):
+        self.repository = repository
+
+    async def status(self, month: str | None = None) -> Result[List[Dict[str, Any]]]:
+        """Spending so far for each tag-based expense category of a month.
+
+        Args:
+            month: Budget month (YYYY-MM); defaults to the current month
+
+        Returns:
+            Result containing dicts with category_id, name, expected, actual
+            and alerted_actual
+        """
+        return await self.repository.get_budget_actuals(month or date.today().strftime("%Y-%m"))
+
+    async def record_alert_levels(self, levels: Dict[str, Decimal | None]) -> Result[None]:
+        """Remember the spending level each category was alerted at."""
+        return await self.repository.set_budget_alert_levels(levels)
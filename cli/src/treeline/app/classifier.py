@@ -0,0 +1,47 @@
+"""Income / expense / transfer classification shared by reports."""
+
+from typing import Optional
+
+from treeline.domain import Transaction
+
+INCOME = "income"
+EXPENSE = "expense"
+TRANSFER = "transfer"
+
+# Tags that mark money moving between the user's own accounts
+TRANSFER_TAGS = frozenset({"transfer", "payment"})
+
+# Account types where incoming money is a payment toward a balance, not income
+LIABILITY_ACCOUNT_TYPES = frozenset({"credit", "loan"})
+
+UNCATEGORIZED = "uncategorized"
+
+
+class Classifier:
+    """Decides whether a transaction is income, an expense, or a transfer.
+
+    Reports should classify through this class so their numbers agree:
+    - Tagged transfer/payment -> transfer (both sides of the movement)
+    - Positive amount on a credit or loan account -> transfer (a payment)
+    - Other positive amounts -> income
+    - Negative amounts -> expense (grouped by top-level tag)
+    """
+
+    def classify(self, transaction: Transaction, account_type: Optional[str]) -> str:
+        tags = {tag.lower() for tag in transaction.tags}
+        if tags & TRANSFER_TAGS:
+            return TRANSFER
+        if transaction.amount > 0:
+            if (account_type or "").lower() in LIABILITY_ACCOUNT_TYPES:
+                return TRANSFER
+            return INCOME
+        return EXPENSE
+
+    def expense_category(self, transaction: Transaction) -> str:
+        """Top-level category for an expense: the first tag, before any ':' or '/'."""
+        if not transaction.tags:
+            return UNCATEGORIZED
+        first_tag = transaction.tags[0]
+        for separator in (":", "/"):
+            first_tag = first_tag.split(separator, 1)[0]
+        return first_tag.strip().lower() or UNCATEGORIZED
@@ -15,24 +15,38 @@ from treeline.abstractions import (
     DataAggregationProvider,
     IntegrationProvider,
     Repository,
+    WriteLock,
 )
+from treeline.app.about_service import AboutService
 from treeline.app.account_service import AccountService
+from treeline.app.anonymize_service import AnonymizeService
+from treeline.app.attachment_service import AttachmentService
 from treeline.app.backfill_service import BackfillService
 from treeline.app.backup_service import BackupService
+from treeline.app.budget_service import BudgetService
 from treeline.app.db_service import DbService
+from treeline.app.dedupe_service import DedupeService
 from treeline.app.doctor_service import DoctorService
 from treeline.app.import_service import ImportService
 from treeline.app.integration_service import IntegrationService
+from treeline.app.maintenance_service import MaintenanceService
+from treeline.app.planned_service import PlannedService
 from treeline.app.plugin_service import PluginService
 from treeline.app.preferences_service import PreferencesService
+from treeline.app.report_service import ReportService
 from treeline.app.status_service import StatusService
 from treeline.app.sync_service import SyncService
 from treeline.app.tagging_service import TaggingService
+from treeline.app.transaction_service import TransactionService
+from treeline.app.warning_service import WarningService
+from treeline.app.year_review_service import YearReviewService
+from treeline.config import get_attachment_extensions, get_attachment_max_bytes
+from treeline.features import DEMO, FEATURE_MODULES, SIMPLEFIN, disabled_error, is_enabled
 from treeline.infra.csv import CSVProvider
-from treeline.infra.demo import DemoDataProvider
-from treeline.infra.duckdb import DuckDBRepository
+from treeline.infra.duckdb import DuckDBRepository, get_supported_data_version
 from treeline.infra.local_backup import LocalBackupStorage
-from treeline.infra.simplefin import SimpleFINProvider
+from treeline.infra.schema_metadata import SCHEMA_METADATA
+from treeline.infra.write_lock import FileWriteLock, write_lock_path
 
 DEFAULT_MAX_BACKUPS = 7
 
@@ -121,6 +135,14 @@ class Container:
         except json.JSONDecodeError as e:
             raise RuntimeError(f"Invalid encryption.json: {e}")
 
+    def _db_is_encrypted(self) -> bool:
+        """Check encryption.json without deriving a key or creating directories."""
+        try:
+            with open(Path(self.treeline_dir) / "encryption.json") as f:
+                return bool(json.load(f).get("encrypted", False))
+        except (OSError, json.JSONDecodeError):
+            return False
+
     def _get_password(self) -> str | None:
         """Get password from environment variable or callback."""
         # Try environment variable first
@@ -160,14 +182,21 @@ class Container:
         """Get the provider registry.
 
         Providers are registered by name. The 'demo' provider returns mock data
-        for testing. CSV import always uses the real CSVProvider.
+        for testing. CSV import always uses the real CSVProvider. SimpleFIN and
+        demo are optional features and are only registered when enabled.
         """
         if "provider_registry" not in self._instances:
-            self._instances["provider_registry"] = {
-                "simplefin": SimpleFINProvider(),
-                "demo": DemoDataProvider(),
-                "csv": CSVProvider(),
-            }
+            registry: Dict[str, DataAggregationProvider] = {}
+            if is_enabled(SIMPLEFIN):
+                from treeline.infra.simplefin import SimpleFINProvider
+
+                registry["simplefin"] = SimpleFINProvider()
+            if is_enabled(DEMO):
+                from treeline.infra.demo import DemoDataProvider
+
+                registry["demo"] = DemoDataProvider()
+            registry["csv"] = CSVProvider()
+            self._instances["provider_registry"] = registry
         return self._instances["provider_registry"]
 
     def sync_service(self) -> SyncService:
@@ -179,14 +208,34 @@ class Container:
                 self.account_service(),
                 self.integration_service(),
                 self.preferences_service(),
+                self.warning_service(),
+                self.budget_service(),
+                self.planned_service(),
+                self.dedupe_service(),
+                demo_mode=self.is_demo_mode,
+                write_lock=self.write_lock(),
             )
         return self._instances["sync_service"]
 
+    def write_lock(self) -> WriteLock:
+        """Get the lock imports and syncs hold while they write."""
+        if "write_lock" not in self._instances:
+            self._instances["write_lock"] = FileWriteLock(
+                write_lock_path(Path(self.db_file_path))
+            )
+        return self._instances["write_lock"]
+
+    def budget_service(self) -> BudgetService:
+        """Get the budget service instance."""
+        if "budget_service" not in self._instances:
+            self._instances["budget_service"] = BudgetService(self.repository())
+        return self._instances["budget_service"]
+
     def integration_service(self) -> IntegrationService:
         """Get the integration service instance."""
         if "integration_service" not in self._instances:
             self._instances["integration_service"] = IntegrationService(
-                self.repository()
+                self.repository(), self.provider_registry()
             )
         return self._instances["integration_service"]
 
@@ -194,6 +243,8 @@ class Container:
         """Get an integration provider by name."""
         provider = self.provider_registry().get(integration_name)
         if not provider:
+            if integration_name.lower() in FEATURE_MODULES:
+                raise ValueError(disabled_error(integration_name))
             raise ValueError(f"Unknown integration: {integration_name}")
 
         if not isinstance(provider, IntegrationProvider):
@@ -212,15 +263,110 @@ class Container:
     def status_service(self) -> StatusService:
         """Get the status service instance."""
         if "status_service" not in self._instances:
-            self._instances["status_service"] = StatusService(self.repository())
+            self._instances["status_service"] = StatusService(
+                self.repository(), self.warning_service()
+            )
         return self._instances["status_service"]
 
+    def warning_service(self) -> WarningService:
+        """Get the provider warning service instance."""
+        if "warning_service" not in self._instances:
+            self._instances["warning_service"] = WarningService(self.repository())
+        return self._instances["warning_service"]
+
+    def transaction_service(self) -> TransactionService:
+        """Get the transaction lookup service instance."""
+        if "transaction_service" not in self._instances:
+            self._instances["transaction_service"] = TransactionService(self.repository())
+        return self._instances["transaction_service"]
+
+    def attachment_service(self) -> AttachmentService:
+        """Get the transaction attachment service instance.
+
+        Demo mode keeps its attachments in a separate directory.
+        """
+        if "attachment_service" not in self._instances:
+            dir_name = "attachments-demo" if self.is_demo_mode else "attachments"
+            self._instances["attachment_service"] = AttachmentService(
+                self.repository(),
+                self.transaction_service(),
+                attachments_dir=Path(self.treeline_dir) / dir_name,
+                max_bytes=get_attachment_max_bytes(),
+                allowed_extensions=get_attachment_extensions(),
+            )
+        return self._instances["attachment_service"]
+
+    def maintenance_service(self) -> MaintenanceService:
+        """Get the maintenance service instance."""
+        if "maintenance_service" not in self._instances:
+            self._instances["maintenance_service"] = MaintenanceService(self.repository())
+        return self._instances["maintenance_service"]
+
+    def anonymize_service(self) -> AnonymizeService:
+        """Get the anonymize service instance."""
+        if "anonymize_service" not in self._instances:
+            self._instances["anonymize_service"] = AnonymizeService(
+                self.repository(),
+                open_database=lambda path: DuckDBRepository(str(path)),
+                schema_metadata=SCHEMA_METADATA,
+            )
+        return self._instances["anonymize_service"]
+
+    def planned_service(self) -> PlannedService:
+        """Get the planned transaction service instance."""
+        if "planned_service" not in self._instances:
+            self._instances["planned_service"] = PlannedService(
+                self.repository(), self.account_service()
+            )
+        return self._instances["planned_service"]
+
+    def dedupe_service(self) -> DedupeService:
+        """Get the duplicate review service instance."""
+        if "dedupe_service" not in self._instances:
+            self._instances["dedupe_service"] = DedupeService(
+                self.repository(), self.integration_service()
+            )
+        return self._instances["dedupe_service"]
+
+    def report_service(self) -> ReportService:
+        """Get the report service instance."""
+        if "report_service" not in self._instances:
+            self._instances["report_service"] = ReportService(self.repository())
+        return self._instances["report_service"]
+
+    def year_review_service(self) -> YearReviewService:
+        """Get the year-in-review service instance."""
+        if "year_review_service" not in self._instances:
+            self._instances["year_review_service"] = YearReviewService(
+                self.repository(), self.report_service()
+            )
+        return self._instances["year_review_service"]
+
     def db_service(self) -> DbService:
         """Get the DB service instance."""
         if "db_service" not in self._instances:
-            self._instances["db_service"] = DbService(self.repository())
+            self._instances["db_service"] = DbService(self.repository(), SCHEMA_METADATA)
         return self._instances["db_service"]
 
+    def about_service(self) -> AboutService:
+        """Get the about service instance."""
+        if "about_service" not in self._instances:
+            encrypted = not self.is_demo_mode and self._db_is_encrypted()
+            # Without a key or password in the environment, opening the
+            # database would prompt; about reports what it can without it
+            locked = encrypted and not (
+                self._encryption_key or self._get_precomputed_key() or os.environ.get("TL_DB_PASSWORD")
+            )
+            self._instances["about_service"] = AboutService(
+                self.repository,
+                self.db_file_path,
+                supported_data_version=get_supported_data_version(),
+                demo_mode=self.is_demo_mode,
+                encrypted=encrypted,
+                locked=locked,
+            )
+        return self._instances["about_service"]
+
     def doctor_service(self) -> DoctorService:
         """Get the doctor service instance."""
         if "doctor_service" not in self._instances:
@@ -240,7 +386,7 @@ class Container:
         """Get the import service instance."""
         if "import_service" not in self._instances:
             self._instances["import_service"] = ImportService(
-                self.repository(), self.provider_registry()
+                self.repository(), self.provider_registry(), self.write_lock()
             )
         return self._instances["import_service"]
 
@@ -254,9 +400,19 @@ class Container:
         """Get the plugin service instance."""
         if "plugin_service" not in self._instances:
             plugins_dir = Path(self.treeline_dir) / "plugins"
-            self._instances["plugin_service"] = PluginService(plugins_dir)
+            self._instances["plugin_service"] = PluginService(
+                plugins_dir, self.http_client_factory()
+            )
         return self._instances["plugin_service"]
 
+    def http_client_factory(self) -> Callable[..., Any] | None:
+        """Factory for blocking HTTP clients, or None in builds without httpx."""
+        try:
+            from treeline.infra.http import build_client
+        except ImportError:
+            return None
+        return build_client
+
     def preferences_service(self) -> PreferencesService:
         """Get the preferences service instance."""
         if "preferences_service" not in self._instances:
@@ -0,0 +1,49 @@
+"""Service for converting amounts between currencies with the configured rate table."""
+
+import re
+from decimal import ROUND_HALF_EVEN, Decimal
+from typing import Dict
+
+from treeline.config import get_exchange_rates
+from treeline.domain import Fail, Ok, Result
+
+CURRENCY_CODE_PATTERN = re.compile(r"[A-Z]{3}")
+
+CENT = Decimal("0.01")
+
+
+def is_currency_code(code: str) -> bool:
+    """Whether code looks like an ISO 4217 code (three letters, uppercase)."""
+    return bool(CURRENCY_CODE_PATTERN.fullmatch(code))
+
+
+class CurrencyService:
+    """Exchange rates between pairs of currencies.
+
+    Rates come from settings.json exchange_rates (see get_exchange_rates)
+    unless given. A pair can be looked up in either direction; there is no
+    chaining through a third currency and no fetching of live rates.
+    """
+
+    def __init__(self, rates: Dict[str, Decimal] | None = None):
+        self.rates = get_exchange_rates() if rates is None else rates
+
+    def rate(self, source: str, target: str) -> Decimal | None:
+        """What one unit of source is worth in target, or None without a rate."""
+        source, target = source.upper(), target.upper()
+        if source == target:
+            return Decimal("1")
+        rate = self.rates.get(f"{source}/{target}")
+        if rate is not None:
+            return rate
+        inverse = self.rates.get(f"{target}/{source}")
+        if inverse is not None:
+            return Decimal("1") / inverse
+        return None
+
+    def convert(self, amount: Decimal, source: str, target: str) -> Result[Decimal]:
+        """Convert amount from source to target, rounded half-even to cents."""
+        rate = self.rate(source, target)
+        if rate is None:
+            return Fail(f"No exchange rate for {source.upper()}/{target.upper()}")
+        return Ok((amount * rate).quantize(CENT, rounding=ROUND_HALF_EVEN))
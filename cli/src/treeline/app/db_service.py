@@ -1,35 +1,264 @@
 """Service for database operations."""
 
+from datetime import date
 from pathlib import Path
-from typing import Any, Dict
+from typing import Any, Dict, List
 
 from treeline.abstractions import Repository
 from treeline.app.backup_service import BackupService
-from treeline.domain import Result, Fail
+from treeline.domain import Fail, Ok, Result, is_system_table
+
+# File formats export_query and export_transactions can write
+EXPORT_FORMATS = ("parquet", "csv", "json")
+
+# Every column of the transactions view (including the computed month, year
+# and display_name) plus the stored columns the view leaves out
+EXPORT_TRANSACTIONS_SQL = """
+SELECT v.*, s.external_ids, s.created_at, s.updated_at
+FROM transactions v
+JOIN sys_transactions s ON s.transaction_id = v.transaction_id
+"""
 
 
 class DbService:
     """Service for database operations."""
 
-    def __init__(self, repository: Repository):
+    def __init__(self, repository: Repository, schema_metadata: Dict[str, Dict[str, Any]]):
+        """Initialize the service.
+
+        Args:
+            repository: The database
+            schema_metadata: Descriptions, expected columns and example
+                queries by table or view name
+        """
         self.repository = repository
+        self.schema_metadata = schema_metadata
 
     async def initialize_db(self) -> Result:
-        """Initialize database directory and schema."""
+        """Initialize database directory and schema.
+
+        Older databases are upgraded in place. Databases written by a newer
+        treeline are refused (context["incompatible_db"] is set) rather than
+        failing later on unknown columns.
+        """
         db_result = await self.repository.ensure_db_exists()
         if not db_result.success:
             return db_result
 
-        return await self.repository.ensure_schema_upgraded()
+        compat_result = await self.check_compatibility()
+        if not compat_result.success:
+            return compat_result
+        if not compat_result.data["compatible"]:
+            required = compat_result.data["min_app_version"] or "a newer version"
+            return Fail(
+                f"Database requires treeline >= {required} "
+                f"(data version {compat_result.data['data_version']}, "
+                f"this build supports {compat_result.data['supported_data_version']})",
+                context={"incompatible_db": True, **compat_result.data},
+            )
+
+        upgrade_result = await self.repository.ensure_schema_upgraded()
+        if not upgrade_result.success:
+            return upgrade_result
+
+        # Views edited by hand (user-run DDL) break queries in confusing ways.
+        # Report them so the caller can point at `tl db repair-views`.
+        drift_result = await self.check_views()
+        if drift_result.success and drift_result.data:
+            return Ok(context={"view_drift": drift_result.data})
+        return upgrade_result
+
+    async def check_views(self) -> Result[List[Dict[str, Any]]]:
+        """Compare the database's views with the columns in the schema metadata.
+
+        Returns:
+            Result containing a list of drifted views (empty if none), each a
+            dict with view, missing (the view doesn't exist), missing_columns
+            and unexpected_columns
+        """
+        columns_result = await self.repository.get_view_columns()
+        if not columns_result.success:
+            return columns_result
+
+        drift = []
+        for name, metadata in sorted(self.schema_metadata.items()):
+            if is_system_table(name):
+                continue
+            expected = list(metadata["columns"])
+            actual = columns_result.data.get(name)
+            if actual is None:
+                drift.append(
+                    {
+                        "view": name,
+                        "missing": True,
+                        "missing_columns": expected,
+                        "unexpected_columns": [],
+                    }
+                )
+                continue
+            missing_columns = [c for c in expected if c not in actual]
+            unexpected_columns = [c for c in actual if c not in expected]
+            if missing_columns or unexpected_columns:
+                drift.append(
+                    {
+                        "view": name,
+                        "missing": False,
+                        "missing_columns": missing_columns,
+                        "unexpected_columns": unexpected_columns,
+                    }
+                )
+        return Ok(drift)
+
+    async def repair_views(self) -> Result[Dict[str, Any]]:
+        """Recreate the views from the migrations, then check them again.
+
+        Returns:
+            Result containing dict with repaired (view names) and
+            remaining_drift (check_views output after the repair)
+        """
+        repair_result = await self.repository.repair_views()
+        if not repair_result.success:
+            return repair_result
+
+        check_result = await self.check_views()
+        if not check_result.success:
+            return check_result
+        return Ok({"repaired": repair_result.data, "remaining_drift": check_result.data})
+
+    async def check_compatibility(self) -> Result[Dict[str, Any]]:
+        """Check whether this build can open the database.
+
+        Returns:
+            Result containing dict with:
+              - "compatible": bool - False when the DB was written by a newer treeline
+              - "data_version": int | None
+              - "min_app_version": str | None
+              - "supported_data_version": int
+        """
+        version_result = await self.repository.get_data_version()
+        if not version_result.success:
+            return version_result
+
+        data = dict(version_result.data)
+        data_version = data.get("data_version")
+        data["compatible"] = (
+            data_version is None or data_version <= data["supported_data_version"]
+        )
+        return Ok(data)
 
-    async def execute_query(self, sql: str) -> Result:
+    async def execute_query(self, sql: str, timeout_secs: float | None = None) -> Result:
         cleaned_sql = self._clean_and_validate_sql(sql)
-        return await self.repository.execute_query(cleaned_sql)
+        return await self.repository.execute_query(cleaned_sql, timeout_secs=timeout_secs)
+
+    async def export_query(
+        self,
+        sql: str,
+        output_path: Path,
+        file_format: str,
+        overwrite: bool = False,
+        timeout_secs: float | None = None,
+    ) -> Result[Dict[str, Any]]:
+        """Write a query's result straight to a parquet, csv or json file.
+
+        Rows go from DuckDB to the file without passing through Python, so
+        types (dates, decimals, lists) are kept in parquet.
+        """
+        cleaned_sql = self._clean_and_validate_sql(sql)
+        return await self.repository.copy_query_to_file(
+            cleaned_sql, output_path, file_format, overwrite=overwrite, timeout_secs=timeout_secs
+        )
+
+    async def export_transactions(
+        self,
+        output_path: Path,
+        file_format: str,
+        start_date: date | None = None,
+        end_date: date | None = None,
+        overwrite: bool = False,
+    ) -> Result[Dict[str, Any]]:
+        """Export transactions (as the transactions view shows them) to a file.
+
+        Rows are ordered by date, oldest first; start_date and end_date are
+        inclusive.
+        """
+        conditions = []
+        if start_date is not None:
+            conditions.append(f"v.transaction_date >= DATE '{start_date.isoformat()}'")
+        if end_date is not None:
+            conditions.append(f"v.transaction_date <= DATE '{end_date.isoformat()}'")
+        where = f"WHERE {' AND '.join(conditions)}" if conditions else ""
+        sql = f"{EXPORT_TRANSACTIONS_SQL} {where} ORDER BY v.transaction_date, v.transaction_id"
+        return await self.repository.copy_query_to_file(
+            sql, output_path, file_format, overwrite=overwrite
+        )
 
     async def execute_write_query(self, sql: str) -> Result:
         cleaned_sql = self._clean_and_validate_sql(sql)
         return await self.repository.execute_write_query(cleaned_sql)
 
+    async def get_schema(
+        self, table: str | None = None, include_system: bool = False
+    ) -> Result[List[Dict[str, Any]]]:
+        """Describe the database's tables and views.
+
+        Columns and types come from information_schema; descriptions and
+        example queries from the schema metadata. Tables missing from the map
+        (e.g. created by community plugins) are listed without descriptions.
+
+        Args:
+            table: Describe only this table or view (system tables included)
+            include_system: Also list sys_* tables
+
+        Returns:
+            Result containing a list of dicts with name, kind ("view" or
+            "table"), description, columns (name, type, description) and
+            examples; views first, then tables, each sorted by name
+        """
+        result = await self.repository.execute_query(
+            """
+            SELECT c.table_name, t.table_type, c.column_name, c.data_type
+            FROM information_schema.columns c
+            JOIN information_schema.tables t
+              ON t.table_catalog = c.table_catalog
+             AND t.table_schema = c.table_schema
+             AND t.table_name = c.table_name
+            WHERE c.table_catalog = current_database() AND c.table_schema = 'main'
+            ORDER BY c.table_name, c.ordinal_position
+            """
+        )
+        if not result.success:
+            return result
+
+        tables: Dict[str, Dict[str, Any]] = {}
+        for name, table_type, column, data_type in result.data["rows"]:
+            if table is not None and name != table:
+                continue
+            if table is None and is_system_table(name) and not include_system:
+                continue
+            metadata = self.schema_metadata.get(name, {})
+            entry = tables.setdefault(
+                name,
+                {
+                    "name": name,
+                    "kind": "view" if table_type == "VIEW" else "table",
+                    "description": metadata.get("description"),
+                    "columns": [],
+                    "examples": metadata.get("examples", []),
+                },
+            )
+            entry["columns"].append(
+                {
+                    "name": column,
+                    "type": data_type,
+                    "description": metadata.get("columns", {}).get(column),
+                }
+            )
+
+        if table is not None and not tables:
+            return Fail(f"Unknown table or view: {table}")
+
+        return Ok(sorted(tables.values(), key=lambda t: (t["kind"] != "view", t["name"])))
+
     def _clean_and_validate_sql(self, sql: str) -> str:
         # TODO: Implement SQL cleaning and validation
         return sql
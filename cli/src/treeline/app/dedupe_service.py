@@ -0,0 +1,282 @@
+"""Service for the duplicate review queue."""
+
+from datetime import date, datetime, timedelta, timezone
+from typing import Any, Dict, List, Set, Tuple
+from uuid import UUID, uuid4
+
+from treeline.abstractions import Repository
+from treeline.app.integration_service import IntegrationService
+from treeline.app.transaction_service import MIN_ID_PREFIX_LENGTH
+from treeline.domain import (
+    DuplicateCandidate,
+    DuplicateKind,
+    DuplicateStatus,
+    Fail,
+    Ok,
+    Result,
+    normalize_tags,
+)
+
+# Transactions sharing a fingerprint are only queued when dated within this
+# many days, so years-old duplicates already accepted don't flood the queue
+# (the same window tl doctor uses)
+DUPLICATE_SCAN_DAYS = 90
+
+
+def parse_status(value: str | None) -> Result[DuplicateStatus | None]:
+    """Parse a --status value; None and "all" mean every status."""
+    if value is None or value.strip().lower() == "all":
+        return Ok(None)
+    try:
+        return Ok(DuplicateStatus(value.strip().lower()))
+    except ValueError:
+        choices = ", ".join(s.value for s in DuplicateStatus)
+        return Fail(f"Invalid status: {value} (expected {choices} or all)")
+
+
+def merged_external_ids(keep: Dict[str, str], duplicate: Dict[str, str]) -> Dict[str, str]:
+    """The duplicate's external IDs added to the kept ones; the kept side wins
+    on a clash, so its fingerprint and provenance stay as they were."""
+    return {**duplicate, **keep}
+
+
+class DedupeService:
+    """Service for possible duplicates: detection, review, dismissal and merging.
+
+    Sync and `tl dedupe scan` record candidates; every pair is stored once,
+    so a dismissed pair found again stays dismissed. Merging a transaction
+    pair soft-deletes the duplicate after moving its external IDs and tags to
+    the kept transaction, so the next sync recognises the provider's copy.
+    Merging an account pair turns off sync for the duplicate account, the
+    fix tl sync suggests for accounts two integrations both sync.
+    """
+
+    def __init__(self, repository: Repository, integration_service: IntegrationService):
+        self.repository = repository
+        self.integration_service = integration_service
+
+    async def scan_transactions(
+        self, days: int = DUPLICATE_SCAN_DAYS, today: date | None = None
+    ) -> Result[Dict[str, int]]:
+        """Queue transactions sharing a fingerprint within an account.
+
+        Returns {"found": pairs detected, "added": pairs newly queued}.
+        """
+        since = (today or date.today()) - timedelta(days=days)
+        pairs_result = await self.repository.find_duplicate_transaction_pairs(since)
+        if not pairs_result.success:
+            return pairs_result
+
+        now = datetime.now(timezone.utc)
+        candidates = [
+            DuplicateCandidate(
+                id=uuid4(),
+                kind=DuplicateKind.TRANSACTION,
+                keep_id=keep_id,
+                duplicate_id=duplicate_id,
+                score=1.0,
+                created_at=now,
+            )
+            for keep_id, duplicate_id in pairs_result.data
+        ]
+        add_result = await self.repository.add_duplicate_candidates(candidates)
+        if not add_result.success:
+            return add_result
+        return Ok({"found": len(candidates), "added": add_result.data})
+
+    async def record_account_duplicates(
+        self, duplicates: List[Dict[str, Any]]
+    ) -> Result[Dict[str, Any]]:
+        """Queue cross-provider account duplicates reported by sync.
+
+        Returns {"added": pairs newly queued, "duplicates": the entries that
+        haven't been dismissed}, so sync stops reporting pairs the user has
+        already looked at.
+        """
+        now = datetime.now(timezone.utc)
+        candidates = []
+        for entry in duplicates:
+            smaller = min(entry["keep"]["transactions"], entry["duplicate"]["transactions"])
+            candidates.append(
+                DuplicateCandidate(
+                    id=uuid4(),
+                    kind=DuplicateKind.ACCOUNT,
+                    keep_id=UUID(entry["keep"]["account_id"]),
+                    duplicate_id=UUID(entry["duplicate"]["account_id"]),
+                    score=min(1.0, entry["shared_transactions"] / smaller) if smaller else 0.0,
+                    created_at=now,
+                )
+            )
+        add_result = await self.repository.add_duplicate_candidates(candidates)
+        if not add_result.success:
+            return add_result
+
+        dismissed_result = await self.repository.list_duplicate_candidates(
+            DuplicateStatus.DISMISSED
+        )
+        if not dismissed_result.success:
+            return dismissed_result
+        dismissed: Set[Tuple[str, str]] = set()
+        for candidate in dismissed_result.data:
+            if candidate.kind == DuplicateKind.ACCOUNT:
+                pair = (str(candidate.keep_id), str(candidate.duplicate_id))
+                dismissed.update({pair, pair[::-1]})
+        return Ok(
+            {
+                "added": add_result.data,
+                "duplicates": [
+                    entry
+                    for entry in duplicates
+                    if (entry["keep"]["account_id"], entry["duplicate"]["account_id"])
+                    not in dismissed
+                ],
+            }
+        )
+
+    async def list_candidates(
+        self, status: DuplicateStatus | None = DuplicateStatus.OPEN
+    ) -> Result[List[Dict[str, Any]]]:
+        """Candidates with both sides looked up, oldest first.
+
+        Each entry is {"candidate", "keep", "duplicate"}; keep and duplicate
+        are Transactions or Accounts, or None if one has since been removed.
+        """
+        list_result = await self.repository.list_duplicate_candidates(status)
+        if not list_result.success:
+            return list_result
+
+        accounts_result = await self.repository.get_accounts()
+        if not accounts_result.success:
+            return accounts_result
+        accounts = {account.id: account for account in accounts_result.data or []}
+
+        async def lookup(kind: DuplicateKind, item_id: UUID):
+            if kind == DuplicateKind.ACCOUNT:
+                return accounts.get(item_id)
+            tx_result = await self.repository.get_transaction_by_id(item_id)
+            return tx_result.data if tx_result.success else None
+
+        entries = []
+        for candidate in list_result.data:
+            entries.append(
+                {
+                    "candidate": candidate,
+                    "keep": await lookup(candidate.kind, candidate.keep_id),
+                    "duplicate": await lookup(candidate.kind, candidate.duplicate_id),
+                }
+            )
+        return Ok(entries)
+
+    async def resolve(self, id_or_prefix: str) -> Result[DuplicateCandidate]:
+        """Find a candidate by ID or unambiguous ID prefix.
+
+        An ambiguous prefix fails with context {"matches": [...]}.
+        """
+        value = id_or_prefix.strip().lower()
+        if len(value) < MIN_ID_PREFIX_LENGTH:
+            return Fail(f"Candidate ID prefix must be at least {MIN_ID_PREFIX_LENGTH} characters")
+
+        list_result = await self.repository.list_duplicate_candidates()
+        if not list_result.success:
+            return list_result
+        matches = [c for c in list_result.data if str(c.id).startswith(value)]
+        if not matches:
+            return Fail(f"No duplicate candidate matches ID: {id_or_prefix}")
+        if len(matches) > 1:
+            return Fail(
+                f"Ambiguous candidate ID prefix: {id_or_prefix}",
+                context={"matches": [str(c.id) for c in matches]},
+            )
+        return Ok(matches[0])
+
+    async def _resolve_open(self, id_or_prefix: str) -> Result[DuplicateCandidate]:
+        resolve_result = await self.resolve(id_or_prefix)
+        if not resolve_result.success:
+            return resolve_result
+        candidate = resolve_result.data
+        if candidate.status != DuplicateStatus.OPEN:
+            return Fail(f"Candidate {str(candidate.id)[:8]} is already {candidate.status.value}")
+        return resolve_result
+
+    async def dismiss(self, id_or_prefix: str) -> Result[DuplicateCandidate]:
+        """Mark a pair as not a duplicate; it won't be queued again."""
+        resolve_result = await self._resolve_open(id_or_prefix)
+        if not resolve_result.success:
+            return resolve_result
+        candidate = resolve_result.data
+        update_result = await self.repository.set_duplicate_candidate_status(
+            candidate.id, DuplicateStatus.DISMISSED
+        )
+        if not update_result.success:
+            return update_result
+        return Ok(
+            candidate.model_copy(
+                update={"status": DuplicateStatus.DISMISSED, "resolved_at": datetime.now(timezone.utc)}
+            )
+        )
+
+    async def merge(self, id_or_prefix: str) -> Result[Dict[str, Any]]:
+        """Merge a pair into its kept side.
+
+        Returns {"candidate", "kind", "kept", "removed"} for transactions, and
+        for accounts also "integrations" (those that stop syncing the duplicate).
+        """
+        resolve_result = await self._resolve_open(id_or_prefix)
+        if not resolve_result.success:
+            return resolve_result
+        candidate = resolve_result.data
+        if candidate.kind == DuplicateKind.ACCOUNT:
+            return await self._merge_accounts(candidate)
+        return await self._merge_transactions(candidate)
+
+    async def _merge_transactions(self, candidate: DuplicateCandidate) -> Result[Dict[str, Any]]:
+        keep_result = await self.repository.get_transaction_by_id(candidate.keep_id)
+        duplicate_result = await self.repository.get_transaction_by_id(candidate.duplicate_id)
+        if not keep_result.success or not duplicate_result.success:
+            return Fail("One of the transactions in this pair no longer exists; dismiss it instead")
+        keep, duplicate = keep_result.data, duplicate_result.data
+        if keep.deleted_at is not None or duplicate.deleted_at is not None:
+            return Fail("One of the transactions in this pair was deleted; dismiss it instead")
+
+        merge_result = await self.repository.merge_duplicate_transaction(
+            candidate.id,
+            keep.id,
+            duplicate.id,
+            merged_external_ids(dict(keep.external_ids), dict(duplicate.external_ids)),
+            normalize_tags([*keep.tags, *duplicate.tags]),
+        )
+        if not merge_result.success:
+            return merge_result
+        return Ok(
+            {
+                "candidate": str(candidate.id),
+                "kind": candidate.kind.value,
+                "kept": str(keep.id),
+                "removed": str(duplicate.id),
+            }
+        )
+
+    async def _merge_accounts(self, candidate: DuplicateCandidate) -> Result[Dict[str, Any]]:
+        account_result = await self.repository.get_account_by_id(candidate.duplicate_id)
+        if not account_result.success or account_result.data is None:
+            return Fail("The duplicate account no longer exists; dismiss this pair instead")
+
+        sync_result = await self.integration_service.set_account_sync_enabled(
+            account_result.data, False
+        )
+        if not sync_result.success:
+            return sync_result
+        update_result = await self.repository.set_duplicate_candidate_status(
+            candidate.id, DuplicateStatus.MERGED
+        )
+        if not update_result.success:
+            return update_result
+        return Ok(
+            {
+                "candidate": str(candidate.id),
+                "kind": candidate.kind.value,
+                "kept": str(candidate.keep_id),
+                "removed": str(candidate.duplicate_id),
+                "integrations": sync_result.data["integrations"],
+            }
+        )
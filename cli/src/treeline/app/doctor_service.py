@@ -2,6 +2,7 @@
 
 from dataclasses import dataclass
 from datetime import date, timedelta
+from decimal import Decimal
 from typing import TYPE_CHECKING, Any, Literal
 
 from treeline.abstractions import Repository
@@ -43,6 +44,7 @@ class DoctorService:
         checks = [
             await self._check_orphaned_transactions(),
             await self._check_orphaned_snapshots(),
+            await self._check_dangling_attachments(),
             await self._check_duplicate_fingerprints(),
             await self._check_date_sanity(),
             await self._check_untagged_transactions(),
@@ -101,7 +103,7 @@ class DoctorService:
                 "transaction_id": row[0],
                 "account_id": row[1],
                 "description": row[2],
-                "amount": float(row[3]) if row[3] else None,
+                "amount": row[3],
             }
             for row in rows
         ]
@@ -149,7 +151,7 @@ class DoctorService:
             {
                 "snapshot_id": row[0],
                 "account_id": row[1],
-                "balance": float(row[2]) if row[2] else None,
+                "balance": row[2],
                 "snapshot_time": str(row[3]) if row[3] else None,
             }
             for row in rows
@@ -162,6 +164,43 @@ class DoctorService:
             details=details,
         )
 
+    async def _check_dangling_attachments(self) -> HealthCheck:
+        """Check for attachments kept after their transaction was deleted."""
+        result = await self.repository.get_dangling_attachments()
+
+        if not result.success:
+            return HealthCheck(
+                name="dangling_attachments",
+                status="error",
+                message=f"Failed to check: {result.error}",
+            )
+
+        if not result.data:
+            return HealthCheck(
+                name="dangling_attachments",
+                status="pass",
+                message="No attachments of deleted transactions",
+            )
+
+        details = [
+            {
+                "attachment_id": str(attachment.id),
+                "transaction_id": str(attachment.transaction_id),
+                "filename": attachment.filename,
+            }
+            for attachment in result.data
+        ]
+
+        return HealthCheck(
+            name="dangling_attachments",
+            status="warning",
+            message=(
+                f"{len(details)} attachment(s) belong to deleted transactions; "
+                "remove them with `tl maintenance prune-attachments`"
+            ),
+            details=details,
+        )
+
     async def _check_duplicate_fingerprints(self) -> HealthCheck:
         """Check for recent transactions with duplicate fingerprints (potential duplicates).
 
@@ -227,7 +266,7 @@ class DoctorService:
                     {
                         "transaction_id": r[0],
                         "date": str(r[1]) if r[1] else None,
-                        "amount": float(r[2]) if r[2] else None,
+                        "amount": r[2],
                         "description": r[3],
                     }
                     for r in detail_result.data.get("rows", [])
@@ -289,7 +328,7 @@ class DoctorService:
                 "transaction_id": row[0],
                 "date": str(row[1]) if row[1] else None,
                 "description": row[2],
-                "amount": float(row[3]) if row[3] else None,
+                "amount": row[3],
             }
             for row in rows
         ]
@@ -434,9 +473,8 @@ class DoctorService:
                 amount,
                 tags,
                 ({match_sum}) as category_matches
-            FROM sys_transactions
-            WHERE deleted_at IS NULL
-              AND strftime('%Y-%m', transaction_date) = '{current_month}'
+            FROM transactions
+            WHERE month = '{current_month}'
               AND amount < 0
               AND ({match_sum}) > 1
             ORDER BY ({match_sum}) DESC, ABS(amount) DESC
@@ -463,14 +501,14 @@ class DoctorService:
             )
 
         # Get total amount being double-counted
-        total_amount = sum(abs(float(row[3])) for row in rows if row[3])
+        total_amount = sum((abs(row[3]) for row in rows if row[3] is not None), Decimal("0.00"))
 
         details = [
             {
                 "transaction_id": row[0],
                 "date": str(row[1]) if row[1] else None,
                 "description": (row[2] or "")[:40],
-                "amount": float(row[3]) if row[3] else None,
+                "amount": row[3],
                 "tags": row[4] if row[4] else [],
                 "category_matches": row[5],
             }
@@ -575,9 +613,8 @@ class DoctorService:
                 description,
                 amount,
                 tags
-            FROM sys_transactions
-            WHERE deleted_at IS NULL
-              AND strftime('%Y-%m', transaction_date) = '{current_month}'
+            FROM transactions
+            WHERE month = '{current_month}'
               AND amount < 0
               AND NOT ({match_any})
             ORDER BY ABS(amount) DESC
@@ -604,30 +641,29 @@ class DoctorService:
             )
 
         # Get total uncategorized amount
-        total_amount = sum(abs(float(row[3])) for row in rows if row[3])
+        total_amount = sum((abs(row[3]) for row in rows if row[3] is not None), Decimal("0.00"))
 
         # Get total expense count for context
         total_query = f"""
-            SELECT COUNT(*), SUM(ABS(amount))
-            FROM sys_transactions
-            WHERE deleted_at IS NULL
-              AND strftime('%Y-%m', transaction_date) = '{current_month}'
+            SELECT COUNT(*), SUM(ABS(amount)::DECIMAL(38,2))
+            FROM transactions
+            WHERE month = '{current_month}'
               AND amount < 0
         """
         total_result = await self.repository.execute_query(total_query)
         total_count = 0
-        total_expense_amount = 0
+        total_expense_amount = Decimal("0.00")
         if total_result.success and total_result.data.get("rows"):
             row = total_result.data["rows"][0]
             total_count = row[0] or 0
-            total_expense_amount = float(row[1]) if row[1] else 0
+            total_expense_amount = row[1] if row[1] is not None else Decimal("0.00")
 
         details = [
             {
                 "transaction_id": row[0],
                 "date": str(row[1]) if row[1] else None,
                 "description": (row[2] or "")[:40],
-                "amount": float(row[3]) if row[3] else None,
+                "amount": row[3],
                 "tags": row[4] if row[4] else [],
             }
             for row in rows[:20]  # Limit details
@@ -1,11 +1,93 @@
 """Service for one-time bulk imports from files or external sources."""
 
+import json
+import os
 from datetime import datetime, timezone
-from typing import Any, Dict, List
-from uuid import UUID
+from pathlib import Path
+from decimal import Decimal
+from typing import Any, Callable, Dict, List, Tuple
+from uuid import UUID, uuid4
 
-from treeline.abstractions import DataAggregationProvider, Repository
-from treeline.domain import Result, Transaction
+from treeline.abstractions import DataAggregationProvider, Repository, WriteLock, write_phase
+from treeline.app.classifier import LIABILITY_ACCOUNT_TYPES
+from treeline.app.currency_service import CurrencyService, is_currency_code
+from treeline.config import (
+    get_fingerprint_version,
+    get_fuzzy_dedup_days,
+    get_import_max_file_bytes,
+)
+from treeline.domain import (
+    MAX_AMOUNT,
+    CSVPreset,
+    DetectionReport,
+    Fail,
+    ImportRecord,
+    Ok,
+    Result,
+    Transaction,
+    amount_in_range,
+    apply_default_tags,
+    assign_fingerprints,
+    nearby_fingerprints,
+    normalize_tags,
+)
+from treeline.utils import get_treeline_dir, resolve_user_path
+
+# Sign profile checks need this many non-zero amounts on each side
+SIGN_PROFILE_MIN_ROWS = 5
+# Existing transactions sampled for an account's sign profile
+SIGN_PROFILE_HISTORY_LIMIT = 500
+# An import looks inverted when this share of its rows has the sign that is
+# rare (the other side of HISTORY_SHARE) in the account's history
+SIGN_PROFILE_IMPORT_SHARE = Decimal("0.8")
+SIGN_PROFILE_HISTORY_SHARE = Decimal("0.7")
+
+# What happens to rows whose currency column differs from the account's
+# currency: dropped with a reason, imported unconverted and tagged fx:<code>,
+# or converted with CurrencyService and tagged fx:<code>
+FOREIGN_CURRENCY_MODES = ("skip", "keep", "convert")
+
+# New rows written per DB transaction. A file import with more rows than
+# this checkpoints after each chunk so an interrupted import can be resumed.
+IMPORT_CHUNK_SIZE = 5000
+
+# Source options a checkpoint keeps, so a resumed import parses the file
+# exactly as the interrupted one did
+CHECKPOINT_OPTION_KEYS = (
+    "column_mapping",
+    "date_format",
+    "flip_signs",
+    "debit_negative",
+    "foreign_currency",
+)
+
+# Bytes read from the start of an import file to recognise non-CSV content
+FILE_SNIFF_BYTES = 512
+
+# Leading bytes of files that are often renamed or saved as .csv, with the
+# detected_type reported for them and the message (formatted with the file
+# name). Matched case-insensitively, after a BOM and leading whitespace.
+NON_CSV_SIGNATURES = [
+    (
+        (b"PK\x03\x04",),
+        "xlsx",
+        "{name} is an Excel workbook (XLSX), not a CSV file. Export it as CSV "
+        "(in Excel or Numbers: File > Save As > CSV) and run tl import on the exported file",
+    ),
+    (
+        (b"%PDF",),
+        "pdf",
+        "{name} is a PDF, not a CSV file. PDF statements can't be imported; "
+        "download the CSV export from your bank's website and run tl import on that",
+    ),
+    (
+        (b"<!doctype html", b"<html"),
+        "html",
+        "{name} is a web page (HTML), not a CSV file, usually a login or error page "
+        "saved in place of the download. Download the CSV export again, or copy the "
+        "table and use Import from Clipboard in the app",
+    ),
+]
 
 
 class ImportService:
@@ -15,31 +97,182 @@ class ImportService:
         self,
         repository: Repository,
         provider_registry: Dict[str, DataAggregationProvider],
+        write_lock: WriteLock | None = None,
     ):
         self.repository = repository
         self.provider_registry = provider_registry
+        self.write_lock = write_lock
 
     async def import_transactions(
         self,
         source_type: str,
         account_id: UUID,
         source_options: Dict[str, Any],
+        force: bool = False,
+        resume: str | None = None,
+        restart: bool = False,
+        should_stop: Callable[[], bool] | None = None,
+        chunk_size: int = IMPORT_CHUNK_SIZE,
     ) -> Result[Dict[str, Any]]:
         """Import transactions from a one-time source using fingerprint deduplication.
 
+        File imports are recorded by content hash. Importing a byte-identical
+        file into the same account again fails with context kind
+        "already_imported" (and the earlier import) unless force is set.
+        Files are checked first with check_import_file.
+
+        New rows are written chunk_size at a time, in file order. After each
+        chunk of a file import the batch record is updated and a checkpoint
+        written (see import_checkpoint_path); should_stop is asked between
+        chunks, and when it returns True the import stops with "interrupted"
+        set. Importing the file again while its checkpoint exists fails with
+        context kind "interrupted_import" until resume (the batch ID) carries
+        on after the last written line or restart deletes the partial batch.
+        A resumed import deduplicates against everything but its own batch,
+        so it ends with the same rows an uninterrupted import would have.
+
+        The write lock is held for the whole import, so a concurrent import
+        or sync can't insert between the duplicate check and the insert; if
+        it stays busy the import fails with context kind "write_locked".
+
         Args:
             source_type: Type of import source ("csv", "ynab", etc.)
             account_id: Treeline account to import transactions into
             source_options: Provider-specific options (e.g., {"file_path": "/path/to/file.csv"})
+            force: Re-process a file even if it was already imported
+            resume: ID (or unique ID prefix) of an interrupted batch to continue
+            restart: Delete this file's interrupted batch and import it from the start
+            should_stop: Checked after each written chunk
+            chunk_size: New rows per DB transaction
 
         Returns:
             Result with stats: {"discovered": 150, "imported": 120, "skipped": 30}
+            and "warnings" (e.g. amounts that look inverted; never blocks the import).
+            "skipped_fuzzy" counts the skipped rows that matched a stored
+            row on a nearby date (import.fuzzy_dedup_days).
+            When rows carry a currency (a CSV currency column),
+            "foreign_currency" counts them by disposition (matched, skipped,
+            kept, converted) under source_options["foreign_currency"], and
+            "currency_skipped" lists the skipped ones with a reason.
+            "imported" counts the whole batch (earlier runs included when
+            resuming); "imported_transactions" only this run's rows.
+            "last_line" is the last file line written, "resumed_from_line"
+            the line a resumed import continued after and "restarted" the
+            batch a restart deleted ({"import_id", "removed"}).
         """
+        async with write_phase(self.write_lock, "import") as locked:
+            if not locked.success:
+                return locked
+            return await self._import_transactions(
+                source_type,
+                account_id,
+                source_options,
+                force=force,
+                resume=resume,
+                restart=restart,
+                should_stop=should_stop,
+                chunk_size=chunk_size,
+            )
+
+    async def _import_transactions(
+        self,
+        source_type: str,
+        account_id: UUID,
+        source_options: Dict[str, Any],
+        force: bool = False,
+        resume: str | None = None,
+        restart: bool = False,
+        should_stop: Callable[[], bool] | None = None,
+        chunk_size: int = IMPORT_CHUNK_SIZE,
+    ) -> Result[Dict[str, Any]]:
+        """Import with the write lock held; see import_transactions."""
         # Get provider
         provider = self.provider_registry.get(source_type.lower())
         if not provider:
             return Result(success=False, error=f"Unknown source type: {source_type}")
 
+        foreign_currency = source_options.get("foreign_currency") or "skip"
+        if foreign_currency not in FOREIGN_CURRENCY_MODES:
+            return Fail(
+                f"Invalid foreign currency mode: {foreign_currency} "
+                f"(expected {', '.join(FOREIGN_CURRENCY_MODES)})"
+            )
+
+        if resume and restart:
+            return Fail("Use either resume or restart, not both")
+
+        # Identify file sources by content so an identical re-import can be short-circuited
+        file_path = source_options.get("file_path")
+        file_sha256 = None
+        import_id = uuid4()
+        checkpoint = None
+        restarted = None
+        if file_path:
+            file_path = resolve_user_path(file_path)
+            source_options = {**source_options, "file_path": file_path}
+            check_result = self.check_import_file(file_path)
+            if not check_result.success:
+                return check_result
+        if file_path and hasattr(provider, "file_sha256"):
+            hash_result = provider.file_sha256(file_path)
+            if not hash_result.success:
+                return hash_result
+            file_sha256 = hash_result.data
+
+            if resume:
+                checkpoint_result = find_resumable_checkpoint(resume, file_sha256, account_id)
+                if not checkpoint_result.success:
+                    return checkpoint_result
+                checkpoint = checkpoint_result.data
+                import_id = UUID(checkpoint["import_id"])
+                # Parse the file exactly as the interrupted run did
+                source_options = {**source_options, **checkpoint.get("options", {})}
+                foreign_currency = source_options.get("foreign_currency") or "skip"
+            else:
+                pending = next(
+                    (
+                        c
+                        for c in load_import_checkpoints()
+                        if c.get("file_sha256") == file_sha256
+                        and c.get("account_id") == str(account_id)
+                    ),
+                    None,
+                )
+                if pending and restart:
+                    undo_result = await self.repository.delete_import(UUID(pending["import_id"]))
+                    if not undo_result.success:
+                        return undo_result
+                    import_checkpoint_path(pending["import_id"]).unlink(missing_ok=True)
+                    restarted = {"import_id": pending["import_id"], "removed": undo_result.data}
+                elif pending:
+                    return Fail(
+                        f"An import of this file was interrupted after line "
+                        f"{pending.get('last_line', 0)} (batch {pending['import_id']})",
+                        context={"kind": "interrupted_import", "checkpoint": pending},
+                    )
+
+            if not force and checkpoint is None:
+                previous_result = await self.repository.get_import_by_file_hash(
+                    file_sha256, account_id
+                )
+                if not previous_result.success:
+                    return previous_result
+                previous = previous_result.data
+                if previous:
+                    return Fail(
+                        f"This exact file was already imported as batch {previous.id} "
+                        f"on {previous.imported_at:%Y-%m-%d}",
+                        context={"kind": "already_imported", "import": previous},
+                    )
+
+            source_options = {
+                **source_options,
+                "file_sha256": file_sha256,
+                "import_id": str(import_id),
+            }
+        elif resume or restart:
+            return Fail("Only file imports can be resumed or restarted")
+
         # Get discovered transactions from source
         discovered_result = await provider.get_transactions(
             start_date=datetime.min,
@@ -52,10 +285,26 @@ class ImportService:
 
         discovered_transactions = discovered_result.data or []
 
+        # Settle rows in another currency before fingerprints are computed,
+        # since converting changes the amount
+        resolved_transactions = discovered_transactions
+        currency_counts = None
+        currency_skipped: List[Dict[str, Any]] = []
+        if any("currency" in tx.external_ids for tx in discovered_transactions):
+            account_result = await self.repository.get_account_by_id(account_id)
+            if not account_result.success:
+                return account_result
+            resolved_transactions, currency_counts, currency_skipped = resolve_currencies(
+                discovered_transactions,
+                account_result.data.currency,
+                foreign_currency,
+                CurrencyService(),
+            )
+
         # Map all transactions to the specified account
         # Note: Reconstruct transactions to recalculate fingerprint with new account_id
         mapped_transactions = []
-        for tx in discovered_transactions:
+        for tx in resolved_transactions:
             tx_dict = tx.model_dump()
             tx_dict["account_id"] = account_id
             # Remove fingerprint from external_ids to force regeneration with new account_id
@@ -64,22 +313,63 @@ class ImportService:
             tx_dict["external_ids"] = ext_ids
             mapped_transactions.append(Transaction(**tx_dict))
 
-        # Group by fingerprint (fingerprint is auto-set in external_ids by domain model)
+        # Refuse the whole file rather than write an amount the money columns
+        # can't hold
+        too_large = [tx for tx in mapped_transactions if not amount_in_range(tx.amount)]
+        if too_large:
+            rows = [
+                {
+                    "line": _source_line(tx) or None,
+                    "date": tx.transaction_date,
+                    "description": tx.description,
+                    "amount": tx.amount,
+                }
+                for tx in too_large
+            ]
+            first = rows[0]
+            where = f"line {first['line']}" if first["line"] else f"{first['description']!r}"
+            return Fail(
+                f"{len(rows)} row(s) have amounts beyond the largest that can be stored "
+                f"(±{MAX_AMOUNT}), first at {where}: {first['amount']}. Nothing was imported",
+                context={"kind": "amount_out_of_range", "rows": rows, "max": str(MAX_AMOUNT)},
+            )
+
+        fingerprint_version = get_fingerprint_version()
+        if fingerprint_version != 1:
+            mapped_transactions = assign_fingerprints(
+                mapped_transactions, fingerprint_version
+            )
+
+        # Group by v1 fingerprint, which identifies identical rows under every version
         discovered_by_fingerprint: Dict[str, List[Transaction]] = {}
         for tx in mapped_transactions:
-            fingerprint = tx.external_ids.get("fingerprint")
-            if fingerprint:
-                discovered_by_fingerprint.setdefault(fingerprint, []).append(tx)
+            discovered_by_fingerprint.setdefault(tx.compute_fingerprint(1), []).append(tx)
 
-        # Query existing counts per fingerprint
+        # Query existing counts for both the v1 and the configured fingerprints,
+        # so rows stored under either version are recognized during a transition
         fingerprints = list(discovered_by_fingerprint.keys())
+        lookup_fingerprints = set(fingerprints)
+        lookup_fingerprints.update(tx.external_ids["fingerprint"] for tx in mapped_transactions)
+        # A resumed import doesn't count its own earlier chunks as duplicates
+        own_batch = import_id if checkpoint is not None else None
         existing_counts_result = (
-            await self.repository.get_transaction_counts_by_fingerprint(fingerprints)
+            await self.repository.get_transaction_counts_by_fingerprint(
+                sorted(lookup_fingerprints), exclude_import_id=own_batch
+            )
         )
         if not existing_counts_result.success:
             return existing_counts_result
 
-        existing_counts = existing_counts_result.data or {}
+        fingerprint_counts = existing_counts_result.data or {}
+        existing_counts = {
+            fingerprint: fingerprint_counts.get(fingerprint, 0)
+            + sum(
+                fingerprint_counts.get(tx.external_ids["fingerprint"], 0)
+                for tx in discovered_txs
+                if tx.external_ids["fingerprint"] != fingerprint
+            )
+            for fingerprint, discovered_txs in discovered_by_fingerprint.items()
+        }
 
         # Determine which transactions to import
         transactions_to_import = []
@@ -106,28 +396,308 @@ class ImportService:
                 )
             skipped_count += discovered_count - new_count
 
-        # Bulk insert (not upsert, these are all new)
+        skipped_fuzzy = 0
+        fuzzy_days = get_fuzzy_dedup_days()
+        if fuzzy_days and transactions_to_import:
+            fuzzy_result = await self._skip_nearby_duplicates(
+                transactions_to_import,
+                mapped_transactions,
+                fuzzy_days,
+                fingerprint_version,
+                exclude_import_id=own_batch,
+            )
+            if not fuzzy_result.success:
+                return fuzzy_result
+            transactions_to_import, fuzzy_skipped = fuzzy_result.data
+            skipped_transactions.extend(fuzzy_skipped)
+            skipped_fuzzy = len(fuzzy_skipped)
+            skipped_count += skipped_fuzzy
+
+        # Compare signs against the account before the new rows join its history
+        sign_result = await self.check_sign_profile(account_id, mapped_transactions)
+        warnings = sign_result.data if sign_result.success else []
+
+        # Rows a resumed import already wrote are left out by file line, so
+        # new rows are written in file order
+        transactions_to_import = sorted(transactions_to_import, key=_source_line)
+        previously_imported = 0
+        resumed_from_line = None
+        if checkpoint is not None:
+            progress_result = await self.repository.get_import_progress(import_id)
+            if not progress_result.success:
+                return progress_result
+            progress = progress_result.data
+            # The checkpoint can trail the database if the last run died
+            # between committing a chunk and writing the checkpoint
+            resumed_from_line = max(checkpoint.get("last_line", 0), progress["last_line"])
+            previously_imported = progress["rows"]
+            transactions_to_import = [
+                tx for tx in transactions_to_import if _source_line(tx) > resumed_from_line
+            ]
+
         if transactions_to_import:
-            import_result = await self.repository.bulk_upsert_transactions(
-                transactions_to_import
+            account_result = await self.repository.get_account_by_id(account_id)
+            if not account_result.success:
+                return account_result
+            transactions_to_import = apply_default_tags(
+                transactions_to_import, [account_result.data]
             )
+
+        def batch_record(imported: int) -> ImportRecord:
+            return ImportRecord(
+                id=import_id,
+                source_type=source_type.lower(),
+                file_name=Path(file_path).name,
+                file_sha256=file_sha256,
+                account_id=account_id,
+                discovered=len(discovered_transactions),
+                imported=imported,
+                skipped=skipped_count,
+                imported_at=datetime.now(timezone.utc),
+            )
+
+        # Bulk insert (not upsert, these are all new), a chunk per DB transaction
+        chunk_size = max(1, chunk_size)
+        written: List[Transaction] = []
+        last_line = resumed_from_line or 0
+        interrupted = False
+        for start in range(0, len(transactions_to_import), chunk_size):
+            chunk = transactions_to_import[start : start + chunk_size]
+            import_result = await self.repository.bulk_upsert_transactions(chunk)
             if not import_result.success:
                 return import_result
+            written.extend(chunk)
+            last_line = max(last_line, _source_line(chunk[-1]))
+
+            if not file_sha256 or start + chunk_size >= len(transactions_to_import):
+                continue
+            record_result = await self.repository.record_import(
+                batch_record(previously_imported + len(written))
+            )
+            if not record_result.success:
+                return record_result
+            write_import_checkpoint(
+                {
+                    "import_id": str(import_id),
+                    "source_type": source_type.lower(),
+                    "file_name": Path(file_path).name,
+                    "file_sha256": file_sha256,
+                    "account_id": str(account_id),
+                    "options": {
+                        key: source_options[key]
+                        for key in CHECKPOINT_OPTION_KEYS
+                        if key in source_options
+                    },
+                    "last_line": last_line,
+                    "discovered": len(discovered_transactions),
+                    "imported": previously_imported + len(written),
+                    "skipped": skipped_count,
+                    "updated_at": datetime.now(timezone.utc).isoformat(),
+                }
+            )
+            if should_stop is not None and should_stop():
+                interrupted = True
+                break
+
+        if file_sha256:
+            record_result = await self.repository.record_import(
+                batch_record(previously_imported + len(written))
+            )
+            if not record_result.success:
+                return record_result
+            if not interrupted:
+                import_checkpoint_path(import_id).unlink(missing_ok=True)
 
         return Result(
             success=True,
             data={
+                "import_id": import_id if file_sha256 else None,
+                "file_sha256": file_sha256,
                 "discovered": len(discovered_transactions),
-                "imported": len(transactions_to_import),
+                "imported": previously_imported + len(written),
                 "skipped": skipped_count,
+                "skipped_fuzzy": skipped_fuzzy,
+                "interrupted": interrupted,
+                "last_line": last_line,
+                "resumed_from_line": resumed_from_line,
+                "restarted": restarted,
                 "fingerprints_checked": len(fingerprints),
-                "imported_transactions": transactions_to_import,
+                "foreign_currency": currency_counts,
+                "currency_skipped": currency_skipped,
+                "warnings": warnings,
+                "imported_transactions": written,
                 "skipped_transactions": skipped_transactions,
             },
         )
 
+    async def _skip_nearby_duplicates(
+        self,
+        candidates: List[Transaction],
+        batch: List[Transaction],
+        days: int,
+        fingerprint_version: int,
+        exclude_import_id: UUID | None = None,
+    ) -> Result[Tuple[List[Transaction], List[Dict[str, Any]]]]:
+        """Drop candidates matching a stored row dated up to days away.
+
+        Banks sometimes move a transaction by a day between the pending feed
+        and the statement, which changes its fingerprint. Each stored row
+        absorbs at most one candidate, and stored rows the exact pass already
+        matched to a row of this batch don't count, so distinct same-amount
+        purchases on nearby days are kept.
+
+        Returns:
+            Result with (candidates to import, skipped entries like the exact
+            pass's, with "fuzzy": True)
+        """
+        nearby = [nearby_fingerprints(tx, days, fingerprint_version) for tx in candidates]
+        counts_result = await self.repository.get_transaction_counts_by_fingerprint(
+            sorted({fingerprint for fingerprints in nearby for fingerprint in fingerprints}),
+            exclude_import_id=exclude_import_id,
+        )
+        if not counts_result.success:
+            return counts_result
+
+        in_batch: Dict[str, int] = {}
+        for tx in batch:
+            for fingerprint in {tx.compute_fingerprint(1), tx.external_ids["fingerprint"]}:
+                in_batch[fingerprint] = in_batch.get(fingerprint, 0) + 1
+        available = {
+            fingerprint: count - in_batch.get(fingerprint, 0)
+            for fingerprint, count in (counts_result.data or {}).items()
+        }
+
+        kept: List[Transaction] = []
+        skipped: List[Dict[str, Any]] = []
+        for tx, fingerprints in zip(candidates, nearby):
+            match = next((fp for fp in fingerprints if available.get(fp, 0) > 0), None)
+            if match is None:
+                kept.append(tx)
+                continue
+            available[match] -= 1
+            skipped.append(
+                {
+                    "transaction": tx,
+                    "fingerprint": match,
+                    "existing_count": counts_result.data[match],
+                    "fuzzy": True,
+                }
+            )
+        return Result(success=True, data=(kept, skipped))
+
+    async def check_sign_profile(
+        self, account_id: UUID, transactions: List[Transaction]
+    ) -> Result[List[str]]:
+        """Warn when the rows' signs look inverted for the target account.
+
+        Compares the share of negative amounts against the account's existing
+        transactions, or, without enough history, against the account type
+        (purchases on credit/loan accounts are negative). Only warns.
+
+        Returns:
+            Result with a list of warning messages (empty when consistent)
+        """
+        imported = _sign_profile(transactions)
+        if imported["count"] < SIGN_PROFILE_MIN_ROWS:
+            return Result(success=True, data=[])
+
+        history_result = await self.repository.get_transactions_filtered(
+            account_id=account_id, limit=SIGN_PROFILE_HISTORY_LIMIT
+        )
+        if not history_result.success:
+            return history_result
+        history = _sign_profile(history_result.data or [])
+
+        imported_negative = imported["negative_share"]
+        if history["count"] >= SIGN_PROFILE_MIN_ROWS:
+            history_negative = history["negative_share"]
+            inverted = (
+                history_negative >= SIGN_PROFILE_HISTORY_SHARE
+                and 1 - imported_negative >= SIGN_PROFILE_IMPORT_SHARE
+            ) or (
+                1 - history_negative >= SIGN_PROFILE_HISTORY_SHARE
+                and imported_negative >= SIGN_PROFILE_IMPORT_SHARE
+            )
+            expected = f"this account's history is {_percent(history_negative)} negative"
+        else:
+            account_result = await self.repository.get_account_by_id(account_id)
+            if not account_result.success:
+                return Result(success=True, data=[])
+            account_type = (account_result.data.account_type or "").lower()
+            inverted = (
+                account_type in LIABILITY_ACCOUNT_TYPES
+                and 1 - imported_negative >= SIGN_PROFILE_IMPORT_SHARE
+            )
+            expected = f"purchases on a {account_type} account should be negative"
+
+        if not inverted:
+            return Result(success=True, data=[])
+
+        return Result(
+            success=True,
+            data=[
+                f"Amounts look inverted: {_percent(imported_negative)} of these rows are "
+                f"negative (net {imported['net']}), but {expected}. "
+                "Consider --flip-signs (or --debit-negative for unsigned debit/credit files)."
+            ],
+        )
+
+    def check_import_file(self, file_path: str | Path) -> Result[None]:
+        """Check that a file can be imported before anything parses it.
+
+        The file must exist, be readable, be no larger than import.max_file_mb
+        and not be an XLSX, PDF or HTML file under another name.
+
+        Args:
+            file_path: Path to the file, as given by the user (see resolve_user_path)
+
+        Returns:
+            Result that on failure has context kind "file_not_found",
+            "file_unreadable", "file_too_large" (with size_bytes and
+            max_bytes) or "wrong_file_type" (with detected_type: "xlsx",
+            "pdf" or "html")
+        """
+        path = resolve_user_path(file_path)
+        name = path.name
+        if not path.exists():
+            return Fail(f"File not found: {file_path}", context={"kind": "file_not_found"})
+        if not path.is_file():
+            return Fail(f"Not a file: {file_path}", context={"kind": "file_unreadable"})
+
+        try:
+            size = path.stat().st_size
+            with open(path, "rb") as f:
+                head = f.read(FILE_SNIFF_BYTES)
+        except OSError as e:
+            return Fail(
+                f"Cannot read {file_path}: {e.strerror or e}", context={"kind": "file_unreadable"}
+            )
+
+        max_bytes = get_import_max_file_bytes()
+        if size > max_bytes:
+            return Fail(
+                f"{name} is {size / 1024 / 1024:.1f} MB, over the "
+                f"{round(max_bytes / 1024 / 1024, 2):g} MB import limit. Split it into "
+                "smaller files (one per year, say) and import each, or raise "
+                "import.max_file_mb in settings.json",
+                context={"kind": "file_too_large", "size_bytes": size, "max_bytes": max_bytes},
+            )
+
+        sniffed = head.removeprefix(b"\xef\xbb\xbf").lstrip().lower()
+        for prefixes, detected_type, message in NON_CSV_SIGNATURES:
+            if sniffed.startswith(tuple(prefix.lower() for prefix in prefixes)):
+                return Fail(
+                    message.format(name=name),
+                    context={"kind": "wrong_file_type", "detected_type": detected_type},
+                )
+        return Result(success=True)
+
+    async def list_imports(self) -> Result[List[ImportRecord]]:
+        """List recorded file imports, most recent first."""
+        return await self.repository.list_imports()
+
     async def detect_columns(
-        self, source_type: str, file_path: str
+        self, source_type: str, file_path: str | Path
     ) -> Result[Dict[str, Any]]:
         """Detect columns automatically for import.
 
@@ -143,12 +713,63 @@ class ImportService:
         if not provider:
             return Result(success=False, error=f"{source_type} provider not available")
 
+        file_path = resolve_user_path(file_path)
+        check_result = self.check_import_file(file_path)
+        if not check_result.success:
+            return check_result
+
         # Call provider-specific detection method
         return provider.detect_columns(file_path)
 
+    async def detect_preset(self, file_path: str | Path) -> Result[CSVPreset | None]:
+        """The institution preset a CSV file's headers match, if any.
+
+        Its mapping is the one detect_columns returns; flip_signs and
+        debit_negative are the sign conventions of that export.
+        """
+        provider = self.provider_registry.get("csv")
+        if not provider:
+            return Result(success=False, error="CSV provider not available")
+
+        file_path = resolve_user_path(file_path)
+        check_result = self.check_import_file(file_path)
+        if not check_result.success:
+            return check_result
+
+        return provider.detect_preset(file_path)
+
+    async def diagnose_csv(
+        self,
+        file_path: str | Path,
+        column_mapping: Dict[str, str] | None = None,
+        debit_negative: bool = False,
+    ) -> Result[DetectionReport]:
+        """Explain how a CSV file would be detected and parsed. Nothing is written.
+
+        Args:
+            file_path: Path to CSV file
+            column_mapping: Mapping to explain instead of the detected one
+            debit_negative: Whether to negate debit amounts in the parsed rows
+
+        Returns:
+            Result with the DetectionReport
+        """
+        provider = self.provider_registry.get("csv")
+        if not provider:
+            return Result(success=False, error="CSV provider not available")
+
+        file_path = resolve_user_path(file_path)
+        check_result = self.check_import_file(file_path)
+        if not check_result.success:
+            return check_result
+
+        return provider.detection_report(
+            file_path, column_mapping, date_format="auto", debit_negative=debit_negative
+        )
+
     async def preview_csv_import(
         self,
-        file_path: str,
+        file_path: str | Path,
         column_mapping: Dict[str, str],
         date_format: str = "auto",
         limit: int = 5,
@@ -173,7 +794,167 @@ class ImportService:
         if not provider:
             return Result(success=False, error="CSV provider not available")
 
+        file_path = resolve_user_path(file_path)
+        check_result = self.check_import_file(file_path)
+        if not check_result.success:
+            return check_result
+
         # Call provider-specific preview method
         return provider.preview_transactions(
             file_path, column_mapping, date_format, limit, flip_signs, debit_negative
         )
+
+
+def resolve_currencies(
+    transactions: List[Transaction],
+    account_currency: str,
+    mode: str,
+    currency_service: CurrencyService,
+) -> Tuple[List[Transaction], Dict[str, Any], List[Dict[str, Any]]]:
+    """Apply a foreign currency mode to rows that carry external_ids["currency"].
+
+    Rows in the account's currency, or without one, are kept as they are.
+    Other rows are skipped ("skip"), or tagged fx:<code> and kept as they
+    are ("keep") or converted to the account's currency ("convert"), with
+    the original amount and currency (and the rate used) stored as JSON in
+    external_ids["original_amount"]. Rows whose currency isn't a three-letter
+    code, and rows with no rate to convert with, are skipped in any mode.
+
+    Returns the rows to import (without the "currency" key), the counts per
+    disposition and the skipped rows with their reason.
+    """
+    counts: Dict[str, Any] = {"mode": mode, "matched": 0, "skipped": 0, "kept": 0, "converted": 0}
+    resolved: List[Transaction] = []
+    skipped: List[Dict[str, Any]] = []
+    for tx in transactions:
+        external_ids = dict(tx.external_ids)
+        currency = external_ids.pop("currency", None)
+        if currency is None or currency == account_currency:
+            counts["matched"] += 1
+            resolved.append(tx.model_copy(update={"external_ids": external_ids}))
+            continue
+
+        amount, reason, rate = tx.amount, None, None
+        if not is_currency_code(currency):
+            reason = f"Unknown currency code: {currency}"
+        elif mode == "skip":
+            reason = f"{currency} row in a {account_currency} account"
+        elif mode == "convert":
+            converted = currency_service.convert(tx.amount, currency, account_currency)
+            if converted.success:
+                amount, rate = converted.data, currency_service.rate(currency, account_currency)
+            else:
+                reason = converted.error
+
+        if reason:
+            counts["skipped"] += 1
+            source = json.loads(tx.external_ids.get("csv_source", "{}"))
+            skipped.append(
+                {
+                    "line": source.get("line"),
+                    "date": tx.transaction_date,
+                    "description": tx.description,
+                    "amount": tx.amount,
+                    "currency": currency,
+                    "reason": reason,
+                }
+            )
+            continue
+
+        original = {"amount": str(tx.amount), "currency": currency}
+        if rate is not None:
+            original["rate"] = str(rate)
+            counts["converted"] += 1
+        else:
+            counts["kept"] += 1
+        external_ids["original_amount"] = json.dumps(original)
+        tags = normalize_tags([*tx.tags, f"fx:{currency}"])
+        resolved.append(
+            tx.model_copy(
+                update={"amount": amount, "tags": tuple(tags), "external_ids": external_ids}
+            )
+        )
+    return resolved, counts, skipped
+
+
+def import_checkpoint_path(import_id: UUID | str) -> Path:
+    """Where an unfinished file import's checkpoint is kept (~/.treeline/tmp)."""
+    return get_treeline_dir() / "tmp" / f"import-{import_id}.checkpoint"
+
+
+def load_import_checkpoints() -> List[Dict[str, Any]]:
+    """Checkpoints of interrupted file imports; unreadable files are ignored."""
+    directory = get_treeline_dir() / "tmp"
+    if not directory.is_dir():
+        return []
+    checkpoints = []
+    for path in sorted(directory.glob("import-*.checkpoint")):
+        try:
+            checkpoint = json.loads(path.read_text())
+        except (OSError, ValueError):
+            continue
+        if isinstance(checkpoint, dict) and checkpoint.get("import_id"):
+            checkpoints.append(checkpoint)
+    return checkpoints
+
+
+def write_import_checkpoint(checkpoint: Dict[str, Any]) -> None:
+    """Write a checkpoint atomically, so a crash leaves the previous one intact."""
+    path = import_checkpoint_path(checkpoint["import_id"])
+    path.parent.mkdir(parents=True, exist_ok=True)
+    temp_path = path.with_suffix(".tmp")
+    temp_path.write_text(json.dumps(checkpoint, indent=2, default=str))
+    os.replace(temp_path, path)
+
+
+def find_resumable_checkpoint(
+    batch: str, file_sha256: str, account_id: UUID
+) -> Result[Dict[str, Any]]:
+    """The checkpoint of batch (an ID or unique ID prefix), if it belongs to this file and account."""
+    prefix = batch.strip().lower()
+    matches = [c for c in load_import_checkpoints() if c["import_id"].startswith(prefix)]
+    if not prefix or not matches:
+        return Fail(
+            f"No interrupted import with batch ID {batch}", context={"kind": "no_checkpoint"}
+        )
+    if len(matches) > 1:
+        return Fail(
+            f"Batch ID {batch} matches {len(matches)} interrupted imports; use more characters",
+            context={"kind": "no_checkpoint"},
+        )
+    checkpoint = matches[0]
+    if checkpoint.get("file_sha256") != file_sha256:
+        return Fail(
+            f"Batch {checkpoint['import_id']} was an import of another file "
+            f"({checkpoint.get('file_name')}); this file's contents differ",
+            context={"kind": "checkpoint_mismatch", "checkpoint": checkpoint},
+        )
+    if checkpoint.get("account_id") != str(account_id):
+        return Fail(
+            f"Batch {checkpoint['import_id']} was an import into another account",
+            context={"kind": "checkpoint_mismatch", "checkpoint": checkpoint},
+        )
+    return Ok(checkpoint)
+
+
+def _source_line(transaction: Transaction) -> int:
+    """The CSV line a transaction came from, or 0 without CSV provenance."""
+    try:
+        return int(json.loads(transaction.external_ids.get("csv_source", "{}")).get("line") or 0)
+    except (TypeError, ValueError):
+        return 0
+
+
+def _sign_profile(transactions: List[Transaction]) -> Dict[str, Any]:
+    """Count, share of negative amounts and net sum of non-zero amounts."""
+    amounts = [tx.amount for tx in transactions if tx.amount != 0]
+    negative = sum(1 for amount in amounts if amount < 0)
+    return {
+        "count": len(amounts),
+        "negative_share": Decimal(negative) / len(amounts) if amounts else Decimal(0),
+        "net": sum(amounts, Decimal("0.00")),
+    }
+
+
+def _percent(share: Decimal) -> str:
+    return f"{share * 100:.0f}%"
@@ -2,15 +2,32 @@
 
 from typing import Any, Dict, List
 
-from treeline.abstractions import IntegrationProvider, Repository
-from treeline.domain import Result
+from treeline.abstractions import DataAggregationProvider, IntegrationProvider, Repository
+from treeline.domain import Account, Fail, Ok, Result
+from treeline.features import disabled_error, is_enabled
+
+# User-managed labels stored inside integration_settings. Providers ignore
+# them; sync still resolves the provider by integration_name.
+DISPLAY_NAME_KEY = "display_name"
+NOTES_KEY = "notes"
+METADATA_KEYS = (DISPLAY_NAME_KEY, NOTES_KEY)
+
+
+def integration_label(integration: Dict[str, Any]) -> str:
+    """The display name of a list_integrations() entry, or its name."""
+    return integration.get("displayName") or integration["integrationName"]
 
 
 class IntegrationService:
     """Service for managing integrations with external providers."""
 
-    def __init__(self, repository: Repository):
+    def __init__(
+        self,
+        repository: Repository,
+        provider_registry: Dict[str, DataAggregationProvider] | None = None,
+    ):
         self.repository = repository
+        self.provider_registry = provider_registry or {}
 
     async def get_integrations(self) -> Result[List[Dict[str, Any]]]:
         """Get list of configured integrations."""
@@ -40,6 +57,144 @@ class IntegrationService:
             return result
 
         if result.data:
-            await self.repository.upsert_integration(integration_name, result.data)
+            # Re-running setup replaces credentials but keeps the user's labels
+            existing = await self.repository.get_integration_settings(integration_name)
+            settings = dict(result.data)
+            for key in METADATA_KEYS:
+                if existing.success and key in (existing.data or {}):
+                    settings.setdefault(key, existing.data[key])
+            save_result = await self.repository.upsert_integration(integration_name, settings)
+            if not save_result.success:
+                return save_result
 
         return result
+
+    async def update_integration_metadata(
+        self,
+        integration_name: str,
+        display_name: str | None = None,
+        notes: str | None = None,
+    ) -> Result[Dict[str, Any]]:
+        """Set an integration's display name and/or notes.
+
+        None leaves a field unchanged; an empty string clears it. All other
+        settings (credentials, accountSettings, ...) are written back as-is.
+
+        Returns:
+            Result containing dict with integration, display_name and notes
+        """
+        name = integration_name.lower()
+        integrations_result = await self.repository.list_integrations()
+        if not integrations_result.success:
+            return integrations_result
+        if name not in [i["integrationName"].lower() for i in integrations_result.data or []]:
+            return Fail(f"Integration '{integration_name}' not found")
+
+        settings_result = await self.repository.get_integration_settings(name)
+        if not settings_result.success:
+            return settings_result
+        settings = dict(settings_result.data or {})
+
+        for key, value in ((DISPLAY_NAME_KEY, display_name), (NOTES_KEY, notes)):
+            if value is None:
+                continue
+            value = value.strip()
+            if value:
+                settings[key] = value
+            else:
+                settings.pop(key, None)
+
+        save_result = await self.repository.upsert_integration(name, settings)
+        if not save_result.success:
+            return save_result
+
+        return Ok(
+            {
+                "integration": name,
+                "display_name": settings.get(DISPLAY_NAME_KEY),
+                "notes": settings.get(NOTES_KEY),
+            }
+        )
+
+    async def set_account_sync_enabled(
+        self, account: Account, enabled: bool
+    ) -> Result[Dict[str, Any]]:
+        """Turn syncing an account on or off in every integration that syncs it.
+
+        Stored as syncDisabled in the integration's accountSettings, keyed by
+        the provider's account ID. Sync skips disabled accounts entirely (no
+        balance, no transactions); data already stored is kept.
+
+        Returns:
+            Result containing dict with account_id, sync_enabled and
+            integrations (names of the integrations updated)
+        """
+        integrations_result = await self.repository.list_integrations()
+        if not integrations_result.success:
+            return integrations_result
+        names = [
+            i["integrationName"].lower()
+            for i in integrations_result.data or []
+            if i["integrationName"].lower() in account.external_ids
+        ]
+        if not names:
+            return Fail(f"'{account.name}' is not synced by any configured integration")
+
+        for name in names:
+            settings_result = await self.repository.get_integration_settings(name)
+            if not settings_result.success:
+                return settings_result
+            settings = dict(settings_result.data or {})
+            account_settings = dict(settings.get("accountSettings") or {})
+            provider_account_id = account.external_ids[name]
+            entry = dict(account_settings.get(provider_account_id) or {})
+            if enabled:
+                entry.pop("syncDisabled", None)
+            else:
+                entry["syncDisabled"] = True
+            if entry:
+                account_settings[provider_account_id] = entry
+            else:
+                account_settings.pop(provider_account_id, None)
+            settings["accountSettings"] = account_settings
+
+            save_result = await self.repository.upsert_integration(name, settings)
+            if not save_result.success:
+                return save_result
+
+        return Ok({"account_id": str(account.id), "sync_enabled": enabled, "integrations": names})
+
+    async def test_integration(self, integration_name: str) -> Result[Dict[str, Any]]:
+        """Check an integration's connectivity and credentials without syncing.
+
+        Nothing is written to the database. Failures carry context["kind"]
+        (e.g. unknown_provider, unauthorized, payment_required, timeout, offline).
+        """
+        provider = self.provider_registry.get(integration_name.lower())
+        if provider is None:
+            if is_enabled(integration_name):
+                error = f"Unknown integration: {integration_name}"
+            else:
+                error = disabled_error(integration_name)
+            return Fail(error, context={"kind": "unknown_provider"})
+
+        integrations_result = await self.repository.list_integrations()
+        if not integrations_result.success:
+            return integrations_result
+
+        names = [
+            i.get("integrationName", "").lower() for i in integrations_result.data or []
+        ]
+        if integration_name.lower() not in names:
+            return Fail(
+                f"Integration '{integration_name}' not found",
+                context={"kind": "not_configured"},
+            )
+
+        settings_result = await self.repository.get_integration_settings(
+            integration_name.lower()
+        )
+        if not settings_result.success:
+            return settings_result
+
+        return await provider.health_check(provider_settings=settings_result.data or {})
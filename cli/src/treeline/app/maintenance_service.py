@@ -0,0 +1,377 @@
+"""Service for database maintenance tasks."""
+
+from datetime import datetime, timezone
+from typing import Any, Dict, List
+from uuid import UUID
+
+from treeline.abstractions import Repository
+from treeline.app.sync_steps import map_transactions, partition_new
+from treeline.config import get_fingerprint_version
+from treeline.domain import (
+    SUPPORTED_FINGERPRINT_VERSIONS,
+    Fail,
+    Ok,
+    Result,
+    Transaction,
+    apply_default_tags,
+    assign_fingerprints,
+)
+
+
+# Offending IDs listed per failed verify check
+VERIFY_SAMPLE_SIZE = 5
+
+
+class MaintenanceService:
+    """Service for database maintenance tasks."""
+
+    def __init__(self, repository: Repository):
+        self.repository = repository
+
+    async def refingerprint(
+        self, to_version: int, dry_run: bool = False
+    ) -> Result[Dict[str, Any]]:
+        """Recompute fingerprints for all non-deleted transactions.
+
+        Existing rows have no batch boundaries, so for v2 identical rows within
+        each account are indexed in (date, created_at) order.
+
+        Returns dict with:
+            - to_version: Target fingerprint version
+            - total: Transactions examined
+            - changed: Transactions whose fingerprint changes
+            - collisions: Fingerprints shared by more than one transaction afterwards,
+              as [{"fingerprint": ..., "transaction_ids": [...]}]
+            - dry_run: Whether changes were written
+        """
+        if to_version not in SUPPORTED_FINGERPRINT_VERSIONS:
+            return Fail(
+                f"Unsupported fingerprint version: {to_version} "
+                f"(supported: {', '.join(str(v) for v in SUPPORTED_FINGERPRINT_VERSIONS)})"
+            )
+
+        transactions_result = await self.repository.get_all_transactions()
+        if not transactions_result.success:
+            return transactions_result
+
+        transactions = transactions_result.data or []
+        recomputed = assign_fingerprints(transactions, to_version)
+
+        changed: Dict[Any, Dict[str, str]] = {}
+        for before, after in zip(transactions, recomputed):
+            if dict(before.external_ids) != dict(after.external_ids):
+                changed[after.id] = dict(after.external_ids)
+
+        collisions = self._find_collisions(recomputed)
+
+        if not dry_run and changed:
+            update_result = await self.repository.update_transaction_external_ids(changed)
+            if not update_result.success:
+                return update_result
+
+        return Ok(
+            {
+                "to_version": to_version,
+                "total": len(transactions),
+                "changed": len(changed),
+                "collisions": collisions,
+                "dry_run": dry_run,
+            }
+        )
+
+    async def adopt_unmapped(
+        self, provider_account_id: str, account_id: UUID, dry_run: bool = False
+    ) -> Result[Dict[str, Any]]:
+        """Move quarantined sync transactions for a provider account into an account.
+
+        Transactions get fingerprints for the target account. Any whose provider
+        transaction ID is already stored are skipped; the quarantine for the
+        provider account is cleared either way.
+
+        Returns dict with:
+            - provider_account_id, account_id
+            - adopted: Transactions moved into the account
+            - skipped: Quarantined transactions that already exist
+            - dry_run: Whether changes were written
+        """
+        account_result = await self.repository.get_account_by_id(account_id)
+        if not account_result.success:
+            return Fail(f"Account not found: {account_id}")
+
+        unmapped_result = await self.repository.get_unmapped_transactions(provider_account_id)
+        if not unmapped_result.success:
+            return unmapped_result
+        unmapped = unmapped_result.data or []
+        if not unmapped:
+            return Fail(f"No unmapped transactions for provider account {provider_account_id}")
+
+        fingerprint_version = get_fingerprint_version()
+        adopted: List[Transaction] = []
+        skipped = 0
+        for integration_name in sorted({u.integration_name for u in unmapped}):
+            mapped = map_transactions(
+                [
+                    (provider_account_id, u.transaction)
+                    for u in unmapped
+                    if u.integration_name == integration_name
+                ],
+                {provider_account_id: account_id},
+            ).transactions
+            if fingerprint_version != 1:
+                mapped = assign_fingerprints(mapped, fingerprint_version)
+
+            external_ids = [
+                tx.external_ids[integration_name]
+                for tx in mapped
+                if tx.external_ids.get(integration_name)
+            ]
+            existing_result = await self.repository.get_transactions_by_external_ids(
+                integration_name, external_ids
+            )
+            if not existing_result.success:
+                return existing_result
+            existing_by_ext_id = {
+                tx.external_ids[integration_name]: tx for tx in existing_result.data or []
+            }
+
+            new, already_stored = partition_new(mapped, existing_by_ext_id, integration_name)
+            adopted.extend(new)
+            skipped += len(already_stored)
+
+        adopted = apply_default_tags(adopted, [account_result.data])
+        if not dry_run:
+            adopt_result = await self.repository.adopt_unmapped_transactions(
+                provider_account_id, adopted
+            )
+            if not adopt_result.success:
+                return adopt_result
+
+        return Ok(
+            {
+                "provider_account_id": provider_account_id,
+                "account_id": str(account_id),
+                "adopted": len(adopted),
+                "skipped": skipped,
+                "dry_run": dry_run,
+            }
+        )
+
+    async def apply_default_tags(
+        self, account_id: UUID, dry_run: bool = False
+    ) -> Result[Dict[str, Any]]:
+        """Add an account's default tags to the transactions already in it.
+
+        New transactions get them on the way in; this catches up the ones
+        stored before the tags were set. Existing tags are kept. Deleted
+        transactions are left alone, and re-running changes nothing.
+
+        Returns dict with:
+            - account_id, default_tags
+            - updated: Transactions that gained a tag
+            - unchanged: Transactions that already had every default tag
+            - dry_run: Whether changes were written
+        """
+        account_result = await self.repository.get_account_by_id(account_id)
+        if not account_result.success:
+            return Fail(f"Account not found: {account_id}")
+        account = account_result.data
+        if not account.default_tags:
+            return Fail(
+                f"Account {account.nickname or account.name} has no default tags "
+                "(set them with 'tl accounts set --default-tags')"
+            )
+
+        tx_result = await self.repository.get_transactions_by_account(account_id)
+        if not tx_result.success:
+            return tx_result
+        transactions = [tx for tx in tx_result.data or [] if tx.deleted_at is None]
+
+        tags_by_id = {
+            tagged.id: list(tagged.tags)
+            for tx, tagged in zip(transactions, apply_default_tags(transactions, [account]))
+            if tagged.tags != tx.tags
+        }
+        if tags_by_id and not dry_run:
+            update_result = await self.repository.bulk_update_transaction_tags(tags_by_id)
+            if not update_result.success:
+                return update_result
+
+        return Ok(
+            {
+                "account_id": str(account_id),
+                "default_tags": list(account.default_tags),
+                "updated": len(tags_by_id),
+                "unchanged": len(transactions) - len(tags_by_id),
+                "dry_run": dry_run,
+            }
+        )
+
+    async def rebuild_search_index(self) -> Result[Dict[str, Any]]:
+        """Refill the lowercased description column that backs transaction search.
+
+        Only needed for rows written outside the CLI, e.g. edited in the SQL editor.
+
+        Returns dict with:
+            - updated: Rows whose search column was missing or stale
+        """
+        rebuild_result = await self.repository.rebuild_search_index()
+        if not rebuild_result.success:
+            return rebuild_result
+
+        return Ok({"updated": rebuild_result.data})
+
+    async def infer_account_dates(self, dry_run: bool = False) -> Result[Dict[str, Any]]:
+        """Fill in unknown opened_at dates from each account's earliest activity.
+
+        Accounts that already have opened_at are left alone, so this is safe
+        to re-run after later syncs. Earliest activity is the first
+        transaction or balance snapshot, which for synced accounts is usually
+        the start of the provider's history rather than the real opening date.
+
+        Returns dict with:
+            - updated: One entry per account (account_id, account, opened_at)
+            - no_activity: Accounts skipped because they have no data yet
+            - dry_run: Whether changes were written
+        """
+        accounts_result = await self.repository.get_accounts()
+        if not accounts_result.success:
+            return accounts_result
+
+        earliest_result = await self.repository.get_earliest_activity_dates()
+        if not earliest_result.success:
+            return earliest_result
+        earliest = earliest_result.data or {}
+
+        updated = []
+        no_activity = 0
+        now = datetime.now(timezone.utc)
+        for account in sorted(accounts_result.data or [], key=lambda a: a.name.casefold()):
+            if account.opened_at is not None:
+                continue
+            opened_at = earliest.get(account.id)
+            if opened_at is None:
+                no_activity += 1
+                continue
+            # Keep opened_at <= closed_at for accounts closed before their data starts
+            if account.closed_at is not None:
+                opened_at = min(opened_at, account.closed_at)
+
+            if not dry_run:
+                update_result = await self.repository.update_account_by_id(
+                    account.model_copy(update={"opened_at": opened_at, "updated_at": now})
+                )
+                if not update_result.success:
+                    return update_result
+            updated.append(
+                {
+                    "account_id": str(account.id),
+                    "account": account.nickname or account.name,
+                    "opened_at": opened_at,
+                }
+            )
+
+        return Ok({"updated": updated, "no_activity": no_activity, "dry_run": dry_run})
+
+    async def verify(self, fix: bool = False) -> Result[Dict[str, Any]]:
+        """Run the integrity checks, optionally repairing what is safe to repair.
+
+        Only malformed JSON columns (reset to empty defaults) and dangling
+        parent IDs (cleared) are fixed; the other failures need a decision.
+        Fixed checks are scanned again, so they report the state after fixing.
+
+        Returns dict with:
+            - checks: One entry per check (name, description, passed, count,
+              ids: the first few offending IDs, fixable)
+            - passed: Whether every check passed
+            - fixes: Rows changed by each fix that ran
+        """
+        repository = self.repository
+
+        async def scan_migrations() -> Result[Dict[str, Any]]:
+            missing_result = await repository.get_missing_migrations()
+            if not missing_result.success:
+                return missing_result
+            missing = missing_result.data
+            return Ok({"count": len(missing), "ids": missing[:VERIFY_SAMPLE_SIZE]})
+
+        def sampled(scan):
+            return lambda: scan(VERIFY_SAMPLE_SIZE)
+
+        # (name, description, scan, repair for the safe subset or None)
+        checks = [
+            (
+                "transaction_accounts",
+                "Transactions belong to existing accounts",
+                sampled(repository.scan_transactions_without_account),
+                None,
+            ),
+            (
+                "fingerprints",
+                "No two transactions share a fingerprint",
+                sampled(repository.scan_duplicate_fingerprints),
+                None,
+            ),
+            (
+                "json_columns",
+                "external_ids and tags are well-formed",
+                sampled(repository.scan_malformed_json),
+                repository.repair_malformed_json,
+            ),
+            (
+                "parent_ids",
+                "Split parts point to an existing parent",
+                sampled(repository.scan_dangling_parent_ids),
+                repository.clear_dangling_parent_ids,
+            ),
+            (
+                "snapshot_accounts",
+                "Balance snapshots belong to existing accounts",
+                sampled(repository.scan_snapshots_without_account),
+                None,
+            ),
+            ("migrations", "Every migration has been applied", scan_migrations, None),
+        ]
+
+        results = []
+        fixes: Dict[str, int] = {}
+        for name, description, scan, repair in checks:
+            scan_result = await scan()
+            if not scan_result.success:
+                return scan_result
+            if fix and repair is not None and scan_result.data["count"]:
+                repair_result = await repair()
+                if not repair_result.success:
+                    return repair_result
+                fixes[name] = repair_result.data
+                scan_result = await scan()
+                if not scan_result.success:
+                    return scan_result
+            results.append(
+                {
+                    "name": name,
+                    "description": description,
+                    "passed": scan_result.data["count"] == 0,
+                    "count": scan_result.data["count"],
+                    "ids": scan_result.data["ids"],
+                    "fixable": repair is not None,
+                }
+            )
+
+        return Ok(
+            {
+                "checks": results,
+                "passed": all(check["passed"] for check in results),
+                "fixes": fixes,
+            }
+        )
+
+    def _find_collisions(self, transactions: List[Transaction]) -> List[Dict[str, Any]]:
+        by_fingerprint: Dict[str, List[str]] = {}
+        for tx in transactions:
+            by_fingerprint.setdefault(tx.external_ids["fingerprint"], []).append(str(tx.id))
+
+        return [
+            {"fingerprint": fingerprint, "transaction_ids": ids}
+            for fingerprint, ids in sorted(by_fingerprint.items())
+            if len(ids) > 1
+        ]
@@ -0,0 +1,412 @@
+"""Service for planned (expected) transactions such as bills and paychecks."""
+
+import calendar
+from datetime import date, datetime, timedelta, timezone
+from decimal import Decimal
+from difflib import SequenceMatcher
+from typing import Any, Dict, Iterable, List
+from uuid import UUID, uuid4
+
+from treeline.abstractions import Repository
+from treeline.app.account_service import AccountDirectory, AccountService
+from treeline.app.classifier import LIABILITY_ACCOUNT_TYPES
+from treeline.app.report_service import merchant_key
+from treeline.app.transaction_service import MIN_ID_PREFIX_LENGTH
+from treeline.domain import (
+    Fail,
+    Ok,
+    PlannedFrequency,
+    PlannedTransaction,
+    Result,
+    Transaction,
+)
+
+DEFAULT_UPCOMING_DAYS = 30
+
+# A synced transaction satisfies a planned occurrence when its amount is within
+# 5% of the planned amount, it is dated within 3 days of the due date, and its
+# description is similar enough (see descriptions_match)
+MATCH_AMOUNT_TOLERANCE = Decimal("0.05")
+MATCH_WINDOW_DAYS = 3
+MATCH_DESCRIPTION_RATIO = 0.6
+
+_MONTHS_PER_STEP = {
+    PlannedFrequency.MONTHLY: 1,
+    PlannedFrequency.QUARTERLY: 3,
+    PlannedFrequency.YEARLY: 12,
+}
+_DAYS_PER_STEP = {
+    PlannedFrequency.WEEKLY: 7,
+    PlannedFrequency.BIWEEKLY: 14,
+}
+
+
+def _on_day(year: int, month: int, day_of_month: int) -> date:
+    """The given day of a month, or the month's last day if it is shorter."""
+    return date(year, month, min(day_of_month, calendar.monthrange(year, month)[1]))
+
+
+def advance_due(
+    due: date, frequency: PlannedFrequency, day_of_month: int | None = None
+) -> date | None:
+    """The occurrence after `due`, or None for one-off items.
+
+    Monthly, quarterly and yearly items land on day_of_month (due's day when
+    None), clamped to the month's last day: an item on the 31st falls on
+    Feb 28 and then Mar 31 again.
+    """
+    if frequency in _DAYS_PER_STEP:
+        return due + timedelta(days=_DAYS_PER_STEP[frequency])
+    if frequency not in _MONTHS_PER_STEP:
+        return None
+    month_index = due.year * 12 + due.month - 1 + _MONTHS_PER_STEP[frequency]
+    return _on_day(month_index // 12, month_index % 12 + 1, day_of_month or due.day)
+
+
+def first_due_on_or_after(today: date, day_of_month: int) -> date:
+    """The first date from today that falls on day_of_month (clamped)."""
+    due = _on_day(today.year, today.month, day_of_month)
+    if due >= today:
+        return due
+    return advance_due(due, PlannedFrequency.MONTHLY, day_of_month)
+
+
+def occurrences(planned: PlannedTransaction, until: date) -> Iterable[date]:
+    """Due dates from next_due through until (inclusive)."""
+    due = planned.next_due
+    while due is not None and due <= until:
+        yield due
+        due = advance_due(due, planned.frequency, planned.day_of_month)
+
+
+def descriptions_match(planned_description: str, description: str | None) -> bool:
+    """Whether a transaction description names the planned item's payee.
+
+    Both are compared as merchant keys (case, punctuation and store numbers
+    ignored). One containing the other matches ("Rent" and "ACH RENT PAYMENT
+    OAKWOOD"), as does a close spelling.
+    """
+    planned_key = merchant_key(planned_description)
+    key = merchant_key(description)
+    if not planned_key or not key:
+        return False
+    if planned_key in key or key in planned_key:
+        return True
+    return SequenceMatcher(None, planned_key, key).ratio() >= MATCH_DESCRIPTION_RATIO
+
+
+def matches_planned(planned: PlannedTransaction, due: date, tx: Transaction) -> bool:
+    """Whether a transaction is the planned item's occurrence due on `due`."""
+    if tx.account_id != planned.account_id or tx.deleted_at is not None:
+        return False
+    if (tx.amount < 0) != (planned.amount < 0):
+        return False
+    if abs(tx.amount - planned.amount) > abs(planned.amount) * MATCH_AMOUNT_TOLERANCE:
+        return False
+    if abs((tx.transaction_date - due).days) > MATCH_WINDOW_DAYS:
+        return False
+    return descriptions_match(planned.description, tx.description)
+
+
+def parse_frequency(value: str) -> Result[PlannedFrequency]:
+    try:
+        return Ok(PlannedFrequency(value.strip().lower()))
+    except ValueError:
+        choices = ", ".join(f.value for f in PlannedFrequency)
+        return Fail(f"Invalid frequency: {value} (expected {choices})")
+
+
+class PlannedService:
+    """Service for planned transactions: bills, paychecks and other expected items.
+
+    Each item tracks its next occurrence (next_due). Sync calls satisfy() with
+    the transactions it inserted; a match marks the occurrence as arrived and
+    moves next_due on (one-off items are deleted). upcoming() projects each
+    account's balance through the occurrences still to come.
+    """
+
+    def __init__(self, repository: Repository, account_service: AccountService):
+        self.repository = repository
+        self.account_service = account_service
+
+    async def list_planned(self) -> Result[List[PlannedTransaction]]:
+        """Get every planned transaction, soonest due first."""
+        return await self.repository.list_planned_transactions()
+
+    async def resolve(self, id_or_prefix: str) -> Result[PlannedTransaction]:
+        """Find a planned transaction by ID or unambiguous ID prefix.
+
+        An ambiguous prefix fails with context {"matches": [...]}.
+        """
+        value = id_or_prefix.strip().lower()
+        if len(value) < MIN_ID_PREFIX_LENGTH:
+            return Fail(
+                f"Planned transaction ID prefix must be at least {MIN_ID_PREFIX_LENGTH} characters"
+            )
+
+        list_result = await self.repository.list_planned_transactions()
+        if not list_result.success:
+            return list_result
+        matches = [p for p in list_result.data or [] if str(p.id).startswith(value)]
+        if not matches:
+            return Fail(f"No planned transaction matches ID: {id_or_prefix}")
+        if len(matches) > 1:
+            return Fail(
+                f"Ambiguous planned transaction ID prefix: {id_or_prefix}",
+                context={"matches": [str(p.id) for p in matches]},
+            )
+        return Ok(matches[0])
+
+    async def upsert(
+        self,
+        account: str,
+        description: str,
+        amount: Decimal,
+        frequency: str,
+        next_due: date | None = None,
+        day_of_month: int | None = None,
+        planned_id: str | None = None,
+        autogenerated: bool = False,
+        today: date | None = None,
+    ) -> Result[PlannedTransaction]:
+        """Add a planned transaction, or replace the one with planned_id.
+
+        Args:
+            account: Account ID, name or nickname
+            amount: Expected amount, negative for money going out
+            frequency: once, weekly, biweekly, monthly, quarterly or yearly
+            next_due: First occurrence; with only day_of_month, the next
+                date from today on that day
+            day_of_month: Day monthly, quarterly and yearly items fall on
+                (default: next_due's day); ignored for other frequencies
+            planned_id: ID or ID prefix of the item to replace
+            autogenerated: Set by recurring-transaction detection
+        """
+        frequency_result = parse_frequency(frequency)
+        if not frequency_result.success:
+            return frequency_result
+        parsed_frequency = frequency_result.data
+
+        if amount == 0:
+            return Fail("Planned amount cannot be zero")
+        if day_of_month is not None and not 1 <= day_of_month <= 31:
+            return Fail("Day of month must be between 1 and 31")
+
+        monthly = parsed_frequency in _MONTHS_PER_STEP
+        if next_due is None:
+            if day_of_month is None or not monthly:
+                return Fail("Give the first due date (or a day of month for monthly items)")
+            next_due = first_due_on_or_after(today or date.today(), day_of_month)
+        if monthly and day_of_month is None:
+            day_of_month = next_due.day
+        if not monthly:
+            day_of_month = None
+
+        account_result = await self.account_service.resolve_account(account)
+        if not account_result.success:
+            return account_result
+
+        existing = None
+        if planned_id is not None:
+            existing_result = await self.resolve(planned_id)
+            if not existing_result.success:
+                return existing_result
+            existing = existing_result.data
+
+        now = datetime.now(timezone.utc)
+        try:
+            planned = PlannedTransaction(
+                id=existing.id if existing else uuid4(),
+                account_id=account_result.data.id,
+                description=description,
+                amount=amount,
+                frequency=parsed_frequency,
+                day_of_month=day_of_month,
+                next_due=next_due,
+                autogenerated=autogenerated,
+                last_transaction_id=existing.last_transaction_id if existing else None,
+                created_at=existing.created_at if existing else now,
+                updated_at=now,
+            )
+        except ValueError as e:
+            return Fail(f"Invalid planned transaction: {e}")
+
+        save_result = await self.repository.upsert_planned_transaction(planned)
+        if not save_result.success:
+            return save_result
+        return Ok(planned)
+
+    async def remove(self, id_or_prefix: str) -> Result[PlannedTransaction]:
+        """Delete a planned transaction; returns the deleted item."""
+        resolve_result = await self.resolve(id_or_prefix)
+        if not resolve_result.success:
+            return resolve_result
+        delete_result = await self.repository.delete_planned_transaction(resolve_result.data.id)
+        if not delete_result.success:
+            return delete_result
+        return resolve_result
+
+    async def satisfy(
+        self, transactions: List[Transaction], dry_run: bool = False
+    ) -> Result[List[Dict[str, Any]]]:
+        """Mark planned occurrences that arrived among newly synced transactions.
+
+        Each transaction satisfies at most one occurrence. An item keeps
+        advancing while transactions match its next occurrence, so a sync
+        that covers several weeks catches up a weekly item. Returns one
+        entry per satisfied occurrence: planned_id, description, due,
+        transaction_id and next_due (None when a one-off item was removed).
+        """
+        if not transactions:
+            return Ok([])
+        list_result = await self.repository.list_planned_transactions()
+        if not list_result.success:
+            return list_result
+
+        used: set[UUID] = set()
+        satisfied: List[Dict[str, Any]] = []
+        now = datetime.now(timezone.utc)
+        for planned in list_result.data or []:
+            item: PlannedTransaction | None = planned
+            while item is not None:
+                due = item.next_due
+                candidates = [
+                    tx
+                    for tx in transactions
+                    if tx.id not in used and matches_planned(item, due, tx)
+                ]
+                if not candidates:
+                    break
+                match = min(
+                    candidates,
+                    key=lambda tx: (
+                        abs((tx.transaction_date - due).days),
+                        abs(tx.amount - item.amount),
+                    ),
+                )
+                used.add(match.id)
+                next_due = advance_due(due, item.frequency, item.day_of_month)
+                satisfied.append(
+                    {
+                        "planned_id": str(item.id),
+                        "description": item.description,
+                        "due": due,
+                        "transaction_id": str(match.id),
+                        "next_due": next_due,
+                    }
+                )
+                item = (
+                    item.model_copy(
+                        update={
+                            "next_due": next_due,
+                            "last_transaction_id": match.id,
+                            "updated_at": now,
+                        }
+                    )
+                    if next_due is not None
+                    else None
+                )
+
+            if dry_run or item == planned:
+                continue
+            if item is None:
+                write_result = await self.repository.delete_planned_transaction(planned.id)
+            else:
+                write_result = await self.repository.upsert_planned_transaction(item)
+            if not write_result.success:
+                return write_result
+        return Ok(satisfied)
+
+    async def upcoming(
+        self, days: int = DEFAULT_UPCOMING_DAYS, today: date | None = None
+    ) -> Result[Dict[str, Any]]:
+        """Planned occurrences due in the next `days` days, with projected balances.
+
+        Occurrences run in due order (overdue ones first, since they are still
+        expected). Each account starts from its balance today (see
+        get_balance_as_of) and each occurrence adds its amount, giving the
+        balance right after it. projected_negative flags asset accounts that
+        would go below zero; credit and loan accounts are negative normally.
+
+        Returns dict with:
+            - start_date / end_date
+            - items: planned_id, account_id, account, description, amount,
+              frequency, due, overdue, projected_balance (None when the
+              account's balance is unknown) and projected_negative
+            - accounts: account_id, account, balance and projected_balance,
+              for accounts with an occurrence in range
+        """
+        today = today or date.today()
+        end_date = today + timedelta(days=max(days, 0))
+
+        list_result = await self.repository.list_planned_transactions()
+        if not list_result.success:
+            return list_result
+        accounts_result = await self.repository.get_accounts()
+        if not accounts_result.success:
+            return accounts_result
+        accounts = {a.id: a for a in accounts_result.data or []}
+        directory = AccountDirectory(list(accounts.values()))
+
+        due_items = sorted(
+            (
+                (due, planned)
+                for planned in list_result.data or []
+                for due in occurrences(planned, end_date)
+            ),
+            key=lambda item: (item[0], item[1].description, str(item[1].id)),
+        )
+
+        balances: Dict[UUID, Decimal | None] = {}
+        starting: Dict[UUID, Decimal | None] = {}
+        for account_id in dict.fromkeys(planned.account_id for _, planned in due_items):
+            balance = None
+            account = accounts.get(account_id)
+            if account is not None and account.is_open_on(today):
+                balance_result = await self.repository.get_balance_as_of(account_id, today)
+                if not balance_result.success:
+                    return balance_result
+                raw = balance_result.data["balance"]
+                balance = None if raw is None else Decimal(raw)
+            starting[account_id] = balances[account_id] = balance
+
+        items = []
+        for due, planned in due_items:
+            balance = balances[planned.account_id]
+            if balance is not None:
+                balance += planned.amount
+                balances[planned.account_id] = balance
+            account = accounts.get(planned.account_id)
+            account_type = (account.account_type or "").lower() if account else ""
+            liability = account_type in LIABILITY_ACCOUNT_TYPES
+            items.append(
+                {
+                    "planned_id": str(planned.id),
+                    "account_id": str(planned.account_id),
+                    "account": directory.name_for(planned.account_id),
+                    "description": planned.description,
+                    "amount": planned.amount,
+                    "frequency": planned.frequency.value,
+                    "due": due,
+                    "overdue": due < today,
+                    "projected_balance": balance,
+                    "projected_negative": balance is not None and balance < 0 and not liability,
+                }
+            )
+
+        return Ok(
+            {
+                "start_date": today,
+                "end_date": end_date,
+                "items": items,
+                "accounts": [
+                    {
+                        "account_id": str(account_id),
+                        "account": directory.name_for(account_id),
+                        "balance": starting[account_id],
+                        "projected_balance": balances[account_id],
+                    }
+                    for account_id in starting
+                ],
+            }
+        )
@@ -6,24 +6,41 @@ import shutil
 import subprocess
 import tempfile
 from pathlib import Path
-from typing import Dict, Any
-
-import httpx
+from typing import Any, Callable, Dict
 
 from treeline.domain import Result
 
+HTTPX_MISSING = "Installing plugins from GitHub requires httpx, which is not installed"
+
 
 class PluginService:
     """Service for managing external plugins."""
 
-    def __init__(self, plugins_dir: Path):
+    def __init__(
+        self, plugins_dir: Path, http_client_factory: Callable[..., Any] | None = None
+    ):
         """Initialize plugin service.
 
         Args:
             plugins_dir: Directory where plugins are installed (e.g., ~/.treeline/plugins)
+            http_client_factory: Builds a blocking httpx client from a timeout and
+                client options; None in builds without httpx
         """
         self.plugins_dir = Path(plugins_dir)
         self.plugins_dir.mkdir(parents=True, exist_ok=True)
+        self.http_client_factory = http_client_factory
+
+    def _load_http(self):
+        """httpx and the client factory, or None without a factory.
+
+        httpx is imported lazily so builds without the network providers (see
+        treeline.features) can still load this module.
+        """
+        if self.http_client_factory is None:
+            return None
+        import httpx
+
+        return httpx, self.http_client_factory
 
     def create_plugin(
         self, name: str, target_dir: Path | None = None
@@ -262,6 +279,11 @@ class PluginService:
         Returns:
             Result with installation details
         """
+        http = self._load_http()
+        if http is None:
+            return Result(success=False, error=HTTPX_MISSING)
+        httpx, build_client = http
+
         # Parse GitHub URL
         parsed = self._parse_github_url(url)
         if not parsed:
@@ -281,13 +303,10 @@ class PluginService:
             api_url = f"https://api.github.com/repos/{owner}/{repo}/releases/latest"
 
         try:
-            with httpx.Client(timeout=30) as client:
+            with build_client(30) as client:
                 response = client.get(
                     api_url,
-                    headers={
-                        "Accept": "application/vnd.github.v3+json",
-                        "User-Agent": "Treeline-CLI",
-                    },
+                    headers={"Accept": "application/vnd.github.v3+json"},
                 )
                 if response.status_code == 404:
                     if version:
@@ -332,7 +351,7 @@ class PluginService:
             temp_path = Path(temp_dir)
 
             try:
-                with httpx.Client(timeout=60, follow_redirects=True) as client:
+                with build_client(60, follow_redirects=True) as client:
                     # Download manifest.json
                     manifest_path = temp_path / "manifest.json"
                     response = client.get(assets["manifest.json"])
@@ -484,6 +503,11 @@ class PluginService:
         Returns:
             Result with manifest data and release info
         """
+        http = self._load_http()
+        if http is None:
+            return Result(success=False, error=HTTPX_MISSING)
+        httpx, build_client = http
+
         # Parse GitHub URL
         parsed = self._parse_github_url(url)
         if not parsed:
@@ -503,13 +527,10 @@ class PluginService:
             api_url = f"https://api.github.com/repos/{owner}/{repo}/releases/latest"
 
         try:
-            with httpx.Client(timeout=30) as client:
+            with build_client(30) as client:
                 response = client.get(
                     api_url,
-                    headers={
-                        "Accept": "application/vnd.github.v3+json",
-                        "User-Agent": "Treeline-CLI",
-                    },
+                    headers={"Accept": "application/vnd.github.v3+json"},
                 )
                 if response.status_code == 404:
                     if version:
@@ -544,7 +565,7 @@ class PluginService:
 
         # Download manifest.json
         try:
-            with httpx.Client(timeout=30, follow_redirects=True) as client:
+            with build_client(30, follow_redirects=True) as client:
                 response = client.get(assets["manifest.json"])
                 response.raise_for_status()
                 manifest = response.json()
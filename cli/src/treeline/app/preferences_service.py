@@ -1,9 +1,11 @@
 """Service for managing user preferences including currency settings."""
 
+from datetime import date
 from decimal import Decimal
 
-from treeline.config import load_settings, save_settings
+from treeline.config import get_display_locale, load_settings, save_settings
 from treeline.domain import Fail, Ok, Result
+from treeline.locales import LOCALE_FORMATS, resolve_locale
 
 # Supported currencies with their symbols and locales
 SUPPORTED_CURRENCIES = {
@@ -86,50 +88,87 @@ def get_currency_symbol(currency: str) -> str:
     return currency  # Fallback to code itself
 
 
+def _locale_format(locale: str | None) -> dict:
+    tag = resolve_locale(locale or get_display_locale())
+    return LOCALE_FORMATS.get(tag) or LOCALE_FORMATS[tag.split("-", 1)[0]]
+
+
+def format_number(
+    amount: Decimal | float | int,
+    decimal_places: int = 2,
+    locale: str | None = None,
+) -> str:
+    """Format a number with the locale's thousands and decimal separators.
+
+    Args:
+        amount: Number to format
+        decimal_places: Number of decimal places (default: 2)
+        locale: Locale tag; defaults to the configured display locale
+
+    Returns:
+        Formatted number (e.g., "1,234,567.89", "1.234.567,89")
+    """
+    conventions = _locale_format(locale)
+    if isinstance(amount, float):
+        amount = Decimal(str(amount))
+    formatted = f"{abs(Decimal(amount)):,.{decimal_places}f}"
+    formatted = (
+        formatted.replace(",", "\0")
+        .replace(".", conventions["decimal"])
+        .replace("\0", conventions["thousands"])
+    )
+    return f"{conventions['minus']}{formatted}" if amount < 0 else formatted
+
+
 def format_currency(
     amount: Decimal | float | int,
     currency: str = DEFAULT_CURRENCY,
     show_symbol: bool = True,
     decimal_places: int = 2,
+    locale: str | None = None,
 ) -> str:
     """Format an amount in the specified currency.
 
+    Separators, minus sign and symbol placement follow the display locale
+    (settings.json display.locale, then LC_ALL), not the currency.
+
     Args:
         amount: Amount to format
         currency: Currency code (e.g., "USD", "EUR")
         show_symbol: Whether to include currency symbol
         decimal_places: Number of decimal places (default: 2)
+        locale: Locale tag; defaults to the configured display locale
 
     Returns:
-        Formatted currency string (e.g., "$1,234.56", "€1.234,56")
+        Formatted currency string (e.g., "$1,234.56", "1.234,56 €")
     """
-    currency = currency.upper()
-    symbol = get_currency_symbol(currency)
+    conventions = _locale_format(locale)
+    formatted = format_number(abs(amount), decimal_places, locale=locale)
 
-    # Convert to float for formatting
-    if isinstance(amount, Decimal):
-        amount = float(amount)
+    if show_symbol:
+        symbol = get_currency_symbol(currency)
+        if conventions["symbol_after"]:
+            formatted = f"{formatted} {symbol}"
+        else:
+            formatted = f"{symbol}{formatted}"
 
-    # Handle negative amounts
-    is_negative = amount < 0
-    abs_amount = abs(amount)
+    if amount < 0:
+        formatted = f"{conventions['minus']}{formatted}"
 
-    # Format the number with thousands separators
-    if decimal_places == 0:
-        formatted = f"{abs_amount:,.0f}"
-    else:
-        formatted = f"{abs_amount:,.{decimal_places}f}"
+    return formatted
 
-    # Build result
-    if show_symbol:
-        result = f"{symbol}{formatted}"
-    else:
-        result = formatted
 
-    if is_negative:
-        result = f"-{result}"
+def format_date(value: date, locale: str | None = None) -> str:
+    """Format a date for display in the configured locale.
 
-    return result
+    Args:
+        value: Date (or datetime) to format
+        locale: Locale tag; defaults to the configured display locale
+
+    Returns:
+        Formatted date (e.g., "03/31/2024", "31.03.2024")
+    """
+    return value.strftime(_locale_format(locale)["date"])
 
 
 def format_currency_compact(
@@ -0,0 +1,66 @@
+"""CSV rendering for the account register.
+
+Amounts are plain decimals (no currency symbols or grouping) so the file
+opens cleanly in a spreadsheet or accounting package.
+"""
+
+import csv
+import io
+from decimal import Decimal
+from typing import Any, Dict
+
+REGISTER_HEADER = ["Date", "Description", "Debit", "Credit", "Balance"]
+
+
+def _amount(value: Decimal | None) -> str:
+    return "" if value is None else format(value, "f")
+
+
+def render_register_csv(register: Dict[str, Any]) -> str:
+    """Render ReportService.register() output as CSV.
+
+    The first row after the header is the opening balance, the last is a
+    summary with total debits, total credits and the net change (in the
+    Balance column).
+    """
+    buffer = io.StringIO()
+    writer = csv.writer(buffer, lineterminator="\n")
+    writer.writerow(REGISTER_HEADER)
+
+    opening_label = "Opening balance"
+    if register["estimated"]:
+        opening_label += " (estimated)"
+    elif register["opening_balance"] is None:
+        opening_label += " (unknown)"
+    writer.writerow(
+        [
+            register["start_date"].isoformat(),
+            opening_label,
+            "",
+            "",
+            _amount(register["opening_balance"]),
+        ]
+    )
+
+    for row in register["rows"]:
+        writer.writerow(
+            [
+                row["date"].isoformat(),
+                row["description"] or "",
+                _amount(row["debit"]),
+                _amount(row["credit"]),
+                _amount(row["balance"]),
+            ]
+        )
+
+    totals = register["totals"]
+    writer.writerow(
+        [
+            register["end_date"].isoformat(),
+            "Total (net change in Balance)",
+            _amount(totals["debits"]),
+            _amount(totals["credits"]),
+            _amount(totals["net"]),
+        ]
+    )
+    return buffer.getvalue()
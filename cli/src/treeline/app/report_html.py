@@ -0,0 +1,334 @@
+"""Self-contained HTML rendering for the monthly report and the year in review.
+
+The output has no scripts or external assets (inline CSS only, bar charts
+are sized divs) so it can be attached to an email as-is.
+"""
+
+from datetime import date
+from decimal import Decimal
+from html import escape
+from typing import Any, Dict, List
+
+from treeline.app.preferences_service import format_currency, format_date, format_number
+
+STYLE = """
+body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; color: #1f2933;
+       max-width: 720px; margin: 24px auto; padding: 0 16px; }
+h1 { font-size: 22px; margin-bottom: 4px; }
+h2 { font-size: 16px; margin: 28px 0 8px; border-bottom: 1px solid #e4e7eb; padding-bottom: 4px; }
+.muted { color: #7b8794; font-size: 13px; }
+.cards { display: flex; flex-wrap: wrap; gap: 12px; margin-top: 16px; }
+.card { flex: 1 1 150px; border: 1px solid #e4e7eb; border-radius: 6px; padding: 10px 12px; }
+.card .label { color: #7b8794; font-size: 12px; text-transform: uppercase; }
+.card .value { font-size: 18px; font-weight: 600; margin-top: 4px; }
+table { width: 100%; border-collapse: collapse; font-size: 14px; }
+td, th { padding: 5px 6px; text-align: left; border-bottom: 1px solid #f0f2f4; }
+th { color: #7b8794; font-weight: 500; font-size: 12px; }
+.num { text-align: right; white-space: nowrap; font-variant-numeric: tabular-nums; }
+.bar-cell { width: 40%; }
+.bar { background: #3e7bfa; height: 10px; border-radius: 2px; }
+.spark { display: flex; align-items: flex-end; gap: 4px; height: 80px; margin-top: 8px; }
+.spark div { flex: 1; background: #3e7bfa; border-radius: 2px 2px 0 0; min-height: 1px; }
+.spark-labels { display: flex; gap: 4px; font-size: 11px; color: #7b8794; }
+.spark-labels span { flex: 1; text-align: center; }
+.bar.negative-bar { background: #c0392b; }
+.positive { color: #1d8348; }
+.negative { color: #c0392b; }
+""".strip()
+
+
+def _money(amount: Decimal | None, currency: str, locale: str | None) -> str:
+    if amount is None:
+        return "-"
+    return escape(format_currency(amount, currency, locale=locale))
+
+
+def _signed_class(amount: Decimal | None) -> str:
+    if amount is None or amount == 0:
+        return ""
+    return "positive" if amount > 0 else "negative"
+
+
+def _card(label: str, value: str, css_class: str = "") -> str:
+    return (
+        f'<div class="card"><div class="label">{escape(label)}</div>'
+        f'<div class="{f"value {css_class}".strip()}">{value}</div></div>'
+    )
+
+
+def _date(value: date, locale: str | None) -> str:
+    return escape(format_date(value, locale))
+
+
+def _document(title: str, body: List[str]) -> str:
+    return "\n".join(
+        [
+            "<!DOCTYPE html>",
+            '<html lang="en">',
+            "<head>",
+            '<meta charset="utf-8">',
+            f"<title>{title}</title>",
+            f"<style>\n{STYLE}\n</style>",
+            "</head>",
+            "<body>",
+            *body,
+            "</body>",
+            "</html>",
+            "",
+        ]
+    )
+
+
+def render_monthly_html(
+    report: Dict[str, Any], currency: str = "USD", locale: str | None = None
+) -> str:
+    """Render ReportService.monthly() output as a standalone HTML document."""
+    net_worth = report["net_worth"]
+    rate = report["savings_rate"]
+
+    cards = "".join(
+        [
+            _card(
+                "Net worth change",
+                _money(net_worth["change"], currency, locale),
+                _signed_class(net_worth["change"]),
+            ),
+            _card("Income", _money(report["income"], currency, locale)),
+            _card("Expenses", _money(report["total_expenses"], currency, locale)),
+            _card(
+                "Net savings",
+                _money(report["net_savings"], currency, locale),
+                _signed_class(report["net_savings"]),
+            ),
+            _card("Savings rate", "-" if rate is None else f"{format_number(rate, 1, locale)}%"),
+        ]
+    )
+
+    net_worth_note = (
+        f"{_money(net_worth['start'], currency, locale)} on {_date(net_worth['start_date'], locale)}"
+        f" &rarr; {_money(net_worth['end'], currency, locale)} on {_date(net_worth['end_date'], locale)}"
+    )
+    if net_worth["unknown_accounts"]:
+        names = ", ".join(escape(name) for name in net_worth["unknown_accounts"])
+        net_worth_note += f". Not included (no balance data): {names}"
+
+    largest_tag = max((entry["amount"] for entry in report["spending_by_tag"]), default=0)
+    tag_rows = []
+    for entry in report["spending_by_tag"]:
+        width = (entry["amount"] / largest_tag * 100) if largest_tag > 0 else 0
+        share = "" if entry["share"] is None else f"{format_number(entry['share'], 1, locale)}%"
+        tag_rows.append(
+            f"<tr><td>{escape(entry['tag'])}</td>"
+            f'<td class="bar-cell"><div class="bar" style="width: {max(width, 0):.1f}%"></div></td>'
+            f'<td class="num">{_money(entry["amount"], currency, locale)}</td>'
+            f'<td class="num muted">{share}</td></tr>'
+        )
+
+    merchant_rows = [
+        f"<tr><td>{escape(entry['merchant'])}</td>"
+        f'<td class="num muted">{entry["count"]}</td>'
+        f'<td class="num">{_money(entry["amount"], currency, locale)}</td></tr>'
+        for entry in report["top_merchants"]
+    ]
+
+    large_rows = [
+        f"<tr><td>{_date(entry['date'], locale)}</td>"
+        f"<td>{escape(entry['description'] or '')}</td>"
+        f"<td class=\"muted\">{escape(entry['account'] or '')}</td>"
+        f'<td class="num {_signed_class(entry["amount"])}">{_money(entry["amount"], currency, locale)}</td></tr>'
+        for entry in report["large_transactions"]
+    ]
+
+    def section(title: str, header: str, rows: list[str]) -> str:
+        if not rows:
+            return f'<h2>{title}</h2><p class="muted">Nothing this month.</p>'
+        return f"<h2>{title}</h2><table><tr>{header}</tr>{''.join(rows)}</table>"
+
+    month = escape(report["month"])
+    period = f"{_date(report['start_date'], locale)} to {_date(report['end_date'], locale)}"
+
+    return _document(
+        f"Treeline monthly report {month}",
+        [
+            f"<h1>Monthly report: {month}</h1>",
+            f'<div class="muted">{period}</div>',
+            f'<div class="cards">{cards}</div>',
+            f'<p class="muted">Net worth: {net_worth_note}</p>',
+            section(
+                "Spending by tag",
+                '<th>Tag</th><th></th><th class="num">Amount</th><th class="num">Share</th>',
+                tag_rows,
+            ),
+            section(
+                "Top merchants",
+                '<th>Merchant</th><th class="num">Transactions</th><th class="num">Spent</th>',
+                merchant_rows,
+            ),
+            section(
+                "Large transactions",
+                '<th>Date</th><th>Description</th><th>Account</th><th class="num">Amount</th>',
+                large_rows,
+            ),
+            '<p class="muted">Transfers and credit card payments are excluded. Generated by Treeline.</p>',
+        ],
+    )
+
+
+def render_year_review_html(
+    review: Dict[str, Any], currency: str = "USD", locale: str | None = None
+) -> str:
+    """Render YearReviewService.review() output as a standalone HTML document."""
+    year = review["year"]
+    title = f"Treeline year in review {year}"
+    period = f"{_date(review['start_date'], locale)} to {_date(review['end_date'], locale)}"
+    if not review["enough_data"]:
+        return _document(
+            title,
+            [
+                f"<h1>{year} in review</h1>",
+                f'<div class="muted">{period}</div>',
+                f"<p>{escape(review['message'])}</p>",
+            ],
+        )
+
+    net_worth = review["net_worth"]
+    rate = review["savings_rate"]
+    subscriptions = review["subscriptions"]
+    cards = "".join(
+        [
+            _card("Income", _money(review["income"], currency, locale)),
+            _card("Expenses", _money(review["total_expenses"], currency, locale)),
+            _card(
+                "Net savings",
+                _money(review["net_savings"], currency, locale),
+                _signed_class(review["net_savings"]),
+            ),
+            _card("Savings rate", "-" if rate is None else f"{format_number(rate, 1, locale)}%"),
+            _card(
+                "Net worth change",
+                _money(net_worth["change"], currency, locale),
+                _signed_class(net_worth["change"]),
+            ),
+            _card("Subscriptions", _money(subscriptions["total"], currency, locale)),
+        ]
+    )
+
+    # Monthly spending as a row of bars, tallest month full height
+    months = review["months"]
+    tallest = max((m["total_expenses"] for m in months), default=0)
+    spark = "".join(
+        f'<div title="{escape(m["month"])}: {_money(m["total_expenses"], currency, locale)}" '
+        f'style="height: {(m["total_expenses"] / tallest * 100) if tallest > 0 else 0:.1f}%"></div>'
+        for m in months
+    )
+    spark_labels = "".join(f"<span>{escape(m['month'][5:])}</span>" for m in months)
+    most_expensive = review["most_expensive_month"]
+    month_note = (
+        ""
+        if most_expensive is None
+        else f'<p class="muted">Most expensive month: {escape(most_expensive["month"])} '
+        f'({_money(most_expensive["total_expenses"], currency, locale)})</p>'
+    )
+
+    largest_change = max((abs(q["change"]) for q in review["quarters"]), default=0)
+    quarter_rows = []
+    for quarter in review["quarters"]:
+        width = (abs(quarter["change"]) / largest_change * 100) if largest_change > 0 else 0
+        bar_class = "bar negative-bar" if quarter["change"] < 0 else "bar"
+        quarter_rows.append(
+            f"<tr><td>{quarter['quarter']}</td>"
+            f'<td class="bar-cell"><div class="{bar_class}" style="width: {width:.1f}%"></div></td>'
+            f'<td class="num">{_money(quarter["end"], currency, locale)}</td>'
+            f'<td class="num {_signed_class(quarter["change"])}">'
+            f"{_money(quarter['change'], currency, locale)}</td></tr>"
+        )
+
+    largest_tag = max((entry["amount"] for entry in review["top_tags"]), default=0)
+    tag_rows = []
+    for entry in review["top_tags"]:
+        width = (entry["amount"] / largest_tag * 100) if largest_tag > 0 else 0
+        share = "" if entry["share"] is None else f"{format_number(entry['share'], 1, locale)}%"
+        tag_rows.append(
+            f"<tr><td>{escape(entry['tag'])}</td>"
+            f'<td class="bar-cell"><div class="bar" style="width: {max(width, 0):.1f}%"></div></td>'
+            f'<td class="num">{_money(entry["amount"], currency, locale)}</td>'
+            f'<td class="num muted">{share}</td></tr>'
+        )
+
+    merchant_rows = [
+        f"<tr><td>{escape(entry['merchant'])}</td>"
+        f'<td class="num muted">{entry["count"]}</td>'
+        f'<td class="num">{_money(entry["amount"], currency, locale)}</td></tr>'
+        for entry in review["top_merchants"]
+    ]
+
+    subscription_rows = [
+        f"<tr><td>{escape(entry['merchant'])}</td>"
+        f'<td class="num muted">{entry["months"]}</td>'
+        f'<td class="num">{_money(entry["typical_amount"], currency, locale)}</td>'
+        f'<td class="num">{_money(entry["total"], currency, locale)}</td></tr>'
+        for entry in subscriptions["charges"]
+    ]
+
+    highlights = []
+    biggest = review["biggest_expense"]
+    if biggest is not None:
+        highlights.append(
+            f"<li>Biggest expense: {escape(biggest['description'] or '')} "
+            f"({_money(-biggest['amount'], currency, locale)}, {_date(biggest['date'], locale)})</li>"
+        )
+    labels = {
+        "most_spending": ("Most spending", "total_expenses"),
+        "largest_increase": ("Grew the most", "change"),
+        "largest_decrease": ("Shrank the most", "change"),
+    }
+    for key, (label, field) in labels.items():
+        entry = review["highlights"][key]
+        if entry is not None:
+            highlights.append(
+                f"<li>{label}: {escape(entry['account'])} "
+                f"({_money(entry[field], currency, locale)})</li>"
+            )
+
+    def section(title: str, header: str, rows: list[str]) -> str:
+        if not rows:
+            return f'<h2>{title}</h2><p class="muted">Nothing this year.</p>'
+        return f"<h2>{title}</h2><table><tr>{header}</tr>{''.join(rows)}</table>"
+
+    heading = f"{year} in review" + (" (so far)" if review["year_to_date"] else "")
+    return _document(
+        title,
+        [
+            f"<h1>{heading}</h1>",
+            f'<div class="muted">{period}</div>',
+            f'<div class="cards">{cards}</div>',
+            "<h2>Spending by month</h2>",
+            f'<div class="spark">{spark}</div>',
+            f'<div class="spark-labels">{spark_labels}</div>',
+            month_note,
+            section(
+                "Net worth by quarter",
+                '<th>Quarter</th><th></th><th class="num">End</th><th class="num">Change</th>',
+                quarter_rows,
+            ),
+            section(
+                "Top tags",
+                '<th>Tag</th><th></th><th class="num">Amount</th><th class="num">Share</th>',
+                tag_rows,
+            ),
+            section(
+                "Top merchants",
+                '<th>Merchant</th><th class="num">Transactions</th><th class="num">Spent</th>',
+                merchant_rows,
+            ),
+            section(
+                "Subscriptions and bills",
+                '<th>Merchant</th><th class="num">Months</th><th class="num">Typical</th>'
+                '<th class="num">Total</th>',
+                subscription_rows,
+            ),
+            "<h2>Highlights</h2>",
+            f"<ul>{''.join(highlights)}</ul>" if highlights else '<p class="muted">None.</p>',
+            '<p class="muted">Transfers and credit card payments are excluded. Generated by Treeline.</p>',
+        ],
+    )
@@ -0,0 +1,1035 @@
+"""Service for financial reports."""
+
+import re
+from collections import Counter
+from datetime import date, datetime, time, timedelta
+from decimal import Decimal
+from typing import Any, Dict, Iterable, List, Tuple
+from uuid import UUID
+
+from treeline.abstractions import Repository
+from treeline.app.account_service import AccountDirectory
+from treeline.app.classifier import EXPENSE, INCOME, TRANSFER, Classifier
+from treeline.domain import Account, Fail, Ok, Result, TransactionStatus, normalize_tags
+from treeline.smoothing import Smoothing, downsample, linear_trend, smooth
+
+
+def _month_key(day: date) -> str:
+    return f"{day.year:04d}-{day.month:02d}"
+
+
+def _months_between(start: date, end: date) -> List[str]:
+    months = []
+    year, month = start.year, start.month
+    while (year, month) <= (end.year, end.month):
+        months.append(f"{year:04d}-{month:02d}")
+        year, month = (year + 1, 1) if month == 12 else (year, month + 1)
+    return months
+
+
+def _month_bounds(year: int, month: int) -> tuple[date, date]:
+    start = date(year, month, 1)
+    next_month = date(year + 1, 1, 1) if month == 12 else date(year, month + 1, 1)
+    return start, next_month - timedelta(days=1)
+
+
+def _savings_rate(income: Decimal, net: Decimal) -> Decimal | None:
+    if income <= 0:
+        return None
+    return (net / income * 100).quantize(Decimal("0.1"))
+
+
+def _comparison(current: Decimal, prior: Decimal) -> Dict[str, Any]:
+    """Current vs prior amount. Without a prior amount there is no percent
+    change; "new" marks amounts that went from zero to something."""
+    return {
+        "current": current,
+        "prior": prior,
+        "change": current - prior,
+        "percent_change": ((current - prior) / abs(prior) * 100).quantize(Decimal("0.1"))
+        if prior != 0
+        else None,
+        "new": prior == 0 and current != 0,
+    }
+
+
+def spending_by_tag(summary: Dict[str, Any]) -> List[Dict[str, Any]]:
+    """A cashflow summary's expenses as [{tag, amount, share}], largest first.
+
+    share is the percent of total_expenses (None when nothing was spent).
+    """
+    total_expenses = summary["total_expenses"]
+    return [
+        {
+            "tag": tag,
+            "amount": amount,
+            "share": (amount / total_expenses * 100).quantize(Decimal("0.1"))
+            if total_expenses > 0
+            else None,
+        }
+        for tag, amount in sorted(summary["expenses"].items(), key=lambda item: -item[1])
+    ]
+
+
+def merchant_key(description: str | None) -> str:
+    """Group descriptions that name the same merchant.
+
+    Case, punctuation and store/terminal numbers are ignored, so
+    "STARBUCKS #1234" and "Starbucks 5678" are one merchant.
+    """
+    text = re.sub(r"#\s*\d+|\b\d{3,}\b", " ", (description or "").lower())
+    text = re.sub(r"[^\w]+", " ", text)
+    return " ".join(text.split())
+
+
+def _days(start: date, end: date) -> Iterable[date]:
+    day = start
+    while day <= end:
+        yield day
+        day += timedelta(days=1)
+
+
+def _warm_up_start(start_date: date, smoothing: Smoothing | None) -> date:
+    """First day a series is read from: enough days before start_date to fill the window."""
+    if smoothing is None:
+        return start_date
+    return start_date - timedelta(days=smoothing.window_days - 1)
+
+
+def _series(
+    daily: List[Tuple[date, Decimal]],
+    key: str,
+    start_date: date,
+    smoothing: Smoothing | None,
+    interval: str,
+    week_start: int,
+    summed: bool = False,
+) -> Dict[str, Any]:
+    """A daily series as report output: smoothed, trimmed to start_date, downsampled.
+
+    Smoothing runs over every day in daily, including the warm-up days before
+    start_date, and before downsampling. Levels such as balances are taken as
+    of each week's or month's last day; flows (summed) are totalled.
+
+    Returns dict with:
+        - smoothing: {window_days, kind}, or None
+        - interval: "day", "week" or "month"
+        - series: [{date, <key>, smoothed?}]
+        - trend: linear_trend of the daily values from start_date (slope per
+          month, projected 30 and 90 days out), or None
+    """
+    smoothed = smooth([value for _, value in daily], smoothing) if smoothing else None
+    points = []
+    for i, (day, value) in enumerate(daily):
+        if day < start_date:
+            continue
+        point: Dict[str, Any] = {"date": day, key: value}
+        if smoothed is not None:
+            point["smoothed"] = smoothed[i]
+        points.append(point)
+
+    return {
+        "smoothing": smoothing.to_json() if smoothing else None,
+        "interval": interval,
+        "series": downsample(
+            points, interval, sum_keys=(key,) if summed else (), week_start=week_start
+        ),
+        "trend": linear_trend([(point["date"], point[key]) for point in points]),
+    }
+
+
+def _preceding_period(start: date, end: date) -> tuple[date, date]:
+    """The period of the same length ending the day before start."""
+    prior_end = start - timedelta(days=1)
+    return prior_end - (end - start), prior_end
+
+
+# Defaults for the monthly summary
+TOP_MERCHANTS = 10
+LARGE_TRANSACTIONS = 5
+
+# Default for the merchants report
+MERCHANTS_REPORT_TOP = 20
+
+# Defaults for the digest
+TOP_CATEGORIES = 3
+BALANCE_CHANGE_PERCENT = Decimal("10")
+
+# A merchant charged in this many months, once a month and for about the
+# same amount (within the tolerance of the median), is a recurring expense
+RECURRING_MIN_MONTHS = 3
+RECURRING_AMOUNT_TOLERANCE = Decimal("0.05")
+
+
+class ReportService:
+    """Service for financial reports."""
+
+    def __init__(self, repository: Repository, classifier: Classifier | None = None):
+        self.repository = repository
+        self.classifier = classifier or Classifier()
+
+    async def cashflow(
+        self,
+        start_date: date,
+        end_date: date,
+        include_pending: bool = True,
+        exclude_tags: Iterable[str] = (),
+        smoothing: Smoothing | None = None,
+        interval: str | None = None,
+        week_start: int = 0,
+    ) -> Result[Dict[str, Any]]:
+        """Build a monthly cash-flow statement for [start_date, end_date].
+
+        Split parents are skipped in favour of their children so amounts are
+        not counted twice. Expenses are reported as positive amounts. Pending
+        transactions count unless include_pending is False. Transactions
+        with any of exclude_tags are left out.
+
+        With an interval or smoothing, the result also has the net flow
+        (income minus expenses) as a series; see _series.
+
+        Returns dict with:
+            - start_date / end_date: The period covered
+            - months: One entry per calendar month, in order
+            - totals: The same fields summed over the period
+            Each entry has income, expenses (by category), total_expenses,
+            net_savings and savings_rate (percent, None without income).
+            - series: [{date, net, smoothed?}] per day, week or month, when asked for
+        """
+        first_day = _warm_up_start(start_date, smoothing)
+        tx_result = await self.repository.get_transactions_in_range(first_day, end_date)
+        if not tx_result.success:
+            return tx_result
+
+        accounts_result = await self.repository.get_accounts()
+        if not accounts_result.success:
+            return accounts_result
+        accounts = accounts_result.data or []
+        account_types = {a.id: a.account_type for a in accounts}
+
+        transactions = tx_result.data or []
+
+        buckets: Dict[str, Dict[str, Any]] = {
+            month: {"income": Decimal("0.00"), "expenses": {}}
+            for month in _months_between(start_date, end_date)
+        }
+        totals: Dict[str, Any] = {"income": Decimal("0.00"), "expenses": {}}
+        account_expenses: Dict[UUID, Decimal] = {}
+        daily_net: Dict[date, Decimal] = {}
+        excluded = set(normalize_tags(exclude_tags))
+
+        for tx in transactions:
+            if excluded.intersection(tx.tags):
+                continue
+            if not include_pending and tx.status == TransactionStatus.PENDING:
+                continue
+
+            kind = self.classifier.classify(tx, account_types.get(tx.account_id))
+            if kind in (INCOME, EXPENSE):
+                daily_net[tx.transaction_date] = (
+                    daily_net.get(tx.transaction_date, Decimal("0.00")) + tx.amount
+                )
+            if tx.transaction_date < start_date:
+                # Only read to warm up the rolling average
+                continue
+            bucket = buckets[_month_key(tx.transaction_date)]
+            if kind == INCOME:
+                bucket["income"] += tx.amount
+                totals["income"] += tx.amount
+            elif kind == EXPENSE:
+                category = self.classifier.expense_category(tx)
+                for target in (bucket, totals):
+                    target["expenses"][category] = (
+                        target["expenses"].get(category, Decimal("0.00")) - tx.amount
+                    )
+                account_expenses[tx.account_id] = (
+                    account_expenses.get(tx.account_id, Decimal("0.00")) - tx.amount
+                )
+
+        data = {
+            "start_date": start_date,
+            "end_date": end_date,
+            "months": [
+                self._summarize(bucket, month=month) for month, bucket in buckets.items()
+            ],
+            "totals": self._summarize(totals),
+            "accounts": self._account_averages(
+                accounts, account_expenses, list(buckets), start_date, end_date
+            ),
+        }
+        if interval is not None or smoothing is not None:
+            daily = [
+                (day, daily_net.get(day, Decimal("0.00")))
+                for day in _days(first_day, end_date)
+            ]
+            data.update(
+                _series(
+                    daily, "net", start_date, smoothing, interval or "day", week_start, summed=True
+                )
+            )
+        return Ok(data)
+
+    async def balance_history(
+        self,
+        account_id: UUID,
+        start_date: date,
+        end_date: date,
+        smoothing: Smoothing | None = None,
+        interval: str = "day",
+        week_start: int = 0,
+    ) -> Result[Dict[str, Any]]:
+        """End-of-day balances of an account over [start_date, end_date].
+
+        The first day's balance comes from the repository's balance_as_of.
+        After that, a day with balance snapshots takes the latest one and any
+        other day adds its transactions to the day before. The balance is
+        zero while the account isn't open; days before anything is known
+        about it are left out.
+
+        Returns dict with:
+            - account_id, account, currency, start_date / end_date
+            - smoothing, interval, trend: See _series
+            - series: [{date, balance, smoothed?}] per day, week or month
+        """
+        account_result = await self.repository.get_account_by_id(account_id)
+        if not account_result.success:
+            return Fail(f"Account not found: {account_id}")
+        account = account_result.data
+
+        first_day = _warm_up_start(start_date, smoothing)
+        opening_result = await self._balance_as_of(account, first_day)
+        if not opening_result.success:
+            return opening_result
+
+        snapshots_result = await self.repository.get_balance_snapshots(account_id)
+        if not snapshots_result.success:
+            return snapshots_result
+        snapshots: Dict[date, Decimal] = {}
+        for snapshot in sorted(snapshots_result.data or [], key=lambda s: s.snapshot_time):
+            snapshots[snapshot.snapshot_time.date()] = snapshot.balance
+
+        tx_result = await self.repository.get_transactions_by_account(
+            account_id, leaves_only=True
+        )
+        if not tx_result.success:
+            return tx_result
+        daily_totals: Dict[date, Decimal] = {}
+        for tx in tx_result.data or []:
+            daily_totals[tx.transaction_date] = (
+                daily_totals.get(tx.transaction_date, Decimal("0.00")) + tx.amount
+            )
+
+        daily: List[Tuple[date, Decimal]] = []
+        balance = opening_result.data
+        for day in _days(first_day, end_date):
+            if not account.is_open_on(day):
+                balance = Decimal("0.00")
+            elif day in snapshots:
+                balance = snapshots[day]
+            elif balance is not None and day != first_day:
+                balance += daily_totals.get(day, Decimal("0.00"))
+            if balance is not None:
+                daily.append((day, balance))
+
+        return Ok(
+            {
+                "account_id": str(account.id),
+                "account": account.nickname or account.name,
+                "currency": account.currency,
+                "start_date": start_date,
+                "end_date": end_date,
+                **_series(daily, "balance", start_date, smoothing, interval, week_start),
+            }
+        )
+
+    @staticmethod
+    def _account_averages(
+        accounts: List[Account],
+        expenses: Dict[UUID, Decimal],
+        months: List[str],
+        start_date: date,
+        end_date: date,
+    ) -> List[Dict[str, Any]]:
+        """Average monthly expenses per account over the months it was open.
+
+        An account opened or closed partway through the period is averaged
+        over the months it was open for at least a day, not the whole period.
+        """
+        entries = []
+        for account in accounts:
+            total = expenses.get(account.id)
+            if not total:
+                continue
+            months_open = 0
+            for month in months:
+                first, last = _month_bounds(int(month[:4]), int(month[5:7]))
+                first, last = max(first, start_date), min(last, end_date)
+                opened = account.opened_at is None or account.opened_at <= last
+                not_closed = account.closed_at is None or account.closed_at >= first
+                if opened and not_closed:
+                    months_open += 1
+            entries.append(
+                {
+                    "account_id": str(account.id),
+                    "account": account.nickname or account.name,
+                    "total_expenses": total,
+                    "months_open": months_open,
+                    # Spending outside the open dates leaves no open months
+                    "average_monthly_expenses": (total / months_open).quantize(Decimal("0.01"))
+                    if months_open
+                    else None,
+                }
+            )
+        return sorted(entries, key=lambda e: (-e["total_expenses"], e["account"]))
+
+    async def _balance_as_of(self, account: Account, as_of: date) -> Result[Decimal | None]:
+        """End-of-day balance; zero before opened_at and after closed_at.
+
+        None when the balance is unknown (no snapshots to anchor on).
+        """
+        if not account.is_open_on(as_of):
+            return Ok(Decimal("0.00"))
+        balance_result = await self.repository.get_balance_as_of(account.id, as_of)
+        if not balance_result.success:
+            return balance_result
+        balance = balance_result.data["balance"]
+        return Ok(None if balance is None else Decimal(balance))
+
+    async def _balances_as_of(
+        self, accounts: List[Account], dates: List[date]
+    ) -> Result[Dict[UUID, Dict[date, Decimal | None]]]:
+        """_balance_as_of for several accounts and dates, in one repository call."""
+        balances_result = await self.repository.get_balances_as_of(
+            [account.id for account in accounts], dates
+        )
+        if not balances_result.success:
+            return balances_result
+        balances: Dict[UUID, Dict[date, Decimal | None]] = {}
+        for account in accounts:
+            balances[account.id] = {}
+            for as_of in dates:
+                balance = balances_result.data[account.id][as_of]["balance"]
+                if not account.is_open_on(as_of):
+                    balance = Decimal("0.00")
+                balances[account.id][as_of] = None if balance is None else Decimal(balance)
+        return Ok(balances)
+
+    async def monthly(
+        self,
+        year: int,
+        month: int,
+        top_merchants: int = TOP_MERCHANTS,
+        large_transactions: int = LARGE_TRANSACTIONS,
+        exclude_tags: Iterable[str] = (),
+    ) -> Result[Dict[str, Any]]:
+        """Build a one-month summary suitable for sharing.
+
+        Income, expenses and spending by tag come from cashflow() so the
+        numbers match `tl report cashflow`. Merchants are transaction
+        descriptions; large transactions are the biggest non-transfer
+        amounts. Net worth is the sum of account balances at the end of the
+        previous month and at the end of this one (or today, mid-month);
+        accounts with no known balance are listed and left out. Transactions
+        with any of exclude_tags are left out of everything but net worth.
+
+        Returns dict with:
+            - month, start_date, end_date
+            - net_worth: start, end, change, start_date, end_date, unknown_accounts
+            - income, total_expenses, net_savings, savings_rate
+            - spending_by_tag: [{tag, amount, share}], largest first (share in percent)
+            - top_merchants: [{merchant, amount, count}], largest first
+            - large_transactions: [{date, description, account, amount, tags}]
+        """
+        start_date, end_date = _month_bounds(year, month)
+
+        cashflow_result = await self.cashflow(start_date, end_date, exclude_tags=exclude_tags)
+        if not cashflow_result.success:
+            return cashflow_result
+        summary = cashflow_result.data["totals"]
+
+        accounts_result = await self.repository.get_accounts()
+        if not accounts_result.success:
+            return accounts_result
+        accounts = accounts_result.data or []
+        account_types = {a.id: a.account_type for a in accounts}
+        directory = AccountDirectory(accounts)
+
+        net_worth_end = min(end_date, date.today())
+        net_worth_result = await self.net_worth_change(
+            accounts, start_date - timedelta(days=1), net_worth_end
+        )
+        if not net_worth_result.success:
+            return net_worth_result
+
+        tx_result = await self.repository.get_transactions_in_range(start_date, end_date)
+        if not tx_result.success:
+            return tx_result
+        transactions = tx_result.data or []
+
+        merchants: Dict[str, Dict[str, Any]] = {}
+        notable = []
+        excluded = set(normalize_tags(exclude_tags))
+        for tx in transactions:
+            if excluded.intersection(tx.tags):
+                continue
+            kind = self.classifier.classify(tx, account_types.get(tx.account_id))
+            if kind == TRANSFER:
+                continue
+            notable.append(tx)
+            if kind == EXPENSE:
+                name = (tx.description or "").strip() or "(no description)"
+                entry = merchants.setdefault(
+                    name, {"merchant": name, "amount": Decimal("0.00"), "count": 0}
+                )
+                entry["amount"] -= tx.amount
+                entry["count"] += 1
+
+        notable.sort(key=lambda tx: (-abs(tx.amount), tx.transaction_date))
+        return Ok(
+            {
+                "month": _month_key(start_date),
+                "start_date": start_date,
+                "end_date": end_date,
+                "net_worth": net_worth_result.data,
+                "income": summary["income"],
+                "total_expenses": summary["total_expenses"],
+                "net_savings": summary["net_savings"],
+                "savings_rate": summary["savings_rate"],
+                "spending_by_tag": spending_by_tag(summary),
+                "top_merchants": sorted(
+                    merchants.values(), key=lambda m: (-m["amount"], m["merchant"])
+                )[:top_merchants],
+                "large_transactions": [
+                    {
+                        "date": tx.transaction_date,
+                        "description": tx.description,
+                        "account": directory.name_for(tx.account_id),
+                        "amount": tx.amount,
+                        "tags": list(tx.tags),
+                    }
+                    for tx in notable[:large_transactions]
+                ],
+            }
+        )
+
+    async def merchants(
+        self,
+        start_date: date,
+        end_date: date,
+        top: int = MERCHANTS_REPORT_TOP,
+        exclude_tags: Iterable[str] = (),
+    ) -> Result[Dict[str, Any]]:
+        """Spending per merchant, with the change from the preceding period.
+
+        Merchants are descriptions grouped by merchant_key; each is labelled
+        with its most common spelling in the period. Only expenses count
+        (the Classifier leaves out income and transfers), and split parents
+        are skipped in favour of their children. The preceding period has
+        the same number of days and ends the day before start_date.
+        Transactions with any of exclude_tags are left out of both periods.
+
+        Returns dict with:
+            - start_date / end_date, prior_start_date / prior_end_date
+            - total_expenses: Spending in the period across all merchants
+            - merchants: The top merchants by amount, each with merchant,
+              amount, count, average, prior_amount, change and
+              percent_change (None when there was no prior spending) and new
+        """
+        prior_start, prior_end = _preceding_period(start_date, end_date)
+
+        accounts_result = await self.repository.get_accounts()
+        if not accounts_result.success:
+            return accounts_result
+        account_types = {a.id: a.account_type for a in accounts_result.data or []}
+
+        tx_result = await self.repository.get_transactions_in_range(prior_start, end_date)
+        if not tx_result.success:
+            return tx_result
+        transactions = tx_result.data or []
+
+        current: Dict[str, Dict[str, Any]] = {}
+        prior: Dict[str, Decimal] = {}
+        excluded = set(normalize_tags(exclude_tags))
+        for tx in transactions:
+            if excluded.intersection(tx.tags):
+                continue
+            if self.classifier.classify(tx, account_types.get(tx.account_id)) != EXPENSE:
+                continue
+            key = merchant_key(tx.description)
+            if tx.transaction_date < start_date:
+                prior[key] = prior.get(key, Decimal("0.00")) - tx.amount
+                continue
+            entry = current.setdefault(
+                key, {"amount": Decimal("0.00"), "count": 0, "names": Counter()}
+            )
+            entry["amount"] -= tx.amount
+            entry["count"] += 1
+            entry["names"][(tx.description or "").strip() or "(no description)"] += 1
+
+        merchants = []
+        for key, entry in current.items():
+            # Most common spelling; ties go to the alphabetically first
+            name = min(entry["names"].items(), key=lambda item: (-item[1], item[0]))[0]
+            comparison = _comparison(entry["amount"], prior.get(key, Decimal("0.00")))
+            merchants.append(
+                {
+                    "merchant": name,
+                    "amount": entry["amount"],
+                    "count": entry["count"],
+                    "average": (entry["amount"] / entry["count"]).quantize(Decimal("0.01")),
+                    "prior_amount": comparison["prior"],
+                    "change": comparison["change"],
+                    "percent_change": comparison["percent_change"],
+                    "new": comparison["new"],
+                }
+            )
+        merchants.sort(key=lambda m: (-m["amount"], m["merchant"]))
+
+        return Ok(
+            {
+                "start_date": start_date,
+                "end_date": end_date,
+                "prior_start_date": prior_start,
+                "prior_end_date": prior_end,
+                "total_expenses": sum((m["amount"] for m in merchants), Decimal("0.00")),
+                "merchants": merchants[:top],
+            }
+        )
+
+    async def spending_by_tag(
+        self, start_date: date, end_date: date, account_id: UUID | None = None
+    ) -> Result[Dict[str, Any]]:
+        """Spending per tag and currency for [start_date, end_date].
+
+        Only expenses count (the Classifier leaves out income, transfers and
+        card payments), and split parents are skipped in favour of their
+        children. A transaction with several tags counts toward each; untagged
+        spending has tag None. With account_id, only that account is counted.
+
+        Returns dict with start_date, end_date and tags: each with tag,
+        currency, total (positive) and transaction_count, largest first.
+        """
+        accounts_result = await self.repository.get_accounts()
+        if not accounts_result.success:
+            return accounts_result
+        accounts = {a.id: a for a in accounts_result.data or []}
+
+        tx_result = await self.repository.get_transactions_in_range(start_date, end_date)
+        if not tx_result.success:
+            return tx_result
+        transactions = tx_result.data or []
+
+        totals: Dict[Tuple[str | None, str | None], Dict[str, Any]] = {}
+        for tx in transactions:
+            if account_id and tx.account_id != account_id:
+                continue
+            account = accounts.get(tx.account_id)
+            account_type = account.account_type if account else None
+            if self.classifier.classify(tx, account_type) != EXPENSE:
+                continue
+            currency = account.currency if account else None
+            for tag in tx.tags or [None]:
+                entry = totals.setdefault(
+                    (tag, currency),
+                    {
+                        "tag": tag,
+                        "currency": currency,
+                        "total": Decimal("0.00"),
+                        "transaction_count": 0,
+                    },
+                )
+                entry["total"] -= tx.amount
+                entry["transaction_count"] += 1
+
+        def order(entry: Dict[str, Any]) -> Tuple[Any, ...]:
+            # Largest first; untagged spending after tags with the same total
+            tag, currency = entry["tag"], entry["currency"]
+            return (-entry["total"], tag is None, tag or "", currency or "")
+
+        return Ok(
+            {
+                "start_date": start_date,
+                "end_date": end_date,
+                "tags": sorted(totals.values(), key=order),
+            }
+        )
+
+    async def register(
+        self,
+        account_id: UUID,
+        start_date: date | None = None,
+        end_date: date | None = None,
+    ) -> Result[Dict[str, Any]]:
+        """Build a bank-statement style register for one account.
+
+        Transactions are listed oldest first with the amount split into a
+        debit (money out, as a positive number) or a credit column. The
+        running balance starts from the account's balance at the end of the
+        day before start_date; it is marked estimated when that balance was
+        reconstructed from a snapshot on another day, and left empty when no
+        balance is known. Split parents are skipped in favour of their
+        children, as in cashflow().
+
+        Returns dict with:
+            - account: id, name, currency
+            - start_date / end_date: The period covered (start defaults to
+              the first transaction, end to today)
+            - opening_balance, opening_balance_status ("snapshot",
+              "reconstructed" or "unknown"), estimated
+            - rows: [{date, description, debit, credit, balance, status}]
+            - totals: debits, credits, net, closing_balance
+        """
+        account_result = await self.repository.get_account_by_id(account_id)
+        if not account_result.success:
+            return Fail(f"Account not found: {account_id}")
+        account = account_result.data
+
+        tx_result = await self.repository.get_transactions_by_account(
+            account_id,
+            order_by="transaction_date ASC, created_at ASC, transaction_id ASC",
+            leaves_only=True,
+        )
+        if not tx_result.success:
+            return tx_result
+        transactions = tx_result.data or []
+
+        end_date = end_date or date.today()
+        if start_date is None:
+            start_date = transactions[0].transaction_date if transactions else end_date
+        if start_date > end_date:
+            return Fail(f"Start date {start_date} is after end date {end_date}")
+
+        opening_result = await self.repository.get_balance_as_of(
+            account_id, start_date - timedelta(days=1)
+        )
+        if not opening_result.success:
+            return opening_result
+        opening = opening_result.data
+
+        balance = opening["balance"]
+        debits = Decimal("0.00")
+        credits = Decimal("0.00")
+        rows = []
+        for tx in transactions:
+            if not start_date <= tx.transaction_date <= end_date:
+                continue
+            if tx.amount < 0:
+                debits -= tx.amount
+            else:
+                credits += tx.amount
+            if balance is not None:
+                balance += tx.amount
+            rows.append(
+                {
+                    "date": tx.transaction_date,
+                    "description": tx.description,
+                    "debit": -tx.amount if tx.amount < 0 else None,
+                    "credit": tx.amount if tx.amount >= 0 else None,
+                    "balance": balance,
+                    "status": TransactionStatus(tx.status).value,
+                }
+            )
+
+        return Ok(
+            {
+                "account": {
+                    "id": account.id,
+                    "name": account.nickname or account.name,
+                    "currency": account.currency,
+                },
+                "start_date": start_date,
+                "end_date": end_date,
+                "opening_balance": opening["balance"],
+                "opening_balance_status": opening["status"],
+                "estimated": opening["reconstructed"],
+                "rows": rows,
+                "totals": {
+                    "debits": debits,
+                    "credits": credits,
+                    "net": credits - debits,
+                    "closing_balance": balance,
+                },
+            }
+        )
+
+    async def digest(
+        self,
+        start_date: date,
+        end_date: date,
+        top_categories: int = TOP_CATEGORIES,
+        balance_change_percent: Decimal = BALANCE_CHANGE_PERCENT,
+    ) -> Result[Dict[str, Any]]:
+        """Build a short summary of a period compared with the one before it.
+
+        The prior period has the same length and ends the day before
+        start_date. Spending comes from cashflow() so the numbers match
+        `tl report cashflow`. New transactions are those added to the
+        database during the period, whatever their transaction date.
+
+        Returns dict with:
+            - start_date, end_date, prior_start_date, prior_end_date
+            - spent: current, prior, change, percent_change (None when the
+              prior amount is zero), new (prior zero, current not)
+            - top_categories: [{category, current, prior, change, percent_change, new}]
+            - largest_transaction: {date, description, account, amount, tags}
+              of the largest expense, or None
+            - new_transactions: count
+            - balance_changes: [{account, currency, start, end, change,
+              percent_change, new}] for accounts that moved by more than
+              balance_change_percent, largest move first
+            - provider_warnings: [{integration, message, first_seen}] unresolved
+        """
+        prior_end_date = start_date - timedelta(days=1)
+        prior_start_date = prior_end_date - (end_date - start_date)
+
+        current_result = await self.cashflow(start_date, end_date)
+        if not current_result.success:
+            return current_result
+        prior_result = await self.cashflow(prior_start_date, prior_end_date)
+        if not prior_result.success:
+            return prior_result
+        current, prior = current_result.data["totals"], prior_result.data["totals"]
+
+        categories = sorted(current["expenses"].items(), key=lambda item: (-item[1], item[0]))
+        top = [
+            {
+                "category": category,
+                **_comparison(amount, prior["expenses"].get(category, Decimal("0.00"))),
+            }
+            for category, amount in categories[:top_categories]
+        ]
+
+        accounts_result = await self.repository.get_accounts()
+        if not accounts_result.success:
+            return accounts_result
+        accounts = accounts_result.data or []
+
+        largest_result = await self.largest_expense(start_date, end_date)
+        if not largest_result.success:
+            return largest_result
+
+        added_result = await self.repository.count_transactions_added(
+            datetime.combine(start_date, time.min).astimezone(),
+            datetime.combine(end_date + timedelta(days=1), time.min).astimezone(),
+        )
+        if not added_result.success:
+            return added_result
+
+        balance_end = min(end_date, date.today())
+        balances_result = await self._balances_as_of(accounts, [prior_end_date, balance_end])
+        if not balances_result.success:
+            return balances_result
+        balance_changes = []
+        for account in accounts:
+            by_date = balances_result.data[account.id]
+            balances = {"start": by_date[prior_end_date], "end": by_date[balance_end]}
+            if balances["start"] is None or balances["end"] is None:
+                continue
+            change = _comparison(balances["end"], balances["start"])
+            moved = change["new"] or (
+                change["percent_change"] is not None
+                and abs(change["percent_change"]) > balance_change_percent
+            )
+            if moved:
+                balance_changes.append(
+                    {
+                        "account": account.nickname or account.name,
+                        "currency": account.currency,
+                        "start": change["prior"],
+                        "end": change["current"],
+                        "change": change["change"],
+                        "percent_change": change["percent_change"],
+                        "new": change["new"],
+                    }
+                )
+        balance_changes.sort(
+            key=lambda c: (not c["new"], -abs(c["percent_change"] or 0), c["account"])
+        )
+
+        warnings_result = await self.repository.list_provider_warnings()
+        if not warnings_result.success:
+            return warnings_result
+
+        return Ok(
+            {
+                "start_date": start_date,
+                "end_date": end_date,
+                "prior_start_date": prior_start_date,
+                "prior_end_date": prior_end_date,
+                "spent": _comparison(current["total_expenses"], prior["total_expenses"]),
+                "top_categories": top,
+                "largest_transaction": largest_result.data,
+                "new_transactions": added_result.data,
+                "balance_changes": balance_changes,
+                "provider_warnings": [
+                    {
+                        "integration": warning.integration_name,
+                        "message": warning.message,
+                        "first_seen": warning.first_seen,
+                    }
+                    for warning in warnings_result.data or []
+                ],
+            }
+        )
+
+    async def largest_expense(
+        self, start_date: date, end_date: date
+    ) -> Result[Dict[str, Any] | None]:
+        """The single largest expense in [start_date, end_date], or None.
+
+        Split parents are skipped in favour of their children; ties go to
+        the earliest date, then description.
+
+        Returns dict with date, description, account, amount and tags.
+        """
+        accounts_result = await self.repository.get_accounts()
+        if not accounts_result.success:
+            return accounts_result
+        accounts = accounts_result.data or []
+        account_types = {a.id: a.account_type for a in accounts}
+
+        tx_result = await self.repository.get_transactions_in_range(start_date, end_date)
+        if not tx_result.success:
+            return tx_result
+        transactions = tx_result.data or []
+        expenses = [
+            tx
+            for tx in transactions
+            if self.classifier.classify(tx, account_types.get(tx.account_id)) == EXPENSE
+        ]
+        largest = min(
+            expenses,
+            key=lambda tx: (tx.amount, tx.transaction_date, tx.description or ""),
+            default=None,
+        )
+        if largest is None:
+            return Ok(None)
+        return Ok(
+            {
+                "date": largest.transaction_date,
+                "description": largest.description,
+                "account": AccountDirectory(accounts).name_for(largest.account_id),
+                "amount": largest.amount,
+                "tags": list(largest.tags),
+            }
+        )
+
+    async def recurring(
+        self,
+        start_date: date,
+        end_date: date,
+        min_months: int = RECURRING_MIN_MONTHS,
+    ) -> Result[Dict[str, Any]]:
+        """Find expenses that recur monthly, such as subscriptions and bills.
+
+        A merchant (grouped by merchant_key) counts as recurring when it was
+        charged in at least min_months calendar months, no more than once a
+        month, and every charge is within RECURRING_AMOUNT_TOLERANCE of the
+        median charge. Weekly groceries or coffee therefore don't count.
+
+        Returns dict with:
+            - start_date / end_date
+            - charges: [{merchant, typical_amount, months, count, total,
+              last_date}], largest total first
+            - total: Sum of the charges' totals
+        """
+        accounts_result = await self.repository.get_accounts()
+        if not accounts_result.success:
+            return accounts_result
+        account_types = {a.id: a.account_type for a in accounts_result.data or []}
+
+        tx_result = await self.repository.get_transactions_in_range(start_date, end_date)
+        if not tx_result.success:
+            return tx_result
+        transactions = tx_result.data or []
+
+        by_merchant: Dict[str, List[Any]] = {}
+        for tx in transactions:
+            if self.classifier.classify(tx, account_types.get(tx.account_id)) != EXPENSE:
+                continue
+            by_merchant.setdefault(merchant_key(tx.description), []).append(tx)
+
+        charges = []
+        for group in by_merchant.values():
+            months = {_month_key(tx.transaction_date) for tx in group}
+            if len(months) < min_months or len(group) != len(months):
+                continue
+            amounts = sorted(-tx.amount for tx in group)
+            typical = amounts[len(amounts) // 2]
+            tolerance = typical * RECURRING_AMOUNT_TOLERANCE
+            if any(abs(amount - typical) > tolerance for amount in amounts):
+                continue
+            latest = max(group, key=lambda tx: tx.transaction_date)
+            charges.append(
+                {
+                    "merchant": (latest.description or "").strip() or "(no description)",
+                    "typical_amount": typical,
+                    "months": len(months),
+                    "count": len(group),
+                    "total": sum(amounts, Decimal("0.00")),
+                    "last_date": latest.transaction_date,
+                }
+            )
+        charges.sort(key=lambda c: (-c["total"], c["merchant"]))
+
+        return Ok(
+            {
+                "start_date": start_date,
+                "end_date": end_date,
+                "charges": charges,
+                "total": sum((c["total"] for c in charges), Decimal("0.00")),
+            }
+        )
+
+    async def net_worth_change(
+        self, accounts: List[Account], start: date, end: date
+    ) -> Result[Dict[str, Any]]:
+        """Sum of account balances at the end of start and of end.
+
+        Accounts with no known balance on either day are listed in
+        unknown_accounts and left out of both sums.
+
+        Returns dict with start, end, change, start_date, end_date and
+        unknown_accounts (names, sorted).
+        """
+        totals = {"start": Decimal("0.00"), "end": Decimal("0.00")}
+        unknown = []
+        balances_result = await self._balances_as_of(accounts, [start, end])
+        if not balances_result.success:
+            return balances_result
+        for account in accounts:
+            by_date = balances_result.data[account.id]
+            balances = {"start": by_date[start], "end": by_date[end]}
+            if balances["start"] is None or balances["end"] is None:
+                unknown.append(account.nickname or account.name)
+                continue
+            for key in totals:
+                totals[key] += balances[key]
+
+        return Ok(
+            {
+                "start": totals["start"],
+                "end": totals["end"],
+                "change": totals["end"] - totals["start"],
+                "start_date": start,
+                "end_date": end,
+                "unknown_accounts": sorted(unknown),
+            }
+        )
+
+    def _summarize(self, bucket: Dict[str, Any], month: str | None = None) -> Dict[str, Any]:
+        income = bucket["income"]
+        expenses = dict(sorted(bucket["expenses"].items()))
+        total_expenses = sum(expenses.values(), Decimal("0.00"))
+        net = income - total_expenses
+        summary: Dict[str, Any] = {} if month is None else {"month": month}
+        summary.update(
+            {
+                "income": income,
+                "expenses": expenses,
+                "total_expenses": total_expenses,
+                "net_savings": net,
+                "savings_rate": _savings_rate(income, net),
+            }
+        )
+        return summary
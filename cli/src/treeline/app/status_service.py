@@ -1,16 +1,155 @@
 """Service for retrieving financial data status and summaries."""
 
-from typing import Any, Dict
+from datetime import date, timedelta
+from decimal import Decimal
+from typing import TYPE_CHECKING, Any, Dict, List, Tuple
+from uuid import UUID
 
 from treeline.abstractions import Repository
-from treeline.domain import Result
+from treeline.app.account_service import account_sort_key
+from treeline.config import get_stale_days
+from treeline.domain import Account, Result
+
+if TYPE_CHECKING:
+    from treeline.app.warning_service import WarningService
+
+# Accounts without an institution name are grouped under this label
+OTHER_INSTITUTION = "Other"
+
+
+def _add_to(totals: Dict[str, Decimal], currency: str, amount: Decimal) -> None:
+    totals[currency] = totals.get(currency, Decimal("0.00")) + amount
+
+
+def group_accounts_by_institution(
+    accounts: List[Account], balances: Dict[UUID, Decimal]
+) -> Dict[str, Any]:
+    """Group accounts by institution with per-currency subtotals.
+
+    Institution names are matched case-insensitively. Groups are sorted by
+    institution ("Other" last) and accounts by nickname or name within each group. Accounts without a known
+    balance are counted in unknown_balances rather than summed as zero.
+
+    Returns:
+        Dict with groups (institution, account_ids, account_count,
+        subtotals by currency, unknown_balances) and totals by currency.
+    """
+    # Keyed case-insensitively; the first spelling seen labels the group
+    labels: Dict[str | None, str] = {}
+    by_institution: Dict[str | None, List[Account]] = {}
+    for account in accounts:
+        name = (account.institution_name or "").strip()
+        key = name.casefold() if name else None
+        labels.setdefault(key, name or OTHER_INSTITUTION)
+        by_institution.setdefault(key, []).append(account)
+
+    groups = []
+    totals: Dict[str, Decimal] = {}
+    for key in sorted(by_institution, key=lambda k: (k is None, k or "")):
+        institution = labels[key]
+        members = sorted(by_institution[key], key=lambda a: (a.nickname or a.name).casefold())
+        subtotals: Dict[str, Decimal] = {}
+        unknown = 0
+        for account in members:
+            balance = balances.get(account.id)
+            if balance is None:
+                unknown += 1
+                continue
+            _add_to(subtotals, account.currency, balance)
+            _add_to(totals, account.currency, balance)
+        groups.append(
+            {
+                "institution": institution,
+                "account_ids": [str(account.id) for account in members],
+                "account_count": len(members),
+                "subtotals": subtotals,
+                "unknown_balances": unknown,
+            }
+        )
+
+    return {"groups": groups, "totals": totals}
+
+
+def _days_since(today: date, newest: date | None, since: date) -> Tuple[int | None, int]:
+    """Days since newest (None if never), and the days that count toward staleness."""
+    if newest is None:
+        return None, (today - since).days
+    days = (today - newest).days
+    return days, days
+
+
+def find_stale_accounts(
+    accounts: List[Account],
+    latest: Dict[UUID, Tuple[date | None, date | None]],
+    thresholds: Dict[str, int],
+    today: date,
+) -> List[Dict[str, Any]]:
+    """Find open accounts whose newest transaction or balance is too old.
+
+    Accounts with a provider ID use the "synced" threshold and the rest the
+    (longer) "manual" one, unless their account type has its own. An account
+    with no transactions or balances yet is measured from when it was added.
+
+    Args:
+        accounts: Accounts to check
+        latest: Newest transaction date and newest snapshot date by account ID
+        thresholds: Days by "synced", "manual" and account type (see get_stale_days)
+        today: Date to measure from
+
+    Returns:
+        Stale accounts sorted by name, each with id, name, synced, threshold_days,
+        latest_transaction_date, latest_snapshot_date, days_since_transaction,
+        days_since_snapshot (None if never) and reasons
+    """
+    stale = []
+    for account in sorted(accounts, key=lambda a: a.name.casefold()):
+        if account.closed_at is not None and account.closed_at < today:
+            continue
+        synced = bool(account.external_ids)
+        threshold = thresholds.get((account.account_type or "").strip().lower()) or thresholds[
+            "synced" if synced else "manual"
+        ]
+        newest_transaction, newest_snapshot = latest.get(account.id, (None, None))
+        added = account.created_at.date()
+        days_transaction, age_transaction = _days_since(today, newest_transaction, added)
+        days_snapshot, age_snapshot = _days_since(today, newest_snapshot, added)
+
+        reasons = []
+        if age_transaction > threshold:
+            reasons.append(
+                f"no transactions in {days_transaction} days"
+                if days_transaction is not None
+                else f"no transactions since it was added {age_transaction} days ago"
+            )
+        if age_snapshot > threshold:
+            reasons.append(
+                f"no balance in {days_snapshot} days"
+                if days_snapshot is not None
+                else f"no balance since it was added {age_snapshot} days ago"
+            )
+        if reasons:
+            stale.append(
+                {
+                    "id": str(account.id),
+                    "name": account.name,
+                    "synced": synced,
+                    "threshold_days": threshold,
+                    "latest_transaction_date": newest_transaction,
+                    "latest_snapshot_date": newest_snapshot,
+                    "days_since_transaction": days_transaction,
+                    "days_since_snapshot": days_snapshot,
+                    "reasons": reasons,
+                }
+            )
+    return stale
 
 
 class StatusService:
     """Service for retrieving financial data status and summaries."""
 
-    def __init__(self, repository: Repository):
+    def __init__(self, repository: Repository, warning_service: "WarningService"):
         self.repository = repository
+        self.warning_service = warning_service
 
     async def get_status(self) -> Result[Dict[str, Any]]:
         """Get financial data status summary."""
@@ -19,19 +158,48 @@ class StatusService:
         if not accounts_result.success:
             return accounts_result
 
-        accounts = accounts_result.data or []
+        # Sorted so --json output is the same from run to run; archived
+        # accounts are left out of the totals, staleness and type checks
+        all_accounts = accounts_result.data or []
+        accounts = sorted(
+            (account for account in all_accounts if account.archived_at is None),
+            key=account_sort_key,
+        )
+        archived_count = len(all_accounts) - len(accounts)
+        row_errors = list((accounts_result.context or {}).get("row_errors", []))
+
+        # Latest balance per account (through today), one query for all accounts
+        balances: Dict[UUID, Decimal] = {}
+        if accounts:
+            tomorrow = date.today() + timedelta(days=1)
+            snapshots_result = await self.repository.get_latest_balance_snapshots(
+                {account.id: tomorrow for account in accounts}
+            )
+            if snapshots_result.success:
+                balances = {
+                    account_id: snapshot.balance
+                    for account_id, snapshot in snapshots_result.data.items()
+                }
+            # A closed account's last snapshot no longer counts toward totals
+            today = date.today()
+            for account in accounts:
+                if account.closed_at is not None and account.closed_at < today:
+                    balances[account.id] = Decimal("0.00")
 
         # Get integrations
         integrations_result = await self.repository.list_integrations()
         if not integrations_result.success:
             return integrations_result
 
-        integrations = integrations_result.data or []
+        integrations = sorted(
+            integrations_result.data or [], key=lambda i: i["integrationName"]
+        )
 
-        # Query for transaction stats
+        # Query for transaction stats; a split transaction counts once however many parts it has
         transaction_stats_query = """
             SELECT
-                COUNT(*) as total_transactions,
+                COUNT(DISTINCT COALESCE(parent_transaction_id, transaction_id))
+                    as total_transactions,
                 MIN(transaction_date) as earliest_date,
                 MAX(transaction_date) as latest_date
             FROM transactions
@@ -47,7 +215,7 @@ class StatusService:
         if rows and len(rows) > 0:
             # Rows are tuples, use column indices
             row = rows[0]
-            total_transactions = row[0] if len(row) > 0 else 0  # COUNT(*)
+            total_transactions = row[0] if len(row) > 0 else 0  # COUNT(DISTINCT ...)
             earliest_date = row[1] if len(row) > 1 else None  # MIN(transaction_date)
             latest_date = row[2] if len(row) > 2 else None  # MAX(transaction_date)
         else:
@@ -69,6 +237,28 @@ class StatusService:
                 balance_rows[0][0] if balance_rows and len(balance_rows) > 0 else 0
             )
 
+        # Unresolved provider warnings (empty if the table doesn't exist yet)
+        warnings_result = await self.warning_service.list_warnings()
+        provider_warnings = warnings_result.data if warnings_result.success else []
+
+        # Newest transaction and balance per account, for staleness
+        latest: Dict[UUID, Tuple[date | None, date | None]] = {}
+        latest_result = await self.repository.execute_query(
+            """
+            SELECT
+                a.account_id,
+                (SELECT MAX(t.transaction_date) FROM transactions t
+                 WHERE t.account_id = a.account_id),
+                (SELECT MAX(CAST(s.snapshot_time AS DATE)) FROM sys_balance_snapshots s
+                 WHERE s.account_id = a.account_id)
+            FROM sys_accounts a
+            """
+        )
+        if latest_result.success:
+            for account_id, newest_transaction, newest_snapshot in latest_result.data["rows"]:
+                latest[UUID(str(account_id))] = (newest_transaction, newest_snapshot)
+        stale_accounts = find_stale_accounts(accounts, latest, get_stale_days(), date.today())
+
         # Return both full data (for display) and summary (for JSON)
         integration_names = [i["integrationName"] for i in integrations]
 
@@ -78,8 +268,11 @@ class StatusService:
                 # Full data for display functions
                 "accounts": accounts,
                 "integrations": integrations,
+                # Latest known balance by account ID (missing = unknown)
+                "balances": balances,
                 # Summary counts
                 "total_accounts": len(accounts),
+                "archived_accounts": archived_count,
                 "total_transactions": total_transactions,
                 "total_snapshots": total_snapshots,
                 "total_integrations": len(integrations),
@@ -87,5 +280,15 @@ class StatusService:
                 # Date range
                 "earliest_date": str(earliest_date) if earliest_date else None,
                 "latest_date": str(latest_date) if latest_date else None,
+                # Unresolved provider warnings
+                "provider_warnings": provider_warnings,
+                # Account rows that could not be read (left out of the above)
+                "row_errors": row_errors,
+                # Open accounts with no recent transactions or balances
+                "stale_accounts": stale_accounts,
+                # Accounts with no type, so reports can't classify them
+                "untyped_accounts": [
+                    account for account in accounts if account.account_type is None
+                ],
             },
         )
@@ -1,15 +1,113 @@
 """Service for synchronizing financial data from providers."""
 
-from datetime import datetime, timedelta, timezone, date
-from typing import Any, Dict, List, TYPE_CHECKING
-
-from treeline.abstractions import DataAggregationProvider, Repository
-from treeline.domain import Result, Transaction
+import asyncio
+from dataclasses import asdict, dataclass
+from datetime import datetime, time, timedelta, timezone, date
+from typing import Any, Awaitable, Callable, Dict, List, Set, Tuple, TYPE_CHECKING
+
+from treeline.abstractions import DataAggregationProvider, Repository, WriteLock, write_phase
+from treeline.app.account_service import account_sort_key
+from treeline.app.budget_service import describe_budget_alert, find_budget_alerts
+from treeline.category_map import (
+    DEFAULT_CATEGORY_MAP,
+    CategoryMap,
+    load_category_map,
+    save_category_map,
+)
+from treeline.config import (
+    get_auto_month_end_snapshots,
+    get_backfill_delay_seconds,
+    get_balance_check_tolerance,
+    get_budget_alerts_enabled,
+    get_category_map_path,
+    get_fingerprint_version,
+    get_keep_unmapped,
+    is_offline,
+)
+from treeline.app.sync_steps import (
+    backfill_range,
+    find_duplicate_account_candidates,
+    find_unexplained_balance_changes,
+    is_duplicate_overlap,
+    match_accounts,
+    map_transactions,
+    month_windows,
+    partition_new,
+    previous_month_end,
+)
+from treeline.domain import (
+    Account,
+    Fail,
+    Ok,
+    Result,
+    Transaction,
+    apply_default_tags,
+    assign_fingerprints,
+)
+from treeline.features import DEMO, disabled_error, is_enabled
 
 if TYPE_CHECKING:
     from treeline.app.account_service import AccountService
+    from treeline.app.budget_service import BudgetService
     from treeline.app.integration_service import IntegrationService
+    from treeline.app.dedupe_service import DedupeService
+    from treeline.app.planned_service import PlannedService
     from treeline.app.preferences_service import PreferencesService
+    from treeline.app.warning_service import WarningService
+
+# Integration settings key holding the checkpoint of an unfinished history
+# backfill: {"start_date", "end_date", "last_completed_window": "YYYY-MM"}
+BACKFILL_CHECKPOINT_KEY = "history_backfill"
+# Months of history `tl backfill transactions` fetches by default
+BACKFILL_DEFAULT_MONTHS = 24
+# Extra attempts at a window before the backfill stops
+BACKFILL_WINDOW_RETRIES = 2
+
+
+def integration_sync_status(entry: Dict[str, Any]) -> str:
+    """"ok", "failed" or "skipped" for one entry of a sync's results list."""
+    if "error" in entry:
+        return "failed"
+    if entry.get("skipped"):
+        return "skipped"
+    return "ok"
+
+
+def overall_sync_status(results: List[Dict[str, Any]]) -> str:
+    """How a sync went as a whole: "ok" when no integration failed, "partial"
+    when some failed and at least one synced, "failed" when none synced.
+
+    Skipped integrations (offline, demo mode, disabled) don't count either way.
+    """
+    statuses = [entry["status"] for entry in results]
+    if "failed" not in statuses:
+        return "ok"
+    return "partial" if "ok" in statuses else "failed"
+
+
+@dataclass(frozen=True)
+class SyncContext:
+    """Options for one sync run, threaded through every step.
+
+    Every repository write during a sync checks dry_run, so a dry run leaves no
+    trace: no accounts, transactions, balance snapshots, warnings or quarantine.
+
+    on_event, when set, is called with a dict for each step as it happens
+    ({"event": "integration_start", "name": ...}, ...); `tl sync --json-stream`
+    writes these out as JSON lines.
+
+    Archived accounts (tl accounts archive) are skipped: the provider's data
+    for them is not written, unless include_archived is set.
+    """
+
+    dry_run: bool = False
+    include_archived: bool = False
+    on_event: Callable[[Dict[str, Any]], None] | None = None
+
+    def emit(self, event: str, **fields: Any) -> None:
+        """Report a progress event to on_event, if set."""
+        if self.on_event is not None:
+            self.on_event({"event": event, **fields})
 
 
 class SyncService:
@@ -22,21 +120,49 @@ class SyncService:
         account_service: "AccountService",
         integration_service: "IntegrationService",
         preferences_service: "PreferencesService",
+        warning_service: "WarningService",
+        budget_service: "BudgetService | None" = None,
+        planned_service: "PlannedService | None" = None,
+        dedupe_service: "DedupeService | None" = None,
+        # Set when repository is the demo database; only the demo provider syncs
+        demo_mode: bool = False,
+        # Held while fetched data is written (see abstractions/write_lock.py)
+        write_lock: WriteLock | None = None,
     ):
         self.provider_registry = provider_registry
         self.repository = repository
         self.account_service = account_service
         self.integration_service = integration_service
         self.preferences_service = preferences_service
+        self.warning_service = warning_service
+        self.budget_service = budget_service
+        self.planned_service = planned_service
+        self.dedupe_service = dedupe_service
+        self.demo_mode = demo_mode
+        self.write_lock = write_lock
 
     def _get_provider(self, integration_name: str) -> DataAggregationProvider | None:
         """Get the provider for a given integration name."""
         return self.provider_registry.get(integration_name.lower())
 
     async def sync_accounts(
-        self, integration_name: str, provider_options: Dict[str, Any]
+        self,
+        integration_name: str,
+        provider_options: Dict[str, Any],
+        existing_accounts: List[Account] | None = None,
+        fetched: Dict[str, Any] | None = None,
+        context: SyncContext | None = None,
     ) -> Result[Dict[str, Any]]:
-        """Sync accounts from a data provider."""
+        """Sync accounts from a data provider.
+
+        Args:
+            integration_name: Integration to sync
+            provider_options: Integration settings passed to the provider
+            existing_accounts: Accounts already loaded by the caller (fetched if None)
+            fetched: Result data of provider.fetch, to avoid asking the provider again
+            context: Run options; in a dry run nothing is written
+        """
+        context = context or SyncContext()
         data_provider = self._get_provider(integration_name)
         if not data_provider:
             return Result(
@@ -49,25 +175,31 @@ class SyncService:
         integration_name_lower = integration_name.lower()
 
         # Get existing accounts to map external IDs
-        existing_accounts_result = await self.repository.get_accounts()
-        if not existing_accounts_result.success:
-            return existing_accounts_result
+        if existing_accounts is None:
+            existing_accounts_result = await self.repository.get_accounts()
+            if not existing_accounts_result.success:
+                return existing_accounts_result
 
-        existing_accounts = existing_accounts_result.data or []
+            existing_accounts = existing_accounts_result.data or []
 
         # Get discovered accounts from provider
-        discovered_result = await data_provider.get_accounts(
-            provider_account_ids=[], provider_settings=provider_options
-        )
-        if not discovered_result.success:
-            return discovered_result
+        if fetched is None:
+            discovered_result = await data_provider.get_accounts(
+                provider_account_ids=[], provider_settings=provider_options
+            )
+            if not discovered_result.success:
+                return discovered_result
+            result_data = discovered_result.data or {}
+        else:
+            result_data = fetched
 
         # Handle new response format: {"accounts": [...], "errors": [...]}
         # or old format: List[Account]
-        result_data = discovered_result.data or {}
+        balance_dates: Dict[str, date] = {}
         if isinstance(result_data, dict):
             discovered_accounts = result_data.get("accounts", [])
-            provider_errors = result_data.get("errors", [])
+            provider_errors = list(result_data.get("errors", []))
+            balance_dates = result_data.get("balance_dates", {})
         else:
             # Backwards compatibility with providers that return List[Account]
             discovered_accounts = result_data
@@ -93,34 +225,52 @@ class SyncService:
 
         # Add currency warnings to provider errors for display
         provider_errors.extend(currency_warnings)
-        discovered_accounts = currency_filtered_accounts
 
-        # Map discovered accounts to existing accounts by external ID
-        updated_accounts = []
-        new_accounts = []  # Track newly discovered accounts
-        for discovered_account in discovered_accounts:
-            matched = False
-            for existing_account in existing_accounts:
-                disc_ext_id = discovered_account.external_ids.get(
-                    integration_name_lower
-                )
-                exist_ext_id = existing_account.external_ids.get(integration_name_lower)
-
-                if disc_ext_id and exist_ext_id and disc_ext_id == exist_ext_id:
-                    # Update discovered account to use existing ID
-                    updated_account = discovered_account.model_copy(
-                        update={"id": existing_account.id}
-                    )
-                    updated_accounts.append(updated_account)
-                    matched = True
-                    break
+        # Accounts with sync disabled (tl accounts disable-sync) are left alone
+        account_settings = provider_options.get("accountSettings", {})
+        discovered_accounts = [
+            account
+            for account in currency_filtered_accounts
+            if not account_settings.get(
+                account.external_ids.get(integration_name_lower), {}
+            ).get("syncDisabled", False)
+        ]
 
-            if not matched:
-                # This is a new account
-                updated_accounts.append(discovered_account)
-                new_accounts.append(discovered_account)
+        # Map discovered accounts to existing accounts by external ID
+        match_result = match_accounts(
+            discovered_accounts, existing_accounts, integration_name_lower
+        )
+        if not match_result.success:
+            return match_result
+        discovered_accounts = match_result.data.accounts
+        new_accounts = match_result.data.new_accounts  # Accounts that didn't exist before
+        if not context.include_archived:
+            discovered_accounts = [
+                account for account in discovered_accounts if account.archived_at is None
+            ]
+
+        # Date each balance by the provider's balance date when it reports one
+        # (never later than today)
+        today = date.today()
+        account_balance_dates: Dict[Any, date] = {
+            account.id: min(
+                balance_dates.get(account.external_ids.get(integration_name_lower), today),
+                today,
+            )
+            for account in discovered_accounts
+        }
 
-        discovered_accounts = updated_accounts
+        if context.dry_run:
+            return Result(
+                success=True,
+                data={
+                    "discovered_accounts": discovered_accounts,
+                    "ingested_accounts": discovered_accounts,
+                    "new_accounts": new_accounts,
+                    "balance_dates": account_balance_dates,
+                    "provider_errors": provider_errors,
+                },
+            )
 
         # Bulk upsert accounts
         ingested_result = await self.repository.bulk_upsert_accounts(
@@ -129,17 +279,29 @@ class SyncService:
         if not ingested_result.success:
             return ingested_result
 
-        # Create balance snapshots for accounts with balances
-        # Use AccountService to leverage deduplication logic
+        # Create balance snapshots for accounts with balances on their balance
+        # dates. Accounts were just upserted, so skip per-account existence
+        # checks; AccountService still deduplicates against that day's snapshots.
+        # Accounts the provider sent without a balance get no snapshot (an
+        # omitted balance isn't zero), and are named in the warnings.
+        # Continue on failure - don't halt sync for balance snapshot issues
+        accounts_by_date: Dict[date, List[Account]] = {}
         for account in discovered_accounts:
-            if account.id and account.balance is not None:
-                # Call AccountService to add balance snapshot (handles deduplication)
-                # Continue on failure - don't halt sync for balance snapshot issues
-                await self.account_service.add_balance_snapshot(
-                    account_id=account.id,
-                    balance=account.balance,
-                    snapshot_date=None,  # Defaults to today
-                    source="sync",
+            if account.balance is None:
+                provider_errors.append(
+                    f"No balance reported for '{account.name}'; no balance snapshot saved"
+                )
+                continue
+            accounts_by_date.setdefault(account_balance_dates[account.id], []).append(account)
+        for snapshot_date, dated_accounts in accounts_by_date.items():
+            snapshots_result = await self.account_service.add_balance_snapshots(
+                dated_accounts,
+                snapshot_date=snapshot_date,
+                source="sync",
+            )
+            if not snapshots_result.success:
+                provider_errors.append(
+                    f"Balance snapshots for {snapshot_date} were not saved: {snapshots_result.error}"
                 )
 
         return Result(
@@ -148,6 +310,7 @@ class SyncService:
                 "discovered_accounts": discovered_accounts,
                 "ingested_accounts": ingested_result.data,
                 "new_accounts": new_accounts,  # Accounts that didn't exist before
+                "balance_dates": account_balance_dates,  # Account ID -> date of its balance
                 "provider_errors": provider_errors,  # Errors from SimpleFIN (e.g., "You must reauthenticate")
             },
         )
@@ -158,9 +321,18 @@ class SyncService:
         start_date: datetime | None = None,
         end_date: datetime | None = None,
         provider_options: Dict[str, Any] | None = None,
-        dry_run: bool = False,
+        accounts: List[Account] | None = None,
+        fetched: Dict[str, Any] | None = None,
+        context: SyncContext | None = None,
     ) -> Result[Dict[str, Any]]:
-        """Sync transactions from a data provider."""
+        """Sync transactions from a data provider.
+
+        Args:
+            accounts: Accounts already loaded by the caller (fetched if None)
+            fetched: Result data of provider.fetch, to avoid asking the provider again
+            context: Run options; in a dry run nothing is written
+        """
+        context = context or SyncContext()
         data_provider = self._get_provider(integration_name)
         if not data_provider:
             return Result(
@@ -173,102 +345,159 @@ class SyncService:
         integration_name_lower = integration_name.lower()
 
         # Get existing accounts to map provider account IDs
-        accounts_result = await self.repository.get_accounts()
-        if not accounts_result.success:
-            return accounts_result
+        if accounts is None:
+            accounts_result = await self.repository.get_accounts()
+            if not accounts_result.success:
+                return accounts_result
 
-        accounts = accounts_result.data or []
+            accounts = accounts_result.data or []
 
         # Get integration settings to check for balances-only accounts
         integration_settings = provider_options or {}
         account_settings = integration_settings.get("accountSettings", {})
 
-        # Get provider account IDs from existing accounts
-        # Exclude accounts marked as balancesOnly in integration settings
-        provider_account_ids = []
-        for acc in accounts:
-            provider_acc_id = acc.external_ids.get(integration_name_lower)
-            if provider_acc_id:
-                # Check if this account is marked as balances-only
-                acc_settings = account_settings.get(provider_acc_id, {})
-                if not acc_settings.get("balancesOnly", False):
-                    provider_account_ids.append(provider_acc_id)
+        # Map provider account IDs to internal account IDs
+        account_id_map = {
+            acc.external_ids.get(integration_name_lower): acc.id
+            for acc in accounts
+            if acc.external_ids.get(integration_name_lower)
+        }
+
+        # Exclude accounts marked as balancesOnly or syncDisabled in integration
+        # settings, and archived accounts unless the run includes them
+        archived_ids = {
+            acc.external_ids.get(integration_name_lower)
+            for acc in accounts
+            if acc.archived_at is not None and not context.include_archived
+        }
+        balances_only_ids = {
+            provider_acc_id
+            for provider_acc_id in account_id_map
+            if account_settings.get(provider_acc_id, {}).get("balancesOnly", False)
+            or account_settings.get(provider_acc_id, {}).get("syncDisabled", False)
+            or provider_acc_id in archived_ids
+        }
+        provider_account_ids = [
+            provider_acc_id
+            for provider_acc_id in account_id_map
+            if provider_acc_id not in balances_only_ids
+        ]
 
         # Get discovered transactions
-        discovered_result = await data_provider.get_transactions(
-            start_date,
-            end_date,
-            provider_account_ids=provider_account_ids,
-            provider_settings=provider_options or {},
-        )
-        if not discovered_result.success:
-            return discovered_result
+        if fetched is None:
+            discovered_result = await data_provider.get_transactions(
+                start_date,
+                end_date,
+                provider_account_ids=provider_account_ids,
+                provider_settings=provider_options or {},
+            )
+            if not discovered_result.success:
+                return discovered_result
+            result_data = discovered_result.data or {}
+        else:
+            result_data = fetched
 
         # Handle new response format: {"transactions": [...], "errors": [...]}
         # or old format: List[...]
-        result_data = discovered_result.data or {}
         if isinstance(result_data, dict):
             discovered_data = result_data.get("transactions", [])
-            provider_errors = result_data.get("errors", [])
+            provider_errors = list(result_data.get("errors", []))
         else:
             # Backwards compatibility with providers that return List
             discovered_data = result_data
             provider_errors = []
 
-        # Map provider account IDs to internal account IDs
-        account_id_map = {
-            acc.external_ids.get(integration_name_lower): acc.id
-            for acc in accounts
-            if acc.external_ids.get(integration_name_lower)
-        }
-
-        mapped_transactions = []
-
         # Handle provider-specific return formats
         # SimpleFIN returns: List[(provider_account_id, Transaction)]
         # CSV returns: List[Transaction] (account_id already set)
-        for item in discovered_data:
-            if isinstance(item, tuple):
-                # Format: (provider_account_id, transaction)
-                provider_acc_id, tx = item
-                internal_acc_id = account_id_map.get(provider_acc_id)
-
-                # Skip transactions with unmapped accounts
-                if not internal_acc_id:
-                    continue
-
-                # Reconstruct transaction with correct account_id to force fingerprint recalculation
-                # Note: model_copy doesn't trigger @model_validator, so we use model_dump + reconstruct
-                tx_dict = tx.model_dump()
-                tx_dict["account_id"] = internal_acc_id
-
-                # Remove old fingerprint to force recalculation
-                if "fingerprint" in tx_dict["external_ids"]:
-                    cleaned_external_ids = {
-                        k: v
-                        for k, v in tx_dict["external_ids"].items()
-                        if k != "fingerprint"
-                    }
-                    tx_dict["external_ids"] = cleaned_external_ids
-
-                # Reconstruct - this triggers @model_validator which auto-generates correct fingerprint
-                mapped_tx = Transaction(**tx_dict)
-                mapped_transactions.append(mapped_tx)
-            else:
-                # Format: Transaction (account_id already set, e.g., from CSV)
-                mapped_transactions.append(item)
+        # Fetched data covers every account, including balances-only ones
+        map_outcome = map_transactions(discovered_data, account_id_map, balances_only_ids)
+        mapped_transactions = map_outcome.transactions
+
+        if data_provider.tags_are_categories:
+            mapped_transactions, category_warning = self._apply_category_map(
+                mapped_transactions, context
+            )
+            if category_warning:
+                provider_errors.append(category_warning)
+        mapped_transactions = apply_default_tags(mapped_transactions, accounts)
+
+        # Transactions for accounts we don't know (a failed account upsert, or a
+        # provider listing transactions for an account it didn't return) are
+        # reported, and kept in quarantine when sync.keep_unmapped is set
+        quarantined = 0
+        if map_outcome.unmapped:
+            unknown_ids = sorted({provider_acc_id for provider_acc_id, _ in map_outcome.unmapped})
+            message = (
+                f"{len(map_outcome.unmapped)} transaction(s) for unknown {integration_name} "
+                f"account(s) {', '.join(unknown_ids)} were not imported"
+            )
+            if get_keep_unmapped() and not context.dry_run:
+                quarantine_result = await self.repository.add_unmapped_transactions(
+                    integration_name_lower, map_outcome.unmapped
+                )
+                if not quarantine_result.success:
+                    return quarantine_result
+                quarantined = len(map_outcome.unmapped)
+                message += "; they were kept for 'tl maintenance adopt-unmapped'"
+            provider_errors.append(message)
+
+        fingerprint_version = get_fingerprint_version()
+        if fingerprint_version != 1:
+            mapped_transactions = assign_fingerprints(
+                mapped_transactions, fingerprint_version
+            )
+
+        # The duplicate check and the insert are one step for other processes
+        async with write_phase(self.write_lock, "sync", skip=context.dry_run) as locked:
+            if not locked.success:
+                return locked
+            insert_result = await self._insert_new_transactions(
+                integration_name_lower, mapped_transactions, context
+            )
+        if not insert_result.success:
+            return insert_result
+        transactions_to_insert, skipped_transactions, ingested_transactions = insert_result.data
+
+        return Result(
+            success=True,
+            data={
+                "discovered_transactions": mapped_transactions,
+                "ingested_transactions": ingested_transactions,
+                "stats": {
+                    "discovered": len(mapped_transactions),
+                    "new": len(transactions_to_insert),
+                    "skipped": len(skipped_transactions),
+                    "unmapped": len(map_outcome.unmapped),
+                    "quarantined": quarantined,
+                },
+                "provider_errors": provider_errors,
+            },
+        )
 
+    async def _insert_new_transactions(
+        self,
+        integration_name_lower: str,
+        mapped_transactions: List[Transaction],
+        context: SyncContext,
+    ) -> Result[Tuple[List[Transaction], List[Transaction], List[Transaction]]]:
+        """Insert the transactions whose provider ID isn't stored yet.
+
+        Returns:
+            (to insert, skipped, ingested); in a dry run nothing is written and
+            ingested is the ones that would be inserted
+        """
         # Get existing transactions by external IDs to check for duplicates
-        external_id_objects = [
-            {integration_name_lower: tx.external_ids.get(integration_name_lower)}
+        external_ids = [
+            tx.external_ids[integration_name_lower]
             for tx in mapped_transactions
             if tx.external_ids.get(integration_name_lower)
         ]
 
         existing_txs: List[Transaction] = []
-        if external_id_objects:
+        if external_ids:
             existing_result = await self.repository.get_transactions_by_external_ids(
-                external_id_objects
+                integration_name_lower, external_ids
             )
             if existing_result.success:
                 existing_txs = existing_result.data or []
@@ -282,21 +511,12 @@ class SyncService:
 
         # Separate new vs skipped transactions
         # IMPORTANT: Skip existing transactions to preserve user-added data like tags
-        transactions_to_insert = []
-        new_count = 0
-        skipped_count = 0
-
-        for discovered_tx in mapped_transactions:
-            ext_id = discovered_tx.external_ids.get(integration_name_lower)
-            if ext_id and ext_id in existing_by_ext_id:
-                # Skip: transaction already exists, preserve user data (tags, etc.)
-                skipped_count += 1
-            else:
-                transactions_to_insert.append(discovered_tx)
-                new_count += 1
+        transactions_to_insert, skipped_transactions = partition_new(
+            mapped_transactions, existing_by_ext_id, integration_name_lower
+        )
 
         # Bulk insert only new transactions (unless dry-run)
-        if dry_run:
+        if context.dry_run:
             # In dry-run mode, don't actually insert
             ingested_transactions = transactions_to_insert
         else:
@@ -307,19 +527,206 @@ class SyncService:
                 return ingested_result
             ingested_transactions = ingested_result.data
 
-        return Result(
-            success=True,
-            data={
-                "discovered_transactions": mapped_transactions,
-                "ingested_transactions": ingested_transactions,
-                "stats": {
-                    "discovered": len(mapped_transactions),
-                    "new": new_count,
-                    "skipped": skipped_count,
-                },
-                "provider_errors": provider_errors,
-            },
+        return Ok((transactions_to_insert, skipped_transactions, ingested_transactions))
+
+    async def backfill_transactions(
+        self,
+        integration_name: str,
+        months: int = BACKFILL_DEFAULT_MONTHS,
+        resume: bool = False,
+        delay: float | None = None,
+        retries: int = BACKFILL_WINDOW_RETRIES,
+        context: SyncContext | None = None,
+        today: date | None = None,
+        sleep: Callable[[float], Awaitable[Any]] = asyncio.sleep,
+    ) -> Result[Dict[str, Any]]:
+        """Fetch an integration's transaction history one calendar month at a time.
+
+        A single request for years of history times out or comes back partial,
+        so windows are fetched oldest first, and each is mapped, deduped and
+        inserted (see sync_transactions) before the next. After each window
+        the integration settings get a checkpoint (BACKFILL_CHECKPOINT_KEY); a
+        window that still fails after `retries` retries stops the run with the
+        checkpoint intact, and resume=True carries on after the last completed
+        window. The checkpoint is cleared once every window is done.
+
+        Args:
+            months: Months of history, counting the current one (ignored on resume)
+            resume: Continue an interrupted backfill instead of starting over
+            delay: Seconds between requests (settings.json sync.backfill_delay_seconds)
+            context: Run options; events are window_start, window_retry,
+                window_done and window_failed
+            today: Today's date, for tests
+            sleep: Awaited between requests, for tests
+
+        Returns:
+            Result with integration, start_date, end_date, windows, windows_run,
+            resumed_after, discovered, new, skipped and provider_warnings. On
+            failure the context has kind "backfill_interrupted", failed_window
+            and last_completed_window.
+        """
+        context = context or SyncContext()
+        delay = get_backfill_delay_seconds() if delay is None else max(delay, 0.0)
+        name = integration_name.lower()
+
+        data_provider = self._get_provider(name)
+        if not data_provider:
+            return Fail(f"Unknown integration: {integration_name}")
+        if not data_provider.can_get_transactions:
+            return Fail(f"{integration_name} does not provide transactions")
+        if data_provider.requires_network and is_offline():
+            return Fail(f"{integration_name} can't be reached in offline mode", context={"kind": "offline"})
+        if self.demo_mode and name != DEMO:
+            return Fail(
+                f"{integration_name} can't be synced into the demo database",
+                context={"kind": "demo_mode"},
+            )
+
+        integrations_result = await self.integration_service.get_integrations()
+        if not integrations_result.success:
+            return integrations_result
+        integration = next(
+            (i for i in integrations_result.data or [] if i["integrationName"].lower() == name),
+            None,
         )
+        if integration is None:
+            return Fail(f"Integration '{integration_name}' not found")
+        settings = dict(integration["integrationOptions"] or {})
+
+        accounts_result = await self.repository.get_accounts()
+        if not accounts_result.success:
+            return accounts_result
+        accounts = accounts_result.data or []
+        if not any(account.external_ids.get(name) for account in accounts):
+            return Fail(f"No {integration_name} accounts yet. Run 'tl sync' first.")
+
+        checkpoint = settings.get(BACKFILL_CHECKPOINT_KEY)
+        if resume:
+            if not checkpoint:
+                return Fail(f"There is no interrupted {integration_name} backfill to resume")
+            start = date.fromisoformat(checkpoint["start_date"])
+            end = date.fromisoformat(checkpoint["end_date"])
+            last_completed = checkpoint.get("last_completed_window")
+        else:
+            start, end = backfill_range(today or date.today(), months)
+            last_completed = None
+
+        windows = month_windows(start, end)
+        pending = [w for w in windows if last_completed is None or f"{w[0]:%Y-%m}" > last_completed]
+        resumed_after = last_completed
+        totals = {"discovered": 0, "new": 0, "skipped": 0}
+        warnings: List[str] = []
+
+        for index, (window_start, window_end) in enumerate(pending):
+            window = f"{window_start:%Y-%m}"
+            context.emit(
+                "window_start",
+                window=window,
+                start_date=window_start.isoformat(),
+                end_date=window_end.isoformat(),
+                number=len(windows) - len(pending) + index + 1,
+                total=len(windows),
+            )
+            for attempt in range(retries + 1):
+                if index or attempt:
+                    await sleep(delay)
+                result = await self.sync_transactions(
+                    name,
+                    start_date=datetime.combine(window_start, time.min, tzinfo=timezone.utc),
+                    end_date=datetime.combine(window_end, time.min, tzinfo=timezone.utc),
+                    provider_options=settings,
+                    accounts=accounts,
+                    context=context,
+                )
+                if result.success:
+                    break
+                if attempt < retries:
+                    context.emit("window_retry", window=window, attempt=attempt + 1, error=result.error)
+
+            if not result.success:
+                context.emit("window_failed", window=window, error=result.error)
+                saved = f"History through {last_completed} is saved. " if last_completed else ""
+                return Fail(
+                    f"Backfill stopped at {window} after {retries + 1} attempts: {result.error}. "
+                    f"{saved}Run 'tl backfill transactions --integration {name} --resume' to continue.",
+                    context={
+                        "kind": "backfill_interrupted",
+                        "failed_window": window,
+                        "last_completed_window": last_completed,
+                        **totals,
+                    },
+                )
+
+            stats = result.data["stats"]
+            for key in totals:
+                totals[key] += stats.get(key, 0)
+            for warning in result.data.get("provider_errors", []):
+                if warning not in warnings:
+                    warnings.append(warning)
+            last_completed = window
+
+            if not context.dry_run:
+                settings[BACKFILL_CHECKPOINT_KEY] = {
+                    "start_date": start.isoformat(),
+                    "end_date": end.isoformat(),
+                    "last_completed_window": window,
+                }
+                save_result = await self.repository.upsert_integration(
+                    integration["integrationName"], settings
+                )
+                if not save_result.success:
+                    return save_result
+            context.emit("window_done", window=window, new=stats.get("new", 0), skipped=stats.get("skipped", 0))
+
+        if not context.dry_run and BACKFILL_CHECKPOINT_KEY in settings:
+            settings.pop(BACKFILL_CHECKPOINT_KEY)
+            save_result = await self.repository.upsert_integration(
+                integration["integrationName"], settings
+            )
+            if not save_result.success:
+                return save_result
+
+        return Ok(
+            {
+                "integration": name,
+                "start_date": start,
+                "end_date": end - timedelta(days=1),
+                "windows": len(windows),
+                "windows_run": len(pending),
+                "resumed_after": resumed_after,
+                **totals,
+                "provider_warnings": warnings,
+                "dry_run": context.dry_run,
+            }
+        )
+
+    def _apply_category_map(
+        self, transactions: List[Transaction], context: SyncContext
+    ) -> Tuple[List[Transaction], str | None]:
+        """Turn provider categories into tags through the user's category map.
+
+        Categories without a mapping are recorded for `tl tag map suggest`,
+        except in a dry run, which also leaves a missing map file uncreated.
+        Returns the transactions and a warning if the map couldn't be used.
+        """
+        path = get_category_map_path()
+        try:
+            if context.dry_run and not path.exists():
+                category_map = CategoryMap(mappings=dict(DEFAULT_CATEGORY_MAP))
+            else:
+                category_map = load_category_map(path)
+        except (OSError, ValueError) as e:
+            return transactions, f"Provider categories were kept as tags: {e}"
+
+        mapped, unmapped = category_map.apply(transactions)
+        new_unmapped: Set[str] = unmapped - category_map.unmapped
+        if new_unmapped and not context.dry_run:
+            category_map.unmapped |= new_unmapped
+            try:
+                save_category_map(category_map, path)
+            except OSError as e:
+                return mapped, f"Unmapped provider categories were not recorded: {e}"
+        return mapped, None
 
     async def sync_balances(
         self, integration_name: str, provider_options: Dict[str, Any]
@@ -393,113 +800,484 @@ class SyncService:
         )
 
     async def sync_all_integrations(
-        self, dry_run: bool = False
+        self,
+        dry_run: bool = False,
+        on_event: Callable[[Dict[str, Any]], None] | None = None,
+        include_archived: bool = False,
     ) -> Result[Dict[str, Any]]:
-        """Sync all configured integrations for a user."""
+        """Sync all configured integrations for a user.
+
+        The result's top-level "dry_run" flag tells consumers whether anything
+        was written, and "overall_status" whether every integration synced
+        (see overall_sync_status); each entry in "results" has its own
+        "status". on_event receives progress events (see SyncContext).
+        Archived accounts are skipped unless include_archived is set. Against
+        the demo database every integration but demo is skipped, so real
+        credentials stored there by mistake never fetch real data.
+        """
+        context = SyncContext(
+            dry_run=dry_run, include_archived=include_archived, on_event=on_event
+        )
+
         # Get integrations from IntegrationService
         integrations_result = await self.integration_service.get_integrations()
         if not integrations_result.success:
             return integrations_result
 
-        integrations = integrations_result.data or []
+        # Synced, and reported, in name order so --json output is stable
+        integrations = sorted(
+            integrations_result.data or [], key=lambda i: i["integrationName"]
+        )
 
         if not integrations:
+            # Offline with only CSV/manual data: there is nothing to fetch
+            if is_offline():
+                return Result(
+                    success=True,
+                    data={
+                        "results": [],
+                        "overall_status": "ok",
+                        "new_accounts_without_type": [],
+                        "budget_alerts": [],
+                        "planned_satisfied": [],
+                        "month_end_snapshots": await self._add_month_end_snapshots(None, context),
+                        "cross_provider_duplicates": [],
+                        "duplicate_candidates_added": 0,
+                        "dry_run": context.dry_run,
+                    },
+                )
             return Result(success=False, error="No integrations configured")
 
+        # Load accounts once; kept up to date in memory as integrations are synced
+        accounts_result = await self.repository.get_accounts()
+        if not accounts_result.success:
+            return accounts_result
+        # An account left out here would be created again under a new ID
+        row_errors = (accounts_result.context or {}).get("row_errors")
+        if row_errors:
+            return Result(
+                success=False,
+                error="Sync stopped because some accounts could not be read: "
+                + "; ".join(row_errors),
+            )
+        known_accounts = {acc.id: acc for acc in accounts_result.data or []}
+
+        budget_before = await self._budget_status(context)
+
         sync_results = []
         all_new_accounts = []  # Track all new accounts across integrations
+        all_ingested: List[Transaction] = []
 
         for integration in integrations:
-            integration_name = integration["integrationName"]
-            integration_options = integration["integrationOptions"]
+            sync_result = await self._sync_integration(
+                integration["integrationName"],
+                integration["integrationOptions"],
+                known_accounts,
+                context,
+            )
+            # Collect new accounts that don't have account_type set
+            for account in sync_result.pop("new_accounts", []):
+                if account.account_type is None:
+                    all_new_accounts.append(account)
+            all_ingested.extend(sync_result.pop("ingested_transactions", []))
+            sync_result["status"] = integration_sync_status(sync_result)
+            sync_results.append(sync_result)
+
+        budget_alerts = await self._check_budget_alerts(budget_before)
+        planned_satisfied = await self._satisfy_planned(all_ingested, context)
+        month_end_snapshots = await self._add_month_end_snapshots(
+            list(known_accounts.values()), context
+        )
+        duplicates = await self._find_cross_provider_duplicates(
+            list(known_accounts.values()), integrations
+        )
+        duplicates, candidates_added = await self._queue_duplicates(duplicates, context)
 
-            # Sync accounts (skip in dry-run since we don't save them anyway)
-            provider_errors = []
-            if not dry_run:
-                accounts_result = await self.sync_accounts(
-                    integration_name, integration_options
-                )
+        return Result(
+            success=True,
+            data={
+                "results": sync_results,
+                "overall_status": overall_sync_status(sync_results),
+                "new_accounts_without_type": sorted(all_new_accounts, key=account_sort_key),
+                "budget_alerts": budget_alerts,
+                "planned_satisfied": planned_satisfied,
+                "month_end_snapshots": month_end_snapshots,
+                "cross_provider_duplicates": duplicates,
+                "duplicate_candidates_added": candidates_added,
+                "dry_run": context.dry_run,
+            },
+        )
 
-                if not accounts_result.success:
-                    sync_results.append(
-                        {
-                            "integration": integration_name,
-                            "accounts_synced": 0,
-                            "transactions_synced": 0,
-                            "error": accounts_result.error,
-                        }
-                    )
-                    continue
-
-                num_accounts = len(accounts_result.data.get("ingested_accounts", []))
-                new_accounts = accounts_result.data.get("new_accounts", [])
-                provider_errors.extend(accounts_result.data.get("provider_errors", []))
-                # Collect new accounts that don't have account_type set
-                for account in new_accounts:
-                    if account.account_type is None:
-                        all_new_accounts.append(account)
-            else:
-                num_accounts = 0  # Don't sync accounts in dry-run
-
-            # Calculate date range for transactions
-            date_range_result = await self._calculate_sync_date_range()
-            if not date_range_result.success:
-                sync_results.append(
-                    {
-                        "integration": integration_name,
-                        "accounts_synced": num_accounts,
-                        "transactions_synced": 0,
-                        "error": "Failed to calculate sync date range",
-                    }
-                )
-                continue
+    async def _find_cross_provider_duplicates(
+        self, accounts: List[Account], integrations: List[Dict[str, Any]]
+    ) -> List[Dict[str, Any]]:
+        """Report accounts that two integrations both sync as separate accounts.
+
+        Candidates match on institution, normalized name and currency, and are
+        reported when they share enough transactions. Nothing is changed: the
+        report suggests turning off sync for the newer account, which stops
+        the duplication without touching existing data. Pairs where either
+        side already has sync disabled are not reported. Best effort: a
+        failed lookup skips the pair.
+        """
+        sync_disabled = {
+            (integration["integrationName"].lower(), provider_account_id)
+            for integration in integrations
+            for provider_account_id, settings in (
+                integration["integrationOptions"].get("accountSettings", {}).items()
+            )
+            if settings.get("syncDisabled", False)
+        }
 
-            date_range = date_range_result.data
+        def disabled(account: Account) -> bool:
+            return any(item in sync_disabled for item in account.external_ids.items())
 
-            # Sync transactions
-            transactions_result = await self.sync_transactions(
-                integration_name,
-                start_date=date_range["start_date"],
-                end_date=date_range["end_date"],
-                provider_options=integration_options,
-                dry_run=dry_run,
+        duplicates = []
+        for keep, duplicate in find_duplicate_account_candidates(accounts):
+            if disabled(keep) or disabled(duplicate):
+                continue
+            overlap_result = await self.repository.get_transaction_overlap(keep.id, duplicate.id)
+            if not overlap_result.success or not is_duplicate_overlap(overlap_result.data):
+                continue
+            overlap = overlap_result.data
+            duplicates.append(
+                {
+                    "keep": {
+                        "account_id": str(keep.id),
+                        "name": keep.name,
+                        "integrations": sorted(keep.external_ids),
+                        "transactions": overlap["a"],
+                    },
+                    "duplicate": {
+                        "account_id": str(duplicate.id),
+                        "name": duplicate.name,
+                        "integrations": sorted(duplicate.external_ids),
+                        "transactions": overlap["b"],
+                    },
+                    "shared_transactions": overlap["shared"],
+                    "suggested_command": f"tl accounts disable-sync {duplicate.id}",
+                }
             )
+        return duplicates
 
-            if not transactions_result.success:
-                sync_results.append(
-                    {
-                        "integration": integration_name,
-                        "accounts_synced": num_accounts,
-                        "transactions_synced": 0,
-                        "sync_type": date_range["sync_type"],
-                        "error": transactions_result.error,
-                    }
-                )
-                continue
+    async def _queue_duplicates(
+        self, duplicates: List[Dict[str, Any]], context: SyncContext
+    ) -> Tuple[List[Dict[str, Any]], int]:
+        """Record possible duplicates in the review queue (tl dedupe review).
 
-            num_transactions = len(
-                transactions_result.data.get("ingested_transactions", [])
-            )
-            tx_stats = transactions_result.data.get("stats", {})
-            provider_errors.extend(transactions_result.data.get("provider_errors", []))
+        Returns the cross-provider duplicates still worth reporting (pairs
+        the user dismissed are dropped) and how many candidates were newly
+        queued, accounts and transactions together. Best effort: a failure
+        queues nothing and reports every duplicate. A dry run queues nothing.
+        """
+        if self.dedupe_service is None or context.dry_run:
+            return duplicates, 0
+
+        added = 0
+        record_result = await self.dedupe_service.record_account_duplicates(duplicates)
+        if record_result.success:
+            duplicates = record_result.data["duplicates"]
+            added += record_result.data["added"]
+        scan_result = await self.dedupe_service.scan_transactions()
+        if scan_result.success:
+            added += scan_result.data["added"]
+        return duplicates, added
+
+    async def _add_month_end_snapshots(
+        self, accounts: List[Account] | None, context: SyncContext
+    ) -> Dict[str, Any]:
+        """Snapshot every account on the last month-end, unless turned off.
+
+        Runs after the integrations so the month-end balance is reconstructed
+        from freshly synced data. Accounts that already have a snapshot that
+        day are skipped, so repeated syncs create at most one per account.
+        Best effort: a failure is reported in the "error" key, not raised.
+        """
+        month_end = previous_month_end(date.today())
+        summary: Dict[str, Any] = {"date": month_end, "created": 0}
+        if not get_auto_month_end_snapshots():
+            return {**summary, "skipped": "disabled"}
 
-            sync_results.append(
-                {
-                    "integration": integration_name,
-                    "accounts_synced": num_accounts,
-                    "transactions_synced": num_transactions,
-                    "transaction_stats": tx_stats,
-                    "sync_type": date_range["sync_type"],
-                    "start_date": date_range["start_date"],
-                    "end_date": date_range["end_date"],
-                    "provider_warnings": provider_errors,
-                }
+        snapshots_result = await self.account_service.add_month_end_snapshots(
+            month_end, accounts, dry_run=context.dry_run
+        )
+        if not snapshots_result.success:
+            return {**summary, "error": snapshots_result.error}
+        return {**summary, "created": len(snapshots_result.data)}
+
+    async def _satisfy_planned(
+        self, transactions: List[Transaction], context: SyncContext
+    ) -> List[Dict[str, Any]]:
+        """Advance planned transactions whose occurrence arrived in this sync.
+
+        Best effort: a failure leaves the planned items as they were. A dry
+        run reports the matches without advancing anything.
+        """
+        if self.planned_service is None:
+            return []
+        satisfy_result = await self.planned_service.satisfy(transactions, dry_run=context.dry_run)
+        if not satisfy_result.success:
+            return []
+        for entry in satisfy_result.data:
+            context.emit("planned_satisfied", **entry)
+        return satisfy_result.data
+
+    async def _budget_status(self, context: SyncContext) -> Dict[str, Any] | None:
+        """This month's budget status before syncing, when budget alerts are on.
+
+        Dry runs never alert, since recording the alert level is a write.
+        """
+        if self.budget_service is None or context.dry_run or not get_budget_alerts_enabled():
+            return None
+        month = date.today().strftime("%Y-%m")
+        status_result = await self.budget_service.status(month)
+        if not status_result.success:
+            return None
+        return {"month": month, "entries": status_result.data}
+
+    async def _check_budget_alerts(
+        self, budget_before: Dict[str, Any] | None
+    ) -> List[Dict[str, Any]]:
+        """Alert on expense categories this sync pushed (further) over budget.
+
+        Best effort: a failed lookup skips the check. Alert levels are
+        recorded so the same overage is not reported again next sync.
+        """
+        if budget_before is None:
+            return []
+        status_result = await self.budget_service.status(budget_before["month"])
+        if not status_result.success:
+            return []
+
+        alerts, levels = find_budget_alerts(
+            budget_before["month"], budget_before["entries"], status_result.data
+        )
+        if levels and not (await self.budget_service.record_alert_levels(levels)).success:
+            return []  # Reporting without recording would repeat the alerts every sync
+
+        currency_result = self.preferences_service.get_currency()
+        currency = currency_result.data if currency_result.success else "USD"
+        return [
+            {**asdict(alert), "message": describe_budget_alert(alert, currency)}
+            for alert in alerts
+        ]
+
+    async def _sync_integration(
+        self,
+        integration_name: str,
+        integration_options: Dict[str, Any],
+        known_accounts: Dict[Any, Account],
+        context: SyncContext,
+    ) -> Dict[str, Any]:
+        """Sync one integration and return its entry for the results list.
+
+        known_accounts is updated in place with the accounts this sync ingested.
+        Failures are reported in the entry's "error" key rather than raised.
+        """
+        failed = {
+            "integration": integration_name,
+            "accounts_synced": 0,
+            "transactions_synced": 0,
+        }
+        context.emit("integration_start", name=integration_name)
+
+        data_provider = self._get_provider(integration_name)
+        if not data_provider and not is_enabled(integration_name):
+            # Configured, but this build leaves the provider out
+            return {**failed, "skipped": "disabled", "reason": disabled_error(integration_name)}
+        if not data_provider:
+            return {**failed, "error": f"Unknown integration: {integration_name}"}
+
+        if data_provider.requires_network and is_offline():
+            return {**failed, "skipped": "offline"}
+
+        if self.demo_mode and integration_name.lower() != DEMO:
+            return {**failed, "skipped": "demo_mode"}
+
+        # Calculate date range for transactions
+        date_range_result = await self._calculate_sync_date_range()
+        if not date_range_result.success:
+            return {**failed, "error": "Failed to calculate sync date range"}
+
+        date_range = date_range_result.data
+
+        # One provider round trip covers both accounts and transactions
+        fetch_result = await data_provider.fetch(
+            date_range["start_date"], date_range["end_date"], integration_options
+        )
+        if not fetch_result.success:
+            return {
+                **failed,
+                "sync_type": date_range["sync_type"],
+                "error": fetch_result.error,
+            }
+
+        fetched = fetch_result.data
+        context.emit(
+            "accounts_fetched", name=integration_name, count=len(fetched.get("accounts", []))
+        )
+
+        # Held from the account upserts through the transaction inserts, so a
+        # concurrent import or sync doesn't insert the same rows in between
+        async with write_phase(self.write_lock, "sync", skip=context.dry_run) as locked:
+            if not locked.success:
+                return {**failed, "sync_type": date_range["sync_type"], "error": locked.error}
+            return await self._store_fetched(
+                integration_name, integration_options, fetched, date_range, known_accounts, context
             )
 
-        return Result(
-            success=True,
-            data={
-                "results": sync_results,
-                "new_accounts_without_type": all_new_accounts,
+    async def _store_fetched(
+        self,
+        integration_name: str,
+        integration_options: Dict[str, Any],
+        fetched: Dict[str, Any],
+        date_range: Dict[str, Any],
+        known_accounts: Dict[Any, Account],
+        context: SyncContext,
+    ) -> Dict[str, Any]:
+        """Write one integration's fetched accounts and transactions; see _sync_integration."""
+        failed = {
+            "integration": integration_name,
+            "accounts_synced": 0,
+            "transactions_synced": 0,
+        }
+
+        # Sync accounts; a dry run resolves them in memory only, so transactions
+        # for accounts that would be created still map in the preview
+        accounts_result = await self.sync_accounts(
+            integration_name,
+            integration_options,
+            existing_accounts=list(known_accounts.values()),
+            fetched={
+                "accounts": fetched.get("accounts", []),
+                "errors": fetched.get("errors", []),
+                "balance_dates": fetched.get("balance_dates", {}),
             },
+            context=context,
+        )
+
+        if not accounts_result.success:
+            return {**failed, "error": accounts_result.error}
+
+        for account in accounts_result.data.get("ingested_accounts", []):
+            known_accounts[account.id] = account
+
+        num_accounts = len(accounts_result.data.get("ingested_accounts", []))
+        new_accounts = accounts_result.data.get("new_accounts", [])
+        provider_errors = list(accounts_result.data.get("provider_errors", []))
+
+        # Sync transactions; provider warnings were already collected above
+        transactions_result = await self.sync_transactions(
+            integration_name,
+            start_date=date_range["start_date"],
+            end_date=date_range["end_date"],
+            provider_options=integration_options,
+            accounts=list(known_accounts.values()),
+            fetched={"transactions": fetched.get("transactions", []), "errors": []},
+            context=context,
+        )
+
+        if not transactions_result.success:
+            return {
+                **failed,
+                "accounts_synced": num_accounts,
+                "sync_type": date_range["sync_type"],
+                "error": transactions_result.error,
+                "new_accounts": new_accounts,
+            }
+
+        num_transactions = len(
+            transactions_result.data.get("ingested_transactions", [])
+        )
+        tx_stats = transactions_result.data.get("stats", {})
+        context.emit(
+            "transactions_inserted",
+            name=integration_name,
+            new=tx_stats.get("new", num_transactions),
+            skipped=tx_stats.get("skipped", 0),
+        )
+        provider_errors.extend(transactions_result.data.get("provider_errors", []))
+
+        balance_changes = await self._check_balance_changes(
+            integration_name,
+            integration_options,
+            accounts_result.data,
+            transactions_result.data.get("ingested_transactions", []),
+            context,
+        )
+
+        # Persist warnings so degraded connections stay visible after this run
+        if not context.dry_run:
+            record_result = await self.warning_service.record_sync_warnings(
+                integration_name, provider_errors
+            )
+            if not record_result.success:
+                provider_errors = provider_errors + [
+                    f"Sync warnings were not saved: {record_result.error}"
+                ]
+
+        return {
+            "integration": integration_name,
+            "accounts_synced": num_accounts,
+            "transactions_synced": num_transactions,
+            "transaction_stats": tx_stats,
+            "sync_type": date_range["sync_type"],
+            "start_date": date_range["start_date"],
+            "end_date": date_range["end_date"],
+            "provider_warnings": provider_errors,
+            "unexplained_balance_changes": balance_changes,
+            "new_accounts": new_accounts,
+            "ingested_transactions": transactions_result.data.get("ingested_transactions", []),
+        }
+
+    async def _check_balance_changes(
+        self,
+        integration_name: str,
+        integration_options: Dict[str, Any],
+        accounts_data: Dict[str, Any],
+        ingested_transactions: List[Transaction],
+        context: SyncContext,
+    ) -> List[Dict[str, Any]]:
+        """Find accounts whose balance moved more than their known transactions.
+
+        Compares each synced balance with the account's previous snapshot
+        (one query for all accounts) and the transactions in between.
+        Balances-only accounts are skipped since their transactions are not
+        synced. Best effort: a failed lookup skips the check.
+        """
+        balance_dates = accounts_data.get("balance_dates", {})
+        account_settings = integration_options.get("accountSettings", {})
+        integration_name_lower = integration_name.lower()
+        accounts = [
+            account
+            for account in accounts_data.get("discovered_accounts", [])
+            if account.balance is not None
+            and account.id in balance_dates
+            and not account_settings.get(
+                account.external_ids.get(integration_name_lower), {}
+            ).get("balancesOnly", False)
+        ]
+        if not accounts:
+            return []
+
+        previous_result = await self.repository.get_latest_balance_snapshots(
+            {account.id: balance_dates[account.id] for account in accounts}
+        )
+        if not previous_result.success or not previous_result.data:
+            return []
+        previous = previous_result.data
+
+        start = min(snapshot.snapshot_time.date() for snapshot in previous.values())
+        end = max(balance_dates[account_id] for account_id in previous)
+        transactions_result = await self.repository.get_transactions_in_range(start, end)
+        if not transactions_result.success:
+            return []
+        transactions = list(transactions_result.data or [])
+        if context.dry_run:
+            # Nothing was written; count what this sync would have added
+            transactions.extend(ingested_transactions)
+
+        changes = find_unexplained_balance_changes(
+            sorted(accounts, key=account_sort_key), balance_dates, previous, transactions, get_balance_check_tolerance()
         )
+        return [asdict(change) for change in changes]
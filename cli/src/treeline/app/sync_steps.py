@@ -0,0 +1,322 @@
+"""Pure steps of a provider sync: account matching, transaction mapping, dedup,
+balance change attribution, duplicate account detection.
+
+SyncService composes these with provider IO and persistence. Keeping them free
+of IO lets account-matching bug reports be reproduced in plain unit tests.
+"""
+
+from dataclasses import dataclass, field
+from datetime import date, timedelta
+from decimal import Decimal
+from typing import Any, Dict, Iterable, List, Set, Tuple
+from uuid import UUID
+
+from treeline.domain import Account, BalanceSnapshot, Fail, Ok, Result, Transaction
+
+# Two accounts from different integrations are reported as duplicates when
+# they share at least this many transactions, making up at least this share
+# of the account with fewer transactions
+DUPLICATE_ACCOUNT_MIN_SHARED = 3
+DUPLICATE_ACCOUNT_MIN_OVERLAP = 0.5
+
+
+@dataclass
+class AccountMatchOutcome:
+    """Discovered accounts resolved against the accounts already stored."""
+
+    # Every discovered account; matched ones carry the existing account's ID
+    accounts: List[Account] = field(default_factory=list)
+    # Discovered accounts with no stored counterpart
+    new_accounts: List[Account] = field(default_factory=list)
+
+
+@dataclass(frozen=True)
+class UnexplainedBalanceChange:
+    """A balance that moved by more than the transactions known for the period."""
+
+    account_id: UUID
+    account_name: str
+    currency: str
+    previous_date: date
+    previous_balance: Decimal
+    balance_date: date
+    balance: Decimal
+    # balance - previous_balance
+    balance_change: Decimal
+    # Sum of transactions dated after previous_date, up to balance_date
+    transactions_total: Decimal
+    # balance_change - transactions_total
+    unexplained_delta: Decimal
+
+
+@dataclass
+class TransactionMapOutcome:
+    """Provider transactions re-pointed at internal account IDs."""
+
+    transactions: List[Transaction] = field(default_factory=list)
+    # (provider account ID, transaction) pairs whose account is not known
+    unmapped: List[Tuple[str, Transaction]] = field(default_factory=list)
+
+
+def match_accounts(
+    discovered: List[Account], existing: List[Account], provider_key: str
+) -> Result[AccountMatchOutcome]:
+    """Match discovered accounts to stored ones by their provider external ID.
+
+    A match keeps the stored account's ID, nickname, account type and archived
+    state, and takes everything else (name, balance, institution) from the
+    provider. Two discovered accounts with the same external ID are an error
+    rather than a silent last-one-wins.
+    """
+    seen: Dict[str, Account] = {}
+    for account in discovered:
+        ext_id = account.external_ids.get(provider_key)
+        if not ext_id:
+            continue
+        if ext_id in seen:
+            return Fail(
+                f"{provider_key} returned two accounts with ID {ext_id}: "
+                f"'{seen[ext_id].name}' and '{account.name}'"
+            )
+        seen[ext_id] = account
+
+    # First stored account wins if the database already holds a duplicate
+    existing_by_ext_id: Dict[str, Account] = {}
+    for account in existing:
+        ext_id = account.external_ids.get(provider_key)
+        if ext_id and ext_id not in existing_by_ext_id:
+            existing_by_ext_id[ext_id] = account
+
+    outcome = AccountMatchOutcome()
+    for account in discovered:
+        ext_id = account.external_ids.get(provider_key)
+        match = existing_by_ext_id.get(ext_id) if ext_id else None
+        if match is None:
+            outcome.accounts.append(account)
+            outcome.new_accounts.append(account)
+            continue
+
+        outcome.accounts.append(
+            account.model_copy(
+                update={
+                    "id": match.id,
+                    "nickname": match.nickname or account.nickname,
+                    "account_type": match.account_type or account.account_type,
+                    "archived_at": match.archived_at,
+                    # The stored account keeps its created_at on upsert
+                    "created_at": match.created_at,
+                }
+            )
+        )
+
+    return Ok(outcome)
+
+
+def map_transactions(
+    items: Iterable[Any],
+    account_id_map: Dict[str, UUID],
+    skip_account_ids: Set[str] = frozenset(),
+) -> TransactionMapOutcome:
+    """Point provider transactions at internal account IDs.
+
+    Items are either (provider_account_id, Transaction) pairs (SimpleFIN) or
+    Transactions whose account_id is already set (CSV). Pairs for accounts in
+    skip_account_ids (balances-only) are dropped; pairs for accounts missing
+    from account_id_map are returned as unmapped.
+    """
+    outcome = TransactionMapOutcome()
+    for item in items:
+        if not isinstance(item, tuple):
+            outcome.transactions.append(item)
+            continue
+
+        provider_acc_id, tx = item
+        if provider_acc_id in skip_account_ids:
+            continue
+
+        internal_acc_id = account_id_map.get(provider_acc_id)
+        if not internal_acc_id:
+            outcome.unmapped.append((provider_acc_id, tx))
+            continue
+
+        # Rebuild rather than model_copy so @model_validator recalculates the
+        # fingerprint for the new account_id
+        tx_dict = tx.model_dump()
+        tx_dict["account_id"] = internal_acc_id
+        tx_dict["external_ids"] = {
+            k: v for k, v in tx_dict["external_ids"].items() if k != "fingerprint"
+        }
+        outcome.transactions.append(Transaction(**tx_dict))
+
+    return outcome
+
+
+def partition_new(
+    mapped: List[Transaction],
+    existing_by_ext_id: Dict[str, Transaction],
+    provider_key: str,
+) -> Tuple[List[Transaction], List[Transaction]]:
+    """Split mapped transactions into (new, skipped).
+
+    Transactions already stored under the same provider external ID are
+    skipped, never overwritten, so user edits like tags survive a re-sync.
+    """
+    new: List[Transaction] = []
+    skipped: List[Transaction] = []
+    for tx in mapped:
+        ext_id = tx.external_ids.get(provider_key)
+        if ext_id and ext_id in existing_by_ext_id:
+            skipped.append(tx)
+        else:
+            new.append(tx)
+    return new, skipped
+
+
+def find_unexplained_balance_changes(
+    accounts: List[Account],
+    balance_dates: Dict[UUID, date],
+    previous_snapshots: Dict[UUID, BalanceSnapshot],
+    transactions: Iterable[Transaction],
+    tolerance: Decimal,
+) -> List[UnexplainedBalanceChange]:
+    """Compare each account's balance change with its transactions.
+
+    For an account with a provider balance on balance_dates[id] and an earlier
+    snapshot, the balance should have moved by the sum of the transactions
+    dated after the snapshot's day, up to and including the balance date.
+    Differences larger than tolerance point at missing (or extra)
+    transactions. Accounts without a balance or an earlier snapshot are
+    skipped, as are deleted transactions.
+    """
+    totals: Dict[UUID, Decimal] = {}
+    windows: Dict[UUID, Tuple[date, date]] = {}
+    for account in accounts:
+        previous = previous_snapshots.get(account.id)
+        balance_date = balance_dates.get(account.id)
+        if account.balance is None or previous is None or balance_date is None:
+            continue
+        if previous.snapshot_time.date() >= balance_date:
+            continue
+        windows[account.id] = (previous.snapshot_time.date(), balance_date)
+        totals[account.id] = Decimal("0.00")
+
+    for tx in transactions:
+        window = windows.get(tx.account_id)
+        if window is None or tx.deleted_at is not None:
+            continue
+        if window[0] < tx.transaction_date <= window[1]:
+            totals[tx.account_id] += tx.amount
+
+    changes = []
+    for account in accounts:
+        if account.id not in windows:
+            continue
+        previous = previous_snapshots[account.id]
+        balance_change = account.balance - previous.balance
+        unexplained = balance_change - totals[account.id]
+        if abs(unexplained) <= tolerance:
+            continue
+        previous_date, balance_date = windows[account.id]
+        changes.append(
+            UnexplainedBalanceChange(
+                account_id=account.id,
+                account_name=account.nickname or account.name,
+                currency=account.currency or "USD",
+                previous_date=previous_date,
+                previous_balance=previous.balance,
+                balance_date=balance_date,
+                balance=account.balance,
+                balance_change=balance_change,
+                transactions_total=totals[account.id],
+                unexplained_delta=unexplained,
+            )
+        )
+    return changes
+
+
+def previous_month_end(today: date) -> date:
+    """The last day of the most recent month that has ended.
+
+    On the last day of a month the month isn't over yet, so that day's
+    month-end is not returned until the next day.
+    """
+    return today.replace(day=1) - timedelta(days=1)
+
+
+
+def backfill_range(today: date, months: int) -> Tuple[date, date]:
+    """The span a history backfill of `months` months covers, end exclusive.
+
+    It starts on the first day of the month months - 1 before today's, so
+    the current month counts as one, and ends tomorrow so today is included.
+    """
+    month_index = today.year * 12 + today.month - 1 - (max(months, 1) - 1)
+    start = date(month_index // 12, month_index % 12 + 1, 1)
+    return start, today + timedelta(days=1)
+
+
+def month_windows(start: date, end: date) -> List[Tuple[date, date]]:
+    """Calendar-month windows covering start..end (exclusive), oldest first.
+
+    Each window runs from its first day to the first day of the next month,
+    clamped to start and end, so the first and last may be partial months.
+    """
+    windows = []
+    window_start = start
+    while window_start < end:
+        next_month = (window_start.replace(day=1) + timedelta(days=32)).replace(day=1)
+        window_end = min(next_month, end)
+        windows.append((window_start, window_end))
+        window_start = window_end
+    return windows
+
+
+def _duplicate_account_key(account: Account) -> Tuple[str, str, str] | None:
+    """Institution, name and currency as compared across integrations.
+
+    Names are compared case-insensitively, ignoring punctuation and spacing
+    ("Total Checking" matches "TOTAL CHECKING."). Accounts without an
+    institution, or that no integration syncs, have no key.
+    """
+    institution = (account.institution_domain or account.institution_name or "").strip().lower()
+    if not institution or not account.external_ids:
+        return None
+    name = "".join(ch for ch in account.name.casefold() if ch.isalnum())
+    return institution, name, (account.currency or "USD").upper()
+
+
+def find_duplicate_account_candidates(accounts: Iterable[Account]) -> List[Tuple[Account, Account]]:
+    """Pairs of accounts from different integrations that look like the same real account.
+
+    Accounts match on institution domain (or name), normalized account name
+    and currency; two accounts synced by the same integration are different
+    accounts at that provider, so never a pair. Each pair is (older, newer)
+    by created_at; whether it's a duplicate is decided by how many
+    transactions the two share (see is_duplicate_overlap).
+    """
+    groups: Dict[Tuple[str, str, str], List[Account]] = {}
+    for account in accounts:
+        key = _duplicate_account_key(account)
+        if key is not None:
+            groups.setdefault(key, []).append(account)
+
+    pairs = []
+    for group in groups.values():
+        group = sorted(group, key=lambda a: (a.created_at, str(a.id)))
+        for i, older in enumerate(group):
+            for newer in group[i + 1 :]:
+                if not set(older.external_ids) & set(newer.external_ids):
+                    pairs.append((older, newer))
+    return pairs
+
+
+def is_duplicate_overlap(overlap: Dict[str, int]) -> bool:
+    """Whether two accounts share enough transactions to be the same account.
+
+    overlap is the result of Repository.get_transaction_overlap.
+    """
+    smaller = min(overlap["a"], overlap["b"])
+    return (
+        overlap["shared"] >= DUPLICATE_ACCOUNT_MIN_SHARED
+        and overlap["shared"] >= smaller * DUPLICATE_ACCOUNT_MIN_OVERLAP
+    )
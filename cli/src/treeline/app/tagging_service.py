@@ -1,10 +1,10 @@
 """Service for managing transaction tagging operations."""
 
-from typing import List
+from typing import Any, Dict, List, Set
 from uuid import UUID
 
 from treeline.abstractions import Repository
-from treeline.domain import Result, Transaction
+from treeline.domain import Result, Transaction, normalize_tag, normalize_tags
 
 
 class TaggingService:
@@ -23,6 +23,8 @@ class TaggingService:
     ) -> Result[Transaction]:
         """Update tags for a single transaction.
 
+        Tags are stored normalized (see normalize_tag).
+
         Args:
             transaction_id: Transaction ID to update
             tags: New list of tags (replaces existing tags)
@@ -30,4 +32,114 @@ class TaggingService:
         Returns:
             Result containing updated Transaction object
         """
-        return await self.repository.update_transaction_tags(transaction_id, tags)
+        return await self.repository.update_transaction_tags(
+            transaction_id, normalize_tags(tags)
+        )
+
+    async def normalize_stored_tags(self, dry_run: bool = True) -> Result[Dict[str, Any]]:
+        """Rewrite tags stored before normalization in their normalized form.
+
+        Covers every transaction, including deleted ones.
+
+        Args:
+            dry_run: Report what would change without writing
+
+        Returns:
+            Result containing dict with dry_run, transactions (number
+            rewritten, or that would be) and tags: one entry per normalized
+            tag that had other spellings, with its variants (as stored,
+            sorted) and the number of transactions using any of them
+        """
+        result = await self.repository.execute_query(
+            "SELECT transaction_id, tags FROM sys_transactions WHERE len(tags) > 0"
+        )
+        if not result.success:
+            return result
+
+        updates: Dict[UUID, List[str]] = {}
+        variants: Dict[str, Set[str]] = {}
+        affected: Dict[str, Set[str]] = {}
+        for transaction_id, tags in result.data["rows"]:
+            normalized = normalize_tags(tags)
+            if normalized == list(tags):
+                continue
+            updates[UUID(transaction_id)] = normalized
+            for tag in tags:
+                key = normalize_tag(tag)
+                if key and tag != key:
+                    variants.setdefault(key, set()).add(tag)
+                    affected.setdefault(key, set()).add(transaction_id)
+
+        if updates and not dry_run:
+            update_result = await self.repository.bulk_update_transaction_tags(updates)
+            if not update_result.success:
+                return update_result
+
+        return Result(
+            success=True,
+            data={
+                "dry_run": dry_run,
+                "transactions": len(updates),
+                "tags": [
+                    {
+                        "tag": tag,
+                        "variants": sorted(variants[tag]),
+                        "transactions": len(affected[tag]),
+                    }
+                    for tag in sorted(variants)
+                ],
+            },
+        )
+
+    async def count_tags(self, tags: List[str]) -> Result[Dict[str, int]]:
+        """Count the transactions (not deleted) that carry each of the given tags.
+
+        Returns:
+            Result containing dict of tag -> transaction count, for tags used at all
+        """
+        wanted = set(normalize_tags(tags))
+        if not wanted:
+            return Result(success=True, data={})
+
+        result = await self.repository.execute_query(
+            "SELECT tags FROM sys_transactions WHERE len(tags) > 0 AND deleted_at IS NULL"
+        )
+        if not result.success:
+            return result
+
+        counts: Dict[str, int] = {}
+        for (row_tags,) in result.data["rows"]:
+            for tag in wanted.intersection(row_tags):
+                counts[tag] = counts.get(tag, 0) + 1
+        return Result(success=True, data=counts)
+
+    async def replace_tag(self, old: str, new: str) -> Result[int]:
+        """Replace a tag with another on every transaction that has it.
+
+        Covers every transaction, including deleted ones. The tag keeps its
+        position; if the transaction already has the new tag, the old one is
+        just dropped.
+
+        Returns:
+            Result containing the number of transactions rewritten
+        """
+        old, new = normalize_tag(old), normalize_tag(new)
+        if not old or not new or old == new:
+            return Result(success=True, data=0)
+
+        result = await self.repository.execute_query(
+            "SELECT transaction_id, tags FROM sys_transactions WHERE len(tags) > 0"
+        )
+        if not result.success:
+            return result
+
+        updates: Dict[UUID, List[str]] = {
+            UUID(transaction_id): normalize_tags(new if tag == old else tag for tag in tags)
+            for transaction_id, tags in result.data["rows"]
+            if old in tags
+        }
+        if updates:
+            update_result = await self.repository.bulk_update_transaction_tags(updates)
+            if not update_result.success:
+                return update_result
+        return Result(success=True, data=len(updates))
@@ -0,0 +1,459 @@
+"""Service for looking up individual transactions."""
+
+import json
+from datetime import date, datetime, timezone
+from decimal import Decimal
+from typing import Any, Awaitable, Callable, Dict, List, Tuple
+from uuid import UUID, uuid4
+
+from treeline.abstractions import Repository
+from treeline.domain import Fail, Ok, Result, Transaction, TransactionStatus, apply_default_tags
+
+# Shortest prefix accepted for ID lookup, to avoid matching half the table
+MIN_ID_PREFIX_LENGTH = 4
+
+# --provider value that searches every external ID key
+ANY_PROVIDER = "any"
+
+# external_ids keys holding provenance rather than an ID; --provider any skips them
+NON_ID_KEYS = frozenset({"csv_source"})
+
+
+async def find_by_external_id(
+    repository: Repository,
+    table: str,
+    provider: str,
+    value: str,
+    lookup: Callable[[str, List[str]], Awaitable[Result[List[Any]]]],
+) -> Result[List[Tuple[Any, str]]]:
+    """Look up accounts or transactions by an external ID.
+
+    Each key is searched with the keyed repository lookup (lookup), so the
+    value is compared exactly and only under that key. provider "any"
+    searches every key in use in table ("accounts" or "transactions").
+
+    Returns (item, matched key) pairs; nothing found fails with context
+    {"kind": "not_found"}.
+    """
+    provider = (provider or "").strip()
+    value = (value or "").strip()
+    if not provider:
+        return Fail("Provider cannot be empty (use a key such as simplefin, or any)")
+    if not value:
+        return Fail("External ID cannot be empty")
+
+    if provider.lower() == ANY_PROVIDER:
+        keys_result = await repository.list_external_id_keys(table)
+        if not keys_result.success:
+            return keys_result
+        keys = [key for key in keys_result.data if key not in NON_ID_KEYS]
+    else:
+        keys = [provider]
+
+    matches = []
+    for key in keys:
+        lookup_result = await lookup(key, [value])
+        if not lookup_result.success:
+            return lookup_result
+        matches.extend((item, key) for item in lookup_result.data)
+
+    if not matches:
+        where = "any external ID key" if provider.lower() == ANY_PROVIDER else provider
+        return Fail(
+            f"No {table[:-1]} has external ID {value!r} under {where}",
+            context={"kind": "not_found", "provider": provider, "id": value},
+        )
+    return Ok(matches)
+
+
+def nest_split_parts(
+    transactions: List[Transaction], parents: Dict[UUID, Transaction]
+) -> List[Tuple[Transaction, int]]:
+    """Order transactions so split parts follow the transaction they were split from.
+
+    Each split is placed where its parent or first part appears in the
+    list: the parent at depth 0 (from parents when it isn't in the list,
+    e.g. because splitting deleted it), then its parts at depth 1. Parts
+    whose parent can't be found stay at depth 0.
+
+    Args:
+        transactions: Transactions in display order
+        parents: Split parents by ID (need not include ones already listed)
+
+    Returns:
+        (transaction, depth) pairs
+    """
+    listed = {tx.id: tx for tx in transactions}
+    parts: Dict[UUID, List[Transaction]] = {}
+    for tx in transactions:
+        if tx.parent_transaction_id:
+            parts.setdefault(tx.parent_transaction_id, []).append(tx)
+
+    rows: List[Tuple[Transaction, int]] = []
+    placed = set()
+    for tx in transactions:
+        key = tx.parent_transaction_id or (tx.id if tx.id in parts else None)
+        if key is None:
+            rows.append((tx, 0))
+            continue
+        if key in placed:
+            continue
+        placed.add(key)
+        parent = listed.get(key) or parents.get(key)
+        if parent is not None:
+            rows.append((parent, 0))
+        rows.extend((part, 0 if parent is None else 1) for part in parts[key])
+    return rows
+
+
+class TransactionService:
+    """Service for looking up, entering and clearing individual transactions."""
+
+    def __init__(self, repository: Repository):
+        self.repository = repository
+
+    async def resolve_transaction_id(self, id_or_prefix: str) -> Result[UUID]:
+        """Resolve a full transaction ID or an unambiguous ID prefix.
+
+        An ambiguous prefix fails with context {"matches": [...]} listing the
+        candidate IDs.
+        """
+        value = id_or_prefix.strip().lower()
+
+        try:
+            return Ok(UUID(value))
+        except ValueError:
+            pass
+
+        if len(value) < MIN_ID_PREFIX_LENGTH:
+            return Fail(
+                f"Transaction ID prefix must be at least {MIN_ID_PREFIX_LENGTH} characters"
+            )
+
+        matches_result = await self.repository.find_transaction_ids_by_prefix(value)
+        if not matches_result.success:
+            return matches_result
+
+        matches = matches_result.data
+        if not matches:
+            return Fail(f"No transaction matches ID: {id_or_prefix}")
+        if len(matches) > 1:
+            return Fail(
+                f"Ambiguous transaction ID prefix: {id_or_prefix}",
+                context={"matches": [str(m) for m in matches]},
+            )
+        return Ok(matches[0])
+
+    async def find_by_external_id(
+        self, provider: str, value: str
+    ) -> Result[List[Tuple[Transaction, str]]]:
+        """Find transactions (deleted ones included) by a provider's external ID.
+
+        provider is an external_ids key such as simplefin, or "any". Returns
+        (transaction, matched key) pairs; see find_by_external_id.
+        """
+        return await find_by_external_id(
+            self.repository,
+            "transactions",
+            provider,
+            value,
+            self.repository.get_transactions_by_external_ids,
+        )
+
+    async def get_transaction_details(self, id_or_prefix: str) -> Result[Dict[str, Any]]:
+        """Get a transaction together with its account, splits and duplicates.
+
+        Returns dict with:
+            - transaction: The transaction itself (soft-deleted ones included)
+            - account: The owning account, or None if it no longer exists
+            - parent: Parent transaction if this is a split child
+            - children: Split children if this is a split parent
+            - fingerprint_matches: Other non-deleted transactions sharing the fingerprint
+            - sources: Provider keys from external_ids (e.g. simplefin, csv)
+            - csv_source: CSV provenance (file_sha256, line, row, import_id), or None
+            - attachments: Files attached to the transaction
+        """
+        id_result = await self.resolve_transaction_id(id_or_prefix)
+        if not id_result.success:
+            return id_result
+
+        tx_result = await self.repository.get_transaction_by_id(id_result.data)
+        if not tx_result.success:
+            return tx_result
+        transaction = tx_result.data
+
+        account_result = await self.repository.get_account_by_id(transaction.account_id)
+        account = account_result.data if account_result.success else None
+
+        parent = None
+        if transaction.parent_transaction_id:
+            parent_result = await self.repository.get_transaction_by_id(
+                transaction.parent_transaction_id
+            )
+            if parent_result.success:
+                parent = parent_result.data
+
+        children_result = await self.repository.get_transactions_by_parent_id(transaction.id)
+        if not children_result.success:
+            return children_result
+
+        fingerprint_matches: List[Transaction] = []
+        fingerprint = transaction.external_ids.get("fingerprint")
+        if fingerprint:
+            matches_result = await self.repository.get_transactions_by_fingerprint(
+                fingerprint
+            )
+            if not matches_result.success:
+                return matches_result
+            fingerprint_matches = [
+                tx for tx in matches_result.data if tx.id != transaction.id
+            ]
+
+        attachments_result = await self.repository.get_attachments(transaction.id)
+        if not attachments_result.success:
+            return attachments_result
+
+        return Ok(
+            {
+                "transaction": transaction,
+                "account": account,
+                "parent": parent,
+                "children": children_result.data,
+                "fingerprint_matches": fingerprint_matches,
+                "sources": sorted(
+                    key
+                    for key in transaction.external_ids
+                    if key not in ("fingerprint", "fingerprint_version", "csv_source")
+                ),
+                "csv_source": self._parse_csv_source(transaction),
+                "attachments": attachments_result.data,
+            }
+        )
+
+    async def create_transaction(
+        self,
+        account_id: UUID,
+        amount: Decimal,
+        description: str | None,
+        transaction_date: date,
+        status: TransactionStatus = TransactionStatus.CLEARED,
+    ) -> Result[Transaction]:
+        """Enter a transaction by hand, e.g. a pending one on a cash account."""
+        account_result = await self.repository.get_account_by_id(account_id)
+        if not account_result.success:
+            return Fail(f"Account not found: {account_id}")
+
+        now = datetime.now(timezone.utc)
+        transaction = Transaction(
+            id=uuid4(),
+            account_id=account_id,
+            amount=amount,
+            description=description,
+            transaction_date=transaction_date,
+            posted_date=transaction_date,
+            status=status,
+            created_at=now,
+            updated_at=now,
+        )
+        [transaction] = apply_default_tags([transaction], [account_result.data])
+        return await self.repository.add_transaction(transaction)
+
+    async def edit(
+        self,
+        id_or_prefix: str,
+        amount: Decimal | None = None,
+        description: str | None = None,
+        transaction_date: date | None = None,
+        posted_date: date | None = None,
+    ) -> Result[Tuple[Transaction, Transaction]]:
+        """Correct a transaction's amount, description or dates.
+
+        Fields left as None keep their value. The fingerprint is recomputed,
+        at the transaction's fingerprint version, from the edited values, so
+        re-importing the corrected row is recognised as a duplicate.
+
+        Returns:
+            (before, after) pair
+        """
+        changes: Dict[str, Any] = {
+            field: value
+            for field, value in (
+                ("amount", amount),
+                ("description", description),
+                ("transaction_date", transaction_date),
+                ("posted_date", posted_date),
+            )
+            if value is not None
+        }
+        if not changes:
+            return Fail("Nothing to change: pass --amount, --description, --date or --posted-date")
+
+        id_result = await self.resolve_transaction_id(id_or_prefix)
+        if not id_result.success:
+            return id_result
+        tx_result = await self.repository.get_transaction_by_id(id_result.data)
+        if not tx_result.success:
+            return Fail(f"No transaction matches ID: {id_or_prefix}")
+        before = tx_result.data
+        if before.deleted_at:
+            return Fail(f"Transaction {before.id} is deleted")
+
+        try:
+            edited = Transaction.model_validate(
+                {
+                    **before.model_dump(),
+                    **changes,
+                    "updated_at": datetime.now(timezone.utc),
+                }
+            )
+        except ValueError as e:
+            return Fail(f"Invalid transaction: {e}")
+
+        after_result = await self._refingerprint(edited)
+        if not after_result.success:
+            return after_result
+        update_result = await self.repository.update_transaction(after_result.data)
+        if not update_result.success:
+            return update_result
+        return Ok((before, update_result.data))
+
+    async def _refingerprint(self, transaction: Transaction) -> Result[Transaction]:
+        """Recompute a transaction's fingerprint at its fingerprint version.
+
+        For v2 the occurrence index is the lowest one no other transaction
+        already uses, as if the row had been imported after the rest.
+        """
+        version = transaction.fingerprint_version
+        occurrence = 0
+        while True:
+            candidate = transaction.with_fingerprint(version, occurrence)
+            if version == 1:
+                return Ok(candidate)
+            taken_result = await self.repository.get_transactions_by_fingerprint(
+                candidate.external_ids["fingerprint"]
+            )
+            if not taken_result.success:
+                return taken_result
+            if all(tx.id == transaction.id for tx in taken_result.data):
+                return Ok(candidate)
+            occurrence += 1
+
+    async def list_transactions(
+        self,
+        account_id: UUID | None = None,
+        status: TransactionStatus | None = None,
+        start_date: date | None = None,
+        end_date: date | None = None,
+        search: str | None = None,
+        limit: int | None = None,
+    ) -> Result[List[Transaction]]:
+        """List non-deleted transactions matching the filters, newest first."""
+        return await self.repository.get_transactions_filtered(
+            account_id=account_id,
+            status=status,
+            start_date=start_date,
+            end_date=end_date,
+            search=search,
+            limit=limit,
+        )
+
+    async def get_split_parents(
+        self, transactions: List[Transaction]
+    ) -> Result[Dict[UUID, Transaction]]:
+        """Get the parents of the split parts among transactions, deleted or not.
+
+        Parents that no longer exist are left out.
+        """
+        listed = {tx.id for tx in transactions}
+        parents: Dict[UUID, Transaction] = {}
+        for tx in transactions:
+            parent_id = tx.parent_transaction_id
+            if parent_id is None or parent_id in listed or parent_id in parents:
+                continue
+            parent_result = await self.repository.get_transaction_by_id(parent_id)
+            if parent_result.success:
+                parents[parent_id] = parent_result.data
+        return Ok(parents)
+
+    async def set_status(
+        self, ids_or_prefixes: List[str], status: TransactionStatus
+    ) -> Result[List[UUID]]:
+        """Set the status of several transactions at once.
+
+        Every ID is resolved before anything is written, so one bad ID leaves
+        all transactions unchanged.
+        """
+        transaction_ids: List[UUID] = []
+        for id_or_prefix in ids_or_prefixes:
+            id_result = await self.resolve_transaction_id(id_or_prefix)
+            if not id_result.success:
+                return id_result
+
+            tx_result = await self.repository.get_transaction_by_id(id_result.data)
+            if not tx_result.success:
+                return Fail(f"No transaction matches ID: {id_or_prefix}")
+            if tx_result.data.deleted_at:
+                return Fail(f"Transaction {id_result.data} is deleted")
+            if id_result.data not in transaction_ids:
+                transaction_ids.append(id_result.data)
+
+        update_result = await self.repository.update_transactions_status(
+            transaction_ids, status
+        )
+        if not update_result.success:
+            return update_result
+        return Ok(transaction_ids)
+
+    async def delete(
+        self, ids_or_prefixes: List[str], restore: bool = False
+    ) -> Result[List[UUID]]:
+        """Soft-delete several transactions at once, or restore them.
+
+        Deleted transactions drop out of the transactions view but stay in
+        the database, so imports and syncs don't add them back. Every ID is
+        resolved before anything is written, so one bad ID leaves all
+        transactions unchanged. A split parent can't be restored while its
+        parts are active, since both would then count.
+
+        Returns:
+            IDs that were deleted (or restored); ones already in that state
+            are left out
+        """
+        transaction_ids: List[UUID] = []
+        for id_or_prefix in ids_or_prefixes:
+            id_result = await self.resolve_transaction_id(id_or_prefix)
+            if not id_result.success:
+                return id_result
+
+            tx_result = await self.repository.get_transaction_by_id(id_result.data)
+            if not tx_result.success:
+                return Fail(f"No transaction matches ID: {id_or_prefix}")
+            transaction = tx_result.data
+            if restore and transaction.deleted_at:
+                children_result = await self.repository.get_children(transaction.id)
+                if not children_result.success:
+                    return children_result
+                if children_result.data:
+                    return Fail(
+                        f"Transaction {transaction.id} was split; delete its split parts "
+                        "before restoring it"
+                    )
+            if bool(transaction.deleted_at) == restore and transaction.id not in transaction_ids:
+                transaction_ids.append(transaction.id)
+
+        if restore:
+            write_result = await self.repository.restore_transactions(transaction_ids)
+        else:
+            write_result = await self.repository.delete_transactions(transaction_ids)
+        if not write_result.success:
+            return write_result
+        return Ok(transaction_ids)
+
+    def _parse_csv_source(self, transaction: Transaction) -> Dict[str, Any] | None:
+        raw = transaction.external_ids.get("csv_source")
+        if not raw:
+            return None
+        try:
+            return json.loads(raw)
+        except ValueError:
+            return {"row": raw}
@@ -0,0 +1,39 @@
+"""Service for tracking provider warnings across sync runs."""
+
+from typing import List
+from uuid import UUID
+
+from treeline.abstractions import Repository
+from treeline.domain import Fail, ProviderWarning, Result
+
+
+class WarningService:
+    """Service for tracking provider warnings across sync runs."""
+
+    def __init__(self, repository: Repository):
+        self.repository = repository
+
+    async def record_sync_warnings(
+        self, integration_name: str, messages: List[str]
+    ) -> Result[None]:
+        """Record warnings from a completed sync of one integration.
+
+        Args:
+            integration_name: Integration that was synced
+            messages: Warnings returned by the provider (empty if the sync was clean)
+        """
+        return await self.repository.record_provider_warnings(integration_name, messages)
+
+    async def list_warnings(
+        self, include_resolved: bool = False
+    ) -> Result[List[ProviderWarning]]:
+        """List provider warnings (unresolved only unless include_resolved)."""
+        return await self.repository.list_provider_warnings(include_resolved)
+
+    async def resolve_warning(self, warning_id: str) -> Result[None]:
+        """Manually mark a warning as resolved."""
+        try:
+            parsed_id = UUID(warning_id)
+        except ValueError:
+            return Fail(f"Invalid warning ID: {warning_id}")
+        return await self.repository.resolve_provider_warning(parsed_id)
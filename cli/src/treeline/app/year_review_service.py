@@ -0,0 +1,217 @@
+"""Year-in-review summary, composed from the individual reports."""
+
+from datetime import date, timedelta
+from decimal import Decimal
+from typing import Any, Dict, List
+
+from treeline.abstractions import Repository
+from treeline.app.report_service import ReportService, spending_by_tag
+from treeline.domain import Account, Fail, Ok, Result
+
+# Entries in the top merchants and top tags lists
+YEAR_REVIEW_TOP = 10
+
+QUARTERS = ((1, 3), (4, 6), (7, 9), (10, 12))
+
+
+def _quarter_end(year: int, last_month: int) -> date:
+    if last_month == 12:
+        return date(year, 12, 31)
+    return date(year, last_month + 1, 1) - timedelta(days=1)
+
+
+class YearReviewService:
+    """Builds the year-in-review summary from ReportService.
+
+    Every figure comes from the report that shows it on its own (cashflow,
+    merchants, recurring, net worth), so the review agrees with them.
+    """
+
+    def __init__(self, repository: Repository, report_service: ReportService):
+        self.repository = repository
+        self.report_service = report_service
+
+    async def review(self, year: int, today: date | None = None) -> Result[Dict[str, Any]]:
+        """Summarize a calendar year; the current year runs to today.
+
+        A year with no income or expenses gives enough_data False and a
+        message instead of a summary full of zeros.
+
+        Returns dict with:
+            - year, start_date, end_date, year_to_date, enough_data
+            - message: Why there is no summary (only when enough_data is False)
+            - income, total_expenses, net_savings, savings_rate
+            - net_worth: start, end, change, start_date, end_date, unknown_accounts
+            - quarters: [{quarter, start_date, end_date, start, end, change,
+              unknown_accounts}] for the quarters that have started
+            - months: [{month, income, total_expenses, net_savings}]
+            - most_expensive_month: One entry of months, or None
+            - top_merchants: [{merchant, amount, count, average}]
+            - top_tags: [{tag, amount, share}]
+            - biggest_expense: {date, description, account, amount, tags}, or None
+            - subscriptions: {total, charges} from ReportService.recurring
+            - accounts: [{account, currency, total_expenses, start, end,
+              change}] by name (start/end/change None when unknown)
+            - highlights: most_spending, largest_increase and largest_decrease
+              (account entries, or None)
+        """
+        today = today or date.today()
+        if year > today.year:
+            return Fail(f"{year} hasn't started yet")
+
+        start_date = date(year, 1, 1)
+        end_date = min(date(year, 12, 31), today)
+        summary: Dict[str, Any] = {
+            "year": year,
+            "start_date": start_date,
+            "end_date": end_date,
+            "year_to_date": end_date < date(year, 12, 31),
+        }
+
+        cashflow_result = await self.report_service.cashflow(start_date, end_date)
+        if not cashflow_result.success:
+            return cashflow_result
+        cashflow = cashflow_result.data
+        totals = cashflow["totals"]
+        if totals["income"] == 0 and totals["total_expenses"] == 0:
+            return Ok(
+                {
+                    **summary,
+                    "enough_data": False,
+                    "message": f"Not enough data for {year}: no income or expenses were recorded",
+                }
+            )
+
+        accounts_result = await self.repository.get_accounts()
+        if not accounts_result.success:
+            return accounts_result
+        accounts = accounts_result.data or []
+
+        net_worth_result = await self.report_service.net_worth_change(
+            accounts, start_date - timedelta(days=1), end_date
+        )
+        if not net_worth_result.success:
+            return net_worth_result
+
+        quarters = []
+        for number, (first_month, last_month) in enumerate(QUARTERS, start=1):
+            quarter_start = date(year, first_month, 1)
+            if quarter_start > end_date:
+                break
+            quarter_end = min(_quarter_end(year, last_month), end_date)
+            quarter_result = await self.report_service.net_worth_change(
+                accounts, quarter_start - timedelta(days=1), quarter_end
+            )
+            if not quarter_result.success:
+                return quarter_result
+            quarters.append({"quarter": f"Q{number}", **quarter_result.data})
+
+        merchants_result = await self.report_service.merchants(
+            start_date, end_date, top=YEAR_REVIEW_TOP
+        )
+        if not merchants_result.success:
+            return merchants_result
+
+        largest_result = await self.report_service.largest_expense(start_date, end_date)
+        if not largest_result.success:
+            return largest_result
+
+        recurring_result = await self.report_service.recurring(start_date, end_date)
+        if not recurring_result.success:
+            return recurring_result
+
+        accounts_summary_result = await self._accounts(
+            accounts, cashflow["accounts"], start_date, end_date
+        )
+        if not accounts_summary_result.success:
+            return accounts_summary_result
+        account_entries = accounts_summary_result.data
+
+        months = [
+            {
+                "month": month["month"],
+                "income": month["income"],
+                "total_expenses": month["total_expenses"],
+                "net_savings": month["net_savings"],
+            }
+            for month in cashflow["months"]
+        ]
+        known = [entry for entry in account_entries if entry["change"] is not None]
+
+        return Ok(
+            {
+                **summary,
+                "enough_data": True,
+                "income": totals["income"],
+                "total_expenses": totals["total_expenses"],
+                "net_savings": totals["net_savings"],
+                "savings_rate": totals["savings_rate"],
+                "net_worth": net_worth_result.data,
+                "quarters": quarters,
+                "months": months,
+                # max keeps the first of equals, so ties go to the earliest month
+                "most_expensive_month": max(
+                    (m for m in months if m["total_expenses"] > 0),
+                    key=lambda m: m["total_expenses"],
+                    default=None,
+                ),
+                "top_merchants": [
+                    {key: merchant[key] for key in ("merchant", "amount", "count", "average")}
+                    for merchant in merchants_result.data["merchants"]
+                ],
+                "top_tags": spending_by_tag(totals)[:YEAR_REVIEW_TOP],
+                "biggest_expense": largest_result.data,
+                "subscriptions": {
+                    "total": recurring_result.data["total"],
+                    "charges": recurring_result.data["charges"],
+                },
+                "accounts": account_entries,
+                "highlights": {
+                    "most_spending": max(
+                        (e for e in account_entries if e["total_expenses"] > 0),
+                        key=lambda e: e["total_expenses"],
+                        default=None,
+                    ),
+                    "largest_increase": max(
+                        (e for e in known if e["change"] > 0),
+                        key=lambda e: e["change"],
+                        default=None,
+                    ),
+                    "largest_decrease": min(
+                        (e for e in known if e["change"] < 0),
+                        key=lambda e: e["change"],
+                        default=None,
+                    ),
+                },
+            }
+        )
+
+    async def _accounts(
+        self,
+        accounts: List[Account],
+        expenses: List[Dict[str, Any]],
+        start_date: date,
+        end_date: date,
+    ) -> Result[List[Dict[str, Any]]]:
+        """Spending and balance change over the year for each account."""
+        spent = {entry["account_id"]: entry["total_expenses"] for entry in expenses}
+        entries = []
+        for account in accounts:
+            balance_result = await self.report_service.net_worth_change(
+                [account], start_date - timedelta(days=1), end_date
+            )
+            if not balance_result.success:
+                return balance_result
+            balance = balance_result.data
+            unknown = bool(balance["unknown_accounts"])
+            entries.append(
+                {
+                    "account": account.nickname or account.name,
+                    "currency": account.currency,
+                    "total_expenses": spent.get(str(account.id), Decimal("0.00")),
+                    "start": None if unknown else balance["start"],
+                    "end": None if unknown else balance["end"],
+                    "change": None if unknown else balance["change"],
+                }
+            )
+        return Ok(sorted(entries, key=lambda e: e["account"].casefold()))
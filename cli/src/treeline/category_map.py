@@ -0,0 +1,155 @@
+"""Provider category to tag mapping (~/.treeline/category_map.json).
+
+Providers such as SimpleFIN report a category per transaction in their own
+vocabulary ("FOOD_AND_DRINK_COFFEE", "Coffee Shops"). Sync turns those into
+tags through this map. A category it has no mapping for becomes a tag as is
+(normalized, so lowercase) and is recorded, so `tl tag map suggest` can list
+the ones worth mapping.
+
+The file is created with DEFAULT_CATEGORY_MAP the first time it is read, and
+after that is the user's to change (`tl tag map set/unset`, or by hand).
+"""
+
+import json
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Dict, List, Set, Tuple
+
+from treeline.domain import Transaction, normalize_tag, normalize_tags
+
+# Keys are provider categories as normalize_tag leaves them
+DEFAULT_CATEGORY_MAP: Dict[str, str] = {
+    # SimpleFIN (categories as the bank or bridge names them)
+    "restaurants": "dining",
+    "fast food": "dining",
+    "coffee shops": "coffee",
+    "groceries": "groceries",
+    "gas & fuel": "gas",
+    "gas stations": "gas",
+    "mortgage & rent": "rent",
+    "utilities": "utilities",
+    "paycheck": "income",
+    "credit card payment": "transfer",
+    "transfer": "transfer",
+    "shopping": "shopping",
+    "travel": "travel",
+    # Plaid personal finance categories
+    "food_and_drink": "dining",
+    "food_and_drink_coffee": "coffee",
+    "food_and_drink_restaurant": "dining",
+    "food_and_drink_fast_food": "dining",
+    "food_and_drink_groceries": "groceries",
+    "transportation_gas": "gas",
+    "transportation_public_transit": "transit",
+    "transportation_taxis_and_ride_shares": "rideshare",
+    "rent_and_utilities_rent": "rent",
+    "rent_and_utilities_gas_and_electricity": "utilities",
+    "rent_and_utilities_internet_and_cable": "utilities",
+    "general_merchandise_online_marketplaces": "shopping",
+    "general_merchandise_clothing_and_accessories": "clothing",
+    "entertainment_tv_and_movies": "entertainment",
+    "travel_flights": "travel",
+    "travel_lodging": "travel",
+    "medical_pharmacies_and_supplements": "health",
+    "income_wages": "income",
+    "loan_payments_credit_card_payment": "transfer",
+    "transfer_in_account_transfer": "transfer",
+    "transfer_out_account_transfer": "transfer",
+    "bank_fees_overdraft_fees": "fees",
+}
+
+
+@dataclass
+class CategoryMap:
+    """Provider categories mapped to tags, and the unmapped categories sync has seen.
+
+    Categories and tags are kept normalized (see normalize_tag).
+    """
+
+    mappings: Dict[str, str] = field(default_factory=dict)
+    unmapped: Set[str] = field(default_factory=set)
+
+    def tag_for(self, category: str) -> str:
+        """The tag for a provider category: its mapping, else the category itself."""
+        key = normalize_tag(category)
+        return self.mappings.get(key, key)
+
+    def apply(self, transactions: List[Transaction]) -> Tuple[List[Transaction], Set[str]]:
+        """Turn provider category tags into mapped tags.
+
+        Returns the transactions and the categories that had no mapping.
+        """
+        seen: Set[str] = set()
+        mapped = []
+        for tx in transactions:
+            if not tx.tags:
+                mapped.append(tx)
+                continue
+            categories = normalize_tags(tx.tags)
+            seen.update(c for c in categories if c not in self.mappings)
+            tags = normalize_tags(self.tag_for(c) for c in categories)
+            mapped.append(tx.model_copy(update={"tags": tuple(tags)}))
+        return mapped, seen
+
+    def set_mapping(self, category: str, tag: str) -> str | None:
+        """Map a category to a tag. Returns the tag it was mapped to before, if any."""
+        key, tag = normalize_tag(category), normalize_tag(tag)
+        if not key:
+            raise ValueError("Provider category cannot be empty")
+        if not tag:
+            raise ValueError("Tag cannot be empty")
+        previous = self.mappings.get(key)
+        self.mappings[key] = tag
+        self.unmapped.discard(key)
+        return previous
+
+    def remove_mapping(self, category: str) -> str | None:
+        """Remove a category's mapping. Returns the tag it was mapped to, if any."""
+        return self.mappings.pop(normalize_tag(category), None)
+
+    def to_json(self) -> Dict[str, object]:
+        return {
+            "mappings": dict(sorted(self.mappings.items())),
+            "unmapped": sorted(self.unmapped),
+        }
+
+
+def _parse(data: object) -> CategoryMap:
+    if not isinstance(data, dict) or not isinstance(data.get("mappings"), dict):
+        raise ValueError("expected an object with a \"mappings\" object")
+    category_map = CategoryMap()
+    for category, tag in data["mappings"].items():
+        key, value = normalize_tag(category), normalize_tag(tag or "")
+        if key and value:
+            category_map.mappings[key] = value
+    unmapped = data.get("unmapped") or []
+    if isinstance(unmapped, list):
+        categories = (normalize_tag(c) for c in unmapped if isinstance(c, str))
+        category_map.unmapped = {c for c in categories if c}
+    return category_map
+
+
+def load_category_map(path: Path) -> CategoryMap:
+    """Read the category map, creating the file with the defaults if it doesn't exist.
+
+    Raises ValueError if the file exists but isn't a valid map, so a typo
+    doesn't silently drop the user's mappings.
+    """
+    if not path.exists():
+        category_map = CategoryMap(mappings=dict(DEFAULT_CATEGORY_MAP))
+        save_category_map(category_map, path)
+        return category_map
+
+    try:
+        with open(path) as f:
+            return _parse(json.load(f))
+    except (json.JSONDecodeError, ValueError) as e:
+        raise ValueError(f"Invalid category map {path}: {e}") from e
+
+
+def save_category_map(category_map: CategoryMap, path: Path) -> None:
+    """Write the category map, mappings and unmapped categories sorted."""
+    path.parent.mkdir(parents=True, exist_ok=True)
+    with open(path, "w") as f:
+        json.dump(category_map.to_json(), f, indent=2)
+        f.write("\n")
@@ -8,10 +8,16 @@ from dotenv import load_dotenv
 from rich.console import Console
 
 from treeline.app.container import Container
-from treeline.commands import backfill, backup, compact, demo, doctor, encrypt, import_cmd, new, plugin, query, remove, setup, status, sync, tag
-from treeline.config import is_demo_mode
+from treeline.commands import about, accounts, backfill, balances, backup, compact, db, dedupe, demo, doctor, encrypt, export, import_cmd, imports, integrations, maintenance, new, planned, plugin, query, remove, report, schema, setup, status, sync, tag, transactions, warnings
+from treeline.config import (
+    is_demo_mode,
+    set_assume_yes,
+    set_json_numbers,
+    set_no_input,
+    set_offline_mode,
+)
 from treeline.theme import get_theme
-from treeline.utils import get_treeline_dir
+from treeline.utils import get_app_version, get_treeline_dir
 
 # Load environment variables from .env file
 load_dotenv()
@@ -19,11 +25,7 @@ load_dotenv()
 
 def get_version() -> str:
     """Get the version from package metadata."""
-    try:
-        from importlib.metadata import version
-        return version("treeline-money")
-    except Exception:
-        return "0.1.0"
+    return get_app_version()
 
 
 def version_callback(value: bool):
@@ -39,6 +41,8 @@ app = typer.Typer(
     no_args_is_help=True,
 )
 console = Console()
+# Warnings go to stderr so they don't break --json output
+err_console = Console(stderr=True)
 theme = get_theme()
 
 
@@ -52,9 +56,41 @@ def main(
         callback=version_callback,
         is_eager=True,
     ),
+    offline: bool = typer.Option(
+        False,
+        "--offline",
+        help="Never contact the network; network integrations fail fast",
+    ),
+    yes: bool = typer.Option(
+        False,
+        "--yes",
+        "-y",
+        help="Answer yes to every confirmation prompt",
+    ),
+    no_input: bool = typer.Option(
+        False,
+        "--no-input",
+        help="Fail instead of prompting (the default when stdin isn't a terminal)",
+    ),
+    json_numbers: bool = typer.Option(
+        False,
+        "--json-numbers",
+        help="Write amounts in --json output as numbers, not strings (deprecated)",
+    ),
 ):
     _ = _version  # Used by callback
+    if offline:
+        set_offline_mode()
+    if yes:
+        set_assume_yes()
+    if no_input:
+        set_no_input()
+    if json_numbers:
+        set_json_numbers()
+
 
+# Exit code when the database was written by a newer treeline
+EXIT_INCOMPATIBLE_DB = 3
 
 # Global container instance
 _container: Container | None = None
@@ -63,6 +99,10 @@ _container: Container | None = None
 def _password_callback() -> str:
     """Interactive password prompt for encrypted databases."""
     from rich.prompt import Prompt
+
+    from treeline.confirm import require_input_or_exit
+
+    require_input_or_exit("Database password (set TL_DB_PASSWORD)")
     return Prompt.ask("Enter database password", password=True)
 
 
@@ -96,13 +136,39 @@ def ensure_treeline_initialized() -> bool:
     db_service = container.db_service()
 
     result = asyncio.run(db_service.initialize_db())
+    if not result.success and result.context and result.context.get("incompatible_db"):
+        console.print(f"[{theme.error}]{result.error}[/{theme.error}]")
+        console.print(f"[{theme.muted}]Upgrade treeline to open this database.[/{theme.muted}]")
+        sys.exit(EXIT_INCOMPATIBLE_DB)
     if not result.success:
         console.print(f"[{theme.error}]Error initializing database: {result.error}[/{theme.error}]")
         sys.exit(1)
+    if result.context and result.context.get("view_drift"):
+        warn_view_drift(result.context["view_drift"])
 
     return needs_init
 
 
+def warn_view_drift(drift: list) -> None:
+    """Warn about views that no longer match the expected schema."""
+    for entry in drift:
+        if entry["missing"]:
+            problem = "is missing"
+        else:
+            details = []
+            if entry["missing_columns"]:
+                details.append(f"missing {', '.join(entry['missing_columns'])}")
+            if entry["unexpected_columns"]:
+                details.append(f"unexpected {', '.join(entry['unexpected_columns'])}")
+            problem = f"has changed ({'; '.join(details)})"
+        err_console.print(
+            f"[{theme.warning}]Warning: view '{entry['view']}' {problem}[/{theme.warning}]"
+        )
+    err_console.print(
+        f"[{theme.muted}]Run 'tl db repair-views' to recreate the views from the schema.[/{theme.muted}]"
+    )
+
+
 # Register commands from modules
 status.register(app, get_container)
 setup.register(app, get_container, ensure_treeline_initialized)
@@ -117,8 +183,22 @@ plugin.register(app, get_container)
 demo.register(app, get_container, ensure_treeline_initialized)
 remove.register(app, get_container, ensure_treeline_initialized)
 import_cmd.register(app, get_container, ensure_treeline_initialized)
+imports.register(app, get_container, ensure_treeline_initialized)
 doctor.register(app, get_container, ensure_treeline_initialized)
 encrypt.register(app, get_container, ensure_treeline_initialized)
+warnings.register(app, get_container, ensure_treeline_initialized)
+accounts.register(app, get_container, ensure_treeline_initialized)
+transactions.register(app, get_container, ensure_treeline_initialized)
+planned.register(app, get_container, ensure_treeline_initialized)
+dedupe.register(app, get_container, ensure_treeline_initialized)
+integrations.register(app, get_container, ensure_treeline_initialized)
+maintenance.register(app, get_container, ensure_treeline_initialized)
+report.register(app, get_container, ensure_treeline_initialized)
+balances.register(app, get_container, ensure_treeline_initialized)
+export.register(app, get_container, ensure_treeline_initialized)
+schema.register(app, get_container, ensure_treeline_initialized)
+db.register(app, get_container, ensure_treeline_initialized)
+about.register(app, get_container)
 
 
 if __name__ == "__main__":
@@ -1,31 +1,47 @@
 """CLI commands module."""
 
 from treeline.commands import (
+    accounts,
     backfill,
+    balances,
     demo,
     encrypt,
     import_cmd,
+    imports,
+    integrations,
+    maintenance,
     new,
     plugin,
     query,
     remove,
+    report,
     setup,
     status,
     sync,
     tag,
+    transactions,
+    warnings,
 )
 
 __all__ = [
+    "accounts",
     "backfill",
+    "balances",
     "demo",
     "encrypt",
     "import_cmd",
+    "imports",
+    "integrations",
+    "maintenance",
     "new",
     "plugin",
     "query",
     "remove",
+    "report",
     "setup",
     "status",
     "sync",
     "tag",
+    "transactions",
+    "warnings",
 ]
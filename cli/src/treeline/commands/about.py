@@ -0,0 +1,102 @@
+"""About command - build, database and platform details for bug reports."""
+
+import asyncio
+import json
+
+import typer
+from rich.console import Console
+from rich.table import Table
+
+from treeline.theme import get_theme
+from treeline.utils import get_log_file_path
+
+console = Console()
+theme = get_theme()
+
+
+def output_json(data: dict) -> None:
+    """Output data as JSON."""
+    print(json.dumps(data, indent=2))
+
+
+def display_error(error: str, show_log_hint: bool = True) -> None:
+    """Display error message in consistent format."""
+    console.print(f"[{theme.error}]Error: {error}[/{theme.error}]")
+    if show_log_hint:
+        log_file = get_log_file_path()
+        console.print(f"[{theme.muted}]See {log_file} for details[/{theme.muted}]")
+
+
+def _or_unknown(value) -> str:
+    return "unknown" if value is None else str(value)
+
+
+def display_about(info: dict) -> None:
+    """Display about details as a two-column table."""
+    database = info["database"]
+    if not database["exists"]:
+        db_state = "not created yet"
+    elif database["migrations_applied"] is None:
+        db_state = "locked (encrypted; set TL_DB_PASSWORD to read)"
+    else:
+        db_state = (
+            f"data version {_or_unknown(database['data_version'])} "
+            f"(supports {database['supported_data_version']}), "
+            f"{database['migrations_applied']} migrations, latest {_or_unknown(database['latest_migration'])}"
+        )
+
+    rows = [
+        ("Version", info["version"]),
+        ("Git commit", _or_unknown(info["git_commit"])),
+        ("Build date", _or_unknown(info["build_date"])),
+        ("Python", info["python"]),
+        ("OS", f"{info['os']} ({info['arch']})"),
+        ("Demo mode", "on" if info["demo_mode"] else "off"),
+        ("Config", info["config_path"] or "none (defaults)"),
+        ("Database", database["path"]),
+        ("Backend", database["backend"] + (", encrypted" if database["encrypted"] else "")),
+        ("Size", _or_unknown(database["size_bytes"]) + (" bytes" if database["size_bytes"] is not None else "")),
+        ("Schema", db_state),
+    ]
+
+    table = Table(show_header=False, box=None, padding=(0, 2))
+    table.add_column(style=theme.muted)
+    table.add_column()
+    for label, value in rows:
+        table.add_row(label, value)
+
+    console.print(f"\n[{theme.ui_header}]treeline {info['version']}[/{theme.ui_header}]\n")
+    console.print(table)
+    console.print()
+
+
+def register(app: typer.Typer, get_container: callable) -> None:
+    """Register the about command with the app."""
+
+    @app.command(name="about")
+    def about_command(
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Show version, build, database and platform details.
+
+        Include this output in bug reports. It contains paths and versions
+        only, never credentials.
+
+        Examples:
+          tl about
+          tl about --json
+        """
+        # Works before `tl` has been set up, so no ensure_initialized
+        result = asyncio.run(get_container().about_service().about())
+        if not result.success:
+            if json_output:
+                output_json({"success": False, "error": result.error})
+            else:
+                display_error(result.error)
+            raise typer.Exit(1)
+
+        if json_output:
+            output_json(result.data)
+            return
+
+        display_about(result.data)
@@ -0,0 +1,557 @@
+"""Accounts command - manage accounts."""
+
+import asyncio
+import json
+from datetime import date
+from decimal import Decimal, InvalidOperation
+
+import typer
+from pydantic import BaseModel
+from rich.console import Console
+from rich.markup import escape
+from rich.table import Table
+
+from treeline.app.account_service import ACCOUNT_TYPES, institution_sort_key
+from treeline.app.preferences_service import format_currency
+from treeline.commands.json_output import decimal_json, model_json
+from treeline.config import get_display_locale
+from treeline.theme import get_theme
+from treeline.utils import get_log_file_path
+
+console = Console()
+theme = get_theme()
+
+# Create accounts subcommand group
+accounts_app = typer.Typer(help="Account management commands")
+
+
+def json_serializer(obj):
+    """Custom JSON serializer for Pydantic models and other objects."""
+    if isinstance(obj, BaseModel):
+        return model_json(obj)
+    if isinstance(obj, Decimal):
+        return decimal_json(obj)
+    return str(obj)
+
+
+def output_json(data: dict) -> None:
+    """Output data as JSON."""
+    print(json.dumps(data, indent=2, default=json_serializer))
+
+
+def display_error(error: str, show_log_hint: bool = True) -> None:
+    """Display error message in consistent format."""
+    console.print(f"[{theme.error}]Error: {error}[/{theme.error}]")
+    if show_log_hint:
+        log_file = get_log_file_path()
+        console.print(f"[{theme.muted}]See {log_file} for details[/{theme.muted}]")
+
+
+def _fail(
+    error: str, json_output: bool, show_log_hint: bool = False, context: dict | None = None
+) -> None:
+    """Report an error in the requested format and exit."""
+    matches = (context or {}).get("matches", [])
+    if json_output:
+        error_data = {"success": False, "error": error}
+        if (context or {}).get("kind"):
+            error_data["kind"] = context["kind"]
+        if matches:
+            error_data["matches"] = matches
+        if (context or {}).get("field"):
+            error_data["field"] = context["field"]
+        output_json(error_data)
+    else:
+        display_error(error, show_log_hint=show_log_hint)
+        for match in matches:
+            console.print(f"  {match}")
+    raise typer.Exit(1)
+
+
+def register(app: typer.Typer, get_container: callable, ensure_initialized: callable) -> None:
+    """Register the accounts commands with the app."""
+    app.add_typer(accounts_app, name="accounts")
+
+    @accounts_app.command(name="list")
+    def accounts_list_command(
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """List accounts, grouped by institution.
+
+        The ID column shows the first block of each account's ID; --json
+        gives the full IDs.
+
+        Examples:
+          tl accounts list
+          tl accounts list --json
+        """
+        ensure_initialized()
+
+        container = get_container()
+        result = asyncio.run(container.account_service().get_accounts())
+        if not result.success:
+            _fail(result.error, json_output)
+
+        accounts = sorted(result.data or [], key=institution_sort_key)
+        if json_output:
+            output_json({"success": True, "accounts": accounts})
+            return
+
+        if not accounts:
+            console.print(f"\n[{theme.muted}]No accounts yet[/{theme.muted}]")
+            console.print(
+                f"[{theme.muted}]Run tl sync to pull them from an integration, "
+                f"or add one with: tl accounts create --name <name>[/{theme.muted}]\n"
+            )
+            return
+
+        locale = get_display_locale()
+        table = Table(title="Accounts", show_header=True, header_style=theme.ui_header)
+        table.add_column("ID", style=theme.muted)
+        table.add_column("Name")
+        table.add_column("Nickname")
+        table.add_column("Type")
+        table.add_column("Institution")
+        table.add_column("Balance", justify="right")
+        table.add_column("Currency")
+
+        for account in accounts:
+            balance = "-"
+            if account.balance is not None:
+                style = theme.negative_amount if account.balance < 0 else theme.positive_amount
+                balance = f"[{style}]{format_currency(account.balance, account.currency, locale=locale)}[/{style}]"
+            name = account.name
+            if account.archived_at is not None:
+                name += f" [{theme.muted}](archived)[/{theme.muted}]"
+            table.add_row(
+                str(account.id)[:8],
+                name,
+                account.nickname or "-",
+                account.account_type or "-",
+                account.institution_name or "-",
+                balance,
+                account.currency,
+            )
+
+        console.print()
+        console.print(table)
+        console.print()
+
+    @accounts_app.command(name="create")
+    def accounts_create_command(
+        name: str = typer.Option(..., "--name", help="Account name"),
+        account_type: str = typer.Option(
+            None, "--type", help="Account type (depository, credit, investment, loan, other)"
+        ),
+        currency: str = typer.Option("USD", "--currency", help="Currency code (e.g., USD)"),
+        institution: str = typer.Option(None, "--institution", help="Institution name"),
+        nickname: str = typer.Option(None, "--nickname", help="Display nickname"),
+        opening_balance: str = typer.Option(
+            None, "--opening-balance", help="Opening balance amount"
+        ),
+        as_of: str = typer.Option(
+            None, "--as-of", help="Opening balance date (YYYY-MM-DD, defaults to today)"
+        ),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Create a manual account (cash, loans, accounts without a feed).
+
+        The account and its opening balance are saved together, or not at
+        all. With --json, an invalid option fails with "field" naming it
+        (name, currency, opening_balance or as_of).
+
+        Examples:
+          tl accounts create --name "Cash"
+          tl accounts create --name "Mortgage" --type loan --institution "Credit Union"
+          tl accounts create --name "Loan to Sam" --opening-balance 500 --as-of 2025-01-01
+        """
+        ensure_initialized()
+
+        balance = None
+        if opening_balance is not None:
+            if not opening_balance.strip():
+                _fail(
+                    "Opening balance is required",
+                    json_output,
+                    context={"field": "opening_balance"},
+                )
+            try:
+                balance = Decimal(opening_balance)
+            except InvalidOperation:
+                _fail(
+                    f"Invalid opening balance: {opening_balance}",
+                    json_output,
+                    context={"field": "opening_balance"},
+                )
+
+        as_of_date = None
+        if as_of is not None:
+            if balance is None:
+                _fail("--as-of requires --opening-balance", json_output)
+            try:
+                as_of_date = date.fromisoformat(as_of)
+            except ValueError:
+                _fail(
+                    f"Invalid date format: {as_of} (expected YYYY-MM-DD)",
+                    json_output,
+                    context={"field": "as_of"},
+                )
+
+        account_service = get_container().account_service()
+        result = asyncio.run(
+            account_service.create_account(
+                name=name,
+                account_type=account_type,
+                institution=institution,
+                currency=currency,
+                nickname=nickname,
+                opening_balance=balance,
+                as_of=as_of_date,
+            )
+        )
+
+        if not result.success:
+            _fail(result.error, json_output, context=result.context)
+
+        account = result.data
+
+        if json_output:
+            output_json({"success": True, "account": account})
+            return
+
+        console.print(f"\n[{theme.success}]✓ Created account: {account.name}[/{theme.success}]")
+        console.print(f"  Account ID: [{theme.emphasis}]{account.id}[/{theme.emphasis}]")
+        if balance is not None:
+            console.print(
+                f"  Opening balance: {balance} ({as_of_date or date.today()})"
+            )
+        console.print()
+
+    @accounts_app.command(name="set")
+    def accounts_set_command(
+        account: str = typer.Argument(..., help="Account name, nickname or ID"),
+        opened_at: str = typer.Option(
+            None, "--opened-at", help="Date the account was opened (YYYY-MM-DD, or 'none' to clear)"
+        ),
+        closed_at: str = typer.Option(
+            None, "--closed-at", help="Date the account was closed (YYYY-MM-DD, or 'none' to clear)"
+        ),
+        default_tags: str = typer.Option(
+            None,
+            "--default-tags",
+            help="Comma-separated tags added to new transactions in the account ('none' to clear)",
+        ),
+        nickname: str = typer.Option(
+            None, "--nickname", help="Name to show the account under ('none' to clear)"
+        ),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Set when an account was opened or closed, its default tags or nickname.
+
+        Reports count a closed account's balance as zero after its closed
+        date, and per-account averages only count months the account was open.
+
+        Default tags are added to every transaction synced, imported or
+        entered into the account from now on. Run `tl maintenance
+        apply-default-tags` to add them to transactions already there.
+
+        Examples:
+          tl accounts set "Old Checking" --closed-at 2025-06-30
+          tl accounts set "Chase Sapphire" --opened-at 2023-02-01
+          tl accounts set "Old Checking" --closed-at none
+          tl accounts set "HSA" --default-tags health,medical
+          tl accounts set "CREDIT CARD ...4821" --nickname Sapphire
+        """
+        ensure_initialized()
+
+        dates = {}
+        for key, option, value in (
+            ("opened_at", "--opened-at", opened_at),
+            ("closed_at", "--closed-at", closed_at),
+        ):
+            if value is None:
+                continue
+            if value.strip().lower() == "none":
+                dates[key] = None
+                continue
+            try:
+                dates[key] = date.fromisoformat(value)
+            except ValueError:
+                _fail(f"Invalid {option} date: {value} (expected YYYY-MM-DD or 'none')", json_output)
+
+        tags = None
+        if default_tags is not None:
+            tags = [] if default_tags.strip().lower() == "none" else default_tags.split(",")
+        if not dates and tags is None and nickname is None:
+            _fail(
+                "Nothing to set: pass --opened-at, --closed-at, --default-tags and/or --nickname",
+                json_output,
+            )
+
+        account_service = get_container().account_service()
+        resolve_result = asyncio.run(account_service.resolve_account(account))
+        if not resolve_result.success:
+            _fail(resolve_result.error, json_output)
+        account_id = resolve_result.data.id
+
+        data = {"account": resolve_result.data, "warnings": []}
+        if dates:
+            result = asyncio.run(account_service.set_account_dates(account_id, dates))
+            if not result.success:
+                _fail(result.error, json_output)
+            data = result.data
+        if tags is not None:
+            result = asyncio.run(account_service.set_default_tags(account_id, tags))
+            if not result.success:
+                _fail(result.error, json_output)
+            data = {**data, "account": result.data}
+        if nickname is not None:
+            value = None if nickname.strip().lower() == "none" else nickname
+            result = asyncio.run(account_service.set_nickname(account_id, value))
+            if not result.success:
+                _fail(result.error, json_output)
+            data = {**data, "account": result.data}
+
+        updated = data["account"]
+        if json_output:
+            output_json({"success": True, **data})
+            return
+
+        console.print(f"\n[{theme.success}]✓ Updated {updated.nickname or updated.name}[/{theme.success}]")
+        console.print(f"  Opened: {updated.opened_at or 'unknown'}")
+        console.print(f"  Closed: {updated.closed_at or '-'}")
+        console.print(f"  Default tags: {', '.join(updated.default_tags) or '-'}")
+        console.print(f"  Nickname: {updated.nickname or '-'}")
+        for warning in data["warnings"]:
+            console.print(f"[{theme.warning}]  ⚠ {warning}[/{theme.warning}]")
+        console.print()
+
+    @accounts_app.command(name="set-type")
+    def accounts_set_type_command(
+        account_id: str = typer.Argument(..., help="Account ID or unique ID prefix"),
+        account_type: str = typer.Argument(..., help=f"One of: {', '.join(ACCOUNT_TYPES)}"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Set an account's type.
+
+        Reports use the type to tell payments from income: money coming into
+        a credit or loan account is a payment, not income. tl sync lists new
+        accounts that still need one.
+
+        Examples:
+          tl accounts set-type 3f2a9c1e credit
+          tl accounts set-type 3f2a9c1e-0b7d-4e55-9a43-61c2f0d8e7b4 savings
+        """
+        ensure_initialized()
+
+        account_service = get_container().account_service()
+        resolve_result = asyncio.run(account_service.resolve_account_id(account_id))
+        if not resolve_result.success:
+            _fail(resolve_result.error, json_output, context=resolve_result.context)
+
+        result = asyncio.run(
+            account_service.update_account_type(resolve_result.data.id, account_type)
+        )
+        if not result.success:
+            _fail(result.error, json_output)
+
+        updated = result.data
+        if json_output:
+            output_json({"success": True, "account": updated})
+            return
+
+        console.print(
+            f"\n[{theme.success}]✓ {updated.nickname or updated.name} is now "
+            f"{updated.account_type}[/{theme.success}]\n"
+        )
+
+    @accounts_app.command(name="nickname")
+    def accounts_nickname_command(
+        account_id: str = typer.Argument(..., help="Account ID or unique ID prefix"),
+        nickname: str = typer.Argument(None, help="Name to show the account under"),
+        clear: bool = typer.Option(False, "--clear", help="Remove the nickname"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Give an account a nickname to show instead of its provider name.
+
+        The provider's name is kept; sync updates it but never the nickname.
+
+        Examples:
+          tl accounts nickname 3f2a9c1e "Sapphire"
+          tl accounts nickname 3f2a9c1e --clear
+        """
+        ensure_initialized()
+
+        if clear == (nickname is not None):
+            _fail("Pass either a nickname or --clear", json_output)
+
+        account_service = get_container().account_service()
+        resolve_result = asyncio.run(account_service.resolve_account_id(account_id))
+        if not resolve_result.success:
+            _fail(resolve_result.error, json_output, context=resolve_result.context)
+
+        result = asyncio.run(account_service.set_nickname(resolve_result.data.id, nickname))
+        if not result.success:
+            _fail(result.error, json_output)
+
+        updated = result.data
+        if json_output:
+            output_json({"success": True, "account": updated})
+            return
+
+        if updated.nickname:
+            message = f"{escape(updated.name)} is now shown as {escape(updated.nickname)}"
+        else:
+            message = f"Removed the nickname from {escape(updated.name)}"
+        console.print(f"\n[{theme.success}]✓ {message}[/{theme.success}]\n")
+
+    def set_archived(account_id: str, archived: bool, json_output: bool) -> None:
+        ensure_initialized()
+
+        account_service = get_container().account_service()
+        resolve_result = asyncio.run(account_service.resolve_account_id(account_id))
+        if not resolve_result.success:
+            _fail(resolve_result.error, json_output, context=resolve_result.context)
+
+        result = asyncio.run(account_service.set_archived(resolve_result.data.id, archived))
+        if not result.success:
+            _fail(result.error, json_output)
+
+        updated = result.data
+        if json_output:
+            output_json({"success": True, "account": updated})
+            return
+
+        name = escape(updated.nickname or updated.name)
+        message = f"Archived {name}" if archived else f"Unarchived {name}"
+        console.print(f"\n[{theme.success}]✓ {message}[/{theme.success}]\n")
+
+    @accounts_app.command(name="archive")
+    def accounts_archive_command(
+        account_id: str = typer.Argument(..., help="Account ID or unique ID prefix"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Archive an account you no longer use.
+
+        Archived accounts are left out of tl status totals and balance
+        backfills, and tl sync skips them unless --include-archived is
+        passed. Their transactions stay in the transactions view.
+
+        Examples:
+          tl accounts archive 3f2a9c1e
+        """
+        set_archived(account_id, True, json_output)
+
+    @accounts_app.command(name="unarchive")
+    def accounts_unarchive_command(
+        account_id: str = typer.Argument(..., help="Account ID or unique ID prefix"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Bring an archived account back into status, backfill and sync.
+
+        Examples:
+          tl accounts unarchive 3f2a9c1e
+        """
+        set_archived(account_id, False, json_output)
+
+    @accounts_app.command(name="find-by-external-id")
+    def accounts_find_by_external_id_command(
+        provider: str = typer.Option(
+            ..., "--provider", help="External ID key (simplefin, lunchflow...) or 'any'"
+        ),
+        external_id: str = typer.Option(..., "--id", help="The provider's ID for the account"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Find an account by a provider's ID and print its Treeline ID.
+
+        With --provider any every external ID key is searched and the key
+        that matched is shown.
+
+        Examples:
+          tl accounts find-by-external-id --provider simplefin --id ACT-91c2
+          tl accounts find-by-external-id --provider any --id ACT-91c2 --json
+        """
+        ensure_initialized()
+
+        result = asyncio.run(
+            get_container().account_service().find_by_external_id(provider, external_id)
+        )
+        if not result.success:
+            _fail(result.error, json_output, context=result.context)
+
+        if json_output:
+            output_json(
+                {
+                    "success": True,
+                    "provider": provider,
+                    "id": external_id,
+                    "matches": [
+                        {"id": str(account.id), "key": key, "account": account}
+                        for account, key in result.data
+                    ],
+                }
+            )
+            return
+
+        console.print()
+        for account, key in result.data:
+            label = account.nickname or account.name
+            if account.institution_name:
+                label += f" ({account.institution_name})"
+            console.print(f"[{theme.emphasis}]{account.id}[/{theme.emphasis}]")
+            console.print(f"  {label}")
+            console.print(f"  [{theme.muted}]{key}: {escape(account.external_ids[key])}[/{theme.muted}]")
+        console.print()
+
+    def _set_sync_enabled(account: str, enabled: bool, json_output: bool) -> None:
+        ensure_initialized()
+
+        container = get_container()
+        resolve_result = asyncio.run(container.account_service().resolve_account(account))
+        if not resolve_result.success:
+            _fail(resolve_result.error, json_output)
+        resolved = resolve_result.data
+
+        result = asyncio.run(
+            container.integration_service().set_account_sync_enabled(resolved, enabled)
+        )
+        if not result.success:
+            _fail(result.error, json_output)
+
+        if json_output:
+            output_json({"success": True, **result.data})
+            return
+
+        state = "enabled" if enabled else "disabled"
+        console.print(
+            f"\n[{theme.success}]✓ Sync {state} for {resolved.nickname or resolved.name}"
+            f" ({', '.join(result.data['integrations'])})[/{theme.success}]"
+        )
+        if not enabled:
+            console.print(
+                f"[{theme.muted}]  Existing transactions and balances are kept[/{theme.muted}]"
+            )
+        console.print()
+
+    @accounts_app.command(name="disable-sync")
+    def accounts_disable_sync_command(
+        account: str = typer.Argument(..., help="Account name, nickname or ID"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Stop syncing an account from its integrations.
+
+        Useful when two integrations sync the same bank account (tl sync
+        reports these): disable sync on one of them to stop the duplicates.
+
+        Examples:
+          tl accounts disable-sync "Checking (Lunch Flow)"
+        """
+        _set_sync_enabled(account, False, json_output)
+
+    @accounts_app.command(name="enable-sync")
+    def accounts_enable_sync_command(
+        account: str = typer.Argument(..., help="Account name, nickname or ID"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Resume syncing an account turned off with disable-sync."""
+        _set_sync_enabled(account, True, json_output)
@@ -1,4 +1,4 @@
-"""Backfill command - backfill historical balance snapshots."""
+"""Backfill command - backfill historical balance snapshots and transactions."""
 
 import asyncio
 from typing import List
@@ -7,6 +7,10 @@ from uuid import UUID
 import typer
 from rich.console import Console
 
+from treeline.app.sync_service import BACKFILL_DEFAULT_MONTHS, SyncContext
+from treeline.commands.sync import output_json_line
+from treeline.config import DEFAULT_WRITE_LOCK_WAIT_SECONDS, set_write_lock_wait
+from treeline.preamble import with_preamble
 from treeline.theme import get_theme
 from treeline.utils import get_log_file_path
 
@@ -29,13 +33,18 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
     def backfill_command(
         resource_type: str = typer.Argument(
             ...,
-            help="Type of resource to backfill (balances)",
+            help="Type of resource to backfill (balances, transactions)",
         ),
         account_id: List[str] = typer.Option(
             None,
             "--account-id",
             help="Account ID to backfill (can specify multiple)",
         ),
+        account: List[str] = typer.Option(
+            None,
+            "--account",
+            help="Account name or nickname to backfill (can specify multiple)",
+        ),
         days: int = typer.Option(
             None,
             "--days",
@@ -46,12 +55,50 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
             "--dry-run",
             help="Preview changes without saving",
         ),
+        force_zero: bool = typer.Option(
+            False,
+            "--force-zero",
+            help="Start from a balance of zero for accounts with no known balance",
+        ),
         verbose: bool = typer.Option(
             False,
             "--verbose",
             "-v",
             help="Show detailed output",
         ),
+        integration: str = typer.Option(
+            "simplefin",
+            "--integration",
+            help="Integration to fetch transaction history from",
+        ),
+        months: int = typer.Option(
+            BACKFILL_DEFAULT_MONTHS,
+            "--months",
+            min=1,
+            help="Months of transaction history to fetch, counting this one",
+        ),
+        resume: bool = typer.Option(
+            False,
+            "--resume",
+            help="Continue an interrupted transactions backfill where it stopped",
+        ),
+        delay: float = typer.Option(
+            None,
+            "--delay",
+            min=0,
+            help="Seconds between requests (default: sync.backfill_delay_seconds, or 2)",
+        ),
+        json_stream: bool = typer.Option(
+            False,
+            "--json-stream",
+            help="Output progress events as JSON lines as they happen, ending with the result",
+        ),
+        wait_lock: float = typer.Option(
+            DEFAULT_WRITE_LOCK_WAIT_SECONDS,
+            "--wait-lock",
+            min=0,
+            help="Seconds each month of transactions waits for another import or sync to finish writing",
+        ),
     ):
         """Backfill historical data.
 
@@ -61,26 +108,45 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
 
           # Backfill specific account
           tl backfill balances --account-id ACCOUNT-UUID
+          tl backfill balances --account "Chase Checking"
 
           # Backfill last 90 days only
           tl backfill balances --days 90 --dry-run
+
+          # Include accounts with no known balance, starting from zero today
+          tl backfill balances --account "Cash" --force-zero
+
+          # Two years of SimpleFIN history, a month per request
+          tl backfill transactions --months 24
+
+          # Pick up after an interrupted run
+          tl backfill transactions --resume
         """
         ensure_initialized()
+        set_write_lock_wait(wait_lock)
 
         if resource_type == "balances":
-            _backfill_balances(get_container, account_id, days, dry_run, verbose)
+            _backfill_balances(
+                get_container, account_id, account, days, dry_run, verbose, force_zero
+            )
+        elif resource_type == "transactions":
+            _backfill_transactions(
+                get_container, integration, months, resume, delay, dry_run, json_stream
+            )
         else:
             display_error(f"Unknown resource type: {resource_type}")
-            console.print(f"[{theme.muted}]Available types: balances[/{theme.muted}]")
+            console.print(f"[{theme.muted}]Available types: balances, transactions[/{theme.muted}]")
             raise typer.Exit(1)
 
 
 def _backfill_balances(
     get_container: callable,
     account_ids_str: List[str] | None,
+    account_names: List[str] | None,
     days: int | None,
     dry_run: bool,
     verbose: bool,
+    force_zero: bool = False,
 ) -> None:
     """Backfill balance snapshots from transaction history."""
     container = get_container()
@@ -89,6 +155,17 @@ def _backfill_balances(
     # Parse account IDs
     account_ids = [UUID(id_str) for id_str in account_ids_str] if account_ids_str else None
 
+    # Resolve account names and nicknames
+    if account_names:
+        account_service = container.account_service()
+        account_ids = account_ids or []
+        for name in account_names:
+            resolve_result = asyncio.run(account_service.resolve_account(name))
+            if not resolve_result.success:
+                display_error(resolve_result.error, show_log_hint=False)
+                raise typer.Exit(1)
+            account_ids.append(resolve_result.data.id)
+
     # Show dry-run indicator
     if dry_run:
         console.print(f"[{theme.warning}]DRY RUN - No changes will be saved[/{theme.warning}]\n")
@@ -96,7 +173,7 @@ def _backfill_balances(
     # Run backfill
     with console.status("[bold]Backfilling balance snapshots..."):
         result = asyncio.run(
-            backfill_service.backfill_balances(account_ids, days, dry_run, verbose)
+            backfill_service.backfill_balances(account_ids, days, dry_run, verbose, force_zero)
         )
 
     if not result.success:
@@ -111,6 +188,15 @@ def _backfill_balances(
         for warning in data["warnings"]:
             console.print(f"  {warning}")
 
+    # Display accounts left out for lack of a starting balance
+    if data.get("accounts_skipped"):
+        console.print(f"\n[{theme.warning}]Skipped accounts[/{theme.warning}]")
+        for skipped in data["accounts_skipped"]:
+            console.print(f"  {skipped['account']}: {skipped['reason']}")
+        console.print(
+            f"[{theme.muted}]Add a balance with tl new balance, or use --force-zero to start from zero[/{theme.muted}]"
+        )
+
     # Display verbose logs
     if verbose and data.get("verbose_logs"):
         console.print(f"\n[{theme.ui_header}]Detailed Logs[/{theme.ui_header}]")
@@ -122,6 +208,76 @@ def _backfill_balances(
     console.print(f"  Accounts processed: {data['accounts_processed']}")
     console.print(f"  Snapshots created: {data['snapshots_created']}")
     console.print(f"  Snapshots skipped: {data['snapshots_skipped']}")
+    if data.get("accounts_skipped"):
+        console.print(f"  Accounts skipped: {len(data['accounts_skipped'])}")
+
+    if dry_run:
+        console.print(f"\n[{theme.warning}]DRY RUN - No changes were saved[/{theme.warning}]")
+
+
+def _print_window_event(event: dict) -> None:
+    """One progress line per window as the backfill goes."""
+    window = event.get("window")
+    if event["event"] == "window_start":
+        console.print(
+            f"[{theme.muted}]{window} ({event['number']}/{event['total']}): fetching...[/{theme.muted}]"
+        )
+    elif event["event"] == "window_retry":
+        console.print(
+            f"[{theme.warning}]{window}: attempt {event['attempt']} failed, retrying: {event['error']}[/{theme.warning}]"
+        )
+    elif event["event"] == "window_done":
+        console.print(f"  {window}: {event['new']} new, {event['skipped']} already stored")
+
+
+def _backfill_transactions(
+    get_container: callable,
+    integration: str,
+    months: int,
+    resume: bool,
+    delay: float | None,
+    dry_run: bool,
+    json_stream: bool,
+) -> None:
+    """Fetch transaction history a month at a time, checkpointing as it goes."""
+    sync_service = get_container().sync_service()
+    context = SyncContext(
+        dry_run=dry_run, on_event=output_json_line if json_stream else _print_window_event
+    )
+
+    if dry_run and not json_stream:
+        console.print(f"[{theme.warning}]DRY RUN - No changes will be saved[/{theme.warning}]\n")
+
+    result = asyncio.run(
+        sync_service.backfill_transactions(
+            integration, months=months, resume=resume, delay=delay, context=context
+        )
+    )
+
+    if json_stream:
+        if not result.success:
+            output_json_line({"event": "error", "error": result.error, **(result.context or {})})
+            raise typer.Exit(1)
+        output_json_line({"event": "result", **with_preamble(result.data)})
+        return
+
+    if not result.success:
+        display_error(result.error, show_log_hint=False)
+        raise typer.Exit(1)
+
+    data = result.data
+    if data.get("provider_warnings"):
+        console.print(f"\n[{theme.warning}]Warnings[/{theme.warning}]")
+        for warning in data["provider_warnings"]:
+            console.print(f"  {warning}")
+
+    console.print(f"\n[{theme.success}]✓[/{theme.success}] Backfill complete")
+    console.print(f"  Period: {data['start_date']} to {data['end_date']}")
+    if data["resumed_after"]:
+        console.print(f"  Resumed after: {data['resumed_after']}")
+    console.print(f"  Months fetched: {data['windows_run']} of {data['windows']}")
+    console.print(f"  New transactions: {data['new']}")
+    console.print(f"  Already stored: {data['skipped']}")
 
     if dry_run:
         console.print(f"\n[{theme.warning}]DRY RUN - No changes were saved[/{theme.warning}]")
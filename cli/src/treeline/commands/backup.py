@@ -6,10 +6,11 @@ from typing import Optional
 
 import typer
 from rich.console import Console
-from rich.prompt import Confirm, Prompt
+from rich.prompt import Prompt
 from rich.table import Table
 
 from treeline.config import is_demo_mode
+from treeline.confirm import confirm_or_exit, deletion_action, require_input_or_exit
 from treeline.theme import get_theme
 
 console = Console()
@@ -212,6 +213,7 @@ def _do_restore(
             console.print(f"\n[{theme.warning}]No backups available to restore[/{theme.warning}]\n")
             raise typer.Exit(1)
 
+        require_input_or_exit("Backup name")
         console.print(f"\n[{theme.ui_header}]Select a backup to restore:[/{theme.ui_header}]\n")
 
         for i, backup in enumerate(backups, 1):
@@ -243,20 +245,8 @@ def _do_restore(
 
     # Confirm restore
     if not force and not json_output:
-        console.print(
-            f"\n[{theme.warning}]This will overwrite your current database with the backup.[/{theme.warning}]"
-        )
-        console.print(f"[{theme.muted}]Backup: {backup_name}[/{theme.muted}]\n")
-
-        try:
-            confirmed = Confirm.ask("Are you sure?", default=False)
-        except (KeyboardInterrupt, EOFError):
-            console.print(f"\n[{theme.muted}]Cancelled[/{theme.muted}]\n")
-            raise typer.Exit(0)
-
-        if not confirmed:
-            console.print(f"[{theme.muted}]Cancelled[/{theme.muted}]\n")
-            raise typer.Exit(0)
+        console.print()
+        confirm_or_exit(f"This will overwrite your current database with backup {backup_name}")
 
     # Perform restore
     if not json_output:
@@ -284,20 +274,14 @@ def _do_clear(container, force: bool, json_output: bool) -> None:
 
     # Confirm clear
     if not force and not json_output:
-        console.print(
-            f"\n[{theme.warning}]This will delete ALL backups.[/{theme.warning}]\n"
+        list_result = asyncio.run(backup_service.list_backups())
+        console.print()
+        confirm_or_exit(
+            deletion_action(len(list_result.data or []), "backups")
+            if list_result.success
+            else "This will delete ALL backups"
         )
 
-        try:
-            confirmed = Confirm.ask("Are you sure?", default=False)
-        except (KeyboardInterrupt, EOFError):
-            console.print(f"\n[{theme.muted}]Cancelled[/{theme.muted}]\n")
-            raise typer.Exit(0)
-
-        if not confirmed:
-            console.print(f"[{theme.muted}]Cancelled[/{theme.muted}]\n")
-            raise typer.Exit(0)
-
     # Perform clear
     if not json_output:
         with console.status(f"[{theme.status_loading}]Deleting backups..."):
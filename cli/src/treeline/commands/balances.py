@@ -0,0 +1,193 @@
+"""Balances command - balance lookups and corrections."""
+
+import asyncio
+import json
+from datetime import date
+from decimal import Decimal, InvalidOperation
+from uuid import UUID
+
+import typer
+from pydantic import BaseModel
+from rich.console import Console
+from rich.table import Table
+
+from treeline.app.preferences_service import format_currency, format_date
+from treeline.commands.json_output import decimal_json, model_json
+from treeline.config import get_display_locale
+from treeline.theme import get_theme
+from treeline.utils import get_log_file_path
+
+console = Console()
+theme = get_theme()
+
+# Create balances subcommand group
+balances_app = typer.Typer(help="Account balance commands")
+
+
+def json_serializer(obj):
+    """Custom JSON serializer for Pydantic models and other objects."""
+    if isinstance(obj, BaseModel):
+        return model_json(obj)
+    if isinstance(obj, Decimal):
+        return decimal_json(obj)
+    if isinstance(obj, date):
+        return obj.isoformat()
+    return str(obj)
+
+
+def output_json(data: dict) -> None:
+    """Output data as JSON."""
+    print(json.dumps(data, indent=2, default=json_serializer))
+
+
+def display_error(error: str, show_log_hint: bool = True) -> None:
+    """Display error message in consistent format."""
+    console.print(f"[{theme.error}]Error: {error}[/{theme.error}]")
+    if show_log_hint:
+        log_file = get_log_file_path()
+        console.print(f"[{theme.muted}]See {log_file} for details[/{theme.muted}]")
+
+
+def _fail(error: str, json_output: bool, show_log_hint: bool = False) -> None:
+    """Report an error in the requested format and exit."""
+    if json_output:
+        output_json({"success": False, "error": error})
+    else:
+        display_error(error, show_log_hint=show_log_hint)
+    raise typer.Exit(1)
+
+
+def _describe_anchor(entry: dict, locale: str) -> str:
+    anchor = entry["anchor"]
+    if anchor is None:
+        return "-"
+    source = f", {anchor['source']}" if anchor["source"] else ""
+    balance = format_currency(anchor["balance"], entry["currency"], locale=locale)
+    return f"{balance} on {format_date(anchor['snapshot_time'], locale)}{source}"
+
+
+def register(app: typer.Typer, get_container: callable, ensure_initialized: callable) -> None:
+    """Register the balances commands with the app."""
+    app.add_typer(balances_app, name="balances")
+
+    @balances_app.command(name="as-of")
+    def balances_as_of_command(
+        as_of: str = typer.Option(..., "--date", help="Date (YYYY-MM-DD)"),
+        account_id: str = typer.Option(None, "--account-id", help="Account ID"),
+        all_accounts: bool = typer.Option(False, "--all-accounts", help="Show every account"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Show account balances at the end of a past date.
+
+        Uses a balance snapshot from that day when available, otherwise
+        reconstructs the balance from the nearest snapshot and the
+        transactions in between. Accounts with no data yet show as unknown.
+
+        Examples:
+          tl balances as-of --account-id ACCOUNT-ID --date 2024-03-31
+          tl balances as-of --all-accounts --date 2024-12-31 --json
+        """
+        ensure_initialized()
+
+        if bool(account_id) == all_accounts:
+            _fail("Specify exactly one of --account-id or --all-accounts", json_output)
+
+        try:
+            as_of_date = date.fromisoformat(as_of)
+        except ValueError:
+            _fail(f"Invalid date format: {as_of} (expected YYYY-MM-DD)", json_output)
+
+        parsed_account_id = None
+        if account_id:
+            try:
+                parsed_account_id = UUID(account_id)
+            except ValueError:
+                _fail(f"Invalid account ID: {account_id}", json_output)
+
+        account_service = get_container().account_service()
+        result = asyncio.run(
+            account_service.get_balances_as_of(as_of_date, account_id=parsed_account_id)
+        )
+
+        if not result.success:
+            _fail(result.error, json_output)
+
+        if json_output:
+            output_json({"success": True, "as_of": as_of_date, "balances": result.data})
+            return
+
+        locale = get_display_locale()
+        table = Table(
+            title=f"Balances as of {format_date(as_of_date, locale)}",
+            show_header=True,
+            header_style=theme.ui_header,
+        )
+        table.add_column("Account")
+        table.add_column("Balance", justify="right")
+        table.add_column("Anchor snapshot", style=theme.muted)
+        table.add_column("Method")
+
+        for entry in result.data:
+            if entry["balance"] is None:
+                balance = f"[{theme.muted}]unknown[/{theme.muted}]"
+            else:
+                style = theme.negative_amount if entry["balance"] < 0 else theme.ui_value
+                amount = format_currency(entry["balance"], entry["currency"], locale=locale)
+                balance = f"[{style}]{amount}[/{style}]"
+            method = entry["status"]
+            if entry["reconstructed"]:
+                method += f" ({entry['transactions_applied']} transactions)"
+            table.add_row(entry["account_name"], balance, _describe_anchor(entry, locale), method)
+
+        console.print()
+        console.print(table)
+        console.print()
+
+    @balances_app.command(name="edit")
+    def balances_edit_command(
+        snapshot_id: str = typer.Argument(..., help="Balance snapshot ID"),
+        balance: str = typer.Option(..., "--balance", help="Corrected balance amount"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Correct the balance of an existing snapshot.
+
+        The snapshot keeps its date and source. Find snapshot IDs with:
+          tl query "SELECT snapshot_id, account_name, snapshot_time, balance FROM balance_snapshots"
+
+        Examples:
+          tl balances edit SNAPSHOT-ID --balance 1234.56
+          tl balances edit SNAPSHOT-ID --balance -250 --json
+        """
+        ensure_initialized()
+
+        try:
+            parsed_id = UUID(snapshot_id)
+        except ValueError:
+            _fail(f"Invalid snapshot ID: {snapshot_id}", json_output)
+
+        try:
+            amount = Decimal(balance)
+        except InvalidOperation:
+            _fail(f"Invalid balance: {balance}", json_output)
+        if not amount.is_finite():
+            _fail(f"Invalid balance: {balance}", json_output)
+
+        account_service = get_container().account_service()
+        result = asyncio.run(account_service.update_balance_snapshot(parsed_id, amount))
+
+        if not result.success:
+            _fail(result.error, json_output)
+
+        snapshot = result.data
+
+        if json_output:
+            output_json({"success": True, "snapshot": snapshot})
+            return
+
+        locale = get_display_locale()
+        console.print(
+            f"\n[{theme.success}]✓ Updated balance snapshot for "
+            f"{format_date(snapshot.snapshot_time.date(), locale)}[/{theme.success}]"
+        )
+        console.print(f"  Balance: [{theme.emphasis}]{snapshot.balance}[/{theme.emphasis}]")
+        console.print()
@@ -0,0 +1,124 @@
+"""Db commands - check and repair the database's views."""
+
+import asyncio
+import json
+
+import typer
+from rich.console import Console
+
+from treeline.theme import get_theme
+from treeline.utils import get_log_file_path
+
+console = Console()
+theme = get_theme()
+
+db_app = typer.Typer(help="Database schema commands")
+
+
+def output_json(data: dict) -> None:
+    """Output data as JSON."""
+    print(json.dumps(data, indent=2))
+
+
+def display_error(error: str, show_log_hint: bool = True) -> None:
+    """Display error message in consistent format."""
+    console.print(f"[{theme.error}]Error: {error}[/{theme.error}]")
+    if show_log_hint:
+        log_file = get_log_file_path()
+        console.print(f"[{theme.muted}]See {log_file} for details[/{theme.muted}]")
+
+
+def _fail(error: str, json_output: bool, show_log_hint: bool = True) -> None:
+    """Report an error in the requested format and exit."""
+    if json_output:
+        output_json({"success": False, "error": error})
+    else:
+        display_error(error, show_log_hint=show_log_hint)
+    raise typer.Exit(1)
+
+
+def display_drift(drift: list) -> None:
+    """Display drifted views, one per line with the columns that differ."""
+    for entry in drift:
+        if entry["missing"]:
+            console.print(f"  [{theme.warning}]{entry['view']}[/{theme.warning}]: view is missing")
+            continue
+        details = []
+        if entry["missing_columns"]:
+            details.append(f"missing {', '.join(entry['missing_columns'])}")
+        if entry["unexpected_columns"]:
+            details.append(f"unexpected {', '.join(entry['unexpected_columns'])}")
+        console.print(
+            f"  [{theme.warning}]{entry['view']}[/{theme.warning}]: {'; '.join(details)}"
+        )
+
+
+def register(app: typer.Typer, get_container: callable, ensure_initialized: callable) -> None:
+    """Register the db commands with the app."""
+    app.add_typer(db_app, name="db")
+
+    @db_app.command(name="check-views")
+    def check_views_command(
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Check that the views (transactions, accounts, ...) have their expected columns.
+
+        Views can drift when they're dropped or replaced by hand, e.g. with
+        tl query "CREATE OR REPLACE VIEW ...".
+
+        Examples:
+          tl db check-views
+          tl db check-views --json
+        """
+        ensure_initialized()
+
+        result = asyncio.run(get_container().db_service().check_views())
+        if not result.success:
+            _fail(result.error, json_output)
+
+        drift = result.data
+        if json_output:
+            output_json({"ok": not drift, "drift": drift})
+            return
+
+        if not drift:
+            console.print(f"[{theme.success}]All views match the expected schema[/{theme.success}]")
+            return
+        console.print(f"[{theme.warning}]{len(drift)} view(s) don't match the expected schema:[/{theme.warning}]")
+        display_drift(drift)
+        console.print(f"\n[{theme.muted}]Run 'tl db repair-views' to recreate them.[/{theme.muted}]")
+        raise typer.Exit(1)
+
+    @db_app.command(name="repair-views")
+    def repair_views_command(
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Recreate the views from the schema migrations.
+
+        Only the CREATE OR REPLACE VIEW statements are re-run. Tables and
+        their data are not touched.
+
+        Examples:
+          tl db repair-views
+        """
+        ensure_initialized()
+
+        result = asyncio.run(get_container().db_service().repair_views())
+        if not result.success:
+            _fail(result.error, json_output)
+
+        data = result.data
+        if json_output:
+            output_json({"success": not data["remaining_drift"], **data})
+            if data["remaining_drift"]:
+                raise typer.Exit(1)
+            return
+
+        console.print(
+            f"[{theme.success}]Recreated {len(data['repaired'])} view(s): "
+            f"{', '.join(data['repaired'])}[/{theme.success}]"
+        )
+        if data["remaining_drift"]:
+            console.print(f"[{theme.warning}]Some views still don't match:[/{theme.warning}]")
+            display_drift(data["remaining_drift"])
+            raise typer.Exit(1)
@@ -0,0 +1,236 @@
+"""Dedupe command - review possible duplicate transactions and accounts."""
+
+import asyncio
+import json
+from decimal import Decimal
+
+import typer
+from pydantic import BaseModel
+from rich.console import Console
+from rich.table import Table
+
+from treeline.app.account_service import short_id
+from treeline.app.dedupe_service import DUPLICATE_SCAN_DAYS, parse_status
+from treeline.app.preferences_service import format_currency, format_date
+from treeline.commands.json_output import decimal_json, model_json
+from treeline.config import get_display_locale
+from treeline.domain import Account, Transaction
+from treeline.theme import get_theme
+from treeline.utils import get_log_file_path
+
+console = Console()
+theme = get_theme()
+
+# Create dedupe subcommand group
+dedupe_app = typer.Typer(help="Review possible duplicate transactions and accounts")
+
+
+def json_serializer(obj):
+    """Custom JSON serializer for Pydantic models and other objects."""
+    if isinstance(obj, BaseModel):
+        return model_json(obj)
+    if isinstance(obj, Decimal):
+        return decimal_json(obj)
+    return str(obj)
+
+
+def output_json(data: dict) -> None:
+    """Output data as JSON."""
+    print(json.dumps(data, indent=2, default=json_serializer))
+
+
+def display_error(error: str, show_log_hint: bool = True) -> None:
+    """Display error message in consistent format."""
+    console.print(f"[{theme.error}]Error: {error}[/{theme.error}]")
+    if show_log_hint:
+        log_file = get_log_file_path()
+        console.print(f"[{theme.muted}]See {log_file} for details[/{theme.muted}]")
+
+
+def _fail(error: str, json_output: bool, context: dict | None = None) -> None:
+    """Report an error in the requested format and exit."""
+    matches = (context or {}).get("matches", [])
+    if json_output:
+        error_data = {"success": False, "error": error}
+        if matches:
+            error_data["matches"] = matches
+        output_json(error_data)
+    else:
+        display_error(error, show_log_hint=False)
+        for match in matches:
+            console.print(f"  {match}")
+    raise typer.Exit(1)
+
+
+def _describe(item: Transaction | Account | None, currency: str, locale: str | None) -> str:
+    """One-line label for either side of a candidate."""
+    if item is None:
+        return f"[{theme.muted}](removed)[/{theme.muted}]"
+    if isinstance(item, Account):
+        return f"{item.nickname or item.name} ({', '.join(sorted(item.external_ids)) or 'manual'})"
+    amount = format_currency(item.amount, currency, locale=locale)
+    return f"{format_date(item.transaction_date, locale)}  {item.description or '-'}  {amount}"
+
+
+def register(app: typer.Typer, get_container: callable, ensure_initialized: callable) -> None:
+    """Register the dedupe commands with the app."""
+    app.add_typer(dedupe_app, name="dedupe")
+
+    @dedupe_app.command(name="scan")
+    def dedupe_scan_command(
+        days: int = typer.Option(
+            DUPLICATE_SCAN_DAYS, "--days", min=1, help="How many days back to look"
+        ),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Queue transactions that share a fingerprint within an account.
+
+        Sync does this after every run; pairs already reviewed stay as they
+        were.
+
+        Examples:
+          tl dedupe scan
+          tl dedupe scan --days 365
+        """
+        ensure_initialized()
+
+        result = asyncio.run(get_container().dedupe_service().scan_transactions(days=days))
+        if not result.success:
+            _fail(result.error, json_output)
+
+        if json_output:
+            output_json({"success": True, **result.data})
+            return
+
+        console.print(
+            f"\n[{theme.success}]✓ Found {result.data['found']} possible duplicate(s), "
+            f"{result.data['added']} new[/{theme.success}]"
+        )
+        if result.data["added"]:
+            console.print(f"[{theme.muted}]Review them with: tl dedupe review[/{theme.muted}]")
+        console.print()
+
+    @dedupe_app.command(name="review")
+    def dedupe_review_command(
+        status: str = typer.Option(
+            "open", "--status", help="open, dismissed, merged or all"
+        ),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """List possible duplicates waiting for review, oldest first.
+
+        Examples:
+          tl dedupe review
+          tl dedupe review --status dismissed
+          tl dedupe review --json
+        """
+        ensure_initialized()
+
+        status_result = parse_status(status)
+        if not status_result.success:
+            _fail(status_result.error, json_output)
+
+        container = get_container()
+        result = asyncio.run(container.dedupe_service().list_candidates(status_result.data))
+        if not result.success:
+            _fail(result.error, json_output)
+
+        entries = result.data
+        if json_output:
+            output_json({"success": True, "candidates": entries})
+            return
+
+        if not entries:
+            console.print(f"\n[{theme.muted}]No possible duplicates to review[/{theme.muted}]\n")
+            return
+
+        currency = container.preferences_service().get_currency().data
+        locale = get_display_locale()
+        table = Table(title="Possible duplicates", show_header=True, header_style=theme.ui_header)
+        table.add_column("ID", style=theme.muted)
+        table.add_column("Kind")
+        table.add_column("Keep")
+        table.add_column("Duplicate")
+        table.add_column("Score", justify="right")
+        if status_result.data is None:
+            table.add_column("Status")
+
+        for entry in entries:
+            candidate = entry["candidate"]
+            row = [
+                short_id(candidate.id),
+                candidate.kind.value,
+                _describe(entry["keep"], currency, locale),
+                _describe(entry["duplicate"], currency, locale),
+                f"{candidate.score:.0%}",
+            ]
+            if status_result.data is None:
+                row.append(candidate.status.value)
+            table.add_row(*row)
+
+        console.print()
+        console.print(table)
+        console.print(
+            f"[{theme.muted}]Merge with: tl dedupe merge <id>   "
+            f"Not a duplicate: tl dedupe dismiss <id>[/{theme.muted}]\n"
+        )
+
+    @dedupe_app.command(name="dismiss")
+    def dedupe_dismiss_command(
+        candidate_id: str = typer.Argument(..., help="Candidate ID or unique ID prefix"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Mark a pair as not a duplicate. It won't be queued again.
+
+        Examples:
+          tl dedupe dismiss 3f2a9c1e
+        """
+        ensure_initialized()
+
+        result = asyncio.run(get_container().dedupe_service().dismiss(candidate_id))
+        if not result.success:
+            _fail(result.error, json_output, result.context)
+
+        if json_output:
+            output_json({"success": True, "candidate": result.data})
+            return
+
+        console.print(f"\n[{theme.success}]✓ Dismissed {short_id(result.data.id)}[/{theme.success}]\n")
+
+    @dedupe_app.command(name="merge")
+    def dedupe_merge_command(
+        candidate_id: str = typer.Argument(..., help="Candidate ID or unique ID prefix"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Merge a pair into the side that is kept.
+
+        For transactions, the duplicate is deleted and its tags and external
+        IDs move to the kept transaction. For accounts, the duplicate stops
+        syncing; its existing transactions and balances are kept.
+
+        Examples:
+          tl dedupe merge 3f2a9c1e
+        """
+        ensure_initialized()
+
+        result = asyncio.run(get_container().dedupe_service().merge(candidate_id))
+        if not result.success:
+            _fail(result.error, json_output, result.context)
+
+        data = result.data
+        if json_output:
+            output_json({"success": True, **data})
+            return
+
+        if data["kind"] == "account":
+            directory = asyncio.run(get_container().account_service().directory())
+            removed = directory.name_for(data["removed"])
+            console.print(
+                f"\n[{theme.success}]✓ Stopped syncing account {removed} "
+                f"({', '.join(data['integrations'])})[/{theme.success}]\n"
+            )
+        else:
+            console.print(
+                f"\n[{theme.success}]✓ Merged transaction {short_id(data['removed'])} "
+                f"into {short_id(data['kept'])}[/{theme.success}]\n"
+            )
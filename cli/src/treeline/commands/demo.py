@@ -1,16 +1,22 @@
 """Demo command - toggle demo mode."""
 
 import asyncio
+from pathlib import Path
 
 import typer
 from rich.console import Console
 
+from treeline.app.container import Container
 from treeline.config import is_demo_mode, set_demo_mode
+from treeline.features import DEMO, disabled_error, is_enabled
 from treeline.theme import get_theme
+from treeline.utils import get_treeline_dir
 
 console = Console()
 theme = get_theme()
 
+DEMO_DB_FILENAME = "demo.duckdb"
+
 # Will be set by register()
 _reset_container: callable = None
 
@@ -21,7 +27,7 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
     @app.command(name="demo")
     def demo_command(
         action: str = typer.Argument(
-            None, help="Action: 'on', 'off', or 'status' (default: status)"
+            None, help="Action: 'on', 'off', 'reset', or 'status' (default: status)"
         ),
     ) -> None:
         """Toggle demo mode on/off.
@@ -33,6 +39,7 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
           tl demo          # Show current status
           tl demo on       # Enable demo mode
           tl demo off      # Disable demo mode
+          tl demo reset    # Recreate demo data from scratch
         """
         # Default to status if no action provided
         if action is None:
@@ -40,15 +47,21 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
 
         action_lower = action.lower()
 
+        if action_lower in ("on", "reset") and not is_enabled(DEMO):
+            console.print(f"[{theme.error}]Error: {disabled_error(DEMO)}[/{theme.error}]")
+            raise typer.Exit(1)
+
         if action_lower == "status":
             _show_status()
         elif action_lower == "on":
             _enable_demo(get_container, ensure_initialized)
         elif action_lower == "off":
             _disable_demo()
+        elif action_lower == "reset":
+            _reset_demo(get_container, ensure_initialized)
         else:
             console.print(f"[{theme.error}]Unknown action: {action}[/{theme.error}]")
-            console.print(f"[{theme.muted}]Use 'on', 'off', or 'status'[/{theme.muted}]")
+            console.print(f"[{theme.muted}]Use 'on', 'off', 'reset', or 'status'[/{theme.muted}]")
             raise typer.Exit(1)
 
 
@@ -70,6 +83,7 @@ def _enable_demo(get_container: callable, ensure_initialized: callable) -> None:
         console.print(f"[{theme.muted}]Demo mode is already enabled[/{theme.muted}]\n")
         return
 
+    real_integrations = _real_integrations()
     set_demo_mode(True)
 
     # Reset container to pick up new database
@@ -77,7 +91,74 @@ def _enable_demo(get_container: callable, ensure_initialized: callable) -> None:
     reset_container()
 
     console.print(f"\n[{theme.success}]Demo mode enabled[/{theme.success}]")
+    if real_integrations:
+        console.print(
+            f"[{theme.warning}]Your real database has integrations configured "
+            f"({', '.join(real_integrations)}). Demo mode switches to a separate "
+            f"database with sample data; they are left untouched and come back "
+            f"with 'tl demo off'.[/{theme.warning}]"
+        )
 
+    _setup_demo_data(get_container, ensure_initialized)
+
+    console.print(f"\n[{theme.muted}]Run 'tl status' to see demo data[/{theme.muted}]")
+    console.print(f"[{theme.muted}]Run 'tl demo off' to return to real data[/{theme.muted}]\n")
+
+
+def _real_integrations() -> list[str]:
+    """Names of the integrations configured in the real database.
+
+    Best effort, and never prompts: an encrypted database that TL_DB_PASSWORD
+    doesn't unlock is not checked.
+    """
+    container = Container(str(get_treeline_dir()))
+    if not Path(container.db_file_path).exists():
+        return []
+    try:
+        result = asyncio.run(container.integration_service().get_integrations())
+    except Exception:
+        return []
+    if not result.success:
+        return []
+    return sorted(
+        integration["integrationName"]
+        for integration in result.data or []
+        if integration["integrationName"] != DEMO
+    )
+
+
+def _reset_demo(get_container: callable, ensure_initialized: callable) -> None:
+    """Delete demo.duckdb and rebuild it with a fresh demo dataset."""
+    if not is_demo_mode():
+        console.print(f"[{theme.error}]Demo mode is off - refusing to reset[/{theme.error}]")
+        console.print(f"[{theme.muted}]Run 'tl demo on' first[/{theme.muted}]")
+        raise typer.Exit(1)
+
+    db_path = Path(get_container().db_file_path)
+    # Belt and braces: never delete anything but the demo database
+    if db_path.name != DEMO_DB_FILENAME:
+        console.print(
+            f"[{theme.error}]Refusing to reset {db_path} - not the demo database[/{theme.error}]"
+        )
+        raise typer.Exit(1)
+
+    # Drop cached services (and their connections) before deleting the file
+    from treeline.cli import reset_container
+    reset_container()
+
+    for path in (db_path, db_path.with_name(db_path.name + ".wal")):
+        if path.exists():
+            path.unlink()
+
+    console.print(f"\n[{theme.success}]Demo database deleted[/{theme.success}]")
+
+    _setup_demo_data(get_container, ensure_initialized)
+
+    console.print(f"\n[{theme.muted}]Run 'tl status' to see demo data[/{theme.muted}]\n")
+
+
+def _setup_demo_data(get_container: callable, ensure_initialized: callable) -> None:
+    """Create the demo integration and populate the demo database."""
     # Initialize demo database and sync demo data
     ensure_initialized()
     container = get_container()
@@ -98,13 +179,21 @@ def _enable_demo(get_container: callable, ensure_initialized: callable) -> None:
 
     if not has_demo:
         # Create demo integration
-        asyncio.run(integration_service.create_integration(demo_provider, "demo", {}))
+        create_result = asyncio.run(
+            integration_service.create_integration(demo_provider, "demo", {})
+        )
+        if not create_result.success:
+            console.print(
+                f"[{theme.error}]Failed to create demo integration: {create_result.error}[/{theme.error}]"
+            )
+            raise typer.Exit(1)
 
     # Sync demo data
     sync_service = container.sync_service()
     console.print(f"[{theme.muted}]Syncing demo data...[/{theme.muted}]")
     with console.status(f"[{theme.status_loading}]Syncing demo accounts and transactions..."):
-        result = asyncio.run(sync_service.sync_all_integrations())
+        # Demo setup must write its data; never inherit a dry-run default
+        result = asyncio.run(sync_service.sync_all_integrations(dry_run=False))
 
     if result.success:
         console.print(f"[{theme.success}]Demo data synced successfully![/{theme.success}]")
@@ -147,9 +236,6 @@ def _enable_demo(get_container: callable, ensure_initialized: callable) -> None:
     else:
         console.print(f"[{theme.warning}]Note: {budget_result.error}[/{theme.warning}]")
 
-    console.print(f"\n[{theme.muted}]Run 'tl status' to see demo data[/{theme.muted}]")
-    console.print(f"[{theme.muted}]Run 'tl demo off' to return to real data[/{theme.muted}]\n")
-
 
 def _disable_demo() -> None:
     """Disable demo mode."""
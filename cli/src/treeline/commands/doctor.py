@@ -6,6 +6,8 @@ import json as json_module
 import typer
 from rich.console import Console
 
+from treeline.app.account_service import short_id
+from treeline.commands.json_output import decimal_json
 from treeline.theme import get_theme
 
 console = Console()
@@ -100,7 +102,7 @@ def register(
                     for check in report.checks
                 },
             }
-            print(json_module.dumps(output, indent=2))
+            print(json_module.dumps(output, indent=2, default=decimal_json))
         else:
             display_report(report, verbose, currency)
 
@@ -171,10 +173,13 @@ def display_details(check, show_all: bool = True, currency: str = "USD") -> None
     details_to_show = check.details if show_all else check.details[:5]
     for detail in details_to_show:
         if check.name == "orphaned_transactions":
-            console.print(f"    [{theme.muted}]txn {detail['transaction_id'][:8]}... → account {detail['account_id'][:8]}...[/{theme.muted}]")
+            console.print(f"    [{theme.muted}]txn {detail['transaction_id'][:8]}... → account {short_id(detail['account_id'])}[/{theme.muted}]")
 
         elif check.name == "orphaned_snapshots":
-            console.print(f"    [{theme.muted}]snapshot {detail['snapshot_id'][:8]}... → account {detail['account_id'][:8]}...[/{theme.muted}]")
+            console.print(f"    [{theme.muted}]snapshot {detail['snapshot_id'][:8]}... → account {short_id(detail['account_id'])}[/{theme.muted}]")
+
+        elif check.name == "dangling_attachments":
+            console.print(f"    [{theme.muted}]{detail['filename']} → txn {short_id(detail['transaction_id'])}[/{theme.muted}]")
 
         elif check.name == "duplicate_fingerprints":
             console.print(f"    [{theme.muted}]Fingerprint {detail['fingerprint']} ({detail['duplicate_count']} copies):[/{theme.muted}]")
@@ -6,9 +6,10 @@ import os
 
 import typer
 from rich.console import Console
-from rich.prompt import Confirm, Prompt
+from rich.prompt import Prompt
 
 from treeline.config import is_demo_mode
+from treeline.confirm import confirm_or_exit, require_input_or_exit
 from treeline.theme import get_theme
 from treeline.utils import get_log_file_path
 
@@ -190,6 +191,7 @@ def _do_encrypt(get_container: callable, password: str | None, json_output: bool
         password = os.environ.get("TL_DB_PASSWORD")
 
     if not password and not json_output:
+        require_input_or_exit("Password (--password or TL_DB_PASSWORD)")
         console.print(f"\n[{theme.ui_header}]Enable Database Encryption[/{theme.ui_header}]\n")
         console.print(
             f"[{theme.warning}]WARNING: If you forget your password, your data cannot be recovered![/{theme.warning}]"
@@ -205,11 +207,7 @@ def _do_encrypt(get_container: callable, password: str | None, json_output: bool
                 raise typer.Exit(1)
 
             if len(password) < 8:
-                console.print(
-                    f"[{theme.warning}]Password should be at least 8 characters[/{theme.warning}]"
-                )
-                if not Confirm.ask("Continue anyway?", default=False):
-                    raise typer.Exit(0)
+                confirm_or_exit("The password is shorter than the recommended 8 characters")
 
         except (KeyboardInterrupt, EOFError):
             console.print(f"\n[{theme.muted}]Cancelled[/{theme.muted}]\n")
@@ -278,6 +276,7 @@ def _do_decrypt(get_container: callable, password: str | None, json_output: bool
         password = os.environ.get("TL_DB_PASSWORD")
 
     if not password and not json_output:
+        require_input_or_exit("Password (--password or TL_DB_PASSWORD)")
         console.print(f"\n[{theme.ui_header}]Disable Database Encryption[/{theme.ui_header}]\n")
         try:
             password = Prompt.ask("Enter current password", password=True)
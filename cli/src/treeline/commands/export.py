@@ -0,0 +1,222 @@
+"""Export command - write account data to files for other tools."""
+
+import asyncio
+import json
+from datetime import date
+from decimal import Decimal
+from pathlib import Path
+from uuid import UUID
+
+import typer
+from pydantic import BaseModel
+from rich.console import Console
+
+from treeline.app.db_service import EXPORT_FORMATS
+from treeline.app.report_csv import render_register_csv
+from treeline.commands.json_output import decimal_json, model_json
+from treeline.theme import get_theme
+from treeline.utils import get_log_file_path
+
+console = Console()
+theme = get_theme()
+
+# Create export subcommand group
+export_app = typer.Typer(help="Export data to files")
+
+REGISTER_FORMATS = ("csv", "json")
+
+
+def json_serializer(obj):
+    """Custom JSON serializer for Pydantic models and other objects."""
+    if isinstance(obj, BaseModel):
+        return model_json(obj)
+    if isinstance(obj, Decimal):
+        return decimal_json(obj)
+    if isinstance(obj, date):
+        return obj.isoformat()
+    return str(obj)
+
+
+def output_json(data: dict) -> None:
+    """Output data as JSON."""
+    print(json.dumps(data, indent=2, default=json_serializer))
+
+
+def display_error(error: str, show_log_hint: bool = True) -> None:
+    """Display error message in consistent format."""
+    console.print(f"[{theme.error}]Error: {error}[/{theme.error}]")
+    if show_log_hint:
+        log_file = get_log_file_path()
+        console.print(f"[{theme.muted}]See {log_file} for details[/{theme.muted}]")
+
+
+def _fail(error: str, json_output: bool, show_log_hint: bool = False) -> None:
+    """Report an error in the requested format and exit."""
+    if json_output:
+        output_json({"success": False, "error": error})
+    else:
+        display_error(error, show_log_hint=show_log_hint)
+    raise typer.Exit(1)
+
+
+def _parse_date(value: str | None, flag: str, json_output: bool) -> date | None:
+    if value is None:
+        return None
+    try:
+        return date.fromisoformat(value)
+    except ValueError:
+        _fail(f"Invalid {flag} date: {value} (expected YYYY-MM-DD)", json_output)
+
+
+def register(app: typer.Typer, get_container: callable, ensure_initialized: callable) -> None:
+    """Register the export commands with the app."""
+    app.add_typer(export_app, name="export")
+
+    @export_app.command(name="register")
+    def register_command(
+        output: str = typer.Option(..., "--output", "-o", help="File to write (.csv or .json)"),
+        account_id: str = typer.Option(None, "--account-id", help="Account to export"),
+        account: str = typer.Option(None, "--account", help="Account to export, by name or nickname"),
+        since: str = typer.Option(None, "--since", help="Start date (YYYY-MM-DD, defaults to the first transaction)"),
+        until: str = typer.Option(None, "--until", help="End date (YYYY-MM-DD, defaults to today)"),
+        export_format: str = typer.Option(
+            None, "--format", help="csv or json (defaults to the --output extension)"
+        ),
+        json_output: bool = typer.Option(False, "--json", help="Output the result as JSON"),
+    ) -> None:
+        """Export one account's register, laid out like a bank statement.
+
+        Columns are date, description, debit (money out), credit (money in)
+        and running balance, oldest first. The opening balance is the
+        account's balance the day before --since; it is marked estimated when
+        it was reconstructed from a balance snapshot on another day, and the
+        balance column is left empty when no balance is known. The last row
+        totals debits and credits, with the net change in the balance column.
+
+        Examples:
+          tl export register --account checking --since 2024-01-01 --until 2024-12-31 -o 2024.csv
+          tl export register --account-id ACCOUNT-ID --output register.json
+        """
+        ensure_initialized()
+
+        if account and account_id:
+            _fail("Use either --account or --account-id, not both", json_output)
+        if not account and not account_id:
+            _fail("--account or --account-id is required", json_output)
+
+        output_path = Path(output).expanduser()
+        fmt = (export_format or output_path.suffix.lstrip(".")).lower()
+        if fmt not in REGISTER_FORMATS:
+            _fail(f"Unsupported format: {fmt or output} (use csv or json)", json_output)
+
+        start_date = _parse_date(since, "--since", json_output)
+        end_date = _parse_date(until, "--until", json_output)
+        if start_date and end_date and start_date > end_date:
+            _fail("Start date must be on or before end date", json_output)
+
+        container = get_container()
+        if account_id:
+            try:
+                parsed_account_id = UUID(account_id)
+            except ValueError:
+                _fail(f"Invalid account ID: {account_id}", json_output)
+        else:
+            resolve_result = asyncio.run(container.account_service().resolve_account(account))
+            if not resolve_result.success:
+                _fail(resolve_result.error, json_output)
+            parsed_account_id = resolve_result.data.id
+
+        result = asyncio.run(
+            container.report_service().register(parsed_account_id, start_date, end_date)
+        )
+        if not result.success:
+            _fail(result.error, json_output, show_log_hint=True)
+        data = result.data
+
+        if fmt == "json":
+            content = json.dumps(data, indent=2, default=json_serializer) + "\n"
+        else:
+            content = render_register_csv(data)
+        try:
+            # newline="" keeps the csv module's line endings as written
+            with open(output_path, "w", encoding="utf-8", newline="") as f:
+                f.write(content)
+        except OSError as e:
+            _fail(f"Could not write {output_path}: {e}", json_output)
+
+        if json_output:
+            output_json(
+                {
+                    "success": True,
+                    "output": str(output_path),
+                    "rows": len(data["rows"]),
+                    "estimated": data["estimated"],
+                }
+            )
+            return
+
+        console.print(
+            f"[{theme.success}]✓ Wrote {len(data['rows'])} transactions to {output_path}[/{theme.success}]"
+        )
+        if data["estimated"]:
+            console.print(
+                f"[{theme.muted}]Opening balance is estimated from a snapshot on another day[/{theme.muted}]"
+            )
+        elif data["opening_balance"] is None:
+            console.print(
+                f"[{theme.warning}]No balance known for this account - the balance column is empty[/{theme.warning}]"
+            )
+
+    @export_app.command(name="transactions")
+    def transactions_command(
+        output: str = typer.Option(
+            ..., "--output", "-o", help="File to write (.parquet, .csv or .json)"
+        ),
+        since: str = typer.Option(None, "--since", help="Start date (YYYY-MM-DD)"),
+        until: str = typer.Option(None, "--until", help="End date (YYYY-MM-DD)"),
+        export_format: str = typer.Option(
+            None, "--format", help="parquet, csv or json (defaults to the --output extension)"
+        ),
+        force: bool = typer.Option(False, "--force", help="Overwrite the file if it exists"),
+        json_output: bool = typer.Option(False, "--json", help="Output the result as JSON"),
+    ) -> None:
+        """Export all transactions, with every column, for other tools.
+
+        Has every column of the transactions view (including month, year and
+        display_name) plus external_ids, created_at and updated_at, oldest
+        first. DuckDB writes the file directly, so parquet keeps dates,
+        decimals and tag lists typed.
+
+        Examples:
+          tl export transactions -o transactions.parquet
+          tl export transactions --since 2024-01-01 --until 2024-12-31 -o 2024.csv
+          tl export transactions -o transactions.parquet --force --json
+        """
+        ensure_initialized()
+
+        output_path = Path(output).expanduser()
+        fmt = (export_format or output_path.suffix.lstrip(".")).lower()
+        if fmt not in EXPORT_FORMATS:
+            _fail(f"Unsupported format: {fmt or output} (use parquet, csv or json)", json_output)
+
+        start_date = _parse_date(since, "--since", json_output)
+        end_date = _parse_date(until, "--until", json_output)
+        if start_date and end_date and start_date > end_date:
+            _fail("Start date must be on or before end date", json_output)
+
+        result = asyncio.run(
+            get_container()
+            .db_service()
+            .export_transactions(output_path, fmt, start_date, end_date, overwrite=force)
+        )
+        if not result.success:
+            hint = " (use --force to overwrite)" if (result.context or {}).get("exists") else ""
+            _fail(result.error + hint, json_output)
+
+        if json_output:
+            output_json({"success": True, **result.data})
+            return
+
+        console.print(
+            f"[{theme.success}]✓ Wrote {result.data['rows']} transactions to {output_path}[/{theme.success}]"
+        )
@@ -3,25 +3,43 @@
 import asyncio
 import json as json_module
 import os
+import signal
 from pathlib import Path
 from typing import Any, Dict, List, Optional
 from uuid import UUID
 
 import typer
 from rich.console import Console
+from rich.markup import escape
 from rich.prompt import Prompt
 from rich.table import Table
 
 from treeline.app.account_service import AccountService
-from treeline.app.import_service import ImportService
-from treeline.domain import Account, Transaction
+from treeline.app.import_service import FOREIGN_CURRENCY_MODES, ImportService
+from treeline.commands.json_output import amount_json
+from treeline.config import DEFAULT_WRITE_LOCK_WAIT_SECONDS, set_write_lock_wait
+from treeline.confirm import require_input_or_exit
+from treeline.domain import Account, CSVPreset, DetectionReport, Transaction
+from treeline.preamble import print_preamble, with_preamble
+from treeline.tables import add_column, print_table, resolve_layout
 from treeline.theme import get_theme
+from treeline.utils import resolve_user_path
 
 console = Console()
 theme = get_theme()
 
 ACCOUNT_TYPES = ["depository", "credit", "investment", "loan", "other"]
 
+PREVIEW_ROWS = 10
+# Rows skipped for their currency that are listed after an import
+CURRENCY_SKIPPED_SHOWN = 10
+# Rows with out-of-range amounts listed when an import is refused
+OUT_OF_RANGE_SHOWN = 10
+# Rows read during preview to judge whether the signs look inverted
+SIGN_CHECK_ROWS = 100
+# Exit code when Ctrl-C stops an import after a chunk (it can be resumed)
+EXIT_IMPORT_INTERRUPTED = 130
+
 
 def register(app: typer.Typer, get_container: callable, ensure_initialized: callable) -> None:
     """Register the import command with the app."""
@@ -29,27 +47,105 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
     @app.command(name="import")
     def import_command(
         file_path: str = typer.Argument(None, help="Path to CSV file (omit for interactive mode)"),
+        doctor_file: str = typer.Argument(None, help="With 'doctor': the CSV file to diagnose"),
         account_id: str = typer.Option(None, "--account-id", help="Account ID to import into"),
+        account: str = typer.Option(None, "--account", help="Account name or nickname to import into"),
         date_column: str = typer.Option(None, "--date-column", help="CSV column name for date"),
         amount_column: str = typer.Option(None, "--amount-column", help="CSV column name for amount"),
         description_column: str = typer.Option(None, "--description-column", help="CSV column name for description"),
         debit_column: str = typer.Option(None, "--debit-column", help="CSV column name for debits"),
         credit_column: str = typer.Option(None, "--credit-column", help="CSV column name for credits"),
+        currency_column: str = typer.Option(None, "--currency-column", help="CSV column name for each row's currency code"),
+        foreign_currency: str = typer.Option(
+            "skip",
+            "--foreign-currency",
+            help="Rows in another currency than the account's: skip, keep (tagged fx:<code>) or convert (settings.json exchange_rates)",
+        ),
         flip_signs: bool = typer.Option(False, "--flip-signs", help="Flip transaction signs (for credit cards)"),
         debit_negative: bool = typer.Option(False, "--debit-negative", help="Negate debit amounts"),
         preview: bool = typer.Option(False, "--preview", help="Preview only, don't import"),
+        force: bool = typer.Option(False, "--force", help="Re-process a file that was already imported"),
+        resume: str = typer.Option(
+            None, "--resume", help="Continue the interrupted import with this batch ID"
+        ),
+        restart: bool = typer.Option(
+            False, "--restart", help="Undo this file's interrupted import and start over"
+        ),
+        wait_lock: float = typer.Option(
+            DEFAULT_WRITE_LOCK_WAIT_SECONDS,
+            "--wait-lock",
+            min=0,
+            help="Seconds to wait for another treeline import or sync to finish writing",
+        ),
         json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
     ) -> None:
         """Import transactions from CSV file.
 
         Run 'tl import' with no arguments for interactive mode with auto-detection.
 
+        Large files are written a few thousand rows at a time. Ctrl-C
+        finishes the current chunk, saves a checkpoint and exits with code
+        130; running the same file again then needs --resume <batch> (which
+        reuses the interrupted run's column options) or --restart.
+
+        Only one import or sync writes at a time; another one running waits
+        up to --wait-lock seconds for it to finish.
+
+        Known exports (Chase, Amex, Capital One, Citi, Discover, Fidelity)
+        are recognized by their headers and get the right columns and sign
+        convention. Explicit column options skip this detection.
+
         Examples:
           tl import
           tl import transactions.csv --account-id <uuid>
           tl import transactions.csv --account-id <uuid> --preview
+          tl import transactions.csv --account-id <uuid> --force
+          tl import transactions.csv --account "Chase Checking"
+
+          # Carry on with an import stopped by Ctrl-C
+          tl import history.csv --account Checking --resume 3f2a9c1e
+
+          # A file mixing currencies: convert rows not in the account's currency
+          tl import brokerage.csv --account Brokerage --currency-column Currency --foreign-currency convert
+
+          # Explain column detection and parsing without importing
+          tl import doctor transactions.csv
         """
         ensure_initialized()
+        set_write_lock_wait(wait_lock)
+
+        if resume and restart:
+            console.print(f"[{theme.error}]Error: Use either --resume or --restart, not both[/{theme.error}]")
+            raise typer.Exit(1)
+
+        if foreign_currency not in FOREIGN_CURRENCY_MODES:
+            console.print(
+                f"[{theme.error}]Error: --foreign-currency must be one of: "
+                f"{', '.join(FOREIGN_CURRENCY_MODES)}[/{theme.error}]"
+            )
+            raise typer.Exit(1)
+
+        if doctor_file and file_path != "doctor":
+            console.print(f"[{theme.error}]Error: Unexpected argument: {doctor_file}[/{theme.error}]")
+            raise typer.Exit(1)
+        if file_path == "doctor" and not doctor_file and not Path(file_path).exists():
+            console.print(f"[{theme.error}]Error: Usage: tl import doctor <file>[/{theme.error}]")
+            raise typer.Exit(1)
+
+        if doctor_file:
+            _do_doctor(
+                get_container().import_service(),
+                doctor_file,
+                _with_currency_column(
+                    _build_column_mapping(
+                        date_column, amount_column, description_column, debit_column, credit_column
+                    ),
+                    currency_column,
+                ),
+                debit_negative,
+                json_output,
+            )
+            return
 
         container = get_container()
         import_service = container.import_service()
@@ -62,8 +158,10 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
         currency_result = preferences_service.get_currency()
         user_currency = currency_result.data if currency_result.success else DEFAULT_CURRENCY
 
+        preset = None
         # Interactive mode - collect parameters interactively
         if file_path is None:
+            require_input_or_exit("File path and --account-id", json_output)
             params = _collect_params_interactive(import_service, account_service, user_currency)
             if params is None:
                 return  # User cancelled
@@ -73,16 +171,24 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
             flip_signs = params["flip_signs"]
             debit_negative = params["debit_negative"]
             column_mapping = params["column_mapping"]
+            preset = params["preset"]
         else:
             # Scriptable mode - validate required params
-            csv_path = Path(file_path).expanduser()
-            if not csv_path.exists():
-                console.print(f"[{theme.error}]Error: File not found: {file_path}[/{theme.error}]")
-                raise typer.Exit(1)
-            file_path = str(csv_path)
+            file_path = str(resolve_user_path(file_path))
+            _check_file_or_exit(import_service, file_path, json_output)
+
+            if account is not None:
+                if account_id:
+                    console.print(f"[{theme.error}]Error: Use either --account or --account-id, not both[/{theme.error}]")
+                    raise typer.Exit(1)
+                resolve_result = asyncio.run(account_service.resolve_account(account))
+                if not resolve_result.success:
+                    console.print(f"[{theme.error}]Error: {resolve_result.error}[/{theme.error}]")
+                    raise typer.Exit(1)
+                account_id = resolve_result.data.id
 
             if not account_id:
-                console.print(f"[{theme.error}]Error: --account-id is required for scriptable import[/{theme.error}]")
+                console.print(f"[{theme.error}]Error: --account or --account-id is required for scriptable import[/{theme.error}]")
                 console.print(f"[{theme.muted}]Run 'tl status --json' to see account IDs[/{theme.muted}]")
                 raise typer.Exit(1)
 
@@ -94,16 +200,27 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
                 column_mapping = _detect_columns(import_service, file_path, json_output)
                 if column_mapping is None:
                     raise typer.Exit(1)
+                # Explicit columns skip presets, and with them the preset's signs
+                preset = _detect_preset(import_service, file_path)
+                if preset:
+                    flip_signs = flip_signs or preset.flip_signs
+                    debit_negative = debit_negative or preset.debit_negative
+            column_mapping = _with_currency_column(column_mapping, currency_column)
 
         # Preview mode
         if preview:
-            _do_preview(import_service, file_path, column_mapping, flip_signs, debit_negative, json_output, user_currency)
+            _do_preview(
+                import_service, file_path, column_mapping, flip_signs, debit_negative, json_output, user_currency,
+                account_id=UUID(account_id) if isinstance(account_id, str) else account_id,
+                preset=preset,
+            )
             return
 
         # Import mode
         _do_import(
             import_service, file_path, UUID(account_id) if isinstance(account_id, str) else account_id,
-            column_mapping, flip_signs, debit_negative, json_output
+            column_mapping, flip_signs, debit_negative, json_output, force, foreign_currency,
+            resume=resume, restart=restart,
         )
 
 
@@ -111,6 +228,22 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
 # Core import operations (shared by both modes)
 # =============================================================================
 
+def _check_file_or_exit(import_service: ImportService, file_path: str, json_output: bool) -> None:
+    """Exit with the reason a file can't be imported (missing, too large, not a CSV).
+
+    With --json the error's kind and details are included, e.g.
+    {"kind": "wrong_file_type", "detected_type": "pdf"}.
+    """
+    result = import_service.check_import_file(file_path)
+    if result.success:
+        return
+    if json_output:
+        print(json_module.dumps({"success": False, "error": result.error, **result.context}, indent=2))
+    else:
+        console.print(f"[{theme.error}]Error: {escape(result.error)}[/{theme.error}]")
+    raise typer.Exit(1)
+
+
 def _detect_columns(
     import_service: ImportService, file_path: str, json_output: bool = False
 ) -> Optional[Dict[str, str]]:
@@ -127,6 +260,12 @@ def _detect_columns(
     return result.data
 
 
+def _detect_preset(import_service: ImportService, file_path: str) -> Optional[CSVPreset]:
+    """Institution preset matching the file's headers. None if there is none."""
+    result = asyncio.run(import_service.detect_preset(file_path))
+    return result.data if result.success else None
+
+
 def _do_preview(
     import_service: ImportService,
     file_path: str,
@@ -135,6 +274,8 @@ def _do_preview(
     debit_negative: bool,
     json_output: bool,
     currency: str = "USD",
+    account_id: Optional[UUID] = None,
+    preset: Optional[CSVPreset] = None,
 ) -> None:
     """Preview transactions without importing."""
     preview_result = asyncio.run(
@@ -142,7 +283,7 @@ def _do_preview(
             file_path=file_path,
             column_mapping=column_mapping,
             date_format="auto",
-            limit=10,
+            limit=SIGN_CHECK_ROWS,
             flip_signs=flip_signs,
             debit_negative=debit_negative,
         )
@@ -152,25 +293,37 @@ def _do_preview(
         console.print(f"[{theme.error}]Error: Preview failed: {preview_result.error}[/{theme.error}]")
         raise typer.Exit(1)
 
+    warnings = _sign_warnings(import_service, account_id, preview_result.data)
+
     if json_output:
         preview_data = {
             "file": file_path,
             "flip_signs": flip_signs,
             "debit_negative": debit_negative,
+            "preset": preset.model_dump(mode="json") if preset else None,
             "preview": [
-                {"date": str(tx.transaction_date), "description": tx.description, "amount": float(tx.amount)}
-                for tx in preview_result.data
+                {"date": str(tx.transaction_date), "description": tx.description, "amount": amount_json(tx.amount, currency)}
+                for tx in preview_result.data[:PREVIEW_ROWS]
             ],
+            "warnings": warnings,
         }
+        detection_result = asyncio.run(
+            import_service.diagnose_csv(file_path, column_mapping, debit_negative)
+        )
+        if detection_result.success:
+            preview_data["detection"] = detection_result.data.model_dump(mode="json")
         print(json_module.dumps(preview_data, indent=2))
     else:
         console.print(f"\n[{theme.ui_header}]Import Preview[/{theme.ui_header}]\n")
         console.print(f"File: {file_path}")
+        if preset:
+            console.print(f"Detected: {preset.name}")
         console.print(f"Flip signs: {flip_signs}")
         if debit_negative:
             console.print(f"Debit negative: {debit_negative}")
         console.print()
-        _display_preview_table(preview_result.data[:10], currency)
+        _display_preview_table(preview_result.data[:PREVIEW_ROWS], currency)
+        _display_warnings(warnings)
         console.print(f"\n[{theme.muted}]Remove --preview flag to import[/{theme.muted}]\n")
 
 
@@ -182,42 +335,235 @@ def _do_import(
     flip_signs: bool,
     debit_negative: bool,
     json_output: bool,
+    force: bool = False,
+    foreign_currency: str = "skip",
+    resume: Optional[str] = None,
+    restart: bool = False,
 ) -> None:
-    """Execute the import."""
+    """Execute the import.
+
+    The first Ctrl-C asks the import to stop once the chunk being written
+    is committed; a second one aborts immediately.
+    """
     source_options = {
         "file_path": file_path,
         "column_mapping": column_mapping,
         "date_format": "auto",
         "flip_signs": flip_signs,
         "debit_negative": debit_negative,
+        "foreign_currency": foreign_currency,
     }
 
-    if not json_output:
-        with console.status(f"[{theme.status_loading}]Importing transactions..."):
-            result = asyncio.run(
-                import_service.import_transactions(
-                    source_type="csv", account_id=account_id, source_options=source_options
-                )
+    stop_requested = []
+
+    def request_stop(signum, frame):
+        if stop_requested:
+            raise KeyboardInterrupt
+        stop_requested.append(True)
+        if not json_output:
+            console.print(
+                f"[{theme.warning}]Stopping after the current chunk (Ctrl-C again to abort)...[/{theme.warning}]"
             )
-    else:
-        result = asyncio.run(
+
+    def run_import():
+        return asyncio.run(
             import_service.import_transactions(
-                source_type="csv", account_id=account_id, source_options=source_options
+                source_type="csv",
+                account_id=account_id,
+                source_options=source_options,
+                force=force,
+                resume=resume,
+                restart=restart,
+                should_stop=lambda: bool(stop_requested),
             )
         )
 
+    previous_handler = signal.signal(signal.SIGINT, request_stop)
+    try:
+        if not json_output:
+            with console.status(f"[{theme.status_loading}]Importing transactions..."):
+                result = run_import()
+        else:
+            result = run_import()
+    finally:
+        signal.signal(signal.SIGINT, previous_handler)
+
     if not result.success:
-        console.print(f"[{theme.error}]Error: {result.error}[/{theme.error}]")
+        kind = (result.context or {}).get("kind")
+        if json_output:
+            error_data = {"success": False, "error": result.error}
+            if kind == "already_imported":
+                error_data["kind"] = kind
+                error_data["import"] = result.context["import"].model_dump(mode="json")
+            elif kind == "amount_out_of_range":
+                error_data["kind"] = kind
+                error_data["rows"] = result.context["rows"]
+            elif kind in ("interrupted_import", "checkpoint_mismatch", "no_checkpoint"):
+                error_data["kind"] = kind
+                if "checkpoint" in result.context:
+                    error_data["checkpoint"] = result.context["checkpoint"]
+            elif kind == "write_locked":
+                error_data["kind"] = kind
+                error_data["holder"] = result.context["holder"]
+            print(json_module.dumps(error_data, indent=2, default=str))
+        else:
+            console.print(f"[{theme.error}]Error: {result.error}[/{theme.error}]")
+            if kind == "already_imported":
+                console.print(f"[{theme.muted}]Use --force to re-process it[/{theme.muted}]")
+            elif kind == "amount_out_of_range":
+                for row in result.context["rows"][:OUT_OF_RANGE_SHOWN]:
+                    line = f"line {row['line']}: " if row["line"] else ""
+                    console.print(
+                        f"  [{theme.muted}]{line}{row['date']} {escape(row['description'] or '')} "
+                        f"{row['amount']}[/{theme.muted}]"
+                    )
+            elif kind == "interrupted_import":
+                batch = result.context["checkpoint"]["import_id"]
+                console.print(
+                    f"[{theme.muted}]Use --resume {batch} to continue it, "
+                    f"or --restart to undo it and start over[/{theme.muted}]"
+                )
         raise typer.Exit(1)
 
+    stats = result.data
+    if stats.get("interrupted"):
+        if json_output:
+            print(json_module.dumps(with_preamble(stats), indent=2, default=str))
+        else:
+            console.print(
+                f"\n[{theme.warning}]Import interrupted after line {stats['last_line']}: "
+                f"{stats['imported']} new transactions saved[/{theme.warning}]"
+            )
+            console.print(
+                f"[{theme.muted}]This import is resumable. Run the same command with "
+                f"--resume {stats['import_id']} to continue[/{theme.muted}]\n"
+            )
+        raise typer.Exit(EXIT_IMPORT_INTERRUPTED)
+
     if json_output:
-        print(json_module.dumps(result.data, indent=2, default=str))
+        print(json_module.dumps(with_preamble(stats), indent=2, default=str))
     else:
-        stats = result.data
+        print_preamble(console)
         console.print(f"\n[{theme.success}]✓ Import complete![/{theme.success}]")
+        if stats.get("restarted"):
+            console.print(
+                f"  Undid interrupted batch {stats['restarted']['import_id']} "
+                f"({stats['restarted']['removed']} transactions)"
+            )
+        if stats.get("resumed_from_line") is not None:
+            console.print(f"  Resumed after line {stats['resumed_from_line']}")
         console.print(f"  Discovered: {stats['discovered']} transactions")
         console.print(f"  Imported: {stats['imported']} new transactions")
-        console.print(f"  Skipped: {stats['skipped']} duplicates\n")
+        if stats.get("skipped_fuzzy"):
+            console.print(
+                f"  Skipped: {stats['skipped']} duplicates "
+                f"({stats['skipped_fuzzy']} matched on a nearby date)\n"
+            )
+        else:
+            console.print(f"  Skipped: {stats['skipped']} duplicates\n")
+        _display_currency_dispositions(stats.get("foreign_currency"), stats.get("currency_skipped", []))
+        _display_warnings(stats.get("warnings", []))
+
+
+def _do_doctor(
+    import_service: ImportService,
+    file_path: str,
+    column_mapping: Optional[Dict[str, str]],
+    debit_negative: bool,
+    json_output: bool,
+) -> None:
+    """Explain how a file would be detected and parsed. Nothing is written."""
+    csv_path = resolve_user_path(file_path)
+    _check_file_or_exit(import_service, str(csv_path), json_output)
+
+    result = asyncio.run(
+        import_service.diagnose_csv(str(csv_path), column_mapping, debit_negative)
+    )
+    if not result.success:
+        if json_output:
+            print(json_module.dumps({"success": False, "error": result.error}, indent=2))
+        else:
+            console.print(f"[{theme.error}]Error: {result.error}[/{theme.error}]")
+        raise typer.Exit(1)
+
+    if json_output:
+        print(json_module.dumps({"success": True, "detection": result.data.model_dump(mode="json")}, indent=2))
+    else:
+        _display_detection_report(result.data, mapping_given=bool(column_mapping))
+
+
+def _display_detection_report(report: DetectionReport, mapping_given: bool) -> None:
+    """Print a detection report section by section."""
+    console.print(f"\n[{theme.ui_header}]Import Doctor[/{theme.ui_header}]\n")
+    console.print(f"File: {escape(report.file_path)}")
+    console.print(f"Encoding: {report.encoding}")
+    console.print(f"Delimiter: {report.delimiter!r}")
+    console.print(f"Header row: line {report.header_row + 1}")
+
+    table = Table(title="Headers", show_header=True, header_style=theme.ui_header)
+    table.add_column("Header")
+    table.add_column("Pattern")
+    table.add_column("Field")
+    table.add_column("Used")
+    for match in report.headers:
+        table.add_row(
+            escape(match.header),
+            escape(match.pattern) if match.pattern else f"[{theme.muted}]unmatched[/{theme.muted}]",
+            match.field or "",
+            "yes" if match.used else "",
+        )
+    console.print()
+    console.print(table)
+
+    if report.preset:
+        console.print(f"Detected: {report.preset.name}")
+        source = "preset"
+    else:
+        source = "given" if mapping_given else "detected"
+    console.print(f"\n[{theme.ui_header}]Column mapping ({source})[/{theme.ui_header}]")
+    for field, column in report.column_mapping.items():
+        console.print(f"  {field}: {escape(column) if column else '-'}")
+
+    console.print(f"\n[{theme.ui_header}]Date format[/{theme.ui_header}]")
+    if not report.date_samples:
+        console.print(f"  [{theme.muted}]No date values sampled[/{theme.muted}]")
+    else:
+        console.print(f"  Samples: {escape(', '.join(report.date_samples))}")
+        console.print(f"  Formats fitting every sample: {', '.join(report.date_format_candidates) or 'none'}")
+        console.print(f"  Chosen: {report.date_format or 'none'}")
+        if len(report.date_format_candidates) > 1:
+            console.print(f"  [{theme.warning}]Ambiguous: the samples fit more than one format[/{theme.warning}]")
+        if report.date_format_mixed:
+            console.print(f"  [{theme.warning}]Rows will be read with different formats[/{theme.warning}]")
+
+    if report.negate_debits is not None:
+        console.print(f"\n[{theme.ui_header}]Debit signs[/{theme.ui_header}]")
+        console.print(f"  Samples: {escape(', '.join(report.debit_samples)) or 'none'}")
+        verdict = "negate (unsigned debits)" if report.negate_debits else "keep as signed"
+        console.print(f"  Verdict: {verdict}")
+
+    for row in report.rows:
+        table = Table(title=f"Line {row.line}", show_header=True, header_style=theme.ui_header)
+        table.add_column("Field")
+        table.add_column("Column")
+        table.add_column("Raw")
+        table.add_column("Parsed")
+        for field, value in row.fields.items():
+            if value.parsed is not None:
+                parsed = escape(value.parsed)
+            elif not value.raw.strip():
+                parsed = f"[{theme.muted}]empty[/{theme.muted}]"
+            else:
+                parsed = f"[{theme.error}]unparsed[/{theme.error}]"
+            table.add_row(field, escape(value.column), escape(value.raw), parsed)
+        console.print()
+        console.print(table)
+        if row.error:
+            console.print(f"  [{theme.error}]Skipped: {escape(row.error)}[/{theme.error}]")
+        else:
+            console.print(f"  Amount: {row.amount}")
+
+    console.print(f"\n[{theme.muted}]Nothing was imported[/{theme.muted}]\n")
 
 
 # =============================================================================
@@ -229,7 +575,7 @@ def _collect_params_interactive(
 ) -> Optional[Dict[str, Any]]:
     """Interactively collect all parameters needed for import.
 
-    Returns dict with: file_path, account_id, column_mapping, flip_signs, debit_negative
+    Returns dict with: file_path, account_id, column_mapping, flip_signs, debit_negative, preset
     Returns None if user cancels.
     """
     console.print(f"\n[{theme.ui_header}]CSV Import[/{theme.ui_header}]\n")
@@ -246,10 +592,10 @@ def _collect_params_interactive(
         console.print(f"[{theme.warning}]Import cancelled[/{theme.warning}]\n")
         return None
 
-    expanded_path = os.path.expanduser(file_path)
-    csv_path = Path(expanded_path)
-    if not csv_path.exists():
-        console.print(f"[{theme.error}]Error: File not found: {file_path}[/{theme.error}]\n")
+    csv_path = resolve_user_path(file_path)
+    check_result = import_service.check_import_file(csv_path)
+    if not check_result.success:
+        console.print(f"[{theme.error}]Error: {escape(check_result.error)}[/{theme.error}]\n")
         return None
 
     # 2. Get account selection
@@ -295,6 +641,9 @@ def _collect_params_interactive(
         return None
 
     column_mapping = detect_result.data
+    preset = _detect_preset(import_service, str(csv_path))
+    if preset:
+        console.print(f"\n[{theme.success}]Detected: {preset.name}[/{theme.success}]")
     console.print(f"\n[{theme.success}]Detected columns:[/{theme.success}]")
     for field, column in column_mapping.items():
         console.print(f"  {field}: {column}")
@@ -306,10 +655,10 @@ def _collect_params_interactive(
         return None
 
     # 4. Interactive preview loop to confirm/adjust sign settings
-    flip_signs = False
-    debit_negative = False
+    flip_signs = preset.flip_signs if preset else False
+    debit_negative = preset.debit_negative if preset else False
     flip_signs, debit_negative = _interactive_preview_loop(
-        import_service, str(csv_path), column_mapping, flip_signs, debit_negative
+        import_service, str(csv_path), column_mapping, flip_signs, debit_negative, account_id, currency
     )
     if flip_signs is None:  # User cancelled
         return None
@@ -320,6 +669,7 @@ def _collect_params_interactive(
         "column_mapping": column_mapping,
         "flip_signs": flip_signs,
         "debit_negative": debit_negative,
+        "preset": preset,
     }
 
 
@@ -329,6 +679,8 @@ def _interactive_preview_loop(
     column_mapping: Dict[str, str],
     flip_signs: bool,
     debit_negative: bool,
+    account_id: Optional[UUID] = None,
+    currency: str = "USD",
 ) -> tuple[Optional[bool], Optional[bool]]:
     """Interactive preview loop allowing user to adjust sign settings.
 
@@ -343,7 +695,7 @@ def _interactive_preview_loop(
                 file_path=file_path,
                 column_mapping=column_mapping,
                 date_format="auto",
-                limit=15,
+                limit=SIGN_CHECK_ROWS,
                 flip_signs=flip_signs,
                 debit_negative=debit_negative,
             )
@@ -369,6 +721,7 @@ def _interactive_preview_loop(
             console.print(f"\n[{theme.muted}]({len(preview_txs)} total transactions in file)[/{theme.muted}]")
             console.print(f"[{theme.ui_header}]Preview Check[/{theme.ui_header}]")
             console.print(f"[{theme.muted}]Spending should appear as NEGATIVE ({theme.negative_amount}), income/refunds as POSITIVE ({theme.positive_amount})[/{theme.muted}]\n")
+            _display_warnings(_sign_warnings(import_service, account_id, preview_txs))
 
         console.print(f"[{theme.info}]What would you like to do?[/{theme.info}]")
         console.print("  [1] Proceed with import")
@@ -433,25 +786,74 @@ def _build_column_mapping(
     return mapping
 
 
+def _with_currency_column(
+    column_mapping: Optional[Dict[str, str]], currency_column: Optional[str]
+) -> Optional[Dict[str, str]]:
+    """Add --currency-column to a given or detected mapping."""
+    if not currency_column:
+        return column_mapping
+    return {**(column_mapping or {}), "currency": currency_column}
+
+
+def _display_currency_dispositions(
+    counts: Optional[Dict[str, Any]], skipped: List[Dict[str, Any]]
+) -> None:
+    """Print what happened to rows in another currency than the account's."""
+    if not counts or not (counts["skipped"] or counts["kept"] or counts["converted"]):
+        return
+    console.print(
+        f"  Other currencies ({counts['mode']}): {counts['skipped']} skipped, "
+        f"{counts['kept']} kept, {counts['converted']} converted"
+    )
+    for row in skipped[:CURRENCY_SKIPPED_SHOWN]:
+        line = f"line {row['line']}: " if row.get("line") else ""
+        console.print(
+            f"  [{theme.muted}]Skipped {line}{row['date']} {escape(row['description'])} "
+            f"{row['amount']} {escape(row['currency'])} ({escape(row['reason'])})[/{theme.muted}]"
+        )
+    if len(skipped) > CURRENCY_SKIPPED_SHOWN:
+        console.print(f"  [{theme.muted}]...and {len(skipped) - CURRENCY_SKIPPED_SHOWN} more[/{theme.muted}]")
+    console.print()
+
+
+def _sign_warnings(
+    import_service: ImportService, account_id: Optional[UUID], transactions: List[Transaction]
+) -> List[str]:
+    """Sign profile warnings for rows about to be imported into an account."""
+    if account_id is None:
+        return []
+    result = asyncio.run(import_service.check_sign_profile(account_id, transactions))
+    return result.data if result.success else []
+
+
+def _display_warnings(warnings: List[str]) -> None:
+    """Print warnings prominently; they never stop an import."""
+    for warning in warnings:
+        console.print(f"[{theme.warning}]⚠ {warning}[/{theme.warning}]\n")
+
+
 def _display_preview_table(transactions: List[Transaction], currency: str = "USD") -> None:
     """Display transaction preview table."""
-    from treeline.app.preferences_service import format_currency
+    from treeline.app.preferences_service import format_currency, format_date
+    from treeline.config import get_display_locale
 
+    locale = get_display_locale()
+    layout = resolve_layout(console)
     table = Table(show_header=True, box=None, padding=(0, 1))
-    table.add_column("Date", width=12)
-    table.add_column("Description", width=40)
-    table.add_column("Amount", justify="right", width=15)
+    add_column(table, layout, "Date")
+    add_column(table, layout, "Description")
+    add_column(table, layout, "Amount", justify="right")
 
     for tx in transactions:
-        date_str = tx.transaction_date.strftime("%Y-%m-%d")
-        desc = (tx.description or "")[:38]
+        date_str = format_date(tx.transaction_date, locale)
+        desc = escape(tx.description or "")
 
-        amount_str = format_currency(tx.amount, currency)
+        amount_str = format_currency(tx.amount, currency, locale=locale)
         amount_style = theme.negative_amount if tx.amount < 0 else theme.positive_amount
 
         table.add_row(date_str, desc, f"[{amount_style}]{amount_str}[/{amount_style}]")
 
-    console.print(table)
+    print_table(console, table, layout)
 
 
 def _prompt_file_path(prompt_text: str = "") -> str:
@@ -0,0 +1,113 @@
+"""Imports command - file import history."""
+
+import asyncio
+import json
+
+import typer
+from pydantic import BaseModel
+from rich.console import Console
+from rich.table import Table
+
+from treeline.app.preferences_service import format_date
+from treeline.commands.json_output import model_json
+from treeline.config import get_display_locale
+from treeline.theme import get_theme
+from treeline.utils import get_log_file_path
+
+console = Console()
+theme = get_theme()
+
+# Create imports subcommand group
+imports_app = typer.Typer(help="File import history")
+
+# Hash prefix shown in tables; --json has the full hash
+SHORT_HASH_LENGTH = 12
+
+
+def json_serializer(obj):
+    """Custom JSON serializer for Pydantic models and other objects."""
+    if isinstance(obj, BaseModel):
+        return model_json(obj)
+    return str(obj)
+
+
+def output_json(data: dict) -> None:
+    """Output data as JSON."""
+    print(json.dumps(data, indent=2, default=json_serializer))
+
+
+def display_error(error: str, show_log_hint: bool = True) -> None:
+    """Display error message in consistent format."""
+    console.print(f"[{theme.error}]Error: {error}[/{theme.error}]")
+    if show_log_hint:
+        log_file = get_log_file_path()
+        console.print(f"[{theme.muted}]See {log_file} for details[/{theme.muted}]")
+
+
+def register(app: typer.Typer, get_container: callable, ensure_initialized: callable) -> None:
+    """Register the imports commands with the app."""
+    app.add_typer(imports_app, name="imports")
+
+    @imports_app.command(name="list")
+    def imports_list_command(
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """List past file imports with their content hashes.
+
+        Re-importing a byte-identical file into the same account is refused
+        unless 'tl import --force' is used.
+
+        Examples:
+          tl imports list
+          tl imports list --json
+        """
+        ensure_initialized()
+
+        container = get_container()
+        result = asyncio.run(container.import_service().list_imports())
+        if not result.success:
+            if json_output:
+                output_json({"success": False, "error": result.error})
+            else:
+                display_error(result.error)
+            raise typer.Exit(1)
+
+        imports = result.data or []
+        if json_output:
+            output_json({"success": True, "imports": imports})
+            return
+
+        if not imports:
+            console.print(f"\n[{theme.muted}]No file imports recorded[/{theme.muted}]\n")
+            return
+
+        accounts_result = asyncio.run(container.account_service().get_accounts())
+        account_names = {
+            account.id: account.nickname or account.name
+            for account in (accounts_result.data or [])
+        }
+
+        locale = get_display_locale()
+        table = Table(title="Imports", show_header=True, header_style=theme.ui_header)
+        table.add_column("Batch", style=theme.muted)
+        table.add_column("Date")
+        table.add_column("File")
+        table.add_column("SHA-256", style=theme.muted)
+        table.add_column("Account")
+        table.add_column("Imported", justify="right")
+        table.add_column("Skipped", justify="right")
+
+        for record in imports:
+            table.add_row(
+                str(record.id),
+                format_date(record.imported_at, locale),
+                record.file_name or "",
+                record.file_sha256[:SHORT_HASH_LENGTH],
+                account_names.get(record.account_id, str(record.account_id)),
+                str(record.imported),
+                str(record.skipped),
+            )
+
+        console.print()
+        console.print(table)
+        console.print()
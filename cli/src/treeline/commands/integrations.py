@@ -0,0 +1,151 @@
+"""Integrations command - check and label configured integrations."""
+
+import asyncio
+import json
+
+import typer
+from pydantic import BaseModel
+from rich.console import Console
+
+from treeline.commands.json_output import model_json
+from treeline.theme import get_theme
+
+console = Console()
+theme = get_theme()
+
+# Create integrations subcommand group
+integrations_app = typer.Typer(help="Integration management commands")
+
+
+def json_serializer(obj):
+    """Custom JSON serializer for Pydantic models and other objects."""
+    if isinstance(obj, BaseModel):
+        return model_json(obj)
+    return str(obj)
+
+
+def output_json(data: dict) -> None:
+    """Output data as JSON."""
+    print(json.dumps(data, indent=2, default=json_serializer))
+
+
+def _fail(
+    name: str, error: str, kind: str, http_status: int | None, json_output: bool
+) -> None:
+    """Report a failed integration check and exit."""
+    if json_output:
+        output_json(
+            {
+                "success": False,
+                "integration": name,
+                "error": error,
+                "kind": kind,
+                "http_status": http_status,
+            }
+        )
+    else:
+        console.print(f"\n[{theme.error}]✗ {name}: needs attention ({kind})[/{theme.error}]")
+        console.print(f"  {error}")
+        if http_status:
+            console.print(f"  [{theme.muted}]HTTP {http_status}[/{theme.muted}]")
+        console.print()
+    raise typer.Exit(1)
+
+
+def _fail_update(name: str, error: str, json_output: bool) -> None:
+    """Report a failed integration update and exit."""
+    if json_output:
+        output_json({"success": False, "integration": name, "error": error})
+    else:
+        console.print(f"[{theme.error}]Error: {error}[/{theme.error}]")
+    raise typer.Exit(1)
+
+
+def register(app: typer.Typer, get_container: callable, ensure_initialized: callable) -> None:
+    """Register the integrations commands with the app."""
+    app.add_typer(integrations_app, name="integrations")
+
+    @integrations_app.command(name="test")
+    def integrations_test_command(
+        integration: str = typer.Argument(..., help="Integration name (e.g., 'simplefin')"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Check that an integration's credentials still work, without syncing.
+
+        Nothing is written to the database.
+
+        Examples:
+          tl integrations test simplefin
+          tl integrations test simplefin --json
+        """
+        ensure_initialized()
+
+        name = integration.lower()
+        result = asyncio.run(get_container().integration_service().test_integration(name))
+        if not result.success:
+            context = result.context or {}
+            _fail(
+                name,
+                result.error,
+                context.get("kind", "error"),
+                context.get("http_status"),
+                json_output,
+            )
+
+        data = result.data
+        if json_output:
+            output_json({"success": True, "integration": name, **data})
+            return
+
+        console.print(f"\n[{theme.success}]✓ {name}: connection OK[/{theme.success}]")
+        console.print(f"  Accounts visible: {data.get('account_count', 0)}")
+        if data.get("http_status"):
+            console.print(f"  [{theme.muted}]HTTP {data['http_status']}[/{theme.muted}]")
+        for warning in data.get("warnings", []):
+            console.print(f"  [{theme.warning}]⚠ {warning}[/{theme.warning}]")
+        console.print()
+
+    @integrations_app.command(name="set")
+    def integrations_set_command(
+        integration: str = typer.Argument(..., help="Integration name (e.g., 'simplefin')"),
+        display_name: str = typer.Option(
+            None, "--display-name", help="Label shown instead of the name (\"\" to clear)"
+        ),
+        notes: str = typer.Option(
+            None, "--notes", help="Free-form notes, e.g. when a token expires (\"\" to clear)"
+        ),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Label an integration with a display name and notes.
+
+        Only changes how the integration is shown; sync still uses its name.
+
+        Examples:
+          tl integrations set simplefin --display-name "Chase + Ally via SimpleFIN"
+          tl integrations set simplefin --notes "Token renewed 2025-01, expires yearly"
+          tl integrations set simplefin --notes ""
+        """
+        ensure_initialized()
+
+        name = integration.lower()
+        if display_name is None and notes is None:
+            _fail_update(name, "Specify --display-name and/or --notes", json_output)
+
+        container = get_container()
+        result = asyncio.run(
+            container.integration_service().update_integration_metadata(
+                name, display_name=display_name, notes=notes
+            )
+        )
+        if not result.success:
+            _fail_update(name, result.error, json_output)
+
+        data = result.data
+        if json_output:
+            output_json({"success": True, **data})
+            return
+
+        console.print(f"\n[{theme.success}]✓ Updated {name}[/{theme.success}]")
+        console.print(f"  Display name: {data['display_name'] or '-'}")
+        console.print(f"  Notes: {data['notes'] or '-'}")
+        console.print()
@@ -0,0 +1,30 @@
+"""JSON forms of amounts and models for --json output.
+
+Amounts are written as exact strings unless --json-numbers asks for the
+old numeric output (see treeline.config.json_numbers).
+"""
+
+from decimal import Decimal
+from typing import Any
+
+from pydantic import BaseModel
+
+from treeline.config import json_numbers
+from treeline.domain import money_json
+
+
+def decimal_json(value: Decimal) -> str | float:
+    """JSON form of a Decimal: its string, or a float under --json-numbers."""
+    return float(value) if json_numbers() else str(value)
+
+
+def amount_json(amount: Decimal, currency: str | None = None) -> str | float:
+    """JSON form of an amount in the currency's decimal places (see money_json),
+    or a float under --json-numbers."""
+    return float(amount) if json_numbers() else money_json(amount, currency)
+
+
+def model_json(model: BaseModel, **context: Any) -> Any:
+    """A model dumped for --json output, with amounts as decimal_json writes
+    them. Extra context (e.g. currency="JPY") goes to the model's serializers."""
+    return model.model_dump(mode="json", context={"json_numbers": json_numbers(), **context})
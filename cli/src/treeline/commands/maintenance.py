@@ -0,0 +1,492 @@
+"""Maintenance command - database maintenance tasks."""
+
+import asyncio
+import json
+from pathlib import Path
+from uuid import UUID
+
+import typer
+from pydantic import BaseModel
+from rich.console import Console
+from rich.table import Table
+
+from treeline.commands.json_output import model_json
+from treeline.config import get_fingerprint_version
+from treeline.theme import get_theme
+from treeline.utils import get_log_file_path
+
+console = Console()
+theme = get_theme()
+
+# Create maintenance subcommand group
+maintenance_app = typer.Typer(help="Database maintenance commands")
+
+# Collisions listed in table output before truncating
+MAX_COLLISIONS_SHOWN = 10
+
+
+def json_serializer(obj):
+    """Custom JSON serializer for Pydantic models and other objects."""
+    if isinstance(obj, BaseModel):
+        return model_json(obj)
+    return str(obj)
+
+
+def output_json(data: dict) -> None:
+    """Output data as JSON."""
+    print(json.dumps(data, indent=2, default=json_serializer))
+
+
+def display_error(error: str, show_log_hint: bool = True) -> None:
+    """Display error message in consistent format."""
+    console.print(f"[{theme.error}]Error: {error}[/{theme.error}]")
+    if show_log_hint:
+        log_file = get_log_file_path()
+        console.print(f"[{theme.muted}]See {log_file} for details[/{theme.muted}]")
+
+
+def register(app: typer.Typer, get_container: callable, ensure_initialized: callable) -> None:
+    """Register the maintenance commands with the app."""
+    app.add_typer(maintenance_app, name="maintenance")
+
+    @maintenance_app.command(name="refingerprint")
+    def refingerprint_command(
+        to_version: int = typer.Option(
+            None, "--to-version", help="Fingerprint version (default: configured version)"
+        ),
+        dry_run: bool = typer.Option(
+            False, "--dry-run", help="Report changes and collisions without writing"
+        ),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Recompute transaction fingerprints with a given strategy version.
+
+        Version 1 hashes account, date, amount and description, so identical
+        same-day purchases share a fingerprint. Version 2 adds an occurrence
+        index so they stay distinct. Set import.fingerprint_version in
+        settings.json to use a version for new imports and syncs.
+
+        Examples:
+          tl maintenance refingerprint --to-version 2 --dry-run
+          tl maintenance refingerprint --to-version 2
+        """
+        ensure_initialized()
+
+        if to_version is None:
+            to_version = get_fingerprint_version()
+
+        maintenance_service = get_container().maintenance_service()
+        result = asyncio.run(maintenance_service.refingerprint(to_version, dry_run=dry_run))
+
+        if not result.success:
+            if json_output:
+                output_json({"success": False, "error": result.error})
+            else:
+                display_error(result.error)
+            raise typer.Exit(1)
+
+        data = result.data
+        if json_output:
+            output_json({"success": True, **data})
+            return
+
+        collisions = data["collisions"]
+        verb = "Would update" if dry_run else "Updated"
+        console.print(
+            f"\n[{theme.ui_header}]Fingerprint v{data['to_version']}[/{theme.ui_header}]"
+        )
+        console.print(f"  Transactions checked: {data['total']}")
+        console.print(f"  {verb}: {data['changed']}")
+
+        if collisions:
+            console.print(
+                f"\n[{theme.warning}]{len(collisions)} fingerprint(s) shared by more than one transaction:"
+                f"[/{theme.warning}]"
+            )
+            for collision in collisions[:MAX_COLLISIONS_SHOWN]:
+                console.print(
+                    f"  {collision['fingerprint']}: {', '.join(collision['transaction_ids'])}"
+                )
+            if len(collisions) > MAX_COLLISIONS_SHOWN:
+                console.print(
+                    f"  [{theme.muted}]... and {len(collisions) - MAX_COLLISIONS_SHOWN} more "
+                    f"(use --json for the full list)[/{theme.muted}]"
+                )
+
+        if dry_run:
+            console.print(f"\n[{theme.muted}]Dry run - no changes written[/{theme.muted}]")
+        console.print()
+
+    @maintenance_app.command(name="adopt-unmapped")
+    def adopt_unmapped_command(
+        provider_account: str = typer.Option(
+            ..., "--provider-account", help="Provider account ID named in the sync warning"
+        ),
+        account_id: str = typer.Option(
+            ..., "--account-id", help="Account to move the transactions into"
+        ),
+        dry_run: bool = typer.Option(
+            False, "--dry-run", help="Report what would be adopted without writing"
+        ),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Move quarantined sync transactions into an existing account.
+
+        Sync keeps transactions for unknown provider accounts only when
+        sync.keep_unmapped is true in settings.json. Transactions that already
+        exist in the account are skipped.
+
+        Examples:
+          tl maintenance adopt-unmapped --provider-account ACT-123 --account-id <uuid> --dry-run
+          tl maintenance adopt-unmapped --provider-account ACT-123 --account-id <uuid>
+        """
+        ensure_initialized()
+
+        try:
+            target_id = UUID(account_id)
+        except ValueError:
+            message = f"Invalid account ID: {account_id}"
+            if json_output:
+                output_json({"success": False, "error": message})
+            else:
+                display_error(message, show_log_hint=False)
+            raise typer.Exit(1)
+
+        maintenance_service = get_container().maintenance_service()
+        result = asyncio.run(
+            maintenance_service.adopt_unmapped(provider_account, target_id, dry_run=dry_run)
+        )
+
+        if not result.success:
+            if json_output:
+                output_json({"success": False, "error": result.error})
+            else:
+                display_error(result.error)
+            raise typer.Exit(1)
+
+        data = result.data
+        if json_output:
+            output_json({"success": True, **data})
+            return
+
+        directory = asyncio.run(get_container().account_service().directory())
+        verb = "Would adopt" if dry_run else "Adopted"
+        console.print(
+            f"\n[{theme.ui_header}]Unmapped transactions for {provider_account}[/{theme.ui_header}]"
+        )
+        console.print(f"  Account: {directory.name_for(target_id)}")
+        console.print(f"  {verb}: {data['adopted']}")
+        console.print(f"  Skipped: {data['skipped']} (already exists)")
+
+        if dry_run:
+            console.print(f"\n[{theme.muted}]Dry run - no changes written[/{theme.muted}]")
+        console.print()
+
+    @maintenance_app.command(name="apply-default-tags")
+    def apply_default_tags_command(
+        account_id: str = typer.Option(
+            ..., "--account-id", help="Account whose transactions get its default tags"
+        ),
+        dry_run: bool = typer.Option(
+            False, "--dry-run", help="Count the transactions that would change without writing"
+        ),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Add an account's default tags to the transactions already in it.
+
+        Sync, imports and `tl new transaction` add default tags to new
+        transactions; run this after setting them with `tl accounts set
+        --default-tags`. Existing tags are kept, and re-running is harmless.
+
+        Examples:
+          tl maintenance apply-default-tags --account-id <uuid> --dry-run
+          tl maintenance apply-default-tags --account-id <uuid>
+        """
+        ensure_initialized()
+
+        try:
+            target_id = UUID(account_id)
+        except ValueError:
+            message = f"Invalid account ID: {account_id}"
+            if json_output:
+                output_json({"success": False, "error": message})
+            else:
+                display_error(message, show_log_hint=False)
+            raise typer.Exit(1)
+
+        maintenance_service = get_container().maintenance_service()
+        result = asyncio.run(maintenance_service.apply_default_tags(target_id, dry_run=dry_run))
+
+        if not result.success:
+            if json_output:
+                output_json({"success": False, "error": result.error})
+            else:
+                display_error(result.error, show_log_hint=False)
+            raise typer.Exit(1)
+
+        data = result.data
+        if json_output:
+            output_json({"success": True, **data})
+            return
+
+        directory = asyncio.run(get_container().account_service().directory())
+        verb = "Would tag" if dry_run else "Tagged"
+        console.print(f"\n[{theme.ui_header}]Default tags for {directory.name_for(target_id)}[/{theme.ui_header}]")
+        console.print(f"  Tags: {', '.join(data['default_tags'])}")
+        console.print(f"  {verb}: {data['updated']} transaction(s)")
+        console.print(f"  Unchanged: {data['unchanged']} (already tagged)")
+
+        if dry_run:
+            console.print(f"\n[{theme.muted}]Dry run - no changes written[/{theme.muted}]")
+        console.print()
+
+    @maintenance_app.command(name="rebuild-search-index")
+    def rebuild_search_index_command(
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Refill the lowercased description column used by transaction search.
+
+        The CLI keeps it current; run this after editing descriptions by other
+        means (e.g. UPDATE statements in the SQL editor) if search misses rows.
+
+        Examples:
+          tl maintenance rebuild-search-index
+        """
+        ensure_initialized()
+
+        maintenance_service = get_container().maintenance_service()
+        result = asyncio.run(maintenance_service.rebuild_search_index())
+
+        if not result.success:
+            if json_output:
+                output_json({"success": False, "error": result.error})
+            else:
+                display_error(result.error)
+            raise typer.Exit(1)
+
+        if json_output:
+            output_json({"success": True, **result.data})
+            return
+
+        console.print(
+            f"\n[{theme.success}]Search index rebuilt[/{theme.success}] "
+            f"({result.data['updated']} row(s) updated)\n"
+        )
+
+    @maintenance_app.command(name="infer-account-dates")
+    def infer_account_dates_command(
+        dry_run: bool = typer.Option(
+            False, "--dry-run", help="Show the dates that would be set without writing"
+        ),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Set unknown account opening dates from the earliest transaction or balance.
+
+        Only accounts without an opened date are changed. Correct any date
+        with `tl accounts set`.
+
+        Examples:
+          tl maintenance infer-account-dates --dry-run
+          tl maintenance infer-account-dates
+        """
+        ensure_initialized()
+
+        maintenance_service = get_container().maintenance_service()
+        result = asyncio.run(maintenance_service.infer_account_dates(dry_run=dry_run))
+
+        if not result.success:
+            if json_output:
+                output_json({"success": False, "error": result.error})
+            else:
+                display_error(result.error)
+            raise typer.Exit(1)
+
+        data = result.data
+        if json_output:
+            output_json({"success": True, **data})
+            return
+
+        verb = "Would set" if dry_run else "Set"
+        console.print(f"\n[{theme.ui_header}]Account opening dates[/{theme.ui_header}]")
+        if not data["updated"]:
+            console.print(f"  [{theme.muted}]No accounts need an opening date[/{theme.muted}]")
+        for entry in data["updated"]:
+            console.print(f"  {verb} {entry['account']}: {entry['opened_at']}")
+        if data["no_activity"]:
+            console.print(
+                f"  [{theme.muted}]{data['no_activity']} account(s) skipped with no transactions "
+                f"or balances yet[/{theme.muted}]"
+            )
+
+        if dry_run:
+            console.print(f"\n[{theme.muted}]Dry run - no changes written[/{theme.muted}]")
+        console.print()
+
+    @maintenance_app.command(name="verify")
+    def verify_command(
+        fix: bool = typer.Option(
+            False, "--fix", help="Repair malformed JSON columns and dangling split parent IDs"
+        ),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Check the database for integrity problems; exits 1 if any check fails.
+
+        Checks that transactions and balance snapshots belong to existing
+        accounts, fingerprints are unique, external_ids and tags are
+        well-formed, split parts point to an existing parent, and every
+        migration has been applied. Useful after a crash or interrupted import.
+
+        Examples:
+          tl maintenance verify
+          tl maintenance verify --fix
+          tl maintenance verify --json
+        """
+        ensure_initialized()
+
+        maintenance_service = get_container().maintenance_service()
+        result = asyncio.run(maintenance_service.verify(fix=fix))
+
+        if not result.success:
+            if json_output:
+                output_json({"success": False, "error": result.error})
+            else:
+                display_error(result.error)
+            raise typer.Exit(1)
+
+        data = result.data
+        if json_output:
+            output_json({"success": True, **data})
+            if not data["passed"]:
+                raise typer.Exit(1)
+            return
+
+        table = Table(show_header=True, header_style=theme.ui_header)
+        table.add_column("Check")
+        table.add_column("Result")
+        table.add_column("Count", justify="right")
+        table.add_column("First offending IDs", style=theme.muted)
+        for check in data["checks"]:
+            status = (
+                f"[{theme.success}]pass[/{theme.success}]"
+                if check["passed"]
+                else f"[{theme.error}]FAIL[/{theme.error}]"
+            )
+            table.add_row(
+                check["description"], status, str(check["count"]), "\n".join(check["ids"])
+            )
+        console.print()
+        console.print(table)
+
+        for name, changed in data["fixes"].items():
+            console.print(f"[{theme.success}]Fixed {name}: {changed} row(s) changed[/{theme.success}]")
+        unfixed = [c for c in data["checks"] if not c["passed"] and c["fixable"]]
+        if unfixed and not fix:
+            console.print(
+                f"[{theme.muted}]Run 'tl maintenance verify --fix' to repair "
+                f"{', '.join(c['name'] for c in unfixed)}[/{theme.muted}]"
+            )
+        console.print()
+
+        if not data["passed"]:
+            raise typer.Exit(1)
+
+    @maintenance_app.command(name="prune-attachments")
+    def prune_attachments_command(
+        dry_run: bool = typer.Option(
+            False, "--dry-run", help="Show the attachments that would be removed"
+        ),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Remove attachments of deleted or missing transactions.
+
+        Deleting a transaction keeps its attachments; this removes them.
+        Files are moved to ~/.treeline/attachments/.trash, not deleted.
+
+        Examples:
+          tl maintenance prune-attachments --dry-run
+          tl maintenance prune-attachments
+        """
+        ensure_initialized()
+
+        attachment_service = get_container().attachment_service()
+        result = asyncio.run(attachment_service.prune(dry_run=dry_run))
+
+        if not result.success:
+            if json_output:
+                output_json({"success": False, "error": result.error})
+            else:
+                display_error(result.error)
+            raise typer.Exit(1)
+
+        data = result.data
+        if json_output:
+            output_json({"success": True, **data})
+            return
+
+        verb = "Would remove" if dry_run else "Removed"
+        console.print(f"\n[{theme.ui_header}]Attachments of deleted transactions[/{theme.ui_header}]")
+        if not data["attachments"]:
+            console.print(f"  [{theme.muted}]None found[/{theme.muted}]")
+        for attachment in data["attachments"]:
+            console.print(
+                f"  {verb} {attachment.filename} (transaction {attachment.transaction_id})"
+            )
+        if data["attachments"] and not dry_run:
+            console.print(
+                f"\n[{theme.muted}]Files moved to {attachment_service.trash_dir}[/{theme.muted}]"
+            )
+
+        if dry_run:
+            console.print(f"\n[{theme.muted}]Dry run - no changes written[/{theme.muted}]")
+        console.print()
+
+    @maintenance_app.command(name="anonymize")
+    def anonymize_command(
+        output: str = typer.Option(..., "--output", "-o", help="Database file to create"),
+        seed: str = typer.Option(
+            None, "--seed", help="Secret for the hashes (default: random; never share it)"
+        ),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Write an anonymized copy of the database to attach to a bug report.
+
+        Descriptions become fake merchants, account and institution names
+        become generic labels, amounts move by up to 20% (sign kept), and
+        provider IDs are re-hashed. Dates are kept and fingerprints are
+        recomputed, so imports and syncs dedup the same way. Integration
+        settings, warnings, attachments, rules and saved queries are left out.
+
+        Examples:
+          tl maintenance anonymize --output repro.duckdb
+        """
+        ensure_initialized()
+
+        anonymize_service = get_container().anonymize_service()
+        result = asyncio.run(anonymize_service.anonymize(Path(output), seed=seed))
+
+        if not result.success:
+            if json_output:
+                output_json({"success": False, "error": result.error})
+            else:
+                display_error(result.error)
+            raise typer.Exit(1)
+
+        data = result.data
+        if json_output:
+            output_json({"success": True, **data})
+            return
+
+        console.print(f"\n[{theme.success}]Anonymized copy written to {data['output']}[/{theme.success}]")
+        console.print(f"  Accounts renamed: {data['accounts']} ({data['institutions']} institution(s))")
+        console.print(
+            f"  Descriptions replaced: {data['descriptions']} unique, "
+            f"as {data['merchants']} fake merchant(s)"
+        )
+        console.print(f"  Amounts and balances perturbed: {data['amounts']}")
+        console.print(f"  External IDs re-hashed: {data['external_ids']}")
+        console.print(f"  Fingerprints recomputed: {data['transactions']} transaction(s)")
+        console.print(f"  Dropped: {', '.join(data['dropped_tables'])}")
+        console.print(
+            f"\n[{theme.muted}]Verified: no original description text is in the copy. "
+            f"Dates are unchanged.[/{theme.muted}]\n"
+        )
@@ -1,4 +1,4 @@
-"""New command - create new resources (balance snapshots)."""
+"""New command - create new resources (balance snapshots, transactions)."""
 
 import asyncio
 from datetime import date
@@ -10,6 +10,8 @@ from rich.console import Console
 from rich.prompt import Prompt
 
 from treeline.commands.import_cmd import _prompt_account_selection
+from treeline.confirm import require_input_or_exit
+from treeline.domain import TransactionStatus
 from treeline.theme import get_theme
 from treeline.utils import get_log_file_path
 
@@ -31,15 +33,24 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
     @app.command(name="new")
     def new_command(
         resource_type: str = typer.Argument(
-            ..., help="Type of resource to create (e.g., 'balance')"
+            ..., help="Type of resource to create ('balance' or 'transaction')"
         ),
         # Balance-specific options
         account_id: str = typer.Option(
             None, "--account-id", help="Account ID (UUID) for balance snapshot"
         ),
+        account: str = typer.Option(
+            None, "--account", help="Account name or nickname (alternative to --account-id)"
+        ),
         balance: str = typer.Option(None, "--balance", help="Account balance amount"),
         snapshot_date: str = typer.Option(
-            None, "--date", help="Snapshot date (YYYY-MM-DD, defaults to today)"
+            None, "--date", help="Snapshot or transaction date (YYYY-MM-DD, defaults to today)"
+        ),
+        # Transaction-specific options
+        amount: str = typer.Option(None, "--amount", help="Transaction amount (negative for spending)"),
+        description: str = typer.Option(None, "--description", help="Transaction description"),
+        status: str = typer.Option(
+            TransactionStatus.CLEARED.value, "--status", help="Transaction status: pending, cleared or reconciled"
         ),
     ) -> None:
         """Create a new resource.
@@ -51,17 +62,101 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
           # Add a balance snapshot (scriptable)
           tl new balance --account-id <uuid> --balance 1234.56
           tl new balance --account-id <uuid> --balance 1234.56 --date 2025-11-15
+          tl new balance --account "Chase Checking" --balance 1234.56
+
+          # Enter a transaction ahead of the statement, clear it later
+          tl new transaction --account-id <uuid> --amount -40 --description "Farmers market" --status pending
         """
         ensure_initialized()
 
+        if account is not None:
+            account_id = _resolve_account_option(get_container, account, account_id)
+
         if resource_type == "balance":
             _create_balance_snapshot(get_container, account_id, balance, snapshot_date)
+        elif resource_type == "transaction":
+            _create_transaction(get_container, account_id, amount, description, snapshot_date, status)
         else:
             display_error(f"Unknown resource type: {resource_type}")
-            console.print(f"[{theme.muted}]Available types: balance[/{theme.muted}]")
+            console.print(f"[{theme.muted}]Available types: balance, transaction[/{theme.muted}]")
             raise typer.Exit(1)
 
 
+def _resolve_account_option(get_container: callable, account: str, account_id: str | None) -> str:
+    """Resolve --account to an account ID string."""
+    if account_id is not None:
+        display_error("Use either --account or --account-id, not both", show_log_hint=False)
+        raise typer.Exit(1)
+
+    result = asyncio.run(get_container().account_service().resolve_account(account))
+    if not result.success:
+        display_error(result.error, show_log_hint=False)
+        raise typer.Exit(1)
+    return str(result.data.id)
+
+
+def _create_transaction(
+    get_container: callable,
+    account_id_str: str | None,
+    amount_str: str | None,
+    description: str | None,
+    date_str: str | None,
+    status_str: str,
+) -> None:
+    """Enter a transaction by hand."""
+    if not account_id_str or amount_str is None:
+        display_error("--account (or --account-id) and --amount are required for transactions", show_log_hint=False)
+        raise typer.Exit(1)
+
+    try:
+        account_id = UUID(account_id_str)
+    except ValueError:
+        display_error(f"Invalid account ID: {account_id_str}", show_log_hint=False)
+        raise typer.Exit(1)
+
+    try:
+        amount = Decimal(amount_str)
+    except Exception:
+        display_error(f"Invalid amount: {amount_str}", show_log_hint=False)
+        raise typer.Exit(1)
+
+    try:
+        transaction_date = date.fromisoformat(date_str) if date_str else date.today()
+    except ValueError:
+        display_error(f"Invalid date format: {date_str}", show_log_hint=False)
+        console.print(f"[{theme.muted}]Date must be in YYYY-MM-DD format[/{theme.muted}]\n")
+        raise typer.Exit(1)
+
+    try:
+        status = TransactionStatus(status_str.lower())
+    except ValueError:
+        display_error(f"Invalid status: {status_str}", show_log_hint=False)
+        console.print(f"[{theme.muted}]Status must be pending, cleared or reconciled[/{theme.muted}]\n")
+        raise typer.Exit(1)
+
+    transaction_service = get_container().transaction_service()
+    result = asyncio.run(
+        transaction_service.create_transaction(
+            account_id=account_id,
+            amount=amount,
+            description=description,
+            transaction_date=transaction_date,
+            status=status,
+        )
+    )
+
+    if not result.success:
+        display_error(f"Failed to add transaction: {result.error}")
+        raise typer.Exit(1)
+
+    transaction = result.data
+    console.print(f"\n[{theme.success}]✓ Added transaction[/{theme.success}]")
+    console.print(f"  ID: {transaction.id}")
+    console.print(f"  Amount: {transaction.amount}")
+    console.print(f"  Date: {transaction.transaction_date}")
+    console.print(f"  Status: {transaction.status.value}\n")
+
+
 def _create_balance_snapshot(
     get_container: callable,
     account_id_str: str | None,
@@ -102,6 +197,7 @@ def _create_balance_snapshot(
 
     else:
         # INTERACTIVE MODE
+        require_input_or_exit("--account-id and --balance")
         console.print(f"\n[{theme.ui_header}]Add Balance Snapshot[/{theme.ui_header}]\n")
 
         accounts_result = asyncio.run(account_service.get_accounts())
@@ -160,7 +256,8 @@ def _create_balance_snapshot(
         raise typer.Exit(1)
 
     snapshot = result.data
+    account_label = asyncio.run(account_service.directory()).name_for(snapshot.account_id)
     console.print(f"\n[{theme.success}]✓ Added balance snapshot[/{theme.success}]")
-    console.print(f"  Account ID: {snapshot.account_id}")
+    console.print(f"  Account: {account_label}")
     console.print(f"  Balance: {snapshot.balance}")
     console.print(f"  Date: {snapshot.snapshot_time.date()}\n")
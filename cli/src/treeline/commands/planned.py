@@ -0,0 +1,222 @@
+"""Planned command - expected transactions such as bills and paychecks."""
+
+import asyncio
+import json
+from datetime import date
+from decimal import Decimal, InvalidOperation
+
+import typer
+from pydantic import BaseModel
+from rich.console import Console
+from rich.table import Table
+
+from treeline.app.preferences_service import format_currency, format_date
+from treeline.commands.json_output import decimal_json, model_json
+from treeline.config import get_display_locale
+from treeline.domain import PlannedFrequency
+from treeline.theme import get_theme
+from treeline.utils import get_log_file_path
+
+console = Console()
+theme = get_theme()
+
+# Create planned subcommand group
+planned_app = typer.Typer(help="Planned transactions: bills, paychecks and other expected items")
+
+FREQUENCY_HELP = "How often it repeats: " + ", ".join(f.value for f in PlannedFrequency)
+
+
+def json_serializer(obj):
+    """Custom JSON serializer for Pydantic models and other objects."""
+    if isinstance(obj, BaseModel):
+        return model_json(obj)
+    if isinstance(obj, Decimal):
+        return decimal_json(obj)
+    return str(obj)
+
+
+def output_json(data: dict) -> None:
+    """Output data as JSON."""
+    print(json.dumps(data, indent=2, default=json_serializer))
+
+
+def display_error(error: str, show_log_hint: bool = True) -> None:
+    """Display error message in consistent format."""
+    console.print(f"[{theme.error}]Error: {error}[/{theme.error}]")
+    if show_log_hint:
+        log_file = get_log_file_path()
+        console.print(f"[{theme.muted}]See {log_file} for details[/{theme.muted}]")
+
+
+def _fail(error: str, json_output: bool, context: dict | None = None) -> None:
+    """Report an error in the requested format and exit."""
+    matches = (context or {}).get("matches", [])
+    if json_output:
+        error_data = {"success": False, "error": error}
+        if matches:
+            error_data["matches"] = matches
+        output_json(error_data)
+    else:
+        display_error(error, show_log_hint=False)
+        for match in matches:
+            console.print(f"  {match}")
+    raise typer.Exit(1)
+
+
+def _schedule(planned) -> str:
+    """Human-readable schedule, e.g. "monthly on day 1"."""
+    if planned.day_of_month is not None:
+        return f"{planned.frequency.value} on day {planned.day_of_month}"
+    return planned.frequency.value
+
+
+def register(app: typer.Typer, get_container: callable, ensure_initialized: callable) -> None:
+    """Register the planned commands with the app."""
+    app.add_typer(planned_app, name="planned")
+
+    @planned_app.command(name="add")
+    def planned_add_command(
+        account: str = typer.Option(..., "--account", help="Account name, nickname or ID"),
+        description: str = typer.Option(
+            ..., "--description", help="Payee, matched against synced descriptions"
+        ),
+        amount: str = typer.Option(..., "--amount", help="Expected amount, negative for bills"),
+        frequency: str = typer.Option("monthly", "--frequency", help=FREQUENCY_HELP),
+        due: str = typer.Option(None, "--due", help="First due date (YYYY-MM-DD)"),
+        day: int = typer.Option(
+            None, "--day", help="Day of month for monthly, quarterly and yearly items"
+        ),
+        planned_id: str = typer.Option(
+            None, "--id", help="Replace the planned transaction with this ID (or ID prefix)"
+        ),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Add an expected transaction, e.g. rent on the 1st.
+
+        When a synced transaction matches (amount within 5%, similar
+        description, dated within 3 days of the due date), the occurrence
+        is marked as arrived and the next one becomes due. A day past the
+        end of a month falls on its last day.
+
+        Examples:
+          tl planned add --account Checking --description Rent --amount -1800 --day 1
+          tl planned add --account Checking --description Payroll --amount 2400 --frequency biweekly --due 2025-01-10
+          tl planned add --account Card --description "Car insurance" --amount -640 --frequency yearly --due 2025-06-15
+          tl planned add --id 3f2a9c1e --account Checking --description Rent --amount -1850 --day 1
+        """
+        ensure_initialized()
+
+        try:
+            parsed_amount = Decimal(amount)
+        except InvalidOperation:
+            _fail(f"Invalid amount: {amount}", json_output)
+        try:
+            next_due = date.fromisoformat(due) if due else None
+        except ValueError:
+            _fail(f"Invalid --due date: {due} (expected YYYY-MM-DD)", json_output)
+
+        result = asyncio.run(
+            get_container()
+            .planned_service()
+            .upsert(
+                account,
+                description,
+                parsed_amount,
+                frequency,
+                next_due=next_due,
+                day_of_month=day,
+                planned_id=planned_id,
+            )
+        )
+        if not result.success:
+            _fail(result.error, json_output, result.context)
+
+        planned = result.data
+        if json_output:
+            output_json({"success": True, "planned": planned})
+            return
+
+        verb = "Updated" if planned_id else "Added"
+        console.print(
+            f"\n[{theme.success}]✓ {verb} {planned.description}, {_schedule(planned)}, "
+            f"next due {format_date(planned.next_due, get_display_locale())}[/{theme.success}]"
+        )
+        console.print(f"[{theme.muted}]ID: {planned.id}[/{theme.muted}]\n")
+
+    @planned_app.command(name="list")
+    def planned_list_command(
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """List planned transactions, soonest due first.
+
+        Examples:
+          tl planned list
+          tl planned list --json
+        """
+        ensure_initialized()
+
+        container = get_container()
+        result = asyncio.run(container.planned_service().list_planned())
+        if not result.success:
+            _fail(result.error, json_output)
+
+        planned_items = result.data or []
+        if json_output:
+            output_json({"success": True, "planned": planned_items})
+            return
+
+        if not planned_items:
+            console.print(f"\n[{theme.muted}]No planned transactions[/{theme.muted}]")
+            console.print(
+                f"[{theme.muted}]Add one with: tl planned add --account <name> "
+                f"--description Rent --amount -1800 --day 1[/{theme.muted}]\n"
+            )
+            return
+
+        directory = asyncio.run(container.account_service().directory())
+        currency = container.preferences_service().get_currency().data
+        locale = get_display_locale()
+        table = Table(title="Planned transactions", show_header=True, header_style=theme.ui_header)
+        table.add_column("ID", style=theme.muted)
+        table.add_column("Next due")
+        table.add_column("Description")
+        table.add_column("Account")
+        table.add_column("Amount", justify="right")
+        table.add_column("Schedule")
+
+        for planned in planned_items:
+            style = theme.negative_amount if planned.amount < 0 else theme.positive_amount
+            table.add_row(
+                str(planned.id)[:8],
+                format_date(planned.next_due, locale),
+                planned.description,
+                directory.name_for(planned.account_id),
+                f"[{style}]{format_currency(planned.amount, currency, locale=locale)}[/{style}]",
+                _schedule(planned),
+            )
+
+        console.print()
+        console.print(table)
+        console.print()
+
+    @planned_app.command(name="remove")
+    def planned_remove_command(
+        planned_id: str = typer.Argument(..., help="Planned transaction ID or unique ID prefix"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Remove a planned transaction.
+
+        Examples:
+          tl planned remove 3f2a9c1e
+        """
+        ensure_initialized()
+
+        result = asyncio.run(get_container().planned_service().remove(planned_id))
+        if not result.success:
+            _fail(result.error, json_output, result.context)
+
+        if json_output:
+            output_json({"success": True, "planned": result.data})
+            return
+
+        console.print(f"\n[{theme.success}]✓ Removed {result.data.description}[/{theme.success}]\n")
@@ -7,6 +7,7 @@ import typer
 from pydantic import BaseModel
 from rich.console import Console
 
+from treeline.commands.json_output import model_json
 from treeline.theme import get_theme
 from treeline.utils import get_log_file_path
 
@@ -20,7 +21,7 @@ plugin_app = typer.Typer(help="Plugin management commands")
 def json_serializer(obj):
     """Custom JSON serializer for Pydantic models and other objects."""
     if isinstance(obj, BaseModel):
-        return obj.model_dump(mode="json")
+        return model_json(obj)
     return str(obj)
 
 
@@ -3,24 +3,45 @@
 import asyncio
 import csv
 import json
+import os
 import sys
+from decimal import Decimal
+from pathlib import Path
 
 import typer
 from pydantic import BaseModel
 from rich.console import Console
+from rich.markup import escape
 from rich.table import Table
 
+from treeline.app.db_service import EXPORT_FORMATS
+from treeline.commands.json_output import decimal_json, model_json
+from treeline.config import get_query_max_file_bytes, get_query_timeout_secs, load_query_snippets
+from treeline.tables import (
+    MAX_COL_WIDTH_HELP,
+    WIDE_HELP,
+    TableLayout,
+    add_column,
+    print_table,
+    project_columns,
+    resolve_layout,
+)
 from treeline.theme import get_theme
 from treeline.utils import get_log_file_path
 
 console = Console()
 theme = get_theme()
 
+# Exit code when a query is cancelled by --timeout
+EXIT_QUERY_CANCELLED = 5
+
 
 def json_serializer(obj):
     """Custom JSON serializer for Pydantic models and other objects."""
     if isinstance(obj, BaseModel):
-        return obj.model_dump(mode="json")
+        return model_json(obj)
+    if isinstance(obj, Decimal):
+        return decimal_json(obj)
     return str(obj)
 
 
@@ -37,7 +58,42 @@ def display_error(error: str, show_log_hint: bool = True) -> None:
         console.print(f"[{theme.muted}]See {log_file} for details[/{theme.muted}]")
 
 
-def display_query_result(columns: list[str], rows: list[list]) -> None:
+def decode_json_columns(
+    column_types: list[str], rows: list, compact: bool
+) -> list[list]:
+    """Decode columns whose declared DuckDB type is JSON.
+
+    Only JSON-typed columns are touched, so VARCHAR values that merely look
+    like JSON are passed through unchanged.
+
+    Args:
+        column_types: DuckDB type codes from the query result
+        rows: Result rows
+        compact: If True, re-encode as single-line JSON strings (table/csv);
+                 otherwise return nested values (json output)
+    """
+    json_indices = {i for i, t in enumerate(column_types) if t == "JSON"}
+    if not json_indices:
+        return rows
+
+    decoded_rows = []
+    for row in rows:
+        decoded = list(row)
+        for i in json_indices:
+            if not isinstance(decoded[i], str):
+                continue
+            try:
+                value = json.loads(decoded[i])
+            except ValueError:
+                continue
+            decoded[i] = json.dumps(value, separators=(",", ":")) if compact else value
+        decoded_rows.append(decoded)
+    return decoded_rows
+
+
+def display_query_result(
+    columns: list[str], rows: list[list], layout: TableLayout = TableLayout()
+) -> None:
     """Display query results as a Rich table."""
     console.print()
 
@@ -46,7 +102,7 @@ def display_query_result(columns: list[str], rows: list[list]) -> None:
     )
 
     for col in columns:
-        table.add_column(col)
+        add_column(table, layout, col)
 
     for row in rows:
         str_row = [
@@ -55,12 +111,51 @@ def display_query_result(columns: list[str], rows: list[list]) -> None:
         ]
         table.add_row(*str_row)
 
-    console.print(table)
+    print_table(console, table, layout)
     console.print(
         f"\n[{theme.muted}]{len(rows)} row{'s' if len(rows) != 1 else ''} returned[/{theme.muted}]\n"
     )
 
 
+def _export_query(
+    db_service,
+    sql: str,
+    output: str,
+    file_format: str | None,
+    force: bool,
+    json_output: bool,
+    timeout_secs: float,
+) -> None:
+    """Write a query's result to a file (--output) and report where it went."""
+    output_path = Path(output).expanduser()
+    fmt = (file_format or output_path.suffix.lstrip(".")).lower()
+    if fmt not in EXPORT_FORMATS:
+        display_error(
+            f"Can't write {output}: unsupported format {fmt or '(none)'}. Choose: parquet, csv, json",
+            show_log_hint=False,
+        )
+        raise typer.Exit(1)
+
+    result = asyncio.run(
+        db_service.export_query(sql, output_path, fmt, overwrite=force, timeout_secs=timeout_secs)
+    )
+    if not result.success:
+        context = result.context or {}
+        hint = " (use --force to overwrite)" if context.get("exists") else ""
+        if json_output:
+            output_json({"success": False, "error": result.error + hint})
+        else:
+            display_error(result.error + hint, show_log_hint=False)
+        raise typer.Exit(EXIT_QUERY_CANCELLED if context.get("cancelled") else 1)
+
+    if json_output:
+        output_json({"success": True, **result.data})
+        return
+    console.print(
+        f"[{theme.success}]✓ Wrote {result.data['rows']} rows to {output_path}[/{theme.success}]"
+    )
+
+
 def register(app: typer.Typer, get_container: callable, ensure_initialized: callable) -> None:
     """Register the query command with the app."""
 
@@ -68,15 +163,42 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
     def query_command(
         sql: str = typer.Argument(None, help="SQL query to execute (SELECT/WITH only)"),
         file: str = typer.Option(None, "--file", "-f", help="Read SQL from file"),
+        snippet: str = typer.Option(
+            None, "--snippet", help="Run a snippet saved in the app's query console, by name"
+        ),
         format: str = typer.Option(
-            "table", "--format", help="Output format (table, json, csv)"
+            None, "--format", help="Output format (table, json, csv; parquet with --output)"
         ),
         json_output: bool = typer.Option(
             False, "--json", help="Output as JSON (alias for --format json)"
         ),
+        timeout: float = typer.Option(
+            None,
+            "--timeout",
+            help="Cancel the query after this many seconds (0 = unlimited; default from query.timeout_secs)",
+        ),
+        max_col_width: int = typer.Option(None, "--max-col-width", help=MAX_COL_WIDTH_HELP),
+        wide: bool = typer.Option(False, "--wide", help=WIDE_HELP),
+        columns_filter: str = typer.Option(
+            None, "--columns", help="Only these columns, in this order (comma-separated)"
+        ),
+        output: str = typer.Option(
+            None, "--output", "-o", help="Write the result to a .parquet, .csv or .json file"
+        ),
+        force: bool = typer.Option(False, "--force", help="Overwrite the --output file if it exists"),
     ) -> None:
         """Execute a SQL query and display results.
 
+        Files passed with --file are limited to query.max_file_mb (10 MB by
+        default). A query cancelled by --timeout exits with code 5.
+
+        Tables are fitted to the terminal, cutting long cells with an
+        ellipsis; piped output and --wide are never truncated. --columns
+        picks columns from the result in every format.
+
+        --output writes the whole result to a file without printing it; the
+        format comes from the extension unless --format says otherwise.
+
         Examples:
           # Inline SQL
           tl query "SELECT * FROM transactions LIMIT 10"
@@ -84,6 +206,9 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
           # From file
           tl query --file queries/monthly_spending.sql
 
+          # A saved snippet
+          tl query --snippet "Monthly spending by tag"
+
           # From stdin (pipe)
           cat queries/analysis.sql | tl query
 
@@ -92,6 +217,15 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
 
           # Output as CSV
           tl query "SELECT * FROM transactions LIMIT 10" --format csv
+
+          # Give up after 30 seconds
+          tl query --file slow_report.sql --timeout 30
+
+          # Save the result as parquet for pandas, polars or another DuckDB
+          tl query "SELECT * FROM transactions" --output transactions.parquet
+
+          # A few columns, without truncation
+          tl query "SELECT * FROM transactions" --columns date,description,amount --wide
         """
         ensure_initialized()
 
@@ -101,7 +235,46 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
         # Determine SQL source
         sql_content = None
 
-        if file:
+        if timeout is not None and timeout < 0:
+            display_error("--timeout must be 0 (unlimited) or a positive number of seconds", show_log_hint=False)
+            raise typer.Exit(1)
+        timeout_secs = get_query_timeout_secs() if timeout is None else timeout
+
+        if snippet:
+            snippets = load_query_snippets()
+            # A user's snippet wins over a built-in of the same name
+            match = next(
+                (
+                    s
+                    for s in sorted(snippets, key=lambda s: bool(s.get("builtin")))
+                    if str(s["name"]).lower() == snippet.strip().lower()
+                ),
+                None,
+            )
+            if match is None:
+                display_error(f"No snippet named '{snippet}'", show_log_hint=False)
+                if snippets:
+                    names = ", ".join(str(s["name"]) for s in snippets)
+                    console.print(f"[{theme.muted}]Available: {escape(names)}[/{theme.muted}]")
+                else:
+                    console.print(
+                        f"[{theme.muted}]Snippets are saved from the app's query console[/{theme.muted}]"
+                    )
+                raise typer.Exit(1)
+            sql_content = str(match["sql"])
+        elif file:
+            max_bytes = get_query_max_file_bytes()
+            try:
+                size = os.path.getsize(file)
+            except OSError:
+                size = 0  # Reported by open() below
+            if size > max_bytes:
+                display_error(
+                    f"{file} is {size / 1024 / 1024:.1f} MB, over the {round(max_bytes / 1024 / 1024, 2):g} MB limit "
+                    "for query files (raise query.max_file_mb in settings.json if this is intended)",
+                    show_log_hint=False,
+                )
+                raise typer.Exit(1)
             try:
                 with open(file, "r") as f:
                     sql_content = f.read()
@@ -127,26 +300,47 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
 
         sql_stripped = sql_content.strip()
 
+        if output:
+            _export_query(db_service, sql_stripped, output, format, force, json_output, timeout_secs)
+            return
+
         # Determine output format
-        output_format = "json" if json_output else format.lower()
+        output_format = "json" if json_output else (format or "table").lower()
         if output_format not in ["table", "json", "csv"]:
             display_error(f"Invalid format: {format}. Choose: table, json, csv")
             raise typer.Exit(1)
 
+        try:
+            layout = resolve_layout(console, max_col_width, wide, columns_filter)
+        except ValueError as e:
+            display_error(str(e), show_log_hint=False)
+            raise typer.Exit(1)
+
         # Execute query
         if output_format == "table":
             with console.status(f"[{theme.status_loading}]Running query..."):
-                result = asyncio.run(db_service.execute_query(sql_stripped))
+                result = asyncio.run(db_service.execute_query(sql_stripped, timeout_secs))
         else:
-            result = asyncio.run(db_service.execute_query(sql_stripped))
+            result = asyncio.run(db_service.execute_query(sql_stripped, timeout_secs))
 
         if not result.success:
-            display_error(result.error)
-            raise typer.Exit(1)
+            cancelled = bool(result.context and result.context.get("cancelled"))
+            display_error(result.error, show_log_hint=not cancelled)
+            raise typer.Exit(EXIT_QUERY_CANCELLED if cancelled else 1)
 
         query_result = result.data
         rows = query_result.get("rows", [])
         columns = query_result.get("columns", [])
+        rows = decode_json_columns(
+            query_result.get("column_types", []),
+            rows,
+            compact=output_format != "json",
+        )
+        try:
+            columns, rows = project_columns(columns, rows, layout.columns)
+        except ValueError as e:
+            display_error(str(e), show_log_hint=False)
+            raise typer.Exit(1)
 
         if len(rows) == 0:
             if output_format == "table":
@@ -166,4 +360,4 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
             for row in rows:
                 writer.writerow(row)
         else:
-            display_query_result(columns, rows)
+            display_query_result(columns, rows, layout)
@@ -4,8 +4,8 @@ import asyncio
 
 import typer
 from rich.console import Console
-from rich.prompt import Confirm
 
+from treeline.confirm import confirm_or_exit
 from treeline.theme import get_theme
 
 console = Console()
@@ -55,18 +55,8 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
 
         # Confirm removal
         if not force:
-            console.print(f"\n[{theme.warning}]This will remove the '{integration}' integration.[/{theme.warning}]")
-            console.print(f"[{theme.muted}]Your synced data will remain in the database.[/{theme.muted}]\n")
-
-            try:
-                confirmed = Confirm.ask("Are you sure?", default=False)
-            except (KeyboardInterrupt, EOFError):
-                console.print(f"\n[{theme.muted}]Cancelled[/{theme.muted}]\n")
-                raise typer.Exit(0)
-
-            if not confirmed:
-                console.print(f"[{theme.muted}]Cancelled[/{theme.muted}]\n")
-                raise typer.Exit(0)
+            console.print(f"\n[{theme.muted}]Your synced data will remain in the database.[/{theme.muted}]")
+            confirm_or_exit(f"This will remove the '{integration}' integration")
 
         # Remove integration
         result = asyncio.run(integration_service.delete_integration(integration.lower()))
@@ -0,0 +1,952 @@
+"""Report command - financial reports."""
+
+import asyncio
+import json
+import re
+from datetime import date
+from decimal import Decimal
+from pathlib import Path
+from typing import List
+from uuid import UUID
+
+import typer
+from pydantic import BaseModel
+from rich.console import Console
+from rich.table import Table
+
+from treeline.app.preferences_service import (
+    DEFAULT_CURRENCY,
+    format_currency,
+    format_date,
+    format_number,
+)
+from treeline.app.report_html import render_monthly_html, render_year_review_html
+from treeline.app.planned_service import DEFAULT_UPCOMING_DAYS
+from treeline.app.report_service import BALANCE_CHANGE_PERCENT, MERCHANTS_REPORT_TOP
+from treeline.commands.json_output import decimal_json, model_json
+from treeline.commands.report_markdown import render_digest_markdown
+from treeline.config import get_display_locale, get_week_start
+from treeline.period import PERIOD_HELP, resolve_date_range
+from treeline.preamble import print_preamble, with_preamble
+from treeline.smoothing import INTERVALS, SMOOTHING_HELP, Smoothing
+from treeline.theme import get_theme
+from treeline.utils import get_log_file_path
+
+console = Console()
+theme = get_theme()
+
+# Create report subcommand group
+report_app = typer.Typer(help="Financial reports")
+
+DEFAULT_PERIOD = "ytd"
+
+EXCLUDE_TAG_HELP = "Leave out transactions with this tag, e.g. business (can specify multiple)"
+INTERVAL_HELP = "One point per day, week or month (smoothing uses every day first)"
+DEFAULT_DIGEST_PERIOD = "last-week"
+
+# Width needed for the label column and each month column in wide layout
+LABEL_COLUMN_WIDTH = 22
+MONTH_COLUMN_WIDTH = 14
+
+
+def json_serializer(obj):
+    """Custom JSON serializer for Pydantic models and other objects."""
+    if isinstance(obj, BaseModel):
+        return model_json(obj)
+    if isinstance(obj, Decimal):
+        return decimal_json(obj)
+    if isinstance(obj, date):
+        return obj.isoformat()
+    return str(obj)
+
+
+def output_json(data: dict) -> None:
+    """Output data as JSON."""
+    print(json.dumps(data, indent=2, default=json_serializer))
+
+
+def display_error(error: str, show_log_hint: bool = True) -> None:
+    """Display error message in consistent format."""
+    console.print(f"[{theme.error}]Error: {error}[/{theme.error}]")
+    if show_log_hint:
+        log_file = get_log_file_path()
+        console.print(f"[{theme.muted}]See {log_file} for details[/{theme.muted}]")
+
+
+def _fail(error: str, json_output: bool, show_log_hint: bool = False) -> None:
+    """Report an error in the requested format and exit."""
+    if json_output:
+        output_json({"success": False, "error": error})
+    else:
+        display_error(error, show_log_hint=show_log_hint)
+    raise typer.Exit(1)
+
+
+def _parse_date(value: str | None, flag: str, json_output: bool) -> date | None:
+    if value is None:
+        return None
+    try:
+        return date.fromisoformat(value)
+    except ValueError:
+        _fail(f"Invalid {flag} date: {value} (expected YYYY-MM-DD)", json_output)
+
+
+def _parse_series_options(
+    smoothing: str | None, interval: str | None, json_output: bool
+) -> Smoothing | None:
+    """Validate --interval and parse --smoothing."""
+    if interval is not None and interval not in INTERVALS:
+        _fail(f"Invalid --interval: {interval} (expected {', '.join(INTERVALS)})", json_output)
+    if smoothing is None:
+        return None
+    try:
+        return Smoothing.parse(smoothing)
+    except ValueError as e:
+        _fail(str(e), json_output)
+
+
+def _resolve_range(
+    period: str | None, since: str | None, until: str | None, json_output: bool
+) -> tuple[date, date]:
+    """Date range from --period/--since/--until; year to date when none is given."""
+    since_date = _parse_date(since, "--since", json_output)
+    until_date = _parse_date(until, "--until", json_output)
+    if period is None and since_date is None and until_date is None:
+        period = DEFAULT_PERIOD
+
+    try:
+        start_date, end_date = resolve_date_range(
+            period, since_date, until_date, week_start=get_week_start()
+        )
+    except ValueError as e:
+        _fail(str(e), json_output)
+
+    if start_date is None:
+        _fail("--since is required when --period is not given", json_output)
+    end_date = end_date or date.today()
+    if start_date > end_date:
+        _fail("Start date must be on or before end date", json_output)
+    return start_date, end_date
+
+
+def _format_rate(rate, locale: str) -> str:
+    return "-" if rate is None else f"{format_number(rate, 1, locale)}%"
+
+
+def _cashflow_rows(
+    entry: dict, categories: list[str], currency: str, locale: str
+) -> list[tuple[str, str]]:
+    """Statement lines for one column (a month or the totals)."""
+
+    def money(amount) -> str:
+        formatted = format_currency(amount, currency, locale=locale)
+        return f"[{theme.negative_amount}]{formatted}[/{theme.negative_amount}]" if amount < 0 else formatted
+
+    rows = [("Income", money(entry["income"]))]
+    rows += [(f"  {category}", money(entry["expenses"].get(category, 0))) for category in categories]
+    rows += [
+        ("Total expenses", money(entry["total_expenses"])),
+        ("Net savings", money(entry["net_savings"])),
+        ("Savings rate", _format_rate(entry["savings_rate"], locale)),
+    ]
+    return rows
+
+
+def _display_trend(trend: dict | None, currency: str, locale: str, label: str = "Trend") -> None:
+    if trend is None:
+        return
+
+    def money(amount) -> str:
+        return format_currency(amount, currency, locale=locale)
+
+    console.print(
+        f"{label}: {money(trend['slope_per_month'])} per month "
+        f"(projected {money(trend['projected_30d'])} in 30 days, "
+        f"{money(trend['projected_90d'])} in 90 days)"
+    )
+
+
+def _display_cashflow(data: dict, currency: str) -> None:
+    locale = get_display_locale()
+    categories = list(data["totals"]["expenses"].keys())
+    columns = data["months"] + [{**data["totals"], "month": "Total"}]
+
+    start, end = format_date(data["start_date"], locale), format_date(data["end_date"], locale)
+    title = f"Cash flow {start} to {end}"
+    wide_enough = console.width >= LABEL_COLUMN_WIDTH + MONTH_COLUMN_WIDTH * len(columns)
+
+    if wide_enough:
+        table = Table(title=title, show_header=True, header_style=theme.ui_header)
+        table.add_column("")
+        for column in columns:
+            table.add_column(column["month"], justify="right")
+
+        per_column = [_cashflow_rows(column, categories, currency, locale) for column in columns]
+        for row_index, (label, _) in enumerate(per_column[0]):
+            table.add_row(label, *(rows[row_index][1] for rows in per_column))
+    else:
+        # Long format: one row per (month, line)
+        table = Table(title=title, show_header=True, header_style=theme.ui_header)
+        table.add_column("Month")
+        table.add_column("Line")
+        table.add_column("Amount", justify="right")
+        for column in columns:
+            for label, value in _cashflow_rows(column, categories, currency, locale):
+                table.add_row(column["month"], label, value)
+            table.add_section()
+
+    console.print()
+    console.print(table)
+
+    if data["accounts"]:
+        by_account = Table(show_header=True, header_style=theme.ui_header)
+        by_account.add_column("Account")
+        by_account.add_column("Expenses", justify="right")
+        by_account.add_column("Months open", justify="right")
+        by_account.add_column("Monthly average", justify="right")
+        for entry in data["accounts"]:
+            average = entry["average_monthly_expenses"]
+            by_account.add_row(
+                entry["account"],
+                format_currency(entry["total_expenses"], currency, locale=locale),
+                str(entry["months_open"]),
+                "-" if average is None else format_currency(average, currency, locale=locale),
+            )
+        console.print(by_account)
+
+    _display_trend(data.get("trend"), currency, locale, label="Net flow trend")
+
+    console.print(
+        f"[{theme.muted}]Transfers and credit card payments are excluded.[/{theme.muted}]\n"
+    )
+
+
+def _parse_month(value: str | None, json_output: bool) -> tuple[int, int]:
+    if value is None:
+        today = date.today()
+        return today.year, today.month
+    match = re.fullmatch(r"(\d{4})-(\d{2})", value.strip())
+    if not match or not 1 <= int(match.group(2)) <= 12:
+        _fail(f"Invalid --month: {value} (expected YYYY-MM)", json_output)
+    return int(match.group(1)), int(match.group(2))
+
+
+def _display_monthly(data: dict, currency: str) -> None:
+    locale = get_display_locale()
+
+    def money(amount) -> str:
+        return "-" if amount is None else format_currency(amount, currency, locale=locale)
+
+    net_worth = data["net_worth"]
+    console.print(f"\n[{theme.ui_header}]Monthly report: {data['month']}[/{theme.ui_header}]")
+    console.print(
+        f"  Net worth:    {money(net_worth['start'])} → {money(net_worth['end'])}"
+        f" ({money(net_worth['change'])})"
+    )
+    console.print(f"  Income:       {money(data['income'])}")
+    console.print(f"  Expenses:     {money(data['total_expenses'])}")
+    console.print(f"  Net savings:  {money(data['net_savings'])}")
+    console.print(f"  Savings rate: {_format_rate(data['savings_rate'], locale)}")
+    if net_worth["unknown_accounts"]:
+        console.print(
+            f"[{theme.muted}]  No balance data for: {', '.join(net_worth['unknown_accounts'])}[/{theme.muted}]"
+        )
+
+    tags = Table(title="Spending by tag", show_header=True, header_style=theme.ui_header)
+    tags.add_column("Tag")
+    tags.add_column("Amount", justify="right")
+    tags.add_column("Share", justify="right")
+    for entry in data["spending_by_tag"]:
+        tags.add_row(entry["tag"], money(entry["amount"]), _format_rate(entry["share"], locale))
+
+    merchants = Table(title="Top merchants", show_header=True, header_style=theme.ui_header)
+    merchants.add_column("Merchant")
+    merchants.add_column("Transactions", justify="right")
+    merchants.add_column("Spent", justify="right")
+    for entry in data["top_merchants"]:
+        merchants.add_row(entry["merchant"], str(entry["count"]), money(entry["amount"]))
+
+    large = Table(title="Large transactions", show_header=True, header_style=theme.ui_header)
+    large.add_column("Date")
+    large.add_column("Description")
+    large.add_column("Amount", justify="right")
+    for entry in data["large_transactions"]:
+        large.add_row(format_date(entry["date"], locale), entry["description"] or "", money(entry["amount"]))
+
+    for table in (tags, merchants, large):
+        console.print()
+        console.print(table)
+    console.print(
+        f"[{theme.muted}]Transfers and credit card payments are excluded.[/{theme.muted}]\n"
+    )
+
+
+def _display_year_review(data: dict, currency: str) -> None:
+    locale = get_display_locale()
+
+    def money(amount) -> str:
+        return "-" if amount is None else format_currency(amount, currency, locale=locale)
+
+    heading = f"{data['year']} in review" + (" (so far)" if data["year_to_date"] else "")
+    console.print(f"\n[{theme.ui_header}]{heading}[/{theme.ui_header}]")
+    if not data["enough_data"]:
+        console.print(f"[{theme.muted}]{data['message']}[/{theme.muted}]\n")
+        return
+
+    net_worth = data["net_worth"]
+    console.print(f"  Income:        {money(data['income'])}")
+    console.print(f"  Expenses:      {money(data['total_expenses'])}")
+    console.print(f"  Net savings:   {money(data['net_savings'])}")
+    console.print(f"  Savings rate:  {_format_rate(data['savings_rate'], locale)}")
+    console.print(
+        f"  Net worth:     {money(net_worth['start'])} → {money(net_worth['end'])}"
+        f" ({money(net_worth['change'])})"
+    )
+    console.print(f"  Subscriptions: {money(data['subscriptions']['total'])}")
+    if net_worth["unknown_accounts"]:
+        console.print(
+            f"[{theme.muted}]  No balance data for: {', '.join(net_worth['unknown_accounts'])}[/{theme.muted}]"
+        )
+
+    quarters = Table(title="Net worth by quarter", show_header=True, header_style=theme.ui_header)
+    quarters.add_column("Quarter")
+    quarters.add_column("End", justify="right")
+    quarters.add_column("Change", justify="right")
+    for entry in data["quarters"]:
+        quarters.add_row(entry["quarter"], money(entry["end"]), money(entry["change"]))
+
+    tags = Table(title="Top tags", show_header=True, header_style=theme.ui_header)
+    tags.add_column("Tag")
+    tags.add_column("Amount", justify="right")
+    tags.add_column("Share", justify="right")
+    for entry in data["top_tags"]:
+        tags.add_row(entry["tag"], money(entry["amount"]), _format_rate(entry["share"], locale))
+
+    merchants = Table(title="Top merchants", show_header=True, header_style=theme.ui_header)
+    merchants.add_column("Merchant")
+    merchants.add_column("Transactions", justify="right")
+    merchants.add_column("Spent", justify="right")
+    for entry in data["top_merchants"]:
+        merchants.add_row(entry["merchant"], str(entry["count"]), money(entry["amount"]))
+
+    for table in (quarters, tags, merchants):
+        console.print()
+        console.print(table)
+
+    console.print(f"\n[{theme.emphasis}]Highlights[/{theme.emphasis}]")
+    month = data["most_expensive_month"]
+    if month is not None:
+        console.print(f"  Most expensive month: {month['month']} ({money(month['total_expenses'])})")
+    biggest = data["biggest_expense"]
+    if biggest is not None:
+        console.print(
+            f"  Biggest expense: {biggest['description'] or ''} ({money(-biggest['amount'])}, "
+            f"{format_date(biggest['date'], locale)})"
+        )
+    highlights = data["highlights"]
+    for key, label, field in (
+        ("most_spending", "Most spending", "total_expenses"),
+        ("largest_increase", "Grew the most", "change"),
+        ("largest_decrease", "Shrank the most", "change"),
+    ):
+        if highlights[key] is not None:
+            console.print(f"  {label}: {highlights[key]['account']} ({money(highlights[key][field])})")
+    console.print(
+        f"[{theme.muted}]Transfers and credit card payments are excluded.[/{theme.muted}]\n"
+    )
+
+
+def _trend(entry: dict, locale: str) -> str:
+    """Arrow and percent change from the preceding period."""
+    if entry["new"]:
+        return f"[{theme.warning}]new[/{theme.warning}]"
+    percent = entry["percent_change"]
+    if percent is None or percent == 0:
+        return "→ 0%"
+    # More spending is the bad direction
+    if percent > 0:
+        return f"[{theme.negative_amount}]↑ {format_number(percent, 1, locale)}%[/{theme.negative_amount}]"
+    return f"[{theme.positive_amount}]↓ {format_number(-percent, 1, locale)}%[/{theme.positive_amount}]"
+
+
+def _display_balance_history(data: dict) -> None:
+    locale = get_display_locale()
+    currency = data["currency"]
+
+    def money(amount) -> str:
+        formatted = format_currency(amount, currency, locale=locale)
+        return f"[{theme.negative_amount}]{formatted}[/{theme.negative_amount}]" if amount < 0 else formatted
+
+    start, end = format_date(data["start_date"], locale), format_date(data["end_date"], locale)
+    table = Table(
+        title=f"{data['account']} balance {start} to {end}",
+        show_header=True,
+        header_style=theme.ui_header,
+    )
+    table.add_column("Date")
+    table.add_column("Balance", justify="right")
+    if data["smoothing"]:
+        smoothing = data["smoothing"]
+        table.add_column(f"{smoothing['kind'].upper()} {smoothing['window_days']}d", justify="right")
+    for point in data["series"]:
+        row = [format_date(point["date"], locale), money(point["balance"])]
+        if "smoothed" in point:
+            row.append(money(point["smoothed"]))
+        table.add_row(*row)
+
+    console.print()
+    if data["series"]:
+        console.print(table)
+        _display_trend(data["trend"], currency, locale)
+    else:
+        console.print(f"[{theme.muted}]No balance known for this period[/{theme.muted}]")
+    console.print()
+
+
+def _display_merchants(data: dict, currency: str) -> None:
+    locale = get_display_locale()
+
+    def money(amount) -> str:
+        return format_currency(amount, currency, locale=locale)
+
+    title = (
+        f"Top merchants: {format_date(data['start_date'], locale)} to "
+        f"{format_date(data['end_date'], locale)}"
+    )
+    table = Table(title=title, show_header=True, header_style=theme.ui_header)
+    table.add_column("Merchant")
+    table.add_column("Transactions", justify="right")
+    table.add_column("Spent", justify="right")
+    table.add_column("Average", justify="right")
+    table.add_column("Trend", justify="right")
+    for entry in data["merchants"]:
+        table.add_row(
+            entry["merchant"],
+            str(entry["count"]),
+            money(entry["amount"]),
+            money(entry["average"]),
+            _trend(entry, locale),
+        )
+
+    console.print()
+    console.print(table)
+    console.print(
+        f"[{theme.muted}]Trend compares with {format_date(data['prior_start_date'], locale)} to "
+        f"{format_date(data['prior_end_date'], locale)}. "
+        f"Income and transfers are excluded.[/{theme.muted}]\n"
+    )
+
+
+def _display_spending_by_tag(data: dict) -> None:
+    locale = get_display_locale()
+    title = (
+        f"Spending by tag: {format_date(data['start_date'], locale)} to "
+        f"{format_date(data['end_date'], locale)}"
+    )
+    table = Table(title=title, show_header=True, header_style=theme.ui_header)
+    table.add_column("Tag")
+    table.add_column("Transactions", justify="right")
+    table.add_column("Spent", justify="right")
+    for entry in data["tags"]:
+        table.add_row(
+            entry["tag"] or f"[{theme.muted}](untagged)[/{theme.muted}]",
+            str(entry["transaction_count"]),
+            format_currency(entry["total"], entry["currency"] or DEFAULT_CURRENCY, locale=locale),
+        )
+
+    console.print()
+    console.print(table)
+    console.print(
+        f"[{theme.muted}]A transaction with several tags counts toward each. "
+        f"Income and transfers are excluded.[/{theme.muted}]\n"
+    )
+
+
+def _display_upcoming(data: dict, currency: str) -> None:
+    locale = get_display_locale()
+
+    def money(amount) -> str:
+        return format_currency(amount, currency, locale=locale)
+
+    if not data["items"]:
+        console.print(
+            f"\n[{theme.muted}]Nothing planned through {format_date(data['end_date'], locale)}. "
+            f"Add bills with tl planned add.[/{theme.muted}]\n"
+        )
+        return
+
+    title = f"Upcoming through {format_date(data['end_date'], locale)}"
+    table = Table(title=title, show_header=True, header_style=theme.ui_header)
+    table.add_column("Due")
+    table.add_column("Description")
+    table.add_column("Account")
+    table.add_column("Amount", justify="right")
+    table.add_column("Balance after", justify="right")
+    for item in data["items"]:
+        due = format_date(item["due"], locale)
+        if item["overdue"]:
+            due = f"[{theme.warning}]{due} (overdue)[/{theme.warning}]"
+        style = theme.negative_amount if item["amount"] < 0 else theme.positive_amount
+        balance = "-" if item["projected_balance"] is None else money(item["projected_balance"])
+        if item["projected_negative"]:
+            balance = f"[{theme.error}]{balance} ⚠[/{theme.error}]"
+        table.add_row(
+            due,
+            item["description"],
+            item["account"],
+            f"[{style}]{money(item['amount'])}[/{style}]",
+            balance,
+        )
+
+    console.print()
+    console.print(table)
+    negatives = sum(1 for item in data["items"] if item["projected_negative"])
+    if negatives:
+        console.print(
+            f"[{theme.warning}]⚠ {negatives} payment(s) would take an account below zero[/{theme.warning}]"
+        )
+    console.print(
+        f"[{theme.muted}]Balances start from today's and add each planned amount in order.[/{theme.muted}]\n"
+    )
+
+
+def register(app: typer.Typer, get_container: callable, ensure_initialized: callable) -> None:
+    """Register the report commands with the app."""
+    app.add_typer(report_app, name="report")
+
+    @report_app.command(name="cashflow")
+    def cashflow_command(
+        period: str = typer.Option(None, "--period", help=PERIOD_HELP),
+        since: str = typer.Option(None, "--since", help="Start date (YYYY-MM-DD)"),
+        until: str = typer.Option(None, "--until", help="End date (YYYY-MM-DD)"),
+        exclude_pending: bool = typer.Option(
+            False, "--exclude-pending", help="Leave out transactions still marked pending"
+        ),
+        exclude_tag: List[str] = typer.Option(None, "--exclude-tag", help=EXCLUDE_TAG_HELP),
+        interval: str = typer.Option(None, "--interval", help=INTERVAL_HELP),
+        smoothing: str = typer.Option(None, "--smoothing", help=SMOOTHING_HELP),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Monthly cash-flow statement: income, expenses by tag, net savings.
+
+        Income excludes transfers and credit card payments (tagged 'transfer'
+        or 'payment', or money coming into a credit/loan account). Expenses
+        are grouped by each transaction's first tag. Pending transactions are
+        included unless --exclude-pending is given. Defaults to year to date.
+
+        With --interval or --smoothing, the JSON output also has the net flow
+        as a series (per day unless --interval says otherwise) and its trend.
+
+        Examples:
+          tl report cashflow
+          tl report cashflow --period 2024-Q3
+          tl report cashflow --exclude-tag business
+          tl report cashflow --interval week --smoothing sma:30 --json
+          tl report cashflow --since 2024-01-01 --until 2024-06-30 --json
+        """
+        ensure_initialized()
+
+        start_date, end_date = _resolve_range(period, since, until, json_output)
+        smoothing_options = _parse_series_options(smoothing, interval, json_output)
+
+        container = get_container()
+        report_service = container.report_service()
+        result = asyncio.run(
+            report_service.cashflow(
+                start_date,
+                end_date,
+                include_pending=not exclude_pending,
+                exclude_tags=exclude_tag or [],
+                smoothing=smoothing_options,
+                interval=interval,
+                week_start=get_week_start(),
+            )
+        )
+
+        if not result.success:
+            _fail(result.error, json_output, show_log_hint=True)
+
+        if json_output:
+            output_json(with_preamble({"success": True, **result.data}))
+            return
+
+        print_preamble(console)
+        currency_result = container.preferences_service().get_currency()
+        _display_cashflow(result.data, currency_result.data)
+
+    @report_app.command(name="balance-history")
+    def balance_history_command(
+        account_id: str = typer.Option(..., "--account-id", help="Account to chart"),
+        period: str = typer.Option(None, "--period", help=PERIOD_HELP),
+        since: str = typer.Option(None, "--since", help="Start date (YYYY-MM-DD)"),
+        until: str = typer.Option(None, "--until", help="End date (YYYY-MM-DD)"),
+        interval: str = typer.Option("day", "--interval", help=INTERVAL_HELP),
+        smoothing: str = typer.Option(None, "--smoothing", help=SMOOTHING_HELP),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """End-of-day balances of an account, with an optional rolling average.
+
+        Days with a balance snapshot use it; other days add that day's
+        transactions to the day before. The rolling average is computed over
+        every day, including the days before --since it needs, and only then
+        reduced to one point per --interval. A linear trend (change per month,
+        projected 30 and 90 days out) is included. Defaults to year to date.
+
+        Examples:
+          tl report balance-history --account-id <uuid>
+          tl report balance-history --account-id <uuid> --period last-year --interval week
+          tl report balance-history --account-id <uuid> --smoothing ema:30 --json
+        """
+        ensure_initialized()
+
+        try:
+            target_id = UUID(account_id)
+        except ValueError:
+            _fail(f"Invalid account ID: {account_id}", json_output)
+        start_date, end_date = _resolve_range(period, since, until, json_output)
+        smoothing_options = _parse_series_options(smoothing, interval, json_output)
+
+        container = get_container()
+        result = asyncio.run(
+            container.report_service().balance_history(
+                target_id,
+                start_date,
+                end_date,
+                smoothing=smoothing_options,
+                interval=interval,
+                week_start=get_week_start(),
+            )
+        )
+        if not result.success:
+            _fail(result.error, json_output, show_log_hint=True)
+
+        if json_output:
+            output_json(with_preamble({"success": True, **result.data}))
+            return
+
+        print_preamble(console)
+        _display_balance_history(result.data)
+
+    @report_app.command(name="merchants")
+    def merchants_command(
+        period: str = typer.Option(None, "--period", help=PERIOD_HELP),
+        since: str = typer.Option(None, "--since", help="Start date (YYYY-MM-DD)"),
+        until: str = typer.Option(None, "--until", help="End date (YYYY-MM-DD)"),
+        top: int = typer.Option(MERCHANTS_REPORT_TOP, "--top", help="Number of merchants to show"),
+        exclude_tag: List[str] = typer.Option(None, "--exclude-tag", help=EXCLUDE_TAG_HELP),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Where the money goes by payee: top merchants with trends.
+
+        Descriptions are grouped into merchants ignoring case, punctuation
+        and store numbers. Each merchant shows total spend, transaction count,
+        average ticket and the change from the preceding period of the same
+        length. Income and transfers are excluded. Defaults to year to date.
+
+        Examples:
+          tl report merchants
+          tl report merchants --period last-month --top 10
+          tl report merchants --exclude-tag business
+          tl report merchants --since 2024-01-01 --until 2024-03-31 --json
+        """
+        ensure_initialized()
+
+        if top < 1:
+            _fail("--top must be at least 1", json_output)
+        start_date, end_date = _resolve_range(period, since, until, json_output)
+
+        container = get_container()
+        result = asyncio.run(
+            container.report_service().merchants(
+                start_date, end_date, top=top, exclude_tags=exclude_tag or []
+            )
+        )
+        if not result.success:
+            _fail(result.error, json_output, show_log_hint=True)
+
+        if json_output:
+            output_json(with_preamble({"success": True, **result.data}))
+            return
+
+        print_preamble(console)
+        _display_merchants(result.data, container.preferences_service().get_currency().data)
+
+    @report_app.command(name="tags")
+    def tags_command(
+        period: str = typer.Option(None, "--period", help=PERIOD_HELP),
+        since: str = typer.Option(None, "--since", help="Start date (YYYY-MM-DD)"),
+        until: str = typer.Option(None, "--until", help="End date (YYYY-MM-DD)"),
+        account_id: str = typer.Option(None, "--account-id", help="Only this account"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Spending per tag, counting each tag of a transaction.
+
+        Unlike cashflow, which files each expense under its first tag, a
+        transaction with several tags counts toward each, so the totals can
+        add up to more than was spent. Income and transfers are excluded.
+        Defaults to year to date.
+
+        Examples:
+          tl report tags
+          tl report tags --period last-month
+          tl report tags --account-id <uuid> --since 2024-01-01 --json
+        """
+        ensure_initialized()
+
+        target_id = None
+        if account_id is not None:
+            try:
+                target_id = UUID(account_id)
+            except ValueError:
+                _fail(f"Invalid account ID: {account_id}", json_output)
+        start_date, end_date = _resolve_range(period, since, until, json_output)
+
+        container = get_container()
+        result = asyncio.run(
+            container.report_service().spending_by_tag(start_date, end_date, account_id=target_id)
+        )
+        if not result.success:
+            _fail(result.error, json_output, show_log_hint=True)
+
+        if json_output:
+            output_json(with_preamble({"success": True, **result.data}))
+            return
+
+        print_preamble(console)
+        _display_spending_by_tag(result.data)
+
+    @report_app.command(name="upcoming")
+    def upcoming_command(
+        days: int = typer.Option(DEFAULT_UPCOMING_DAYS, "--days", help="How many days ahead to look"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Upcoming bills and other planned transactions, with projected balances.
+
+        Lists occurrences of planned transactions (see tl planned) due in the
+        next --days days, plus overdue ones that haven't arrived yet. Each
+        shows its account's projected balance right after it, and payments
+        that would take a checking or savings account below zero are flagged.
+
+        Examples:
+          tl report upcoming
+          tl report upcoming --days 60 --json
+        """
+        ensure_initialized()
+
+        if days < 0:
+            _fail("--days cannot be negative", json_output)
+
+        container = get_container()
+        result = asyncio.run(container.planned_service().upcoming(days))
+        if not result.success:
+            _fail(result.error, json_output, show_log_hint=True)
+
+        if json_output:
+            output_json(with_preamble({"success": True, **result.data}))
+            return
+
+        print_preamble(console)
+        _display_upcoming(result.data, container.preferences_service().get_currency().data)
+
+    @report_app.command(name="monthly")
+    def monthly_command(
+        month: str = typer.Option(None, "--month", help="Month to report (YYYY-MM, defaults to this month)"),
+        output: str = typer.Option(
+            None, "--output", "-o", help="Write the report to a file (.html or .json)"
+        ),
+        exclude_tag: List[str] = typer.Option(None, "--exclude-tag", help=EXCLUDE_TAG_HELP),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Monthly summary: net worth change, income and expenses, top merchants.
+
+        Also lists spending by tag and the month's largest transactions. With
+        --output report.html the summary is written as a single self-contained
+        HTML file (no external assets) that can be attached to an email;
+        --output report.json writes the same data as JSON.
+
+        Examples:
+          tl report monthly
+          tl report monthly --month 2024-03 --output report.html
+          tl report monthly --month 2024-03 --output report.json
+          tl report monthly --exclude-tag business
+        """
+        ensure_initialized()
+
+        year, month_number = _parse_month(month, json_output)
+
+        output_path = Path(output).expanduser() if output else None
+        if output_path is not None and output_path.suffix.lower() not in (".html", ".htm", ".json"):
+            _fail(f"Unsupported output format: {output} (use .html or .json)", json_output)
+
+        container = get_container()
+        result = asyncio.run(
+            container.report_service().monthly(
+                year, month_number, exclude_tags=exclude_tag or []
+            )
+        )
+        if not result.success:
+            _fail(result.error, json_output, show_log_hint=True)
+
+        currency = container.preferences_service().get_currency().data
+
+        if output_path is not None:
+            if output_path.suffix.lower() == ".json":
+                content = json.dumps(result.data, indent=2, default=json_serializer) + "\n"
+            else:
+                content = render_monthly_html(result.data, currency, get_display_locale())
+            try:
+                output_path.write_text(content, encoding="utf-8")
+            except OSError as e:
+                _fail(f"Could not write {output_path}: {e}", json_output)
+
+            if json_output:
+                output_json({"success": True, "output": str(output_path)})
+            else:
+                console.print(f"[{theme.success}]✓ Wrote {output_path}[/{theme.success}]")
+            return
+
+        if json_output:
+            output_json(with_preamble({"success": True, **result.data}))
+            return
+
+        print_preamble(console)
+        _display_monthly(result.data, currency)
+
+    @report_app.command(name="digest")
+    def digest_command(
+        period: str = typer.Option(DEFAULT_DIGEST_PERIOD, "--period", help=PERIOD_HELP),
+        balance_threshold: float = typer.Option(
+            float(BALANCE_CHANGE_PERCENT),
+            "--balance-threshold",
+            help="List accounts whose balance moved by more than this percent",
+        ),
+        output: str = typer.Option(
+            None, "--output", "-o", help="Write the digest to a file (.md or .json)"
+        ),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Short summary of a period compared with the one before it.
+
+        Shows total spending with the change from the prior period of the
+        same length, the top categories, the largest expense, how many
+        transactions were added, accounts whose balance moved by more than
+        --balance-threshold percent, and unresolved provider warnings.
+        Prints Markdown, ready to paste into notes or chat.
+
+        Examples:
+          tl report digest
+          tl report digest --period last-month --output digest.md
+          tl report digest --json
+        """
+        ensure_initialized()
+
+        try:
+            start_date, end_date = resolve_date_range(
+                period, None, None, week_start=get_week_start()
+            )
+        except ValueError as e:
+            _fail(str(e), json_output)
+
+        if not 0 <= balance_threshold < float("inf"):
+            _fail(f"Invalid --balance-threshold: {balance_threshold}", json_output)
+
+        output_path = Path(output).expanduser() if output else None
+        if output_path is not None and output_path.suffix.lower() not in (".md", ".markdown", ".json"):
+            _fail(f"Unsupported output format: {output} (use .md or .json)", json_output)
+
+        container = get_container()
+        result = asyncio.run(
+            container.report_service().digest(
+                start_date, end_date, balance_change_percent=Decimal(str(balance_threshold))
+            )
+        )
+        if not result.success:
+            _fail(result.error, json_output, show_log_hint=True)
+
+        currency = container.preferences_service().get_currency().data
+
+        if output_path is not None:
+            if output_path.suffix.lower() == ".json":
+                content = json.dumps(result.data, indent=2, default=json_serializer) + "\n"
+            else:
+                content = render_digest_markdown(result.data, currency, get_display_locale())
+            try:
+                output_path.write_text(content, encoding="utf-8")
+            except OSError as e:
+                _fail(f"Could not write {output_path}: {e}", json_output)
+
+            if json_output:
+                output_json({"success": True, "output": str(output_path)})
+            else:
+                console.print(f"[{theme.success}]✓ Wrote {output_path}[/{theme.success}]")
+            return
+
+        if json_output:
+            output_json(with_preamble({"success": True, **result.data}))
+            return
+
+        print_preamble(console)
+        print(render_digest_markdown(result.data, currency, get_display_locale()), end="")
+
+    @report_app.command(name="year")
+    def year_command(
+        year: int = typer.Option(None, "--year", help="Year to review (defaults to this year)"),
+        output: str = typer.Option(
+            None, "--output", "-o", help="Write the review to a file (.html or .json)"
+        ),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Year in review: income, savings rate, net worth by quarter, top spending.
+
+        Also shows the top merchants and tags, the biggest expense, the most
+        expensive month, what recurring charges (subscriptions and bills)
+        cost over the year and per-account highlights. The figures match
+        the cashflow, merchants and monthly reports for the same dates.
+        With --output review.html the review is written as a single
+        self-contained HTML file; --output review.json writes the data.
+
+        Examples:
+          tl report year
+          tl report year --year 2024 --output review.html
+          tl report year --year 2024 --json
+        """
+        ensure_initialized()
+
+        if year is None:
+            year = date.today().year
+        elif not 1900 <= year <= 9999:
+            _fail(f"Invalid --year: {year}", json_output)
+
+        output_path = Path(output).expanduser() if output else None
+        if output_path is not None and output_path.suffix.lower() not in (".html", ".htm", ".json"):
+            _fail(f"Unsupported output format: {output} (use .html or .json)", json_output)
+
+        container = get_container()
+        result = asyncio.run(container.year_review_service().review(year))
+        if not result.success:
+            _fail(result.error, json_output, show_log_hint=True)
+
+        currency = container.preferences_service().get_currency().data
+
+        if output_path is not None:
+            if output_path.suffix.lower() == ".json":
+                content = json.dumps(result.data, indent=2, default=json_serializer) + "\n"
+            else:
+                content = render_year_review_html(result.data, currency, get_display_locale())
+            try:
+                output_path.write_text(content, encoding="utf-8")
+            except OSError as e:
+                _fail(f"Could not write {output_path}: {e}", json_output)
+
+            if json_output:
+                output_json({"success": True, "output": str(output_path)})
+            else:
+                console.print(f"[{theme.success}]✓ Wrote {output_path}[/{theme.success}]")
+            return
+
+        if json_output:
+            output_json(with_preamble({"success": True, **result.data}))
+            return
+
+        print_preamble(console)
+        _display_year_review(result.data, currency)
@@ -0,0 +1,83 @@
+"""Markdown rendering for the digest.
+
+Plain Markdown (headings, bullets and one table) so the digest pastes
+cleanly into notes apps and chat.
+"""
+
+from decimal import Decimal
+from typing import Any, Dict
+
+from treeline.app.preferences_service import format_currency, format_date, format_number
+
+
+def _money(amount: Decimal, currency: str, locale: str | None) -> str:
+    return format_currency(amount, currency, locale=locale)
+
+
+def _percent(entry: Dict[str, Any], locale: str | None) -> str:
+    """e.g. "+12.5%", "new", or "-" when both amounts are zero."""
+    if entry["new"]:
+        return "new"
+    if entry["percent_change"] is None:
+        return "-"
+    sign = "+" if entry["percent_change"] > 0 else ""
+    return f"{sign}{format_number(entry['percent_change'], 1, locale)}%"
+
+
+def _cell(text: str) -> str:
+    return text.replace("|", "\\|")
+
+
+def render_digest_markdown(
+    digest: Dict[str, Any], currency: str = "USD", locale: str | None = None
+) -> str:
+    """Render ReportService.digest() output as Markdown."""
+    spent = digest["spent"]
+    lines = [
+        f"# Digest: {format_date(digest['start_date'], locale)} to {format_date(digest['end_date'], locale)}",
+        "",
+        f"- **Spent:** {_money(spent['current'], currency, locale)} "
+        f"({_percent(spent, locale)} vs {_money(spent['prior'], currency, locale)} the period before)",
+        f"- **New transactions:** {digest['new_transactions']}",
+    ]
+
+    largest = digest["largest_transaction"]
+    if largest is not None:
+        where = f" ({largest['account']})" if largest["account"] else ""
+        lines.append(
+            f"- **Largest transaction:** {largest['description'] or '(no description)'}, "
+            f"{_money(-largest['amount'], currency, locale)}{where} on {format_date(largest['date'], locale)}"
+        )
+
+    lines += ["", "## Top categories", ""]
+    if digest["top_categories"]:
+        lines += ["| Category | Spent | Before | Change |", "| --- | ---: | ---: | ---: |"]
+        for entry in digest["top_categories"]:
+            lines.append(
+                f"| {_cell(entry['category'])} | {_money(entry['current'], currency, locale)} "
+                f"| {_money(entry['prior'], currency, locale)} | {_percent(entry, locale)} |"
+            )
+    else:
+        lines.append("No spending this period.")
+
+    if digest["balance_changes"]:
+        lines += ["", "## Balance changes", ""]
+        for change in digest["balance_changes"]:
+            account_currency = change["currency"] or currency
+            lines.append(
+                f"- {change['account']}: {_money(change['start'], account_currency, locale)} → "
+                f"{_money(change['end'], account_currency, locale)} ({_percent(change, locale)})"
+            )
+
+    if digest["provider_warnings"]:
+        lines += ["", "## Provider warnings", ""]
+        for warning in digest["provider_warnings"]:
+            lines.append(f"- {warning['integration']}: {warning['message']}")
+
+    lines += [
+        "",
+        "_Transfers and credit card payments are excluded. "
+        f"Compared with {format_date(digest['prior_start_date'], locale)} to "
+        f"{format_date(digest['prior_end_date'], locale)}._",
+    ]
+    return "\n".join(lines) + "\n"
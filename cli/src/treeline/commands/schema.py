@@ -0,0 +1,99 @@
+"""Schema command - describe tables, views and their columns."""
+
+import asyncio
+import json
+
+import typer
+from rich.console import Console
+from rich.table import Table
+
+from treeline.theme import get_theme
+from treeline.utils import get_log_file_path
+
+console = Console()
+theme = get_theme()
+
+
+def output_json(data: dict) -> None:
+    """Output data as JSON."""
+    print(json.dumps(data, indent=2))
+
+
+def display_error(error: str, show_log_hint: bool = True) -> None:
+    """Display error message in consistent format."""
+    console.print(f"[{theme.error}]Error: {error}[/{theme.error}]")
+    if show_log_hint:
+        log_file = get_log_file_path()
+        console.print(f"[{theme.muted}]See {log_file} for details[/{theme.muted}]")
+
+
+def _fail(error: str, json_output: bool, show_log_hint: bool = False) -> None:
+    """Report an error in the requested format and exit."""
+    if json_output:
+        output_json({"success": False, "error": error})
+    else:
+        display_error(error, show_log_hint=show_log_hint)
+    raise typer.Exit(1)
+
+
+def display_table(entry: dict, show_examples: bool) -> None:
+    """Display one table or view with its columns."""
+    title = f"[{theme.emphasis}]{entry['name']}[/{theme.emphasis}] ({entry['kind']})"
+    if entry["description"]:
+        title += f" - {entry['description']}"
+    console.print(title)
+
+    table = Table(show_header=True, header_style=theme.ui_header, box=None, padding=(0, 2))
+    table.add_column("Column")
+    table.add_column("Type", style=theme.muted)
+    table.add_column("Description")
+    for column in entry["columns"]:
+        table.add_row(column["name"], column["type"], column["description"] or "")
+    console.print(table)
+
+    if show_examples and entry["examples"]:
+        console.print(f"\n[{theme.emphasis}]Examples:[/{theme.emphasis}]")
+        for example in entry["examples"]:
+            console.print(f'  tl query "{example}"', markup=False, highlight=False)
+    console.print()
+
+
+def register(app: typer.Typer, get_container: callable, ensure_initialized: callable) -> None:
+    """Register the schema command with the app."""
+
+    @app.command(name="schema")
+    def schema_command(
+        table: str = typer.Argument(None, help="Table or view to describe"),
+        include_system: bool = typer.Option(
+            False, "--all", help="Include internal sys_* tables"
+        ),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Show the tables and views available to tl query, with their columns.
+
+        Examples:
+          tl schema
+          tl schema transactions
+          tl schema --all --json
+        """
+        ensure_initialized()
+
+        db_service = get_container().db_service()
+        result = asyncio.run(db_service.get_schema(table=table, include_system=include_system))
+
+        if not result.success:
+            _fail(result.error, json_output)
+
+        if json_output:
+            output_json({"success": True, "tables": result.data})
+            return
+
+        console.print()
+        for entry in result.data:
+            display_table(entry, show_examples=table is not None)
+
+        if table is None:
+            hint = "Run 'tl schema <name>' for example queries"
+            if not include_system:
+                hint += ", or 'tl schema --all' to include sys_* tables"
+            console.print(f"[{theme.muted}]{hint}[/{theme.muted}]")
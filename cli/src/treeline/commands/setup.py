@@ -6,7 +6,8 @@ import typer
 from rich.console import Console
 from rich.prompt import Prompt
 
-from treeline.config import is_demo_mode
+from treeline.confirm import require_input_or_exit
+from treeline.features import SIMPLEFIN, disabled_error, is_enabled
 from treeline.theme import get_theme
 from treeline.utils import get_log_file_path
 
@@ -33,6 +34,11 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
         token: str = typer.Option(
             None, "--token", help="Setup token (optional, will prompt if not provided)"
         ),
+        allow_demo_db: bool = typer.Option(
+            False,
+            "--allow-demo-db",
+            help="Set up a real integration even though demo mode is on",
+        ),
     ) -> None:
         """Set up financial data integrations.
 
@@ -46,12 +52,16 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
           # Non-interactive setup with token
           tl setup simplefin --token YOUR_TOKEN
 
-        Note: For demo mode, use 'tl demo on' instead.
+        Note: For demo mode, use 'tl demo on' instead. Real integrations are
+        refused while demo mode is on, since their credentials would be stored
+        in the throwaway demo database; --allow-demo-db overrides this (sync
+        in demo mode still only uses the demo provider).
         """
         ensure_initialized()
 
         if integration is None:
             # Interactive wizard
+            require_input_or_exit("Integration name")
             console.print(f"\n[{theme.ui_header}]Integration Setup[/{theme.ui_header}]\n")
             console.print(f"[{theme.info}]Available integrations:[/{theme.info}]")
             console.print(f"  [{theme.emphasis}]1[/{theme.emphasis}] - SimpleFIN")
@@ -73,13 +83,19 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
         # Handle specific integrations
         integration_lower = integration.lower()
         if integration_lower == "simplefin":
-            # Block in demo mode
-            if is_demo_mode():
-                console.print(
-                    f"[{theme.warning}]Cannot set up integrations in demo mode[/{theme.warning}]"
+            if not is_enabled(SIMPLEFIN):
+                display_error(disabled_error(SIMPLEFIN), show_log_hint=False)
+                raise typer.Exit(1)
+            # Real credentials don't belong in the demo database
+            if get_container().is_demo_mode and not allow_demo_db:
+                display_error(
+                    "Demo mode is on: SimpleFIN would be set up in demo.duckdb, "
+                    "not your real database",
+                    show_log_hint=False,
                 )
                 console.print(
-                    f"[{theme.muted}]Use 'tl demo off' to switch to real mode first[/{theme.muted}]\n"
+                    f"[{theme.muted}]Run 'tl demo off' first, or pass --allow-demo-db "
+                    f"if you really want it in the demo database[/{theme.muted}]\n"
                 )
                 raise typer.Exit(1)
             _setup_simplefin(get_container, token)
@@ -106,6 +122,7 @@ def _setup_simplefin(get_container: callable, token: str | None = None) -> None:
     if token:
         setup_token = token.strip()
     else:
+        require_input_or_exit("Setup token (--token)")
         console.print(
             f"[{theme.muted}]If you don't have a SimpleFIN account, create one at: https://beta-bridge.simplefin.org/[/{theme.muted}]\n"
         )
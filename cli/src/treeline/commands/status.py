@@ -2,6 +2,8 @@
 
 import asyncio
 import json
+from datetime import date
+from decimal import Decimal
 
 import typer
 from pydantic import BaseModel
@@ -9,16 +11,29 @@ from rich.console import Console
 from rich.table import Table
 
 from treeline.app.container import Container
+from treeline.app.integration_service import integration_label
+from treeline.app.preferences_service import format_currency, format_date, format_number
+from treeline.app.status_service import group_accounts_by_institution
+from treeline.commands.json_output import decimal_json, model_json
+from treeline.config import get_display_locale
+from treeline.preamble import print_preamble, with_preamble
 from treeline.theme import get_theme
 
 console = Console()
 theme = get_theme()
 
+GROUP_BY_CHOICES = ("institution", "none")
+
+# Above this many accounts, status groups by institution unless --group-by none
+AUTO_GROUP_ACCOUNT_THRESHOLD = 8
+
 
 def json_serializer(obj):
     """Custom JSON serializer for Pydantic models and other objects."""
     if isinstance(obj, BaseModel):
-        return obj.model_dump(mode="json")
+        return model_json(obj)
+    if isinstance(obj, Decimal):
+        return decimal_json(obj)
     return str(obj)
 
 
@@ -27,8 +42,98 @@ def output_json(data: dict) -> None:
     print(json.dumps(data, indent=2, default=json_serializer))
 
 
-def display_status(status: dict) -> None:
+def _format_totals(totals: dict, locale: str) -> str:
+    if not totals:
+        return "-"
+    return ", ".join(
+        format_currency(amount, currency, locale=locale)
+        for currency, amount in sorted(totals.items())
+    )
+
+
+def _balance_cell(account, balances: dict, locale: str) -> str:
+    balance = balances.get(account.id)
+    if balance is None:
+        return f"[{theme.muted}]unknown[/{theme.muted}]"
+    style = theme.negative_amount if balance < 0 else theme.ui_value
+    return f"[{style}]{format_currency(balance, account.currency, locale=locale)}[/{style}]"
+
+
+def display_accounts(status: dict, grouping: dict | None, locale: str) -> None:
+    """Display the accounts table, with institution subtotals when grouped."""
+    accounts = status["accounts"]
+    if not accounts:
+        return
+    balances = status["balances"]
+
+    table = Table(show_header=True, header_style=theme.ui_header, show_footer=grouping is not None)
+    table.add_column("Account", footer="Total" if grouping else "")
+    table.add_column("Institution", style=theme.muted)
+    table.add_column(
+        "Balance",
+        justify="right",
+        footer=_format_totals(grouping["totals"], locale) if grouping else "",
+    )
+
+    if grouping is None:
+        for account in sorted(accounts, key=lambda a: (a.nickname or a.name).casefold()):
+            table.add_row(
+                account.nickname or account.name,
+                account.institution_name or "",
+                _balance_cell(account, balances, locale),
+            )
+    else:
+        by_id = {str(account.id): account for account in accounts}
+        for group in grouping["groups"]:
+            for account_id in group["account_ids"]:
+                account = by_id[account_id]
+                table.add_row(
+                    account.nickname or account.name,
+                    group["institution"],
+                    _balance_cell(account, balances, locale),
+                )
+            subtotal = _format_totals(group["subtotals"], locale)
+            if group["unknown_balances"]:
+                subtotal += f" (+{group['unknown_balances']} unknown)"
+            table.add_row(
+                f"[{theme.emphasis}]{group['institution']} subtotal[/{theme.emphasis}]",
+                "",
+                f"[{theme.emphasis}]{subtotal}[/{theme.emphasis}]",
+                end_section=True,
+            )
+
+    console.print()
+    console.print(table)
+
+
+def warning_integrations(status: dict) -> list[str]:
+    """Names of integrations with unresolved provider warnings."""
+    return sorted({warning.integration_name for warning in status["provider_warnings"]})
+
+
+def display_needs_attention(status: dict) -> None:
+    """List stale accounts, integrations with warnings and untyped accounts."""
+    integrations = warning_integrations(status)
+    if not (status["stale_accounts"] or integrations or status["untyped_accounts"]):
+        return
+
+    console.print(f"\n[{theme.warning}]⚠ Needs attention:[/{theme.warning}]")
+    for stale in status["stale_accounts"]:
+        console.print(
+            f"  [{theme.warning}]• {stale['name']}: {'; '.join(stale['reasons'])}[/{theme.warning}]"
+        )
+    for name in integrations:
+        console.print(f"  [{theme.warning}]• {name}: unresolved provider warnings[/{theme.warning}]")
+    for account in status["untyped_accounts"]:
+        console.print(
+            f"  [{theme.warning}]• {account.name}: no account type "
+            f"(tl accounts set-type {str(account.id)[:8]} <type>)[/{theme.warning}]"
+        )
+
+
+def display_status(status: dict, grouping: dict | None = None) -> None:
     """Display status using Rich formatting."""
+    locale = get_display_locale()
     console.print(f"\n[{theme.ui_header}]📊 Financial Data Status[/{theme.ui_header}]\n")
 
     # Display summary
@@ -36,24 +141,53 @@ def display_status(status: dict) -> None:
     summary_table.add_column("Metric", style=theme.info)
     summary_table.add_column("Value", style=theme.ui_value)
 
-    summary_table.add_row("Accounts", str(len(status["accounts"])))
-    summary_table.add_row("Transactions", str(status["total_transactions"]))
-    summary_table.add_row("Balance Snapshots", str(status["total_snapshots"]))
-    summary_table.add_row("Integrations", str(len(status["integrations"])))
+    accounts = format_number(len(status["accounts"]), 0, locale)
+    if status.get("archived_accounts"):
+        accounts += f" ({format_number(status['archived_accounts'], 0, locale)} archived)"
+    summary_table.add_row("Accounts", accounts)
+    summary_table.add_row("Transactions", format_number(status["total_transactions"], 0, locale))
+    summary_table.add_row("Balance Snapshots", format_number(status["total_snapshots"], 0, locale))
+    summary_table.add_row("Integrations", format_number(len(status["integrations"]), 0, locale))
 
     console.print(summary_table)
 
-    # Date range
-    if status["earliest_date"] and status["latest_date"]:
+    display_accounts(status, grouping, locale)
+
+    display_needs_attention(status)
+
+    # Unresolved provider warnings - shown prominently so degraded connections aren't missed
+    if status["provider_warnings"]:
+        console.print(f"\n[{theme.warning}]⚠ Provider warnings:[/{theme.warning}]")
+        for warning in status["provider_warnings"]:
+            console.print(
+                f"  [{theme.warning}]• {warning.integration_name}: {warning.message}[/{theme.warning}]"
+            )
         console.print(
-            f"\n[{theme.muted}]Date range: {status['earliest_date']} to {status['latest_date']}[/{theme.muted}]"
+            f"[{theme.muted}]Run 'tl warnings list' for details or 'tl warnings resolve <id>' to dismiss[/{theme.muted}]"
         )
 
+    # Corrupted rows - left out of the numbers above, so say so
+    if status["row_errors"]:
+        console.print(f"\n[{theme.warning}]⚠ Some data could not be read:[/{theme.warning}]")
+        for row_error in status["row_errors"]:
+            console.print(f"  [{theme.warning}]• {row_error}[/{theme.warning}]")
+
+    # Date range
+    if status["earliest_date"] and status["latest_date"]:
+        earliest = format_date(date.fromisoformat(status["earliest_date"]), locale)
+        latest = format_date(date.fromisoformat(status["latest_date"]), locale)
+        console.print(f"\n[{theme.muted}]Date range: {earliest} to {latest}[/{theme.muted}]")
+
     # Show integrations
     if status["integrations"]:
         console.print(f"\n[{theme.emphasis}]Connected Integrations:[/{theme.emphasis}]")
         for integration in status["integrations"]:
-            console.print(f"  • {integration['integrationName']}")
+            name = integration["integrationName"]
+            label = integration_label(integration)
+            suffix = f" [{theme.muted}]({name})[/{theme.muted}]" if label != name else ""
+            console.print(f"  • {label}{suffix}")
+            if integration.get("notes"):
+                console.print(f"    [{theme.muted}]{integration['notes']}[/{theme.muted}]")
 
     console.print()
 
@@ -63,9 +197,30 @@ def register(app: typer.Typer, get_container: callable) -> None:
 
     @app.command(name="status")
     def status_command(
+        group_by: str = typer.Option(
+            None,
+            "--group-by",
+            help=(
+                "Group accounts: institution or none "
+                f"(default: institution when there are more than {AUTO_GROUP_ACCOUNT_THRESHOLD} accounts)"
+            ),
+        ),
         json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
     ) -> None:
-        """Show account summary and statistics."""
+        """Show account summary and statistics.
+
+        Examples:
+          tl status
+          tl status --group-by institution
+          tl status --json --group-by none
+        """
+        if group_by is not None and group_by.lower() not in GROUP_BY_CHOICES:
+            console.print(
+                f"[{theme.error}]Error: Invalid --group-by: {group_by} "
+                f"(expected {' or '.join(GROUP_BY_CHOICES)})[/{theme.error}]"
+            )
+            raise typer.Exit(1)
+
         container = get_container()
         status_service = container.status_service()
 
@@ -75,26 +230,61 @@ def register(app: typer.Typer, get_container: callable) -> None:
             console.print(f"[{theme.error}]Error: {result.error}[/{theme.error}]")
             raise typer.Exit(1)
 
+        accounts = result.data["accounts"]
+        balances = result.data["balances"]
+        if group_by is None:
+            group_by = "institution" if len(accounts) > AUTO_GROUP_ACCOUNT_THRESHOLD else "none"
+        grouping = (
+            group_accounts_by_institution(accounts, balances)
+            if group_by.lower() == "institution"
+            else None
+        )
+
         if json_output:
             json_data = {
                 "total_accounts": result.data["total_accounts"],
+                "archived_accounts": result.data["archived_accounts"],
                 "total_transactions": result.data["total_transactions"],
                 "total_snapshots": result.data["total_snapshots"],
                 "total_integrations": result.data["total_integrations"],
                 "integration_names": result.data["integration_names"],
+                "integrations": [
+                    {
+                        "name": integration["integrationName"],
+                        "display_name": integration.get("displayName"),
+                        "notes": integration.get("notes"),
+                    }
+                    for integration in result.data["integrations"]
+                ],
                 "accounts": [
                     {
                         "id": str(acc.id),
                         "name": acc.name,
+                        "nickname": acc.nickname,
                         "institution_name": acc.institution_name,
+                        "currency": acc.currency,
+                        "balance": balances.get(acc.id),
                     }
-                    for acc in result.data["accounts"]
+                    for acc in accounts
                 ],
+                "group_by": "institution" if grouping else None,
                 "date_range": {
                     "earliest": result.data["earliest_date"],
                     "latest": result.data["latest_date"],
                 },
+                "provider_warnings": result.data["provider_warnings"],
+                "row_errors": result.data["row_errors"],
+                "stale_accounts": result.data["stale_accounts"],
+                "warning_integrations": warning_integrations(result.data),
+                "untyped_accounts": [
+                    {"id": str(acc.id), "name": acc.name}
+                    for acc in result.data["untyped_accounts"]
+                ],
             }
-            output_json(json_data)
+            if grouping:
+                json_data["groups"] = grouping["groups"]
+                json_data["totals"] = grouping["totals"]
+            output_json(with_preamble(json_data))
         else:
-            display_status(result.data)
+            print_preamble(console)
+            display_status(result.data, grouping)
@@ -2,22 +2,39 @@
 
 import asyncio
 import json
+from decimal import Decimal
 
 import typer
 from pydantic import BaseModel
 from rich.console import Console
 
+from treeline.app.account_service import short_id
+from treeline.app.preferences_service import format_currency
+from treeline.commands.json_output import decimal_json, model_json
+from treeline.config import (
+    DEFAULT_WRITE_LOCK_WAIT_SECONDS,
+    get_display_locale,
+    is_offline,
+    set_offline_mode,
+    set_write_lock_wait,
+)
+from treeline.preamble import print_preamble, with_preamble
 from treeline.theme import get_theme
 from treeline.utils import get_log_file_path
 
 console = Console()
 theme = get_theme()
 
+# Exit code when some integrations synced and others failed
+EXIT_SYNC_PARTIAL = 6
+
 
 def json_serializer(obj):
     """Custom JSON serializer for Pydantic models and other objects."""
     if isinstance(obj, BaseModel):
-        return obj.model_dump(mode="json")
+        return model_json(obj)
+    if isinstance(obj, Decimal):
+        return decimal_json(obj)
     return str(obj)
 
 
@@ -26,6 +43,11 @@ def output_json(data: dict) -> None:
     print(json.dumps(data, indent=2, default=json_serializer))
 
 
+def output_json_line(data: dict) -> None:
+    """Output data as a single line of JSON, flushed so readers see it right away."""
+    print(json.dumps(data, default=json_serializer), flush=True)
+
+
 def display_error(error: str, show_log_hint: bool = True) -> None:
     """Display error message in consistent format."""
     console.print(f"[{theme.error}]Error: {error}[/{theme.error}]")
@@ -34,11 +56,31 @@ def display_error(error: str, show_log_hint: bool = True) -> None:
         console.print(f"[{theme.muted}]See {log_file} for details[/{theme.muted}]")
 
 
+def sync_exit_code(data: dict) -> int:
+    """0 when every integration synced, EXIT_SYNC_PARTIAL when only some did, 1 when none did."""
+    return {"ok": 0, "partial": EXIT_SYNC_PARTIAL}.get(data["overall_status"], 1)
+
+
+def describe_balance_change(change: dict, locale: str | None = None) -> str:
+    """One-line explanation of an unexplained balance change."""
+
+    def money(amount) -> str:
+        return format_currency(amount, change["currency"], locale=locale)
+
+    moved = f"{change['account_name']}: balance moved {money(change['balance_change'])}"
+    if abs(change["transactions_total"]) < abs(change["balance_change"]):
+        return f"{moved} but only {money(change['transactions_total'])} of transactions were found"
+    return f"{moved} but transactions total {money(change['transactions_total'])}"
+
+
 def display_sync_result(data: dict, dry_run: bool = False) -> None:
     """Display sync results using Rich formatting."""
     header = "Synchronizing Financial Data (DRY RUN)" if dry_run else "Synchronizing Financial Data"
     console.print(f"\n[{theme.ui_header}]{header}[/{theme.ui_header}]\n")
 
+    if not data["results"] and is_offline():
+        console.print(f"[{theme.muted}]Offline mode: no network integrations to sync[/{theme.muted}]")
+
     for sync_result in data["results"]:
         integration_name = sync_result["integration"]
         console.print(f"[{theme.emphasis}]Syncing {integration_name}...[/{theme.emphasis}]")
@@ -49,6 +91,20 @@ def display_sync_result(data: dict, dry_run: bool = False) -> None:
             console.print(f"[{theme.muted}]    See {log_file} for details[/{theme.muted}]")
             continue
 
+        if sync_result.get("skipped") == "offline":
+            console.print(f"[{theme.muted}]  Skipped (offline mode)[/{theme.muted}]")
+            continue
+
+        if sync_result.get("skipped") == "demo_mode":
+            console.print(
+                f"[{theme.muted}]  Skipped (demo mode only syncs demo data; 'tl demo off' to sync it)[/{theme.muted}]"
+            )
+            continue
+
+        if sync_result.get("skipped") == "disabled":
+            console.print(f"[{theme.muted}]  Skipped ({sync_result['reason']})[/{theme.muted}]")
+            continue
+
         console.print(
             f"[{theme.success}]  ✓[/{theme.success}] Synced {sync_result['accounts_synced']} account(s)"
         )
@@ -73,6 +129,11 @@ def display_sync_result(data: dict, dry_run: bool = False) -> None:
             console.print(f"[{theme.muted}]    Discovered: {discovered}[/{theme.muted}]")
             console.print(f"[{theme.muted}]    New: {new}[/{theme.muted}]")
             console.print(f"[{theme.muted}]    Skipped: {skipped} (already exists)[/{theme.muted}]")
+            unmapped = tx_stats.get("unmapped", 0)
+            if unmapped:
+                console.print(
+                    f"[{theme.warning}]    Unmapped: {unmapped} (no matching account)[/{theme.warning}]"
+                )
         else:
             console.print(
                 f"[{theme.success}]  ✓[/{theme.success}] Synced {sync_result['transactions_synced']} transaction(s)"
@@ -92,7 +153,90 @@ def display_sync_result(data: dict, dry_run: bool = False) -> None:
                 f"[{theme.muted}]    Visit https://beta-bridge.simplefin.org/ to fix connection issues[/{theme.muted}]"
             )
 
-    if dry_run:
+        balance_changes = sync_result.get("unexplained_balance_changes", [])
+        if balance_changes:
+            locale = get_display_locale()
+            console.print(f"\n[{theme.warning}]  ⚠ Balance changes not explained by transactions:[/{theme.warning}]")
+            for change in balance_changes:
+                console.print(f"[{theme.warning}]    • {describe_balance_change(change, locale)}[/{theme.warning}]")
+            console.print(
+                f"[{theme.muted}]    Transactions may be missing or still pending at the bank[/{theme.muted}]"
+            )
+
+    month_end = data.get("month_end_snapshots", {})
+    if month_end.get("created"):
+        console.print(
+            f"\n[{theme.success}]✓[/{theme.success}] Added {month_end['created']} month-end "
+            f"balance snapshot(s) for {month_end['date']}"
+        )
+    elif month_end.get("error"):
+        console.print(
+            f"\n[{theme.warning}]⚠ Month-end balance snapshots were not saved: "
+            f"{month_end['error']}[/{theme.warning}]"
+        )
+
+    duplicates = data.get("cross_provider_duplicates", [])
+    if duplicates:
+        console.print(
+            f"\n[{theme.warning}]⚠ These accounts look like the same account synced by two "
+            f"integrations:[/{theme.warning}]"
+        )
+        for entry in duplicates:
+            keep, duplicate = entry["keep"], entry["duplicate"]
+            console.print(
+                f"[{theme.warning}]  • {keep['name']} ({', '.join(keep['integrations'])}) and "
+                f"{duplicate['name']} ({', '.join(duplicate['integrations'])}): "
+                f"{entry['shared_transactions']} transactions in common[/{theme.warning}]"
+            )
+            console.print(
+                f"[{theme.muted}]    To stop syncing the newer one: "
+                f"{entry['suggested_command']}[/{theme.muted}]"
+            )
+
+    untyped = data.get("new_accounts_without_type", [])
+    if untyped:
+        console.print(f"\n[{theme.warning}]⚠ New accounts need an account type:[/{theme.warning}]")
+        for account in untyped:
+            console.print(
+                f"[{theme.warning}]  • {account.name} ({short_id(account.id)})[/{theme.warning}]"
+            )
+        console.print(
+            f"[{theme.muted}]    Set one with: tl accounts set-type <id> <type>[/{theme.muted}]"
+        )
+
+    candidates_added = data.get("duplicate_candidates_added", 0)
+    if candidates_added:
+        console.print(
+            f"\n[{theme.warning}]⚠ {candidates_added} possible duplicate(s) to review: "
+            f"tl dedupe review[/{theme.warning}]"
+        )
+
+    budget_alerts = data.get("budget_alerts", [])
+    if budget_alerts:
+        console.print(f"\n[{theme.warning}]⚠ Over budget this month:[/{theme.warning}]")
+        for alert in budget_alerts:
+            console.print(f"[{theme.warning}]  • {alert['message']}[/{theme.warning}]")
+
+    planned_satisfied = data.get("planned_satisfied", [])
+    if planned_satisfied:
+        console.print(f"\n[{theme.success}]✓ Planned transactions that arrived:[/{theme.success}]")
+        for entry in planned_satisfied:
+            next_due = f"next due {entry['next_due']}" if entry["next_due"] else "done"
+            console.print(f"  • {entry['description']} (due {entry['due']}, {next_due})")
+
+    # Repeated last so a failure isn't lost in the middle of the output
+    failed = [entry["integration"] for entry in data["results"] if entry["status"] == "failed"]
+    if data["overall_status"] == "failed":
+        console.print(
+            f"\n[{theme.error}]✗ Sync failed: no integration synced "
+            f"({', '.join(failed)})[/{theme.error}]\n"
+        )
+    elif data["overall_status"] == "partial":
+        console.print(
+            f"\n[{theme.warning}]⚠ Sync finished with errors: {len(failed)} of "
+            f"{len(data['results'])} integration(s) failed ({', '.join(failed)})[/{theme.warning}]\n"
+        )
+    elif dry_run:
         console.print(
             f"\n[{theme.warning}]⚠[/{theme.warning}] Dry run completed - no changes were made\n"
         )
@@ -108,9 +252,26 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
     @app.command(name="sync")
     def sync_command(
         json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+        json_stream: bool = typer.Option(
+            False,
+            "--json-stream",
+            help="Output progress events as JSON lines as they happen, ending with the result",
+        ),
         dry_run: bool = typer.Option(
             False, "--dry-run", help="Show what would be synced without making changes"
         ),
+        offline: bool = typer.Option(
+            False, "--offline", help="Skip network integrations instead of contacting them"
+        ),
+        include_archived: bool = typer.Option(
+            False, "--include-archived", help="Also sync accounts archived with tl accounts archive"
+        ),
+        wait_lock: float = typer.Option(
+            DEFAULT_WRITE_LOCK_WAIT_SECONDS,
+            "--wait-lock",
+            min=0,
+            help="Seconds to wait for another treeline import or sync to finish writing",
+        ),
     ) -> None:
         """Synchronize data from connected integrations.
 
@@ -120,19 +281,60 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
 
           # Preview without saving
           tl sync --dry-run
+
+          # Only local providers; network integrations are skipped
+          tl sync --offline
+
+          # Update archived accounts too
+          tl sync --include-archived
+
+          # One JSON event per line: integration_start, accounts_fetched,
+          # transactions_inserted, then result (or error)
+          tl sync --json-stream
+
+        Writes wait for a running tl import or sync to finish (up to
+        --wait-lock seconds); an integration that can't get its turn fails.
+
+        Exits 0 when every integration synced, 6 when some failed and
+        others synced, and 1 when none synced. With --json, overall_status
+        is "ok", "partial" or "failed" to match, and each result has a status.
         """
+        if offline:
+            set_offline_mode()
+        set_write_lock_wait(wait_lock)
+
         ensure_initialized()
 
         container = get_container()
         sync_service = container.sync_service()
 
+        if json_stream:
+            result = asyncio.run(
+                sync_service.sync_all_integrations(
+                    dry_run=dry_run, on_event=output_json_line, include_archived=include_archived
+                )
+            )
+            if not result.success:
+                output_json_line({"event": "error", "error": result.error})
+                raise typer.Exit(1)
+            output_json_line({"event": "result", **with_preamble(result.data)})
+            raise typer.Exit(sync_exit_code(result.data))
+
         # Sync all integrations with visual feedback
         if not json_output:
             status_msg = "Syncing integrations (dry-run)..." if dry_run else "Syncing integrations..."
             with console.status(f"[{theme.status_loading}]{status_msg}"):
-                result = asyncio.run(sync_service.sync_all_integrations(dry_run=dry_run))
+                result = asyncio.run(
+                    sync_service.sync_all_integrations(
+                        dry_run=dry_run, include_archived=include_archived
+                    )
+                )
         else:
-            result = asyncio.run(sync_service.sync_all_integrations(dry_run=dry_run))
+            result = asyncio.run(
+                sync_service.sync_all_integrations(
+                    dry_run=dry_run, include_archived=include_archived
+                )
+            )
 
         if not result.success:
             display_error(result.error)
@@ -143,6 +345,8 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
             raise typer.Exit(1)
 
         if json_output:
-            output_json(result.data)
+            output_json(with_preamble(result.data))
         else:
+            print_preamble(console)
             display_sync_result(result.data, dry_run=dry_run)
+        raise typer.Exit(sync_exit_code(result.data))
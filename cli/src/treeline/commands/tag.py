@@ -3,12 +3,18 @@
 import asyncio
 import json
 import sys
+from typing import List
 from uuid import UUID
 
 import typer
 from pydantic import BaseModel
 from rich.console import Console
+from rich.table import Table
 
+from treeline.category_map import load_category_map, save_category_map
+from treeline.commands.json_output import model_json
+from treeline.config import get_category_map_path
+from treeline.domain import normalize_tag, normalize_tags
 from treeline.theme import get_theme
 from treeline.utils import get_log_file_path
 
@@ -19,7 +25,7 @@ theme = get_theme()
 def json_serializer(obj):
     """Custom JSON serializer for Pydantic models and other objects."""
     if isinstance(obj, BaseModel):
-        return obj.model_dump(mode="json")
+        return model_json(obj)
     return str(obj)
 
 
@@ -36,12 +42,135 @@ def display_error(error: str, show_log_hint: bool = True) -> None:
         console.print(f"[{theme.muted}]See {log_file} for details[/{theme.muted}]")
 
 
+def display_normalize_result(data: dict) -> None:
+    """Display the outcome of `tl tag normalize`."""
+    if not data["transactions"]:
+        console.print(f"\n[{theme.success}]✓ All tags are already normalized[/{theme.success}]\n")
+        return
+
+    verb = "Would rewrite" if data["dry_run"] else "Rewrote"
+    console.print(f"\n[{theme.ui_header}]{verb} tags on {data['transactions']} transaction(s)[/{theme.ui_header}]\n")
+    for entry in data["tags"]:
+        variants = ", ".join(repr(variant) for variant in entry["variants"])
+        console.print(
+            f"  {variants} → [{theme.emphasis}]{entry['tag']}[/{theme.emphasis}] "
+            f"[{theme.muted}]({entry['transactions']} transaction(s))[/{theme.muted}]"
+        )
+
+    if data["dry_run"]:
+        console.print(f"\n[{theme.muted}]Run 'tl tag normalize --apply' to rewrite them[/{theme.muted}]\n")
+    else:
+        console.print()
+
+
+TAG_MAP_USAGE = "Usage: tl tag map list | set <provider-category> <tag> | unset <provider-category> | suggest"
+
+
+def run_tag_map(args: List[str], retroactive: bool, json_output: bool, get_container: callable) -> None:
+    """Handle `tl tag map ...`: view and edit the provider category to tag map."""
+    action, rest = (args[0], args[1:]) if args else ("list", [])
+    expected = {"list": 0, "set": 2, "unset": 1, "suggest": 0}
+    if action not in expected or len(rest) != expected[action]:
+        display_error(TAG_MAP_USAGE, show_log_hint=False)
+        raise typer.Exit(1)
+    if retroactive and action != "set":
+        display_error("--retroactive is only used with 'tl tag map set'", show_log_hint=False)
+        raise typer.Exit(1)
+
+    path = get_category_map_path()
+    try:
+        category_map = load_category_map(path)
+    except (OSError, ValueError) as e:
+        display_error(str(e), show_log_hint=False)
+        raise typer.Exit(1)
+
+    if action == "list":
+        if json_output:
+            output_json(category_map.to_json())
+            return
+        table = Table(title="Provider category → tag")
+        table.add_column("Provider category")
+        table.add_column("Tag", style=theme.emphasis)
+        for category, tag in sorted(category_map.mappings.items()):
+            table.add_row(category, tag)
+        console.print()
+        console.print(table)
+        console.print(f"[{theme.muted}]{path}[/{theme.muted}]\n")
+        return
+
+    if action == "suggest":
+        result = asyncio.run(
+            get_container().tagging_service().count_tags(sorted(category_map.unmapped))
+        )
+        if not result.success:
+            display_error(result.error)
+            raise typer.Exit(1)
+        suggestions = [
+            {"category": category, "transactions": count}
+            for category, count in sorted(result.data.items(), key=lambda item: (-item[1], item[0]))
+        ]
+        if json_output:
+            output_json({"suggestions": suggestions})
+            return
+        if not suggestions:
+            console.print(f"\n[{theme.success}]✓ No unmapped provider categories[/{theme.success}]\n")
+            return
+        console.print(f"\n[{theme.ui_header}]Unmapped provider categories[/{theme.ui_header}]\n")
+        for entry in suggestions:
+            console.print(
+                f"  {entry['category']} [{theme.muted}]({entry['transactions']} transaction(s))[/{theme.muted}]"
+            )
+        console.print(f"\n[{theme.muted}]Map one with 'tl tag map set <provider-category> <tag>'[/{theme.muted}]\n")
+        return
+
+    category = normalize_tag(rest[0])
+    if action == "unset":
+        previous = category_map.remove_mapping(category)
+        if previous is None:
+            display_error(f"No mapping for provider category: {category}", show_log_hint=False)
+            raise typer.Exit(1)
+        save_category_map(category_map, path)
+        if json_output:
+            output_json({"category": category, "previous": previous})
+        else:
+            console.print(f"\n[{theme.success}]✓ Removed mapping {category} → {previous}[/{theme.success}]\n")
+        return
+
+    try:
+        previous = category_map.set_mapping(category, rest[1])
+    except ValueError as e:
+        display_error(str(e), show_log_hint=False)
+        raise typer.Exit(1)
+    tag = category_map.mappings[category]
+    save_category_map(category_map, path)
+
+    # Synced transactions tagged with the raw category, before it was mapped
+    retagged = None
+    if retroactive:
+        result = asyncio.run(get_container().tagging_service().replace_tag(category, tag))
+        if not result.success:
+            display_error(result.error)
+            raise typer.Exit(1)
+        retagged = result.data
+
+    if json_output:
+        output_json({"category": category, "tag": tag, "previous": previous, "retagged": retagged})
+        return
+    console.print(f"\n[{theme.success}]✓ Mapped {category} → {tag}[/{theme.success}]")
+    if retagged is not None:
+        console.print(f"[{theme.muted}]Retagged {retagged} existing transaction(s)[/{theme.muted}]")
+    console.print()
+
+
 def register(app: typer.Typer, get_container: callable, ensure_initialized: callable) -> None:
     """Register the tag command with the app."""
 
     @app.command(name="tag")
     def tag_command(
         tags: str = typer.Argument(..., help="Comma-separated tags to apply"),
+        map_args: List[str] = typer.Argument(
+            None, help="With 'map': list, set <provider-category> <tag>, unset <provider-category> or suggest"
+        ),
         ids: str = typer.Option(
             None, "--ids", help="Comma-separated transaction IDs (or read from stdin)"
         ),
@@ -49,10 +178,23 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
             False, "--replace", help="Replace all tags (default: append/merge)"
         ),
         json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+        dry_run: bool = typer.Option(
+            False, "--dry-run", help="With 'normalize': show what would change"
+        ),
+        apply: bool = typer.Option(False, "--apply", help="With 'normalize': rewrite stored tags"),
+        retroactive: bool = typer.Option(
+            False,
+            "--retroactive",
+            help="With 'map set': also retag existing transactions tagged with the provider category",
+        ),
     ) -> None:
         """Apply tags to specific transactions (scriptable).
 
         By default, tags are merged with existing tags. Use --replace to replace all tags.
+        Tags are stored trimmed and lowercase, without a leading '#'.
+
+        'tl tag normalize --dry-run' / '--apply' cleans up tags stored before
+        that, merging variants like "Dining " and "DINING" into "dining".
 
         Examples:
           # Append tags to transactions (default)
@@ -67,9 +209,47 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
 
           # Pipe comma-separated IDs
           echo "abc123,def456" | tl tag groceries
+
+          # Preview, then merge differently-cased variants of existing tags
+          tl tag normalize --dry-run
+          tl tag normalize --apply
+
+          # Map provider categories (e.g. from SimpleFIN) to your own tags
+          tl tag map list
+          tl tag map suggest
+          tl tag map set FOOD_AND_DRINK_COFFEE coffee --retroactive
+          tl tag map unset FOOD_AND_DRINK_COFFEE
         """
         ensure_initialized()
 
+        if tags == "map":
+            run_tag_map(map_args or [], retroactive, json_output, get_container)
+            return
+        if map_args:
+            display_error(f"Unexpected arguments: {' '.join(map_args)}", show_log_hint=False)
+            raise typer.Exit(1)
+        if retroactive:
+            display_error("--retroactive is only used with 'tl tag map set'", show_log_hint=False)
+            raise typer.Exit(1)
+
+        if dry_run or apply:
+            if tags != "normalize":
+                display_error("--dry-run and --apply are only used with 'tl tag normalize'", show_log_hint=False)
+                raise typer.Exit(1)
+            if dry_run and apply:
+                display_error("Use either --dry-run or --apply, not both", show_log_hint=False)
+                raise typer.Exit(1)
+
+            result = asyncio.run(get_container().tagging_service().normalize_stored_tags(dry_run=dry_run))
+            if not result.success:
+                display_error(result.error)
+                raise typer.Exit(1)
+            if json_output:
+                output_json(result.data)
+            else:
+                display_normalize_result(result.data)
+            return
+
         # Parse IDs from --ids option or stdin
         if ids:
             transaction_ids = [tid.strip() for tid in ids.split(",") if tid.strip()]
@@ -91,7 +271,7 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
             raise typer.Exit(1)
 
         # Parse tags
-        tag_list = [tag.strip() for tag in tags.split(",") if tag.strip()]
+        tag_list = normalize_tags(tags.split(","))
         if not tag_list:
             display_error("No tags provided")
             raise typer.Exit(1)
@@ -128,8 +308,7 @@ def register(app: typer.Typer, get_container: callable, ensure_initialized: call
                     and tags_result.data.get("rows")
                 ):
                     current_tags = tags_result.data["rows"][0][0] or []
-                    merged_tags = list(set(current_tags) | set(tag_list))
-                    final_tags = merged_tags
+                    final_tags = normalize_tags([*current_tags, *tag_list])
 
             result = asyncio.run(
                 tagging_service.update_transaction_tags(transaction_id, final_tags)
@@ -0,0 +1,723 @@
+"""Transactions command - inspect individual transactions."""
+
+import asyncio
+import json
+from datetime import date
+from decimal import Decimal
+from typing import List
+from uuid import UUID
+
+import typer
+from pydantic import BaseModel
+from rich.console import Console
+from rich.markup import escape
+from rich.table import Table
+
+from treeline.app.account_service import AccountDirectory, short_id
+from treeline.app.preferences_service import DEFAULT_CURRENCY, format_currency, format_date
+from treeline.app.transaction_service import nest_split_parts
+from treeline.commands.json_output import decimal_json, model_json
+from treeline.config import get_display_locale
+from treeline.domain import TransactionStatus
+from treeline.tables import MAX_COL_WIDTH_HELP, WIDE_HELP, add_column, print_table, resolve_layout
+from treeline.theme import get_theme
+from treeline.utils import get_log_file_path
+
+console = Console()
+theme = get_theme()
+
+# Create transactions subcommand group
+transactions_app = typer.Typer(help="Transaction inspection and status commands")
+
+DEFAULT_LIST_LIMIT = 50
+
+STATUS_HELP = "Transaction status: pending, cleared or reconciled"
+
+
+def json_serializer(obj):
+    """Custom JSON serializer for Pydantic models and other objects."""
+    if isinstance(obj, BaseModel):
+        return model_json(obj)
+    if isinstance(obj, Decimal):
+        return decimal_json(obj)
+    return str(obj)
+
+
+def output_json(data: dict) -> None:
+    """Output data as JSON."""
+    print(json.dumps(data, indent=2, default=json_serializer))
+
+
+def display_error(error: str, show_log_hint: bool = True) -> None:
+    """Display error message in consistent format."""
+    console.print(f"[{theme.error}]Error: {error}[/{theme.error}]")
+    if show_log_hint:
+        log_file = get_log_file_path()
+        console.print(f"[{theme.muted}]See {log_file} for details[/{theme.muted}]")
+
+
+def _fail(error: str, json_output: bool, context: dict | None = None) -> None:
+    """Report an error in the requested format and exit."""
+    matches = (context or {}).get("matches", [])
+    if json_output:
+        error_data = {"success": False, "error": error}
+        if (context or {}).get("kind"):
+            error_data["kind"] = context["kind"]
+        if matches:
+            error_data["matches"] = matches
+        output_json(error_data)
+    else:
+        display_error(error, show_log_hint=False)
+        for match in matches:
+            console.print(f"  {match}")
+    raise typer.Exit(1)
+
+
+def _parse_status(value: str, json_output: bool) -> TransactionStatus:
+    try:
+        return TransactionStatus(value.lower())
+    except ValueError:
+        _fail(f"Invalid status: {value} (expected pending, cleared or reconciled)", json_output)
+
+
+def _set_status(
+    get_container: callable, transaction_ids: List[str], status: TransactionStatus, json_output: bool
+) -> None:
+    transaction_service = get_container().transaction_service()
+    result = asyncio.run(transaction_service.set_status(transaction_ids, status))
+    if not result.success:
+        _fail(result.error, json_output, result.context)
+
+    if json_output:
+        output_json({"success": True, "status": status.value, "transaction_ids": result.data})
+        return
+
+    console.print(
+        f"\n[{theme.success}]✓ Marked {len(result.data)} transaction(s) {status.value}[/{theme.success}]\n"
+    )
+
+
+def _format_amount(amount, currency: str, locale: str) -> str:
+    style = theme.negative_amount if amount < 0 else theme.positive_amount
+    return f"[{style}]{format_currency(amount, currency, locale=locale)}[/{style}]"
+
+
+def _summary_line(tx, currency: str, locale: str) -> str:
+    """One-line summary of a related transaction."""
+    deleted = " (deleted)" if tx.deleted_at else ""
+    return (
+        f"{tx.id}  {format_date(tx.transaction_date, locale)}  "
+        f"{_format_amount(tx.amount, currency, locale)}  {tx.description or ''}{deleted}"
+    )
+
+
+def _edit_changes(before, after, currency: str, locale: str) -> List[tuple]:
+    """(label, before, after) for each field an edit changed, formatted for display."""
+    fields = (
+        ("Amount", lambda tx: _format_amount(tx.amount, currency, locale)),
+        ("Description", lambda tx: escape(tx.description or "")),
+        ("Date", lambda tx: format_date(tx.transaction_date, locale)),
+        ("Posted date", lambda tx: format_date(tx.posted_date, locale)),
+        ("Fingerprint", lambda tx: tx.external_ids.get("fingerprint", "")),
+    )
+    return [
+        (label, show(before), show(after))
+        for label, show in fields
+        if show(before) != show(after)
+    ]
+
+
+def _format_size(size_bytes: int) -> str:
+    if size_bytes < 1024:
+        return f"{size_bytes} B"
+    if size_bytes < 1024 * 1024:
+        return f"{size_bytes / 1024:.1f} KB"
+    return f"{size_bytes / (1024 * 1024):.1f} MB"
+
+
+def _attachment_json(attachment, attachment_service) -> dict:
+    return {**attachment.model_dump(mode="json"), "path": str(attachment_service.path_for(attachment))}
+
+
+def register(app: typer.Typer, get_container: callable, ensure_initialized: callable) -> None:
+    """Register the transactions commands with the app."""
+    app.add_typer(transactions_app, name="transactions")
+
+    @transactions_app.command(name="show")
+    def transactions_show_command(
+        transaction_id: str = typer.Argument(..., help="Transaction ID or unique ID prefix"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Show everything known about a single transaction.
+
+        Includes the account, external IDs, tags, split parent/children and
+        any other transactions sharing the same fingerprint.
+
+        Examples:
+          tl transactions show 3f2a9c1e
+          tl transactions show 3f2a9c1e-0b6d-4d8e-9a51-2c7e4f1b8a90 --json
+        """
+        ensure_initialized()
+
+        transaction_service = get_container().transaction_service()
+        result = asyncio.run(transaction_service.get_transaction_details(transaction_id))
+
+        if not result.success:
+            matches = (result.context or {}).get("matches", [])
+            if json_output:
+                error_data = {"success": False, "error": result.error}
+                if matches:
+                    error_data["matches"] = matches
+                output_json(error_data)
+            else:
+                display_error(result.error, show_log_hint=False)
+                for match in matches:
+                    console.print(f"  {match}")
+            raise typer.Exit(1)
+
+        details = result.data
+        if json_output:
+            output_json({"success": True, **details})
+            return
+
+        tx = details["transaction"]
+        account = details["account"]
+        currency = account.currency if account else DEFAULT_CURRENCY
+        locale = get_display_locale()
+
+        console.print(f"\n[{theme.ui_header}]Transaction {tx.id}[/{theme.ui_header}]\n")
+        console.print(
+            f"  Date:         {format_date(tx.transaction_date, locale)}"
+            f" (posted {format_date(tx.posted_date, locale)})"
+        )
+        console.print(f"  Amount:       {_format_amount(tx.amount, currency, locale)}")
+        console.print(f"  Description:  {tx.description or ''}")
+        console.print(f"  Tags:         {', '.join(tx.tags) if tx.tags else '-'}")
+        console.print(f"  Status:       {tx.status.value}")
+        if account:
+            account_label = account.nickname or account.name
+            if account.institution_name:
+                account_label += f" ({account.institution_name})"
+            console.print(f"  Account:      {account_label}")
+        else:
+            console.print(f"  Account:      [{theme.warning}]{short_id(tx.account_id)} (missing)[/{theme.warning}]")
+        console.print(f"  Created:      {tx.created_at}")
+        console.print(f"  Updated:      {tx.updated_at}")
+        if tx.deleted_at:
+            console.print(f"  [{theme.warning}]Deleted:      {tx.deleted_at}[/{theme.warning}]")
+
+        external_ids = {k: v for k, v in tx.external_ids.items() if k != "csv_source"}
+        if external_ids:
+            console.print(f"\n[{theme.ui_header}]External IDs[/{theme.ui_header}]")
+            for key, value in sorted(external_ids.items()):
+                console.print(f"  {key}: {value}")
+
+        csv_source = details["csv_source"]
+        if csv_source:
+            console.print(f"\n[{theme.ui_header}]CSV source[/{theme.ui_header}]")
+            if csv_source.get("import_id"):
+                console.print(f"  Batch:        {csv_source['import_id']}")
+            if csv_source.get("file_sha256"):
+                console.print(f"  File SHA-256: {csv_source['file_sha256']}")
+            if csv_source.get("line"):
+                console.print(f"  Line:         {csv_source['line']}")
+            console.print(f"  Row:          {escape(csv_source.get('row', ''))}")
+
+        if details["parent"]:
+            console.print(f"\n[{theme.ui_header}]Split from[/{theme.ui_header}]")
+            console.print(f"  {_summary_line(details['parent'], currency, locale)}")
+        elif tx.parent_transaction_id:
+            console.print(f"\n[{theme.ui_header}]Split from[/{theme.ui_header}]")
+            console.print(f"  [{theme.warning}]{tx.parent_transaction_id} (missing)[/{theme.warning}]")
+
+        if details["children"]:
+            console.print(f"\n[{theme.ui_header}]Split into[/{theme.ui_header}]")
+            for child in details["children"]:
+                console.print(f"  {_summary_line(child, currency, locale)}")
+
+        attachments = details["attachments"]
+        if attachments:
+            attachment_service = get_container().attachment_service()
+            console.print(f"\n[{theme.ui_header}]Attachments[/{theme.ui_header}]")
+            for attachment in attachments:
+                path = attachment_service.path_for(attachment)
+                missing = "" if path.exists() else f"  [{theme.warning}](file missing)[/{theme.warning}]"
+                console.print(
+                    f"  {attachment.id}  {escape(attachment.filename)}  "
+                    f"{_format_size(attachment.size_bytes)}{missing}"
+                )
+                console.print(f"    [{theme.muted}]{path}[/{theme.muted}]")
+            if tx.deleted_at:
+                console.print(
+                    f"  [{theme.warning}]Kept although the transaction is deleted; "
+                    f"remove with: tl maintenance prune-attachments[/{theme.warning}]"
+                )
+
+        if details["fingerprint_matches"]:
+            console.print(
+                f"\n[{theme.ui_header}]Other transactions with the same fingerprint[/{theme.ui_header}]"
+            )
+            for match in details["fingerprint_matches"]:
+                console.print(f"  {_summary_line(match, currency, locale)}")
+
+        console.print()
+
+    @transactions_app.command(name="find-by-external-id")
+    def transactions_find_by_external_id_command(
+        provider: str = typer.Option(
+            ..., "--provider", help="External ID key (simplefin, lunchflow, csv, fingerprint...) or 'any'"
+        ),
+        external_id: str = typer.Option(..., "--id", help="The provider's ID for the transaction"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Find a transaction by a provider's ID and print its Treeline ID.
+
+        With --provider any every external ID key is searched and the key
+        that matched is shown. Deleted transactions are included.
+
+        Examples:
+          tl transactions find-by-external-id --provider simplefin --id TRN-4f1c
+          tl transactions find-by-external-id --provider any --id TRN-4f1c --json
+        """
+        ensure_initialized()
+
+        container = get_container()
+        result = asyncio.run(
+            container.transaction_service().find_by_external_id(provider, external_id)
+        )
+        if not result.success:
+            _fail(result.error, json_output, result.context)
+
+        if json_output:
+            output_json(
+                {
+                    "success": True,
+                    "provider": provider,
+                    "id": external_id,
+                    "matches": [
+                        {"id": str(tx.id), "key": key, "transaction": tx} for tx, key in result.data
+                    ],
+                }
+            )
+            return
+
+        accounts_result = asyncio.run(container.account_service().get_accounts())
+        accounts = {account.id: account for account in (accounts_result.data or [])}
+        directory = AccountDirectory(accounts_result.data or [])
+        locale = get_display_locale()
+        console.print()
+        for tx, key in result.data:
+            account = accounts.get(tx.account_id)
+            currency = account.currency if account else DEFAULT_CURRENCY
+            deleted = f"  [{theme.warning}](deleted)[/{theme.warning}]" if tx.deleted_at else ""
+            console.print(f"[{theme.emphasis}]{tx.id}[/{theme.emphasis}]{deleted}")
+            console.print(
+                f"  {format_date(tx.transaction_date, locale)}  "
+                f"{_format_amount(tx.amount, currency, locale)}  {escape(tx.description or '')}"
+            )
+            console.print(
+                f"  [{theme.muted}]{directory.name_for(tx.account_id)} · "
+                f"{key}: {escape(tx.external_ids[key])}[/{theme.muted}]"
+            )
+        console.print()
+
+    @transactions_app.command(name="list")
+    def transactions_list_command(
+        account_id: str = typer.Option(None, "--account-id", help="Only this account"),
+        account: str = typer.Option(None, "--account", help="Only this account, by name or nickname"),
+        status: str = typer.Option(None, "--status", help=STATUS_HELP),
+        since: str = typer.Option(None, "--since", help="Start date (YYYY-MM-DD)"),
+        until: str = typer.Option(None, "--until", help="End date (YYYY-MM-DD)"),
+        search: str = typer.Option(
+            None, "--search", help="Only descriptions containing this text (case-insensitive)"
+        ),
+        limit: int = typer.Option(DEFAULT_LIST_LIMIT, "--limit", help="Maximum rows to show"),
+        flat: bool = typer.Option(
+            False, "--flat", help="Don't group split parts under the transaction they came from"
+        ),
+        max_col_width: int = typer.Option(None, "--max-col-width", help=MAX_COL_WIDTH_HELP),
+        wide: bool = typer.Option(False, "--wide", help=WIDE_HELP),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """List transactions, newest first.
+
+        Split parts are shown indented under the transaction they were split
+        from, which is listed for reference but not counted.
+
+        Examples:
+          tl transactions list --status pending
+          tl transactions list --flat
+          tl transactions list --account-id ACCOUNT-ID --since 2025-01-01 --json
+          tl transactions list --search netflix
+          tl transactions list --account checking --status pending
+          tl transactions list --wide | less -S
+        """
+        ensure_initialized()
+
+        try:
+            layout = resolve_layout(console, max_col_width, wide)
+        except ValueError as e:
+            _fail(str(e), json_output)
+
+        if account and account_id:
+            _fail("Use either --account or --account-id, not both", json_output)
+
+        parsed_account_id = None
+        if account_id:
+            try:
+                parsed_account_id = UUID(account_id)
+            except ValueError:
+                _fail(f"Invalid account ID: {account_id}", json_output)
+        elif account:
+            resolve_result = asyncio.run(get_container().account_service().resolve_account(account))
+            if not resolve_result.success:
+                _fail(resolve_result.error, json_output)
+            parsed_account_id = resolve_result.data.id
+
+        dates = {}
+        for flag, value in (("--since", since), ("--until", until)):
+            try:
+                dates[flag] = date.fromisoformat(value) if value else None
+            except ValueError:
+                _fail(f"Invalid {flag} date: {value} (expected YYYY-MM-DD)", json_output)
+
+        container = get_container()
+        result = asyncio.run(
+            container.transaction_service().list_transactions(
+                account_id=parsed_account_id,
+                status=_parse_status(status, json_output) if status else None,
+                start_date=dates["--since"],
+                end_date=dates["--until"],
+                search=search,
+                limit=limit,
+            )
+        )
+        if not result.success:
+            _fail(result.error, json_output)
+
+        transactions = result.data
+        if json_output:
+            output_json({"success": True, "transactions": transactions})
+            return
+
+        if not transactions:
+            console.print(f"\n[{theme.muted}]No transactions found[/{theme.muted}]\n")
+            return
+
+        if flat:
+            rows = [(tx, 0) for tx in transactions]
+        else:
+            parents_result = asyncio.run(
+                container.transaction_service().get_split_parents(transactions)
+            )
+            rows = nest_split_parts(transactions, parents_result.data or {})
+
+        accounts_result = asyncio.run(container.account_service().get_accounts())
+        accounts = {account.id: account for account in (accounts_result.data or [])}
+        directory = AccountDirectory(accounts_result.data or [])
+        locale = get_display_locale()
+
+        table = Table(show_header=True, header_style=theme.ui_header)
+        add_column(table, layout, "ID", style=theme.muted)
+        add_column(table, layout, "Date")
+        add_column(table, layout, "Account")
+        add_column(table, layout, "Description")
+        add_column(table, layout, "Amount", justify="right")
+        add_column(table, layout, "Status")
+
+        split_ids = {tx.parent_transaction_id for tx, depth in rows if depth}
+        for tx, depth in rows:
+            account = accounts.get(tx.account_id)
+            currency = account.currency if account else DEFAULT_CURRENCY
+            if tx.id in split_ids:
+                # The parts are the transaction; the parent is shown for context only
+                table.add_row(
+                    str(tx.id)[:8],
+                    format_date(tx.transaction_date, locale),
+                    directory.name_for(tx.account_id),
+                    f"[{theme.muted}]{escape(tx.description or '')}[/{theme.muted}]",
+                    f"[{theme.muted}]{format_currency(tx.amount, currency, locale=locale)}[/{theme.muted}]",
+                    f"[{theme.muted}]split[/{theme.muted}]",
+                )
+                continue
+            status_style = theme.warning if tx.status == TransactionStatus.PENDING else theme.muted
+            description = escape(tx.description or "")
+            table.add_row(
+                str(tx.id)[:8],
+                format_date(tx.transaction_date, locale),
+                directory.name_for(tx.account_id),
+                f"  ⌙ {description}" if depth else description,
+                _format_amount(tx.amount, currency, locale),
+                f"[{status_style}]{tx.status.value}[/{status_style}]",
+            )
+
+        console.print()
+        print_table(console, table, layout)
+        console.print()
+
+    @transactions_app.command(name="clear")
+    def transactions_clear_command(
+        transaction_ids: List[str] = typer.Argument(..., help="Transaction IDs or unique ID prefixes"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Mark transactions as cleared (e.g. once they appear on a statement).
+
+        Examples:
+          tl transactions clear 3f2a9c1e 7b01d4aa
+        """
+        ensure_initialized()
+        _set_status(get_container, transaction_ids, TransactionStatus.CLEARED, json_output)
+
+    @transactions_app.command(name="set-status")
+    def transactions_set_status_command(
+        status: str = typer.Argument(..., help=STATUS_HELP),
+        transaction_ids: List[str] = typer.Argument(..., help="Transaction IDs or unique ID prefixes"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Set the status of one or more transactions.
+
+        Examples:
+          tl transactions set-status pending 3f2a9c1e
+          tl transactions set-status reconciled 3f2a9c1e 7b01d4aa --json
+        """
+        ensure_initialized()
+        _set_status(
+            get_container, transaction_ids, _parse_status(status, json_output), json_output
+        )
+
+    @transactions_app.command(name="delete")
+    def transactions_delete_command(
+        transaction_ids: List[str] = typer.Argument(..., help="Transaction IDs or unique ID prefixes"),
+        restore: bool = typer.Option(False, "--restore", help="Undo an earlier delete"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Delete transactions, e.g. a bogus row from an import.
+
+        Deleted transactions no longer show up in lists, reports or status,
+        but are kept so re-importing the same file or re-syncing doesn't add
+        them back. Use --restore to bring them back.
+
+        Examples:
+          tl transactions delete 3f2a9c1e 7b01d4aa
+          tl transactions delete 3f2a9c1e --restore
+        """
+        ensure_initialized()
+
+        transaction_service = get_container().transaction_service()
+        result = asyncio.run(transaction_service.delete(transaction_ids, restore=restore))
+        if not result.success:
+            _fail(result.error, json_output, result.context)
+
+        if json_output:
+            output_json({"success": True, "restored": restore, "transaction_ids": result.data})
+            return
+
+        verb = "Restored" if restore else "Deleted"
+        console.print(f"\n[{theme.success}]✓ {verb} {len(result.data)} transaction(s)[/{theme.success}]")
+        if not restore and result.data:
+            console.print(
+                f"[{theme.muted}]Undo with: tl transactions delete "
+                f"{' '.join(str(tx_id)[:8] for tx_id in result.data)} --restore[/{theme.muted}]"
+            )
+        console.print()
+
+    @transactions_app.command(name="edit")
+    def transactions_edit_command(
+        transaction_id: str = typer.Argument(..., help="Transaction ID or unique ID prefix"),
+        amount: str = typer.Option(None, "--amount", help="New amount, e.g. -12.50"),
+        description: str = typer.Option(None, "--description", help="New description"),
+        transaction_date: str = typer.Option(None, "--date", help="New transaction date (YYYY-MM-DD)"),
+        posted_date: str = typer.Option(None, "--posted-date", help="New posted date (YYYY-MM-DD)"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Fix a transaction's amount, description or dates, e.g. after a bad import.
+
+        The fingerprint is recomputed from the new values, so re-importing
+        the corrected row is still recognised as a duplicate.
+
+        Examples:
+          tl transactions edit 3f2a9c1e --date 2025-03-02
+          tl transactions edit 3f2a9c1e --amount -12.50 --description "Corner cafe"
+        """
+        ensure_initialized()
+
+        parsed_amount = None
+        if amount is not None:
+            try:
+                parsed_amount = Decimal(amount)
+            except ArithmeticError:
+                parsed_amount = None
+            if parsed_amount is None or not parsed_amount.is_finite():
+                _fail(f"Invalid --amount: {amount} (expected a number like -12.50)", json_output)
+
+        dates = {}
+        for flag, value in (("--date", transaction_date), ("--posted-date", posted_date)):
+            try:
+                dates[flag] = date.fromisoformat(value) if value else None
+            except ValueError:
+                _fail(f"Invalid {flag}: {value} (expected YYYY-MM-DD)", json_output)
+
+        container = get_container()
+        result = asyncio.run(
+            container.transaction_service().edit(
+                transaction_id,
+                amount=parsed_amount,
+                description=description,
+                transaction_date=dates["--date"],
+                posted_date=dates["--posted-date"],
+            )
+        )
+        if not result.success:
+            _fail(result.error, json_output, result.context)
+
+        before, after = result.data
+        if json_output:
+            output_json({"success": True, "before": before, "after": after})
+            return
+
+        accounts_result = asyncio.run(container.account_service().get_accounts())
+        account = next(
+            (a for a in accounts_result.data or [] if a.id == after.account_id), None
+        )
+        currency = account.currency if account else DEFAULT_CURRENCY
+        changes = _edit_changes(before, after, currency, get_display_locale())
+
+        console.print(f"\n[{theme.success}]✓ Updated transaction {after.id}[/{theme.success}]\n")
+        if not changes:
+            console.print(f"[{theme.muted}]Nothing changed: the new values match the old ones[/{theme.muted}]\n")
+            return
+        for label, old_value, new_value in changes:
+            console.print(f"  {label + ':':<14}{old_value} → {new_value}")
+        console.print()
+
+    @transactions_app.command(name="attach")
+    def transactions_attach_command(
+        transaction_id: str = typer.Argument(..., help="Transaction ID or unique ID prefix"),
+        file_path: str = typer.Argument(..., help="File to attach, e.g. a PDF receipt"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Attach a file (e.g. a receipt) to a transaction.
+
+        The file is copied into ~/.treeline/attachments, so the original can
+        be moved or deleted afterwards. Size and file types are limited by
+        attachments.max_file_mb and attachments.allowed_extensions in
+        settings.json.
+
+        Examples:
+          tl transactions attach 3f2a9c1e ~/Downloads/receipt.pdf
+        """
+        ensure_initialized()
+
+        attachment_service = get_container().attachment_service()
+        result = asyncio.run(attachment_service.attach(transaction_id, file_path))
+        if not result.success:
+            _fail(result.error, json_output, result.context)
+
+        attachment = result.data
+        if json_output:
+            output_json({"success": True, "attachment": _attachment_json(attachment, attachment_service)})
+            return
+
+        console.print(
+            f"\n[{theme.success}]✓ Attached {escape(attachment.filename)} "
+            f"to transaction {short_id(attachment.transaction_id)}[/{theme.success}]"
+        )
+        console.print(f"[{theme.muted}]Attachment ID: {attachment.id}[/{theme.muted}]\n")
+
+    @transactions_app.command(name="detach")
+    def transactions_detach_command(
+        attachment_id: str = typer.Argument(..., help="Attachment ID (see tl transactions show)"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Remove an attachment. Its file is moved to ~/.treeline/attachments/.trash.
+
+        Examples:
+          tl transactions detach 9b1de0c4-6f0e-4c1a-8f57-1d2a3b4c5d6e
+        """
+        ensure_initialized()
+
+        result = asyncio.run(get_container().attachment_service().detach(attachment_id))
+        if not result.success:
+            _fail(result.error, json_output)
+
+        attachment = result.data["attachment"]
+        trash_path = result.data["trash_path"]
+        if json_output:
+            output_json(
+                {
+                    "success": True,
+                    "attachment": attachment,
+                    "trash_path": str(trash_path) if trash_path else None,
+                }
+            )
+            return
+
+        console.print(f"\n[{theme.success}]✓ Removed {escape(attachment.filename)}[/{theme.success}]")
+        if trash_path:
+            console.print(f"[{theme.muted}]File moved to {trash_path}[/{theme.muted}]\n")
+        else:
+            console.print(f"[{theme.warning}]The file was already missing[/{theme.warning}]\n")
+
+    @transactions_app.command(name="attachments")
+    def transactions_attachments_command(
+        transaction_id: str = typer.Argument(..., help="Transaction ID or unique ID prefix"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """List a transaction's attachments with the paths of their files.
+
+        Examples:
+          tl transactions attachments 3f2a9c1e --json
+        """
+        ensure_initialized()
+
+        attachment_service = get_container().attachment_service()
+        result = asyncio.run(attachment_service.list_attachments(transaction_id))
+        if not result.success:
+            _fail(result.error, json_output, result.context)
+
+        if json_output:
+            output_json(
+                {
+                    "success": True,
+                    "attachments": [_attachment_json(a, attachment_service) for a in result.data],
+                }
+            )
+            return
+
+        if not result.data:
+            console.print(f"\n[{theme.muted}]No attachments[/{theme.muted}]\n")
+            return
+
+        console.print()
+        for attachment in result.data:
+            console.print(
+                f"{attachment.id}  {escape(attachment.filename)}  {_format_size(attachment.size_bytes)}"
+            )
+            console.print(f"  [{theme.muted}]{attachment_service.path_for(attachment)}[/{theme.muted}]")
+        console.print()
+
+    @transactions_app.command(name="attachment-path")
+    def transactions_attachment_path_command(
+        attachment_id: str = typer.Argument(..., help="Attachment ID"),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """Print the path of an attachment's file.
+
+        Examples:
+          open "$(tl transactions attachment-path 9b1de0c4-6f0e-4c1a-8f57-1d2a3b4c5d6e)"
+        """
+        ensure_initialized()
+
+        attachment_service = get_container().attachment_service()
+        result = asyncio.run(attachment_service.get_attachment(attachment_id))
+        if not result.success:
+            _fail(result.error, json_output)
+
+        path = attachment_service.path_for(result.data)
+        if not path.exists():
+            _fail(f"Attachment file is missing: {path}", json_output)
+
+        if json_output:
+            output_json({"success": True, "path": str(path)})
+            return
+        print(path)
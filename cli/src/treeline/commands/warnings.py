@@ -0,0 +1,144 @@
+"""Warnings command - list and resolve persisted provider warnings."""
+
+import asyncio
+import json
+
+import typer
+from pydantic import BaseModel
+from rich.console import Console
+from rich.table import Table
+
+from treeline.commands.json_output import model_json
+from treeline.theme import get_theme
+from treeline.utils import get_log_file_path
+
+console = Console()
+theme = get_theme()
+
+
+def json_serializer(obj):
+    """Custom JSON serializer for Pydantic models and other objects."""
+    if isinstance(obj, BaseModel):
+        return model_json(obj)
+    return str(obj)
+
+
+def output_json(data: dict) -> None:
+    """Output data as JSON."""
+    print(json.dumps(data, indent=2, default=json_serializer))
+
+
+def display_error(error: str, show_log_hint: bool = True) -> None:
+    """Display error message in consistent format."""
+    console.print(f"[{theme.error}]Error: {error}[/{theme.error}]")
+    if show_log_hint:
+        log_file = get_log_file_path()
+        console.print(f"[{theme.muted}]See {log_file} for details[/{theme.muted}]")
+
+
+def register(app: typer.Typer, get_container: callable, ensure_initialized: callable) -> None:
+    """Register the warnings command with the app."""
+
+    @app.command(name="warnings")
+    def warnings_command(
+        action: str = typer.Argument(
+            "list", help="Action: 'list' or 'resolve' (default: list)"
+        ),
+        warning_id: str = typer.Argument(None, help="Warning ID (for resolve)"),
+        show_all: bool = typer.Option(
+            False, "--all", help="Include resolved warnings"
+        ),
+        json_output: bool = typer.Option(False, "--json", help="Output as JSON"),
+    ) -> None:
+        """List or resolve provider warnings recorded during sync.
+
+        Warnings are resolved automatically when the next sync of that
+        integration comes back clean.
+
+        Examples:
+          tl warnings                    # List unresolved warnings
+          tl warnings list --all         # Include resolved warnings
+          tl warnings resolve WARNING-ID # Dismiss a warning
+        """
+        ensure_initialized()
+
+        action_lower = action.lower()
+        if action_lower == "list":
+            _list_warnings(get_container, show_all, json_output)
+        elif action_lower == "resolve":
+            if not warning_id:
+                display_error("Warning ID is required for resolve", show_log_hint=False)
+                raise typer.Exit(1)
+            _resolve_warning(get_container, warning_id, json_output)
+        else:
+            display_error(f"Unknown action: {action}", show_log_hint=False)
+            console.print(f"[{theme.muted}]Use 'list' or 'resolve'[/{theme.muted}]")
+            raise typer.Exit(1)
+
+
+def _list_warnings(get_container: callable, show_all: bool, json_output: bool) -> None:
+    """List provider warnings."""
+    warning_service = get_container().warning_service()
+    result = asyncio.run(warning_service.list_warnings(include_resolved=show_all))
+
+    if not result.success:
+        if json_output:
+            output_json({"error": result.error})
+        else:
+            display_error(result.error)
+        raise typer.Exit(1)
+
+    warnings = result.data or []
+
+    if json_output:
+        output_json({"warnings": warnings})
+        return
+
+    if not warnings:
+        console.print(f"\n[{theme.success}]No provider warnings[/{theme.success}]\n")
+        return
+
+    table = Table(title="Provider Warnings", show_header=True, header_style=theme.ui_header)
+    table.add_column("ID", style=theme.muted)
+    table.add_column("Integration")
+    table.add_column("Message")
+    table.add_column("First Seen")
+    table.add_column("Last Seen")
+    if show_all:
+        table.add_column("Resolved")
+
+    for warning in warnings:
+        row = [
+            str(warning.id),
+            warning.integration_name,
+            warning.message,
+            warning.first_seen.strftime("%Y-%m-%d %H:%M"),
+            warning.last_seen.strftime("%Y-%m-%d %H:%M"),
+        ]
+        if show_all:
+            row.append(
+                warning.resolved_at.strftime("%Y-%m-%d %H:%M") if warning.resolved_at else ""
+            )
+        table.add_row(*row)
+
+    console.print()
+    console.print(table)
+    console.print()
+
+
+def _resolve_warning(get_container: callable, warning_id: str, json_output: bool) -> None:
+    """Mark a provider warning as resolved."""
+    warning_service = get_container().warning_service()
+    result = asyncio.run(warning_service.resolve_warning(warning_id))
+
+    if not result.success:
+        if json_output:
+            output_json({"error": result.error})
+        else:
+            display_error(result.error, show_log_hint=False)
+        raise typer.Exit(1)
+
+    if json_output:
+        output_json({"resolved": warning_id})
+    else:
+        console.print(f"[{theme.success}]✓ Warning resolved[/{theme.success}]")
@@ -1,9 +1,11 @@
 """Configuration management for Treeline."""
 
 import json
+import math
 from pathlib import Path
-from typing import Any, Dict
+from typing import Any, Dict, List
 
+from treeline.locales import resolve_locale
 from treeline.utils import get_treeline_dir
 
 
@@ -34,6 +36,35 @@ def save_settings(settings: Dict[str, Any]) -> None:
         json.dump(settings, f, indent=2)
 
 
+def get_snippets_path() -> Path:
+    """Get path to the saved query snippets file (shared with UI)."""
+    return get_treeline_dir() / "snippets.json"
+
+
+def get_category_map_path() -> Path:
+    """Get path to the provider category to tag map (see treeline.category_map)."""
+    return get_treeline_dir() / "category_map.json"
+
+
+def load_query_snippets() -> List[Dict[str, Any]]:
+    """Load saved query snippets ({name, sql, description, builtin}).
+
+    The app writes snippets.json, seeding it with its built-in snippets the
+    first time the query console opens. Returns an empty list if the file is
+    missing or unreadable.
+    """
+    snippets_path = get_snippets_path()
+    if not snippets_path.exists():
+        return []
+
+    try:
+        with open(snippets_path) as f:
+            snippets = json.load(f).get("snippets", [])
+    except (json.JSONDecodeError, IOError, AttributeError):
+        return []
+    return [s for s in snippets if isinstance(s, dict) and s.get("name") and s.get("sql")]
+
+
 def is_demo_mode() -> bool:
     """Check if demo mode is enabled.
 
@@ -63,3 +94,385 @@ def set_demo_mode(enabled: bool) -> None:
         settings["app"] = {}
     settings["app"]["demoMode"] = enabled
     save_settings(settings)
+
+
+def get_week_start() -> int:
+    """Get the configured first day of the week (0=Monday .. 6=Sunday).
+
+    Read from settings.json app.weekStart (e.g. "monday", "sunday").
+    Defaults to Monday (ISO 8601) when unset or unrecognized.
+    """
+    from treeline.period import WEEKDAYS
+
+    settings = load_settings()
+    week_start = str(settings.get("app", {}).get("weekStart", "monday")).lower()
+    if week_start in WEEKDAYS:
+        return WEEKDAYS.index(week_start)
+    return 0
+
+
+def get_display_locale() -> str:
+    """Get the locale used to format amounts and dates in CLI tables.
+
+    Read from settings.json display.locale, then the LC_ALL environment
+    variable. Unsupported or missing locales resolve to en-US.
+    """
+    import os
+
+    settings = load_settings()
+    locale = settings.get("display", {}).get("locale") or os.getenv("LC_ALL")
+    return resolve_locale(locale)
+
+
+def get_fingerprint_version() -> int:
+    """Get the fingerprint strategy version for new imports and syncs.
+
+    Read from settings.json import.fingerprint_version. Defaults to 1.
+    """
+    from treeline.domain import DEFAULT_FINGERPRINT_VERSION, SUPPORTED_FINGERPRINT_VERSIONS
+
+    settings = load_settings()
+    version = settings.get("import", {}).get("fingerprint_version", DEFAULT_FINGERPRINT_VERSION)
+    try:
+        version = int(version)
+    except (TypeError, ValueError):
+        return DEFAULT_FINGERPRINT_VERSION
+    if version in SUPPORTED_FINGERPRINT_VERSIONS:
+        return version
+    return DEFAULT_FINGERPRINT_VERSION
+
+
+def get_fuzzy_dedup_days() -> int:
+    """Get how many days apart an imported row and a stored one may be dated
+    and still count as duplicates.
+
+    Read from settings.json import.fuzzy_dedup_days. Defaults to 0 (exact
+    dates only); invalid or negative values are treated as 0.
+    """
+    settings = load_settings()
+    days = settings.get("import", {}).get("fuzzy_dedup_days", 0)
+    try:
+        return max(int(days), 0)
+    except (TypeError, ValueError):
+        return 0
+
+
+# Default cap on files `tl import` will read
+DEFAULT_IMPORT_MAX_FILE_MB = 200
+
+
+def get_import_max_file_bytes() -> int:
+    """Get the largest file `tl import` will read, in bytes.
+
+    Read from settings.json import.max_file_mb. Defaults to 200 MB.
+    """
+    settings = load_settings()
+    try:
+        max_mb = float(settings.get("import", {}).get("max_file_mb", DEFAULT_IMPORT_MAX_FILE_MB))
+    except (TypeError, ValueError):
+        max_mb = DEFAULT_IMPORT_MAX_FILE_MB
+    if max_mb <= 0:
+        max_mb = DEFAULT_IMPORT_MAX_FILE_MB
+    return int(max_mb * 1024 * 1024)
+
+
+def get_exchange_rates() -> Dict[str, "Decimal"]:
+    """Get the exchange rate table used to convert imported amounts.
+
+    Read from settings.json exchange_rates: pairs like "CAD/USD" mapped to
+    how much one unit of the first currency is worth in the second, e.g.
+    {"CAD/USD": "0.73"}. Pairs are uppercased; entries that aren't a
+    positive number are ignored. Defaults to no rates.
+    """
+    from decimal import Decimal, InvalidOperation
+
+    settings = load_settings()
+    configured = settings.get("exchange_rates")
+    if not isinstance(configured, dict):
+        return {}
+    rates = {}
+    for pair, value in configured.items():
+        try:
+            rate = Decimal(str(value))
+        except InvalidOperation:
+            continue
+        if rate.is_finite() and rate > 0:
+            rates[str(pair).strip().upper()] = rate
+    return rates
+
+
+def is_offline() -> bool:
+    """Check if offline mode is enabled.
+
+    Offline mode is enabled via the global --offline flag or the
+    TREELINE_OFFLINE environment variable. Network providers fail fast
+    instead of attempting a connection.
+    """
+    import os
+
+    return os.getenv("TREELINE_OFFLINE", "").lower() in ("true", "1", "yes")
+
+
+def set_offline_mode() -> None:
+    """Enable offline mode for the rest of this process."""
+    import os
+
+    os.environ["TREELINE_OFFLINE"] = "1"
+
+
+# Seconds an import, sync or backfill waits for another one to finish writing
+DEFAULT_WRITE_LOCK_WAIT_SECONDS = 30
+
+
+def get_write_lock_wait() -> float:
+    """Seconds to wait for the write lock (see infra/write_lock.py).
+
+    Set via --wait-lock on tl import, sync and backfill, or the
+    TREELINE_WRITE_LOCK_WAIT environment variable.
+    """
+    import os
+
+    try:
+        seconds = float(os.getenv("TREELINE_WRITE_LOCK_WAIT", DEFAULT_WRITE_LOCK_WAIT_SECONDS))
+    except ValueError:
+        return DEFAULT_WRITE_LOCK_WAIT_SECONDS
+    return seconds if math.isfinite(seconds) and seconds >= 0 else DEFAULT_WRITE_LOCK_WAIT_SECONDS
+
+
+def set_write_lock_wait(seconds: float) -> None:
+    """Wait this long for the write lock for the rest of this process."""
+    import os
+
+    os.environ["TREELINE_WRITE_LOCK_WAIT"] = str(seconds)
+
+
+def assume_yes() -> bool:
+    """Check if confirmation prompts should be answered yes.
+
+    Enabled via the global --yes flag or the TREELINE_YES environment variable.
+    """
+    import os
+
+    return os.getenv("TREELINE_YES", "").lower() in ("true", "1", "yes")
+
+
+def set_assume_yes() -> None:
+    """Answer yes to confirmation prompts for the rest of this process."""
+    import os
+
+    os.environ["TREELINE_YES"] = "1"
+
+
+def json_numbers() -> bool:
+    """Check if --json output should write amounts as numbers.
+
+    Amounts are written as strings in the currency's decimal places
+    ("-4.50") so JavaScript consumers don't round large ones. The global
+    --json-numbers flag or the TREELINE_JSON_NUMBERS environment variable
+    brings back numbers for scripts written against the old output; it will
+    be removed in the next release. Read by treeline.commands.json_output.
+    """
+    import os
+
+    return os.getenv("TREELINE_JSON_NUMBERS", "").lower() in ("true", "1", "yes")
+
+
+def set_json_numbers() -> None:
+    """Write amounts in --json output as numbers for the rest of this process."""
+    import os
+
+    os.environ["TREELINE_JSON_NUMBERS"] = "1"
+
+
+def is_no_input() -> bool:
+    """Check if prompts are disabled (commands fail instead of asking).
+
+    Enabled via the global --no-input flag or the TREELINE_NO_INPUT
+    environment variable. Prompts are also disabled when stdin isn't a
+    terminal; see treeline.confirm.
+    """
+    import os
+
+    return os.getenv("TREELINE_NO_INPUT", "").lower() in ("true", "1", "yes")
+
+
+def set_no_input() -> None:
+    """Disable prompts for the rest of this process."""
+    import os
+
+    os.environ["TREELINE_NO_INPUT"] = "1"
+
+
+def get_network_proxy() -> str | None:
+    """Get the proxy URL configured for provider requests.
+
+    Read from settings.json network.proxy. When unset, the standard
+    HTTPS_PROXY/HTTP_PROXY/NO_PROXY environment variables apply.
+    """
+    settings = load_settings()
+    proxy = settings.get("network", {}).get("proxy")
+    return str(proxy) if proxy else None
+
+
+def get_balance_check_tolerance() -> "Decimal":
+    """How far a synced balance may drift from its transactions unremarked.
+
+    After a sync, each account's balance change since its previous snapshot
+    is compared with the transactions in between; larger differences are
+    reported. Read from settings.json sync.balance_tolerance. Defaults to 0.01.
+    """
+    from decimal import Decimal, InvalidOperation
+
+    settings = load_settings()
+    try:
+        tolerance = Decimal(str(settings.get("sync", {}).get("balance_tolerance", "0.01")))
+    except InvalidOperation:
+        return Decimal("0.01")
+    if not tolerance.is_finite() or tolerance < 0:
+        return Decimal("0.01")
+    return tolerance
+
+
+def get_budget_alerts_enabled() -> bool:
+    """Whether sync warns when new transactions push a budget category over.
+
+    Read from settings.json budgets.alerts. Defaults to False.
+    """
+    settings = load_settings()
+    return settings.get("budgets", {}).get("alerts", False) is True
+
+
+def get_keep_unmapped() -> bool:
+    """Whether sync quarantines transactions for unknown provider accounts.
+
+    Read from settings.json sync.keep_unmapped. Defaults to False, in which
+    case unmapped transactions are only counted and reported.
+    """
+    settings = load_settings()
+    return bool(settings.get("sync", {}).get("keep_unmapped", False))
+
+
+
+# Default pause between the requests of `tl backfill transactions`
+DEFAULT_BACKFILL_DELAY_SECONDS = 2.0
+
+
+def get_backfill_delay_seconds() -> float:
+    """Seconds to wait between the month-sized requests of a history backfill.
+
+    Read from settings.json sync.backfill_delay_seconds. Defaults to 2;
+    invalid or negative values fall back to the default.
+    """
+    settings = load_settings()
+    delay = settings.get("sync", {}).get("backfill_delay_seconds", DEFAULT_BACKFILL_DELAY_SECONDS)
+    try:
+        delay = float(delay)
+    except (TypeError, ValueError):
+        return DEFAULT_BACKFILL_DELAY_SECONDS
+    if not math.isfinite(delay) or delay < 0:
+        return DEFAULT_BACKFILL_DELAY_SECONDS
+    return delay
+
+def get_auto_month_end_snapshots() -> bool:
+    """Whether sync fills in a balance snapshot on the last month-end.
+
+    Read from settings.json snapshots.auto_month_end. Defaults to True.
+    """
+    settings = load_settings()
+    return settings.get("snapshots", {}).get("auto_month_end", True) is not False
+
+
+# Default cap on `tl query --file` size
+DEFAULT_QUERY_MAX_FILE_MB = 10
+
+
+def get_query_timeout_secs() -> float:
+    """Get the default `tl query` timeout in seconds (0 = unlimited).
+
+    Read from settings.json query.timeout_secs. Defaults to 0.
+    """
+    settings = load_settings()
+    try:
+        timeout = float(settings.get("query", {}).get("timeout_secs", 0))
+    except (TypeError, ValueError):
+        return 0
+    return max(timeout, 0)
+
+
+def get_query_max_file_bytes() -> int:
+    """Get the largest SQL file `tl query --file` will read, in bytes.
+
+    Read from settings.json query.max_file_mb. Defaults to 10 MB.
+    """
+    settings = load_settings()
+    try:
+        max_mb = float(settings.get("query", {}).get("max_file_mb", DEFAULT_QUERY_MAX_FILE_MB))
+    except (TypeError, ValueError):
+        max_mb = DEFAULT_QUERY_MAX_FILE_MB
+    if max_mb <= 0:
+        max_mb = DEFAULT_QUERY_MAX_FILE_MB
+    return int(max_mb * 1024 * 1024)
+
+
+# Default cap on files attached with `tl transactions attach`
+DEFAULT_ATTACHMENT_MAX_FILE_MB = 20
+
+# Default file types `tl transactions attach` accepts
+DEFAULT_ATTACHMENT_EXTENSIONS = ["pdf", "png", "jpg", "jpeg", "heic", "gif", "webp", "txt", "csv"]
+
+
+def get_attachment_max_bytes() -> int:
+    """Get the largest file `tl transactions attach` will accept, in bytes.
+
+    Read from settings.json attachments.max_file_mb. Defaults to 20 MB.
+    """
+    settings = load_settings()
+    try:
+        max_mb = float(
+            settings.get("attachments", {}).get("max_file_mb", DEFAULT_ATTACHMENT_MAX_FILE_MB)
+        )
+    except (TypeError, ValueError):
+        max_mb = DEFAULT_ATTACHMENT_MAX_FILE_MB
+    if max_mb <= 0:
+        max_mb = DEFAULT_ATTACHMENT_MAX_FILE_MB
+    return int(max_mb * 1024 * 1024)
+
+
+def get_attachment_extensions() -> List[str]:
+    """Get the file extensions `tl transactions attach` accepts (lowercase, no dot).
+
+    Read from settings.json attachments.allowed_extensions.
+    """
+    settings = load_settings()
+    extensions = settings.get("attachments", {}).get("allowed_extensions")
+    if not isinstance(extensions, list) or not extensions:
+        return list(DEFAULT_ATTACHMENT_EXTENSIONS)
+    return [str(ext).lower().lstrip(".") for ext in extensions]
+
+
+# Days without new data before `tl status` calls an account stale
+DEFAULT_STALE_DAYS_SYNCED = 7
+DEFAULT_STALE_DAYS_MANUAL = 45
+
+
+def get_stale_days() -> Dict[str, int]:
+    """Get the staleness thresholds used by `tl status`, in days.
+
+    Read from settings.json status.stale_days: "synced" for accounts with a
+    provider ID (default 7), "manual" for the rest (default 45), and any
+    other key as an account type whose accounts use that threshold instead,
+    e.g. {"investment": 30}. Type keys are lowercase.
+    """
+    settings = load_settings()
+    configured = settings.get("status", {}).get("stale_days")
+    thresholds = {"synced": DEFAULT_STALE_DAYS_SYNCED, "manual": DEFAULT_STALE_DAYS_MANUAL}
+    if not isinstance(configured, dict):
+        return thresholds
+    for key, value in configured.items():
+        try:
+            days = int(value)
+        except (TypeError, ValueError):
+            continue
+        if days > 0:
+            thresholds[str(key).strip().lower()] = days
+    return thresholds
@@ -0,0 +1,117 @@
+"""Prompts that scripts can answer (--yes) or refuse (--no-input).
+
+Every yes/no confirmation goes through Confirm so the wording and the
+scripted behavior are the same everywhere. Prompts are disabled when
+--no-input is given or stdin isn't a terminal (cron, pipes, the desktop
+app); a prompt that can't be shown fails with an error_kind instead of
+blocking on input that will never come.
+"""
+
+import json
+import sys
+from typing import TextIO
+
+import typer
+from rich.console import Console
+
+from treeline.config import assume_yes, is_no_input
+from treeline.domain import Fail, Ok, Result
+from treeline.theme import get_theme
+
+console = Console()
+theme = get_theme()
+
+# error_kind (in the failure context and --json errors) when a confirmation
+# was needed but prompts are disabled; pass --yes (or the command's --force)
+CONFIRMATION_REQUIRED = "confirmation_required"
+
+# error_kind when a value would have been prompted for; pass it as an option
+INPUT_REQUIRED = "input_required"
+
+
+def deletion_action(count: int, noun: str = "rows") -> str:
+    """Standard action text for a permanent delete, e.g. "This will permanently delete 3 rows"."""
+    return f"This will permanently delete {count} {noun}"
+
+
+class Confirm:
+    """Yes/no confirmations and checks before interactive prompts.
+
+    Defaults come from the global flags and stdin; tests inject the reader,
+    writer and whether it is a terminal.
+    """
+
+    def __init__(
+        self,
+        yes: bool | None = None,
+        no_input: bool | None = None,
+        reader: TextIO | None = None,
+        writer: TextIO | None = None,
+        is_tty: bool | None = None,
+    ):
+        self.reader = reader or sys.stdin
+        self.writer = writer or sys.stdout
+        if is_tty is None:
+            is_tty = bool(getattr(self.reader, "isatty", lambda: False)())
+        self.yes = assume_yes() if yes is None else yes
+        self.no_input = (is_no_input() if no_input is None else no_input) or not is_tty
+
+    def ask(self, action: str) -> Result[bool]:
+        """Ask "<action>. Continue? [y/N]"; only y or yes (any case) confirms.
+
+        Returns Ok(True) without asking under --yes, and fails with
+        error_kind confirmation_required when prompts are disabled.
+        """
+        question = f"{action}. Continue?"
+        if self.yes:
+            return Ok(True)
+        if self.no_input:
+            return Fail(
+                f"Confirmation required: {question} Pass --yes to proceed.",
+                context={"error_kind": CONFIRMATION_REQUIRED},
+            )
+
+        self.writer.write(f"{question} [y/N] ")
+        self.writer.flush()
+        try:
+            answer = self.reader.readline()
+        except KeyboardInterrupt:
+            answer = ""
+        if not answer.endswith("\n"):
+            self.writer.write("\n")
+        return Ok(answer.strip().lower() in ("y", "yes"))
+
+    def require_input(self, what: str) -> Result[None]:
+        """Check that what (e.g. "Setup token") may be prompted for."""
+        if self.no_input:
+            return Fail(
+                f"{what} is required and prompts are disabled; pass it as an option",
+                context={"error_kind": INPUT_REQUIRED},
+            )
+        return Ok()
+
+
+def _exit_with_error(result: Result, json_output: bool) -> None:
+    error_kind = (result.context or {}).get("error_kind")
+    if json_output:
+        print(json.dumps({"success": False, "error": result.error, "error_kind": error_kind}))
+    else:
+        console.print(f"[{theme.error}]Error: {result.error}[/{theme.error}]")
+    raise typer.Exit(1)
+
+
+def confirm_or_exit(action: str, json_output: bool = False) -> None:
+    """Confirm action for a command: exit 0 if declined, 1 if prompts are disabled."""
+    result = Confirm().ask(action)
+    if not result.success:
+        _exit_with_error(result, json_output)
+    if not result.data:
+        console.print(f"[{theme.muted}]Cancelled[/{theme.muted}]\n")
+        raise typer.Exit(0)
+
+
+def require_input_or_exit(what: str, json_output: bool = False) -> None:
+    """Exit 1 before an interactive prompt for what if prompts are disabled."""
+    result = Confirm().require_input(what)
+    if not result.success:
+        _exit_with_error(result, json_output)
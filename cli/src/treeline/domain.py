@@ -2,14 +2,35 @@
 
 from __future__ import annotations
 
-from datetime import date, datetime, timezone
+from datetime import date, datetime, timedelta, timezone
 from decimal import Decimal
+from enum import Enum
 from types import MappingProxyType
-from typing import Any, Dict, Generic, Mapping, Type, TypeVar
+from typing import (
+    Annotated,
+    Any,
+    Dict,
+    Generic,
+    Iterable,
+    List,
+    Mapping,
+    Tuple,
+    Type,
+    TypeVar,
+)
 
 from uuid import UUID
 
-from pydantic import BaseModel, ConfigDict, Field, field_validator, model_validator
+from pydantic import (
+    BaseModel,
+    ConfigDict,
+    Field,
+    PlainSerializer,
+    SerializationInfo,
+    field_serializer,
+    field_validator,
+    model_validator,
+)
 
 
 class User(BaseModel):
@@ -28,6 +49,40 @@ def _ensure_tzinfo(value: datetime) -> datetime:
     return value.astimezone(timezone.utc)
 
 
+# Currencies without two decimal places (ISO 4217 minor units)
+CURRENCY_MINOR_UNITS: Mapping[str, int] = MappingProxyType({"JPY": 0, "KRW": 0})
+
+
+def money_quantum(currency: str | None = None) -> Decimal:
+    """Smallest unit of a currency: 0.01 for most, 1 for JPY and KRW."""
+    return Decimal(1).scaleb(-CURRENCY_MINOR_UNITS.get((currency or "").upper(), 2))
+
+
+def money_json(amount: Decimal, currency: str | None = None) -> str:
+    """JSON form of an amount: a string with the currency's decimal places
+    ("-4.50", or "-450" in yen), which JavaScript can't round the way it
+    rounds large numbers. Unknown currencies get two."""
+    return str(amount.quantize(money_quantum(currency)))
+
+
+def _money_for_json(amount: Decimal, currency: str | None, info: SerializationInfo) -> str | float:
+    # The commands layer asks for numbers under --json-numbers; rows stored
+    # as JSON (quarantined transactions) never pass that and stay exact
+    if (info.context or {}).get("json_numbers"):
+        return float(amount)
+    return money_json(amount, currency)
+
+
+def _serialize_money(amount: Decimal, info: SerializationInfo) -> str | float:
+    return _money_for_json(amount, (info.context or {}).get("currency"), info)
+
+
+# Amounts and balances; validated as Decimal (from strings or numbers) and
+# written to JSON by money_json. Callers that know the currency pass it in
+# the serialization context ({"currency": "JPY"}).
+Money = Annotated[Decimal, PlainSerializer(_serialize_money, when_used="json")]
+
+
 class Account(BaseModel):
     """Represents a financial account owned by the user."""
 
@@ -39,10 +94,16 @@ class Account(BaseModel):
     account_type: str | None = None
     currency: str = Field(default="USD")
     external_ids: Dict[str, str] = Field(default_factory=dict)
-    balance: Decimal | None = None
+    balance: Decimal | None = None  # Written to JSON in the account's currency
     institution_name: str | None = None
     institution_url: str | None = None
     institution_domain: str | None = None
+    opened_at: date | None = None
+    closed_at: date | None = None  # Balance counts as zero after this date
+    # Added to every transaction synced, imported or entered into the account
+    default_tags: tuple[str, ...] = ()
+    # Archived accounts are left out of status, backfill and sync
+    archived_at: datetime | None = None
     created_at: datetime
     updated_at: datetime
 
@@ -60,11 +121,22 @@ class Account(BaseModel):
         if value is None:
             return {}
         if isinstance(value, Mapping):
+            # Sorted by key so output doesn't depend on provider or DB order
             normalized = {str(key): str(val) for key, val in value.items()}
-            return normalized
+            return dict(sorted(normalized.items()))
         msg = "external_ids must be a mapping"
         raise TypeError(msg)
 
+    @field_validator("default_tags", mode="before")
+    @classmethod
+    def _normalize_default_tags(cls, value: object) -> tuple[str, ...]:
+        if value is None:
+            return ()
+        if isinstance(value, (list, tuple)):
+            return tuple(normalize_tags(value))
+        msg = "default_tags must be a list of strings"
+        raise TypeError(msg)
+
     @field_validator("currency")
     @classmethod
     def _normalize_currency(cls, value: str) -> str:
@@ -84,6 +156,145 @@ class Account(BaseModel):
     def _require_timezone_updated(cls, value: datetime) -> datetime:
         return _ensure_tzinfo(value)
 
+    @field_serializer("balance", when_used="json")
+    def _serialize_balance(self, value: Decimal | None, info: SerializationInfo) -> str | float | None:
+        return None if value is None else _money_for_json(value, self.currency, info)
+
+    @model_validator(mode="after")
+    def _closed_after_opened(self) -> "Account":
+        if self.opened_at and self.closed_at and self.closed_at < self.opened_at:
+            msg = f"closed_at ({self.closed_at}) is before opened_at ({self.opened_at})"
+            raise ValueError(msg)
+        return self
+
+    def is_open_on(self, day: date) -> bool:
+        """Whether the account existed on a day (unknown dates count as open)."""
+        if self.opened_at and day < self.opened_at:
+            return False
+        return not (self.closed_at and day > self.closed_at)
+
+
+class TransactionStatus(str, Enum):
+    """Where a transaction is in the reconciliation workflow.
+
+    Manually entered transactions can start as pending and are cleared when
+    they show up on a statement. Synced and imported rows are always cleared.
+    """
+
+    PENDING = "pending"
+    CLEARED = "cleared"
+    RECONCILED = "reconciled"
+
+
+# Largest magnitude the DECIMAL(18,2) money columns (amounts, balances) hold
+MAX_AMOUNT = Decimal("9999999999999999.99")
+
+
+def amount_in_range(amount: Decimal) -> bool:
+    """Whether an amount can be stored without overflowing the money columns."""
+    return amount.is_finite() and abs(amount) <= MAX_AMOUNT
+
+
+def balance_from_anchor(
+    anchor_balance: Decimal,
+    anchor_date: date,
+    as_of: date,
+    transactions: Iterable[Tuple[date, Decimal]],
+) -> Tuple[Decimal, int]:
+    """End-of-day balance on as_of, from a known end-of-day balance on anchor_date.
+
+    Going forward, the transactions after the anchor day up to as_of are
+    added; going back, the ones after as_of up to the anchor day are
+    subtracted. Transactions outside that range are ignored. Both balance
+    backfill and point-in-time balances use this, so they always agree.
+
+    Returns:
+        The balance and how many transactions were applied
+    """
+    start, end = sorted((anchor_date, as_of))
+    delta = Decimal("0")
+    applied = 0
+    for day, amount in transactions:
+        if start < day <= end:
+            delta += amount
+            applied += 1
+    if as_of < anchor_date:
+        return anchor_balance - delta, applied
+    return anchor_balance + delta, applied
+
+
+def is_system_table(name: str) -> bool:
+    """Whether a table is internal storage (listed only with --all)."""
+    return name.startswith("sys_")
+
+
+def normalize_tag(tag: str) -> str:
+    """The stored form of a tag: trimmed, lowercase, single-spaced, no leading '#'.
+
+    "  Dining ", "#dining" and "DINING" all become "dining". Every path that
+    writes tags applies this, so variants never fragment reports.
+    """
+    tag = str(tag).strip()
+    while tag.startswith("#"):
+        tag = tag[1:].lstrip()
+    return " ".join(tag.split()).lower()
+
+
+def normalize_tags(tags: Any) -> List[str]:
+    """Normalize each tag, dropping empty ones and duplicates (first one wins)."""
+    normalized: List[str] = []
+    for tag in tags or ():
+        if tag is None:
+            continue
+        tag = normalize_tag(tag)
+        if tag and tag not in normalized:
+            normalized.append(tag)
+    return normalized
+
+
+def normalize_fingerprint_description(description: str | None) -> str:
+    """Normalize a description the way fingerprints see it.
+
+    Handles CSV vs SimpleFIN format differences:
+    - Removes literal "null" strings (CSV exports)
+    - Removes card number masks (XXXXXXXXXXXX1234 - CSV only)
+    - Normalizes account/phone numbers to last 4 digits (XXXXXX7070 vs 7208987070)
+    - Removes whitespace and special characters
+    """
+    import re
+
+    desc = (description or "").lower()
+
+    # Remove literal "null" strings (common in CSV exports)
+    desc_normalized = re.sub(r"\bnull\b", "", desc)
+
+    # Remove card number masks (10+ X's followed by 4 digits) - these only appear in CSV
+    desc_normalized = re.sub(r"x{10,}\d{4}", "", desc_normalized)
+
+    # Normalize shorter phone/account numbers (7-12 digits or X's + digits)
+    # These appear in both CSV and SimpleFIN, just masked differently
+    # Examples: XXXXXX7070 vs 7208987070, XXXX9969 vs 00009969
+    def normalize_account_numbers(match):
+        text = match.group(0)
+        # Extract digits only
+        digits = "".join(c for c in text if c.isdigit())
+        # Keep last 4 digits if we have at least 4
+        if len(digits) >= 4:
+            return digits[-4:]
+        return text
+
+    desc_normalized = re.sub(
+        r"[x0-9]{7,12}", normalize_account_numbers, desc_normalized
+    )
+
+    # Remove whitespace
+    desc_normalized = re.sub(r"\s+", "", desc_normalized)
+
+    # Remove all special characters, keep only alphanumeric
+    desc_normalized = re.sub(r"[^a-z0-9]", "", desc_normalized)
+
+    return desc_normalized
+
 
 class Transaction(BaseModel):
     """A single transaction belonging to an account."""
@@ -93,7 +304,7 @@ class Transaction(BaseModel):
     id: UUID
     account_id: UUID
     external_ids: Dict[str, str] = Field(default_factory=dict)
-    amount: Decimal
+    amount: Money
     description: str | None = None
     transaction_date: date  # Changed from datetime - no timezone needed
     posted_date: date  # Changed from datetime - no timezone needed
@@ -104,6 +315,7 @@ class Transaction(BaseModel):
     deleted_at: datetime | None = None
     # Split transaction support - children reference parent
     parent_transaction_id: UUID | None = None
+    status: TransactionStatus = TransactionStatus.CLEARED
 
     # Note: Zero-amount transactions are valid (transfers, pending, corrections, etc.)
     # so we don't validate against zero amounts
@@ -114,8 +326,9 @@ class Transaction(BaseModel):
         if value is None:
             return {}
         if isinstance(value, Mapping):
+            # Sorted by key so output doesn't depend on provider or DB order
             normalized = {str(key): str(val) for key, val in value.items()}
-            return normalized
+            return dict(sorted(normalized.items()))
         msg = "external_ids must be a mapping"
         raise TypeError(msg)
 
@@ -172,7 +385,10 @@ class Transaction(BaseModel):
 
     @model_validator(mode="after")
     def _generate_fingerprint_if_missing(self) -> "Transaction":
-        """Auto-generate fingerprint and store in external_ids if not present."""
+        """Auto-generate a v1 fingerprint and store in external_ids if not present.
+
+        Use with_fingerprint() / assign_fingerprints() for other versions.
+        """
         if "fingerprint" not in self.external_ids:
             fingerprint = self._calculate_fingerprint()
             # external_ids is a dict, but the model is frozen, so we need to use object.__setattr__
@@ -181,63 +397,121 @@ class Transaction(BaseModel):
             object.__setattr__(self, "external_ids", ids_dict)
         return self
 
-    def _calculate_fingerprint(self) -> str:
-        """Generate fingerprint hash for deduplication.
+    @property
+    def fingerprint_version(self) -> int:
+        """Fingerprint version stored in external_ids (rows without one are v1)."""
+        return int(self.external_ids.get("fingerprint_version", 1))
 
-        Uses: account_id, transaction_date, amount (with sign), and normalized description.
+    def compute_fingerprint(self, version: int = 1, occurrence: int = 0) -> str:
+        """Compute the fingerprint for a given strategy version.
 
-        Description normalization handles CSV vs SimpleFIN format differences:
-        - Removes literal "null" strings (CSV exports)
-        - Removes card number masks (XXXXXXXXXXXX1234 - CSV only)
-        - Normalizes account/phone numbers to last 4 digits (XXXXXX7070 vs 7208987070)
-        - Removes whitespace and special characters
+        v1 hashes account, date, amount and normalized description, so identical
+        same-day purchases collapse into one. v2 also hashes the occurrence index
+        of the row among identical rows in the same batch, so they survive.
         """
+        if version == 1:
+            return self._calculate_fingerprint()
+        if version == 2:
+            import hashlib
+
+            base = self._fingerprint_base()
+            return hashlib.sha256(f"{base}|{occurrence}".encode()).hexdigest()[:16]
+        raise ValueError(f"Unsupported fingerprint version: {version}")
+
+    def with_fingerprint(self, version: int, occurrence: int = 0) -> "Transaction":
+        """Return a copy with the fingerprint recomputed for the given version."""
+        ids_dict = dict(self.external_ids)
+        ids_dict["fingerprint"] = self.compute_fingerprint(version, occurrence)
+        if version == 1:
+            ids_dict.pop("fingerprint_version", None)
+        else:
+            ids_dict["fingerprint_version"] = str(version)
+        return self.model_copy(update={"external_ids": ids_dict})
+
+    def _calculate_fingerprint(self) -> str:
+        """Generate the v1 fingerprint hash for deduplication."""
         import hashlib
-        import re
 
+        fingerprint_str = self._fingerprint_base()
+        return hashlib.sha256(fingerprint_str.encode()).hexdigest()[:16]
+
+    def _fingerprint_base(self) -> str:
+        """Build the identity string hashed into fingerprints.
+
+        Uses: account_id, transaction_date, amount (with sign), and normalized description.
+
+        Descriptions are normalized with normalize_fingerprint_description().
+        """
         tx_date = self.transaction_date.isoformat()  # Already a date object
         # Keep sign - purchases and refunds are different transactions
         # Special case: normalize negative zero to positive zero for consistency
         amount = self.amount if self.amount != 0 else abs(self.amount)
         amount_normalized = f"{amount:.2f}"
 
-        # Normalize description to handle CSV vs SimpleFIN differences
-        desc = (self.description or "").lower()
+        desc_normalized = normalize_fingerprint_description(self.description)
+
+        return f"{self.account_id}|{tx_date}|{amount_normalized}|{desc_normalized}"
 
-        # Remove literal "null" strings (common in CSV exports)
-        desc_normalized = re.sub(r"\bnull\b", "", desc)
 
-        # Remove card number masks (10+ X's followed by 4 digits) - these only appear in CSV
-        desc_normalized = re.sub(r"x{10,}\d{4}", "", desc_normalized)
+SUPPORTED_FINGERPRINT_VERSIONS = (1, 2)
+DEFAULT_FINGERPRINT_VERSION = 1
 
-        # Normalize shorter phone/account numbers (7-12 digits or X's + digits)
-        # These appear in both CSV and SimpleFIN, just masked differently
-        # Examples: XXXXXX7070 vs 7208987070, XXXX9969 vs 00009969
-        def normalize_account_numbers(match):
-            text = match.group(0)
-            # Extract digits only
-            digits = "".join(c for c in text if c.isdigit())
-            # Keep last 4 digits if we have at least 4
-            if len(digits) >= 4:
-                return digits[-4:]
-            return text
 
-        desc_normalized = re.sub(
-            r"[x0-9]{7,12}", normalize_account_numbers, desc_normalized
-        )
+def assign_fingerprints(
+    transactions: List[Transaction], version: int
+) -> List[Transaction]:
+    """Fingerprint a batch of transactions (one import or sync) with the given version.
 
-        # Remove whitespace
-        desc_normalized = re.sub(r"\s+", "", desc_normalized)
+    For v2, identical rows (same account, date, amount and normalized
+    description) get occurrence indexes 0, 1, 2... in batch order.
+    """
+    if version not in SUPPORTED_FINGERPRINT_VERSIONS:
+        raise ValueError(f"Unsupported fingerprint version: {version}")
 
-        # Remove all special characters, keep only alphanumeric
-        desc_normalized = re.sub(r"[^a-z0-9]", "", desc_normalized)
+    occurrences: Dict[str, int] = {}
+    fingerprinted = []
+    for tx in transactions:
+        base = tx.compute_fingerprint(1)
+        occurrence = occurrences.get(base, 0)
+        occurrences[base] = occurrence + 1
+        fingerprinted.append(tx.with_fingerprint(version, occurrence))
+    return fingerprinted
 
-        fingerprint_str = (
-            f"{self.account_id}|{tx_date}|{amount_normalized}|{desc_normalized}"
-        )
-        fingerprint_hash = hashlib.sha256(fingerprint_str.encode()).hexdigest()[:16]
 
-        return fingerprint_hash
+def apply_default_tags(
+    transactions: Iterable[Transaction], accounts: Iterable[Account]
+) -> List[Transaction]:
+    """Add each account's default tags to the transactions in that account.
+
+    Sync, file imports and `tl new transaction` all pass new transactions
+    through this. A transaction's own tags (provider categories, tags from
+    rules or typed by hand) stay first and in order; default tags it doesn't
+    already have follow. Transactions that gain nothing are returned as is.
+    """
+    defaults = {a.id: a.default_tags for a in accounts if a.default_tags}
+    tagged = []
+    for tx in transactions:
+        tags = tuple(normalize_tags([*tx.tags, *defaults.get(tx.account_id, ())]))
+        tagged.append(tx if tags == tx.tags else tx.model_copy(update={"tags": tags}))
+    return tagged
+
+
+def nearby_fingerprints(tx: Transaction, days: int, version: int) -> List[str]:
+    """Fingerprints tx would have if dated up to days earlier or later, nearest first.
+
+    Each shifted date yields the v1 fingerprint and, for other versions, the
+    fingerprint of a first occurrence, so rows stored under either are found.
+    """
+    fingerprints = []
+    for distance in range(1, days + 1):
+        for offset in (-distance, distance):
+            shifted = tx.model_copy(
+                update={"transaction_date": tx.transaction_date + timedelta(days=offset)}
+            )
+            fingerprints.append(shifted.compute_fingerprint(1))
+            if version != 1:
+                fingerprints.append(shifted.compute_fingerprint(version))
+    return fingerprints
 
 
 class BalanceSnapshot(BaseModel):
@@ -247,11 +521,118 @@ class BalanceSnapshot(BaseModel):
 
     id: UUID
     account_id: UUID
-    balance: Decimal
+    balance: Money
     snapshot_time: datetime  # Naive datetime (local time)
     created_at: datetime  # Timezone-aware (UTC)
     updated_at: datetime  # Timezone-aware (UTC)
-    source: str | None = None  # 'sync', 'manual', 'backfill', or None for legacy
+    source: str | None = None  # 'sync', 'manual', 'backfill', 'auto' (month-end), or None for legacy
+
+    @field_validator("created_at")
+    @classmethod
+    def _require_timezone_created(cls, value: datetime) -> datetime:
+        return _ensure_tzinfo(value)
+
+    @field_validator("updated_at")
+    @classmethod
+    def _require_timezone_updated(cls, value: datetime) -> datetime:
+        return _ensure_tzinfo(value)
+
+
+class ProviderWarning(BaseModel):
+    """A soft error reported by a provider during sync, tracked until resolved."""
+
+    model_config = ConfigDict(frozen=True, str_strip_whitespace=True, extra="forbid")
+
+    id: UUID
+    integration_name: str
+    message: str
+    first_seen: datetime  # Timezone-aware (UTC)
+    last_seen: datetime  # Timezone-aware (UTC)
+    resolved_at: datetime | None = None
+
+    @field_validator("first_seen", "last_seen")
+    @classmethod
+    def _require_timezone(cls, value: datetime) -> datetime:
+        return _ensure_tzinfo(value)
+
+
+class ImportRecord(BaseModel):
+    """One file import, identified by the SHA-256 of the file's bytes."""
+
+    model_config = ConfigDict(frozen=True, str_strip_whitespace=True, extra="forbid")
+
+    id: UUID
+    source_type: str
+    file_name: str | None = None
+    file_sha256: str
+    account_id: UUID
+    discovered: int = 0
+    imported: int = 0
+    skipped: int = 0
+    imported_at: datetime  # Timezone-aware (UTC)
+
+    @field_validator("imported_at")
+    @classmethod
+    def _require_timezone(cls, value: datetime) -> datetime:
+        return _ensure_tzinfo(value)
+
+
+class Attachment(BaseModel):
+    """A file attached to a transaction, e.g. a receipt."""
+
+    model_config = ConfigDict(frozen=True, str_strip_whitespace=True, extra="forbid")
+
+    id: UUID
+    transaction_id: UUID
+    filename: str
+    stored_path: str  # Relative to the attachments directory
+    sha256: str
+    size_bytes: int
+    created_at: datetime  # Timezone-aware (UTC)
+
+    @field_validator("created_at")
+    @classmethod
+    def _require_timezone(cls, value: datetime) -> datetime:
+        return _ensure_tzinfo(value)
+
+
+class PlannedFrequency(str, Enum):
+    """How often a planned transaction repeats."""
+
+    ONCE = "once"
+    WEEKLY = "weekly"
+    BIWEEKLY = "biweekly"
+    MONTHLY = "monthly"
+    QUARTERLY = "quarterly"
+    YEARLY = "yearly"
+
+
+class PlannedTransaction(BaseModel):
+    """An expected transaction, e.g. rent on the 1st of every month."""
+
+    model_config = ConfigDict(frozen=True, str_strip_whitespace=True, extra="forbid")
+
+    id: UUID
+    account_id: UUID
+    description: str
+    amount: Money  # Negative for bills, like transactions
+    frequency: PlannedFrequency
+    # Monthly, quarterly and yearly items fall on this day (clamped to the
+    # month's last day); None keeps next_due's day
+    day_of_month: int | None = Field(default=None, ge=1, le=31)
+    next_due: date
+    autogenerated: bool = False  # Added by recurring-transaction detection
+    last_transaction_id: UUID | None = None  # Transaction that satisfied the last occurrence
+    created_at: datetime  # Timezone-aware (UTC)
+    updated_at: datetime  # Timezone-aware (UTC)
+
+    @field_validator("description")
+    @classmethod
+    def _validate_description(cls, value: str) -> str:
+        if not value:
+            msg = "planned transaction description cannot be empty"
+            raise ValueError(msg)
+        return value
 
     @field_validator("created_at")
     @classmethod
@@ -264,6 +645,128 @@ class BalanceSnapshot(BaseModel):
         return _ensure_tzinfo(value)
 
 
+class DuplicateKind(str, Enum):
+    """What a duplicate candidate pairs up."""
+
+    TRANSACTION = "transaction"
+    ACCOUNT = "account"
+
+
+class DuplicateStatus(str, Enum):
+    """Where a duplicate candidate is in review."""
+
+    OPEN = "open"
+    DISMISSED = "dismissed"
+    MERGED = "merged"
+
+
+class DuplicateCandidate(BaseModel):
+    """A pair of transactions or accounts that look like the same thing."""
+
+    model_config = ConfigDict(frozen=True, extra="forbid")
+
+    id: UUID
+    kind: DuplicateKind
+    keep_id: UUID  # Kept on merge
+    duplicate_id: UUID  # Folded into keep_id on merge
+    score: float = Field(ge=0, le=1)
+    status: DuplicateStatus = DuplicateStatus.OPEN
+    created_at: datetime  # Timezone-aware (UTC)
+    resolved_at: datetime | None = None
+
+    @field_validator("created_at")
+    @classmethod
+    def _require_timezone_created(cls, value: datetime) -> datetime:
+        return _ensure_tzinfo(value)
+
+    @field_validator("resolved_at")
+    @classmethod
+    def _require_timezone_resolved(cls, value: datetime | None) -> datetime | None:
+        return _ensure_tzinfo(value) if value is not None else None
+
+
+class UnmappedTransaction(BaseModel):
+    """A synced transaction held back because its provider account is unknown."""
+
+    model_config = ConfigDict(frozen=True, str_strip_whitespace=True, extra="forbid")
+
+    integration_name: str
+    provider_account_id: str
+    transaction: Transaction
+    first_seen: datetime  # Timezone-aware (UTC)
+
+    @field_validator("first_seen")
+    @classmethod
+    def _require_timezone(cls, value: datetime) -> datetime:
+        return _ensure_tzinfo(value)
+
+
+class HeaderMatch(BaseModel):
+    """How CSV column detection treated one header."""
+
+    model_config = ConfigDict(frozen=True, extra="forbid")
+
+    header: str
+    field: str | None = None  # First field whose patterns match, None if unmatched
+    pattern: str | None = None  # The detection pattern that matched
+    used: bool = False  # Whether the header ended up in the column mapping
+
+
+class ParsedField(BaseModel):
+    """One mapped CSV value before and after parsing."""
+
+    model_config = ConfigDict(frozen=True, extra="forbid")
+
+    column: str
+    raw: str
+    parsed: str | None = None  # None when the value could not be parsed
+
+
+class ParsedRow(BaseModel):
+    """A sampled CSV row as the importer would read it."""
+
+    model_config = ConfigDict(frozen=True, extra="forbid")
+
+    line: int  # 1-based line in the file
+    fields: Dict[str, ParsedField] = Field(default_factory=dict)  # Keyed by mapping field
+    amount: Money | None = None  # Final amount, after debit/credit handling
+    error: str | None = None  # Why the importer would skip this row
+
+
+class CSVPreset(BaseModel):
+    """Known export format of an institution, matched on its exact header names."""
+
+    model_config = ConfigDict(frozen=True, extra="forbid")
+
+    id: str
+    name: str  # Shown as "Detected: <name>"
+    headers: List[str]
+    column_mapping: Dict[str, str]
+    flip_signs: bool = False
+    debit_negative: bool = False
+
+
+class DetectionReport(BaseModel):
+    """Everything CSV auto-detection decided about a file, with its evidence."""
+
+    model_config = ConfigDict(frozen=True, extra="forbid")
+
+    file_path: str
+    delimiter: str
+    encoding: str
+    header_row: int  # 0-based line index of the header row
+    headers: List[HeaderMatch] = Field(default_factory=list)
+    column_mapping: Dict[str, str | None] = Field(default_factory=dict)
+    date_samples: List[str] = Field(default_factory=list)
+    date_format_candidates: List[str] = Field(default_factory=list)  # Parse every sample
+    date_format: str | None = None  # First candidate, None if no format fits all samples
+    date_format_mixed: bool = False  # Samples would be read with different formats
+    debit_samples: List[str] = Field(default_factory=list)
+    negate_debits: bool | None = None  # None without a debit column
+    rows: List[ParsedRow] = Field(default_factory=list)
+    preset: CSVPreset | None = None  # Institution preset the detected mapping came from
+
+
 T = TypeVar("T")
 
 
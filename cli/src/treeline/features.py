@@ -0,0 +1,54 @@
+"""Optional provider features.
+
+The SimpleFIN and demo providers can be left out, e.g. when treeline is
+embedded for import and query only. A feature is enabled when its module
+imports (so a build or install without httpx simply has no SimpleFIN) and
+it isn't listed in TREELINE_DISABLED_FEATURES (comma-separated).
+
+Code outside the container and the command layer never imports these
+providers directly; it asks is_enabled() and degrades.
+"""
+
+import importlib
+import os
+from typing import List
+
+SIMPLEFIN = "simplefin"
+DEMO = "demo"
+
+# Feature name -> module that implements it
+FEATURE_MODULES = {
+    SIMPLEFIN: "treeline.infra.simplefin",
+    DEMO: "treeline.infra.demo",
+}
+
+DISABLED_FEATURES_ENV = "TREELINE_DISABLED_FEATURES"
+
+
+def _disabled_by_env() -> set[str]:
+    value = os.getenv(DISABLED_FEATURES_ENV, "")
+    return {name.strip().lower() for name in value.split(",") if name.strip()}
+
+
+def is_enabled(feature: str) -> bool:
+    """Whether a feature is available in this build."""
+    feature = feature.lower()
+    if feature not in FEATURE_MODULES:
+        return True  # Not an optional feature
+    if feature in _disabled_by_env():
+        return False
+    try:
+        importlib.import_module(FEATURE_MODULES[feature])
+    except ImportError:
+        return False
+    return True
+
+
+def enabled_features() -> List[str]:
+    """Names of the optional features that are available."""
+    return [feature for feature in FEATURE_MODULES if is_enabled(feature)]
+
+
+def disabled_error(feature: str) -> str:
+    """Error message for using a feature this build doesn't have."""
+    return f"treeline was built without {feature.lower()} support"
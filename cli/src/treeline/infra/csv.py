@@ -1,6 +1,9 @@
 """CSV file provider for importing transactions."""
 
+import codecs
 import csv
+import hashlib
+import json
 import re
 from datetime import date, datetime, timezone
 from decimal import Decimal, InvalidOperation
@@ -9,7 +12,187 @@ from typing import Any, Dict, List, Tuple
 from uuid import uuid4
 
 from treeline.abstractions import DataAggregationProvider
-from treeline.domain import Account, BalanceSnapshot, Fail, Ok, Result, Transaction
+from treeline.domain import (
+    Account,
+    BalanceSnapshot,
+    CSVPreset,
+    DetectionReport,
+    Fail,
+    HeaderMatch,
+    Ok,
+    ParsedField,
+    ParsedRow,
+    Result,
+    Transaction,
+)
+from treeline.utils import resolve_user_path
+
+# Raw row text kept in a transaction's csv_source provenance is cut to this length
+CSV_SOURCE_ROW_MAX_LENGTH = 200
+
+HASH_CHUNK_SIZE = 64 * 1024
+
+# Encodings tried in order on a sample of the file; latin-1 decodes any bytes
+CSV_ENCODINGS = ["utf-8-sig", "latin-1"]
+# Delimiters the sniffer may pick, and the one used when it can't decide
+CSV_DELIMITERS = ",;\t|"
+CSV_DEFAULT_DELIMITER = ","
+SNIFF_SAMPLE_BYTES = 64 * 1024
+
+# Rows sampled for date and debit sign detection, and rows shown fully parsed
+DETECTION_SAMPLE_ROWS = 10
+DETECTION_PARSED_ROWS = 3
+
+AUTO_DATE_FORMATS = [
+    "%Y-%m-%d",  # 2024-10-01
+    "%m/%d/%Y",  # 10/01/2024
+    "%d/%m/%Y",  # 01/10/2024
+    "%Y/%m/%d",  # 2024/10/01
+    "%m-%d-%Y",  # 10-01-2024
+    "%d-%m-%Y",  # 01-10-2024
+]
+
+DATE_FORMAT_NAMES = {
+    "YYYY-MM-DD": "%Y-%m-%d",
+    "MM/DD/YYYY": "%m/%d/%Y",
+    "DD/MM/YYYY": "%d/%m/%Y",
+    "YYYY/MM/DD": "%Y/%m/%d",
+}
+
+# Expanded patterns for column detection with fuzzy matching
+DATE_PATTERNS = [
+    "date",
+    "transaction date",
+    "trans date",
+    "txn date",
+    "txndate",
+    "posted",
+    "post date",
+    "dt",  # Abbreviated
+]
+DESCRIPTION_PATTERNS = [
+    "description",
+    "desc",  # Abbreviated
+    "memo",
+    "payee",
+    "merchant",
+    "details",
+    "narration",  # International
+]
+DESCRIPTION_FALLBACK_PATTERNS = ["name", "type", "ref", "reference", "category"]
+AMOUNT_PATTERNS = [
+    "amount",
+    "amt",  # Abbreviated
+    "total",
+    "transaction amount",
+]
+DEBIT_PATTERNS = [
+    "debit",
+    "dr",  # International abbreviation
+    "withdrawal",
+    "debit amount",
+]
+CREDIT_PATTERNS = [
+    "credit",
+    "cr",  # International abbreviation
+    "deposit",
+    "credit amount",
+]
+CURRENCY_PATTERNS = [
+    "currency",
+    "ccy",  # Abbreviated
+    "iso currency code",
+]
+
+# Order in which an unused header is attributed to a field in detection reports
+HEADER_FIELD_PATTERNS = [
+    ("date", DATE_PATTERNS),
+    ("amount", AMOUNT_PATTERNS),
+    ("debit", DEBIT_PATTERNS),
+    ("credit", CREDIT_PATTERNS),
+    ("currency", CURRENCY_PATTERNS),
+    ("description", DESCRIPTION_PATTERNS),
+    ("description", DESCRIPTION_FALLBACK_PATTERNS),
+]
+
+
+# Institution export formats, consulted before the patterns above. Shared with
+# the desktop app, which compiles the same file in.
+CSV_PRESETS_FILE = Path(__file__).parent / "csv_presets.json"
+
+
+def load_presets() -> List[CSVPreset]:
+    """Institution presets bundled with treeline."""
+    data = json.loads(CSV_PRESETS_FILE.read_text(encoding="utf-8"))
+    return [CSVPreset(**preset) for preset in data["presets"]]
+
+
+def _normalize_header(header: str) -> str:
+    return header.lstrip("\ufeff").strip().lower()
+
+
+def match_preset(headers: List[str]) -> CSVPreset | None:
+    """The preset with exactly these header names, in any order and any case.
+
+    The returned preset's mapping uses the file's own spelling of each header.
+    """
+    actual = {_normalize_header(header): header for header in headers}
+    for preset in load_presets():
+        if set(actual) != {_normalize_header(header) for header in preset.headers}:
+            continue
+        mapping = {
+            field: actual[_normalize_header(column)]
+            for field, column in preset.column_mapping.items()
+        }
+        return preset.model_copy(update={"column_mapping": mapping})
+    return None
+
+
+def sniff_csv_format(path: Path) -> Tuple[str, str]:
+    """The encoding and delimiter to read the file with, from a sample of it.
+
+    A UTF-8 file is reported as "utf-8", or "utf-8-sig" when it starts with
+    a byte order mark.
+    """
+    with open(path, "rb") as f:
+        raw = f.read(SNIFF_SAMPLE_BYTES)
+        if f.read(1):
+            # Don't cut a multi-byte character or a row in half
+            raw = raw[: raw.rfind(b"\n") + 1] or raw
+
+    for encoding in CSV_ENCODINGS:
+        try:
+            sample = raw.decode(encoding)
+            break
+        except UnicodeDecodeError:
+            continue
+    if encoding == "utf-8-sig" and not raw.startswith(codecs.BOM_UTF8):
+        encoding = "utf-8"
+
+    try:
+        delimiter = csv.Sniffer().sniff(sample, delimiters=CSV_DELIMITERS).delimiter
+    except csv.Error:
+        delimiter = CSV_DEFAULT_DELIMITER
+    return encoding, delimiter
+
+
+def _match_pattern(text: str, patterns: List[str]) -> str | None:
+    """First pattern contained in text."""
+    return next((pattern for pattern in patterns if pattern in text), None)
+
+
+def _strip_currency_suffix(header: str) -> str:
+    """Lowercased header without a trailing currency code like " USD"."""
+    return re.sub(r"\s+(usd|eur|gbp|cad|aud)$", "", header.lower().strip())
+
+
+def _strptime(value: str, fmt: str) -> date | None:
+    """Parse a date with one format, None if it does not fit."""
+    try:
+        # Return date object, not datetime (no timezone conversion)
+        return datetime.strptime(value, fmt).date()
+    except ValueError:
+        return None
 
 
 class CSVProvider(DataAggregationProvider):
@@ -42,7 +225,15 @@ class CSVProvider(DataAggregationProvider):
         provider_account_ids: List[str] = [],
         provider_settings: Dict[str, Any] | None = None,
     ) -> Result[List[Transaction]]:
-        """Parse CSV file and return transactions."""
+        """Parse CSV file and return transactions.
+
+        Each transaction records where it came from in external_ids["csv_source"]
+        (JSON with file_sha256, the 1-based line number, the truncated raw row,
+        and import_id when provider_settings has one). With a currency column,
+        a row's non-empty currency is kept, uppercased, in
+        external_ids["currency"] for ImportService to compare with the
+        account's.
+        """
         if not provider_settings:
             return Fail("provider_settings is required")
 
@@ -59,16 +250,33 @@ class CSVProvider(DataAggregationProvider):
         debit_negative = provider_settings.get("debit_negative", False)
 
         # Check if file exists
-        path = Path(file_path)
+        path = resolve_user_path(file_path)
         if not path.exists():
             return Fail(f"File not found: {file_path}")
 
+        file_sha256 = provider_settings.get("file_sha256")
+        if not file_sha256:
+            hash_result = self.file_sha256(path)
+            if not hash_result.success:
+                return hash_result
+            file_sha256 = hash_result.data
+        import_id = provider_settings.get("import_id")
+
         try:
-            with open(path, "r", encoding="utf-8") as f:
-                reader = csv.DictReader(f)
+            encoding, delimiter = sniff_csv_format(path)
+            with open(path, "r", encoding=encoding) as f:
+                reader = csv.DictReader(f, delimiter=delimiter)
                 transactions = []
 
+                # A row starts on the line after the previous row ended (rows
+                # with quoted newlines span several lines), and the first one
+                # after the header, which reading fieldnames consumes.
+                if reader.fieldnames is None:
+                    return Ok([])
+                next_line = reader.line_num + 1
                 for row in reader:
+                    line_number, next_line = next_line, reader.line_num + 1
+
                     # Parse transaction from CSV row
                     tx_result = self._parse_transaction_row(
                         row, column_mapping, date_format, debit_negative
@@ -83,6 +291,13 @@ class CSVProvider(DataAggregationProvider):
                     if flip_signs:
                         tx = tx.model_copy(update={"amount": -tx.amount})
 
+                    source = {"file_sha256": file_sha256, "line": line_number}
+                    if import_id:
+                        source["import_id"] = str(import_id)
+                    source["row"] = self._raw_row_text(row)
+                    external_ids = {**tx.external_ids, "csv_source": json.dumps(source)}
+                    tx = tx.model_copy(update={"external_ids": external_ids})
+
                     transactions.append(tx)
 
                 return Ok(transactions)
@@ -98,6 +313,28 @@ class CSVProvider(DataAggregationProvider):
         """CSV provider does not support getting balances."""
         return Fail("CSV provider does not support getting balances")
 
+    def file_sha256(self, file_path: str | Path) -> Result[str]:
+        """SHA-256 of the file's bytes, read in chunks."""
+        try:
+            digest = hashlib.sha256()
+            with open(resolve_user_path(file_path), "rb") as f:
+                for chunk in iter(lambda: f.read(HASH_CHUNK_SIZE), b""):
+                    digest.update(chunk)
+            return Ok(digest.hexdigest())
+        except Exception as e:
+            return Fail(f"Failed to hash file: {str(e)}")
+
+    def _raw_row_text(self, row: Dict[str, Any]) -> str:
+        """The row's values joined back together, truncated for storage."""
+        values = []
+        for value in row.values():
+            # Extra unnamed columns are collected into a list by DictReader
+            if isinstance(value, list):
+                values.extend(value)
+            else:
+                values.append(value or "")
+        return ",".join(values)[:CSV_SOURCE_ROW_MAX_LENGTH]
+
     def _parse_transaction_row(
         self,
         row: Dict[str, str],
@@ -114,6 +351,7 @@ class CSVProvider(DataAggregationProvider):
             debit_col = column_mapping.get("debit")
             credit_col = column_mapping.get("credit")
             posted_date_col = column_mapping.get("posted_date")
+            currency_col = column_mapping.get("currency")
 
             if not date_col:
                 return Fail("date column is required in column_mapping")
@@ -197,6 +435,12 @@ class CSVProvider(DataAggregationProvider):
                 raw_description = row.get(description_col, "").strip()
                 description = self._clean_description(raw_description)
 
+            external_ids = {}
+            if currency_col:
+                currency = row.get(currency_col, "").strip().upper()
+                if currency:
+                    external_ids["currency"] = currency
+
             # Create transaction
             # Note: account_id will be set by ImportService when mapping to target account
             transaction = Transaction(
@@ -204,6 +448,7 @@ class CSVProvider(DataAggregationProvider):
                 account_id=uuid4(),  # Placeholder, will be replaced by ImportService
                 amount=amount,
                 description=description,
+                external_ids=external_ids,
                 transaction_date=transaction_date,
                 posted_date=posted_date,
                 created_at=datetime.now(timezone.utc),
@@ -220,38 +465,29 @@ class CSVProvider(DataAggregationProvider):
         if not date_str:
             return None
 
-        # Try auto-detection if format is "auto"
-        if date_format == "auto":
-            formats = [
-                "%Y-%m-%d",  # 2024-10-01
-                "%m/%d/%Y",  # 10/01/2024
-                "%d/%m/%Y",  # 01/10/2024
-                "%Y/%m/%d",  # 2024/10/01
-                "%m-%d-%Y",  # 10-01-2024
-                "%d-%m-%Y",  # 01-10-2024
-            ]
-        else:
-            # Map common format names to strftime formats
-            format_map = {
-                "YYYY-MM-DD": "%Y-%m-%d",
-                "MM/DD/YYYY": "%m/%d/%Y",
-                "DD/MM/YYYY": "%d/%m/%Y",
-                "YYYY/MM/DD": "%Y/%m/%d",
-            }
-            fmt = format_map.get(date_format)
-            if not fmt:
-                return None
-            formats = [fmt]
+        formats = self._date_formats(date_format)
 
         # Try each format
         for fmt in formats:
-            try:
-                dt = datetime.strptime(date_str, fmt)
-                # Return date object, not datetime (no timezone conversion)
-                return dt.date()
-            except ValueError:
-                continue
+            parsed = _strptime(date_str, fmt)
+            if parsed:
+                return parsed
+
+        return None
 
+    def _date_formats(self, date_format: str) -> List[str]:
+        """strftime formats to try, in order, for a date_format setting."""
+        if date_format == "auto":
+            return list(AUTO_DATE_FORMATS)
+        # Map common format names to strftime formats
+        fmt = DATE_FORMAT_NAMES.get(date_format)
+        return [fmt] if fmt else []
+
+    def _date_format_for(self, date_str: str, date_format: str) -> str | None:
+        """The format _parse_date would use for a value."""
+        for fmt in self._date_formats(date_format):
+            if _strptime(date_str, fmt):
+                return fmt
         return None
 
     def _parse_amount(self, amount_str: str) -> Decimal | None:
@@ -298,117 +534,230 @@ class CSVProvider(DataAggregationProvider):
 
         return cleaned
 
-    def detect_columns(self, file_path: str) -> Result[Dict[str, str]]:
+    def detect_columns(self, file_path: str | Path) -> Result[Dict[str, str]]:
         """Auto-detect column mapping from CSV headers.
 
-        Returns best-guess mapping for date, amount, and description columns.
+        Returns the mapping of a matching institution preset, otherwise the
+        best-guess mapping for date, amount, and description columns.
         """
         try:
-            with open(file_path, "r", encoding="utf-8") as f:
-                reader = csv.DictReader(f)
+            headers = self._read_headers(file_path)
+            detected, _, _ = self._detect_mapping(headers)
+            return Ok(detected)
+
+        except Exception as e:
+            return Fail(f"Failed to detect columns: {str(e)}")
+
+    def detect_preset(self, file_path: str | Path) -> Result[CSVPreset | None]:
+        """The institution preset matching the file's headers, if any."""
+        try:
+            return Ok(match_preset(self._read_headers(file_path)))
+        except Exception as e:
+            return Fail(f"Failed to detect columns: {str(e)}")
+
+    def _read_headers(self, file_path: str | Path) -> List[str]:
+        path = resolve_user_path(file_path)
+        encoding, delimiter = sniff_csv_format(path)
+        with open(path, "r", encoding=encoding) as f:
+            reader = csv.DictReader(f, delimiter=delimiter)
+            return reader.fieldnames or []
+
+    def detection_report(
+        self,
+        file_path: str | Path,
+        column_mapping: Dict[str, str] | None = None,
+        date_format: str = "auto",
+        debit_negative: bool = False,
+    ) -> Result[DetectionReport]:
+        """Explain how the file would be read, without importing anything.
+
+        Covers header matching, the resulting column mapping (detected unless
+        one is given), the date format decision, the debit sign verdict and
+        the first rows parsed field by field. A detected mapping that came
+        from an institution preset names it, and its rows are parsed with the
+        preset's debit convention.
+        """
+        try:
+            path = resolve_user_path(file_path)
+            encoding, delimiter = sniff_csv_format(path)
+            with open(path, "r", encoding=encoding) as f:
+                reader = csv.DictReader(f, delimiter=delimiter)
                 headers = reader.fieldnames or []
+                sampled = []
+                next_line = reader.line_num + 1
+                for row in reader:
+                    sampled.append((next_line, row))
+                    next_line = reader.line_num + 1
+                    if len(sampled) >= DETECTION_SAMPLE_ROWS:
+                        break
 
-            # Expanded patterns for column detection with fuzzy matching
-            date_patterns = [
-                "date",
-                "transaction date",
-                "trans date",
-                "txn date",
-                "txndate",
-                "posted",
-                "post date",
-                "dt",  # Abbreviated
+            detected, header_matches, preset = self._detect_mapping(headers)
+            mapping = column_mapping or detected
+            if column_mapping:
+                preset = None
+                mapped_columns = set(column_mapping.values())
+                header_matches = [
+                    match.model_copy(update={"used": match.header in mapped_columns})
+                    for match in header_matches
+                ]
+            elif preset:
+                debit_negative = debit_negative or preset.debit_negative
+            rows = [row for _, row in sampled]
+
+            date_samples = []
+            if mapping.get("date"):
+                date_samples = [
+                    row.get(mapping["date"], "").strip()
+                    for row in rows
+                    if row.get(mapping["date"], "").strip()
+                ]
+            candidates = [
+                fmt
+                for fmt in self._date_formats(date_format)
+                if date_samples and all(_strptime(value, fmt) for value in date_samples)
             ]
-            desc_patterns = [
-                "description",
-                "desc",  # Abbreviated
-                "memo",
-                "payee",
-                "merchant",
-                "details",
-                "narration",  # International
-            ]
-            amount_patterns = [
-                "amount",
-                "amt",  # Abbreviated
-                "total",
-                "transaction amount",
-            ]
-            debit_patterns = [
-                "debit",
-                "dr",  # International abbreviation
-                "withdrawal",
-                "debit amount",
-            ]
-            credit_patterns = [
-                "credit",
-                "cr",  # International abbreviation
-                "deposit",
-                "credit amount",
+            formats_used = {
+                self._date_format_for(value, date_format) for value in date_samples
+            }
+
+            negate_debits = None
+            debit_samples: List[str] = []
+            if mapping.get("debit"):
+                negate_debits, debit_samples = self._debit_sign_verdict(
+                    rows, mapping["debit"]
+                )
+
+            parsed_rows = [
+                self._explain_row(line, row, mapping, date_format, debit_negative)
+                for line, row in sampled[:DETECTION_PARSED_ROWS]
             ]
 
-            detected = {}
+            return Ok(
+                DetectionReport(
+                    file_path=str(file_path),
+                    delimiter=delimiter,
+                    encoding=encoding,
+                    header_row=0,
+                    headers=header_matches,
+                    column_mapping=mapping,
+                    date_samples=date_samples,
+                    date_format_candidates=candidates,
+                    date_format=candidates[0] if candidates else None,
+                    date_format_mixed=len(formats_used - {None}) > 1,
+                    debit_samples=debit_samples,
+                    negate_debits=negate_debits,
+                    rows=parsed_rows,
+                    preset=preset,
+                )
+            )
 
-            # Find date column
-            for header in headers:
-                header_lower = header.lower().strip()
-                if any(pattern in header_lower for pattern in date_patterns):
-                    detected["date"] = header
-                    break
+        except Exception as e:
+            return Fail(f"Failed to analyze CSV file: {str(e)}")
 
-            # Find amount column (prefer single amount column) - with fuzzy matching
-            for header in headers:
-                header_lower = header.lower().strip()
-                # Remove common suffixes like currency codes for fuzzy matching
-                header_clean = re.sub(r"\s+(usd|eur|gbp|cad|aud)$", "", header_lower)
-                if any(pattern in header_clean for pattern in amount_patterns):
-                    detected["amount"] = header
-                    break
+    def _detect_mapping(
+        self, headers: List[str]
+    ) -> Tuple[Dict[str, str], List[HeaderMatch], CSVPreset | None]:
+        """Match headers against the institution presets, then the detection patterns.
 
-            # If no 'amount' found, check for debit/credit
-            if "amount" not in detected:
-                debit_col = None
-                credit_col = None
-                for header in headers:
-                    header_lower = header.lower().strip()
-                    if any(pattern in header_lower for pattern in debit_patterns):
-                        debit_col = header
-                    if any(pattern in header_lower for pattern in credit_patterns):
-                        credit_col = header
-
-                if debit_col or credit_col:
-                    detected["debit"] = debit_col
-                    detected["credit"] = credit_col
-
-            # Find description column with fallback
+        Returns the column mapping, for every header the first pattern it
+        matched and whether it was used, and the preset the mapping came from.
+        """
+        preset = match_preset(headers)
+        if preset:
+            fields = {column: field for field, column in preset.column_mapping.items()}
+            matches = [
+                HeaderMatch(
+                    header=header,
+                    field=fields.get(header),
+                    pattern=f"preset:{preset.id}" if header in fields else None,
+                    used=header in fields,
+                )
+                for header in headers
+            ]
+            return dict(preset.column_mapping), matches, preset
+
+        detected = {}
+        used: Dict[str, Tuple[str, str]] = {}
+
+        # Find date column
+        for header in headers:
+            pattern = _match_pattern(header.lower().strip(), DATE_PATTERNS)
+            if pattern:
+                detected["date"] = header
+                used[header] = ("date", pattern)
+                break
+
+        # Find amount column (prefer single amount column) - with fuzzy matching
+        for header in headers:
+            pattern = _match_pattern(_strip_currency_suffix(header), AMOUNT_PATTERNS)
+            if pattern:
+                detected["amount"] = header
+                used[header] = ("amount", pattern)
+                break
+
+        # If no 'amount' found, check for debit/credit
+        if "amount" not in detected:
+            debit_col = None
+            credit_col = None
             for header in headers:
                 header_lower = header.lower().strip()
+                debit_pattern = _match_pattern(header_lower, DEBIT_PATTERNS)
+                if debit_pattern:
+                    debit_col = (header, debit_pattern)
+                credit_pattern = _match_pattern(header_lower, CREDIT_PATTERNS)
+                if credit_pattern:
+                    credit_col = (header, credit_pattern)
+
+            if debit_col or credit_col:
+                detected["debit"] = debit_col[0] if debit_col else None
+                detected["credit"] = credit_col[0] if credit_col else None
+                for field, match in (("debit", debit_col), ("credit", credit_col)):
+                    if match:
+                        used[match[0]] = (field, match[1])
+
+        # Find currency column (multi-currency exports)
+        for header in headers:
+            if header in used:
+                continue
+            pattern = _match_pattern(header.lower().strip(), CURRENCY_PATTERNS)
+            if pattern:
+                detected["currency"] = header
+                used[header] = ("currency", pattern)
+                break
+
+        # Find description column, then fall back to weaker names
+        for field_patterns in (DESCRIPTION_PATTERNS, DESCRIPTION_FALLBACK_PATTERNS):
+            for header in headers:
                 # Skip if this is a date column
                 if detected.get("date") and header == detected["date"]:
                     continue
-                if any(pattern in header_lower for pattern in desc_patterns):
+                pattern = _match_pattern(header.lower().strip(), field_patterns)
+                if pattern:
                     detected["description"] = header
+                    used[header] = ("description", pattern)
                     break
+            if "description" in detected:
+                break
+
+        matches = []
+        for header in headers:
+            if header in used:
+                field, pattern = used[header]
+                matches.append(HeaderMatch(header=header, field=field, pattern=pattern, used=True))
+                continue
+            field, pattern = None, None
+            for candidate_field, patterns in HEADER_FIELD_PATTERNS:
+                text = _strip_currency_suffix(header) if candidate_field == "amount" else header.lower().strip()
+                pattern = _match_pattern(text, patterns)
+                if pattern:
+                    field = candidate_field
+                    break
+            matches.append(HeaderMatch(header=header, field=field, pattern=pattern))
 
-            # If no description found, try fallback columns
-            if "description" not in detected:
-                fallback_patterns = ["name", "type", "ref", "reference", "category"]
-                for header in headers:
-                    header_lower = header.lower().strip()
-                    # Skip if this is a date column
-                    if detected.get("date") and header == detected["date"]:
-                        continue
-                    if any(pattern in header_lower for pattern in fallback_patterns):
-                        detected["description"] = header
-                        break
-
-            return Ok(detected)
-
-        except Exception as e:
-            return Fail(f"Failed to detect columns: {str(e)}")
+        return detected, matches, None
 
     def should_negate_debits(
-        self, file_path: str, debit_col: str, credit_col: str
+        self, file_path: str | Path, debit_col: str, credit_col: str
     ) -> Result[bool]:
         """Detect if debit values should be negated (unsigned debit/credit convention).
 
@@ -416,34 +765,81 @@ class CSVProvider(DataAggregationProvider):
         Returns False if debits are already signed (negative) or mixed.
         """
         try:
-            with open(file_path, "r", encoding="utf-8") as f:
-                reader = csv.DictReader(f)
-
-                debit_values = []
+            path = resolve_user_path(file_path)
+            encoding, delimiter = sniff_csv_format(path)
+            with open(path, "r", encoding=encoding) as f:
+                reader = csv.DictReader(f, delimiter=delimiter)
+                rows = []
                 for i, row in enumerate(reader):
-                    if i >= 10:  # Sample first 10 rows
+                    if i >= DETECTION_SAMPLE_ROWS:
                         break
+                    rows.append(row)
 
-                    debit_str = row.get(debit_col, "").strip()
-                    if debit_str:
-                        debit_amt = self._parse_amount(debit_str)
-                        if debit_amt is not None:
-                            debit_values.append(debit_amt)
-
-                # If we have debit values and they're all positive, suggest negating
-                if len(debit_values) >= 2:  # Need at least 2 samples
-                    all_positive = all(amt > 0 for amt in debit_values)
-                    return Ok(all_positive)
-
-                # Not enough data to determine
-                return Ok(False)
+            negate, _ = self._debit_sign_verdict(rows, debit_col)
+            return Ok(negate)
 
         except Exception as e:
             return Fail(f"Failed to analyze debit convention: {str(e)}")
 
+    def _debit_sign_verdict(
+        self, rows: List[Dict[str, str]], debit_col: str
+    ) -> Tuple[bool, List[str]]:
+        """Whether sampled debits are all unsigned positives, and the samples used."""
+        samples = []
+        debit_values = []
+        for row in rows:
+            debit_str = (row.get(debit_col) or "").strip()
+            if debit_str:
+                debit_amt = self._parse_amount(debit_str)
+                if debit_amt is not None:
+                    samples.append(debit_str)
+                    debit_values.append(debit_amt)
+
+        # Need at least 2 samples; all positive suggests negating
+        if len(debit_values) >= 2:
+            return all(amt > 0 for amt in debit_values), samples
+
+        # Not enough data to determine
+        return False, samples
+
+    def _explain_row(
+        self,
+        line: int,
+        row: Dict[str, str],
+        column_mapping: Dict[str, str],
+        date_format: str,
+        debit_negative: bool,
+    ) -> ParsedRow:
+        """Parse one row field by field, keeping raw and parsed values."""
+        fields = {}
+        for field, column in column_mapping.items():
+            if not column:
+                continue
+            raw = row.get(column) or ""
+            value = raw.strip()
+            if field in ("date", "posted_date"):
+                parsed_date = self._parse_date(value, date_format)
+                parsed = parsed_date.isoformat() if parsed_date else None
+            elif field in ("amount", "debit", "credit"):
+                parsed_amount = self._parse_amount(value)
+                parsed = str(parsed_amount) if parsed_amount is not None else None
+            elif field == "currency":
+                parsed = value.upper() or None
+            else:
+                parsed = self._clean_description(value)
+            fields[field] = ParsedField(column=column, raw=raw, parsed=parsed)
+
+        tx_result = self._parse_transaction_row(row, column_mapping, date_format, debit_negative)
+        return ParsedRow(
+            line=line,
+            fields=fields,
+            amount=tx_result.data.amount if tx_result.success else None,
+            error=None if tx_result.success else tx_result.error,
+        )
+
     def preview_transactions(
         self,
-        file_path: str,
+        file_path: str | Path,
         column_mapping: Dict[str, str],
         date_format: str = "auto",
         limit: int = 5,
@@ -455,8 +851,10 @@ class CSVProvider(DataAggregationProvider):
         This is used to show the user what will be imported before committing.
         """
         try:
-            with open(file_path, "r", encoding="utf-8") as f:
-                reader = csv.DictReader(f)
+            path = resolve_user_path(file_path)
+            encoding, delimiter = sniff_csv_format(path)
+            with open(path, "r", encoding=encoding) as f:
+                reader = csv.DictReader(f, delimiter=delimiter)
                 transactions = []
 
                 for i, row in enumerate(reader):
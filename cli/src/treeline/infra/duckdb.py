@@ -2,33 +2,192 @@
 
 import json
 import os
-from datetime import datetime, timezone
+import re
+import threading
+from datetime import date, datetime, timezone
 from decimal import Decimal
 from pathlib import Path
 from types import MappingProxyType
-from typing import Any, Dict, List
-from uuid import UUID
+from typing import Any, Callable, Dict, Iterable, List, Tuple, TypeVar
+from uuid import UUID, uuid4
 
 import duckdb
 
 from treeline.abstractions import Repository
-from treeline.domain import Account, BalanceSnapshot, Fail, Ok, Result, Transaction
+from treeline.abstractions.db import RowTransform
+from treeline.domain import (
+    MAX_AMOUNT,
+    Account,
+    Attachment,
+    BalanceSnapshot,
+    DuplicateCandidate,
+    DuplicateStatus,
+    Fail,
+    ImportRecord,
+    Ok,
+    PlannedTransaction,
+    ProviderWarning,
+    Result,
+    Transaction,
+    TransactionStatus,
+    UnmappedTransaction,
+    amount_in_range,
+    balance_from_anchor,
+)
+from treeline.utils import get_app_version
+
+MIGRATIONS_DIR = Path(__file__).parent / "migrations"
+
+T = TypeVar("T")
+
+# Set to 1 to make failed writes raise StrictWriteError instead of returning Fail
+STRICT_WRITES_ENV = "TREELINE_STRICT_WRITES"
+
+# Fingerprints looked up per query; fuzzy dedup multiplies an import's set
+FINGERPRINT_LOOKUP_CHUNK_SIZE = 1000
+
+
+# COPY ... TO options per export format
+COPY_FORMATS = {
+    "parquet": "FORMAT PARQUET",
+    "csv": "FORMAT CSV, HEADER",
+    "json": "FORMAT JSON, ARRAY true",
+}
+
+
+def _sql_string(value: str) -> str:
+    """A SQL string literal, for the places DuckDB can't bind a parameter."""
+    return "'" + value.replace("'", "''") + "'"
+
+
+class StrictWriteError(AssertionError):
+    """A repository write failed while strict_writes was on.
+
+    Used in tests so a caller that ignores a failed write's Result (and
+    reports success with missing data) fails loudly instead.
+    """
+
+
+def get_supported_data_version() -> int:
+    """Get the data version this build understands (highest migration number shipped)."""
+    numbers = [
+        int(f.name.split("_", 1)[0])
+        for f in MIGRATIONS_DIR.glob("*.sql")
+        if f.name.split("_", 1)[0].isdigit()
+    ]
+    return max(numbers, default=0)
+
+
+_VIEW_STATEMENT = re.compile(
+    r"CREATE\s+OR\s+REPLACE\s+VIEW\s+(\w+)\s+AS\b.*?;", re.IGNORECASE | re.DOTALL
+)
+
+
+def get_view_definitions() -> Dict[str, str]:
+    """Get the latest CREATE OR REPLACE VIEW statement for each view in the migrations.
+
+    Later migrations replace earlier definitions, so this is what a fully
+    migrated database's views should be.
+    """
+    definitions: Dict[str, str] = {}
+    for migration_file in sorted(MIGRATIONS_DIR.glob("*.sql")):
+        for match in _VIEW_STATEMENT.finditer(migration_file.read_text()):
+            definitions[match.group(1).lower()] = match.group(0)
+    return definitions
 
 
 class DuckDBRepository(Repository):
     """DuckDB implementation of Repository."""
 
-    def __init__(self, db_file_path: str, encryption_key: str | None = None):
+    def __init__(
+        self,
+        db_file_path: str,
+        encryption_key: str | None = None,
+        strict_writes: bool | None = None,
+    ):
         """Initialize with a database file path and optional encryption key.
 
         Args:
             db_file_path: Full path to the DuckDB database file
             encryption_key: Hex-encoded encryption key (if database is encrypted)
+            strict_writes: Raise StrictWriteError when a write fails instead of
+                returning Fail (defaults to TREELINE_STRICT_WRITES=1; for tests)
         """
         self.db_path = Path(db_file_path)
         self.db_dir = self.db_path.parent
         self.db_dir.mkdir(parents=True, exist_ok=True)
         self._encryption_key = encryption_key
+        if strict_writes is None:
+            strict_writes = os.environ.get(STRICT_WRITES_ENV) == "1"
+        self.strict_writes = strict_writes
+
+    @staticmethod
+    def _check_amounts(kind: str, records: Iterable[Tuple[Any, Decimal | None]]) -> Result | None:
+        """Fail naming the first record whose amount doesn't fit the money columns.
+
+        records are (ID, amount) pairs; kind ("transaction", "balance
+        snapshot", ...) names them in the error. Checked before writing, so
+        nothing is truncated or half-written.
+        """
+        for record_id, amount in records:
+            if amount is not None and not amount_in_range(amount):
+                return Fail(
+                    f"{kind.capitalize()} {record_id} has amount {amount}, beyond the largest "
+                    f"amount that can be stored (±{MAX_AMOUNT})",
+                    context={
+                        "kind": "amount_out_of_range",
+                        "record": kind,
+                        "id": str(record_id),
+                        "amount": str(amount),
+                        "max": str(MAX_AMOUNT),
+                    },
+                )
+        return None
+
+    def _write_failed(self, context: str, error: Exception) -> Result:
+        """Fail a write with context (what was being written), or raise in strict mode."""
+        message = f"{context}: {error}"
+        if self.strict_writes:
+            raise StrictWriteError(message) from error
+        return Fail(message)
+
+    @staticmethod
+    def _settings_json(settings: Dict[str, Any]) -> str:
+        """Serialize settings as strict JSON (NaN/Infinity are not valid JSON).
+
+        Keys are sorted so the stored settings don't depend on insertion order.
+        """
+        return json.dumps(settings, allow_nan=False, sort_keys=True)
+
+    @staticmethod
+    def _map_rows(
+        table: str,
+        key_column: str,
+        rows: List[Any],
+        key: Callable[[Any], Any],
+        build: Callable[[Any], T],
+    ) -> Tuple[List[T], List[str]]:
+        """Build one model per row, skipping rows that can't be read.
+
+        A corrupted row (bad ID, missing date, invalid JSON) is reported
+        rather than failing the whole read or being papered over with a
+        default. Returns the models and one message per skipped row, naming
+        the table and the row's primary key.
+        """
+        models, row_errors = [], []
+        for row in rows:
+            try:
+                models.append(build(row))
+            except Exception as e:
+                row_errors.append(
+                    f"Skipped unreadable row in {table} ({key_column} {key(row)!r}): {e}"
+                )
+        return models, row_errors
+
+    @staticmethod
+    def _with_row_errors(data: Any, row_errors: List[str]) -> Result:
+        """Ok result carrying skipped-row messages in context["row_errors"]."""
+        return Ok(data, context={"row_errors": row_errors} if row_errors else None)
 
     def _ensure_timezone(self, dt: datetime) -> datetime:
         """Ensure datetime is timezone-aware."""
@@ -79,7 +238,7 @@ class DuckDBRepository(Repository):
             # Create database if it doesn't exist - use _get_connection for encryption support
             conn = self._get_connection()
 
-            migrations_dir = Path(__file__).parent / "migrations"
+            migrations_dir = MIGRATIONS_DIR
 
             # Check if sys_migrations table exists
             tables_result = conn.execute(
@@ -107,34 +266,180 @@ class DuckDBRepository(Repository):
                 ).fetchall()
 
                 if not result:
-                    # Migration hasn't been applied yet, run it
+                    # Migration hasn't been applied yet. Run it and record it
+                    # together, so a failed record can't leave it half-applied.
                     with open(migration_file, "r") as f:
                         migration_sql = f.read()
-                    conn.execute(migration_sql)
+                    conn.execute("BEGIN TRANSACTION")
+                    try:
+                        conn.execute(migration_sql)
+                        conn.execute(
+                            "INSERT INTO sys_migrations (migration_name) VALUES (?)",
+                            [migration_name],
+                        )
+                        conn.execute("COMMIT")
+                    except Exception as e:
+                        self._rollback_quietly(conn)
+                        return self._write_failed(
+                            f"Failed to initialize database: migration {migration_name}", e
+                        )
 
-                    # Record that this migration has been applied
-                    conn.execute(
-                        "INSERT INTO sys_migrations (migration_name) VALUES (?)",
-                        [migration_name],
-                    )
+            self._record_data_version(conn)
 
             conn.close()
             return Ok()
         except Exception as e:
-            return Fail(f"Failed to initialize database: {str(e)}")
+            return self._write_failed("Failed to initialize database", e)
+
+    def _record_data_version(self, conn: duckdb.DuckDBPyConnection) -> None:
+        """Write the data version to sys_meta, never lowering an existing value."""
+        supported = get_supported_data_version()
+        row = conn.execute(
+            "SELECT value FROM sys_meta WHERE key = 'data_version'"
+        ).fetchone()
+        if row and int(row[0]) >= supported:
+            return
+
+        for key, value in (
+            ("data_version", str(supported)),
+            ("min_app_version", get_app_version()),
+        ):
+            conn.execute(
+                """
+                INSERT INTO sys_meta (key, value, updated_at)
+                VALUES (?, ?, CURRENT_TIMESTAMP)
+                ON CONFLICT (key) DO UPDATE SET
+                    value = EXCLUDED.value,
+                    updated_at = EXCLUDED.updated_at
+                """,
+                [key, value],
+            )
+
+    async def get_data_version(self) -> Result[Dict[str, Any]]:
+        """Get the data version recorded in sys_meta."""
+        data: Dict[str, Any] = {
+            "data_version": None,
+            "min_app_version": None,
+            "supported_data_version": get_supported_data_version(),
+        }
+        if not self.db_path.exists():
+            return Ok(data)
+
+        try:
+            conn = self._get_connection(read_only=True)
+            try:
+                has_meta = conn.execute(
+                    "SELECT 1 FROM information_schema.tables WHERE table_name = 'sys_meta'"
+                ).fetchall()
+                if has_meta:
+                    rows = conn.execute(
+                        "SELECT key, value FROM sys_meta WHERE key IN ('data_version', 'min_app_version')"
+                    ).fetchall()
+                    meta = dict(rows)
+                    if "data_version" in meta:
+                        data["data_version"] = int(meta["data_version"])
+                    data["min_app_version"] = meta.get("min_app_version")
+            finally:
+                conn.close()
+            return Ok(data)
+        except Exception as e:
+            return Fail(f"Failed to read data version: {str(e)}")
+
+    async def get_migration_status(self) -> Result[Dict[str, Any]]:
+        """Get the migrations recorded in sys_migrations."""
+        data: Dict[str, Any] = {"applied": 0, "latest": None}
+        if not self.db_path.exists():
+            return Ok(data)
+
+        try:
+            conn = self._get_connection(read_only=True)
+            try:
+                has_migrations = conn.execute(
+                    "SELECT 1 FROM information_schema.tables WHERE table_name = 'sys_migrations'"
+                ).fetchall()
+                if has_migrations:
+                    applied, latest = conn.execute(
+                        "SELECT COUNT(*), MAX(migration_name) FROM sys_migrations"
+                    ).fetchone()
+                    data["applied"] = applied
+                    data["latest"] = latest
+            finally:
+                conn.close()
+            return Ok(data)
+        except Exception as e:
+            return Fail(f"Failed to read migrations: {str(e)}")
+
+    async def get_view_columns(self) -> Result[Dict[str, List[str]]]:
+        """Get the columns of every view, in column order."""
+        try:
+            conn = self._get_connection(read_only=True)
+            try:
+                rows = conn.execute(
+                    """
+                    SELECT c.table_name, c.column_name
+                    FROM information_schema.columns c
+                    JOIN information_schema.tables t
+                      ON t.table_catalog = c.table_catalog
+                     AND t.table_schema = c.table_schema
+                     AND t.table_name = c.table_name
+                    WHERE c.table_catalog = current_database() AND c.table_schema = 'main'
+                      AND t.table_type = 'VIEW'
+                    ORDER BY c.table_name, c.ordinal_position
+                    """
+                ).fetchall()
+            finally:
+                conn.close()
+            views: Dict[str, List[str]] = {}
+            for view, column in rows:
+                views.setdefault(view, []).append(column)
+            return Ok(views)
+        except Exception as e:
+            return Fail(f"Failed to read view columns: {str(e)}")
+
+    async def repair_views(self) -> Result[List[str]]:
+        """Re-run the migrations' view definitions in one transaction."""
+        definitions = get_view_definitions()
+        try:
+            conn = self._get_connection()
+            conn.execute("BEGIN TRANSACTION")
+            try:
+                for sql in definitions.values():
+                    conn.execute(sql)
+                conn.execute("COMMIT")
+            except Exception:
+                self._rollback_quietly(conn)
+                raise
+            finally:
+                conn.close()
+            return Ok(sorted(definitions))
+        except Exception as e:
+            return self._write_failed("Failed to repair views", e)
 
-    async def add_account(self, account: Account) -> Result[Account]:
-        """Add a single account."""
+    async def add_account(
+        self, account: Account, opening_balance: BalanceSnapshot | None = None
+    ) -> Result[Account]:
+        """Add a single account, and its opening balance snapshot if given.
+
+        Both are written in one transaction: neither is saved if the other fails.
+        """
+        if opening_balance is not None:
+            out_of_range = self._check_amounts(
+                "balance snapshot", [(opening_balance.id, opening_balance.balance)]
+            )
+            if out_of_range:
+                return out_of_range
+        conn = None
         try:
             conn = self._get_connection()
+            conn.execute("BEGIN TRANSACTION")
 
             conn.execute(
                 """
                 INSERT INTO sys_accounts (
                     account_id, name, nickname, account_type, currency,
                     external_ids, institution_name, institution_url, institution_domain,
-                    created_at, updated_at
-                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                    opened_at, closed_at, default_tags, archived_at, created_at, updated_at
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
                 """,
                 [
                     str(account.id),
@@ -142,22 +447,33 @@ class DuckDBRepository(Repository):
                     account.nickname,
                     account.account_type,
                     account.currency,
-                    json.dumps(dict(account.external_ids)),
+                    json.dumps(dict(account.external_ids), sort_keys=True),
                     account.institution_name,
                     account.institution_url,
                     account.institution_domain,
+                    account.opened_at,
+                    account.closed_at,
+                    json.dumps(list(account.default_tags)),
+                    account.archived_at,
                     account.created_at,
                     account.updated_at,
                 ],
             )
+            if opening_balance is not None:
+                conn.execute(self._UPSERT_BALANCE_SQL, self._balance_params(opening_balance))
 
+            conn.execute("COMMIT")
             conn.close()
             return Ok(account)
         except Exception as e:
-            return Fail(f"Failed to add account: {str(e)}")
+            self._rollback_quietly(conn)
+            return self._write_failed(f"Failed to add account {account.id} ({account.name!r})", e)
 
     async def add_transaction(self, transaction: Transaction) -> Result[Transaction]:
         """Add a single transaction."""
+        out_of_range = self._check_amounts("transaction", [(transaction.id, transaction.amount)])
+        if out_of_range:
+            return out_of_range
         try:
             conn = self._get_connection()
 
@@ -165,69 +481,133 @@ class DuckDBRepository(Repository):
                 """
                 INSERT INTO sys_transactions (
                     transaction_id, account_id, external_ids, amount, description,
-                    transaction_date, posted_date, tags, created_at, updated_at
-                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                    description_search, transaction_date, posted_date, tags,
+                    created_at, updated_at, status
+                ) VALUES (?, ?, ?, ?, ?, lower(?), ?, ?, ?, ?, ?, ?)
                 """,
                 [
                     str(transaction.id),
                     str(transaction.account_id),
-                    json.dumps(dict(transaction.external_ids)),
-                    float(transaction.amount),
+                    json.dumps(dict(transaction.external_ids), sort_keys=True),
+                    transaction.amount,
+                    transaction.description,
                     transaction.description,
                     transaction.transaction_date,
                     transaction.posted_date,
                     list(transaction.tags),
                     transaction.created_at,
                     transaction.updated_at,
+                    transaction.status.value,
                 ],
             )
 
             conn.close()
             return Ok(transaction)
         except Exception as e:
-            return Fail(f"Failed to add transaction: {str(e)}")
+            return self._write_failed(
+                f"Failed to add transaction {transaction.id} to sys_transactions "
+                f"(account {transaction.account_id})",
+                e,
+            )
 
-    async def add_balance(self, balance: BalanceSnapshot) -> Result[BalanceSnapshot]:
-        """Add a balance snapshot."""
+    async def update_transaction(self, transaction: Transaction) -> Result[Transaction]:
+        """Save a transaction's amount, description, dates and external_ids.
+
+        Fails if no non-deleted transaction has that ID.
+        """
+        out_of_range = self._check_amounts("transaction", [(transaction.id, transaction.amount)])
+        if out_of_range:
+            return out_of_range
         try:
             conn = self._get_connection()
 
-            conn.execute(
+            updated = conn.execute(
                 """
-                INSERT INTO sys_balance_snapshots (
-                    snapshot_id, account_id, balance, snapshot_time, created_at, source
-                ) VALUES (?, ?, ?, ?, ?, ?)
+                UPDATE sys_transactions SET
+                    amount = ?, description = ?, description_search = lower(?),
+                    transaction_date = ?, posted_date = ?, external_ids = ?, updated_at = ?
+                WHERE transaction_id = ? AND deleted_at IS NULL
                 """,
                 [
-                    str(balance.id),
-                    str(balance.account_id),
-                    float(balance.balance),
-                    balance.snapshot_time,
-                    balance.created_at,
-                    balance.source,
+                    transaction.amount,
+                    transaction.description,
+                    transaction.description,
+                    transaction.transaction_date,
+                    transaction.posted_date,
+                    json.dumps(dict(transaction.external_ids), sort_keys=True),
+                    transaction.updated_at,
+                    str(transaction.id),
                 ],
-            )
+            ).fetchone()[0]
+
+            conn.close()
+            if not updated:
+                return Fail(f"Transaction not found: {transaction.id}")
+            return Ok(transaction)
+        except Exception as e:
+            return self._write_failed(f"Failed to update transaction {transaction.id}", e)
+
+    # Re-adding a snapshot ID overwrites its balance, time and source but
+    # keeps created_at, so replays of a sync or backfill are idempotent.
+    _UPSERT_BALANCE_SQL = """
+        INSERT INTO sys_balance_snapshots (
+            snapshot_id, account_id, balance, snapshot_time, created_at, updated_at, source
+        ) VALUES (?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT (snapshot_id) DO UPDATE SET
+            balance = excluded.balance,
+            snapshot_time = excluded.snapshot_time,
+            source = excluded.source,
+            updated_at = excluded.updated_at
+    """
+
+    @staticmethod
+    def _balance_params(balance: BalanceSnapshot) -> List[Any]:
+        return [
+            str(balance.id),
+            str(balance.account_id),
+            balance.balance,
+            balance.snapshot_time,
+            balance.created_at,
+            balance.updated_at,
+            balance.source,
+        ]
+
+    async def add_balance(self, balance: BalanceSnapshot) -> Result[BalanceSnapshot]:
+        """Add a balance snapshot, or overwrite the one with the same ID."""
+        out_of_range = self._check_amounts("balance snapshot", [(balance.id, balance.balance)])
+        if out_of_range:
+            return out_of_range
+        try:
+            conn = self._get_connection()
+
+            conn.execute(self._UPSERT_BALANCE_SQL, self._balance_params(balance))
 
             conn.close()
             return Ok(balance)
         except Exception as e:
-            return Fail(f"Failed to add balance: {str(e)}")
+            return self._write_failed(
+                f"Failed to add balance snapshot {balance.id} (account {balance.account_id})", e
+            )
 
     async def bulk_upsert_accounts(
         self, accounts: List[Account]
     ) -> Result[List[Account]]:
-        """Bulk upsert accounts."""
+        """Bulk upsert accounts. All or nothing: a failure writes none of them."""
+        conn = None
+        current = None
         try:
             conn = self._get_connection()
+            conn.execute("BEGIN TRANSACTION")
 
             for account in accounts:
+                current = account
                 conn.execute(
                     """
                     INSERT INTO sys_accounts (
                         account_id, name, nickname, account_type, currency,
                         external_ids, institution_name, institution_url, institution_domain,
-                        created_at, updated_at
-                    ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                        opened_at, closed_at, created_at, updated_at
+                    ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
                     ON CONFLICT (account_id) DO UPDATE SET
                         name = excluded.name,
                         nickname = COALESCE(sys_accounts.nickname, excluded.nickname),
@@ -237,6 +617,8 @@ class DuckDBRepository(Repository):
                         institution_name = COALESCE(excluded.institution_name, sys_accounts.institution_name),
                         institution_url = COALESCE(excluded.institution_url, sys_accounts.institution_url),
                         institution_domain = COALESCE(excluded.institution_domain, sys_accounts.institution_domain),
+                        opened_at = COALESCE(sys_accounts.opened_at, excluded.opened_at),
+                        closed_at = COALESCE(sys_accounts.closed_at, excluded.closed_at),
                         updated_at = excluded.updated_at
                     """,
                     [
@@ -245,40 +627,76 @@ class DuckDBRepository(Repository):
                         account.nickname,
                         account.account_type,
                         account.currency,
-                        json.dumps(dict(account.external_ids)),
+                        json.dumps(dict(account.external_ids), sort_keys=True),
                         account.institution_name,
                         account.institution_url,
                         account.institution_domain,
+                        account.opened_at,
+                        account.closed_at,
                         account.created_at,
                         account.updated_at,
                     ],
                 )
 
+            conn.execute("COMMIT")
             conn.close()
             return Ok(accounts)
         except Exception as e:
-            return Fail(f"Failed to bulk upsert accounts: {str(e)}")
+            self._rollback_quietly(conn)
+            where = f" (at account {current.id})" if current else ""
+            return self._write_failed(f"Failed to bulk upsert accounts{where}", e)
+
+    @staticmethod
+    def _rollback_quietly(conn: duckdb.DuckDBPyConnection | None) -> None:
+        """Roll back and close after a failed write; the original error is what gets reported."""
+        if conn is None:
+            return
+        try:
+            conn.execute("ROLLBACK")
+        except duckdb.Error:
+            pass  # No transaction was open (the failure came before BEGIN)
+        conn.close()
 
     async def bulk_upsert_transactions(
         self, transactions: List[Transaction]
     ) -> Result[List[Transaction]]:
-        """Bulk upsert transactions."""
+        """Bulk upsert transactions. All or nothing: a failure writes none of them."""
+        out_of_range = self._check_amounts(
+            "transaction", [(tx.id, tx.amount) for tx in transactions]
+        )
+        if out_of_range:
+            return out_of_range
+        conn = None
         try:
             conn = self._get_connection()
+            conn.execute("BEGIN TRANSACTION")
+            self._upsert_transactions(conn, transactions)
+            conn.execute("COMMIT")
 
-            for transaction in transactions:
+            conn.close()
+            return Ok(transactions)
+        except Exception as e:
+            self._rollback_quietly(conn)
+            return self._write_failed("Failed to bulk upsert transactions", e)
+
+    def _upsert_transactions(
+        self, conn: duckdb.DuckDBPyConnection, transactions: List[Transaction]
+    ) -> None:
+        for transaction in transactions:
+            try:
                 conn.execute(
                     """
                     INSERT INTO sys_transactions (
                         transaction_id, account_id, external_ids, amount, description,
-                        transaction_date, posted_date, tags, created_at, updated_at,
-                        deleted_at, parent_transaction_id
-                    ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                        description_search, transaction_date, posted_date, tags,
+                        created_at, updated_at, deleted_at, parent_transaction_id, status
+                    ) VALUES (?, ?, ?, ?, ?, lower(?), ?, ?, ?, ?, ?, ?, ?, ?)
                     ON CONFLICT (transaction_id) DO UPDATE SET
                         account_id = excluded.account_id,
                         external_ids = excluded.external_ids,
                         amount = excluded.amount,
                         description = excluded.description,
+                        description_search = excluded.description_search,
                         transaction_date = excluded.transaction_date,
                         posted_date = excluded.posted_date,
                         tags = excluded.tags,
@@ -287,8 +705,9 @@ class DuckDBRepository(Repository):
                     [
                         str(transaction.id),
                         str(transaction.account_id),
-                        json.dumps(dict(transaction.external_ids)),
-                        float(transaction.amount),
+                        json.dumps(dict(transaction.external_ids), sort_keys=True),
+                        transaction.amount,
+                        transaction.description,
                         transaction.description,
                         transaction.transaction_date,
                         transaction.posted_date,
@@ -297,54 +716,90 @@ class DuckDBRepository(Repository):
                         transaction.updated_at,
                         transaction.deleted_at,
                         str(transaction.parent_transaction_id) if transaction.parent_transaction_id else None,
+                        transaction.status.value,
                     ],
                 )
-
-            conn.close()
-            return Ok(transactions)
-        except Exception as e:
-            return Fail(f"Failed to bulk upsert transactions: {str(e)}")
+            except Exception as e:
+                raise RuntimeError(
+                    f"transaction {transaction.id} (account {transaction.account_id}): {e}"
+                ) from e
 
     async def bulk_add_balances(
         self, balances: List[BalanceSnapshot]
     ) -> Result[List[BalanceSnapshot]]:
-        """Bulk add balance snapshots."""
+        """Bulk add (or overwrite) balance snapshots. All or nothing: a failure writes none of them."""
+        out_of_range = self._check_amounts(
+            "balance snapshot", [(balance.id, balance.balance) for balance in balances]
+        )
+        if out_of_range:
+            return out_of_range
+        conn = None
+        current = None
         try:
             conn = self._get_connection()
+            conn.execute("BEGIN TRANSACTION")
 
             for balance in balances:
-                conn.execute(
-                    """
-                    INSERT INTO sys_balance_snapshots (
-                        snapshot_id, account_id, balance, snapshot_time, created_at, source
-                    ) VALUES (?, ?, ?, ?, ?, ?)
-                    """,
-                    [
-                        str(balance.id),
-                        str(balance.account_id),
-                        float(balance.balance),
-                        balance.snapshot_time,
-                        balance.created_at,
-                        balance.source,
-                    ],
-                )
+                current = balance
+                conn.execute(self._UPSERT_BALANCE_SQL, self._balance_params(balance))
 
+            conn.execute("COMMIT")
             conn.close()
             return Ok(balances)
         except Exception as e:
-            return Fail(f"Failed to bulk add balances: {str(e)}")
+            self._rollback_quietly(conn)
+            where = f" (at snapshot {current.id}, account {current.account_id})" if current else ""
+            return self._write_failed(f"Failed to bulk add balances{where}", e)
+
+    async def update_balance_snapshot(
+        self, snapshot_id: UUID, balance: Decimal
+    ) -> Result[BalanceSnapshot]:
+        """Change a snapshot's balance and bump updated_at. Fails if no snapshot has that ID."""
+        out_of_range = self._check_amounts("balance snapshot", [(snapshot_id, balance)])
+        if out_of_range:
+            return out_of_range
+        try:
+            conn = self._get_connection()
+
+            row = conn.execute(
+                """
+                UPDATE sys_balance_snapshots SET balance = ?, updated_at = ?
+                WHERE snapshot_id = ?
+                RETURNING snapshot_id, account_id, balance, snapshot_time,
+                          created_at, updated_at, source
+                """,
+                [balance, datetime.now(timezone.utc), str(snapshot_id)],
+            ).fetchone()
+
+            conn.close()
+            if not row:
+                return Fail(f"Balance snapshot not found: {snapshot_id}")
+            return Ok(
+                BalanceSnapshot(
+                    id=UUID(row[0]),
+                    account_id=UUID(row[1]),
+                    balance=Decimal(str(row[2])),
+                    snapshot_time=self._ensure_timezone(row[3]),
+                    created_at=self._ensure_timezone(row[4]),
+                    updated_at=self._ensure_timezone(row[5]),
+                    source=row[6],
+                )
+            )
+        except Exception as e:
+            return self._write_failed(f"Failed to update balance snapshot {snapshot_id}", e)
 
     async def update_account_by_id(self, account: Account) -> Result[Account]:
-        """Update an account by ID."""
+        """Update an account by ID. Fails if no account has that ID."""
         try:
             conn = self._get_connection()
 
-            conn.execute(
+            updated = conn.execute(
                 """
                 UPDATE sys_accounts SET
                     name = ?, nickname = ?, account_type = ?, currency = ?,
                     external_ids = ?, institution_name = ?, institution_url = ?,
-                    institution_domain = ?, updated_at = ?
+                    institution_domain = ?, opened_at = ?, closed_at = ?, default_tags = ?,
+                    archived_at = ?, updated_at = ?
                 WHERE account_id = ?
                 """,
                 [
@@ -352,55 +807,73 @@ class DuckDBRepository(Repository):
                     account.nickname,
                     account.account_type,
                     account.currency,
-                    json.dumps(dict(account.external_ids)),
+                    json.dumps(dict(account.external_ids), sort_keys=True),
                     account.institution_name,
                     account.institution_url,
                     account.institution_domain,
+                    account.opened_at,
+                    account.closed_at,
+                    json.dumps(list(account.default_tags)),
+                    account.archived_at,
                     account.updated_at,
                     str(account.id),
                 ],
-            )
+            ).fetchone()[0]
 
             conn.close()
+            if not updated:
+                return Fail(f"Account not found: {account.id}")
             return Ok(account)
         except Exception as e:
-            return Fail(f"Failed to update account: {str(e)}")
+            return self._write_failed(f"Failed to update account {account.id}", e)
 
     async def get_accounts(self) -> Result[List[Account]]:
         """Get all accounts."""
         try:
             conn = self._get_connection(read_only=True)
 
-            result = conn.execute("SELECT * FROM sys_accounts").fetchall()
+            result = conn.execute("SELECT * FROM sys_accounts ORDER BY name, account_id").fetchall()
             columns = [desc[0] for desc in conn.description]
 
-            accounts = []
-            for row in result:
-                row_dict = dict(zip(columns, row))
-                account = Account(
-                    id=UUID(row_dict["account_id"]),
-                    name=row_dict["name"],
-                    nickname=row_dict["nickname"],
-                    account_type=row_dict["account_type"],
-                    currency=row_dict["currency"],
-                    external_ids=MappingProxyType(
-                        json.loads(row_dict["external_ids"])
-                        if row_dict["external_ids"]
-                        else {}
-                    ),
-                    institution_name=row_dict["institution_name"],
-                    institution_url=row_dict["institution_url"],
-                    institution_domain=row_dict["institution_domain"],
-                    created_at=self._ensure_timezone(row_dict["created_at"]),
-                    updated_at=self._ensure_timezone(row_dict["updated_at"]),
-                )
-                accounts.append(account)
+            accounts, row_errors = self._map_rows(
+                "sys_accounts",
+                "account_id",
+                [dict(zip(columns, row)) for row in result],
+                lambda row_dict: row_dict["account_id"],
+                self._row_to_account,
+            )
 
             conn.close()
-            return Ok(accounts)
+            return self._with_row_errors(accounts, row_errors)
         except Exception as e:
             return Fail(f"Failed to get accounts: {str(e)}")
 
+    def _row_to_account(self, row_dict: Dict[str, Any]) -> Account:
+        """Build an Account from a SELECT * row of sys_accounts."""
+        return Account(
+            id=UUID(row_dict["account_id"]),
+            name=row_dict["name"],
+            nickname=row_dict["nickname"],
+            account_type=row_dict["account_type"],
+            currency=row_dict["currency"],
+            external_ids=MappingProxyType(
+                json.loads(row_dict["external_ids"])
+                if row_dict["external_ids"]
+                else {}
+            ),
+            institution_name=row_dict["institution_name"],
+            institution_url=row_dict["institution_url"],
+            institution_domain=row_dict["institution_domain"],
+            opened_at=row_dict.get("opened_at"),
+            closed_at=row_dict.get("closed_at"),
+            default_tags=(
+                json.loads(row_dict["default_tags"]) if row_dict.get("default_tags") else ()
+            ),
+            archived_at=self._ensure_timezone(row_dict.get("archived_at")),
+            created_at=self._ensure_timezone(row_dict["created_at"]),
+            updated_at=self._ensure_timezone(row_dict["updated_at"]),
+        )
+
     async def get_account_by_id(self, account_id: UUID) -> Result[Account]:
         """Get a single account by ID."""
         try:
@@ -416,78 +889,133 @@ class DuckDBRepository(Repository):
 
             columns = [desc[0] for desc in conn.description]
             row_dict = dict(zip(columns, result))
+            conn.close()
 
-            account = Account(
-                id=UUID(row_dict["account_id"]),
-                name=row_dict["name"],
-                nickname=row_dict["nickname"],
-                account_type=row_dict["account_type"],
-                currency=row_dict["currency"],
-                external_ids=MappingProxyType(
-                    json.loads(row_dict["external_ids"])
-                    if row_dict["external_ids"]
-                    else {}
-                ),
-                institution_name=row_dict["institution_name"],
-                institution_url=row_dict["institution_url"],
-                institution_domain=row_dict["institution_domain"],
-                created_at=self._ensure_timezone(row_dict["created_at"]),
-                updated_at=self._ensure_timezone(row_dict["updated_at"]),
-            )
+            try:
+                return Ok(self._row_to_account(row_dict))
+            except Exception as e:
+                return Fail(
+                    f"Unreadable row in sys_accounts (account_id {row_dict['account_id']!r}): {e}"
+                )
+        except Exception as e:
+            return Fail(f"Failed to get account: {str(e)}")
+
+    async def get_accounts_by_external_ids(
+        self, provider_key: str, ids: List[str]
+    ) -> Result[List[Account]]:
+        """Get accounts whose external_ids[provider_key] is one of ids."""
+        try:
+            if not ids:
+                return Ok([])
+
+            conn = self._get_connection(read_only=True)
+
+            placeholders = ", ".join("?" for _ in ids)
+            result = conn.execute(
+                f"""
+                SELECT * FROM sys_accounts
+                WHERE json_extract_string(external_ids, ?) IN ({placeholders})
+                ORDER BY name, account_id
+                """,
+                [f'$."{provider_key}"', *ids],
+            ).fetchall()
+            columns = [desc[0] for desc in conn.description]
 
             conn.close()
-            return Ok(account)
+            return Ok([self._row_to_account(dict(zip(columns, row))) for row in result])
         except Exception as e:
-            return Fail(f"Failed to get account: {str(e)}")
+            return Fail(f"Failed to get accounts: {str(e)}")
+
+    _EXTERNAL_ID_TABLES = {"accounts": "sys_accounts", "transactions": "sys_transactions"}
 
-    async def get_account_by_external_id(self, external_id: str) -> Result[Account]:
-        """Get an account by external ID."""
-        # This requires JSON querying which DuckDB supports
-        return Fail("Not implemented")
+    async def list_external_id_keys(self, table: str) -> Result[List[str]]:
+        """Distinct external_ids keys in use by "accounts" or "transactions".
+
+        Rows whose external_ids isn't a JSON object are skipped.
+        """
+        if table not in self._EXTERNAL_ID_TABLES:
+            return Fail(f"Unknown table for external IDs: {table}")
+        try:
+            conn = self._get_connection(read_only=True)
+            rows = conn.execute(
+                f"""
+                SELECT DISTINCT unnest(json_keys(external_ids)) AS key
+                FROM {self._EXTERNAL_ID_TABLES[table]}
+                WHERE NOT ({self._EXTERNAL_IDS_MALFORMED}) AND external_ids IS NOT NULL
+                ORDER BY key
+                """
+            ).fetchall()
+            conn.close()
+            return Ok([row[0] for row in rows])
+        except Exception as e:
+            return Fail(f"Failed to list external ID keys: {str(e)}")
+
+    # The rows the transactions view counts (migrations 017/018): not
+    # deleted, and not a split parent that still has live children, wherever
+    # those children are dated. Applies to sys_transactions aliased as t.
+    _LEAF_TRANSACTIONS = """
+        t.deleted_at IS NULL
+        AND NOT EXISTS (
+            SELECT 1 FROM sys_transactions c
+            WHERE c.parent_transaction_id = t.transaction_id AND c.deleted_at IS NULL
+        )
+    """
+
+    _TRANSACTION_COLUMNS = """
+        transaction_id,
+        account_id,
+        external_ids,
+        amount,
+        description,
+        transaction_date,
+        posted_date,
+        tags,
+        created_at,
+        updated_at,
+        deleted_at,
+        parent_transaction_id,
+        status
+    """
+
+    def _row_to_transaction(self, row: tuple) -> Transaction:
+        """Build a Transaction from a row selected with _TRANSACTION_COLUMNS."""
+        return Transaction(
+            id=UUID(row[0]),
+            account_id=UUID(row[1]),
+            external_ids=MappingProxyType(json.loads(row[2]) if row[2] else {}),
+            amount=Decimal(str(row[3])),
+            description=row[4],
+            transaction_date=row[5],
+            posted_date=row[6],
+            tags=tuple(row[7]) if row[7] else (),
+            created_at=self._ensure_timezone(row[8]),
+            updated_at=self._ensure_timezone(row[9]),
+            deleted_at=self._ensure_timezone(row[10]) if row[10] else None,
+            parent_transaction_id=UUID(row[11]) if row[11] else None,
+            status=row[12] or TransactionStatus.CLEARED,
+        )
 
     async def get_transactions_by_external_ids(
-        self, external_ids: List[Dict[str, str]]
+        self, provider_key: str, ids: List[str]
     ) -> Result[List[Transaction]]:
-        """Get transactions by external IDs."""
+        """Get transactions whose external_ids[provider_key] is one of ids."""
         try:
-            conn = self._get_connection(read_only=True)
+            if not ids:
+                return Ok([])
 
-            transactions = []
-            for ext_id_obj in external_ids:
-                # Query for transactions with matching external IDs
-                result = conn.execute(
-                    "SELECT * FROM sys_transactions WHERE external_ids::VARCHAR LIKE ?",
-                    [f"%{list(ext_id_obj.values())[0]}%"],
-                ).fetchall()
+            conn = self._get_connection(read_only=True)
 
-                columns = [desc[0] for desc in conn.description]
-
-                for row in result:
-                    row_dict = dict(zip(columns, row))
-                    transaction = Transaction(
-                        id=UUID(row_dict["transaction_id"]),
-                        account_id=UUID(row_dict["account_id"]),
-                        external_ids=MappingProxyType(
-                            json.loads(row_dict["external_ids"])
-                            if row_dict["external_ids"]
-                            else {}
-                        ),
-                        amount=Decimal(str(row_dict["amount"])),
-                        description=row_dict["description"],
-                        transaction_date=row_dict[
-                            "transaction_date"
-                        ],  # Already a date object
-                        posted_date=row_dict["posted_date"],  # Already a date object
-                        tags=tuple(row_dict["tags"]) if row_dict["tags"] else tuple(),
-                        created_at=self._ensure_timezone(row_dict["created_at"]),
-                        updated_at=self._ensure_timezone(row_dict["updated_at"]),
-                        deleted_at=self._ensure_timezone(row_dict["deleted_at"]) if row_dict.get("deleted_at") else None,
-                        parent_transaction_id=UUID(row_dict["parent_transaction_id"]) if row_dict.get("parent_transaction_id") else None,
-                    )
-                    transactions.append(transaction)
+            placeholders = ", ".join("?" for _ in ids)
+            rows = conn.execute(
+                f"""
+                SELECT {self._TRANSACTION_COLUMNS} FROM sys_transactions
+                WHERE json_extract_string(external_ids, ?) IN ({placeholders})
+                """,
+                [f'$."{provider_key}"', *ids],
+            ).fetchall()
 
             conn.close()
-            return Ok(transactions)
+            return Ok([self._row_to_transaction(row) for row in rows])
         except Exception as e:
             return Fail(f"Failed to get transactions: {str(e)}")
 
@@ -512,10 +1040,12 @@ class DuckDBRepository(Repository):
             result = conn.execute(query, params).fetchall()
             columns = [desc[0] for desc in conn.description]
 
-            balances = []
-            for row in result:
-                row_dict = dict(zip(columns, row))
-                balance = BalanceSnapshot(
+            balances, row_errors = self._map_rows(
+                "sys_balance_snapshots",
+                "snapshot_id",
+                [dict(zip(columns, row)) for row in result],
+                lambda row_dict: row_dict["snapshot_id"],
+                lambda row_dict: BalanceSnapshot(
                     id=UUID(row_dict["snapshot_id"]),
                     account_id=UUID(row_dict["account_id"]),
                     balance=Decimal(str(row_dict["balance"])),
@@ -523,33 +1053,328 @@ class DuckDBRepository(Repository):
                     created_at=self._ensure_timezone(row_dict["created_at"]),
                     updated_at=self._ensure_timezone(row_dict["updated_at"]),
                     source=row_dict.get("source"),
+                ),
+            )
+
+            conn.close()
+            return self._with_row_errors(balances, row_errors)
+        except Exception as e:
+            return Fail(f"Failed to get balance snapshots: {str(e)}")
+
+    async def get_latest_balance_snapshots(
+        self, cutoffs: Dict[UUID, date]
+    ) -> Result[Dict[UUID, BalanceSnapshot]]:
+        """Get each account's most recent snapshot dated before its cutoff date."""
+        if not cutoffs:
+            return Ok({})
+        try:
+            conn = self._get_connection(read_only=True)
+
+            account_ids = [str(account_id) for account_id in cutoffs]
+            rows = conn.execute(
+                """
+                SELECT snapshot_id, account_id, balance, snapshot_time, created_at, updated_at, source
+                FROM (
+                    SELECT s.*, ROW_NUMBER() OVER (
+                        PARTITION BY s.account_id ORDER BY s.snapshot_time DESC, s.created_at DESC
+                    ) AS position
+                    FROM sys_balance_snapshots s
+                    JOIN (
+                        SELECT UNNEST(?::VARCHAR[]) AS account_id, UNNEST(?::DATE[]) AS cutoff
+                    ) c ON s.account_id = c.account_id
+                    WHERE CAST(s.snapshot_time AS DATE) < c.cutoff
+                )
+                WHERE position = 1
+                """,
+                [account_ids, list(cutoffs.values())],
+            ).fetchall()
+
+            conn.close()
+            snapshots = {}
+            for row in rows:
+                snapshot = BalanceSnapshot(
+                    id=UUID(row[0]),
+                    account_id=UUID(row[1]),
+                    balance=Decimal(str(row[2])),
+                    snapshot_time=self._ensure_timezone(row[3]),
+                    created_at=self._ensure_timezone(row[4]),
+                    updated_at=self._ensure_timezone(row[5]),
+                    source=row[6],
                 )
-                balances.append(balance)
+                snapshots[snapshot.account_id] = snapshot
+            return Ok(snapshots)
+        except Exception as e:
+            return Fail(f"Failed to get latest balance snapshots: {str(e)}")
+
+    async def get_transaction_overlap(
+        self, account_a: UUID, account_b: UUID
+    ) -> Result[Dict[str, int]]:
+        """Count transactions two accounts have in common (same date and amount)."""
+        try:
+            conn = self._get_connection(read_only=True)
+            a, b, shared = conn.execute(
+                """
+                WITH counted AS (
+                    SELECT account_id, transaction_date, amount, COUNT(*) AS n
+                    FROM sys_transactions
+                    WHERE account_id IN (?, ?)
+                      AND deleted_at IS NULL
+                      AND parent_transaction_id IS NULL
+                    GROUP BY account_id, transaction_date, amount
+                ),
+                a AS (SELECT * FROM counted WHERE account_id = ?),
+                b AS (SELECT * FROM counted WHERE account_id = ?)
+                SELECT
+                    (SELECT COALESCE(SUM(n), 0) FROM a),
+                    (SELECT COALESCE(SUM(n), 0) FROM b),
+                    (SELECT COALESCE(SUM(LEAST(a.n, b.n)), 0)
+                     FROM a JOIN b USING (transaction_date, amount))
+                """,
+                [str(account_a), str(account_b), str(account_a), str(account_b)],
+            ).fetchone()
+            conn.close()
+            return Ok({"a": int(a), "b": int(b), "shared": int(shared)})
+        except Exception as e:
+            return Fail(f"Failed to compare transactions of two accounts: {str(e)}")
+
+    async def get_balance_as_of(self, account_id: UUID, as_of: date) -> Result[Dict[str, Any]]:
+        """Get an account's end-of-day balance on a date (see get_balances_as_of)."""
+        balances_result = await self.get_balances_as_of([account_id], [as_of])
+        if not balances_result.success:
+            return balances_result
+        return Ok(balances_result.data[account_id][as_of])
+
+    async def get_balances_as_of(
+        self, account_ids: List[UUID], dates: List[date]
+    ) -> Result[Dict[UUID, Dict[date, Dict[str, Any]]]]:
+        """Get end-of-day balances for several accounts on several dates.
+
+        A snapshot on day D is treated as the balance at the end of D. Without
+        one, the nearest snapshot is used as an anchor: an earlier snapshot plus
+        the transactions after it, or a later snapshot minus the transactions
+        up to it (balance_from_anchor, the same math as balance backfill).
+        Snapshots and transactions are read once for all accounts and dates.
+        """
+        if not account_ids or not dates:
+            return Ok({account_id: {} for account_id in account_ids})
+        try:
+            conn = self._get_connection(read_only=True)
+            ids = [str(account_id) for account_id in account_ids]
 
+            snapshots: Dict[str, List[Tuple[Any, ...]]] = {key: [] for key in ids}
+            for row in conn.execute(
+                """
+                SELECT account_id, CAST(snapshot_time AS DATE), snapshot_time, balance, source
+                FROM sys_balance_snapshots
+                WHERE account_id IN (SELECT UNNEST(?::VARCHAR[]))
+                ORDER BY snapshot_time
+                """,
+                [ids],
+            ).fetchall():
+                snapshots[str(row[0])].append(row[1:])
+
+            # A later snapshot alone can't tell us the account existed yet
+            first_transaction = {
+                str(key): day
+                for key, day in conn.execute(
+                    """
+                    SELECT account_id, MIN(transaction_date) FROM sys_transactions
+                    WHERE account_id IN (SELECT UNNEST(?::VARCHAR[])) AND deleted_at IS NULL
+                    GROUP BY account_id
+                    """,
+                    [ids],
+                ).fetchall()
+            }
+
+            anchors: Dict[Tuple[str, date], Tuple[Any, ...]] = {}
+            for key in ids:
+                for as_of in dates:
+                    before = [s for s in snapshots[key] if s[0] <= as_of]
+                    after = [s for s in snapshots[key] if s[0] > as_of]
+                    before = before[-1] if before else None
+                    after = after[0] if after else None
+                    # Prefer the closer anchor; on a tie, the earlier snapshot
+                    anchor = before
+                    if after and (
+                        not before or (after[0] - as_of).days < (as_of - before[0]).days
+                    ):
+                        anchor = after
+                    if anchor is after and before is None:
+                        existed = first_transaction.get(key)
+                        if existed is None or existed > as_of:
+                            anchor = None
+                    if anchor is not None:
+                        anchors[(key, as_of)] = anchor
+
+            # The transactions between each anchor day and its target day
+            between: Dict[str, List[Tuple[date, Decimal]]] = {key: [] for key in ids}
+            spans = [sorted((anchor[0], as_of)) for (_, as_of), anchor in anchors.items()]
+            if spans:
+                for key, day, amount in conn.execute(
+                    """
+                    SELECT account_id, transaction_date, amount FROM transactions
+                    WHERE account_id IN (SELECT UNNEST(?::VARCHAR[]))
+                      AND transaction_date > ? AND transaction_date <= ?
+                    """,
+                    [ids, min(s[0] for s in spans), max(s[1] for s in spans)],
+                ).fetchall():
+                    between[str(key)].append((day, Decimal(str(amount))))
             conn.close()
+
+            balances: Dict[UUID, Dict[date, Dict[str, Any]]] = {}
+            for account_id, key in zip(account_ids, ids):
+                balances[account_id] = {}
+                for as_of in dates:
+                    result: Dict[str, Any] = {
+                        "account_id": account_id,
+                        "as_of": as_of,
+                        "balance": None,
+                        "status": "unknown",
+                        "reconstructed": False,
+                        "anchor": None,
+                        "transactions_applied": 0,
+                    }
+                    balances[account_id][as_of] = result
+                    anchor = anchors.get((key, as_of))
+                    if anchor is None:
+                        continue
+
+                    anchor_date, anchor_time, anchor_balance, anchor_source = anchor
+                    result["anchor"] = {
+                        "snapshot_time": anchor_time,
+                        "balance": Decimal(str(anchor_balance)),
+                        "source": anchor_source,
+                    }
+                    if anchor_date == as_of:
+                        result["balance"] = Decimal(str(anchor_balance))
+                        result["status"] = "snapshot"
+                        continue
+
+                    balance, applied = balance_from_anchor(
+                        Decimal(str(anchor_balance)), anchor_date, as_of, between[key]
+                    )
+                    result["balance"] = balance
+                    result["status"] = "reconstructed"
+                    result["reconstructed"] = True
+                    result["transactions_applied"] = applied
             return Ok(balances)
         except Exception as e:
-            return Fail(f"Failed to get balance snapshots: {str(e)}")
+            return Fail(f"Failed to get balances as of {', '.join(map(str, dates))}: {str(e)}")
+
+    async def execute_query(
+        self, sql: str, timeout_secs: float | None = None
+    ) -> Result[Dict[str, Any]]:
+        """Execute a SQL query and return structured results.
 
-    async def execute_query(self, sql: str) -> Result[Dict[str, Any]]:
-        """Execute a SQL query and return structured results."""
+        With a timeout, a timer thread interrupts the connection when the
+        deadline passes.
+        """
+        conn = None
+        deadline = None
         try:
             conn = self._get_connection(read_only=True)
 
-            result = conn.execute(sql).fetchall()
+            if timeout_secs:
+                deadline = threading.Timer(timeout_secs, conn.interrupt)
+                deadline.daemon = True
+                deadline.start()
+            try:
+                result = conn.execute(sql).fetchall()
+            finally:
+                if deadline is not None:
+                    deadline.cancel()
             columns = [desc[0] for desc in conn.description] if conn.description else []
+            # DuckDB reports JSON-typed columns as "JSON" (plain VARCHAR is "STRING")
+            column_types = (
+                [str(desc[1]) for desc in conn.description] if conn.description else []
+            )
 
             conn.close()
             return Ok(
                 {
                     "columns": columns,
+                    "column_types": column_types,
                     "rows": result,  # Return raw tuples, not dicts
                     "row_count": len(result),
                 }
             )
+        except duckdb.InterruptException:
+            conn.close()
+            return Fail(
+                f"Query cancelled after {timeout_secs:g}s", context={"cancelled": True}
+            )
         except Exception as e:
             return Fail(f"Failed to execute query: {str(e)}")
 
+    async def copy_query_to_file(
+        self,
+        sql: str,
+        output_path: Path,
+        file_format: str,
+        overwrite: bool = False,
+        timeout_secs: float | None = None,
+    ) -> Result[Dict[str, Any]]:
+        """Write a query's result to a file with COPY ... TO on a read-only connection.
+
+        COPY can't take the destination as a parameter, so DuckDB writes to
+        a temporary file with a generated name in the destination directory
+        (the directory quoted as a string literal), which is then renamed
+        onto output_path. A failed or cancelled COPY leaves nothing behind.
+        Parquet row counts are read back from the written file's metadata.
+        """
+        output_path = Path(output_path)
+        options = COPY_FORMATS.get(file_format)
+        if options is None:
+            return Fail(f"Unsupported format: {file_format} (use {', '.join(COPY_FORMATS)})")
+        if output_path.exists() and not overwrite:
+            return Fail(f"{output_path} already exists", context={"exists": True})
+        if output_path.is_dir():
+            return Fail(f"{output_path} is a directory")
+        directory = output_path.parent.resolve()
+        if not directory.is_dir():
+            return Fail(f"Directory not found: {output_path.parent}")
+        if not os.access(directory, os.W_OK):
+            return Fail(f"Can't write to {output_path.parent}: permission denied")
+
+        temp_path = directory / f".treeline-export-{uuid4().hex}.{file_format}"
+        query = sql.strip().rstrip(";").strip()
+        conn = None
+        deadline = None
+        try:
+            conn = self._get_connection(read_only=True)
+            if timeout_secs:
+                deadline = threading.Timer(timeout_secs, conn.interrupt)
+                deadline.daemon = True
+                deadline.start()
+            try:
+                copied = conn.execute(
+                    f"COPY ({query}) TO {_sql_string(str(temp_path))} ({options})"
+                ).fetchone()
+            finally:
+                if deadline is not None:
+                    deadline.cancel()
+
+            rows = copied[0] if copied else 0
+            if file_format == "parquet":
+                rows = conn.execute(
+                    "SELECT COALESCE(SUM(num_rows), 0) FROM parquet_file_metadata("
+                    f"{_sql_string(str(temp_path))})"
+                ).fetchone()[0]
+            conn.close()
+            os.replace(temp_path, output_path)
+            return Ok({"output": str(output_path), "format": file_format, "rows": int(rows)})
+        except duckdb.InterruptException:
+            conn.close()
+            temp_path.unlink(missing_ok=True)
+            return Fail(
+                f"Export cancelled after {timeout_secs:g}s", context={"cancelled": True}
+            )
+        except Exception as e:
+            if conn is not None:
+                conn.close()
+            temp_path.unlink(missing_ok=True)
+            return Fail(f"Failed to export to {output_path}: {str(e)}")
+
     async def execute_write_query(self, sql: str) -> Result[None]:
         """Execute SQL write query (INSERT, UPDATE, DELETE)."""
         try:
@@ -650,43 +1475,47 @@ class DuckDBRepository(Repository):
             return Fail(f"Failed to get date range info: {str(e)}")
 
     async def get_transaction_counts_by_fingerprint(
-        self, fingerprints: List[str]
+        self, fingerprints: List[str], exclude_import_id: UUID | None = None
     ) -> Result[Dict[str, int]]:
-        """Get count of existing transactions for each fingerprint."""
+        """Get count of existing transactions for each fingerprint.
+
+        Looked up FINGERPRINT_LOOKUP_CHUNK_SIZE fingerprints at a time. With
+        exclude_import_id, rows written by that file import don't count.
+        Deleted rows do count, so re-importing a file doesn't bring back
+        transactions deleted with tl transactions delete.
+        """
         try:
             if not fingerprints:
                 return Ok({})
 
             conn = self._get_connection(read_only=True)
 
-            # Build query with requested fingerprints and their counts
-            fingerprints_list = ", ".join(f"'{fp}'" for fp in fingerprints)
-
-            query = f"""
-                WITH requested_fingerprints AS (
-                    SELECT unnest([{fingerprints_list}]) as fingerprint
-                ),
-                counts AS (
+            exclude_clause = ""
+            exclude_params = []
+            if exclude_import_id is not None:
+                exclude_clause = f"AND COALESCE({self._CSV_IMPORT_ID_SQL}, '') <> ?"
+                exclude_params = [str(exclude_import_id)]
+
+            counts_dict = {fingerprint: 0 for fingerprint in fingerprints}
+            for start in range(0, len(fingerprints), FINGERPRINT_LOOKUP_CHUNK_SIZE):
+                chunk = fingerprints[start : start + FINGERPRINT_LOOKUP_CHUNK_SIZE]
+                placeholders = ", ".join("?" for _ in chunk)
+                rows = conn.execute(
+                    f"""
                     SELECT
                         json_extract_string(external_ids, '$.fingerprint') as fingerprint,
                         COUNT(*) as count
                     FROM sys_transactions
-                    WHERE json_extract_string(external_ids, '$.fingerprint') IN ({fingerprints_list})
+                    WHERE json_extract_string(external_ids, '$.fingerprint') IN ({placeholders})
+                    {exclude_clause}
                     GROUP BY json_extract_string(external_ids, '$.fingerprint')
-                )
-                SELECT
-                    rf.fingerprint,
-                    COALESCE(c.count, 0) as count
-                FROM requested_fingerprints rf
-                LEFT JOIN counts c ON rf.fingerprint = c.fingerprint
-            """
+                    """,
+                    chunk + exclude_params,
+                ).fetchall()
+                for fingerprint, count in rows:
+                    counts_dict[fingerprint] = int(count)
 
-            results = conn.execute(query).fetchall()
             conn.close()
-
-            # Build dictionary mapping fingerprint -> count
-            counts_dict = {row[0]: int(row[1]) for row in results}
-
             return Ok(counts_dict)
         except Exception as e:
             return Fail(f"Failed to get transaction counts by fingerprint: {str(e)}")
@@ -707,13 +1536,13 @@ class DuckDBRepository(Repository):
                     integration_settings = excluded.integration_settings,
                     updated_at = ?
                 """,
-                [integration_name, json.dumps(integration_options), now, now, now],
+                [integration_name, self._settings_json(integration_options), now, now, now],
             )
 
             conn.close()
             return Ok(None)
         except Exception as e:
-            return Fail(f"Failed to upsert integration: {str(e)}")
+            return self._write_failed(f"Failed to save integration '{integration_name}'", e)
 
     async def list_integrations(self) -> Result[List[Dict[str, Any]]]:
         """List all integrations."""
@@ -721,13 +1550,21 @@ class DuckDBRepository(Repository):
             conn = self._get_connection(read_only=True)
 
             result = conn.execute(
-                "SELECT integration_name, integration_settings FROM sys_integrations",
+                "SELECT integration_name, integration_settings FROM sys_integrations "
+                "ORDER BY integration_name",
             ).fetchall()
 
-            integrations = [
-                {"integrationName": row[0], "integrationOptions": json.loads(row[1])}
-                for row in result
-            ]
+            integrations = []
+            for name, settings_json in result:
+                settings = json.loads(settings_json)
+                integrations.append(
+                    {
+                        "integrationName": name,
+                        "integrationOptions": settings,
+                        "displayName": settings.get("display_name"),
+                        "notes": settings.get("notes"),
+                    }
+                )
 
             conn.close()
             return Ok(integrations)
@@ -757,7 +1594,7 @@ class DuckDBRepository(Repository):
             conn.close()
             return Ok(None)
         except Exception as e:
-            return Fail(f"Failed to delete integration: {str(e)}")
+            return self._write_failed(f"Failed to delete integration '{integration_name}'", e)
 
     async def get_integration_settings(
         self, integration_name: str
@@ -781,15 +1618,84 @@ class DuckDBRepository(Repository):
         except Exception as e:
             return Fail(f"Failed to get integration settings: {str(e)}")
 
-    async def get_tag_statistics(self) -> Result[Dict[str, int]]:
-        """Get tag usage statistics (frequency count for each tag)."""
+    async def get_budget_actuals(self, month: str) -> Result[List[Dict[str, Any]]]:
+        """Get spending so far for each tag-based expense category of a month.
+
+        Matches transactions the way the budget plugin does: any (or, with
+        require_all, all) of the category's tags, optionally only one sign,
+        totalled as ABS(SUM(amount)).
+        """
         try:
             conn = self._get_connection(read_only=True)
 
-            # Query to unnest tags and count occurrences
-            # DuckDB requires UNNEST in FROM clause, not SELECT
-            result = conn.execute("""
-                SELECT
+            rows = conn.execute(
+                """
+                SELECT c.category_id, c.name, c.expected, c.alerted_actual,
+                       COALESCE(ABS(SUM(t.amount::DECIMAL(38,2))), 0) AS actual
+                FROM sys_plugin_budget_categories c
+                LEFT JOIN transactions t
+                  ON t.month = c.month
+                 AND CASE WHEN c.require_all THEN list_has_all(t.tags, c.tags)
+                          ELSE list_has_any(t.tags, c.tags) END
+                 AND (c.amount_sign IS NULL
+                      OR (c.amount_sign = 'positive' AND t.amount > 0)
+                      OR (c.amount_sign = 'negative' AND t.amount < 0))
+                WHERE c.month = ? AND c.type = 'expense' AND len(c.tags) > 0
+                GROUP BY c.category_id, c.name, c.expected, c.alerted_actual, c.sort_order
+                ORDER BY c.sort_order, c.name
+                """,
+                [month],
+            ).fetchall()
+
+            conn.close()
+            return Ok(
+                [
+                    {
+                        "category_id": row[0],
+                        "name": row[1],
+                        "expected": Decimal(str(row[2])),
+                        "alerted_actual": None if row[3] is None else Decimal(str(row[3])),
+                        "actual": Decimal(str(row[4])),
+                    }
+                    for row in rows
+                ]
+            )
+        except Exception as e:
+            return Fail(f"Failed to get budget actuals: {str(e)}")
+
+    async def set_budget_alert_levels(
+        self, levels: Dict[str, Decimal | None]
+    ) -> Result[None]:
+        """Record the spending level each category was last alerted at (None clears it)."""
+        if not levels:
+            return Ok(None)
+        conn = None
+        try:
+            conn = self._get_connection()
+            conn.execute("BEGIN TRANSACTION")
+            for category_id, level in levels.items():
+                conn.execute(
+                    "UPDATE sys_plugin_budget_categories SET alerted_actual = ? WHERE category_id = ?",
+                    [level, category_id],
+                )
+            conn.execute("COMMIT")
+            conn.close()
+            return Ok(None)
+        except Exception as e:
+            self._rollback_quietly(conn)
+            return self._write_failed(
+                f"Failed to save budget alert levels for {len(levels)} categories", e
+            )
+
+    async def get_tag_statistics(self) -> Result[Dict[str, int]]:
+        """Get tag usage statistics (frequency count for each tag)."""
+        try:
+            conn = self._get_connection(read_only=True)
+
+            # Query to unnest tags and count occurrences
+            # DuckDB requires UNNEST in FROM clause, not SELECT
+            result = conn.execute("""
+                SELECT
                     tag,
                     COUNT(*) as count
                 FROM sys_transactions, UNNEST(tags) as t(tag)
@@ -823,8 +1729,9 @@ class DuckDBRepository(Repository):
                 where_clauses.append("(tags IS NOT NULL AND len(tags) > 0)")
 
             if filters.get("search"):
-                where_clauses.append("(LOWER(description) LIKE ?)")
-                params.append(f"%{filters['search'].lower()}%")
+                search_sql, search_params = self._description_search_clause(filters["search"])
+                where_clauses.append(search_sql)
+                params.extend(search_params)
 
             where_sql = " AND ".join(where_clauses) if where_clauses else "1=1"
 
@@ -842,7 +1749,8 @@ class DuckDBRepository(Repository):
                     created_at,
                     updated_at,
                     deleted_at,
-                    parent_transaction_id
+                    parent_transaction_id,
+                    status
                 FROM sys_transactions
                 WHERE {where_sql}
                 ORDER BY transaction_date DESC
@@ -867,6 +1775,7 @@ class DuckDBRepository(Repository):
                         updated_at=self._ensure_timezone(row[9]),
                         deleted_at=self._ensure_timezone(row[10]) if row[10] else None,
                         parent_transaction_id=UUID(row[11]) if row[11] else None,
+                        status=row[12] or TransactionStatus.CLEARED,
                     )
                 )
 
@@ -879,123 +1788,1450 @@ class DuckDBRepository(Repository):
         self,
         account_id: UUID,
         order_by: str = "transaction_date DESC",
+        leaves_only: bool = False,
     ) -> Result[List[Transaction]]:
         """Get all transactions for a specific account."""
         try:
             conn = self._get_connection(read_only=True)
 
+            leaves = f"AND {self._LEAF_TRANSACTIONS}" if leaves_only else ""
             result = conn.execute(
                 f"""
-                SELECT
-                    transaction_id,
-                    account_id,
-                    external_ids,
-                    amount,
-                    description,
-                    transaction_date,
-                    posted_date,
-                    tags,
-                    created_at,
-                    updated_at,
-                    deleted_at,
-                    parent_transaction_id
-                FROM sys_transactions
+                SELECT {self._TRANSACTION_COLUMNS}
+                FROM sys_transactions t
                 WHERE account_id = ?
+                {leaves}
                 ORDER BY {order_by}
             """,
                 [str(account_id)],
             ).fetchall()
 
-            transactions = []
-            for row in result:
-                transactions.append(
-                    Transaction(
-                        id=UUID(row[0]),
-                        account_id=UUID(row[1]),
-                        external_ids=json.loads(row[2]) if row[2] else {},
-                        amount=Decimal(str(row[3])),
-                        description=row[4],
-                        transaction_date=row[5],
-                        posted_date=row[6],
-                        tags=tuple(row[7]) if row[7] else (),
-                        created_at=self._ensure_timezone(row[8]),
-                        updated_at=self._ensure_timezone(row[9]),
-                        deleted_at=self._ensure_timezone(row[10]) if row[10] else None,
-                        parent_transaction_id=UUID(row[11]) if row[11] else None,
-                    )
-                )
+            transactions, row_errors = self._map_rows(
+                "sys_transactions",
+                "transaction_id",
+                result,
+                lambda row: row[0],
+                self._row_to_transaction,
+            )
 
             conn.close()
-            return Ok(transactions)
+            return self._with_row_errors(transactions, row_errors)
         except Exception as e:
             return Fail(f"Failed to get transactions by account: {str(e)}")
 
-    async def update_transaction_tags(
-        self, transaction_id: UUID, tags: List[str]
-    ) -> Result[Transaction]:
-        """Update tags for a single transaction."""
+    async def get_all_transactions(
+        self, include_deleted: bool = False
+    ) -> Result[List[Transaction]]:
+        """Get all transactions, ordered by account, date and creation time."""
         try:
-            conn = self._get_connection()
+            conn = self._get_connection(read_only=True)
 
-            now = datetime.now(timezone.utc)
+            where = "" if include_deleted else "WHERE deleted_at IS NULL"
+            rows = conn.execute(
+                f"""
+                SELECT {self._TRANSACTION_COLUMNS} FROM sys_transactions
+                {where}
+                ORDER BY account_id, transaction_date, created_at, transaction_id
+                """
+            ).fetchall()
 
-            # Update the transaction
-            conn.execute(
+            conn.close()
+            return Ok([self._row_to_transaction(row) for row in rows])
+        except Exception as e:
+            return Fail(f"Failed to get transactions: {str(e)}")
+
+    async def get_transactions_in_range(
+        self, start_date: date, end_date: date
+    ) -> Result[List[Transaction]]:
+        """Get non-deleted transactions dated within [start_date, end_date],
+        without split parents that have live children."""
+        try:
+            conn = self._get_connection(read_only=True)
+
+            rows = conn.execute(
+                f"""
+                SELECT {self._TRANSACTION_COLUMNS} FROM sys_transactions t
+                WHERE {self._LEAF_TRANSACTIONS}
+                  AND transaction_date BETWEEN ? AND ?
+                ORDER BY transaction_date, created_at
+                """,
+                [start_date, end_date],
+            ).fetchall()
+
+            conn.close()
+            return Ok([self._row_to_transaction(row) for row in rows])
+        except Exception as e:
+            return Fail(f"Failed to get transactions: {str(e)}")
+
+    async def count_transactions_added(self, since: datetime, until: datetime) -> Result[int]:
+        """Count non-deleted transactions added (synced or imported) in [since, until)."""
+        try:
+            conn = self._get_connection(read_only=True)
+
+            # created_at is stored as naive UTC
+            bounds = [dt.astimezone(timezone.utc).replace(tzinfo=None) for dt in (since, until)]
+            row = conn.execute(
                 """
-                UPDATE sys_transactions
-                SET tags = ?, updated_at = ?
-                WHERE transaction_id = ?
-            """,
-                [tags, now, transaction_id],
+                SELECT COUNT(*) FROM sys_transactions
+                WHERE deleted_at IS NULL
+                  AND created_at >= ? AND created_at < ?
+                """,
+                bounds,
+            ).fetchone()
+
+            conn.close()
+            return Ok(row[0])
+        except Exception as e:
+            return Fail(f"Failed to count transactions: {str(e)}")
+
+    async def get_transactions_filtered(
+        self,
+        account_id: UUID | None = None,
+        status: TransactionStatus | None = None,
+        start_date: date | None = None,
+        end_date: date | None = None,
+        search: str | None = None,
+        limit: int | None = None,
+    ) -> Result[List[Transaction]]:
+        """Get non-deleted transactions matching all given filters, newest first."""
+        try:
+            conn = self._get_connection(read_only=True)
+
+            where_clauses = ["deleted_at IS NULL"]
+            params: List[Any] = []
+            if account_id:
+                where_clauses.append("account_id = ?")
+                params.append(str(account_id))
+            if status:
+                where_clauses.append("status = ?")
+                params.append(TransactionStatus(status).value)
+            if start_date:
+                where_clauses.append("transaction_date >= ?")
+                params.append(start_date)
+            if end_date:
+                where_clauses.append("transaction_date <= ?")
+                params.append(end_date)
+            if search:
+                search_sql, search_params = self._description_search_clause(search)
+                where_clauses.append(search_sql)
+                params.extend(search_params)
+
+            limit_sql = ""
+            if limit is not None:
+                limit_sql = "LIMIT ?"
+                params.append(limit)
+
+            rows = conn.execute(
+                f"""
+                SELECT {self._TRANSACTION_COLUMNS} FROM sys_transactions
+                WHERE {" AND ".join(where_clauses)}
+                ORDER BY transaction_date DESC, created_at DESC
+                {limit_sql}
+                """,
+                params,
+            ).fetchall()
+
+            conn.close()
+            return Ok([self._row_to_transaction(row) for row in rows])
+        except Exception as e:
+            return Fail(f"Failed to get transactions: {str(e)}")
+
+    @staticmethod
+    def _description_search_clause(search: str) -> Tuple[str, List[Any]]:
+        """WHERE clause matching descriptions that contain search, ignoring case.
+
+        A single token without LIKE wildcards is matched against the pre-lowered
+        description_search column, so no row needs LOWER() at query time. Rows
+        whose description_search was never filled in fall back to description.
+        Anything else keeps the plain LOWER(description) LIKE scan.
+        """
+        term = search.lower()
+        if term and not any(c.isspace() or c in "%_" for c in term):
+            return (
+                "(contains(description_search, ?) "
+                "OR (description_search IS NULL AND contains(LOWER(description), ?)))",
+                [term, term],
             )
+        return "(LOWER(description) LIKE ?)", [f"%{term}%"]
 
-            # Fetch the updated transaction
-            result = conn.execute(
+    async def rebuild_search_index(self) -> Result[int]:
+        """Recompute description_search where it is missing or stale.
+
+        Returns how many rows were updated.
+        """
+        try:
+            conn = self._get_connection()
+            updated = conn.execute(
+                """
+                UPDATE sys_transactions SET description_search = lower(description)
+                WHERE description_search IS DISTINCT FROM lower(description)
+                """
+            ).fetchone()[0]
+
+            conn.close()
+            return Ok(int(updated))
+        except Exception as e:
+            return self._write_failed("Failed to rebuild search index on sys_transactions", e)
+
+    async def get_earliest_activity_dates(self) -> Result[Dict[UUID, date]]:
+        """Get each account's earliest transaction or balance snapshot date."""
+        try:
+            conn = self._get_connection(read_only=True)
+            rows = conn.execute(
+                """
+                SELECT account_id, MIN(activity_date) FROM (
+                    SELECT account_id, transaction_date AS activity_date
+                    FROM sys_transactions WHERE deleted_at IS NULL
+                    UNION ALL
+                    SELECT account_id, CAST(snapshot_time AS DATE)
+                    FROM sys_balance_snapshots
+                )
+                GROUP BY account_id
                 """
+            ).fetchall()
+
+            conn.close()
+            return Ok({UUID(account_id): earliest for account_id, earliest in rows})
+        except Exception as e:
+            return Fail(f"Failed to get account activity dates: {str(e)}")
+
+    # Integrity scans for `tl maintenance verify`. Each query selects the IDs
+    # of offending rows as "id". JSON functions are guarded with json_valid
+    # so one malformed value can't fail the whole scan.
+    _EXTERNAL_IDS_MALFORMED = """
+        CASE WHEN json_valid(external_ids) THEN json_type(external_ids) END
+            IS DISTINCT FROM 'OBJECT' AND external_ids IS NOT NULL
+    """
+    _TAGS_MALFORMED = "len(list_filter(tags, tag -> tag IS NULL)) > 0"
+    _DANGLING_PARENT = """
+        t.parent_transaction_id IS NOT NULL
+        AND (
+            t.parent_transaction_id = t.transaction_id
+            OR NOT EXISTS (
+                SELECT 1 FROM sys_transactions p
+                WHERE p.transaction_id = t.parent_transaction_id
+            )
+        )
+    """
+
+    def _scan_ids(self, query: str, limit: int) -> Result[Dict[str, Any]]:
+        """Count the rows a scan query selects and return the first few IDs."""
+        try:
+            conn = self._get_connection(read_only=True)
+            count = conn.execute(f"SELECT COUNT(*) FROM ({query})").fetchone()[0]
+            rows = conn.execute(
+                f"SELECT id FROM ({query}) ORDER BY id LIMIT ?", [limit]
+            ).fetchall()
+            conn.close()
+            return Ok({"count": int(count), "ids": [str(row[0]) for row in rows]})
+        except Exception as e:
+            return Fail(f"Failed to scan: {str(e)}")
+
+    async def scan_transactions_without_account(self, limit: int = 5) -> Result[Dict[str, Any]]:
+        """Find transactions (deleted or not) whose account_id isn't in sys_accounts."""
+        return self._scan_ids(
+            """
+            SELECT t.transaction_id AS id FROM sys_transactions t
+            WHERE NOT EXISTS (SELECT 1 FROM sys_accounts a WHERE a.account_id = t.account_id)
+            """,
+            limit,
+        )
+
+    async def scan_duplicate_fingerprints(self, limit: int = 5) -> Result[Dict[str, Any]]:
+        """Find non-deleted transactions sharing a fingerprint with another one."""
+        return self._scan_ids(
+            """
+            WITH fingerprinted AS (
                 SELECT
                     transaction_id,
-                    account_id,
-                    external_ids,
-                    amount,
-                    description,
-                    transaction_date,
-                    posted_date,
-                    tags,
-                    created_at,
-                    updated_at,
-                    deleted_at,
-                    parent_transaction_id
+                    CASE WHEN json_valid(external_ids)
+                        THEN json_extract_string(external_ids, '$.fingerprint') END AS fingerprint
                 FROM sys_transactions
-                WHERE transaction_id = ?
+                WHERE deleted_at IS NULL
+            )
+            SELECT transaction_id AS id FROM fingerprinted
+            WHERE fingerprint IN (
+                SELECT fingerprint FROM fingerprinted
+                WHERE fingerprint IS NOT NULL
+                GROUP BY fingerprint HAVING COUNT(*) > 1
+            )
             """,
-                [transaction_id],
-            ).fetchone()
+            limit,
+        )
+
+    async def scan_malformed_json(self, limit: int = 5) -> Result[Dict[str, Any]]:
+        """Find accounts and transactions whose external_ids isn't a JSON object,
+        and transactions with NULL entries in tags."""
+        return self._scan_ids(
+            f"""
+            SELECT account_id AS id FROM sys_accounts
+            WHERE {self._EXTERNAL_IDS_MALFORMED}
+            UNION ALL
+            SELECT transaction_id AS id FROM sys_transactions
+            WHERE ({self._EXTERNAL_IDS_MALFORMED}) OR {self._TAGS_MALFORMED}
+            """,
+            limit,
+        )
+
+    async def scan_dangling_parent_ids(self, limit: int = 5) -> Result[Dict[str, Any]]:
+        """Find transactions whose parent_transaction_id is missing or themselves."""
+        return self._scan_ids(
+            f"SELECT t.transaction_id AS id FROM sys_transactions t WHERE {self._DANGLING_PARENT}",
+            limit,
+        )
+
+    async def scan_snapshots_without_account(self, limit: int = 5) -> Result[Dict[str, Any]]:
+        """Find balance snapshots whose account_id isn't in sys_accounts."""
+        return self._scan_ids(
+            """
+            SELECT s.snapshot_id AS id FROM sys_balance_snapshots s
+            WHERE NOT EXISTS (SELECT 1 FROM sys_accounts a WHERE a.account_id = s.account_id)
+            """,
+            limit,
+        )
 
-            if not result:
-                conn.close()
-                return Fail(f"Transaction {transaction_id} not found")
+    async def get_missing_migrations(self) -> Result[List[str]]:
+        """Get the migrations shipped with this build that sys_migrations doesn't record."""
+        try:
+            conn = self._get_connection(read_only=True)
+            applied = {
+                row[0]
+                for row in conn.execute("SELECT migration_name FROM sys_migrations").fetchall()
+            }
+            conn.close()
+            return Ok(
+                [f.name for f in sorted(MIGRATIONS_DIR.glob("*.sql")) if f.name not in applied]
+            )
+        except Exception as e:
+            return Fail(f"Failed to read migrations: {str(e)}")
 
-            transaction = Transaction(
-                id=UUID(result[0]),
-                account_id=UUID(result[1]),
-                external_ids=MappingProxyType(
-                    json.loads(result[2]) if result[2] else {}
-                ),
-                amount=Decimal(str(result[3])),
-                description=result[4],
-                transaction_date=result[5],
-                posted_date=result[6],
-                tags=tuple(result[7]) if result[7] else (),
-                created_at=self._ensure_timezone(result[8]),
-                updated_at=self._ensure_timezone(result[9]),
-                deleted_at=self._ensure_timezone(result[10]) if result[10] else None,
-                parent_transaction_id=UUID(result[11]) if result[11] else None,
+    async def repair_malformed_json(self) -> Result[int]:
+        """Reset external_ids that aren't JSON objects to {} and drop NULL tags.
+
+        Returns how many rows were changed.
+        """
+        conn = None
+        try:
+            conn = self._get_connection()
+            conn.execute("BEGIN TRANSACTION")
+            accounts = conn.execute(
+                f"UPDATE sys_accounts SET external_ids = '{{}}' WHERE {self._EXTERNAL_IDS_MALFORMED}"
+            ).fetchone()[0]
+            transactions = conn.execute(
+                f"""
+                UPDATE sys_transactions SET
+                    external_ids = CASE WHEN {self._EXTERNAL_IDS_MALFORMED}
+                        THEN '{{}}' ELSE external_ids END,
+                    tags = list_filter(tags, tag -> tag IS NOT NULL)
+                WHERE ({self._EXTERNAL_IDS_MALFORMED}) OR {self._TAGS_MALFORMED}
+                """
+            ).fetchone()[0]
+            conn.execute("COMMIT")
+            conn.close()
+            return Ok(int(accounts) + int(transactions))
+        except Exception as e:
+            self._rollback_quietly(conn)
+            return self._write_failed("Failed to repair JSON columns", e)
+
+    async def clear_dangling_parent_ids(self) -> Result[int]:
+        """Clear parent_transaction_id where the parent is missing or the row itself.
+
+        Returns how many rows were changed.
+        """
+        try:
+            conn = self._get_connection()
+            updated = conn.execute(
+                f"""
+                UPDATE sys_transactions AS t SET parent_transaction_id = NULL
+                WHERE {self._DANGLING_PARENT}
+                """
+            ).fetchone()[0]
+            conn.close()
+            return Ok(int(updated))
+        except Exception as e:
+            return self._write_failed("Failed to clear dangling parent IDs", e)
+
+    async def update_transactions_status(
+        self, transaction_ids: List[UUID], status: TransactionStatus
+    ) -> Result[int]:
+        """Set the status of many transactions. Returns how many were updated."""
+        try:
+            if not transaction_ids:
+                return Ok(0)
+
+            conn = self._get_connection()
+            placeholders = ", ".join("?" for _ in transaction_ids)
+            updated = conn.execute(
+                f"""
+                UPDATE sys_transactions SET status = ?, updated_at = ?
+                WHERE transaction_id IN ({placeholders}) AND deleted_at IS NULL
+                """,
+                [
+                    TransactionStatus(status).value,
+                    datetime.now(timezone.utc),
+                    *(str(tx_id) for tx_id in transaction_ids),
+                ],
+            ).fetchone()[0]
+
+            conn.close()
+            return Ok(int(updated))
+        except Exception as e:
+            return self._write_failed(
+                f"Failed to update status of {len(transaction_ids)} transaction(s) to {TransactionStatus(status).value}", e
             )
 
+    async def delete_transactions(self, transaction_ids: List[UUID]) -> Result[int]:
+        """Soft-delete transactions by setting deleted_at. Returns how many were deleted.
+
+        The rows stay in sys_transactions, so fingerprint and provider ID
+        dedupe still see them and re-importing or re-syncing them is a no-op.
+        """
+        try:
+            if not transaction_ids:
+                return Ok(0)
+
+            conn = self._get_connection()
+            now = datetime.now(timezone.utc)
+            placeholders = ", ".join("?" for _ in transaction_ids)
+            deleted = conn.execute(
+                f"""
+                UPDATE sys_transactions SET deleted_at = ?, updated_at = ?
+                WHERE transaction_id IN ({placeholders}) AND deleted_at IS NULL
+                """,
+                [now, now, *(str(tx_id) for tx_id in transaction_ids)],
+            ).fetchone()[0]
+
             conn.close()
-            return Ok(transaction)
+            return Ok(int(deleted))
+        except Exception as e:
+            return self._write_failed(f"Failed to delete {len(transaction_ids)} transaction(s)", e)
+
+    async def restore_transactions(self, transaction_ids: List[UUID]) -> Result[int]:
+        """Clear deleted_at on soft-deleted transactions. Returns how many were restored."""
+        try:
+            if not transaction_ids:
+                return Ok(0)
+
+            conn = self._get_connection()
+            placeholders = ", ".join("?" for _ in transaction_ids)
+            restored = conn.execute(
+                f"""
+                UPDATE sys_transactions SET deleted_at = NULL, updated_at = ?
+                WHERE transaction_id IN ({placeholders}) AND deleted_at IS NOT NULL
+                """,
+                [datetime.now(timezone.utc), *(str(tx_id) for tx_id in transaction_ids)],
+            ).fetchone()[0]
+
+            conn.close()
+            return Ok(int(restored))
         except Exception as e:
-            return Fail(f"Failed to update transaction tags: {str(e)}")
+            return self._write_failed(f"Failed to restore {len(transaction_ids)} transaction(s)", e)
+
+    async def update_transaction_external_ids(
+        self, external_ids_by_id: Dict[UUID, Dict[str, str]]
+    ) -> Result[int]:
+        """Replace external_ids for many transactions in a single DB transaction."""
+        try:
+            if not external_ids_by_id:
+                return Ok(0)
+
+            conn = self._get_connection()
+            try:
+                conn.execute("BEGIN TRANSACTION")
+                conn.executemany(
+                    """
+                    UPDATE sys_transactions
+                    SET external_ids = ?, updated_at = CURRENT_TIMESTAMP
+                    WHERE transaction_id = ?
+                    """,
+                    [
+                        [json.dumps(external_ids, sort_keys=True), str(transaction_id)]
+                        for transaction_id, external_ids in external_ids_by_id.items()
+                    ],
+                )
+                conn.execute("COMMIT")
+            except Exception:
+                conn.execute("ROLLBACK")
+                raise
+            finally:
+                conn.close()
+
+            return Ok(len(external_ids_by_id))
+        except Exception as e:
+            return self._write_failed(
+                f"Failed to update external IDs of {len(external_ids_by_id)} transaction(s)", e
+            )
+
+    async def get_transaction_by_id(self, transaction_id: UUID) -> Result[Transaction]:
+        """Get a single transaction by ID."""
+        try:
+            conn = self._get_connection(read_only=True)
+
+            row = conn.execute(
+                f"SELECT {self._TRANSACTION_COLUMNS} FROM sys_transactions WHERE transaction_id = ?",
+                [str(transaction_id)],
+            ).fetchone()
+
+            conn.close()
+            if not row:
+                return Fail(f"Transaction not found: {transaction_id}")
+            return Ok(self._row_to_transaction(row))
+        except Exception as e:
+            return Fail(f"Failed to get transaction: {str(e)}")
+
+    async def find_transaction_ids_by_prefix(
+        self, prefix: str, limit: int = 10
+    ) -> Result[List[UUID]]:
+        """Find transaction IDs starting with the given prefix."""
+        try:
+            conn = self._get_connection(read_only=True)
+
+            rows = conn.execute(
+                """
+                SELECT transaction_id FROM sys_transactions
+                WHERE starts_with(transaction_id, ?)
+                ORDER BY transaction_id
+                LIMIT ?
+                """,
+                [prefix.lower(), limit],
+            ).fetchall()
+
+            conn.close()
+            return Ok([UUID(row[0]) for row in rows])
+        except Exception as e:
+            return Fail(f"Failed to find transactions: {str(e)}")
+
+    async def get_transactions_by_parent_id(
+        self, parent_transaction_id: UUID
+    ) -> Result[List[Transaction]]:
+        """Get the split children of a transaction."""
+        try:
+            conn = self._get_connection(read_only=True)
+
+            rows = conn.execute(
+                f"""
+                SELECT {self._TRANSACTION_COLUMNS} FROM sys_transactions
+                WHERE parent_transaction_id = ?
+                ORDER BY created_at
+                """,
+                [str(parent_transaction_id)],
+            ).fetchall()
+
+            conn.close()
+            return Ok([self._row_to_transaction(row) for row in rows])
+        except Exception as e:
+            return Fail(f"Failed to get child transactions: {str(e)}")
+
+    async def get_children(self, parent_id: UUID) -> Result[List[Transaction]]:
+        """Get the non-deleted split parts of a transaction (empty if it isn't split)."""
+        try:
+            conn = self._get_connection(read_only=True)
+
+            rows = conn.execute(
+                f"""
+                SELECT {self._TRANSACTION_COLUMNS} FROM sys_transactions
+                WHERE parent_transaction_id = ? AND deleted_at IS NULL
+                ORDER BY created_at
+                """,
+                [str(parent_id)],
+            ).fetchall()
+
+            conn.close()
+            return Ok([self._row_to_transaction(row) for row in rows])
+        except Exception as e:
+            return Fail(f"Failed to get split parts: {str(e)}")
+
+    async def get_transactions_by_fingerprint(
+        self, fingerprint: str
+    ) -> Result[List[Transaction]]:
+        """Get non-deleted transactions with the given fingerprint."""
+        try:
+            conn = self._get_connection(read_only=True)
+
+            rows = conn.execute(
+                f"""
+                SELECT {self._TRANSACTION_COLUMNS} FROM sys_transactions
+                WHERE json_extract_string(external_ids, '$.fingerprint') = ?
+                  AND deleted_at IS NULL
+                """,
+                [fingerprint],
+            ).fetchall()
+
+            conn.close()
+            return Ok([self._row_to_transaction(row) for row in rows])
+        except Exception as e:
+            return Fail(f"Failed to get transactions by fingerprint: {str(e)}")
+
+    async def update_transaction_tags(
+        self, transaction_id: UUID, tags: List[str]
+    ) -> Result[Transaction]:
+        """Update tags for a single transaction."""
+        try:
+            conn = self._get_connection()
+
+            now = datetime.now(timezone.utc)
+
+            # Update the transaction
+            conn.execute(
+                """
+                UPDATE sys_transactions
+                SET tags = ?, updated_at = ?
+                WHERE transaction_id = ?
+            """,
+                [tags, now, transaction_id],
+            )
+
+            # Fetch the updated transaction
+            result = conn.execute(
+                """
+                SELECT
+                    transaction_id,
+                    account_id,
+                    external_ids,
+                    amount,
+                    description,
+                    transaction_date,
+                    posted_date,
+                    tags,
+                    created_at,
+                    updated_at,
+                    deleted_at,
+                    parent_transaction_id,
+                    status
+                FROM sys_transactions
+                WHERE transaction_id = ?
+            """,
+                [transaction_id],
+            ).fetchone()
+
+            if not result:
+                conn.close()
+                return Fail(f"Transaction {transaction_id} not found")
+
+            transaction = Transaction(
+                id=UUID(result[0]),
+                account_id=UUID(result[1]),
+                external_ids=MappingProxyType(
+                    json.loads(result[2]) if result[2] else {}
+                ),
+                amount=Decimal(str(result[3])),
+                description=result[4],
+                transaction_date=result[5],
+                posted_date=result[6],
+                tags=tuple(result[7]) if result[7] else (),
+                created_at=self._ensure_timezone(result[8]),
+                updated_at=self._ensure_timezone(result[9]),
+                deleted_at=self._ensure_timezone(result[10]) if result[10] else None,
+                parent_transaction_id=UUID(result[11]) if result[11] else None,
+                status=result[12] or TransactionStatus.CLEARED,
+            )
+
+            conn.close()
+            return Ok(transaction)
+        except Exception as e:
+            return self._write_failed(f"Failed to update tags of transaction {transaction_id}", e)
+
+    async def bulk_update_transaction_tags(
+        self, tags_by_id: Dict[UUID, List[str]]
+    ) -> Result[int]:
+        """Replace the tags of several transactions in one transaction."""
+        try:
+            if not tags_by_id:
+                return Ok(0)
+
+            conn = self._get_connection()
+            now = datetime.now(timezone.utc)
+            conn.execute("BEGIN TRANSACTION")
+            try:
+                conn.executemany(
+                    "UPDATE sys_transactions SET tags = ?, updated_at = ? WHERE transaction_id = ?",
+                    [[list(tags), now, str(tx_id)] for tx_id, tags in tags_by_id.items()],
+                )
+                conn.execute("COMMIT")
+            except Exception:
+                self._rollback_quietly(conn)
+                raise
+            finally:
+                conn.close()
+            return Ok(len(tags_by_id))
+        except Exception as e:
+            return self._write_failed("Failed to update transaction tags", e)
+
+    async def record_provider_warnings(
+        self, integration_name: str, messages: List[str]
+    ) -> Result[None]:
+        """Persist provider warnings, deduplicating open ones and resolving on clean syncs."""
+        try:
+            conn = self._get_connection()
+            now = datetime.now(timezone.utc)
+
+            if not messages:
+                conn.execute(
+                    """
+                    UPDATE sys_provider_warnings
+                    SET resolved_at = ?
+                    WHERE integration_name = ? AND resolved_at IS NULL
+                    """,
+                    [now, integration_name],
+                )
+                conn.close()
+                return Ok()
+
+            for message in dict.fromkeys(messages):
+                existing = conn.execute(
+                    """
+                    SELECT warning_id FROM sys_provider_warnings
+                    WHERE integration_name = ? AND message = ? AND resolved_at IS NULL
+                    """,
+                    [integration_name, message],
+                ).fetchone()
+
+                if existing:
+                    conn.execute(
+                        "UPDATE sys_provider_warnings SET last_seen = ? WHERE warning_id = ?",
+                        [now, existing[0]],
+                    )
+                else:
+                    conn.execute(
+                        """
+                        INSERT INTO sys_provider_warnings (
+                            warning_id, integration_name, message, first_seen, last_seen
+                        ) VALUES (?, ?, ?, ?, ?)
+                        """,
+                        [str(uuid4()), integration_name, message, now, now],
+                    )
+
+            conn.close()
+            return Ok()
+        except Exception as e:
+            return self._write_failed(
+                f"Failed to record provider warnings for '{integration_name}'", e
+            )
+
+    async def list_provider_warnings(
+        self, include_resolved: bool = False
+    ) -> Result[List[ProviderWarning]]:
+        """List provider warnings, most recently seen first."""
+        try:
+            conn = self._get_connection(read_only=True)
+
+            where_clause = "" if include_resolved else "WHERE resolved_at IS NULL"
+            rows = conn.execute(
+                f"""
+                SELECT warning_id, integration_name, message, first_seen, last_seen, resolved_at
+                FROM sys_provider_warnings
+                {where_clause}
+                ORDER BY last_seen DESC, integration_name, message
+                """
+            ).fetchall()
+
+            warnings = [
+                ProviderWarning(
+                    id=UUID(row[0]),
+                    integration_name=row[1],
+                    message=row[2],
+                    first_seen=self._ensure_timezone(row[3]),
+                    last_seen=self._ensure_timezone(row[4]),
+                    resolved_at=self._ensure_timezone(row[5]) if row[5] else None,
+                )
+                for row in rows
+            ]
+
+            conn.close()
+            return Ok(warnings)
+        except Exception as e:
+            return Fail(f"Failed to list provider warnings: {str(e)}")
+
+    async def resolve_provider_warning(self, warning_id: UUID) -> Result[None]:
+        """Mark a provider warning as resolved."""
+        try:
+            conn = self._get_connection()
+
+            existing = conn.execute(
+                "SELECT resolved_at FROM sys_provider_warnings WHERE warning_id = ?",
+                [str(warning_id)],
+            ).fetchone()
+            if not existing:
+                conn.close()
+                return Fail(f"Warning not found: {warning_id}")
+
+            conn.execute(
+                "UPDATE sys_provider_warnings SET resolved_at = ? WHERE warning_id = ?",
+                [datetime.now(timezone.utc), str(warning_id)],
+            )
+
+            conn.close()
+            return Ok()
+        except Exception as e:
+            return self._write_failed(f"Failed to resolve provider warning {warning_id}", e)
+
+    async def add_unmapped_transactions(
+        self, integration_name: str, items: List[Tuple[str, Transaction]]
+    ) -> Result[None]:
+        """Quarantine transactions whose provider account didn't map during sync."""
+        try:
+            if not items:
+                return Ok()
+
+            conn = self._get_connection()
+            now = datetime.now(timezone.utc)
+            conn.executemany(
+                """
+                INSERT INTO sys_unmapped_transactions (
+                    integration_name, external_id, provider_account_id,
+                    transaction_data, first_seen
+                ) VALUES (?, ?, ?, ?, ?)
+                ON CONFLICT (integration_name, external_id) DO NOTHING
+                """,
+                [
+                    [
+                        integration_name,
+                        tx.external_ids.get(integration_name) or tx.external_ids["fingerprint"],
+                        provider_account_id,
+                        tx.model_dump_json(),
+                        now,
+                    ]
+                    for provider_account_id, tx in items
+                ],
+            )
+
+            conn.close()
+            return Ok()
+        except Exception as e:
+            return self._write_failed(
+                f"Failed to quarantine unmapped transactions for '{integration_name}'", e
+            )
+
+    async def get_unmapped_transactions(
+        self, provider_account_id: str | None = None
+    ) -> Result[List[UnmappedTransaction]]:
+        """List quarantined transactions, optionally for one provider account."""
+        try:
+            conn = self._get_connection(read_only=True)
+
+            where_clause = "WHERE provider_account_id = ?" if provider_account_id else ""
+            rows = conn.execute(
+                f"""
+                SELECT integration_name, provider_account_id, transaction_data, first_seen
+                FROM sys_unmapped_transactions
+                {where_clause}
+                ORDER BY provider_account_id, first_seen
+                """,
+                [provider_account_id] if provider_account_id else [],
+            ).fetchall()
+
+            unmapped = [
+                UnmappedTransaction(
+                    integration_name=row[0],
+                    provider_account_id=row[1],
+                    transaction=Transaction.model_validate_json(row[2]),
+                    first_seen=self._ensure_timezone(row[3]),
+                )
+                for row in rows
+            ]
+
+            conn.close()
+            return Ok(unmapped)
+        except Exception as e:
+            return Fail(f"Failed to list unmapped transactions: {str(e)}")
+
+    async def adopt_unmapped_transactions(
+        self, provider_account_id: str, transactions: List[Transaction]
+    ) -> Result[int]:
+        """Insert adopted transactions and clear the provider account's quarantine."""
+        out_of_range = self._check_amounts(
+            "transaction", [(tx.id, tx.amount) for tx in transactions]
+        )
+        if out_of_range:
+            return out_of_range
+        try:
+            conn = self._get_connection()
+            try:
+                conn.execute("BEGIN TRANSACTION")
+                self._upsert_transactions(conn, transactions)
+                conn.execute(
+                    "DELETE FROM sys_unmapped_transactions WHERE provider_account_id = ?",
+                    [provider_account_id],
+                )
+                conn.execute("COMMIT")
+            except Exception:
+                conn.execute("ROLLBACK")
+                raise
+            finally:
+                conn.close()
+
+            return Ok(len(transactions))
+        except Exception as e:
+            return self._write_failed(
+                f"Failed to adopt unmapped transactions from provider account {provider_account_id}", e
+            )
+
+    _IMPORT_COLUMNS = """
+        import_id, source_type, file_name, file_sha256, account_id,
+        discovered, imported, skipped, imported_at
+    """
+
+    # A row's CSV provenance is a JSON string inside external_ids
+    _CSV_IMPORT_ID_SQL = (
+        "json_extract_string(json_extract_string(external_ids, '$.csv_source'), '$.import_id')"
+    )
+    _CSV_LINE_SQL = (
+        "json_extract_string(json_extract_string(external_ids, '$.csv_source'), '$.line')"
+    )
+
+    def _row_to_import_record(self, row: tuple) -> ImportRecord:
+        return ImportRecord(
+            id=UUID(row[0]),
+            source_type=row[1],
+            file_name=row[2],
+            file_sha256=row[3],
+            account_id=UUID(row[4]),
+            discovered=row[5],
+            imported=row[6],
+            skipped=row[7],
+            imported_at=self._ensure_timezone(row[8]),
+        )
+
+    async def record_import(self, record: ImportRecord) -> Result[None]:
+        """Record a file import, or update the counts of one already recorded.
+
+        A chunked import records its batch after each chunk; imported_at
+        keeps the time of the first.
+        """
+        try:
+            conn = self._get_connection()
+            conn.execute(
+                f"""
+                INSERT INTO sys_imports ({self._IMPORT_COLUMNS})
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT (import_id) DO UPDATE SET
+                    discovered = excluded.discovered,
+                    imported = excluded.imported,
+                    skipped = excluded.skipped
+                """,
+                [
+                    str(record.id),
+                    record.source_type,
+                    record.file_name,
+                    record.file_sha256,
+                    str(record.account_id),
+                    record.discovered,
+                    record.imported,
+                    record.skipped,
+                    record.imported_at,
+                ],
+            )
+            conn.close()
+            return Ok()
+        except Exception as e:
+            return self._write_failed(f"Failed to record import {record.id} ({record.file_name})", e)
+
+    async def get_import_progress(self, import_id: UUID) -> Result[Dict[str, int]]:
+        """Rows written so far by a file import: {"rows": n, "last_line": highest CSV line}."""
+        try:
+            conn = self._get_connection(read_only=True)
+            rows, last_line = conn.execute(
+                f"""
+                SELECT COUNT(*), COALESCE(MAX(CAST({self._CSV_LINE_SQL} AS INTEGER)), 0)
+                FROM sys_transactions
+                WHERE {self._CSV_IMPORT_ID_SQL} = ?
+                """,
+                [str(import_id)],
+            ).fetchone()
+            conn.close()
+            return Ok({"rows": int(rows), "last_line": int(last_line)})
+        except Exception as e:
+            return Fail(f"Failed to get progress of import {import_id}: {str(e)}")
+
+    async def delete_import(self, import_id: UUID) -> Result[int]:
+        """Delete a file import's record and every transaction it wrote, in one DB transaction."""
+        conn = None
+        try:
+            conn = self._get_connection()
+            conn.execute("BEGIN TRANSACTION")
+            (removed,) = conn.execute(
+                f"SELECT COUNT(*) FROM sys_transactions WHERE {self._CSV_IMPORT_ID_SQL} = ?",
+                [str(import_id)],
+            ).fetchone()
+            conn.execute(
+                f"DELETE FROM sys_transactions WHERE {self._CSV_IMPORT_ID_SQL} = ?",
+                [str(import_id)],
+            )
+            conn.execute("DELETE FROM sys_imports WHERE import_id = ?", [str(import_id)])
+            conn.execute("COMMIT")
+            conn.close()
+            return Ok(int(removed))
+        except Exception as e:
+            self._rollback_quietly(conn)
+            return self._write_failed(f"Failed to delete import {import_id}", e)
+
+    async def get_import_by_file_hash(
+        self, file_sha256: str, account_id: UUID
+    ) -> Result[ImportRecord | None]:
+        """Get the most recent import of a file (by content hash) into an account."""
+        try:
+            conn = self._get_connection(read_only=True)
+            row = conn.execute(
+                f"""
+                SELECT {self._IMPORT_COLUMNS} FROM sys_imports
+                WHERE file_sha256 = ? AND account_id = ?
+                ORDER BY imported_at DESC LIMIT 1
+                """,
+                [file_sha256, str(account_id)],
+            ).fetchone()
+            conn.close()
+            return Ok(self._row_to_import_record(row) if row else None)
+        except Exception as e:
+            return Fail(f"Failed to get import: {str(e)}")
+
+    async def list_imports(self) -> Result[List[ImportRecord]]:
+        """List file imports, most recent first."""
+        try:
+            conn = self._get_connection(read_only=True)
+            rows = conn.execute(
+                f"SELECT {self._IMPORT_COLUMNS} FROM sys_imports ORDER BY imported_at DESC"
+            ).fetchall()
+            conn.close()
+            return Ok([self._row_to_import_record(row) for row in rows])
+        except Exception as e:
+            return Fail(f"Failed to list imports: {str(e)}")
+
+    _ATTACHMENT_COLUMNS = """
+        attachment_id, transaction_id, filename, stored_path, sha256, size_bytes, created_at
+    """
+
+    def _row_to_attachment(self, row: tuple) -> Attachment:
+        return Attachment(
+            id=UUID(row[0]),
+            transaction_id=UUID(row[1]),
+            filename=row[2],
+            stored_path=row[3],
+            sha256=row[4],
+            size_bytes=row[5],
+            created_at=self._ensure_timezone(row[6]),
+        )
+
+    async def add_attachment(self, attachment: Attachment) -> Result[None]:
+        """Record a file attached to a transaction."""
+        try:
+            conn = self._get_connection()
+            conn.execute(
+                f"""
+                INSERT INTO sys_attachments ({self._ATTACHMENT_COLUMNS})
+                VALUES (?, ?, ?, ?, ?, ?, ?)
+                """,
+                [
+                    str(attachment.id),
+                    str(attachment.transaction_id),
+                    attachment.filename,
+                    attachment.stored_path,
+                    attachment.sha256,
+                    attachment.size_bytes,
+                    attachment.created_at,
+                ],
+            )
+            conn.close()
+            return Ok()
+        except Exception as e:
+            return self._write_failed(f"Failed to add attachment {attachment.filename}", e)
+
+    async def get_attachments(self, transaction_id: UUID) -> Result[List[Attachment]]:
+        """Get a transaction's attachments, oldest first."""
+        try:
+            conn = self._get_connection(read_only=True)
+            rows = conn.execute(
+                f"""
+                SELECT {self._ATTACHMENT_COLUMNS} FROM sys_attachments
+                WHERE transaction_id = ?
+                ORDER BY created_at, filename
+                """,
+                [str(transaction_id)],
+            ).fetchall()
+            conn.close()
+            return Ok([self._row_to_attachment(row) for row in rows])
+        except Exception as e:
+            return Fail(f"Failed to get attachments: {str(e)}")
+
+    async def get_attachment(self, attachment_id: UUID) -> Result[Attachment | None]:
+        """Get one attachment by ID."""
+        try:
+            conn = self._get_connection(read_only=True)
+            row = conn.execute(
+                f"SELECT {self._ATTACHMENT_COLUMNS} FROM sys_attachments WHERE attachment_id = ?",
+                [str(attachment_id)],
+            ).fetchone()
+            conn.close()
+            return Ok(self._row_to_attachment(row) if row else None)
+        except Exception as e:
+            return Fail(f"Failed to get attachment: {str(e)}")
+
+    async def delete_attachment(self, attachment_id: UUID) -> Result[None]:
+        """Delete an attachment's row. The file is the caller's responsibility."""
+        try:
+            conn = self._get_connection()
+            conn.execute(
+                "DELETE FROM sys_attachments WHERE attachment_id = ?", [str(attachment_id)]
+            )
+            conn.close()
+            return Ok()
+        except Exception as e:
+            return self._write_failed(f"Failed to delete attachment {attachment_id}", e)
+
+    async def get_dangling_attachments(self) -> Result[List[Attachment]]:
+        """Get attachments whose transaction is soft-deleted or no longer exists."""
+        try:
+            conn = self._get_connection(read_only=True)
+            rows = conn.execute(
+                """
+                SELECT a.attachment_id, a.transaction_id, a.filename, a.stored_path,
+                       a.sha256, a.size_bytes, a.created_at
+                FROM sys_attachments a
+                LEFT JOIN sys_transactions t ON t.transaction_id = a.transaction_id
+                WHERE t.transaction_id IS NULL OR t.deleted_at IS NOT NULL
+                ORDER BY a.created_at, a.filename
+                """
+            ).fetchall()
+            conn.close()
+            return Ok([self._row_to_attachment(row) for row in rows])
+        except Exception as e:
+            return Fail(f"Failed to get dangling attachments: {str(e)}")
+
+    _PLANNED_COLUMNS = """
+        planned_id, account_id, description, amount, frequency, day_of_month, next_due,
+        autogenerated, last_transaction_id, created_at, updated_at
+    """
+
+    def _row_to_planned(self, row: tuple) -> PlannedTransaction:
+        return PlannedTransaction(
+            id=UUID(row[0]),
+            account_id=UUID(row[1]),
+            description=row[2],
+            amount=row[3],
+            frequency=row[4],
+            day_of_month=row[5],
+            next_due=row[6],
+            autogenerated=row[7],
+            last_transaction_id=UUID(row[8]) if row[8] else None,
+            created_at=self._ensure_timezone(row[9]),
+            updated_at=self._ensure_timezone(row[10]),
+        )
+
+    async def upsert_planned_transaction(self, planned: PlannedTransaction) -> Result[None]:
+        """Add a planned transaction, or replace the one with its ID."""
+        out_of_range = self._check_amounts("planned transaction", [(planned.id, planned.amount)])
+        if out_of_range:
+            return out_of_range
+        try:
+            conn = self._get_connection()
+            conn.execute(
+                f"""
+                INSERT INTO sys_planned_transactions ({self._PLANNED_COLUMNS})
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT (planned_id) DO UPDATE SET
+                    account_id = excluded.account_id,
+                    description = excluded.description,
+                    amount = excluded.amount,
+                    frequency = excluded.frequency,
+                    day_of_month = excluded.day_of_month,
+                    next_due = excluded.next_due,
+                    autogenerated = excluded.autogenerated,
+                    last_transaction_id = excluded.last_transaction_id,
+                    updated_at = excluded.updated_at
+                """,
+                [
+                    str(planned.id),
+                    str(planned.account_id),
+                    planned.description,
+                    planned.amount,
+                    planned.frequency.value,
+                    planned.day_of_month,
+                    planned.next_due,
+                    planned.autogenerated,
+                    str(planned.last_transaction_id) if planned.last_transaction_id else None,
+                    planned.created_at,
+                    planned.updated_at,
+                ],
+            )
+            conn.close()
+            return Ok()
+        except Exception as e:
+            return self._write_failed(f"Failed to save planned transaction {planned.description}", e)
+
+    async def list_planned_transactions(self) -> Result[List[PlannedTransaction]]:
+        """Get every planned transaction, soonest due first."""
+        try:
+            conn = self._get_connection(read_only=True)
+            rows = conn.execute(
+                f"""
+                SELECT {self._PLANNED_COLUMNS} FROM sys_planned_transactions
+                ORDER BY next_due, description, planned_id
+                """
+            ).fetchall()
+            conn.close()
+            return Ok([self._row_to_planned(row) for row in rows])
+        except Exception as e:
+            return Fail(f"Failed to list planned transactions: {str(e)}")
+
+    async def delete_planned_transaction(self, planned_id: UUID) -> Result[None]:
+        """Delete a planned transaction."""
+        try:
+            conn = self._get_connection()
+            conn.execute(
+                "DELETE FROM sys_planned_transactions WHERE planned_id = ?", [str(planned_id)]
+            )
+            conn.close()
+            return Ok()
+        except Exception as e:
+            return self._write_failed(f"Failed to delete planned transaction {planned_id}", e)
+
+    async def find_duplicate_transaction_pairs(self, since: date) -> Result[List[Tuple[UUID, UUID]]]:
+        """Pairs (keep, duplicate) of non-deleted transactions in one account
+        sharing a fingerprint, dated on or after since."""
+        try:
+            conn = self._get_connection(read_only=True)
+            rows = conn.execute(
+                """
+                WITH fingerprinted AS (
+                    SELECT
+                        transaction_id,
+                        account_id,
+                        created_at,
+                        CASE WHEN json_valid(external_ids)
+                            THEN json_extract_string(external_ids, '$.fingerprint') END AS fingerprint
+                    FROM sys_transactions
+                    WHERE deleted_at IS NULL AND transaction_date >= ?
+                ),
+                ranked AS (
+                    SELECT
+                        transaction_id,
+                        FIRST_VALUE(transaction_id) OVER (
+                            PARTITION BY account_id, fingerprint
+                            ORDER BY created_at, transaction_id
+                        ) AS keep_id
+                    FROM fingerprinted
+                    WHERE fingerprint IS NOT NULL
+                )
+                SELECT keep_id, transaction_id FROM ranked
+                WHERE transaction_id <> keep_id
+                ORDER BY keep_id, transaction_id
+                """,
+                [since],
+            ).fetchall()
+            conn.close()
+            return Ok([(UUID(keep), UUID(duplicate)) for keep, duplicate in rows])
+        except Exception as e:
+            return Fail(f"Failed to find duplicate transactions: {str(e)}")
+
+    _DUPLICATE_COLUMNS = """
+        candidate_id, kind, keep_id, duplicate_id, score, status, created_at, resolved_at
+    """
+
+    def _row_to_duplicate_candidate(self, row: tuple) -> DuplicateCandidate:
+        return DuplicateCandidate(
+            id=UUID(row[0]),
+            kind=row[1],
+            keep_id=UUID(row[2]),
+            duplicate_id=UUID(row[3]),
+            score=row[4],
+            status=row[5],
+            created_at=self._ensure_timezone(row[6]),
+            resolved_at=self._ensure_timezone(row[7]) if row[7] else None,
+        )
+
+    async def add_duplicate_candidates(self, candidates: List[DuplicateCandidate]) -> Result[int]:
+        """Queue candidates for review, skipping pairs already recorded."""
+        if not candidates:
+            return Ok(0)
+        try:
+            conn = self._get_connection()
+            try:
+                conn.execute("BEGIN TRANSACTION")
+                added = 0
+                for candidate in candidates:
+                    keep, duplicate = str(candidate.keep_id), str(candidate.duplicate_id)
+                    exists = conn.execute(
+                        """
+                        SELECT 1 FROM sys_duplicate_candidates
+                        WHERE kind = ?
+                          AND ((keep_id = ? AND duplicate_id = ?) OR (keep_id = ? AND duplicate_id = ?))
+                        """,
+                        [candidate.kind.value, keep, duplicate, duplicate, keep],
+                    ).fetchone()
+                    if exists:
+                        continue
+                    conn.execute(
+                        f"""
+                        INSERT INTO sys_duplicate_candidates ({self._DUPLICATE_COLUMNS})
+                        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                        """,
+                        [
+                            str(candidate.id),
+                            candidate.kind.value,
+                            keep,
+                            duplicate,
+                            candidate.score,
+                            candidate.status.value,
+                            candidate.created_at,
+                            candidate.resolved_at,
+                        ],
+                    )
+                    added += 1
+                conn.execute("COMMIT")
+            except Exception:
+                conn.execute("ROLLBACK")
+                raise
+            finally:
+                conn.close()
+            return Ok(added)
+        except Exception as e:
+            return self._write_failed("Failed to record duplicate candidates", e)
+
+    async def list_duplicate_candidates(
+        self, status: DuplicateStatus | None = None
+    ) -> Result[List[DuplicateCandidate]]:
+        """Get duplicate candidates, oldest first."""
+        try:
+            conn = self._get_connection(read_only=True)
+            where = "WHERE status = ?" if status is not None else ""
+            rows = conn.execute(
+                f"""
+                SELECT {self._DUPLICATE_COLUMNS} FROM sys_duplicate_candidates
+                {where}
+                ORDER BY created_at, candidate_id
+                """,
+                [status.value] if status is not None else [],
+            ).fetchall()
+            conn.close()
+            return Ok([self._row_to_duplicate_candidate(row) for row in rows])
+        except Exception as e:
+            return Fail(f"Failed to list duplicate candidates: {str(e)}")
+
+    async def set_duplicate_candidate_status(
+        self, candidate_id: UUID, status: DuplicateStatus
+    ) -> Result[None]:
+        """Move a candidate to a status; resolved_at is set unless it is reopened."""
+        try:
+            conn = self._get_connection()
+            conn.execute(
+                """
+                UPDATE sys_duplicate_candidates
+                SET status = ?,
+                    resolved_at = CASE WHEN ? = 'open' THEN NULL ELSE CURRENT_TIMESTAMP END
+                WHERE candidate_id = ?
+                """,
+                [status.value, status.value, str(candidate_id)],
+            )
+            conn.close()
+            return Ok()
+        except Exception as e:
+            return self._write_failed(f"Failed to update duplicate candidate {candidate_id}", e)
+
+    async def merge_duplicate_transaction(
+        self,
+        candidate_id: UUID,
+        keep_id: UUID,
+        duplicate_id: UUID,
+        external_ids: Dict[str, str],
+        tags: List[str],
+    ) -> Result[None]:
+        """Fold a duplicate transaction into the kept one and mark the candidate merged."""
+        try:
+            conn = self._get_connection()
+            try:
+                conn.execute("BEGIN TRANSACTION")
+                conn.execute(
+                    """
+                    UPDATE sys_transactions
+                    SET external_ids = ?, tags = ?, updated_at = CURRENT_TIMESTAMP
+                    WHERE transaction_id = ?
+                    """,
+                    [json.dumps(external_ids, sort_keys=True), tags, str(keep_id)],
+                )
+                conn.execute(
+                    """
+                    UPDATE sys_transactions
+                    SET deleted_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+                    WHERE transaction_id = ?
+                    """,
+                    [str(duplicate_id)],
+                )
+                conn.execute(
+                    """
+                    UPDATE sys_duplicate_candidates
+                    SET status = 'merged', resolved_at = CURRENT_TIMESTAMP
+                    WHERE candidate_id = ?
+                    """,
+                    [str(candidate_id)],
+                )
+                conn.execute("COMMIT")
+            except Exception:
+                conn.execute("ROLLBACK")
+                raise
+            finally:
+                conn.close()
+            return Ok()
+        except Exception as e:
+            return self._write_failed(f"Failed to merge transaction {duplicate_id} into {keep_id}", e)
+
+    async def copy_to_database(
+        self, output_path: Path, tables: Dict[str, RowTransform | None]
+    ) -> Result[Dict[str, int]]:
+        """Copy tables row by row into a new database created from the migrations."""
+        output_path = Path(output_path)
+        if output_path.exists():
+            return Fail(f"{output_path} already exists")
+
+        target = DuckDBRepository(str(output_path), strict_writes=self.strict_writes)
+        schema_result = await target.ensure_schema_upgraded()
+        if not schema_result.success:
+            output_path.unlink(missing_ok=True)
+            return schema_result
+
+        copied: Dict[str, int] = {}
+        try:
+            source = self._get_connection(read_only=True)
+            dest = duckdb.connect(str(output_path))
+            try:
+                existing = {
+                    row[0]
+                    for row in source.execute(
+                        """
+                        SELECT table_name FROM information_schema.tables
+                        WHERE table_catalog = current_database() AND table_schema = 'main'
+                          AND table_type = 'BASE TABLE'
+                        """
+                    ).fetchall()
+                }
+                dest.execute("BEGIN TRANSACTION")
+                for table, transform in tables.items():
+                    if table not in existing:
+                        copied[table] = 0
+                        continue
+                    cursor = source.execute(f'SELECT * FROM "{table}"')
+                    columns = [desc[0] for desc in cursor.description]
+                    rows = []
+                    for values in cursor.fetchall():
+                        row = dict(zip(columns, values))
+                        if transform is not None:
+                            row = transform(row)
+                        rows.append([row[column] for column in columns])
+                    if rows:
+                        column_list = ", ".join(f'"{column}"' for column in columns)
+                        placeholders = ", ".join("?" for _ in columns)
+                        dest.executemany(
+                            f'INSERT INTO "{table}" ({column_list}) VALUES ({placeholders})',
+                            rows,
+                        )
+                    copied[table] = len(rows)
+                dest.execute("COMMIT")
+            finally:
+                source.close()
+                dest.close()
+        except Exception as e:
+            output_path.unlink(missing_ok=True)
+            return self._write_failed(f"Failed to copy database to {output_path}", e)
+
+        return Ok(copied)
 
     async def compact(self) -> Result[Dict[str, Any]]:
         """Compact the database to reclaim space from deleted rows.
@@ -0,0 +1,58 @@
+"""Shared HTTP client factory for network providers."""
+
+from typing import Any, Dict
+
+import httpx
+
+from treeline.config import get_network_proxy, is_offline
+from treeline.utils import get_app_version
+
+USER_AGENT = f"treeline/{get_app_version()}"
+
+
+class OfflineError(httpx.RequestError):
+    """Raised instead of opening a connection while offline mode is on."""
+
+    def __init__(self) -> None:
+        super().__init__("Offline mode is on (--offline / TREELINE_OFFLINE)")
+
+
+def _client_options(timeout: float, options: Dict[str, Any]) -> Dict[str, Any]:
+    """Common client options: User-Agent, proxy override and env proxies.
+
+    trust_env keeps HTTPS_PROXY/HTTP_PROXY/NO_PROXY (and SSL_CERT_FILE) working
+    for users behind corporate proxies. A network.proxy setting takes
+    precedence over the environment.
+    """
+    if is_offline():
+        raise OfflineError()
+
+    headers = {"User-Agent": USER_AGENT, **options.pop("headers", {})}
+    client_options: Dict[str, Any] = {
+        "timeout": timeout,
+        "headers": headers,
+        "trust_env": True,
+        **options,
+    }
+    proxy = get_network_proxy()
+    if proxy:
+        client_options["proxy"] = proxy
+    return client_options
+
+
+def build_async_client(timeout: float = 30.0, **options: Any) -> httpx.AsyncClient:
+    """Create an async client for provider requests.
+
+    Raises:
+        OfflineError: When offline mode is on
+    """
+    return httpx.AsyncClient(**_client_options(timeout, options))
+
+
+def build_client(timeout: float = 30.0, **options: Any) -> httpx.Client:
+    """Create a blocking client for plugin and update downloads.
+
+    Raises:
+        OfflineError: When offline mode is on
+    """
+    return httpx.Client(**_client_options(timeout, options))
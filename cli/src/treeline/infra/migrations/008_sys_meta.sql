@@ -0,0 +1,8 @@
+-- Application metadata (key/value)
+-- Stores the data version written by the migration framework so older
+-- binaries can refuse to open databases created by a newer Treeline.
+CREATE TABLE IF NOT EXISTS sys_meta (
+    key VARCHAR PRIMARY KEY,
+    value VARCHAR NOT NULL,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,11 @@
+-- Persist soft errors returned by providers during sync
+-- (e.g., SimpleFIN "Connection to <bank> may need attention").
+-- Repeated identical messages bump last_seen; a clean sync resolves them.
+CREATE TABLE IF NOT EXISTS sys_provider_warnings (
+    warning_id VARCHAR PRIMARY KEY,
+    integration_name VARCHAR NOT NULL,
+    message VARCHAR NOT NULL,
+    first_seen TIMESTAMP NOT NULL,
+    last_seen TIMESTAMP NOT NULL,
+    resolved_at TIMESTAMP
+);
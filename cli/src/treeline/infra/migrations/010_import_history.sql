@@ -0,0 +1,16 @@
+-- Record each file import so re-importing an identical file can be detected
+-- without re-processing it. file_sha256 is the hash of the raw file bytes.
+-- Per-row provenance lives in sys_transactions.external_ids["csv_source"].
+CREATE TABLE IF NOT EXISTS sys_imports (
+    import_id VARCHAR PRIMARY KEY,
+    source_type VARCHAR NOT NULL,
+    file_name VARCHAR,
+    file_sha256 VARCHAR NOT NULL,
+    account_id VARCHAR NOT NULL,
+    discovered INTEGER NOT NULL DEFAULT 0,
+    imported INTEGER NOT NULL DEFAULT 0,
+    skipped INTEGER NOT NULL DEFAULT 0,
+    imported_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_imports_file_sha256 ON sys_imports(file_sha256);
@@ -0,0 +1,28 @@
+-- Add status column for the pending -> cleared -> reconciled workflow on
+-- manually tracked accounts. Values: 'pending', 'cleared', 'reconciled'.
+-- Existing rows, syncs and imports are 'cleared'.
+
+ALTER TABLE sys_transactions ADD COLUMN IF NOT EXISTS status VARCHAR DEFAULT 'cleared';
+
+UPDATE sys_transactions SET status = 'cleared' WHERE status IS NULL;
+
+-- Update the transactions view to include status
+CREATE OR REPLACE VIEW transactions AS
+SELECT
+    t.transaction_id,
+    t.account_id,
+    t.amount,
+    t.description,
+    t.transaction_date,
+    t.posted_date,
+    t.tags,
+    t.parent_transaction_id,
+    t.status,
+    -- Account details
+    a.name AS account_name,
+    a.account_type,
+    a.currency,
+    a.institution_name
+FROM sys_transactions t
+LEFT JOIN sys_accounts a ON t.account_id = a.account_id
+WHERE t.deleted_at IS NULL;
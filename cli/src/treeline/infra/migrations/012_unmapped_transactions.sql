@@ -0,0 +1,14 @@
+-- Quarantine for provider transactions whose provider account ID didn't map to
+-- a known account during sync. Only written when sync.keep_unmapped is true in
+-- settings.json; `tl maintenance adopt-unmapped` moves rows into sys_transactions.
+-- external_id is the provider's transaction ID, so overlapping syncs don't duplicate.
+CREATE TABLE IF NOT EXISTS sys_unmapped_transactions (
+    integration_name VARCHAR NOT NULL,
+    external_id VARCHAR NOT NULL,
+    provider_account_id VARCHAR NOT NULL,
+    transaction_data JSON NOT NULL,
+    first_seen TIMESTAMP NOT NULL,
+    PRIMARY KEY (integration_name, external_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_unmapped_provider_account ON sys_unmapped_transactions(provider_account_id);
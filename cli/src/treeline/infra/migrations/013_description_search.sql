@@ -0,0 +1,11 @@
+-- Lowercased copy of description so case-insensitive search can skip a
+-- per-row LOWER(). The CLI repository keeps it in sync on insert and update;
+-- rows written by other means (e.g. the SQL editor) are NULL or stale until
+-- `tl maintenance rebuild-search-index` is run.
+
+ALTER TABLE sys_transactions ADD COLUMN IF NOT EXISTS description_search VARCHAR;
+
+UPDATE sys_transactions SET description_search = lower(description);
+
+-- Serves exact and prefix lookups on the lowered description
+CREATE INDEX IF NOT EXISTS idx_sys_transactions_description_search ON sys_transactions(description_search);
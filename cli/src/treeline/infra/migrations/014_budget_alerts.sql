@@ -0,0 +1,5 @@
+-- Spending level at which sync last raised a budget alert for a category
+-- (settings.json budgets.alerts). Sync only alerts again once spending grows
+-- past it; NULL means no alert is outstanding (never alerted, or back under
+-- the expected amount).
+ALTER TABLE sys_plugin_budget_categories ADD COLUMN IF NOT EXISTS alerted_actual DECIMAL(12,2);
@@ -0,0 +1,10 @@
+-- When an account was opened and closed. Reports treat an account's balance
+-- as zero outside these dates, so a closed account stops adding its last
+-- balance to net worth, and per-account averages only count months the
+-- account was open. NULL means unknown; `tl maintenance infer-account-dates`
+-- fills opened_at from the earliest transaction or balance snapshot.
+ALTER TABLE sys_accounts ADD COLUMN IF NOT EXISTS opened_at DATE;
+ALTER TABLE sys_accounts ADD COLUMN IF NOT EXISTS closed_at DATE;
+
+CREATE OR REPLACE VIEW accounts AS
+SELECT * FROM sys_accounts;
@@ -0,0 +1,15 @@
+-- Files (e.g. PDF receipts) attached to transactions. The files themselves
+-- live under ~/.treeline/attachments/; stored_path is relative to that
+-- directory. There is no foreign key: deleting a transaction keeps its
+-- attachments (and files) until `tl maintenance prune-attachments`.
+CREATE TABLE IF NOT EXISTS sys_attachments (
+    attachment_id VARCHAR PRIMARY KEY,
+    transaction_id VARCHAR NOT NULL,
+    filename VARCHAR NOT NULL,
+    stored_path VARCHAR NOT NULL,
+    sha256 VARCHAR NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_attachments_transaction_id ON sys_attachments(transaction_id);
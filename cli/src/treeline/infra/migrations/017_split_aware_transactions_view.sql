@@ -0,0 +1,27 @@
+-- Splitting a transaction soft-deletes the parent and adds its parts as
+-- children (parent_transaction_id). A parent that still has live children
+-- (e.g. restored by hand) would be counted twice in every SUM over the
+-- view, so leave it out: the children are the transaction.
+CREATE OR REPLACE VIEW transactions AS
+SELECT
+    t.transaction_id,
+    t.account_id,
+    t.amount,
+    t.description,
+    t.transaction_date,
+    t.posted_date,
+    t.tags,
+    t.parent_transaction_id,
+    t.status,
+    -- Account details
+    a.name AS account_name,
+    a.account_type,
+    a.currency,
+    a.institution_name
+FROM sys_transactions t
+LEFT JOIN sys_accounts a ON t.account_id = a.account_id
+WHERE t.deleted_at IS NULL
+  AND NOT EXISTS (
+      SELECT 1 FROM sys_transactions c
+      WHERE c.parent_transaction_id = t.transaction_id AND c.deleted_at IS NULL
+  );
@@ -0,0 +1,52 @@
+-- Columns that dashboard queries and plugins were each deriving on their own,
+-- slightly differently: a transaction's month and year, the name to show for
+-- its account, and each account's latest balance. New columns go after the
+-- existing ones, so queries written against the earlier views (including
+-- SELECT * read by position) are unaffected. The one exception: a query
+-- defining its own "... AS month" and grouping by month now groups by the
+-- view's column (input columns win over aliases in GROUP BY); select month
+-- directly instead.
+CREATE OR REPLACE VIEW transactions AS
+SELECT
+    t.transaction_id,
+    t.account_id,
+    t.amount,
+    t.description,
+    t.transaction_date,
+    t.posted_date,
+    t.tags,
+    t.parent_transaction_id,
+    t.status,
+    -- Account details
+    a.name AS account_name,
+    a.account_type,
+    a.currency,
+    a.institution_name,
+    -- Computed columns
+    strftime(t.transaction_date, '%Y-%m') AS month,
+    year(t.transaction_date) AS year,
+    a.nickname,
+    COALESCE(NULLIF(a.nickname, ''), a.name) AS display_name,
+    -- Always false: deleted transactions are left out below. Lets a query
+    -- say so explicitly instead of relying on the view.
+    t.deleted_at IS NOT NULL AS is_deleted
+FROM sys_transactions t
+LEFT JOIN sys_accounts a ON t.account_id = a.account_id
+WHERE t.deleted_at IS NULL
+  AND NOT EXISTS (
+      SELECT 1 FROM sys_transactions c
+      WHERE c.parent_transaction_id = t.transaction_id AND c.deleted_at IS NULL
+  );
+
+-- current_balance is the most recent snapshot's balance (NULL if there are
+-- none). The balance column from sys_accounts stays as it was.
+CREATE OR REPLACE VIEW accounts AS
+SELECT
+    a.*,
+    s.current_balance
+FROM sys_accounts a
+LEFT JOIN (
+    SELECT account_id, arg_max(balance, snapshot_time) AS current_balance
+    FROM sys_balance_snapshots
+    GROUP BY account_id
+) s ON s.account_id = a.account_id;
@@ -0,0 +1,34 @@
+-- Tags added to every transaction synced, imported or entered by hand into an
+-- account, e.g. "health" on an HSA or "business" on a business checking
+-- account. A JSON array of normalized tags; set with `tl accounts set
+-- --default-tags`, and added to stored transactions with
+-- `tl maintenance apply-default-tags`.
+ALTER TABLE sys_accounts ADD COLUMN IF NOT EXISTS default_tags JSON DEFAULT '[]';
+
+-- The accounts view selected a.*, which would put default_tags before
+-- current_balance. Columns are listed so existing ones keep their positions
+-- and the new one goes last.
+CREATE OR REPLACE VIEW accounts AS
+SELECT
+    a.account_id,
+    a.name,
+    a.nickname,
+    a.account_type,
+    a.currency,
+    a.balance,
+    a.external_ids,
+    a.institution_name,
+    a.institution_url,
+    a.institution_domain,
+    a.created_at,
+    a.updated_at,
+    a.opened_at,
+    a.closed_at,
+    s.current_balance,
+    a.default_tags
+FROM sys_accounts a
+LEFT JOIN (
+    SELECT account_id, arg_max(balance, snapshot_time) AS current_balance
+    FROM sys_balance_snapshots
+    GROUP BY account_id
+) s ON s.account_id = a.account_id;
@@ -0,0 +1,21 @@
+-- Expected transactions, e.g. rent of -1800.00 on the 1st of every month.
+-- The schedule is a frequency (once, weekly, biweekly, monthly, quarterly or
+-- yearly) plus, for the monthly kinds, day_of_month; a day past the end of a
+-- month falls on its last day. next_due is the next occurrence that hasn't
+-- shown up yet: sync advances it when a matching transaction arrives, and
+-- deletes one-off items. `tl report upcoming` projects balances from them.
+CREATE TABLE IF NOT EXISTS sys_planned_transactions (
+    planned_id VARCHAR PRIMARY KEY,
+    account_id VARCHAR NOT NULL,
+    description VARCHAR NOT NULL,
+    amount DECIMAL(15,2) NOT NULL,
+    frequency VARCHAR NOT NULL,
+    day_of_month INTEGER,
+    next_due DATE NOT NULL,
+    autogenerated BOOLEAN NOT NULL DEFAULT FALSE,
+    last_transaction_id VARCHAR,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_planned_transactions_account_id ON sys_planned_transactions(account_id);
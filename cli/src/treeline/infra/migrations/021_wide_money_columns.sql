@@ -0,0 +1,122 @@
+-- Widen money columns from DECIMAL(15,2) to DECIMAL(18,2), along with the
+-- budget plugin's DECIMAL(12,2) ones. The old limit, just under 10 trillion,
+-- is within reach of a mortgage or brokerage account tracked in yen or won;
+-- DECIMAL(18,2) holds up to 9,999,999,999,999,999.99.
+-- Sums in reports are computed as DECIMAL(38,2), so totals can't overflow.
+--
+-- DuckDB can't change a column's type in a table that has indexes or takes
+-- part in a foreign key, so the account, transaction and balance tables are
+-- copied aside, dropped (children first), recreated with the same columns
+-- and constraints, and refilled. Views bind by name and use the new tables.
+
+CREATE TABLE widen_accounts AS SELECT * FROM sys_accounts;
+CREATE TABLE widen_transactions AS SELECT * FROM sys_transactions;
+CREATE TABLE widen_balance_snapshots AS SELECT * FROM sys_balance_snapshots;
+
+DROP TABLE sys_transactions;
+DROP TABLE sys_balance_snapshots;
+DROP TABLE sys_accounts;
+
+CREATE TABLE sys_accounts (
+    account_id VARCHAR PRIMARY KEY,
+    name VARCHAR NOT NULL,
+    nickname VARCHAR,
+    account_type VARCHAR,
+    currency VARCHAR NOT NULL DEFAULT 'USD',
+    balance DECIMAL(18,2),
+    external_ids JSON DEFAULT '{}',
+    institution_name VARCHAR,
+    institution_url VARCHAR,
+    institution_domain VARCHAR,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    opened_at DATE,
+    closed_at DATE,
+    default_tags JSON DEFAULT '[]'
+);
+
+CREATE TABLE sys_transactions (
+    transaction_id VARCHAR PRIMARY KEY,
+    account_id VARCHAR NOT NULL,
+    amount DECIMAL(18,2) NOT NULL,
+    description VARCHAR,
+    transaction_date DATE NOT NULL,
+    posted_date DATE NOT NULL,
+    tags VARCHAR[],
+    external_ids JSON DEFAULT '{}',
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    deleted_at TIMESTAMP,
+    parent_transaction_id VARCHAR,
+    status VARCHAR DEFAULT 'cleared',
+    description_search VARCHAR,
+    FOREIGN KEY (account_id) REFERENCES sys_accounts(account_id)
+);
+
+CREATE TABLE sys_balance_snapshots (
+    snapshot_id VARCHAR PRIMARY KEY,
+    account_id VARCHAR NOT NULL,
+    balance DECIMAL(18,2) NOT NULL,
+    snapshot_time TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    source VARCHAR,
+    FOREIGN KEY (account_id) REFERENCES sys_accounts(account_id)
+);
+
+INSERT INTO sys_accounts (
+    account_id, name, nickname, account_type, currency, balance, external_ids,
+    institution_name, institution_url, institution_domain, created_at, updated_at,
+    opened_at, closed_at, default_tags
+)
+SELECT
+    account_id, name, nickname, account_type, currency, balance, external_ids,
+    institution_name, institution_url, institution_domain, created_at, updated_at,
+    opened_at, closed_at, default_tags
+FROM widen_accounts;
+
+INSERT INTO sys_transactions (
+    transaction_id, account_id, amount, description, transaction_date, posted_date,
+    tags, external_ids, created_at, updated_at, deleted_at, parent_transaction_id,
+    status, description_search
+)
+SELECT
+    transaction_id, account_id, amount, description, transaction_date, posted_date,
+    tags, external_ids, created_at, updated_at, deleted_at, parent_transaction_id,
+    status, description_search
+FROM widen_transactions;
+
+INSERT INTO sys_balance_snapshots (
+    snapshot_id, account_id, balance, snapshot_time, created_at, updated_at, source
+)
+SELECT snapshot_id, account_id, balance, snapshot_time, created_at, updated_at, source
+FROM widen_balance_snapshots;
+
+DROP TABLE widen_accounts;
+DROP TABLE widen_transactions;
+DROP TABLE widen_balance_snapshots;
+
+CREATE INDEX IF NOT EXISTS idx_sys_transactions_account_id ON sys_transactions(account_id);
+CREATE INDEX IF NOT EXISTS idx_sys_transactions_date ON sys_transactions(transaction_date);
+CREATE INDEX IF NOT EXISTS idx_sys_transactions_parent_id ON sys_transactions(parent_transaction_id);
+CREATE INDEX IF NOT EXISTS idx_sys_transactions_description_search ON sys_transactions(description_search);
+CREATE INDEX IF NOT EXISTS idx_sys_balance_snapshots_account_id ON sys_balance_snapshots(account_id);
+CREATE INDEX IF NOT EXISTS idx_sys_balance_snapshots_time ON sys_balance_snapshots(snapshot_time);
+
+-- Tables without foreign keys only need their indexes out of the way
+DROP INDEX IF EXISTS idx_planned_transactions_account_id;
+ALTER TABLE sys_planned_transactions ALTER COLUMN amount TYPE DECIMAL(18,2);
+CREATE INDEX IF NOT EXISTS idx_planned_transactions_account_id ON sys_planned_transactions(account_id);
+
+DROP INDEX IF EXISTS idx_budget_categories_month;
+DROP INDEX IF EXISTS idx_budget_categories_month_type;
+ALTER TABLE sys_plugin_budget_categories ALTER COLUMN expected TYPE DECIMAL(18,2);
+ALTER TABLE sys_plugin_budget_categories ALTER COLUMN alerted_actual TYPE DECIMAL(18,2);
+CREATE INDEX IF NOT EXISTS idx_budget_categories_month ON sys_plugin_budget_categories(month);
+CREATE INDEX IF NOT EXISTS idx_budget_categories_month_type ON sys_plugin_budget_categories(month, type);
+
+DROP INDEX IF EXISTS idx_budget_rollovers_source;
+DROP INDEX IF EXISTS idx_budget_rollovers_target;
+ALTER TABLE sys_plugin_budget_rollovers ALTER COLUMN amount TYPE DECIMAL(18,2);
+CREATE INDEX IF NOT EXISTS idx_budget_rollovers_source ON sys_plugin_budget_rollovers(source_month);
+CREATE INDEX IF NOT EXISTS idx_budget_rollovers_target ON sys_plugin_budget_rollovers(to_month);
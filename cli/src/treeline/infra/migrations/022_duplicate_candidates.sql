@@ -0,0 +1,15 @@
+-- Possible duplicates waiting for review. A candidate is a pair: two
+-- transactions in one account sharing a fingerprint, or two accounts that
+-- separate integrations both sync. keep_id is the one kept on merge.
+-- Candidates are never deleted: a dismissed pair stays here so detecting it
+-- again doesn't put it back in the queue. status is open, dismissed or merged.
+CREATE TABLE IF NOT EXISTS sys_duplicate_candidates (
+    candidate_id VARCHAR PRIMARY KEY,
+    kind VARCHAR NOT NULL,
+    keep_id VARCHAR NOT NULL,
+    duplicate_id VARCHAR NOT NULL,
+    score DOUBLE NOT NULL,
+    status VARCHAR NOT NULL DEFAULT 'open',
+    created_at TIMESTAMP NOT NULL,
+    resolved_at TIMESTAMP
+);
@@ -0,0 +1,28 @@
+-- The name to show for an account: its nickname (set with `tl accounts
+-- nickname`) or else the provider's name, matching the transactions view's
+-- display_name. Added last so existing columns keep their positions.
+CREATE OR REPLACE VIEW accounts AS
+SELECT
+    a.account_id,
+    a.name,
+    a.nickname,
+    a.account_type,
+    a.currency,
+    a.balance,
+    a.external_ids,
+    a.institution_name,
+    a.institution_url,
+    a.institution_domain,
+    a.created_at,
+    a.updated_at,
+    a.opened_at,
+    a.closed_at,
+    s.current_balance,
+    a.default_tags,
+    COALESCE(NULLIF(a.nickname, ''), a.name) AS display_name
+FROM sys_accounts a
+LEFT JOIN (
+    SELECT account_id, arg_max(balance, snapshot_time) AS current_balance
+    FROM sys_balance_snapshots
+    GROUP BY account_id
+) s ON s.account_id = a.account_id;
@@ -0,0 +1,31 @@
+-- Archived accounts (tl accounts archive) are left out of status totals,
+-- balance backfills and sync, but their transactions stay in the
+-- transactions view. archived_at is NULL for active accounts.
+ALTER TABLE sys_accounts ADD COLUMN IF NOT EXISTS archived_at TIMESTAMP;
+
+CREATE OR REPLACE VIEW accounts AS
+SELECT
+    a.account_id,
+    a.name,
+    a.nickname,
+    a.account_type,
+    a.currency,
+    a.balance,
+    a.external_ids,
+    a.institution_name,
+    a.institution_url,
+    a.institution_domain,
+    a.created_at,
+    a.updated_at,
+    a.opened_at,
+    a.closed_at,
+    s.current_balance,
+    a.default_tags,
+    COALESCE(NULLIF(a.nickname, ''), a.name) AS display_name,
+    a.archived_at
+FROM sys_accounts a
+LEFT JOIN (
+    SELECT account_id, arg_max(balance, snapshot_time) AS current_balance
+    FROM sys_balance_snapshots
+    GROUP BY account_id
+) s ON s.account_id = a.account_id;
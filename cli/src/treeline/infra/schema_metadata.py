@@ -0,0 +1,339 @@
+"""Descriptions of the database tables and views, for `tl schema`.
+
+Column names and types come from the live database; this map supplies the
+human-readable descriptions and example queries. Update it in the same
+change as any migration that adds, renames or drops a table or column:
+tests/unit/infra/test_schema_metadata.py fails when the two disagree.
+
+Views (accounts, transactions, ...) are the user-facing interface. sys_*
+tables hold the underlying data and are only listed with --all.
+"""
+
+from typing import Any, Dict
+
+SCHEMA_METADATA: Dict[str, Dict[str, Any]] = {
+    # ------------------------------------------------------------------
+    # User-facing views
+    # ------------------------------------------------------------------
+    "accounts": {
+        "description": "Accounts, one row per account",
+        "columns": {
+            "account_id": "Account UUID",
+            "name": "Account name from the provider or as entered",
+            "nickname": "Optional display name",
+            "account_type": "checking, savings, credit, investment, loan, ... (may be NULL)",
+            "currency": "ISO currency code, e.g. USD",
+            "balance": "Unused; see current_balance",
+            "external_ids": "JSON object of provider IDs, e.g. {\"simplefin\": \"ACT-1\"}",
+            "institution_name": "Bank or institution name",
+            "institution_url": "Institution website",
+            "institution_domain": "Institution domain, e.g. chase.com",
+            "opened_at": "Date the account was opened, or NULL if unknown",
+            "closed_at": "Date the account was closed; reports count its balance as zero after this",
+            "created_at": "When the account was added (UTC)",
+            "updated_at": "When the account was last changed (UTC)",
+            "current_balance": "Balance from the latest balance snapshot, or NULL if there is none",
+            "default_tags": "JSON array of tags added to every new transaction in the account",
+            "display_name": "Nickname of the account, or its name if it has none",
+            "archived_at": "When the account was archived (UTC), or NULL if it is active",
+        },
+        "examples": [
+            "SELECT name, institution_name, account_type FROM accounts ORDER BY name",
+            "SELECT display_name, current_balance FROM accounts ORDER BY display_name",
+            "SELECT account_id FROM accounts WHERE name ILIKE '%checking%'",
+        ],
+    },
+    "transactions": {
+        "description": "Transactions with account details; excludes deleted and split parents",
+        "columns": {
+            "transaction_id": "Transaction UUID",
+            "account_id": "Account UUID",
+            "amount": "Amount; negative for money out, positive for money in",
+            "description": "Description from the bank or import",
+            "transaction_date": "Date the transaction happened",
+            "posted_date": "Date the transaction posted",
+            "tags": "List of tags, e.g. ['groceries']",
+            "parent_transaction_id": "For split parts, the transaction that was split",
+            "status": "pending, cleared or reconciled",
+            "account_name": "Name of the account",
+            "account_type": "Type of the account",
+            "currency": "Currency of the account",
+            "institution_name": "Institution of the account",
+            "month": "Month of transaction_date, e.g. 2025-12",
+            "year": "Year of transaction_date",
+            "nickname": "Nickname of the account, or NULL",
+            "display_name": "Nickname of the account, or its name if it has none",
+            "is_deleted": "Always false; deleted transactions are not in this view",
+        },
+        "examples": [
+            "SELECT transaction_date, description, amount FROM transactions ORDER BY transaction_date DESC LIMIT 20",
+            "SELECT unnest(tags) AS tag, SUM(amount) FROM transactions WHERE amount < 0 GROUP BY tag",
+            "SELECT month, SUM(amount) FROM transactions GROUP BY month ORDER BY month",
+            "SELECT display_name, SUM(amount) FROM transactions WHERE year = 2025 GROUP BY display_name",
+        ],
+    },
+    "balance_snapshots": {
+        "description": "Account balances recorded at points in time, with account details",
+        "columns": {
+            "snapshot_id": "Snapshot UUID",
+            "account_id": "Account UUID",
+            "balance": "Account balance at snapshot_time",
+            "snapshot_time": "When the balance applies (local time)",
+            "source": "sync, manual, backfill, or NULL for older data",
+            "created_at": "When the snapshot was recorded (UTC)",
+            "updated_at": "When the snapshot was last changed (UTC)",
+            "account_name": "Name of the account",
+            "institution_name": "Institution of the account",
+        },
+        "examples": [
+            "SELECT account_name, balance, snapshot_time FROM balance_snapshots ORDER BY snapshot_time DESC LIMIT 20",
+            "SELECT account_name, arg_max(balance, snapshot_time) AS latest FROM balance_snapshots GROUP BY account_name",
+        ],
+    },
+    "budget_categories": {
+        "description": "Budget categories, one set per month",
+        "columns": {
+            "category_id": "Category UUID",
+            "month": "Budget month, e.g. 2025-12",
+            "type": "income or expense",
+            "name": "Category name",
+            "expected": "Budgeted amount for the month",
+            "tags": "Tags whose transactions count toward the category",
+            "require_all": "Whether a transaction needs all tags (otherwise any)",
+            "amount_sign": "positive, negative, or NULL for either",
+            "sort_order": "Display order",
+        },
+        "examples": [
+            "SELECT name, type, expected FROM budget_categories WHERE month = '2025-12' ORDER BY sort_order",
+        ],
+    },
+    # ------------------------------------------------------------------
+    # System tables
+    # ------------------------------------------------------------------
+    "sys_accounts": {
+        "description": "Account storage behind the accounts view",
+        "columns": {
+            "account_id": "Account UUID",
+            "name": "Account name",
+            "nickname": "Optional display name",
+            "account_type": "Account type",
+            "currency": "ISO currency code",
+            "balance": "Unused; see sys_balance_snapshots",
+            "external_ids": "JSON object of provider IDs",
+            "institution_name": "Institution name",
+            "institution_url": "Institution website",
+            "institution_domain": "Institution domain",
+            "opened_at": "Date opened, or NULL",
+            "closed_at": "Date closed, or NULL while open",
+            "created_at": "Created (UTC)",
+            "updated_at": "Last changed (UTC)",
+            "default_tags": "JSON array of tags added to new transactions",
+            "archived_at": "Archived (UTC), or NULL while active",
+        },
+    },
+    "sys_transactions": {
+        "description": "Transaction storage, including deleted transactions and split parents",
+        "columns": {
+            "transaction_id": "Transaction UUID",
+            "account_id": "Account UUID",
+            "amount": "Signed amount",
+            "description": "Description",
+            "transaction_date": "Date the transaction happened",
+            "posted_date": "Date the transaction posted",
+            "tags": "List of tags",
+            "external_ids": "JSON object of provider IDs and the dedup fingerprint",
+            "created_at": "Created (UTC)",
+            "updated_at": "Last changed (UTC)",
+            "deleted_at": "Set when deleted, or when split into parts",
+            "parent_transaction_id": "For split parts, the transaction that was split",
+            "status": "pending, cleared or reconciled",
+            "description_search": "Lowercased description for search",
+        },
+    },
+    "sys_balance_snapshots": {
+        "description": "Balance snapshot storage behind the balance_snapshots view",
+        "columns": {
+            "snapshot_id": "Snapshot UUID",
+            "account_id": "Account UUID",
+            "balance": "Balance",
+            "snapshot_time": "When the balance applies (local time)",
+            "created_at": "Created (UTC)",
+            "updated_at": "Last changed (UTC)",
+            "source": "sync, manual, backfill, or NULL",
+        },
+    },
+    "sys_integrations": {
+        "description": "Configured integrations and their settings",
+        "columns": {
+            "integration_name": "Integration name, e.g. simplefin",
+            "integration_settings": "JSON settings, including credentials",
+            "created_at": "Created (UTC)",
+            "updated_at": "Last changed (UTC)",
+        },
+    },
+    "sys_migrations": {
+        "description": "Applied schema migrations",
+        "columns": {
+            "migration_name": "Migration file name",
+            "applied_at": "When it was applied",
+        },
+    },
+    "sys_meta": {
+        "description": "Application metadata, e.g. the data version",
+        "columns": {
+            "key": "Metadata key",
+            "value": "Metadata value",
+            "updated_at": "Last changed",
+        },
+    },
+    "sys_provider_warnings": {
+        "description": "Soft errors reported by providers during sync",
+        "columns": {
+            "warning_id": "Warning UUID",
+            "integration_name": "Integration that reported it",
+            "message": "Warning text",
+            "first_seen": "First reported",
+            "last_seen": "Last reported",
+            "resolved_at": "When resolved, or NULL while active",
+        },
+    },
+    "sys_imports": {
+        "description": "File imports, used to detect re-imports of the same file",
+        "columns": {
+            "import_id": "Import UUID",
+            "source_type": "Import format, e.g. csv",
+            "file_name": "Imported file name",
+            "file_sha256": "SHA-256 of the file bytes",
+            "account_id": "Account imported into",
+            "discovered": "Rows found in the file",
+            "imported": "Transactions added",
+            "skipped": "Rows skipped as duplicates",
+            "imported_at": "When the import ran",
+        },
+    },
+    "sys_attachments": {
+        "description": "Files attached to transactions, stored under ~/.treeline/attachments",
+        "columns": {
+            "attachment_id": "Attachment UUID",
+            "transaction_id": "Transaction the file is attached to",
+            "filename": "Original file name",
+            "stored_path": "Path of the copy, relative to the attachments directory",
+            "sha256": "SHA-256 of the file bytes",
+            "size_bytes": "File size in bytes",
+            "created_at": "When the file was attached",
+        },
+    },
+    "sys_planned_transactions": {
+        "description": "Expected transactions such as bills, behind tl planned and tl report upcoming",
+        "columns": {
+            "planned_id": "Planned transaction UUID",
+            "account_id": "Account it is expected in",
+            "description": "What it is, matched against synced descriptions",
+            "amount": "Expected amount (negative for bills)",
+            "frequency": "once, weekly, biweekly, monthly, quarterly or yearly",
+            "day_of_month": "Day monthly, quarterly and yearly items fall on, or NULL",
+            "next_due": "Next occurrence that hasn't arrived yet",
+            "autogenerated": "Whether recurring-transaction detection added it",
+            "last_transaction_id": "Transaction that satisfied the last occurrence",
+            "created_at": "Created",
+            "updated_at": "Last changed",
+        },
+    },
+    "sys_duplicate_candidates": {
+        "description": "Possible duplicate transactions and accounts, behind tl dedupe",
+        "columns": {
+            "candidate_id": "Candidate UUID",
+            "kind": "transaction or account",
+            "keep_id": "Transaction or account kept when the pair is merged",
+            "duplicate_id": "Transaction or account merged into keep_id",
+            "score": "How alike the pair is, from 0 to 1",
+            "status": "open, dismissed or merged",
+            "created_at": "When the pair was first detected",
+            "resolved_at": "When it was dismissed or merged, or NULL",
+        },
+    },
+    "sys_unmapped_transactions": {
+        "description": "Synced transactions whose provider account didn't match an account",
+        "columns": {
+            "integration_name": "Integration that returned it",
+            "external_id": "Provider transaction ID",
+            "provider_account_id": "Provider account ID that didn't match",
+            "transaction_data": "JSON of the provider transaction",
+            "first_seen": "When it was first synced",
+        },
+    },
+    "sys_plugin_budget_categories": {
+        "description": "Budget plugin storage behind the budget_categories view",
+        "columns": {
+            "category_id": "Category UUID",
+            "month": "Budget month",
+            "type": "income or expense",
+            "name": "Category name",
+            "expected": "Budgeted amount",
+            "tags": "Tags counted toward the category",
+            "require_all": "Whether all tags are required",
+            "amount_sign": "positive, negative, or NULL",
+            "sort_order": "Display order",
+            "created_at": "Created",
+            "updated_at": "Last changed",
+            "alerted_actual": "Spending when sync last raised a budget alert, or NULL",
+        },
+    },
+    "sys_plugin_budget_rollovers": {
+        "description": "Budget plugin rollovers between categories and months",
+        "columns": {
+            "rollover_id": "Rollover ID",
+            "source_month": "Month the rollover is from",
+            "from_category": "Category name it is from",
+            "to_category": "Category name it goes to",
+            "to_month": "Month it goes to",
+            "amount": "Amount rolled over",
+            "created_at": "Created",
+        },
+    },
+    "sys_plugin_transactions_rules": {
+        "description": "Transactions plugin auto-tag rules",
+        "columns": {
+            "rule_id": "Rule ID",
+            "name": "Rule name",
+            "sql_condition": "SQL WHERE clause (advanced rules)",
+            "conditions": "JSON conditions (rule builder)",
+            "condition_logic": "all or any",
+            "tags": "Tags to apply",
+            "enabled": "Whether the rule runs",
+            "sort_order": "Order rules run in",
+            "created_at": "Created",
+            "updated_at": "Last changed",
+        },
+    },
+    "sys_plugin_accounts_overrides": {
+        "description": "Accounts plugin per-account settings",
+        "columns": {
+            "account_id": "Account UUID",
+            "classification_override": "asset, liability, or NULL for automatic",
+            "exclude_from_net_worth": "Whether net worth leaves the account out",
+            "created_at": "Created",
+            "updated_at": "Last changed",
+        },
+    },
+    "sys_plugin_query_history": {
+        "description": "Query plugin history",
+        "columns": {
+            "history_id": "History entry ID",
+            "query": "SQL that was run",
+            "success": "Whether it succeeded",
+            "executed_at": "When it ran",
+        },
+    },
+    "sys_plugin_query_saved": {
+        "description": "Query plugin saved queries",
+        "columns": {
+            "saved_query_id": "Saved query ID",
+            "name": "Query name",
+            "query": "SQL",
+            "description": "Optional description",
+            "created_at": "Created",
+            "updated_at": "Last changed",
+        },
+    },
+}
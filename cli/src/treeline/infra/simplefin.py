@@ -1,23 +1,61 @@
 """SimpleFIN infrastructure implementation."""
 
 import base64
-from datetime import datetime, timezone
+import os
+import sys
+from datetime import date, datetime, timezone
 from decimal import Decimal
 from types import MappingProxyType
-from typing import Any, Dict, List
+from typing import Any, Dict, List, Tuple
 from urllib.parse import urlparse
 from uuid import UUID, uuid4
 
 import httpx
 
 from treeline.abstractions import DataAggregationProvider, IntegrationProvider
-from treeline.domain import Account, BalanceSnapshot, Fail, Ok, Result, Transaction
+from treeline.domain import (
+    Account,
+    BalanceSnapshot,
+    Fail,
+    Ok,
+    Result,
+    Transaction,
+    normalize_tags,
+)
+from treeline.infra.http import OfflineError, build_async_client
 from treeline.utils import get_logger
 
+# Connectivity checks should fail fast; sync requests allow 30 seconds
+HEALTH_CHECK_TIMEOUT_SECONDS = 10.0
+
+
+OFFLINE_MESSAGE = "Offline mode is on; SimpleFIN was not contacted"
+
+# Lets the end-to-end tests point SimpleFIN at a mock bridge on this machine.
+# Ignored by the packaged binary (PyInstaller sets sys.frozen), so a release
+# build can never be talked into sending credentials over plain HTTP.
+ALLOW_LOCALHOST_ENV = "TREELINE_SIMPLEFIN_ALLOW_LOCALHOST"
+LOCALHOST_NAMES = frozenset({"localhost", "127.0.0.1"})
+
+
+def allow_localhost_bridge() -> bool:
+    """Whether access URLs may point at a local mock bridge over HTTP."""
+    if getattr(sys, "frozen", False):
+        return False
+    return os.environ.get(ALLOW_LOCALHOST_ENV, "").lower() in ("1", "true")
+
 
 class SimpleFINProvider(DataAggregationProvider, IntegrationProvider):
     """SimpleFIN implementation for data aggregation."""
 
+    @property
+    def requires_network(self) -> bool:
+        return True
+
+    @property
+    def tags_are_categories(self) -> bool:
+        return True
+
     @property
     def can_get_accounts(self) -> bool:
         return True
@@ -36,102 +74,143 @@ class SimpleFINProvider(DataAggregationProvider, IntegrationProvider):
         provider_settings: Dict[str, Any] = {},
     ) -> Result[List[Account]]:
         """Get accounts from SimpleFIN."""
+        data_result = await self._get_accounts_payload(provider_settings, [], "accounts")
+        if not data_result.success:
+            return data_result
+
+        data = data_result.data
+        accounts = []
+        balance_dates = {}
+        for acc_data in data.get("accounts", []):
+            # Filter by account IDs if specified
+            if provider_account_ids and acc_data["id"] not in provider_account_ids:
+                continue
+            accounts.append(self._map_account(acc_data))
+            balance_date = self._parse_balance_date(acc_data)
+            if balance_date:
+                balance_dates[acc_data["id"]] = balance_date
+
+        # Return accounts along with any API errors/warnings
+        return Ok(
+            {
+                "accounts": accounts,
+                "errors": data.get("errors", []),
+                "balance_dates": balance_dates,
+            }
+        )
+
+    async def fetch(
+        self,
+        start_date: datetime,
+        end_date: datetime,
+        provider_settings: Dict[str, Any] = {},
+    ) -> Result[Dict[str, Any]]:
+        """Fetch accounts and transactions with a single /accounts request.
+
+        SimpleFIN includes transactions in every /accounts response, so asking
+        for accounts and transactions separately would be two round trips.
+        """
+        data_result = await self._get_accounts_payload(
+            provider_settings, self._date_params(start_date, end_date), "data"
+        )
+        if not data_result.success:
+            return data_result
+
+        data = data_result.data
+        accounts = []
+        transactions = []
+        balance_dates = {}
+        for acc_data in data.get("accounts", []):
+            accounts.append(self._map_account(acc_data))
+            balance_date = self._parse_balance_date(acc_data)
+            if balance_date:
+                balance_dates[acc_data["id"]] = balance_date
+            for tx_data in acc_data.get("transactions", []):
+                transactions.append((acc_data["id"], self._map_transaction(tx_data)))
+
+        return Ok(
+            {
+                "accounts": accounts,
+                "transactions": transactions,
+                "errors": data.get("errors", []),
+                "balance_dates": balance_dates,
+            }
+        )
+
+    async def health_check(
+        self, provider_settings: Dict[str, Any] = {}
+    ) -> Result[Dict[str, Any]]:
+        """Check SimpleFIN credentials by listing accounts (balances only).
+
+        Uses a shorter timeout than sync so a dead connection is reported quickly.
+        """
         access_url = provider_settings.get("accessUrl")
         if not access_url:
-            return Fail("accessUrl is required for SimpleFIN")
+            return Fail("accessUrl is required for SimpleFIN", context={"kind": "not_configured"})
 
-        # Parse and validate access URL
         parse_result = self._parse_access_url(access_url)
         if not parse_result.success:
-            return parse_result
+            return Fail(parse_result.error, context={"kind": "not_configured"})
 
         url_parts = parse_result.data
 
         try:
-            async with httpx.AsyncClient() as client:
+            async with build_async_client(HEALTH_CHECK_TIMEOUT_SECONDS) as client:
                 response = await client.get(
                     f"{url_parts['clean_url']}/accounts",
+                    params={"balances-only": "1"},
                     auth=(url_parts["username"], url_parts["password"]),
-                    timeout=30.0,
+                    timeout=HEALTH_CHECK_TIMEOUT_SECONDS,
                 )
-
-                # Handle specific HTTP error codes with actionable messages
-                if response.status_code == 403:
-                    return Fail(
-                        "SimpleFIN authentication failed. Your access token may be invalid or revoked. "
-                        "Please reset your SimpleFIN credentials at https://beta-bridge.simplefin.org/"
-                    )
-                if response.status_code == 402:
-                    return Fail(
-                        "SimpleFIN subscription payment required. "
-                        "Please check your SimpleFIN account at https://beta-bridge.simplefin.org/"
-                    )
-                if response.status_code != 200:
-                    return Fail(f"SimpleFIN API error: HTTP {response.status_code}")
-
-                data = response.json()
-
-                # Check for API-level errors (e.g., "You must reauthenticate")
-                # These are warnings/errors from SimpleFIN about individual connections
-                api_errors = data.get("errors", [])
-                if api_errors:
-                    logger = get_logger("infra.simplefin")
-                    logger.warning(f"SimpleFIN returned errors: {api_errors}")
-
-                accounts = []
-
-                for acc_data in data.get("accounts", []):
-                    # Filter by account IDs if specified
-                    if (
-                        provider_account_ids
-                        and acc_data["id"] not in provider_account_ids
-                    ):
-                        continue
-
-                    # Extract balance if present
-                    balance = None
-                    if "balance" in acc_data and acc_data["balance"] is not None:
-                        balance = Decimal(str(acc_data["balance"]))
-
-                    account = Account(
-                        id=uuid4(),
-                        name=acc_data["name"],
-                        currency=acc_data.get("currency", "USD"),
-                        external_ids=MappingProxyType({"simplefin": acc_data["id"]}),
-                        balance=balance,
-                        institution_name=acc_data.get("org", {}).get("name"),
-                        institution_url=acc_data.get("org", {}).get("url"),
-                        institution_domain=acc_data.get("org", {}).get("domain"),
-                        created_at=datetime.now(timezone.utc),
-                        updated_at=datetime.now(timezone.utc),
-                    )
-                    accounts.append(account)
-
-                # Return accounts along with any API errors/warnings
-                return Ok({"accounts": accounts, "errors": api_errors})
-
-        except httpx.TimeoutException as e:
-            logger = get_logger("infra.simplefin")
-            logger.error(f"Timeout fetching SimpleFIN accounts: {e}", exc_info=True)
+        except OfflineError:
+            return Fail(OFFLINE_MESSAGE, context={"kind": "offline"})
+        except httpx.TimeoutException:
             return Fail(
-                f"Failed to fetch SimpleFIN accounts: Connection timed out after 30 seconds"
-            )
-        except httpx.ConnectError as e:
-            logger = get_logger("infra.simplefin")
-            logger.error(
-                f"Connection error fetching SimpleFIN accounts: {e}", exc_info=True
+                f"SimpleFIN did not respond within {HEALTH_CHECK_TIMEOUT_SECONDS:.0f} seconds",
+                context={"kind": "timeout"},
             )
+        except httpx.ConnectError:
             return Fail(
-                f"Failed to fetch SimpleFIN accounts: Unable to connect to SimpleFIN servers"
+                "Unable to connect to SimpleFIN servers", context={"kind": "unreachable"}
             )
         except Exception as e:
             logger = get_logger("infra.simplefin")
-            logger.error(
-                f"Unexpected error fetching SimpleFIN accounts: {e}", exc_info=True
+            logger.error(f"Unexpected error checking SimpleFIN: {e}", exc_info=True)
+            return Fail(f"SimpleFIN check failed: {type(e).__name__}", context={"kind": "error"})
+
+        status = response.status_code
+        if status == 403:
+            return Fail(
+                "SimpleFIN authentication failed. Your access token may be invalid or revoked.",
+                context={"kind": "unauthorized", "http_status": status},
             )
+        if status == 402:
             return Fail(
-                f"Failed to fetch SimpleFIN accounts: {type(e).__name__}: {str(e)}"
+                "SimpleFIN subscription payment required.",
+                context={"kind": "payment_required", "http_status": status},
             )
+        if status != 200:
+            return Fail(
+                f"SimpleFIN API error: HTTP {status}",
+                context={"kind": "http_error", "http_status": status},
+            )
+
+        try:
+            data = response.json()
+        except ValueError:
+            return Fail(
+                "SimpleFIN returned an invalid response",
+                context={"kind": "http_error", "http_status": status},
+            )
+
+        return Ok(
+            {
+                "status": "ok",
+                "http_status": status,
+                "account_count": len(data.get("accounts", [])),
+                "warnings": data.get("errors", []),
+            }
+        )
 
     async def get_transactions(
         self,
@@ -141,29 +220,60 @@ class SimpleFINProvider(DataAggregationProvider, IntegrationProvider):
         provider_settings: Dict[str, Any] = {},
     ) -> Result[List[Transaction]]:
         """Get transactions from SimpleFIN."""
+        params = self._date_params(start_date, end_date)
+        # Note: SimpleFIN uses 'account' (singular) specified multiple times, not comma-separated
+        for acc_id in provider_account_ids:
+            params.append(("account", acc_id))
+
+        data_result = await self._get_accounts_payload(
+            provider_settings, params, "transactions"
+        )
+        if not data_result.success:
+            return data_result
+
+        data = data_result.data
+
+        # Return list of tuples: (simplefin_account_id, transaction)
+        # This allows service layer to map accounts without polluting external_ids
+        transactions_with_accounts = []
+        for acc_data in data.get("accounts", []):
+            for tx_data in acc_data.get("transactions", []):
+                transactions_with_accounts.append(
+                    (acc_data["id"], self._map_transaction(tx_data))
+                )
+
+        # Return transactions along with any API errors/warnings
+        return Ok(
+            {"transactions": transactions_with_accounts, "errors": data.get("errors", [])}
+        )
+
+    async def _get_accounts_payload(
+        self,
+        provider_settings: Dict[str, Any],
+        params: List[Tuple[str, str]],
+        what: str,
+    ) -> Result[Dict[str, Any]]:
+        """GET /accounts and return the decoded JSON.
+
+        Args:
+            provider_settings: Integration settings holding the accessUrl
+            params: Query parameters
+            what: What is being fetched, for error messages ("accounts", ...)
+        """
         access_url = provider_settings.get("accessUrl")
         if not access_url:
             return Fail("accessUrl is required for SimpleFIN")
 
+        # Parse and validate access URL
         parse_result = self._parse_access_url(access_url)
         if not parse_result.success:
             return parse_result
 
         url_parts = parse_result.data
+        logger = get_logger("infra.simplefin")
 
         try:
-            # Build query parameters
-            # Note: SimpleFIN uses 'account' (singular) specified multiple times, not comma-separated
-            params: list[tuple[str, str]] = []
-            if start_date:
-                params.append(("start-date", str(int(start_date.timestamp()))))
-            if end_date:
-                params.append(("end-date", str(int(end_date.timestamp()))))
-            if provider_account_ids:
-                for acc_id in provider_account_ids:
-                    params.append(("account", acc_id))
-
-            async with httpx.AsyncClient() as client:
+            async with build_async_client() as client:
                 response = await client.get(
                     f"{url_parts['clean_url']}/accounts",
                     auth=(url_parts["username"], url_parts["password"]),
@@ -191,71 +301,85 @@ class SimpleFINProvider(DataAggregationProvider, IntegrationProvider):
                 # These are warnings/errors from SimpleFIN about individual connections
                 api_errors = data.get("errors", [])
                 if api_errors:
-                    logger = get_logger("infra.simplefin")
                     logger.warning(f"SimpleFIN returned errors: {api_errors}")
 
-                # Return list of tuples: (simplefin_account_id, transaction)
-                # This allows service layer to map accounts without polluting external_ids
-                transactions_with_accounts = []
-
-                for acc_data in data.get("accounts", []):
-                    simplefin_account_id = acc_data["id"]
-                    for tx_data in acc_data.get("transactions", []):
-                        transaction = Transaction(
-                            id=uuid4(),
-                            account_id=UUID(
-                                int=0
-                            ),  # Placeholder, will be mapped by service
-                            external_ids=MappingProxyType(
-                                {
-                                    "simplefin": tx_data["id"],
-                                }
-                            ),
-                            amount=Decimal(str(tx_data["amount"])),
-                            description=tx_data.get("description", ""),
-                            transaction_date=datetime.fromtimestamp(
-                                tx_data["posted"], tz=timezone.utc
-                            ),
-                            posted_date=datetime.fromtimestamp(
-                                tx_data["posted"], tz=timezone.utc
-                            ),
-                            tags=tuple([tx_data["extra"]["category"]])
-                            if tx_data.get("extra", {}).get("category")
-                            else tuple(),
-                            created_at=datetime.now(timezone.utc),
-                            updated_at=datetime.now(timezone.utc),
-                        )
-                        transactions_with_accounts.append(
-                            (simplefin_account_id, transaction)
-                        )
-
-                # Return transactions along with any API errors/warnings
-                return Ok(
-                    {"transactions": transactions_with_accounts, "errors": api_errors}
-                )
+                return Ok(data)
 
+        except OfflineError:
+            return Fail(OFFLINE_MESSAGE, context={"kind": "offline"})
         except httpx.TimeoutException as e:
-            logger = get_logger("infra.simplefin")
-            logger.error(f"Timeout fetching SimpleFIN transactions: {e}", exc_info=True)
+            logger.error(f"Timeout fetching SimpleFIN {what}: {e}", exc_info=True)
             return Fail(
-                f"Failed to fetch SimpleFIN transactions: Connection timed out after 30 seconds"
+                f"Failed to fetch SimpleFIN {what}: Connection timed out after 30 seconds"
             )
         except httpx.ConnectError as e:
-            logger = get_logger("infra.simplefin")
-            logger.error(
-                f"Connection error fetching SimpleFIN transactions: {e}", exc_info=True
-            )
+            logger.error(f"Connection error fetching SimpleFIN {what}: {e}", exc_info=True)
             return Fail(
-                f"Failed to fetch SimpleFIN transactions: Unable to connect to SimpleFIN servers"
+                f"Failed to fetch SimpleFIN {what}: Unable to connect to SimpleFIN servers"
             )
         except Exception as e:
-            logger = get_logger("infra.simplefin")
-            logger.error(
-                f"Unexpected error fetching SimpleFIN transactions: {e}", exc_info=True
-            )
-            return Fail(
-                f"Failed to fetch SimpleFIN transactions: {type(e).__name__}: {str(e)}"
-            )
+            logger.error(f"Unexpected error fetching SimpleFIN {what}: {e}", exc_info=True)
+            return Fail(f"Failed to fetch SimpleFIN {what}: {type(e).__name__}: {str(e)}")
+
+    def _date_params(
+        self, start_date: datetime | None, end_date: datetime | None
+    ) -> List[Tuple[str, str]]:
+        """start-date/end-date query parameters as Unix timestamps."""
+        params: List[Tuple[str, str]] = []
+        if start_date:
+            params.append(("start-date", str(int(start_date.timestamp()))))
+        if end_date:
+            params.append(("end-date", str(int(end_date.timestamp()))))
+        return params
+
+    def _map_account(self, acc_data: Dict[str, Any]) -> Account:
+        """Build an Account from a SimpleFIN account object."""
+        # Extract balance if present
+        balance = None
+        if acc_data.get("balance") not in (None, ""):
+            balance = Decimal(str(acc_data["balance"]))
+
+        return Account(
+            id=uuid4(),
+            name=acc_data["name"],
+            currency=acc_data.get("currency", "USD"),
+            external_ids=MappingProxyType({"simplefin": acc_data["id"]}),
+            balance=balance,
+            institution_name=acc_data.get("org", {}).get("name"),
+            institution_url=acc_data.get("org", {}).get("url"),
+            institution_domain=acc_data.get("org", {}).get("domain"),
+            created_at=datetime.now(timezone.utc),
+            updated_at=datetime.now(timezone.utc),
+        )
+
+    def _map_transaction(self, tx_data: Dict[str, Any]) -> Transaction:
+        """Build a Transaction from a SimpleFIN transaction object."""
+        return Transaction(
+            id=uuid4(),
+            account_id=UUID(int=0),  # Placeholder, will be mapped by service
+            external_ids=MappingProxyType(
+                {
+                    "simplefin": tx_data["id"],
+                }
+            ),
+            amount=Decimal(str(tx_data["amount"])),
+            description=tx_data.get("description", ""),
+            transaction_date=datetime.fromtimestamp(tx_data["posted"], tz=timezone.utc),
+            posted_date=datetime.fromtimestamp(tx_data["posted"], tz=timezone.utc),
+            tags=tuple(normalize_tags([tx_data.get("extra", {}).get("category") or ""])),
+            created_at=datetime.now(timezone.utc),
+            updated_at=datetime.now(timezone.utc),
+        )
+
+    def _parse_balance_date(self, acc_data: Dict[str, Any]) -> date | None:
+        """The UTC date of the account's balance-date timestamp, if valid."""
+        value = acc_data.get("balance-date")
+        if value is None:
+            return None
+        try:
+            return datetime.fromtimestamp(int(value), tz=timezone.utc).date()
+        except (TypeError, ValueError, OverflowError, OSError):
+            return None
 
     async def get_balances(
         self,
@@ -286,7 +410,7 @@ class SimpleFINProvider(DataAggregationProvider, IntegrationProvider):
                 return Fail("Invalid setup token format")
 
             # Exchange setup token for access URL
-            async with httpx.AsyncClient() as client:
+            async with build_async_client() as client:
                 response = await client.post(claim_url, timeout=30.0)
 
                 if response.status_code != 200:
@@ -299,6 +423,8 @@ class SimpleFINProvider(DataAggregationProvider, IntegrationProvider):
 
                 return Ok({"accessUrl": access_url})
 
+        except OfflineError:
+            return Fail(OFFLINE_MESSAGE, context={"kind": "offline"})
         except httpx.TimeoutException as e:
             logger = get_logger("infra.simplefin")
             logger.error(
@@ -332,19 +458,24 @@ class SimpleFINProvider(DataAggregationProvider, IntegrationProvider):
         except Exception:
             return Fail("Invalid URL format")
 
+        local_bridge = parsed.hostname in LOCALHOST_NAMES and allow_localhost_bridge()
+
         # Validate HTTPS
-        if parsed.scheme != "https":
+        if parsed.scheme != "https" and not local_bridge:
             return Fail("accessUrl must use HTTPS")
 
         # Validate domain
-        if not parsed.hostname or not parsed.hostname.endswith("simplefin.org"):
+        if not local_bridge and (
+            not parsed.hostname or not parsed.hostname.endswith("simplefin.org")
+        ):
             return Fail("accessUrl must be from simplefin.org domain")
 
         # Validate credentials
         if not parsed.username or not parsed.password:
             return Fail("accessUrl must contain username and password")
 
-        clean_url = f"{parsed.scheme}://{parsed.hostname}{parsed.path}"
+        host = f"{parsed.hostname}:{parsed.port}" if parsed.port else parsed.hostname
+        clean_url = f"{parsed.scheme}://{host}{parsed.path}"
 
         return Ok(
             {
@@ -0,0 +1,174 @@
+"""Advisory lock serializing the write phases of imports, syncs and backfills."""
+
+import asyncio
+import json
+import os
+import sys
+import time
+from datetime import datetime, timezone
+from pathlib import Path
+from typing import Any, Dict
+
+from treeline.abstractions import WriteLock
+from treeline.config import get_write_lock_wait
+from treeline.domain import Fail, Ok, Result
+
+# Suffix of the lock file kept next to each database; the app takes it too for
+# its own writes (ui/src-tauri)
+WRITE_LOCK_SUFFIX = ".write.lock"
+# How often a waiting process looks at the lock again
+POLL_INTERVAL_SECONDS = 0.1
+# A lock file that still can't be read after this long was left by a process
+# that died between creating and writing it
+UNREADABLE_LOCK_GRACE_SECONDS = 5
+
+
+def write_lock_path(db_path: Path) -> Path:
+    """The lock file for a database, so the demo and real databases lock separately."""
+    db_path = Path(db_path)
+    return db_path.with_name(db_path.name + WRITE_LOCK_SUFFIX)
+
+
+def pid_running(pid: int) -> bool:
+    """Whether a process with this ID is running."""
+    if pid <= 0:
+        return False
+    if sys.platform == "win32":
+        # os.kill would terminate the process on Windows
+        import ctypes
+
+        kernel32 = ctypes.WinDLL("kernel32", use_last_error=True)
+        process_query_limited_information, still_active, access_denied = 0x1000, 259, 5
+        handle = kernel32.OpenProcess(process_query_limited_information, False, pid)
+        if not handle:
+            return ctypes.get_last_error() == access_denied
+        try:
+            exit_code = ctypes.c_ulong()
+            if not kernel32.GetExitCodeProcess(handle, ctypes.byref(exit_code)):
+                return True
+            return exit_code.value == still_active
+        finally:
+            kernel32.CloseHandle(handle)
+    try:
+        os.kill(pid, 0)
+    except ProcessLookupError:
+        return False
+    except PermissionError:
+        return True
+    return True
+
+
+class FileWriteLock(WriteLock):
+    """WriteLock held as a lock file next to the database (see write_lock_path).
+
+    Treeline opens a DuckDB connection per query, so without it two
+    processes could each take their dedupe snapshot before either inserts,
+    and both insert the same rows. The lock file is created atomically and
+    holds {"pid", "operation", "started_at"}; a lock whose process is gone
+    is stale and taken over. Waiting for a live one gives up after
+    wait_seconds (get_write_lock_wait() when None).
+
+    Reentrant within a process, so a sync's nested write phases don't wait
+    on each other.
+    """
+
+    def __init__(self, path: Path, wait_seconds: float | None = None):
+        self.path = Path(path)
+        self.wait_seconds = wait_seconds
+        self._depth = 0
+
+    async def acquire(self, operation: str) -> Result[None]:
+        if self._depth:
+            self._depth += 1
+            return Ok(None)
+
+        wait = self.wait_seconds if self.wait_seconds is not None else get_write_lock_wait()
+        deadline = time.monotonic() + wait
+        while True:
+            try:
+                fd = os.open(self.path, os.O_CREAT | os.O_EXCL | os.O_WRONLY)
+            except FileExistsError:
+                holder = self._read_holder()
+                if self._is_stale(holder):
+                    self._remove_stale(holder)
+                    continue
+                if time.monotonic() >= deadline:
+                    return self._locked_error(holder, wait)
+                await asyncio.sleep(POLL_INTERVAL_SECONDS)
+                continue
+
+            with os.fdopen(fd, "w") as f:
+                json.dump(
+                    {
+                        "pid": os.getpid(),
+                        "operation": operation,
+                        "started_at": datetime.now(timezone.utc).isoformat(),
+                    },
+                    f,
+                )
+            self._depth = 1
+            return Ok(None)
+
+    def release(self) -> None:
+        if not self._depth:
+            return
+        self._depth -= 1
+        if self._depth:
+            return
+        holder = self._read_holder()
+        if holder is not None and holder.get("pid") == os.getpid():
+            self.path.unlink(missing_ok=True)
+
+    def _read_holder(self) -> Dict[str, Any] | None:
+        try:
+            holder = json.loads(self.path.read_text())
+        except (OSError, ValueError):
+            return None
+        return holder if isinstance(holder, dict) else None
+
+    def _is_stale(self, holder: Dict[str, Any] | None) -> bool:
+        if holder is None:
+            try:
+                age = time.time() - self.path.stat().st_mtime
+            except OSError:
+                # Released since the failed create; just try again
+                return False
+            return age > UNREADABLE_LOCK_GRACE_SECONDS
+        try:
+            return not pid_running(int(holder.get("pid", 0)))
+        except (TypeError, ValueError):
+            return True
+
+    def _remove_stale(self, holder: Dict[str, Any] | None) -> None:
+        """Remove a stale lock without removing one another process just took.
+
+        The lock is moved aside first; if what was moved isn't the stale
+        lock (another process replaced it in between), it's put back.
+        """
+        aside = self.path.with_name(f"{self.path.name}.stale-{os.getpid()}")
+        try:
+            os.replace(self.path, aside)
+        except OSError:
+            return
+        try:
+            moved = json.loads(aside.read_text())
+        except (OSError, ValueError):
+            moved = None
+        if moved != holder:
+            try:
+                os.link(aside, self.path)
+            except OSError:
+                pass
+        aside.unlink(missing_ok=True)
+
+    def _locked_error(self, holder: Dict[str, Any] | None, wait: float) -> Result[None]:
+        if holder:
+            who = f"{holder.get('operation', 'unknown')}, pid {holder.get('pid', '?')}"
+        else:
+            who = "starting up"
+        return Fail(
+            f"Another treeline operation ({who}) is writing to the database. Gave up "
+            f"after waiting {wait:g}s; try again when it finishes, or wait longer with "
+            f"--wait-lock",
+            context={"kind": "write_locked", "holder": holder},
+        )
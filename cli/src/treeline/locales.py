@@ -0,0 +1,36 @@
+"""Locales amounts and dates are formatted for, and how to resolve one."""
+
+# Number and date conventions, looked up by full locale tag then by language.
+# Unknown locales fall back to DEFAULT_LOCALE.
+LOCALE_FORMATS = {
+    "en-US": {"thousands": ",", "decimal": ".", "minus": "-", "symbol_after": False, "date": "%m/%d/%Y"},
+    "en": {"thousands": ",", "decimal": ".", "minus": "-", "symbol_after": False, "date": "%d/%m/%Y"},
+    "de": {"thousands": ".", "decimal": ",", "minus": "-", "symbol_after": True, "date": "%d.%m.%Y"},
+    "fr": {"thousands": ".", "decimal": ",", "minus": "-", "symbol_after": True, "date": "%d/%m/%Y"},
+    "es": {"thousands": ".", "decimal": ",", "minus": "-", "symbol_after": True, "date": "%d/%m/%Y"},
+    "it": {"thousands": ".", "decimal": ",", "minus": "-", "symbol_after": True, "date": "%d/%m/%Y"},
+    "nl": {"thousands": ".", "decimal": ",", "minus": "-", "symbol_after": False, "date": "%d-%m-%Y"},
+    "pt": {"thousands": ".", "decimal": ",", "minus": "-", "symbol_after": False, "date": "%d/%m/%Y"},
+    "sv": {"thousands": "\u00a0", "decimal": ",", "minus": "\u2212", "symbol_after": True, "date": "%Y-%m-%d"},
+    "nb": {"thousands": "\u00a0", "decimal": ",", "minus": "\u2212", "symbol_after": True, "date": "%d.%m.%Y"},
+}
+
+DEFAULT_LOCALE = "en-US"
+
+
+def resolve_locale(value: str | None) -> str:
+    """Normalize a locale name to a supported tag.
+
+    Accepts BCP 47 tags ("de-DE") and POSIX names ("de_DE.UTF-8").
+
+    Returns:
+        The normalized tag, or DEFAULT_LOCALE if the language isn't supported
+    """
+    if not value:
+        return DEFAULT_LOCALE
+    tag = value.split(".", 1)[0].split("@", 1)[0].replace("_", "-")
+    language, _, region = tag.partition("-")
+    language = language.lower()
+    if language not in LOCALE_FORMATS:
+        return DEFAULT_LOCALE
+    return f"{language}-{region.upper()}" if region else language
@@ -0,0 +1,145 @@
+"""Period strings for reports (e.g. 2024-Q3, last-month, ytd).
+
+A period resolves to an inclusive (start, end) date range so report
+commands can accept a single --period instead of --since/--until.
+"""
+
+import calendar
+import re
+from datetime import date, timedelta
+from typing import Optional, Tuple
+
+DateRange = Tuple[date, date]
+
+WEEKDAYS = ["monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday"]
+
+_YEAR_RE = re.compile(r"^(\d{4})$")
+_QUARTER_RE = re.compile(r"^(\d{4})-q([1-4])$")
+_MONTH_RE = re.compile(r"^(\d{4})-(\d{2})$")
+_WEEK_RE = re.compile(r"^(\d{4})-w(\d{2})$")
+_LAST_DAYS_RE = re.compile(r"^last-(\d+)d$")
+_RELATIVE_RE = re.compile(r"^(this|last)-(week|month|quarter|year)$")
+
+PERIOD_HELP = (
+    "Period: YYYY, YYYY-QN, YYYY-MM, YYYY-Www, this-/last-week|month|quarter|year, "
+    "ytd, mtd, or last-Nd"
+)
+
+
+def _month_range(year: int, month: int) -> DateRange:
+    last_day = calendar.monthrange(year, month)[1]
+    return date(year, month, 1), date(year, month, last_day)
+
+
+def _quarter_range(year: int, quarter: int) -> DateRange:
+    first_month = (quarter - 1) * 3 + 1
+    start, _ = _month_range(year, first_month)
+    _, end = _month_range(year, first_month + 2)
+    return start, end
+
+
+def _week_range(day: date, week_start: int) -> DateRange:
+    start = day - timedelta(days=(day.weekday() - week_start) % 7)
+    return start, start + timedelta(days=6)
+
+
+def _shift_month(year: int, month: int, delta: int) -> Tuple[int, int]:
+    index = year * 12 + (month - 1) + delta
+    return index // 12, index % 12 + 1
+
+
+def parse_period(
+    value: str, today: Optional[date] = None, week_start: int = 0
+) -> DateRange:
+    """Resolve a period string to an inclusive (start, end) date range.
+
+    Args:
+        value: Period string (case-insensitive), see PERIOD_HELP
+        today: Reference date for relative periods (defaults to today)
+        week_start: First day of the week, 0=Monday .. 6=Sunday
+
+    Raises:
+        ValueError: If the period string is not recognized
+    """
+    today = today or date.today()
+    period = value.strip().lower()
+
+    if period == "ytd":
+        return date(today.year, 1, 1), today
+    if period == "mtd":
+        return date(today.year, today.month, 1), today
+    if period == "today":
+        return today, today
+
+    match = _LAST_DAYS_RE.match(period)
+    if match:
+        days = int(match.group(1))
+        if days < 1:
+            raise ValueError(f"Invalid period: {value} (day count must be at least 1)")
+        return today - timedelta(days=days - 1), today
+
+    match = _RELATIVE_RE.match(period)
+    if match:
+        which, unit = match.groups()
+        offset = 0 if which == "this" else -1
+        if unit == "week":
+            start, end = _week_range(today, week_start)
+            shift = timedelta(days=7 * offset)
+            return start + shift, end + shift
+        if unit == "month":
+            return _month_range(*_shift_month(today.year, today.month, offset))
+        if unit == "quarter":
+            quarter = (today.month - 1) // 3 + 1
+            year, month = _shift_month(today.year, (quarter - 1) * 3 + 1, 3 * offset)
+            return _quarter_range(year, (month - 1) // 3 + 1)
+        year = today.year + offset
+        return date(year, 1, 1), date(year, 12, 31)
+
+    match = _YEAR_RE.match(period)
+    if match:
+        year = int(match.group(1))
+        return date(year, 1, 1), date(year, 12, 31)
+
+    match = _QUARTER_RE.match(period)
+    if match:
+        return _quarter_range(int(match.group(1)), int(match.group(2)))
+
+    match = _MONTH_RE.match(period)
+    if match:
+        year, month = int(match.group(1)), int(match.group(2))
+        if not 1 <= month <= 12:
+            raise ValueError(f"Invalid period: {value} (month must be 01-12)")
+        return _month_range(year, month)
+
+    match = _WEEK_RE.match(period)
+    if match:
+        # ISO weeks always start on Monday, regardless of week_start
+        year, week = int(match.group(1)), int(match.group(2))
+        try:
+            start = date.fromisocalendar(year, week, 1)
+        except ValueError:
+            raise ValueError(f"Invalid period: {value} (no such ISO week)") from None
+        return start, start + timedelta(days=6)
+
+    raise ValueError(f"Invalid period: {value}. Expected {PERIOD_HELP}")
+
+
+def resolve_date_range(
+    period: Optional[str],
+    since: Optional[date],
+    until: Optional[date],
+    today: Optional[date] = None,
+    week_start: int = 0,
+) -> Tuple[Optional[date], Optional[date]]:
+    """Combine a --period option with --since/--until options.
+
+    --period is an alternative to --since/--until, so giving both is an error.
+
+    Raises:
+        ValueError: If both forms are given or the period is invalid
+    """
+    if period is None:
+        return since, until
+    if since is not None or until is not None:
+        raise ValueError("--period cannot be combined with --since/--until")
+    return parse_period(period, today=today, week_start=week_start)
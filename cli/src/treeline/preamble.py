@@ -0,0 +1,36 @@
+"""Notices shown ahead of a command's output.
+
+Commands that report on the user's data (status, sync, reports, the import
+summary) start their human-facing output with print_preamble and pass their
+--json payload through with_preamble, so the notices read the same
+everywhere. Today that is the demo mode marker: screenshots of demo data
+otherwise look exactly like real finances.
+"""
+
+from typing import Any, Dict
+
+from rich.console import Console
+
+from treeline.config import is_demo_mode
+from treeline.theme import get_theme
+
+theme = get_theme()
+
+DEMO_BANNER = "DEMO MODE — data is simulated"
+
+
+def print_preamble(console: Console) -> None:
+    """Print the notices that go above human-facing output."""
+    if is_demo_mode():
+        console.print(f"[{theme.muted}]{DEMO_BANNER}[/{theme.muted}]")
+
+
+def with_preamble(data: Dict[str, Any]) -> Dict[str, Any]:
+    """Add the notices to a --json payload as top-level fields.
+
+    Real-mode payloads are returned unchanged; in demo mode "demo_mode": true
+    comes first.
+    """
+    if is_demo_mode():
+        return {"demo_mode": True, **data}
+    return data
@@ -0,0 +1,162 @@
+"""Rolling averages, downsampling and linear trends for daily series.
+
+Balance history and cash flow are built one value per calendar day. They are
+smoothed over that full daily series, including days before the requested
+range when there are any, and only then downsampled to weeks or months. That
+way the first and last points of a chart average as many days as the rest,
+which smoothing a paged or downsampled series can't do.
+
+All math is Decimal (default 28-digit context). Smoothed values and trend
+figures are rounded half-even to cents when returned; the EMA carries full
+precision from day to day so rounding doesn't accumulate.
+"""
+
+from dataclasses import dataclass
+from datetime import date, timedelta
+from decimal import ROUND_HALF_EVEN, Decimal
+from typing import Any, Dict, Iterable, List, Sequence, Tuple
+
+SMOOTHING_KINDS = ("sma", "ema")
+INTERVALS = ("day", "week", "month")
+MAX_WINDOW_DAYS = 365
+# Days ahead the trend line is projected
+PROJECTION_DAYS = (30, 90)
+# Average Gregorian month, for the trend's slope per month
+DAYS_PER_MONTH = Decimal("365.2425") / 12
+
+CENT = Decimal("0.01")
+
+SMOOTHING_HELP = (
+    "Add a rolling average per point: sma:<days> (simple) or ema:<days> (exponential), e.g. sma:30"
+)
+
+
+def to_cents(value: Decimal) -> Decimal:
+    return value.quantize(CENT, rounding=ROUND_HALF_EVEN)
+
+
+@dataclass(frozen=True)
+class Smoothing:
+    """A rolling average over window_days calendar days."""
+
+    window_days: int
+    kind: str = "sma"
+
+    def __post_init__(self) -> None:
+        if self.kind not in SMOOTHING_KINDS:
+            raise ValueError(
+                f"Unknown smoothing kind: {self.kind} (expected {' or '.join(SMOOTHING_KINDS)})"
+            )
+        if not 1 <= self.window_days <= MAX_WINDOW_DAYS:
+            raise ValueError(f"Smoothing window must be 1 to {MAX_WINDOW_DAYS} days")
+
+    @classmethod
+    def parse(cls, text: str) -> "Smoothing":
+        """Parse "sma:30" or "ema:7". Raises ValueError on anything else."""
+        kind, _, window = text.strip().lower().partition(":")
+        try:
+            window_days = int(window)
+        except ValueError:
+            raise ValueError(
+                f"Invalid smoothing: {text} (expected <kind>:<days>, e.g. sma:30)"
+            ) from None
+        return cls(window_days=window_days, kind=kind)
+
+    def to_json(self) -> Dict[str, Any]:
+        return {"window_days": self.window_days, "kind": self.kind}
+
+
+def smooth(values: Sequence[Decimal], smoothing: Smoothing) -> List[Decimal]:
+    """Rolling average of a daily series, one value per input value, in cents.
+
+    SMA is the mean of the last window_days values. EMA weights each day by
+    alpha = 2 / (window_days + 1) and starts from the first value. During
+    warm-up (fewer than window_days values so far) SMA averages the values
+    there are; callers pass days before the range they report to avoid it.
+    """
+    smoothed: List[Decimal] = []
+    if smoothing.kind == "sma":
+        total = Decimal("0")
+        for i, value in enumerate(values):
+            total += value
+            if i >= smoothing.window_days:
+                total -= values[i - smoothing.window_days]
+            smoothed.append(total / min(i + 1, smoothing.window_days))
+    else:
+        alpha = Decimal(2) / (smoothing.window_days + 1)
+        average = None
+        for value in values:
+            average = value if average is None else alpha * value + (1 - alpha) * average
+            smoothed.append(average)
+    return [to_cents(value) for value in smoothed]
+
+
+def linear_trend(points: Sequence[Tuple[date, Decimal]]) -> Dict[str, Any] | None:
+    """Least-squares line through (day, value) points.
+
+    Returns slope_per_month and the line's value PROJECTION_DAYS after the
+    last point (projected_30d, projected_90d), in cents, or None with fewer
+    than two distinct days.
+    """
+    if len({day for day, _ in points}) < 2:
+        return None
+
+    first = points[0][0]
+    xs = [Decimal((day - first).days) for day, _ in points]
+    ys = [value for _, value in points]
+    mean_x = sum(xs) / len(xs)
+    mean_y = sum(ys) / len(ys)
+    slope = sum((x - mean_x) * (y - mean_y) for x, y in zip(xs, ys)) / sum(
+        (x - mean_x) ** 2 for x in xs
+    )
+    intercept = mean_y - slope * mean_x
+
+    last = max(day for day, _ in points)
+    trend: Dict[str, Any] = {
+        "start_date": first,
+        "end_date": last,
+        "slope_per_month": to_cents(slope * DAYS_PER_MONTH),
+    }
+    for days in PROJECTION_DAYS:
+        x = Decimal((last - first).days + days)
+        trend[f"projected_{days}d"] = to_cents(intercept + slope * x)
+    return trend
+
+
+def _bucket(day: date, interval: str, week_start: int) -> date:
+    if interval == "week":
+        return day - timedelta(days=(day.weekday() - week_start) % 7)
+    if interval == "month":
+        return day.replace(day=1)
+    return day
+
+
+def downsample(
+    points: Iterable[Dict[str, Any]],
+    interval: str,
+    sum_keys: Iterable[str] = (),
+    week_start: int = 0,
+) -> List[Dict[str, Any]]:
+    """One point per week or month: the last day's point, dated that day.
+
+    Values under sum_keys (flows such as a day's net amount) are totalled
+    over the bucket instead; everything else, including smoothed values, is
+    taken as of the bucket's last day. "day" returns the points unchanged.
+    """
+    if interval not in INTERVALS:
+        raise ValueError(f"Unknown interval: {interval} (expected {', '.join(INTERVALS)})")
+    points = list(points)
+    if interval == "day":
+        return points
+
+    sum_keys = tuple(sum_keys)
+    buckets: Dict[date, Dict[str, Any]] = {}
+    for point in points:
+        key = _bucket(point["date"], interval, week_start)
+        previous = buckets.get(key)
+        merged = dict(point)
+        if previous is not None:
+            for name in sum_keys:
+                merged[name] = previous[name] + point[name]
+        buckets[key] = merged
+    return list(buckets.values())
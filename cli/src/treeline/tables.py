@@ -0,0 +1,153 @@
+"""Tables that fit the terminal.
+
+Query results, transaction lists and import previews can be wider than the
+terminal, and rich's default is to wrap every cell until each row is a
+block of fragments. Tables built here keep one line per row instead: every
+column is capped at max_col_width and cut with an ellipsis, and if the table
+still doesn't fit the terminal the widest columns are cut further, so short
+columns (dates, amounts) stay readable.
+
+Nothing is truncated with --wide or when output isn't a terminal (piped to
+a file, less or grep); the table is then printed at its natural width.
+"""
+
+from dataclasses import dataclass
+from typing import Any, List, Optional, Sequence, Tuple
+
+from rich.console import Console
+from rich.measure import Measurement
+from rich.table import Table
+
+DEFAULT_MAX_COL_WIDTH = 40
+
+# Measuring limit for untruncated tables; wider than any real result
+UNBOUNDED_WIDTH = 100_000
+
+# Narrowest a column is cut to when fitting the terminal: one character and …
+MIN_COL_WIDTH = 2
+
+MAX_COL_WIDTH_HELP = f"Cut cells longer than this with … (default {DEFAULT_MAX_COL_WIDTH} in a terminal)"
+WIDE_HELP = "Don't truncate cells or fit the table to the terminal"
+
+
+@dataclass(frozen=True)
+class TableLayout:
+    """How a table is fitted to the output.
+
+    max_col_width of None means no truncation; columns of None means all
+    columns, in their original order.
+    """
+
+    max_col_width: Optional[int] = DEFAULT_MAX_COL_WIDTH
+    columns: Optional[List[str]] = None
+
+
+def resolve_layout(
+    console: Console,
+    max_col_width: Optional[int] = None,
+    wide: bool = False,
+    columns: Optional[str] = None,
+) -> TableLayout:
+    """Build a TableLayout from the --max-col-width, --wide and --columns flags.
+
+    An explicit --max-col-width applies even when output is piped.
+
+    Raises:
+        ValueError: If the flags conflict or are out of range
+    """
+    if wide and max_col_width is not None:
+        raise ValueError("Use either --wide or --max-col-width, not both")
+    if max_col_width is not None and max_col_width < MIN_COL_WIDTH:
+        raise ValueError(f"--max-col-width must be at least {MIN_COL_WIDTH}")
+
+    if max_col_width is None and not wide and console.is_terminal:
+        max_col_width = DEFAULT_MAX_COL_WIDTH
+    return TableLayout(max_col_width=max_col_width, columns=parse_columns(columns))
+
+
+def parse_columns(columns: Optional[str]) -> Optional[List[str]]:
+    """Split a --columns value ("a,b,c") into names; None if not given."""
+    if columns is None:
+        return None
+    names = [name.strip() for name in columns.split(",") if name.strip()]
+    if not names:
+        raise ValueError("--columns needs at least one column name")
+    return names
+
+
+def project_columns(
+    columns: Sequence[str], rows: Sequence[Sequence[Any]], wanted: Optional[List[str]]
+) -> Tuple[List[str], List[List[Any]]]:
+    """Keep only the wanted columns, in the order given.
+
+    Names match case-insensitively, like SQL identifiers.
+
+    Raises:
+        ValueError: If a wanted column isn't in the result
+    """
+    if not wanted:
+        return list(columns), [list(row) for row in rows]
+
+    positions = {}
+    for i, name in enumerate(columns):
+        positions.setdefault(name.lower(), i)
+    unknown = [name for name in wanted if name.lower() not in positions]
+    if unknown:
+        raise ValueError(
+            f"Unknown column{'s' if len(unknown) > 1 else ''}: {', '.join(unknown)} "
+            f"(available: {', '.join(columns)})"
+        )
+
+    indices = [positions[name.lower()] for name in wanted]
+    return [columns[i] for i in indices], [[row[i] for i in indices] for row in rows]
+
+
+def add_column(table: Table, layout: TableLayout, header: str, **kwargs: Any) -> None:
+    """Add a column that follows the layout's truncation.
+
+    Keyword arguments are passed to Table.add_column.
+    """
+    if layout.max_col_width is not None:
+        kwargs.setdefault("no_wrap", True)
+        kwargs.setdefault("overflow", "ellipsis")
+        kwargs.setdefault("max_width", layout.max_col_width)
+    table.add_column(header, **kwargs)
+
+
+def print_table(console: Console, table: Table, layout: TableLayout) -> None:
+    """Print a table fitted to the terminal, or at full width when not truncating."""
+    if layout.max_col_width is not None:
+        _fit_to_width(console, table, console.width)
+        console.print(table)
+        return
+    measurement = console.measure(table, options=console.options.update_width(UNBOUNDED_WIDTH))
+    table.width = measurement.maximum
+    console.print(table, crop=False)
+
+
+def _fit_to_width(console: Console, table: Table, width: int) -> None:
+    """Lower the widest columns' max_width until the table fits in width.
+
+    Rich would shrink every column in proportion instead, squeezing short
+    columns to nothing while long ones stay wide.
+    """
+    options = console.options.update_width(UNBOUNDED_WIDTH)
+    widths = []
+    for column in table.columns:
+        natural = max(
+            Measurement.get(console, options, cell).maximum
+            for cell in [column.header, *column.cells]
+        )
+        widths.append(min(natural, column.max_width or natural))
+    overhead = console.measure(table, options=options).maximum - sum(widths)
+
+    available = width - overhead
+    if sum(widths) <= available:
+        return
+    # Largest level L such that capping every column at L fits
+    level = max(widths)
+    while level > MIN_COL_WIDTH and sum(min(w, level) for w in widths) > available:
+        level -= 1
+    for column, natural in zip(table.columns, widths):
+        if natural > level:
+            column.max_width = level
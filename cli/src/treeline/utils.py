@@ -4,6 +4,9 @@ import logging
 import os
 from datetime import datetime
 from pathlib import Path
+from typing import Union
+from urllib.parse import urlparse
+from urllib.request import url2pathname
 
 
 def get_treeline_dir() -> Path:
@@ -19,6 +22,77 @@ def get_treeline_dir() -> Path:
     return Path.home() / ".treeline"
 
 
+def resolve_user_path(value: Union[str, os.PathLike]) -> Path:
+    """Turn a path the user typed, pasted or the desktop app passed into an OS path.
+
+    A path that exists as given is used as is. Otherwise surrounding
+    whitespace and matching quotes are dropped (Windows "Copy as path", or
+    'C:\\...' passed through cmd.exe, which keeps the quotes), and a file://
+    URL is turned into the path it names, percent-decoded. ~ is expanded.
+    Path objects are only expanded; they are already OS paths.
+    """
+    if not isinstance(value, str):
+        return Path(value).expanduser()
+
+    as_given = Path(value).expanduser()
+    if as_given.exists():
+        return as_given
+
+    text = value.strip()
+    if len(text) >= 2 and text[0] == text[-1] and text[0] in "\"'":
+        text = text[1:-1].strip()
+    if text.lower().startswith("file:"):
+        url = urlparse(text)
+        text = url2pathname(url.path)
+        if url.netloc and url.netloc != "localhost":
+            # file://server/share/x.csv is a UNC path on Windows
+            text = f"//{url.netloc}{text}"
+    return Path(text).expanduser()
+
+
+def get_app_version() -> str:
+    """Get the installed treeline version from package metadata."""
+    try:
+        from importlib.metadata import version
+
+        return version("treeline-money")
+    except Exception:
+        return "0.1.0"
+
+
+# Written next to the package by the release build (ui/scripts/build-cli.sh, publish.yml)
+BUILD_INFO_FILE = Path(__file__).parent / "_build_info.json"
+
+
+def get_build_info() -> dict:
+    """Get the git commit and build date of this build.
+
+    Release binaries read them from _build_info.json; a source checkout
+    falls back to asking git for the commit. Unknown values are None.
+    """
+    import json
+    import subprocess
+
+    try:
+        data = json.loads(BUILD_INFO_FILE.read_text())
+        return {"git_commit": data.get("git_commit"), "build_date": data.get("build_date")}
+    except (OSError, ValueError):
+        pass
+
+    try:
+        result = subprocess.run(
+            ["git", "rev-parse", "--short=12", "HEAD"],
+            cwd=Path(__file__).parent,
+            capture_output=True,
+            text=True,
+            timeout=2,
+        )
+        commit = result.stdout.strip() if result.returncode == 0 else None
+    except (OSError, subprocess.SubprocessError):
+        commit = None
+    return {"git_commit": commit or None, "build_date": None}
+
+
 def get_log_dir() -> Path:
     """Get the treeline log directory.
 
@@ -0,0 +1,193 @@
+"""Mock SimpleFIN bridge for end-to-end sync tests.
+
+Serves the endpoints the CLI uses, from a JSON fixture:
+  POST /claim/<token>        -> access URL pointing back at this server
+  GET  /simplefin/accounts   -> accounts, with transactions inside start-date/end-date
+
+Fixture transactions use "days_ago" instead of a "posted" timestamp (and
+accounts "balance_days_ago" instead of "balance-date") so the data always
+falls inside the CLI's sync windows.
+
+Usage:
+    with MockSimpleFIN() as bridge:
+        token = bridge.setup_token()   # pass to `tl setup simplefin --token`
+        bridge.fail_with(403)          # inject errors
+        bridge.fail_after(3, 500)      # ...once three more requests succeed
+        bridge.latency = 0.5           # slow responses down
+
+The CLI only accepts this server's plain-HTTP localhost URLs with
+TREELINE_SIMPLEFIN_ALLOW_LOCALHOST=1 set.
+"""
+
+import base64
+import copy
+import json
+import threading
+import time
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from pathlib import Path
+from typing import Any, Dict, List
+from urllib.parse import parse_qs, urlparse
+
+FIXTURES_DIR = Path(__file__).parent / "fixtures"
+DEFAULT_FIXTURE = FIXTURES_DIR / "simplefin_accounts.json"
+
+USERNAME = "mock-user"
+PASSWORD = "mock-secret"
+SECONDS_PER_DAY = 86400
+
+
+class MockSimpleFIN:
+    """A SimpleFIN bridge on 127.0.0.1, running on a background thread."""
+
+    def __init__(self, fixture: Path = DEFAULT_FIXTURE, latency: float = 0.0):
+        self.latency = latency
+        self.error_status: int | None = None
+        self._succeed_before_error: int | None = None
+        self.requests: List[Dict[str, Any]] = []
+        self._now = int(time.time())
+        self._data = self._load_fixture(fixture)
+        self._server = ThreadingHTTPServer(("127.0.0.1", 0), self._handler_class())
+        self._thread = threading.Thread(target=self._server.serve_forever, daemon=True)
+
+    @property
+    def base_url(self) -> str:
+        host, port = self._server.server_address[:2]
+        return f"http://{host}:{port}"
+
+    @property
+    def access_url(self) -> str:
+        host, port = self._server.server_address[:2]
+        return f"http://{USERNAME}:{PASSWORD}@{host}:{port}/simplefin"
+
+    def setup_token(self, claim_id: str = "mock-claim") -> str:
+        """Base64 setup token whose claim URL is this server."""
+        return base64.b64encode(f"{self.base_url}/claim/{claim_id}".encode()).decode()
+
+    def fail_with(self, status: int | None) -> None:
+        """Answer every request with this HTTP status (e.g. 403, 402, 429, 500); None to stop."""
+        self.error_status = status
+        self._succeed_before_error = None
+
+    def fail_after(self, count: int, status: int) -> None:
+        """Answer the next `count` requests normally, then every one after with `status`."""
+        self.error_status = status
+        self._succeed_before_error = count
+
+    def _injected_error(self) -> int | None:
+        if self._succeed_before_error:
+            self._succeed_before_error -= 1
+            return None
+        return self.error_status
+
+    def add_transaction(self, account_id: str, transaction: Dict[str, Any]) -> None:
+        """Add a fixture-style transaction (with "days_ago") to an account."""
+        account = next(a for a in self._data["accounts"] if a["id"] == account_id)
+        account["transactions"].append(self._resolve_transaction(transaction))
+
+    def start(self) -> "MockSimpleFIN":
+        self._thread.start()
+        return self
+
+    def stop(self) -> None:
+        self._server.shutdown()
+        self._server.server_close()
+
+    def __enter__(self) -> "MockSimpleFIN":
+        return self.start()
+
+    def __exit__(self, *exc) -> None:
+        self.stop()
+
+    def _load_fixture(self, fixture: Path) -> Dict[str, Any]:
+        data = json.loads(fixture.read_text())
+        for account in data["accounts"]:
+            days_ago = account.pop("balance_days_ago", 0)
+            account["balance-date"] = self._now - days_ago * SECONDS_PER_DAY
+            account["transactions"] = [
+                self._resolve_transaction(tx) for tx in account.get("transactions", [])
+            ]
+        return data
+
+    def _resolve_transaction(self, transaction: Dict[str, Any]) -> Dict[str, Any]:
+        tx = dict(transaction)
+        days_ago = tx.pop("days_ago", 0)
+        tx.setdefault("posted", self._now - days_ago * SECONDS_PER_DAY)
+        tx.setdefault("transacted_at", tx["posted"])
+        return tx
+
+    def _accounts_payload(self, query: Dict[str, List[str]]) -> Dict[str, Any]:
+        """The /accounts response for the given query, like the real bridge."""
+        start = int(query["start-date"][0]) if "start-date" in query else None
+        end = int(query["end-date"][0]) if "end-date" in query else None
+        wanted = set(query.get("account", []))
+        balances_only = query.get("balances-only", ["0"])[0] == "1"
+
+        payload = copy.deepcopy(self._data)
+        if wanted:
+            payload["accounts"] = [a for a in payload["accounts"] if a["id"] in wanted]
+        for account in payload["accounts"]:
+            if balances_only:
+                account["transactions"] = []
+                continue
+            # start-date is inclusive, end-date exclusive
+            account["transactions"] = [
+                tx
+                for tx in account["transactions"]
+                if (start is None or tx["posted"] >= start) and (end is None or tx["posted"] < end)
+            ]
+        return payload
+
+    def _handler_class(self):
+        bridge = self
+
+        class Handler(BaseHTTPRequestHandler):
+            def log_message(self, *args) -> None:
+                pass
+
+            def _record(self) -> Dict[str, List[str]]:
+                parsed = urlparse(self.path)
+                query = parse_qs(parsed.query)
+                bridge.requests.append(
+                    {"method": self.command, "path": parsed.path, "query": query}
+                )
+                if bridge.latency:
+                    time.sleep(bridge.latency)
+                return query
+
+            def _send(self, status: int, body: str, content_type: str = "text/plain") -> None:
+                encoded = body.encode()
+                self.send_response(status)
+                self.send_header("Content-Type", content_type)
+                self.send_header("Content-Length", str(len(encoded)))
+                self.end_headers()
+                self.wfile.write(encoded)
+
+            def _authorized(self) -> bool:
+                expected = base64.b64encode(f"{USERNAME}:{PASSWORD}".encode()).decode()
+                return self.headers.get("Authorization") == f"Basic {expected}"
+
+            def do_POST(self) -> None:
+                self._record()
+                error_status = bridge._injected_error()
+                if error_status:
+                    self._send(error_status, "Injected error")
+                elif self.path.startswith("/claim/"):
+                    self._send(200, bridge.access_url)
+                else:
+                    self._send(404, "Not found")
+
+            def do_GET(self) -> None:
+                query = self._record()
+                error_status = bridge._injected_error()
+                if error_status:
+                    self._send(error_status, "Injected error")
+                elif urlparse(self.path).path != "/simplefin/accounts":
+                    self._send(404, "Not found")
+                elif not self._authorized():
+                    self._send(403, "Forbidden")
+                else:
+                    payload = bridge._accounts_payload(query)
+                    self._send(200, json.dumps(payload), "application/json")
+
+        return Handler
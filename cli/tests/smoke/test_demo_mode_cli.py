@@ -8,6 +8,7 @@ Based on TEST_PLAN.md - covers all major CLI commands.
 
 import json
 import os
+import re
 import subprocess
 import tempfile
 from pathlib import Path
@@ -39,6 +40,9 @@ def run_cli(args: list[str], treeline_dir: str, input_text: str | None = None) -
     )
 
 
+FULL_UUID = re.compile(r"[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}")
+
+
 class TestDemoCommand:
     """Tests for tl demo command."""
 
@@ -82,6 +86,53 @@ class TestDemoCommand:
             # Should show either ON or OFF
             assert "Demo mode is" in result.stdout
 
+    def test_demo_reset_rebuilds_demo_data(self):
+        """Test that 'tl demo reset' discards changes and restores demo data."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+            result = run_cli(["query", "SELECT COUNT(*) FROM transactions", "--json"], tmpdir)
+            original_count = json.loads(result.stdout)["rows"][0][0]
+
+            run_cli(["accounts", "create", "--name", "Scratch"], tmpdir)
+
+            result = run_cli(["demo", "reset"], tmpdir)
+            assert result.returncode == 0, f"demo reset failed: {result.stderr}"
+
+            result = run_cli(["query", "SELECT COUNT(*) FROM accounts WHERE name = 'Scratch'", "--json"], tmpdir)
+            assert json.loads(result.stdout)["rows"][0][0] == 0
+            result = run_cli(["query", "SELECT COUNT(*) FROM transactions", "--json"], tmpdir)
+            assert json.loads(result.stdout)["rows"][0][0] == original_count
+
+    def test_demo_reset_refuses_when_demo_off(self):
+        """Test that 'tl demo reset' never touches the real database."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            result = run_cli(["demo", "reset"], tmpdir)
+            assert result.returncode == 1
+
+
+class TestAccountsCommand:
+    """Tests for tl accounts command."""
+
+    def test_accounts_list_json_sorted_by_institution(self):
+        """Test that accounts list --json returns every account, grouped by institution."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+            result = run_cli(["accounts", "list", "--json"], tmpdir)
+            assert result.returncode == 0, f"accounts list failed: {result.stderr}"
+
+            accounts = json.loads(result.stdout)["accounts"]
+            assert accounts
+            institutions = [acc["institution_name"] or "" for acc in accounts]
+            assert institutions == sorted(institutions, key=lambda i: (not i, i.casefold()))
+
+    def test_accounts_list_empty_database(self):
+        """Test that accounts list points at tl sync when there are no accounts."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            result = run_cli(["accounts", "list"], tmpdir)
+            assert result.returncode == 0
+            assert "No accounts yet" in result.stdout
+            assert "tl sync" in result.stdout
+
 
 class TestStatusCommand:
     """Tests for tl status command."""
@@ -108,6 +159,66 @@ class TestStatusCommand:
             assert data["total_accounts"] > 0
             assert data["total_transactions"] > 0
 
+    def test_status_json_is_identical_between_runs(self):
+        """Test that status --json is byte-identical for the same data, accounts sorted by name."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+            first = run_cli(["status", "--json"], tmpdir)
+            second = run_cli(["status", "--json"], tmpdir)
+            assert first.returncode == 0
+            assert first.stdout == second.stdout
+
+            names = [acc["name"] for acc in json.loads(first.stdout)["accounts"]]
+            assert names == sorted(names, key=str.casefold)
+
+    def test_status_group_by_institution(self):
+        """Test that --group-by institution adds groups with string subtotals."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+            result = run_cli(["status", "--json", "--group-by", "institution"], tmpdir)
+            assert result.returncode == 0
+
+            data = json.loads(result.stdout)
+            assert data["group_by"] == "institution"
+            grouped_ids = [i for group in data["groups"] for i in group["account_ids"]]
+            assert sorted(grouped_ids) == sorted(acc["id"] for acc in data["accounts"])
+            assert all(isinstance(v, str) for v in data["totals"].values())
+
+            flat = json.loads(
+                run_cli(["status", "--json", "--group-by", "none"], tmpdir).stdout
+            )
+            assert flat["group_by"] is None
+            assert "groups" not in flat
+
+
+class TestDemoBanner:
+    """Tests for the demo mode marker on human-facing output."""
+
+    def test_banner_and_json_field_in_demo_mode(self):
+        """Test that reports say they're simulated and --json stays parseable."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+            for args in (["status"], ["sync"], ["report", "cashflow"]):
+                result = run_cli(args, tmpdir)
+                assert result.returncode == 0, result.stderr
+                assert result.stdout.lstrip().startswith("DEMO MODE"), args
+
+                result = run_cli(args + ["--json"], tmpdir)
+                assert result.returncode == 0, result.stderr
+                assert json.loads(result.stdout)["demo_mode"] is True
+
+    def test_no_banner_in_real_mode(self):
+        """Test that nothing is added once demo mode is off."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+            run_cli(["demo", "off"], tmpdir)
+            result = run_cli(["status"], tmpdir)
+            assert result.returncode == 0, result.stderr
+            assert "DEMO MODE" not in result.stdout
+
+            data = json.loads(run_cli(["status", "--json"], tmpdir).stdout)
+            assert "demo_mode" not in data
+
 
 class TestSyncCommand:
     """Tests for tl sync command."""
@@ -130,6 +241,27 @@ class TestSyncCommand:
             data = json.loads(result.stdout)
             assert "results" in data
 
+    def test_sync_json_stream_emits_one_event_per_line(self):
+        """Test that sync --json-stream reports each step, ending with the full result."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+            result = run_cli(["sync", "--json-stream"], tmpdir)
+            assert result.returncode == 0, result.stderr
+
+            events = [json.loads(line) for line in result.stdout.splitlines()]
+            assert [e["event"] for e in events] == [
+                "integration_start",
+                "accounts_fetched",
+                "transactions_inserted",
+                "result",
+            ]
+            assert events[0]["name"] == "demo"
+            assert events[1]["count"] > 0
+            assert events[3]["demo_mode"] is True
+            assert events[3]["results"][0]["integration"] == "demo"
+            stats = events[3]["results"][0]["transaction_stats"]
+            assert (events[2]["new"], events[2]["skipped"]) == (stats["new"], stats["skipped"])
+
     def test_sync_dry_run(self):
         """Test that sync --dry-run shows preview without changing data."""
         with tempfile.TemporaryDirectory() as tmpdir:
@@ -180,6 +312,36 @@ class TestQueryCommand:
             data = json.loads(result.stdout)
             assert len(data["rows"]) > 0
 
+    def test_query_json_columns_are_nested(self):
+        """Test that JSON-typed columns come back as nested JSON, VARCHAR untouched."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+            sql = (
+                "SELECT to_json(['groceries']) AS tags, external_ids, "
+                "'{\"not\":\"json-typed\"}' AS note FROM transactions LIMIT 1"
+            )
+            result = run_cli(["query", sql, "--json"], tmpdir)
+            assert result.returncode == 0
+
+            row = json.loads(result.stdout)["rows"][0]
+            assert row[0] == ["groceries"]
+            assert isinstance(row[1], dict)
+            assert "fingerprint" in row[1]
+            assert row[2] == '{"not":"json-typed"}'
+
+    def test_query_json_columns_compact_in_csv(self):
+        """Test that JSON-typed columns render as single-line JSON in CSV."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+            result = run_cli(
+                ["query", "SELECT external_ids FROM transactions LIMIT 1", "--format", "csv"],
+                tmpdir,
+            )
+            assert result.returncode == 0
+            lines = result.stdout.strip().splitlines()
+            assert len(lines) == 2
+            assert '""fingerprint"":' in lines[1]
+
     def test_query_rejects_write_operations(self):
         """Test that DELETE/UPDATE/INSERT are rejected."""
         with tempfile.TemporaryDirectory() as tmpdir:
@@ -187,6 +349,61 @@ class TestQueryCommand:
             result = run_cli(["query", "DELETE FROM transactions"], tmpdir)
             assert result.returncode != 0
 
+    def test_query_timeout_cancels_with_exit_code_5(self):
+        """Test that --timeout interrupts a runaway query."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+            result = run_cli(
+                [
+                    "query",
+                    "SELECT SUM(a.range * b.range) FROM range(100000000) a, range(100000000) b",
+                    "--timeout",
+                    "1",
+                ],
+                tmpdir,
+            )
+            assert result.returncode == 5
+            assert "cancelled after 1s" in result.stdout
+
+    def test_query_file_over_size_limit_is_refused(self):
+        """Test that --file refuses files over query.max_file_mb."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+            settings_path = Path(tmpdir) / ".treeline" / "settings.json"
+            settings = json.loads(settings_path.read_text())
+            settings["query"] = {"max_file_mb": 0.001}
+            settings_path.write_text(json.dumps(settings))
+            sql_file = Path(tmpdir) / "big.sql"
+            sql_file.write_text("SELECT 1;\n" + "-- padding\n" * 200)
+
+            result = run_cli(["query", "--file", str(sql_file)], tmpdir)
+            assert result.returncode == 1
+            assert "query.max_file_mb" in " ".join(result.stdout.split())
+
+
+    def test_query_runs_saved_snippet(self):
+        """Test that --snippet runs SQL saved in snippets.json, by name."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+            snippets_path = Path(tmpdir) / ".treeline" / "snippets.json"
+            snippets_path.write_text(
+                json.dumps(
+                    {
+                        "snippets": [
+                            {"name": "Count", "sql": "SELECT 1 AS n", "description": "", "builtin": True},
+                            {"name": "count", "sql": "SELECT 2 AS n", "description": "", "builtin": False},
+                        ]
+                    }
+                )
+            )
+
+            result = run_cli(["query", "--snippet", "COUNT", "--json"], tmpdir)
+            assert result.returncode == 0
+            assert json.loads(result.stdout)["rows"] == [[2]]
+
+            result = run_cli(["query", "--snippet", "Missing"], tmpdir)
+            assert result.returncode == 1
+            assert "No snippet named 'Missing'" in result.stdout
 
 class TestTagCommand:
     """Tests for tl tag command."""
@@ -315,6 +532,223 @@ class TestNewCommand:
             assert result.returncode == 0
 
 
+class TestAccountsCommand:
+    """Tests for tl accounts commands."""
+
+    def test_accounts_create_with_opening_balance(self):
+        """Test that 'tl accounts create' creates an account and opening snapshot."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+
+            result = run_cli([
+                "accounts", "create",
+                "--name", "Cash",
+                "--type", "depository",
+                "--opening-balance", "250.00",
+                "--as-of", "2025-01-15",
+                "--json",
+            ], tmpdir)
+            assert result.returncode == 0, f"accounts create failed: {result.stderr}"
+            account_id = json.loads(result.stdout)["account"]["id"]
+
+            result = run_cli([
+                "query",
+                f"SELECT balance, source FROM balance_snapshots WHERE account_id = '{account_id}'",
+                "--json"
+            ], tmpdir)
+            data = json.loads(result.stdout)
+            assert len(data["rows"]) == 1
+            assert data["rows"][0][1] == "manual"
+
+    def test_accounts_create_rejects_bad_input(self):
+        """Test that invalid currency and future dates are rejected."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+
+            result = run_cli(["accounts", "create", "--name", "Cash", "--currency", "dollars"], tmpdir)
+            assert result.returncode == 1
+
+            result = run_cli([
+                "accounts", "create",
+                "--name", "Cash",
+                "--opening-balance", "10",
+                "--as-of", "2999-01-01",
+            ], tmpdir)
+            assert result.returncode == 1
+
+    def test_accounts_create_json_names_the_invalid_field(self):
+        """Test that --json errors say which option was invalid, for the app's form."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+
+            cases = [
+                (["--name", " "], "name"),
+                (["--name", "Cash", "--currency", "EURO"], "currency"),
+                (["--name", "Cash", "--opening-balance", "1,000"], "opening_balance"),
+                (["--name", "Cash", "--opening-balance", "10000000000000000"], "opening_balance"),
+                (["--name", "Cash", "--opening-balance", "1", "--as-of", "06/01/2024"], "as_of"),
+            ]
+            for args, field in cases:
+                result = run_cli(["accounts", "create", *args, "--json"], tmpdir)
+                assert result.returncode == 1, args
+                error = json.loads(result.stdout)
+                assert error["field"] == field, error
+
+            result = run_cli(["query", "SELECT COUNT(*) FROM accounts WHERE name = 'Cash'", "--json"], tmpdir)
+            assert json.loads(result.stdout)["rows"][0][0] == 0
+
+
+class TestTransactionsCommand:
+    """Tests for tl transactions commands."""
+
+    def test_transactions_show_by_prefix(self):
+        """Test that 'tl transactions show' accepts a unique ID prefix."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+
+            result = run_cli(["query", "SELECT transaction_id FROM transactions LIMIT 1", "--json"], tmpdir)
+            transaction_id = json.loads(result.stdout)["rows"][0][0]
+
+            result = run_cli(["transactions", "show", transaction_id[:13], "--json"], tmpdir)
+            assert result.returncode == 0, f"transactions show failed: {result.stderr}"
+            data = json.loads(result.stdout)
+            assert data["transaction"]["id"] == transaction_id
+            assert data["account"] is not None
+            assert "fingerprint_matches" in data
+
+    def test_transactions_show_unknown_id(self):
+        """Test that an unknown ID fails cleanly."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+
+            result = run_cli(["transactions", "show", "ffffffff-ffff-ffff-ffff-ffffffffffff", "--json"], tmpdir)
+            assert result.returncode == 1
+            assert json.loads(result.stdout)["success"] is False
+
+    def test_pending_transaction_can_be_cleared(self):
+        """Test entering a pending transaction, listing it and clearing it."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+
+            result = run_cli(["query", "SELECT account_id FROM accounts LIMIT 1", "--json"], tmpdir)
+            account_id = json.loads(result.stdout)["rows"][0][0]
+
+            result = run_cli(
+                ["new", "transaction", "--account-id", account_id, "--amount", "-40",
+                 "--description", "Farmers market", "--status", "pending"],
+                tmpdir,
+            )
+            assert result.returncode == 0, f"new transaction failed: {result.stderr}"
+
+            result = run_cli(["transactions", "list", "--status", "pending", "--json"], tmpdir)
+            assert result.returncode == 0, f"transactions list failed: {result.stderr}"
+            pending = json.loads(result.stdout)["transactions"]
+            market = [tx for tx in pending if tx["description"] == "Farmers market"]
+            assert len(market) == 1
+
+            result = run_cli(["transactions", "clear", market[0]["id"], "--json"], tmpdir)
+            assert result.returncode == 0, f"transactions clear failed: {result.stderr}"
+
+            result = run_cli(["transactions", "list", "--status", "pending", "--json"], tmpdir)
+            pending = json.loads(result.stdout)["transactions"]
+            assert all(tx["description"] != "Farmers market" for tx in pending)
+
+    def test_attach_show_and_detach(self):
+        """Test attaching a receipt, seeing it in show, and detaching it."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+
+            result = run_cli(["query", "SELECT transaction_id FROM transactions LIMIT 1", "--json"], tmpdir)
+            transaction_id = json.loads(result.stdout)["rows"][0][0]
+            receipt = Path(tmpdir) / "receipt.pdf"
+            receipt.write_bytes(b"%PDF-1.4 receipt")
+
+            result = run_cli(["transactions", "attach", transaction_id, str(receipt), "--json"], tmpdir)
+            assert result.returncode == 0, f"transactions attach failed: {result.stderr}"
+            attachment = json.loads(result.stdout)["attachment"]
+            assert Path(attachment["path"]).read_bytes() == receipt.read_bytes()
+            assert "attachments-demo" in attachment["path"]
+
+            result = run_cli(["transactions", "show", transaction_id, "--json"], tmpdir)
+            assert [a["id"] for a in json.loads(result.stdout)["attachments"]] == [attachment["id"]]
+
+            result = run_cli(["transactions", "detach", attachment["id"], "--json"], tmpdir)
+            assert result.returncode == 0, f"transactions detach failed: {result.stderr}"
+            assert not Path(attachment["path"]).exists()
+            assert Path(json.loads(result.stdout)["trash_path"]).exists()
+
+
+class TestIntegrationsCommand:
+    """Tests for tl integrations commands."""
+
+    def test_integrations_test_demo(self):
+        """Test that the demo integration checks out without syncing."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+
+            result = run_cli(["integrations", "test", "demo", "--json"], tmpdir)
+            assert result.returncode == 0, f"integrations test failed: {result.stderr}"
+            data = json.loads(result.stdout)
+            assert data["success"] is True
+            assert data["account_count"] > 0
+
+    def test_integrations_test_not_configured(self):
+        """Test that an unconfigured integration fails with a structured kind."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+
+            result = run_cli(["integrations", "test", "simplefin", "--json"], tmpdir)
+            assert result.returncode == 1
+            assert json.loads(result.stdout)["kind"] == "not_configured"
+
+
+class TestReportCommand:
+    """Tests for tl report commands."""
+
+    def test_report_cashflow_json(self):
+        """Test that 'tl report cashflow' returns one entry per month plus totals."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+
+            result = run_cli(["report", "cashflow", "--period", "last-quarter", "--json"], tmpdir)
+            assert result.returncode == 0, f"report cashflow failed: {result.stderr}"
+            data = json.loads(result.stdout)
+            assert len(data["months"]) == 3
+            assert "net_savings" in data["totals"]
+
+    def test_report_cashflow_period_conflicts_with_since(self):
+        """Test that --period and --since cannot be combined."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+
+            result = run_cli(["report", "cashflow", "--period", "ytd", "--since", "2024-01-01"], tmpdir)
+            assert result.returncode == 1
+
+
+class TestBalancesCommand:
+    """Tests for tl balances commands."""
+
+    def test_balances_as_of_all_accounts_json(self):
+        """Test that 'tl balances as-of --all-accounts' returns one entry per account."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+
+            result = run_cli(["balances", "as-of", "--all-accounts", "--date", "2000-01-01", "--json"], tmpdir)
+            assert result.returncode == 0, f"balances as-of failed: {result.stderr}"
+            data = json.loads(result.stdout)
+            assert len(data["balances"]) > 0
+            assert all(entry["status"] == "unknown" for entry in data["balances"])
+            assert all(entry["balance"] is None for entry in data["balances"])
+
+    def test_balances_as_of_requires_account_selection(self):
+        """Test that one of --account-id or --all-accounts is required."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+
+            result = run_cli(["balances", "as-of", "--date", "2024-01-01"], tmpdir)
+            assert result.returncode == 1
+
+
 class TestBackfillCommand:
     """Tests for tl backfill command."""
 
@@ -337,6 +771,32 @@ class TestBackfillCommand:
             assert result.returncode == 0
 
 
+class TestAccountNamesInOutput:
+    """Tests that default (non-JSON) output names accounts instead of printing UUIDs."""
+
+    def test_commands_print_account_names(self):
+        """Test that commands which only have an account_id still print a name."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+
+            result = run_cli(["query", "SELECT account_id FROM accounts LIMIT 1", "--json"], tmpdir)
+            account_id = json.loads(result.stdout)["rows"][0][0]
+
+            commands = [
+                ["transactions", "list", "--limit", "20"],
+                ["backfill", "balances", "--days", "7", "--dry-run", "--verbose"],
+                ["report", "digest", "--period", "last-month"],
+                ["new", "balance", "--account-id", account_id, "--balance", "1234.56"],
+                ["sync"],
+                ["dedupe", "scan"],
+                ["dedupe", "review", "--status", "all"],
+            ]
+            for args in commands:
+                result = run_cli(args, tmpdir)
+                assert result.returncode == 0, f"{' '.join(args)} failed: {result.stderr}"
+                assert not FULL_UUID.search(result.stdout), f"{' '.join(args)} printed a UUID"
+
+
 class TestImportCommand:
     """Tests for tl import command."""
 
@@ -359,6 +819,26 @@ class TestImportCommand:
             assert result.returncode == 0
             assert "Test Transaction" in result.stdout or "preview" in result.stdout.lower()
 
+    def test_import_doctor_json(self):
+        """Test that import doctor explains detection without importing."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+
+            csv_path = Path(tmpdir) / "test.csv"
+            csv_path.write_text("Date,Description,Amount\n2025-01-01,DoctorOnly123,-50.00\n")
+
+            result = run_cli(["import", "doctor", str(csv_path), "--json"], tmpdir)
+            assert result.returncode == 0, f"import doctor failed: {result.stderr}"
+            detection = json.loads(result.stdout)["detection"]
+            assert detection["column_mapping"]["amount"] == "Amount"
+            assert detection["rows"][0]["fields"]["amount"]["parsed"] == "-50.00"
+
+            result = run_cli(
+                ["query", "SELECT COUNT(*) FROM transactions WHERE description = 'DoctorOnly123'", "--json"],
+                tmpdir,
+            )
+            assert json.loads(result.stdout)["rows"][0][0] == 0
+
     def test_import_actually_imports(self):
         """Test that import without --preview actually imports transactions."""
         with tempfile.TemporaryDirectory() as tmpdir:
@@ -410,6 +890,34 @@ class TestImportCommand:
             assert result.returncode != 0
             assert "account-id" in result.stdout.lower() or "account-id" in result.stderr.lower()
 
+    def test_reimporting_identical_file_requires_force(self):
+        """Test that the same file is only re-processed with --force."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+
+            csv_path = Path(tmpdir) / "test.csv"
+            csv_path.write_text("Date,Description,Amount\n2025-01-01,ReimportTest,-12.00\n")
+
+            result = run_cli(["query", "SELECT account_id FROM accounts LIMIT 1", "--json"], tmpdir)
+            account_id = json.loads(result.stdout)["rows"][0][0]
+
+            result = run_cli(["import", str(csv_path), "--account-id", account_id, "--json"], tmpdir)
+            assert result.returncode == 0
+            import_id = json.loads(result.stdout)["import_id"]
+
+            result = run_cli(["import", str(csv_path), "--account-id", account_id, "--json"], tmpdir)
+            assert result.returncode == 1
+            data = json.loads(result.stdout)
+            assert data["kind"] == "already_imported"
+            assert data["import"]["id"] == import_id
+
+            result = run_cli(["import", str(csv_path), "--account-id", account_id, "--force"], tmpdir)
+            assert result.returncode == 0
+
+            result = run_cli(["imports", "list", "--json"], tmpdir)
+            assert result.returncode == 0
+            assert len(json.loads(result.stdout)["imports"]) == 2
+
 
 class TestRemoveCommand:
     """Tests for tl remove command."""
@@ -516,6 +1024,22 @@ class TestBackupCommand:
             result = run_cli(["backup", "list"], tmpdir)
             assert "No backups found" in result.stdout
 
+    def test_backup_clear_without_terminal_needs_yes(self):
+        """Test that a confirmation fails without a terminal and --yes answers it."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            run_cli(["demo", "on"], tmpdir)
+            run_cli(["backup", "create"], tmpdir)
+
+            # subprocess stdin isn't a terminal, so the prompt can't be shown
+            result = run_cli(["backup", "clear"], tmpdir)
+            assert result.returncode == 1
+            assert "Confirmation required" in result.stdout
+            assert "No backups found" not in run_cli(["backup", "list"], tmpdir).stdout
+
+            result = run_cli(["--yes", "backup", "clear"], tmpdir)
+            assert result.returncode == 0
+            assert "Deleted" in result.stdout
+
     def test_backup_retention(self):
         """Test that backup respects --max-backups retention."""
         with tempfile.TemporaryDirectory() as tmpdir:
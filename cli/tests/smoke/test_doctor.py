@@ -90,6 +90,7 @@ class TestDoctorCommand:
             assert "checks" in data
             assert "orphaned_transactions" in data["checks"]
             assert "orphaned_snapshots" in data["checks"]
+            assert "dangling_attachments" in data["checks"]
             assert "duplicate_fingerprints" in data["checks"]
             assert "date_sanity" in data["checks"]
             assert "untagged_transactions" in data["checks"]
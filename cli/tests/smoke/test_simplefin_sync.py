@@ -0,0 +1,199 @@
+"""End-to-end smoke tests for SimpleFIN setup and sync against a mock bridge.
+
+The CLI runs in a subprocess exactly as users run it; only the bridge is fake
+(see mock_simplefin.py).
+"""
+
+import json
+import os
+import subprocess
+import tempfile
+from pathlib import Path
+
+from mock_simplefin import PASSWORD, USERNAME, MockSimpleFIN
+
+# Fixture transactions inside the initial 90-day window (TRN-c5 is older)
+INITIAL_TRANSACTIONS = 7
+
+
+def run_cli(
+    args: list[str], treeline_dir: str, allow_localhost: bool = True
+) -> subprocess.CompletedProcess:
+    """Run treeline CLI command against the mock bridge.
+
+    Args:
+        args: CLI arguments (e.g., ["sync", "--json"])
+        treeline_dir: Path to treeline data directory
+        allow_localhost: Let SimpleFIN use the mock's plain-HTTP localhost URLs
+
+    Returns:
+        CompletedProcess with stdout, stderr, returncode
+    """
+    env = os.environ.copy()
+    env["TREELINE_DIR"] = str(Path(treeline_dir) / ".treeline")
+    env["NO_PROXY"] = "127.0.0.1,localhost"
+    if allow_localhost:
+        env["TREELINE_SIMPLEFIN_ALLOW_LOCALHOST"] = "1"
+    else:
+        env.pop("TREELINE_SIMPLEFIN_ALLOW_LOCALHOST", None)
+    env.pop("TREELINE_DEMO_MODE", None)
+    env.pop("TREELINE_OFFLINE", None)
+
+    cmd = ["uv", "run", "treeline"] + args
+    return subprocess.run(cmd, capture_output=True, text=True, env=env)
+
+
+def query_rows(sql: str, treeline_dir: str) -> list:
+    result = run_cli(["query", sql, "--json"], treeline_dir)
+    assert result.returncode == 0, f"query failed: {result.stdout}{result.stderr}"
+    return json.loads(result.stdout)["rows"]
+
+
+def setup_simplefin(bridge: MockSimpleFIN, treeline_dir: str) -> None:
+    result = run_cli(["setup", "simplefin", "--token", bridge.setup_token()], treeline_dir)
+    assert result.returncode == 0, f"setup failed: {result.stdout}{result.stderr}"
+    assert "setup successfully" in result.stdout
+
+
+def sync(treeline_dir: str) -> dict:
+    result = run_cli(["sync", "--json"], treeline_dir)
+    assert result.returncode == 0, f"sync failed: {result.stdout}{result.stderr}"
+    (entry,) = json.loads(result.stdout)["results"]
+    return entry
+
+
+class TestSimpleFINSync:
+    """Tests for tl setup simplefin and tl sync against the mock bridge."""
+
+    def test_initial_then_incremental_sync(self):
+        """Test that an overlapping incremental sync adds only new transactions."""
+        with tempfile.TemporaryDirectory() as tmpdir, MockSimpleFIN() as bridge:
+            setup_simplefin(bridge, tmpdir)
+            assert bridge.requests[0]["path"] == "/claim/mock-claim"
+
+            initial = sync(tmpdir)
+            assert "error" not in initial, initial
+            assert initial["sync_type"] == "initial"
+            assert initial["accounts_synced"] == 2
+            assert initial["transaction_stats"]["new"] == INITIAL_TRANSACTIONS
+
+            assert query_rows("SELECT COUNT(*) FROM sys_accounts", tmpdir) == [[2]]
+            rows = query_rows("SELECT external_ids FROM sys_transactions", tmpdir)
+            assert len(rows) == INITIAL_TRANSACTIONS
+            external_ids = [row[0] for row in rows]
+            assert all(ids.get("fingerprint") for ids in external_ids)
+            assert len({ids["fingerprint"] for ids in external_ids}) == INITIAL_TRANSACTIONS
+            assert "TRN-c5" not in {ids["simplefin"] for ids in external_ids}
+
+            bridge.add_transaction(
+                "ACT-checking",
+                {"id": "TRN-c6", "days_ago": 0, "amount": "-9.99", "description": "Spotify"},
+            )
+            incremental = sync(tmpdir)
+            assert incremental["sync_type"] == "incremental"
+            assert incremental["transaction_stats"]["new"] == 1
+            # The 7-day overlap re-fetches recent transactions, which are skipped
+            assert incremental["transaction_stats"]["skipped"] > 0
+            assert "start-date" in bridge.requests[-1]["query"]
+
+            rows = query_rows(
+                """
+                SELECT COUNT(*), COUNT(DISTINCT json_extract_string(external_ids, '$.simplefin'))
+                FROM sys_transactions
+                """,
+                tmpdir,
+            )
+            assert rows == [[INITIAL_TRANSACTIONS + 1, INITIAL_TRANSACTIONS + 1]]
+
+    def test_revoked_access_reports_friendly_error(self):
+        """Test that a 403 from the bridge produces the reset-credentials message."""
+        with tempfile.TemporaryDirectory() as tmpdir, MockSimpleFIN() as bridge:
+            setup_simplefin(bridge, tmpdir)
+            bridge.fail_with(403)
+
+            # Per-integration failures are reported in the results, not the exit code
+            entry = sync(tmpdir)
+            assert "authentication failed" in entry["error"]
+            assert "reset your SimpleFIN credentials" in entry["error"]
+            assert query_rows("SELECT COUNT(*) FROM sys_transactions", tmpdir) == [[0]]
+
+            result = run_cli(["integrations", "test", "simplefin", "--json"], tmpdir)
+            assert result.returncode == 1
+            check = json.loads(result.stdout)
+            assert check["kind"] == "unauthorized"
+            assert check["http_status"] == 403
+
+    def test_other_bridge_errors_are_reported(self):
+        """Test that 402, 429 and 500 responses surface as sync errors."""
+        with tempfile.TemporaryDirectory() as tmpdir, MockSimpleFIN() as bridge:
+            setup_simplefin(bridge, tmpdir)
+
+            bridge.fail_with(402)
+            assert "payment required" in sync(tmpdir)["error"]
+
+            for status in (429, 500):
+                bridge.fail_with(status)
+                assert f"HTTP {status}" in sync(tmpdir)["error"]
+
+    def test_sync_rejects_localhost_without_escape_hatch(self):
+        """Test that a plain-HTTP localhost bridge is refused by default."""
+        with tempfile.TemporaryDirectory() as tmpdir, MockSimpleFIN() as bridge:
+            setup_simplefin(bridge, tmpdir)
+
+            result = run_cli(["sync", "--json"], tmpdir, allow_localhost=False)
+
+            (entry,) = json.loads(result.stdout)["results"]
+            assert "HTTPS" in entry["error"]
+            assert all(r["method"] == "POST" for r in bridge.requests)
+
+    def test_about_does_not_leak_credentials(self):
+        """Test that tl about reports the database but none of the integration's secrets."""
+        with tempfile.TemporaryDirectory() as tmpdir, MockSimpleFIN() as bridge:
+            setup_simplefin(bridge, tmpdir)
+
+            result = run_cli(["about", "--json"], tmpdir)
+            assert result.returncode == 0, f"about failed: {result.stdout}{result.stderr}"
+            info = json.loads(result.stdout)
+            assert info["database"]["migrations_applied"] > 0
+            assert info["database"]["latest_migration"].endswith(".sql")
+
+            text = run_cli(["about"], tmpdir)
+            for output in (result.stdout, text.stdout, text.stderr):
+                assert PASSWORD not in output
+                assert USERNAME not in output
+                assert bridge.access_url not in output
+
+    def test_backfill_resumes_after_a_failed_window(self):
+        """Test that a backfill stopped by bridge errors resumes after its last completed month."""
+        with tempfile.TemporaryDirectory() as tmpdir, MockSimpleFIN() as bridge:
+            setup_simplefin(bridge, tmpdir)
+            assert sync(tmpdir)["transaction_stats"]["new"] == INITIAL_TRANSACTIONS
+
+            # Three months succeed, then the fourth fails on every attempt
+            bridge.fail_after(3, 500)
+            args = ["backfill", "transactions", "--months", "9", "--delay", "0", "--json-stream"]
+            result = run_cli(args, tmpdir)
+            assert result.returncode == 1, result.stdout
+            events = [json.loads(line) for line in result.stdout.splitlines()]
+            assert [e["window"] for e in events if e["event"] == "window_done"] == [
+                e["window"] for e in events if e["event"] == "window_start"
+            ][:3]
+            assert sum(e["event"] == "window_retry" for e in events) == 2
+            error = events[-1]
+            assert error["kind"] == "backfill_interrupted"
+            assert "--resume" in error["error"]
+            failed_requests = len(bridge.requests)
+
+            bridge.fail_with(None)
+            result = run_cli(args + ["--resume"], tmpdir)
+            assert result.returncode == 0, result.stdout
+            summary = json.loads(result.stdout.splitlines()[-1])
+            assert summary["resumed_after"] == error["last_completed_window"]
+            assert summary["windows_run"] == summary["windows"] - 3
+            assert len(bridge.requests) - failed_requests == summary["windows_run"]
+
+            # Only the 200-day-old transaction was outside the initial sync
+            rows = query_rows("SELECT external_ids FROM sys_transactions", tmpdir)
+            external_ids = [row[0] for row in rows]
+            assert len(external_ids) == INITIAL_TRANSACTIONS + 1
+            assert "TRN-c5" in {ids["simplefin"] for ids in external_ids}
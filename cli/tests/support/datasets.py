@@ -0,0 +1,149 @@
+"""Deterministic generated datasets and database seeding for tests and benchmarks.
+
+Everything here is a function of its arguments: IDs are uuid5s and amounts,
+merchants and dates come from a Random seeded with the account and seed, so
+the same call gives the same rows on every machine. The benchmarks in
+cli/benchmarks use the large sizes; tests use small ones.
+"""
+
+import csv
+import random
+from datetime import date, datetime, time, timedelta, timezone
+from decimal import Decimal
+from pathlib import Path
+from typing import Iterable, List, Sequence
+from uuid import NAMESPACE_URL, UUID, uuid5
+
+from treeline.domain import Account, BalanceSnapshot, Transaction
+from treeline.infra.duckdb import DuckDBRepository
+
+DATASET_NAMESPACE = uuid5(NAMESPACE_URL, "https://treeline.money/test-datasets")
+DATASET_START = date(2024, 1, 1)
+# Fixed so created_at/updated_at don't vary between runs
+DATASET_CREATED_AT = datetime(2024, 1, 1, tzinfo=timezone.utc)
+
+# (description, typical amount); amounts vary by up to 50% around these
+MERCHANTS = [
+    ("Whole Foods Market", Decimal("-92.40")),
+    ("Trader Joe's", Decimal("-61.15")),
+    ("Blue Bottle Coffee", Decimal("-6.25")),
+    ("Shell Oil 5724", Decimal("-48.90")),
+    ("Amazon.com", Decimal("-37.99")),
+    ("Uber Trip", Decimal("-18.60")),
+    ("Chipotle Mexican Grill", Decimal("-13.45")),
+    ("Target", Decimal("-54.20")),
+    ("CVS Pharmacy", Decimal("-21.80")),
+    ("Netflix", Decimal("-15.99")),
+    ("PG&E Web Online", Decimal("-142.87")),
+    ("Employer Direct Deposit - Payroll", Decimal("4250.00")),
+    ("Venmo Cashout", Decimal("75.00")),
+    ("Interest Payment", Decimal("4.12")),
+]
+
+CSV_COLUMN_MAPPING = {"date": "Date", "description": "Description", "amount": "Amount"}
+
+
+def dataset_id(*parts: object) -> UUID:
+    """A UUID that is the same for the same parts on every run."""
+    return uuid5(DATASET_NAMESPACE, "/".join(str(part) for part in parts))
+
+
+def make_accounts(count: int, provider: str | None = None) -> List[Account]:
+    """Checking accounts named "Account 1".."Account N".
+
+    With a provider, each has external_ids {provider: "<provider>-acct-N"}.
+    """
+    accounts = []
+    for index in range(1, count + 1):
+        external_ids = {provider: f"{provider}-acct-{index}"} if provider else {}
+        accounts.append(
+            Account(
+                id=dataset_id("account", index),
+                name=f"Account {index}",
+                account_type="depository",
+                external_ids=external_ids,
+                created_at=DATASET_CREATED_AT,
+                updated_at=DATASET_CREATED_AT,
+            )
+        )
+    return accounts
+
+
+def make_transactions(
+    account: Account,
+    count: int,
+    seed: int = 0,
+    days: int = 365,
+    provider: str | None = None,
+) -> List[Transaction]:
+    """count transactions spread over days days from DATASET_START, oldest first.
+
+    With a provider, each has a provider external ID
+    ("<provider>-tx-<seed>-<n>"), as synced transactions do.
+    """
+    rng = random.Random(f"{account.id}/{seed}")
+    offsets = sorted(rng.randrange(days) for _ in range(count))
+    transactions = []
+    for index, offset in enumerate(offsets):
+        description, typical = rng.choice(MERCHANTS)
+        amount = (typical * Decimal(rng.randint(50, 150)) / 100).quantize(Decimal("0.01"))
+        day = DATASET_START + timedelta(days=offset)
+        external_ids = {provider: f"{provider}-tx-{seed}-{index}"} if provider else {}
+        transactions.append(
+            Transaction(
+                id=dataset_id("transaction", account.id, seed, index),
+                account_id=account.id,
+                amount=amount,
+                description=description,
+                transaction_date=day,
+                posted_date=day,
+                external_ids=external_ids,
+                created_at=DATASET_CREATED_AT,
+                updated_at=DATASET_CREATED_AT,
+            )
+        )
+    return transactions
+
+
+def make_balance_snapshot(account: Account, balance: Decimal, as_of: date) -> BalanceSnapshot:
+    """An end-of-day synced balance for account."""
+    return BalanceSnapshot(
+        id=dataset_id("balance", account.id, as_of),
+        account_id=account.id,
+        balance=balance,
+        snapshot_time=datetime.combine(as_of, time(23, 59, 59)),
+        created_at=DATASET_CREATED_AT,
+        updated_at=DATASET_CREATED_AT,
+        source="sync",
+    )
+
+
+def write_csv(path: Path, transactions: Iterable[Transaction]) -> Path:
+    """Write transactions as a bank export read with CSV_COLUMN_MAPPING."""
+    with open(path, "w", newline="") as f:
+        writer = csv.writer(f)
+        writer.writerow(["Date", "Description", "Amount"])
+        for tx in transactions:
+            writer.writerow([tx.transaction_date.isoformat(), tx.description, str(tx.amount)])
+    return path
+
+
+async def seed_database(
+    path: Path,
+    accounts: Sequence[Account],
+    transactions: Sequence[Transaction] = (),
+    balances: Sequence[BalanceSnapshot] = (),
+) -> DuckDBRepository:
+    """A migrated database at path holding the given accounts, transactions and balances."""
+    repository = DuckDBRepository(str(path))
+    await repository.ensure_schema_upgraded()
+    for account in accounts:
+        result = await repository.add_account(account)
+        assert result.success, result.error
+    if transactions:
+        result = await repository.bulk_upsert_transactions(list(transactions))
+        assert result.success, result.error
+    if balances:
+        result = await repository.bulk_add_balances(list(balances))
+        assert result.success, result.error
+    return repository
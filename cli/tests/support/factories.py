@@ -0,0 +1,61 @@
+"""One-off domain objects for tests, with defaults for every required field."""
+
+from datetime import date, datetime, timezone
+from decimal import Decimal
+from typing import Any
+from uuid import UUID, uuid4
+
+from treeline.domain import Account, BalanceSnapshot, Transaction
+
+
+def make_account(name: str = "Checking", **fields: Any) -> Account:
+    """An account; fields override the defaults."""
+    now = datetime.now(timezone.utc)
+    return Account(
+        **{"id": uuid4(), "name": name, "created_at": now, "updated_at": now, **fields}
+    )
+
+
+def make_transaction(
+    account_id: UUID,
+    amount: str = "-12.50",
+    day: date = date(2024, 7, 15),
+    **fields: Any,
+) -> Transaction:
+    """A transaction dated day, described "tx <amount>"; fields override the defaults."""
+    now = datetime.now(timezone.utc)
+    return Transaction(
+        **{
+            "id": uuid4(),
+            "account_id": account_id,
+            "amount": Decimal(amount),
+            "description": f"tx {amount}",
+            "transaction_date": day,
+            "posted_date": day,
+            "created_at": now,
+            "updated_at": now,
+            **fields,
+        }
+    )
+
+
+def make_snapshot(
+    account_id: UUID,
+    balance: str = "100.00",
+    day: date = date(2024, 7, 15),
+    **fields: Any,
+) -> BalanceSnapshot:
+    """A manual balance snapshot taken at midnight of day; fields override the defaults."""
+    now = datetime.now(timezone.utc)
+    return BalanceSnapshot(
+        **{
+            "id": uuid4(),
+            "account_id": account_id,
+            "balance": Decimal(balance),
+            "snapshot_time": datetime.combine(day, datetime.min.time()),
+            "created_at": now,
+            "updated_at": now,
+            "source": "manual",
+            **fields,
+        }
+    )
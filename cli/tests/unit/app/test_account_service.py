@@ -0,0 +1,339 @@
+"""Unit tests for resolving accounts by name, nickname, or ID."""
+
+from datetime import date
+from decimal import Decimal
+from uuid import uuid4
+
+import pytest
+
+from treeline.app.account_service import (
+    AccountDirectory,
+    AccountService,
+    institution_sort_key,
+    match_account,
+    parse_account_type,
+)
+from treeline.domain import Account
+
+from tests.support.factories import make_account, make_transaction
+
+CHECKING = make_account("Chase Checking", nickname="Bills")
+SAPPHIRE = make_account("Chase Sapphire Reserve®")
+SAVINGS = make_account("Ally Savings", nickname="Emergency Fund")
+JOINT = make_account("Joint (Joe's & Ann's)")
+STRASSE = make_account("Sparkasse Straße")
+ACCOUNTS = [CHECKING, SAPPHIRE, SAVINGS, JOINT, STRASSE]
+
+
+@pytest.mark.parametrize(
+    "identifier, expected",
+    [
+        ("Chase Checking", CHECKING),
+        ("chase checking", CHECKING),
+        ("  CHASE   checking ", CHECKING),
+        ("bills", CHECKING),
+        ("emergency", SAVINGS),
+        ("ally", SAVINGS),
+        ("chase s", SAPPHIRE),
+    ],
+)
+def test_exact_and_unique_prefix_matches(identifier, expected):
+    """Test case-insensitive exact and prefix matching on names and nicknames."""
+    result = match_account(identifier, ACCOUNTS)
+
+    assert result.success, result.error
+    assert result.data.id == expected.id
+
+
+def test_ambiguous_prefix_lists_candidates_with_ids():
+    """Test that a prefix matching several accounts fails with each candidate."""
+    result = match_account("chase", ACCOUNTS)
+
+    assert not result.success
+    assert "matches 2 accounts" in result.error
+    assert f"Chase Checking (Bills) - {CHECKING.id}" in result.error
+    assert str(SAPPHIRE.id) in result.error
+    assert str(SAVINGS.id) not in result.error
+
+
+def test_exact_match_wins_over_longer_prefix_matches():
+    """Test that an exact name wins even when it is a prefix of other names."""
+    card = make_account("Amex")
+    business = make_account("Amex Business")
+
+    result = match_account("AMEX", [business, card])
+
+    assert result.data.id == card.id
+
+
+def test_duplicate_exact_matches_are_ambiguous():
+    """Test that two accounts with the same name are never guessed between."""
+    first = make_account("Checking")
+    second = make_account("Old bank", nickname="checking")
+
+    result = match_account("Checking", [first, second])
+
+    assert not result.success
+    assert str(first.id) in result.error
+    assert str(second.id) in result.error
+
+
+def test_punctuation_must_match():
+    """Test that punctuation is compared literally, not stripped or treated as a pattern."""
+    assert match_account("joint (joe's", ACCOUNTS).data.id == JOINT.id
+    assert match_account("Chase Sapphire Reserve®", ACCOUNTS).data.id == SAPPHIRE.id
+    assert not match_account("joint joes", ACCOUNTS).success
+    assert not match_account("chase.*", ACCOUNTS).success
+
+
+def test_unicode_case_folding():
+    """Test full Unicode case folding (ß matches SS) and compatibility normalization."""
+    assert match_account("SPARKASSE STRASSE", ACCOUNTS).data.id == STRASSE.id
+    # Fullwidth letters normalize to their ASCII equivalents
+    assert match_account("ＡＬＬＹ", ACCOUNTS).data.id == SAVINGS.id
+
+
+@pytest.mark.parametrize("identifier", ["", "   "])
+def test_empty_identifier_fails(identifier):
+    """Test that a blank identifier never matches every account."""
+    assert not match_account(identifier, ACCOUNTS).success
+
+
+def test_no_match_fails():
+    """Test that an unknown name fails with the identifier in the message."""
+    result = match_account("Wells Fargo", ACCOUNTS)
+
+    assert not result.success
+    assert "Wells Fargo" in result.error
+
+
+def test_directory_prefers_nickname_and_shortens_unknown_ids():
+    """Test that the directory names known accounts and shortens unknown IDs."""
+    directory = AccountDirectory(ACCOUNTS)
+    missing = uuid4()
+
+    assert directory.name_for(CHECKING.id) == "Bills"
+    assert directory.name_for(str(SAPPHIRE.id)) == "Chase Sapphire Reserve®"
+    assert directory.name_for(missing) == f"{str(missing)[:8]}..."
+    assert directory.name_for("not-a-uuid") == "not-a-uuid"
+
+
+def test_institution_sort_groups_by_institution_then_name():
+    """Test that accounts sort by institution, then name, with no institution last."""
+    def account(name: str, institution: str | None) -> Account:
+        return make_account(name, institution_name=institution)
+
+    cash = account("Cash", None)
+    sapphire = account("Sapphire", "Chase")
+    checking = account("checking", "chase")
+    savings = account("Savings", "Ally")
+
+    ordered = sorted([cash, sapphire, checking, savings], key=institution_sort_key)
+
+    assert ordered == [savings, checking, sapphire, cash]
+
+
+@pytest.mark.asyncio
+async def test_resolve_account_by_name_and_uuid(repository):
+    """Test that resolve_account matches names and short-circuits UUIDs to the ID path."""
+    service = AccountService(repository)
+    created = await service.create_account("Chase Checking", "checking", nickname="Bills")
+    account_id = created.data.id
+
+    assert (await service.resolve_account("bills")).data.id == account_id
+    assert (await service.resolve_account(str(account_id))).data.id == account_id
+    assert (await service.resolve_account(str(account_id).upper())).data.id == account_id
+
+
+@pytest.mark.asyncio
+async def test_resolve_unknown_uuid_does_not_fall_back_to_names(repository):
+    """Test that a UUID-shaped identifier is only ever looked up by ID."""
+    service = AccountService(repository)
+    missing_id = uuid4()
+    await service.create_account(str(missing_id), "checking")
+
+    result = await service.resolve_account(str(missing_id))
+
+    assert not result.success
+    assert f"Account not found: {missing_id}" == result.error
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize("opening_balance", ["nan", "inf", "-Infinity"])
+async def test_non_finite_opening_balance_is_rejected(repository, opening_balance):
+    """Test that NaN and infinite opening balances fail before anything is written."""
+    service = AccountService(repository)
+
+    result = await service.create_account(
+        "Loan to Sam", None, opening_balance=Decimal(opening_balance)
+    )
+
+    assert not result.success
+    assert "Opening balance must be a number" in result.error
+    assert (await repository.get_accounts()).data == []
+    assert (await repository.get_balance_snapshots()).data == []
+
+
+@pytest.mark.asyncio
+async def test_opening_balance_too_large_is_rejected_before_writing(repository):
+    """Test that an opening balance beyond the money columns fails with nothing written."""
+    service = AccountService(repository)
+
+    result = await service.create_account(
+        "Loan to Sam", None, opening_balance=Decimal("12345678901234567")
+    )
+
+    assert not result.success
+    assert "Opening balance is too large" in result.error
+    assert (await repository.get_accounts()).data == []
+
+
+@pytest.mark.asyncio
+async def test_account_and_opening_balance_are_saved_together(repository, monkeypatch):
+    """Test that a failed opening balance write leaves no half-created account."""
+    service = AccountService(repository)
+    monkeypatch.setattr(repository, "_UPSERT_BALANCE_SQL", "INSERT INTO missing_table VALUES (?)")
+
+    result = await service.create_account("Loan to Sam", None, opening_balance=Decimal("500"))
+
+    assert not result.success
+    assert (await repository.get_accounts()).data == []
+
+    monkeypatch.undo()
+    created = await service.create_account(
+        "Loan to Sam", None, opening_balance=Decimal("500"), as_of=date(2025, 1, 1)
+    )
+    snapshots = (await repository.get_balance_snapshots(account_id=created.data.id)).data
+    assert [(s.balance, s.snapshot_time.date(), s.source) for s in snapshots] == [
+        (Decimal("500"), date(2025, 1, 1), "manual")
+    ]
+
+
+def test_parse_account_type_normalizes_known_types():
+    """Test that account types are case-insensitive and unknown ones are rejected."""
+    assert parse_account_type(" Credit ").data == "credit"
+    result = parse_account_type("depository")
+    assert not result.success
+    assert "checking, savings, credit, investment, loan, other" in result.error
+
+
+@pytest.mark.asyncio
+async def test_set_type_by_unique_id_prefix(repository):
+    """Test resolving an account by ID prefix and persisting its new type."""
+    service = AccountService(repository)
+    created = (await service.create_account("Chase Sapphire", None)).data
+
+    resolved = await service.resolve_account_id(str(created.id)[:8].upper())
+    assert resolved.success, resolved.error
+    result = await service.update_account_type(resolved.data.id, "Credit")
+
+    assert result.success, result.error
+    stored = (await repository.get_account_by_id(created.id)).data
+    assert stored.account_type == "credit"
+
+
+@pytest.mark.asyncio
+async def test_set_type_fails_on_unknown_id_and_type(repository):
+    """Test the errors for an unknown UUID, a short prefix and an unknown type."""
+    service = AccountService(repository)
+    created = (await service.create_account("Cash", None)).data
+    missing_id = uuid4()
+
+    missing = await service.resolve_account_id(str(missing_id))
+    assert missing.error == f"Account not found: {missing_id}"
+    assert "at least" in (await service.resolve_account_id("ab")).error
+    assert not (await service.update_account_type(missing_id, "checking")).success
+
+    invalid = await service.update_account_type(created.id, "brokerage")
+    assert "Invalid account type" in invalid.error
+    assert (await repository.get_account_by_id(created.id)).data.account_type is None
+
+
+@pytest.mark.asyncio
+async def test_set_and_clear_nickname(repository):
+    """Test that a nickname is trimmed, stored and cleared, and blank ones are refused."""
+    service = AccountService(repository)
+    created = (await service.create_account("CREDIT CARD ...4821", "credit")).data
+
+    result = await service.set_nickname(created.id, "  Sapphire ")
+    assert result.data.nickname == "Sapphire"
+    assert result.data.updated_at > created.updated_at
+    assert (await repository.get_account_by_id(created.id)).data.nickname == "Sapphire"
+    assert (await service.resolve_account("sapphire")).data.id == created.id
+
+    assert not (await service.set_nickname(created.id, "   ")).success
+    await service.set_nickname(created.id, None)
+    assert (await repository.get_account_by_id(created.id)).data.nickname is None
+
+
+@pytest.mark.asyncio
+async def test_archive_and_unarchive(repository):
+    """Test that archiving stamps archived_at once and unarchiving clears it."""
+    service = AccountService(repository)
+    created = (await service.create_account("Old Checking", "checking")).data
+
+    archived = (await service.set_archived(created.id, True)).data
+    assert archived.archived_at is not None
+    again = (await service.set_archived(created.id, True)).data
+    assert again.archived_at == archived.archived_at
+    stored = (await repository.get_account_by_id(created.id)).data
+    assert stored.archived_at is not None
+
+    await service.set_archived(created.id, False)
+    assert (await repository.get_account_by_id(created.id)).data.archived_at is None
+    assert not (await service.set_archived(uuid4(), True)).success
+
+
+@pytest.mark.asyncio
+async def test_find_account_by_external_id(repository):
+    """Test keyed and any-key account lookups and the not-found kind."""
+    synced = make_account(external_ids={"simplefin": "ACT-1%"})
+    other = make_account("Savings", external_ids={"lunchflow": "ACT-1"})
+    await repository.add_account(synced)
+    await repository.add_account(other)
+    service = AccountService(repository)
+
+    keyed = await service.find_by_external_id("simplefin", "ACT-1%")
+    assert [(a.id, key) for a, key in keyed.data] == [(synced.id, "simplefin")]
+    anywhere = await service.find_by_external_id("any", "ACT-1")
+    assert [(a.id, key) for a, key in anywhere.data] == [(other.id, "lunchflow")]
+    missing = await service.find_by_external_id("simplefin", "ACT-1")
+    assert missing.context["kind"] == "not_found"
+
+
+@pytest.mark.asyncio
+async def test_month_end_snapshot_is_reconstructed_once(repository):
+    """Test that a month-end snapshot is filled in from the next snapshot, only once."""
+    service = AccountService(repository)
+    account = (await service.create_account("Checking", "depository")).data
+    await repository.add_transaction(
+        make_transaction(account.id, "-50.00", date(2024, 4, 2), description="Groceries")
+    )
+    await service.add_balance_snapshot(account.id, Decimal("950.00"), date(2024, 4, 3), "sync")
+
+    dry_run = await service.add_month_end_snapshots(date(2024, 3, 31), dry_run=True)
+    first = await service.add_month_end_snapshots(date(2024, 3, 31))
+    second = await service.add_month_end_snapshots(date(2024, 3, 31))
+
+    assert [s.balance for s in dry_run.data] == [Decimal("1000.00")]
+    (snapshot,) = first.data
+    assert snapshot.balance == Decimal("1000.00")
+    assert snapshot.source == "auto"
+    assert snapshot.snapshot_time.date() == date(2024, 3, 31)
+    assert second.data == []
+    stored = (await repository.get_balance_snapshots(date="2024-03-31")).data
+    assert [s.id for s in stored] == [snapshot.id]
+
+
+@pytest.mark.asyncio
+async def test_month_end_snapshot_skips_accounts_with_no_earlier_data(repository):
+    """Test that a brand-new account gets no month-end snapshot before it existed."""
+    service = AccountService(repository)
+    account = (await service.create_account("New Savings", "depository")).data
+    await service.add_balance_snapshot(account.id, Decimal("500.00"), date(2024, 4, 2), "sync")
+
+    result = await service.add_month_end_snapshots(date(2024, 3, 31))
+
+    assert result.success, result.error
+    assert result.data == []
+    assert (await repository.get_balance_snapshots(date="2024-03-31")).data == []
@@ -0,0 +1,98 @@
+"""Unit tests for AttachmentService."""
+
+from pathlib import Path
+
+import pytest
+import pytest_asyncio
+
+from treeline.app.attachment_service import AttachmentService
+from treeline.app.transaction_service import TransactionService
+
+from tests.support.factories import make_transaction
+
+
+@pytest_asyncio.fixture
+async def workspace(repository, account_id, tmp_path):
+    """Create a fresh database with one transaction, and a receipt to attach."""
+    transaction = make_transaction(account_id, "-1299.00", description="Laptop")
+    await repository.add_transaction(transaction)
+
+    receipt = tmp_path / "receipt.pdf"
+    receipt.write_bytes(b"%PDF-1.4 receipt")
+
+    service = AttachmentService(
+        repository,
+        TransactionService(repository),
+        attachments_dir=tmp_path / "attachments",
+        max_bytes=1024,
+        allowed_extensions=["pdf", "png"],
+    )
+    return service, transaction, receipt
+
+
+@pytest.mark.asyncio
+async def test_attach_copies_file_and_detach_moves_it_to_trash(workspace):
+    """Test that attach stores a copy by year and detach keeps the file in the trash."""
+    service, transaction, receipt = workspace
+
+    result = await service.attach(str(transaction.id)[:8], str(receipt))
+
+    assert result.success, result.error
+    attachment = result.data
+    assert attachment.filename == "receipt.pdf"
+    assert attachment.stored_path == f"2024/{attachment.id}-receipt.pdf"
+    assert attachment.size_bytes == receipt.stat().st_size
+    assert service.path_for(attachment).read_bytes() == receipt.read_bytes()
+
+    listed = await service.list_attachments(str(transaction.id))
+    assert [a.id for a in listed.data] == [attachment.id]
+
+    detached = await service.detach(str(attachment.id))
+
+    assert detached.success, detached.error
+    assert not service.path_for(attachment).exists()
+    assert detached.data["trash_path"].read_bytes() == receipt.read_bytes()
+    assert (await service.list_attachments(str(transaction.id))).data == []
+
+
+@pytest.mark.asyncio
+async def test_attach_enforces_size_and_extension(workspace):
+    """Test the size cap and extension allowlist."""
+    service, transaction, receipt = workspace
+    big = receipt.with_name("scan.png")
+    big.write_bytes(b"x" * 2048)
+    script = receipt.with_name("run.sh")
+    script.write_text("echo hi")
+
+    too_big = await service.attach(str(transaction.id), str(big))
+    wrong_type = await service.attach(str(transaction.id), str(script))
+
+    assert not too_big.success
+    assert "limited to" in too_big.error
+    assert not wrong_type.success
+    assert "Can't attach .sh files" in wrong_type.error
+    assert not service.attachments_dir.exists()
+
+
+@pytest.mark.asyncio
+async def test_prune_only_removes_attachments_of_deleted_transactions(workspace):
+    """Test that attachments survive a transaction's deletion until pruned."""
+    service, transaction, receipt = workspace
+    attachment = (await service.attach(str(transaction.id), str(receipt))).data
+
+    assert (await service.prune(dry_run=True)).data["attachments"] == []
+
+    await service.repository.execute_write_query(
+        f"UPDATE sys_transactions SET deleted_at = now() WHERE transaction_id = '{transaction.id}'"
+    )
+    dry_run = await service.prune(dry_run=True)
+
+    assert [a.id for a in dry_run.data["attachments"]] == [attachment.id]
+    assert service.path_for(attachment).exists()
+
+    pruned = await service.prune()
+
+    assert [a.id for a in pruned.data["attachments"]] == [attachment.id]
+    assert not service.path_for(attachment).exists()
+    assert (service.trash_dir / Path(attachment.stored_path).name).exists()
+    assert (await service.repository.get_dangling_attachments()).data == []
@@ -0,0 +1,112 @@
+"""Unit tests for BackfillService."""
+
+from datetime import date, datetime, timedelta, timezone
+from decimal import Decimal
+from unittest.mock import AsyncMock
+
+import pytest
+
+from treeline.app.backfill_service import BackfillService
+from treeline.domain import Ok
+
+from tests.support.factories import make_account, make_transaction
+
+
+def days_ago(days: int) -> date:
+    return date.today() - timedelta(days=days)
+
+
+def make_repository(accounts, transactions):
+    """Repository with no balance snapshots and the given accounts and transactions."""
+    repository = AsyncMock()
+    repository.get_accounts.return_value = Ok(accounts)
+    repository.get_balance_snapshots.return_value = Ok([])
+    repository.get_transactions_by_account.side_effect = lambda account_id, **_: Ok(
+        [tx for tx in transactions if tx.account_id == account_id]
+    )
+    repository.bulk_add_balances.return_value = Ok(None)
+    return repository
+
+
+@pytest.mark.asyncio
+async def test_accounts_without_a_known_balance_are_skipped():
+    """Test that manual and provider accounts with no balance aren't walked back from zero."""
+    cash = make_account("Cash", nickname="Wallet")
+    checking = make_account("Checking", external_ids={"simplefin": "sf-checking"})
+    repository = make_repository(
+        [cash, checking],
+        [
+            make_transaction(cash.id, "-20.00", days_ago(3)),
+            make_transaction(checking.id, "-50.00", days_ago(2)),
+        ],
+    )
+
+    result = await BackfillService(repository).backfill_balances()
+
+    assert result.success
+    assert result.data["accounts_processed"] == 2
+    assert result.data["snapshots_created"] == 0
+    assert [(s["account_id"], s["account"]) for s in result.data["accounts_skipped"]] == [
+        (str(cash.id), "Wallet"),
+        (str(checking.id), "Checking"),
+    ]
+    repository.bulk_add_balances.assert_not_called()
+
+
+@pytest.mark.asyncio
+async def test_archived_accounts_are_skipped():
+    """Test that an archived account is listed as skipped and gets no snapshots."""
+    old = make_account(
+        "Old Savings", balance=Decimal("100.00"), archived_at=datetime.now(timezone.utc)
+    )
+    repository = make_repository([old], [make_transaction(old.id, "-50.00", days_ago(2))])
+
+    result = await BackfillService(repository).backfill_balances()
+
+    assert result.data["accounts_processed"] == 0
+    assert [s["account_id"] for s in result.data["accounts_skipped"]] == [str(old.id)]
+    repository.get_transactions_by_account.assert_not_called()
+    repository.bulk_add_balances.assert_not_called()
+
+
+@pytest.mark.asyncio
+async def test_provider_balance_anchors_accounts_without_snapshots():
+    """Test that a balance reported by the provider is used when there are no snapshots."""
+    checking = make_account("Checking", balance=Decimal("100.00"))
+    repository = make_repository(
+        [checking],
+        [
+            make_transaction(checking.id, "-30.00", days_ago(1)),
+            make_transaction(checking.id, "-50.00", days_ago(2)),
+        ],
+    )
+
+    result = await BackfillService(repository).backfill_balances()
+
+    assert result.data["accounts_skipped"] == []
+    (snapshots,) = repository.bulk_add_balances.call_args.args
+    # End-of-day balances: day 1 already includes its -30.00
+    assert [s.balance for s in snapshots] == [Decimal("100.00"), Decimal("130.00")]
+
+
+@pytest.mark.asyncio
+async def test_force_zero_starts_unknown_balances_at_zero():
+    """Test that --force-zero walks back from zero today instead of skipping."""
+    cash = make_account("Cash")
+    repository = make_repository(
+        [cash],
+        [
+            make_transaction(cash.id, "-20.00", days_ago(1)),
+            make_transaction(cash.id, "5.00", days_ago(3)),
+        ],
+    )
+
+    result = await BackfillService(repository).backfill_balances(force_zero=True)
+
+    assert result.data["accounts_skipped"] == []
+    assert result.data["snapshots_created"] == 2
+    (snapshots,) = repository.bulk_add_balances.call_args.args
+    assert [(s.snapshot_time.date(), s.balance, s.source) for s in snapshots] == [
+        (date.today() - timedelta(days=1), Decimal("0.00"), "backfill"),
+        (date.today() - timedelta(days=3), Decimal("20.00"), "backfill"),
+    ]
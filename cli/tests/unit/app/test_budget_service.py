@@ -0,0 +1,72 @@
+"""Tests for budget alerts raised during sync."""
+
+from decimal import Decimal
+
+from treeline.app.budget_service import BudgetAlert, describe_budget_alert, find_budget_alerts
+
+
+def entry(actual: str, expected: str = "500", alerted: str | None = None) -> dict:
+    return {
+        "category_id": "cat-1",
+        "name": "groceries",
+        "expected": Decimal(expected),
+        "actual": Decimal(actual),
+        "alerted_actual": Decimal(alerted) if alerted is not None else None,
+    }
+
+
+def test_alerts_when_sync_crosses_budget():
+    alerts, levels = find_budget_alerts("2025-12", [entry("525")], [entry("612")])
+
+    assert alerts == [
+        BudgetAlert(
+            category_id="cat-1",
+            category="groceries",
+            month="2025-12",
+            expected=Decimal("500"),
+            actual=Decimal("612"),
+            over_by=Decimal("112"),
+            sync_delta=Decimal("87"),
+        )
+    ]
+    assert levels == {"cat-1": Decimal("612")}
+
+
+def test_no_repeat_alert_until_spending_grows():
+    # Already alerted at 612; a sync that adds nothing stays quiet
+    alerts, levels = find_budget_alerts(
+        "2025-12", [entry("612", alerted="612")], [entry("612", alerted="612")]
+    )
+    assert alerts == []
+    assert levels == {}
+
+    # More spending past the alerted level alerts again
+    alerts, levels = find_budget_alerts(
+        "2025-12", [entry("612", alerted="612")], [entry("650", alerted="612")]
+    )
+    assert [a.sync_delta for a in alerts] == [Decimal("38")]
+    assert levels == {"cat-1": Decimal("650")}
+
+
+def test_under_budget_clears_alert_level():
+    alerts, levels = find_budget_alerts(
+        "2025-12", [entry("612", alerted="612")], [entry("480", alerted="612")]
+    )
+    assert alerts == []
+    assert levels == {"cat-1": None}
+
+
+def test_describe_budget_alert():
+    alert = BudgetAlert(
+        category_id="cat-1",
+        category="groceries",
+        month="2025-12",
+        expected=Decimal("500"),
+        actual=Decimal("612"),
+        over_by=Decimal("112"),
+        sync_delta=Decimal("87"),
+    )
+    assert (
+        describe_budget_alert(alert, "USD", locale="en_US")
+        == "groceries: $612 of $500 (over by $112, +$87 from this sync)"
+    )
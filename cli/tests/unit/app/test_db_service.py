@@ -0,0 +1,173 @@
+"""Unit tests for DbService."""
+
+import tempfile
+from datetime import date
+from decimal import Decimal
+from pathlib import Path
+
+import duckdb
+import pytest
+
+from treeline.app.db_service import DbService
+from treeline.infra.duckdb import DuckDBRepository, get_supported_data_version
+from treeline.infra.schema_metadata import SCHEMA_METADATA
+
+from tests.support.factories import make_account, make_transaction
+
+
+@pytest.fixture
+def db_path():
+    """Path to a fresh database file in a temporary directory."""
+    with tempfile.TemporaryDirectory() as tmpdir:
+        yield Path(tmpdir) / "treeline.duckdb"
+
+
+@pytest.mark.asyncio
+async def test_initialize_db_records_data_version(db_path):
+    """Test that initializing a new database writes the supported data version."""
+    service = DbService(DuckDBRepository(str(db_path)), SCHEMA_METADATA)
+
+    result = await service.initialize_db()
+    assert result.success
+
+    compat = await service.check_compatibility()
+    assert compat.success
+    assert compat.data["compatible"]
+    assert compat.data["data_version"] == get_supported_data_version()
+
+
+@pytest.mark.asyncio
+async def test_initialize_db_refuses_newer_database(db_path):
+    """Test that a database written by a newer treeline is refused."""
+    service = DbService(DuckDBRepository(str(db_path)), SCHEMA_METADATA)
+    assert (await service.initialize_db()).success
+
+    conn = duckdb.connect(str(db_path))
+    conn.execute(
+        "UPDATE sys_meta SET value = ? WHERE key = 'data_version'",
+        [str(get_supported_data_version() + 1)],
+    )
+    conn.execute("UPDATE sys_meta SET value = '99.0.0' WHERE key = 'min_app_version'")
+    conn.close()
+
+    result = await service.initialize_db()
+
+    assert not result.success
+    assert result.context["incompatible_db"]
+    assert "requires treeline >= 99.0.0" in result.error
+
+
+@pytest.mark.asyncio
+async def test_get_schema_lists_views_and_hides_system_tables(db_path):
+    """Test that get_schema lists views with typed, described columns; sys_* only with --all."""
+    service = DbService(DuckDBRepository(str(db_path)), SCHEMA_METADATA)
+    assert (await service.initialize_db()).success
+
+    result = await service.get_schema()
+
+    assert result.success
+    names = [entry["name"] for entry in result.data]
+    assert {"accounts", "transactions", "balance_snapshots"} <= set(names)
+    assert not any(name.startswith("sys_") for name in names)
+    transactions = next(entry for entry in result.data if entry["name"] == "transactions")
+    assert transactions["kind"] == "view"
+    amount = next(c for c in transactions["columns"] if c["name"] == "amount")
+    assert amount["type"].startswith("DECIMAL")
+    assert amount["description"]
+
+    with_system = await service.get_schema(include_system=True)
+    assert "sys_transactions" in [entry["name"] for entry in with_system.data]
+
+
+@pytest.mark.asyncio
+async def test_get_schema_single_table(db_path):
+    """Test describing one table, including a sys_* table, and an unknown name."""
+    service = DbService(DuckDBRepository(str(db_path)), SCHEMA_METADATA)
+    assert (await service.initialize_db()).success
+
+    (entry,) = (await service.get_schema(table="sys_imports")).data
+    assert entry["kind"] == "table"
+
+    result = await service.get_schema(table="nope")
+    assert not result.success
+    assert result.error == "Unknown table or view: nope"
+
+
+@pytest.mark.asyncio
+async def test_view_drift_is_detected_and_repaired(db_path):
+    """Test that a view missing a column is reported on startup and repair restores it."""
+    service = DbService(DuckDBRepository(str(db_path)), SCHEMA_METADATA)
+    first = await service.initialize_db()
+    assert first.success
+    assert not (first.context or {}).get("view_drift")
+
+    conn = duckdb.connect(str(db_path))
+    conn.execute(
+        "CREATE OR REPLACE VIEW transactions AS "
+        "SELECT * EXCLUDE (status) FROM sys_transactions WHERE deleted_at IS NULL"
+    )
+    conn.execute("DROP VIEW balance_snapshots")
+    conn.close()
+
+    result = await service.initialize_db()
+
+    assert result.success
+    drift = {entry["view"]: entry for entry in result.context["view_drift"]}
+    assert set(drift) == {"transactions", "balance_snapshots"}
+    assert drift["balance_snapshots"]["missing"]
+    assert "status" in drift["transactions"]["missing_columns"]
+    assert "account_name" in drift["transactions"]["missing_columns"]
+    assert "deleted_at" in drift["transactions"]["unexpected_columns"]
+
+    repaired = await service.repair_views()
+
+    assert repaired.success
+    assert {"transactions", "balance_snapshots"} <= set(repaired.data["repaired"])
+    assert repaired.data["remaining_drift"] == []
+    assert (await service.check_views()).data == []
+
+
+@pytest.mark.asyncio
+async def test_export_transactions_round_trips_through_parquet(db_path):
+    """Test that an exported parquet file reads back with every row and typed columns."""
+    repository = DuckDBRepository(str(db_path))
+    service = DbService(repository, SCHEMA_METADATA)
+    assert (await service.initialize_db()).success
+
+    account = make_account(account_type="depository")
+    await repository.add_account(account)
+    for day, amount in ((3, "-12.50"), (9, "2400.00"), (21, "-1800.00")):
+        await repository.add_transaction(
+            make_transaction(
+                account.id, amount, date(2024, 3, day),
+                description=f"Transaction {day}", tags=["test"],
+            )
+        )
+
+    output = db_path.parent / "transactions.parquet"
+    result = await service.export_transactions(output, "parquet", start_date=date(2024, 3, 5))
+
+    assert result.success, result.error
+    assert result.data["rows"] == 2
+    assert [p.name for p in db_path.parent.iterdir() if p.name.startswith(".treeline-export")] == []
+
+    read_back = await service.execute_query(
+        f"SELECT transaction_date, amount, month, display_name, tags FROM read_parquet('{output}')"
+    )
+    assert read_back.success, read_back.error
+    assert read_back.data["rows"] == [
+        (date(2024, 3, 9), Decimal("2400.00"), "2024-03", "Checking", ["test"]),
+        (date(2024, 3, 21), Decimal("-1800.00"), "2024-03", "Checking", ["test"]),
+    ]
+
+    refused = await service.export_transactions(output, "parquet")
+    assert not refused.success
+    assert refused.context["exists"]
+    overwritten = await service.export_transactions(output, "parquet", overwrite=True)
+    assert overwritten.data["rows"] == 3
+
+    missing_dir = await service.export_query(
+        "SELECT 1", db_path.parent / "missing" / "out.parquet", "parquet"
+    )
+    assert not missing_dir.success
+    assert "Directory not found" in missing_dir.error
@@ -0,0 +1,146 @@
+"""Unit tests for DedupeService and the duplicate review queue."""
+
+from datetime import date, timedelta
+
+import pytest
+import pytest_asyncio
+
+from treeline.app.dedupe_service import DedupeService, merged_external_ids, parse_status
+from treeline.app.integration_service import IntegrationService
+from treeline.domain import DuplicateKind, DuplicateStatus, Transaction
+
+from tests.support.factories import make_account, make_transaction
+
+TODAY = date.today()
+
+
+def coffee(account_id, **fields) -> Transaction:
+    return make_transaction(
+        account_id, "-4.50", TODAY - timedelta(days=3), description="Blue Bottle Coffee", **fields
+    )
+
+
+@pytest_asyncio.fixture
+async def workspace(repository):
+    """Fresh database with a checking account holding one transaction twice."""
+    account = make_account()
+    await repository.add_account(account)
+    keep = coffee(account.id, tags=("coffee",), external_ids={"simplefin": "sf-1"})
+    duplicate = coffee(account.id, tags=("work",), external_ids={"csv": "row-7"})
+    await repository.add_transaction(keep)
+    await repository.add_transaction(duplicate)
+
+    service = DedupeService(repository, IntegrationService(repository))
+    return service, repository, keep, duplicate
+
+
+def test_parse_status_accepts_all():
+    """Test that "all" (or no status) lists every status and junk is rejected."""
+    assert parse_status("all").data is None
+    assert parse_status(None).data is None
+    assert parse_status("Dismissed").data == DuplicateStatus.DISMISSED
+    assert not parse_status("closed").success
+
+
+def test_merged_external_ids_keep_the_kept_side_on_clashes():
+    """Test that the kept transaction's IDs win and the duplicate's others are added."""
+    merged = merged_external_ids(
+        {"fingerprint": "aaa", "simplefin": "sf-1"}, {"fingerprint": "aaa", "csv": "row-7"}
+    )
+
+    assert merged == {"fingerprint": "aaa", "simplefin": "sf-1", "csv": "row-7"}
+
+
+@pytest.mark.asyncio
+async def test_dismissed_pair_is_not_queued_again(workspace):
+    """Test a candidate's lifecycle: detected, dismissed, then detected again without reopening."""
+    service, _, keep, duplicate = workspace
+
+    first = await service.scan_transactions()
+    assert first.data == {"found": 1, "added": 1}
+
+    open_result = await service.list_candidates()
+    assert len(open_result.data) == 1
+    entry = open_result.data[0]
+    candidate = entry["candidate"]
+    assert candidate.kind == DuplicateKind.TRANSACTION
+    assert candidate.score == 1.0
+    assert {entry["keep"].id, entry["duplicate"].id} == {keep.id, duplicate.id}
+
+    dismiss_result = await service.dismiss(str(candidate.id)[:8])
+    assert dismiss_result.success
+    assert dismiss_result.data.status == DuplicateStatus.DISMISSED
+
+    again = await service.scan_transactions()
+    assert again.data == {"found": 1, "added": 0}
+    assert (await service.list_candidates()).data == []
+
+    dismissed = (await service.list_candidates(DuplicateStatus.DISMISSED)).data
+    assert [e["candidate"].id for e in dismissed] == [candidate.id]
+    assert dismissed[0]["candidate"].resolved_at is not None
+
+    redismiss = await service.dismiss(str(candidate.id))
+    assert not redismiss.success
+    assert "already dismissed" in redismiss.error
+
+
+@pytest.mark.asyncio
+async def test_merge_folds_the_duplicate_into_the_kept_transaction(workspace):
+    """Test that merging soft-deletes the duplicate and moves its tags and external IDs."""
+    service, repository, _, _ = workspace
+    await service.scan_transactions()
+    candidate = (await service.list_candidates()).data[0]["candidate"]
+
+    merge_result = await service.merge(str(candidate.id))
+
+    assert merge_result.success, merge_result.error
+    assert merge_result.data["kind"] == "transaction"
+    kept = (await repository.get_transaction_by_id(candidate.keep_id)).data
+    removed = (await repository.get_transaction_by_id(candidate.duplicate_id)).data
+    assert removed.deleted_at is not None
+    assert kept.deleted_at is None
+    assert set(kept.tags) == {"coffee", "work"}
+    assert kept.external_ids["simplefin"] == "sf-1"
+    assert kept.external_ids["csv"] == "row-7"
+
+    merged = (await service.list_candidates(DuplicateStatus.MERGED)).data
+    assert [e["candidate"].id for e in merged] == [candidate.id]
+    assert (await service.scan_transactions()).data == {"found": 0, "added": 0}
+
+
+@pytest.mark.asyncio
+async def test_old_duplicates_are_outside_the_scan_window(workspace):
+    """Test that pairs dated before the scan window aren't queued."""
+    service, _, _, _ = workspace
+
+    result = await service.scan_transactions(days=1)
+
+    assert result.data == {"found": 0, "added": 0}
+
+
+@pytest.mark.asyncio
+async def test_dismissed_account_pair_is_no_longer_reported(workspace):
+    """Test that sync's cross-provider duplicates are queued once and drop out once dismissed."""
+    service, repository, _, _ = workspace
+    first = make_account("Checking (SimpleFIN)", external_ids={"simplefin": "acct-1"})
+    second = make_account("Checking (Lunch Flow)", external_ids={"lunchflow": "acct-9"})
+    await repository.add_account(first)
+    await repository.add_account(second)
+    reported = [
+        {
+            "keep": {"account_id": str(first.id), "transactions": 40},
+            "duplicate": {"account_id": str(second.id), "transactions": 20},
+            "shared_transactions": 15,
+        }
+    ]
+
+    recorded = await service.record_account_duplicates(reported)
+    assert recorded.data == {"added": 1, "duplicates": reported}
+
+    candidate = (await service.list_candidates()).data[0]["candidate"]
+    assert candidate.kind == DuplicateKind.ACCOUNT
+    assert candidate.score == 0.75
+    await service.dismiss(str(candidate.id))
+
+    again = await service.record_account_duplicates(reported)
+    assert again.data == {"added": 0, "duplicates": []}
@@ -0,0 +1,642 @@
+"""Unit tests for ImportService file checks, import history, dedup and sign checks."""
+
+import asyncio
+import json
+from datetime import date
+from decimal import Decimal
+from pathlib import Path
+
+import pytest
+
+from treeline.app.import_service import ImportService, import_checkpoint_path
+from treeline.domain import Account
+from treeline.infra.csv import CSVProvider
+from treeline.infra.duckdb import DuckDBRepository
+from treeline.infra.write_lock import FileWriteLock, write_lock_path
+
+from tests.support.datasets import (
+    CSV_COLUMN_MAPPING,
+    make_accounts,
+    make_transactions,
+    seed_database,
+    write_csv,
+)
+from tests.support.factories import make_account, make_transaction
+
+CSV_CONTENT = """Date,Description,Amount
+2024-10-01,Coffee,-5.50
+2024-10-02,Groceries,-45.00
+"""
+
+
+def source_options(csv_path: Path) -> dict:
+    return {
+        "file_path": str(csv_path),
+        "column_mapping": {"date": "Date", "description": "Description", "amount": "Amount"},
+    }
+
+
+@pytest.mark.asyncio
+async def test_identical_file_reimport_is_short_circuited(repository, account_id, tmp_path):
+    """Test that importing the same bytes again is refused without --force."""
+    csv_path = tmp_path / "statement.csv"
+    csv_path.write_text(CSV_CONTENT)
+    service = ImportService(repository, {"csv": CSVProvider()})
+
+    first = await service.import_transactions("csv", account_id, source_options(csv_path))
+    assert first.success
+    assert first.data["imported"] == 2
+
+    # Same content under a different name is still the same file
+    copy_path = tmp_path / "statement (1).csv"
+    copy_path.write_text(CSV_CONTENT)
+    second = await service.import_transactions("csv", account_id, source_options(copy_path))
+
+    assert not second.success
+    assert second.context["kind"] == "already_imported"
+    assert second.context["import"].id == first.data["import_id"]
+
+    forced = await service.import_transactions(
+        "csv", account_id, source_options(copy_path), force=True
+    )
+    assert forced.success
+    assert forced.data["imported"] == 0
+    assert forced.data["skipped"] == 2
+
+    history = await service.list_imports()
+    assert history.success
+    assert len(history.data) == 2
+    assert {record.file_sha256 for record in history.data} == {first.data["file_sha256"]}
+
+
+@pytest.mark.asyncio
+async def test_changed_file_is_processed(repository, account_id, tmp_path):
+    """Test that a file with new rows is imported normally."""
+    csv_path = tmp_path / "statement.csv"
+    csv_path.write_text(CSV_CONTENT)
+    service = ImportService(repository, {"csv": CSVProvider()})
+    await service.import_transactions("csv", account_id, source_options(csv_path))
+
+    csv_path.write_text(CSV_CONTENT + "2024-10-03,Lunch,-12.00\n")
+    result = await service.import_transactions("csv", account_id, source_options(csv_path))
+
+    assert result.success
+    assert result.data["imported"] == 1
+
+
+@pytest.mark.asyncio
+async def test_reimport_does_not_bring_back_deleted_rows(repository, account_id, tmp_path):
+    """Test that fingerprint dedupe still counts soft-deleted transactions."""
+    csv_path = tmp_path / "statement.csv"
+    csv_path.write_text(CSV_CONTENT)
+    service = ImportService(repository, {"csv": CSVProvider()})
+    first = await service.import_transactions("csv", account_id, source_options(csv_path))
+    imported = await repository.get_transactions_by_account(account_id)
+    await repository.delete_transactions([imported.data[0].id])
+
+    result = await service.import_transactions(
+        "csv", account_id, source_options(csv_path), force=True
+    )
+
+    assert first.data["imported"] == 2
+    assert result.success
+    assert result.data["imported"] == 0
+    assert result.data["skipped"] == 2
+
+
+class SlowDedupeRepository(DuckDBRepository):
+    """Repository that yields between the dedupe snapshot and the insert."""
+
+    async def get_transaction_counts_by_fingerprint(self, fingerprints, exclude_import_id=None):
+        result = await super().get_transaction_counts_by_fingerprint(
+            fingerprints, exclude_import_id
+        )
+        await asyncio.sleep(0.2)
+        return result
+
+
+@pytest.fixture
+def slow_repository(repository):
+    """The conftest database, opened through SlowDedupeRepository."""
+    return SlowDedupeRepository(str(repository.db_path))
+
+
+@pytest.mark.asyncio
+async def test_concurrent_overlapping_imports_do_not_duplicate(
+    account_id, slow_repository, tmp_path
+):
+    """Test that two imports racing over the same rows take turns under the write lock."""
+    repository = slow_repository
+    lock_path = write_lock_path(repository.db_path)
+    first_path, second_path = tmp_path / "october.csv", tmp_path / "october-again.csv"
+    first_path.write_text(CSV_CONTENT)
+    second_path.write_text(CSV_CONTENT + "2024-10-03,Gas,-38.20\n")
+
+    def importer() -> ImportService:
+        # Each import has its own lock object, as two tl processes would
+        lock = FileWriteLock(lock_path, wait_seconds=10)
+        return ImportService(repository, {"csv": CSVProvider()}, lock)
+
+    first, second = await asyncio.gather(
+        importer().import_transactions("csv", account_id, source_options(first_path)),
+        importer().import_transactions("csv", account_id, source_options(second_path)),
+    )
+
+    assert first.success and second.success
+    assert first.data["imported"] + second.data["imported"] == 3
+    duplicates = await repository.execute_query(
+        """
+        SELECT json_extract_string(external_ids, '$.fingerprint') AS fingerprint
+        FROM sys_transactions
+        GROUP BY fingerprint
+        HAVING COUNT(*) > 1
+        """
+    )
+    assert duplicates.data["rows"] == []
+    assert not lock_path.exists()
+
+
+def statement_csv(amounts) -> str:
+    rows = [f"2024-10-{day:02d},Row {day},{amount}" for day, amount in enumerate(amounts, 1)]
+    return "Date,Description,Amount\n" + "\n".join(rows) + "\n"
+
+
+async def add_credit_card(repository) -> Account:
+    account = make_account("Visa", account_type="credit")
+    await repository.add_account(account)
+    return account
+
+
+@pytest.mark.asyncio
+async def test_inverted_import_warns_against_history(repository, tmp_path):
+    """Test that mostly positive rows into a mostly negative account warn but still import."""
+    card = await add_credit_card(repository)
+    service = ImportService(repository, {"csv": CSVProvider()})
+
+    history_path = tmp_path / "september.csv"
+    history_path.write_text(statement_csv(["-12.00", "-40.00", "-8.50", "-99.99", "-5.25", "300.00"]))
+    history = await service.import_transactions("csv", card.id, source_options(history_path))
+    assert history.data["warnings"] == []
+
+    inverted_path = tmp_path / "october.csv"
+    inverted_path.write_text(statement_csv(["15.00", "42.00", "9.75", "120.00", "6.10", "-250.00"]))
+    result = await service.import_transactions("csv", card.id, source_options(inverted_path))
+
+    assert result.success
+    assert result.data["imported"] == 6
+    assert len(result.data["warnings"]) == 1
+    assert "look inverted" in result.data["warnings"][0]
+    assert "--flip-signs" in result.data["warnings"][0]
+
+
+@pytest.mark.asyncio
+async def test_flipped_import_is_consistent(repository, tmp_path):
+    """Test that the same rows with signs flipped do not warn."""
+    card = await add_credit_card(repository)
+    service = ImportService(repository, {"csv": CSVProvider()})
+
+    csv_path = tmp_path / "october.csv"
+    csv_path.write_text(statement_csv(["15.00", "42.00", "9.75", "120.00", "6.10", "-250.00"]))
+    options = {**source_options(csv_path), "flip_signs": True}
+    result = await service.import_transactions("csv", card.id, options)
+
+    assert result.success
+    assert result.data["warnings"] == []
+
+
+@pytest.mark.asyncio
+async def test_sign_check_uses_account_type_without_history(repository):
+    """Test that a new credit card expects negative purchases."""
+    card = await add_credit_card(repository)
+    service = ImportService(repository, {"csv": CSVProvider()})
+    positive = [make_transaction(card.id, amount) for amount in ("10", "20", "30", "40", "50")]
+    negative = [make_transaction(card.id, f"-{amount}") for amount in ("10", "20", "30", "40", "50")]
+
+    assert len((await service.check_sign_profile(card.id, positive)).data) == 1
+    assert (await service.check_sign_profile(card.id, negative)).data == []
+
+
+@pytest.mark.asyncio
+async def test_sign_check_needs_enough_rows(repository, account_id):
+    """Test that a handful of rows never warns."""
+    service = ImportService(repository, {"csv": CSVProvider()})
+    rows = [make_transaction(account_id, "10"), make_transaction(account_id, "20")]
+
+    assert (await service.check_sign_profile(account_id, rows)).data == []
+
+
+def coffee_csv(*days: int) -> str:
+    rows = [f"2025-03-{day:02d},Corner Coffee,-4.50" for day in days]
+    return "Date,Description,Amount\n" + "\n".join(rows) + "\n"
+
+
+@pytest.fixture
+def fuzzy_dedup_days(tmp_path, monkeypatch):
+    """Set import.fuzzy_dedup_days to 1 in a throwaway settings file."""
+    monkeypatch.setenv("TREELINE_DIR", str(tmp_path))
+    (tmp_path / "settings.json").write_text(json.dumps({"import": {"fuzzy_dedup_days": 1}}))
+
+
+@pytest.mark.asyncio
+async def test_fuzzy_dedup_skips_a_shifted_date_but_not_other_purchases(
+    repository, account_id, tmp_path, fuzzy_dedup_days
+):
+    """Test that a row dated a day off is skipped while purchases two days away are kept."""
+    service = ImportService(repository, {"csv": CSVProvider()})
+    pending_path = tmp_path / "pending.csv"
+    pending_path.write_text(coffee_csv(15))
+    await service.import_transactions("csv", account_id, source_options(pending_path))
+
+    statement_path = tmp_path / "statement.csv"
+    statement_path.write_text(coffee_csv(13, 14, 17))
+    result = await service.import_transactions("csv", account_id, source_options(statement_path))
+
+    assert result.success, result.error
+    assert result.data["imported"] == 2
+    assert result.data["skipped"] == 1
+    assert result.data["skipped_fuzzy"] == 1
+    (skipped,) = result.data["skipped_transactions"]
+    assert skipped["transaction"].transaction_date == date(2025, 3, 14)
+    assert skipped["fuzzy"] is True
+    imported_dates = sorted(tx.transaction_date for tx in result.data["imported_transactions"])
+    assert imported_dates == [date(2025, 3, 13), date(2025, 3, 17)]
+
+
+@pytest.mark.asyncio
+async def test_fuzzy_dedup_does_not_reuse_an_exact_match(
+    repository, account_id, tmp_path, fuzzy_dedup_days
+):
+    """Test that a stored row matched exactly can't also absorb the next day's purchase."""
+    service = ImportService(repository, {"csv": CSVProvider()})
+    pending_path = tmp_path / "pending.csv"
+    pending_path.write_text(coffee_csv(15))
+    await service.import_transactions("csv", account_id, source_options(pending_path))
+
+    statement_path = tmp_path / "statement.csv"
+    statement_path.write_text(coffee_csv(15, 16))
+    result = await service.import_transactions("csv", account_id, source_options(statement_path))
+
+    assert result.data["imported"] == 1
+    assert result.data["skipped"] == 1
+    assert result.data["skipped_fuzzy"] == 0
+
+
+@pytest.mark.asyncio
+async def test_fuzzy_dedup_is_off_by_default(repository, account_id, tmp_path, monkeypatch):
+    """Test that without the setting only exact dates are deduplicated."""
+    monkeypatch.setenv("TREELINE_DIR", str(tmp_path))
+    service = ImportService(repository, {"csv": CSVProvider()})
+    pending_path = tmp_path / "pending.csv"
+    pending_path.write_text(coffee_csv(15))
+    await service.import_transactions("csv", account_id, source_options(pending_path))
+
+    statement_path = tmp_path / "statement.csv"
+    statement_path.write_text(coffee_csv(14))
+    result = await service.import_transactions("csv", account_id, source_options(statement_path))
+
+    assert result.data["imported"] == 1
+    assert result.data["skipped_fuzzy"] == 0
+
+
+# Starts of files that turn up renamed to .csv
+NON_CSV_FILES = {
+    "xlsx": b"PK\x03\x04\x14\x00\x06\x00\x08\x00\x00\x00!\x00[Content_Types].xml",
+    "pdf": b"%PDF-1.7\n%\xe2\xe3\xcf\xd3\n1 0 obj\n<< /Type /Catalog >>\n",
+    "html": b"\n  <!DOCTYPE html>\n<html><head><title>Sign in</title></head></html>\n",
+}
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize("detected_type", sorted(NON_CSV_FILES))
+async def test_non_csv_files_are_named_before_parsing(
+    repository, account_id, tmp_path, monkeypatch, detected_type
+):
+    """Test that preview and import both refuse an XLSX, PDF or HTML file saved as .csv."""
+    monkeypatch.setenv("TREELINE_DIR", str(tmp_path))
+    csv_path = tmp_path / "statement.csv"
+    csv_path.write_bytes(NON_CSV_FILES[detected_type])
+    service = ImportService(repository, {"csv": CSVProvider()})
+
+    preview = await service.preview_csv_import(
+        str(csv_path), source_options(csv_path)["column_mapping"]
+    )
+    imported = await service.import_transactions("csv", account_id, source_options(csv_path))
+
+    for result in (preview, imported):
+        assert not result.success
+        assert result.context == {"kind": "wrong_file_type", "detected_type": detected_type}
+        assert result.error.startswith("statement.csv is ")
+        assert "not a CSV file" in result.error
+    assert (await service.list_imports()).data == []
+
+
+@pytest.mark.asyncio
+async def test_import_file_size_and_existence_are_checked(tmp_path, monkeypatch, repository):
+    """Test the size cap from import.max_file_mb and the missing-file kind."""
+    monkeypatch.setenv("TREELINE_DIR", str(tmp_path))
+    (tmp_path / "settings.json").write_text(json.dumps({"import": {"max_file_mb": 0.0001}}))
+    csv_path = tmp_path / "statement.csv"
+    csv_path.write_text(CSV_CONTENT * 2)
+    service = ImportService(repository, {"csv": CSVProvider()})
+
+    too_large = service.check_import_file(str(csv_path))
+    missing = service.check_import_file(str(tmp_path / "missing.csv"))
+
+    assert too_large.context["kind"] == "file_too_large"
+    assert too_large.context["size_bytes"] == csv_path.stat().st_size
+    assert "Split it into smaller files" in too_large.error
+    assert missing.context == {"kind": "file_not_found"}
+    csv_path.write_text(CSV_CONTENT)
+    assert service.check_import_file(str(csv_path)).success
+
+
+@pytest.mark.asyncio
+async def test_import_accepts_file_urls_with_spaces_and_unicode(repository, account_id, tmp_path):
+    """Test the file:// form some dialogs return, percent-encoded, with a trailing newline."""
+    csv_path = tmp_path / "Relevé de compte 2024.csv"
+    csv_path.write_text(CSV_CONTENT)
+    service = ImportService(repository, {"csv": CSVProvider()})
+    options = source_options(csv_path)
+    options["file_path"] = csv_path.as_uri() + "\n"
+
+    result = await service.import_transactions("csv", account_id, options)
+
+    assert result.success, result.error
+    assert result.data["imported"] == 2
+
+
+MIXED_CURRENCY_CSV = """Date,Description,Amount,Currency
+2024-10-01,Coffee,-5.50,USD
+2024-10-02,Poutine,-12.00,CAD
+2024-10-03,Refund,3.00,
+"""
+
+
+def currency_options(csv_path: Path, foreign_currency: str) -> dict:
+    options = source_options(csv_path)
+    options["column_mapping"]["currency"] = "Currency"
+    options["foreign_currency"] = foreign_currency
+    return options
+
+
+@pytest.fixture
+def exchange_rates(tmp_path, monkeypatch):
+    """Set a USD/CAD rate in a throwaway settings file (used inverted for CAD/USD)."""
+    monkeypatch.setenv("TREELINE_DIR", str(tmp_path))
+    (tmp_path / "settings.json").write_text(json.dumps({"exchange_rates": {"USD/CAD": "1.25"}}))
+
+
+@pytest.mark.asyncio
+async def test_foreign_currency_rows_are_skipped_by_default(
+    repository, account_id, tmp_path, exchange_rates
+):
+    """Test that a CAD row in a USD account is left out with a reason."""
+    csv_path = tmp_path / "brokerage.csv"
+    csv_path.write_text(MIXED_CURRENCY_CSV)
+    options = currency_options(csv_path, "skip")
+    del options["foreign_currency"]
+    service = ImportService(repository, {"csv": CSVProvider()})
+
+    result = await service.import_transactions("csv", account_id, options)
+
+    assert result.success, result.error
+    assert result.data["imported"] == 2
+    assert result.data["foreign_currency"] == {
+        "mode": "skip", "matched": 2, "skipped": 1, "kept": 0, "converted": 0
+    }
+    (skipped,) = result.data["currency_skipped"]
+    assert (skipped["line"], skipped["currency"]) == (3, "CAD")
+    assert skipped["reason"] == "CAD row in a USD account"
+    for tx in result.data["imported_transactions"]:
+        assert "currency" not in tx.external_ids
+        assert tx.tags == ()
+
+
+@pytest.mark.asyncio
+async def test_foreign_currency_rows_can_be_kept_with_an_fx_tag(
+    repository, account_id, tmp_path, exchange_rates
+):
+    """Test that keep imports the CAD amount unchanged, tagged and with its original recorded."""
+    csv_path = tmp_path / "brokerage.csv"
+    csv_path.write_text(MIXED_CURRENCY_CSV)
+    service = ImportService(repository, {"csv": CSVProvider()})
+
+    result = await service.import_transactions(
+        "csv", account_id, currency_options(csv_path, "keep")
+    )
+
+    assert result.data["imported"] == 3
+    assert result.data["foreign_currency"]["kept"] == 1
+    (kept,) = [tx for tx in result.data["imported_transactions"] if tx.tags]
+    assert kept.amount == Decimal("-12.00")
+    assert kept.tags == ("fx:cad",)
+    assert json.loads(kept.external_ids["original_amount"]) == {
+        "amount": "-12.00", "currency": "CAD"
+    }
+
+
+@pytest.mark.asyncio
+async def test_foreign_currency_rows_can_be_converted_with_the_rate_table(
+    repository, account_id, tmp_path, exchange_rates
+):
+    """Test that convert uses the configured rate (inverted here) and keeps the original."""
+    csv_path = tmp_path / "brokerage.csv"
+    csv_path.write_text(MIXED_CURRENCY_CSV)
+    service = ImportService(repository, {"csv": CSVProvider()})
+
+    result = await service.import_transactions(
+        "csv", account_id, currency_options(csv_path, "convert")
+    )
+
+    assert result.data["imported"] == 3
+    assert result.data["foreign_currency"]["converted"] == 1
+    (converted,) = [tx for tx in result.data["imported_transactions"] if tx.tags]
+    # -12.00 CAD / 1.25
+    assert converted.amount == Decimal("-9.60")
+    assert converted.tags == ("fx:cad",)
+    original = json.loads(converted.external_ids["original_amount"])
+    assert (original["amount"], original["currency"]) == ("-12.00", "CAD")
+    assert Decimal(original["rate"]) == Decimal("0.8")
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize("foreign_currency", ["keep", "convert"])
+async def test_unknown_currency_codes_and_missing_rates_are_skipped(
+    repository, account_id, tmp_path, exchange_rates, foreign_currency
+):
+    """Test that a code that isn't one, or a currency without a rate, is never imported blindly."""
+    csv_path = tmp_path / "brokerage.csv"
+    csv_path.write_text(
+        "Date,Description,Amount,Currency\n"
+        "2024-10-01,Coffee,-5.50,usd\n"
+        "2024-10-02,Gift shop,-20.00,CA$\n"
+        "2024-10-03,Croissant,-4.00,EUR\n"
+    )
+    service = ImportService(repository, {"csv": CSVProvider()})
+
+    result = await service.import_transactions(
+        "csv", account_id, currency_options(csv_path, foreign_currency)
+    )
+
+    assert result.success, result.error
+    reasons = {row["currency"]: row["reason"] for row in result.data["currency_skipped"]}
+    assert reasons["CA$"] == "Unknown currency code: CA$"
+    if foreign_currency == "keep":
+        assert result.data["foreign_currency"]["kept"] == 1
+        assert result.data["imported"] == 2
+    else:
+        assert reasons["EUR"] == "No exchange rate for EUR/USD"
+        assert result.data["imported"] == 1
+    assert result.data["foreign_currency"]["matched"] == 1
+
+
+@pytest.mark.asyncio
+async def test_invalid_foreign_currency_mode_fails(repository, account_id, tmp_path):
+    """Test that an unknown --foreign-currency value is refused before reading the file."""
+    csv_path = tmp_path / "brokerage.csv"
+    csv_path.write_text(MIXED_CURRENCY_CSV)
+    service = ImportService(repository, {"csv": CSVProvider()})
+
+    result = await service.import_transactions(
+        "csv", account_id, currency_options(csv_path, "round")
+    )
+
+    assert not result.success
+    assert "Invalid foreign currency mode: round" in result.error
+
+
+@pytest.mark.asyncio
+async def test_generated_statement_skips_exactly_the_seeded_rows(tmp_path, monkeypatch):
+    """Test dedup against a pre-seeded database with the datasets the benchmarks use."""
+    monkeypatch.setenv("TREELINE_DIR", str(tmp_path))
+    (account,) = make_accounts(1)
+    rows = make_transactions(account, 400)
+    repository = await seed_database(tmp_path / "seeded.duckdb", [account])
+    service = ImportService(repository, {"csv": CSVProvider()})
+    for name, batch in (("seed.csv", rows[:150]), ("statement.csv", rows)):
+        options = {
+            "file_path": str(write_csv(tmp_path / name, batch)),
+            "column_mapping": CSV_COLUMN_MAPPING,
+        }
+        result = await service.import_transactions("csv", account.id, options)
+
+    assert make_transactions(account, 400) == rows
+    assert (result.data["imported"], result.data["skipped"]) == (250, 150)
+
+
+def write_history_csv(path: Path) -> Path:
+    """22 rows on lines 2-23; the last two repeat lines 2 and 3."""
+    lines = ["Date,Description,Amount"]
+    lines += [f"2024-01-{day:02d},Store {day},-{day}.00" for day in range(1, 21)]
+    lines += ["2024-01-01,Store 1,-1.00", "2024-01-02,Store 2,-2.00"]
+    path.write_text("\n".join(lines) + "\n")
+    return path
+
+
+async def import_in_chunks(repository, account_id, csv_path: Path, **kwargs):
+    service = ImportService(repository, {"csv": CSVProvider()})
+    return await service.import_transactions(
+        "csv", account_id, source_options(csv_path), chunk_size=4, **kwargs
+    )
+
+
+async def transaction_count(repository) -> int:
+    result = await repository.execute_query("SELECT COUNT(*) FROM transactions")
+    return result.data["rows"][0][0]
+
+
+@pytest.mark.asyncio
+async def test_interrupted_import_resumes_to_the_same_rows(
+    repository, account_id, tmp_path, monkeypatch
+):
+    """Test that stopping after two chunks and resuming matches an uninterrupted import."""
+    monkeypatch.setenv("TREELINE_DIR", str(tmp_path))
+    csv_path = write_history_csv(tmp_path / "history.csv")
+    seed_path = tmp_path / "seed.csv"
+    seed_path.write_text("Date,Description,Amount\n2024-01-05,Store 5,-5.00\n")
+
+    reference = DuckDBRepository(str(tmp_path / "reference.duckdb"))
+    await reference.ensure_schema_upgraded()
+    await reference.add_account(make_account(id=account_id))
+    for repo in (repository, reference):
+        assert (await import_in_chunks(repo, account_id, seed_path)).success
+    uninterrupted = await import_in_chunks(reference, account_id, csv_path)
+    assert uninterrupted.data["imported"] == 21
+    assert uninterrupted.data["skipped"] == 1
+
+    checks = []
+
+    def stop_after_two_chunks() -> bool:
+        checks.append(True)
+        return len(checks) >= 2
+
+    first = await import_in_chunks(
+        repository, account_id, csv_path, should_stop=stop_after_two_chunks
+    )
+
+    assert first.success, first.error
+    assert first.data["interrupted"]
+    assert first.data["imported"] == 8
+    assert first.data["last_line"] == 10
+    checkpoint_path = import_checkpoint_path(first.data["import_id"])
+    assert json.loads(checkpoint_path.read_text())["last_line"] == 10
+
+    rerun = await import_in_chunks(repository, account_id, csv_path)
+    assert not rerun.success
+    assert rerun.context["kind"] == "interrupted_import"
+
+    resumed = await import_in_chunks(
+        repository, account_id, csv_path, resume=str(first.data["import_id"])[:8]
+    )
+
+    assert resumed.success, resumed.error
+    assert not resumed.data["interrupted"]
+    assert resumed.data["resumed_from_line"] == 10
+    assert resumed.data["imported"] == uninterrupted.data["imported"]
+    assert resumed.data["skipped"] == uninterrupted.data["skipped"]
+    assert len(resumed.data["imported_transactions"]) == 13
+    assert not checkpoint_path.exists()
+    assert await transaction_count(repository) == await transaction_count(reference) == 22
+    records = {r.id: r for r in (await repository.list_imports()).data}
+    assert records[first.data["import_id"]].imported == 21
+
+
+@pytest.mark.asyncio
+async def test_restart_undoes_the_interrupted_batch(repository, account_id, tmp_path, monkeypatch):
+    """Test that --restart deletes the partial batch before importing from the start."""
+    monkeypatch.setenv("TREELINE_DIR", str(tmp_path))
+    csv_path = write_history_csv(tmp_path / "history.csv")
+
+    first = await import_in_chunks(repository, account_id, csv_path, should_stop=lambda: True)
+    assert first.data["imported"] == 4
+
+    unknown = await import_in_chunks(repository, account_id, csv_path, resume="ffffffff")
+    assert unknown.context["kind"] == "no_checkpoint"
+
+    restarted = await import_in_chunks(repository, account_id, csv_path, restart=True)
+
+    assert restarted.success, restarted.error
+    assert restarted.data["restarted"] == {"import_id": str(first.data["import_id"]), "removed": 4}
+    assert restarted.data["imported"] == 22
+    assert await transaction_count(repository) == 22
+    assert [r.id for r in (await repository.list_imports()).data] == [restarted.data["import_id"]]
+    assert not import_checkpoint_path(first.data["import_id"]).exists()
+
+
+@pytest.mark.asyncio
+async def test_amount_too_large_to_store_rejects_the_whole_file(repository, account_id):
+    """Test that a 17-digit amount fails naming its line instead of being truncated."""
+    csv_path = Path(__file__).parents[2] / "fixtures" / "oversized_amount.csv"
+    service = ImportService(repository, {"csv": CSVProvider()})
+
+    result = await service.import_transactions("csv", account_id, source_options(csv_path))
+
+    assert not result.success
+    assert result.context["kind"] == "amount_out_of_range"
+    assert "line 3" in result.error
+    assert [(r["line"], r["amount"]) for r in result.context["rows"]] == [
+        (3, Decimal("-12345678901234567.00"))
+    ]
+    assert (await service.list_imports()).data == []
+    stored = await repository.execute_query("SELECT COUNT(*) FROM sys_transactions")
+    assert stored.data["rows"][0][0] == 0
@@ -0,0 +1,170 @@
+"""Unit tests for MaintenanceService."""
+
+from datetime import date
+from uuid import uuid4
+
+import pytest
+import pytest_asyncio
+
+from treeline.app.maintenance_service import MaintenanceService
+
+from tests.support.factories import make_transaction
+
+
+@pytest_asyncio.fixture
+async def repository(repository, account_id):
+    """A database with two identical same-day purchases."""
+    await repository.bulk_upsert_transactions(
+        [
+            make_transaction(account_id, "-4.50", date(2025, 1, 6), description="Coffee")
+            for _ in range(2)
+        ]
+    )
+    return repository
+
+
+@pytest.mark.asyncio
+async def test_refingerprint_dry_run_reports_without_writing(repository):
+    """Test that a dry run reports changes but leaves fingerprints alone."""
+    service = MaintenanceService(repository)
+
+    result = await service.refingerprint(2, dry_run=True)
+
+    assert result.success
+    assert result.data["changed"] == 2
+    assert result.data["collisions"] == []
+
+    transactions = (await repository.get_all_transactions()).data
+    assert all(tx.fingerprint_version == 1 for tx in transactions)
+
+
+@pytest.mark.asyncio
+async def test_refingerprint_round_trip_reports_collisions(repository):
+    """Test that v2 separates identical rows and going back to v1 reports the collision."""
+    service = MaintenanceService(repository)
+
+    result = await service.refingerprint(2)
+    assert result.success
+    transactions = (await repository.get_all_transactions()).data
+    assert all(tx.fingerprint_version == 2 for tx in transactions)
+    assert len({tx.external_ids["fingerprint"] for tx in transactions}) == 2
+
+    result = await service.refingerprint(1, dry_run=True)
+    assert result.success
+    assert len(result.data["collisions"]) == 1
+    assert len(result.data["collisions"][0]["transaction_ids"]) == 2
+
+
+@pytest.mark.asyncio
+async def test_refingerprint_rejects_unknown_version(repository):
+    """Test that an unsupported version fails without touching data."""
+    result = await MaintenanceService(repository).refingerprint(9)
+
+    assert not result.success
+
+
+@pytest.mark.asyncio
+async def test_adopt_unmapped_moves_quarantined_transactions(repository, monkeypatch, tmp_path):
+    """Test that adopting re-points quarantined transactions and clears the quarantine."""
+    monkeypatch.setenv("TREELINE_DIR", str(tmp_path))
+    account = (await repository.get_accounts()).data[0]
+    orphan = make_transaction(
+        uuid4(), "-20.00", date(2025, 1, 7),
+        description="Groceries", external_ids={"simplefin": "TRN-1"},
+    )
+    await repository.add_unmapped_transactions("simplefin", [("ACT-9", orphan)])
+    service = MaintenanceService(repository)
+
+    preview = await service.adopt_unmapped("ACT-9", account.id, dry_run=True)
+    assert preview.data["adopted"] == 1
+    assert len((await repository.get_unmapped_transactions("ACT-9")).data) == 1
+
+    result = await service.adopt_unmapped("ACT-9", account.id)
+
+    assert result.success
+    assert result.data["adopted"] == 1
+    assert (await repository.get_unmapped_transactions()).data == []
+    adopted = (await repository.get_transactions_by_external_ids("simplefin", ["TRN-1"])).data
+    assert len(adopted) == 1
+    assert adopted[0].account_id == account.id
+    assert adopted[0].external_ids["fingerprint"] != orphan.external_ids["fingerprint"]
+
+    # Nothing left to adopt
+    again = await service.adopt_unmapped("ACT-9", account.id)
+    assert not again.success
+
+
+@pytest.mark.asyncio
+async def test_verify_reports_corruption_and_fixes_the_safe_subset(repository):
+    """Test each check against a deliberately corrupted database, then --fix."""
+    service = MaintenanceService(repository)
+    (account,) = (await repository.get_accounts()).data
+    broken_account, split_part = uuid4(), uuid4()
+    for sql in (
+        f"INSERT INTO sys_accounts (account_id, name, external_ids) "
+        f"VALUES ('{broken_account}', 'Broken', '[1]')",
+        f"INSERT INTO sys_transactions (transaction_id, account_id, amount, transaction_date, "
+        f"posted_date, tags, parent_transaction_id) VALUES ('{split_part}', '{account.id}', -1, "
+        f"'2025-01-07', '2025-01-07', ['food', NULL], '{split_part}')",
+        "DELETE FROM sys_migrations WHERE migration_name = '016_attachments.sql'",
+    ):
+        assert (await repository.execute_write_query(sql)).success
+
+    result = await service.verify()
+
+    assert result.success, result.error
+    assert result.data["passed"] is False
+    checks = {check["name"]: check for check in result.data["checks"]}
+    assert checks["transaction_accounts"]["passed"]
+    assert checks["snapshot_accounts"]["passed"]
+    # The fixture's two identical purchases share a v1 fingerprint
+    assert checks["fingerprints"]["count"] == 2
+    assert checks["json_columns"]["count"] == 2
+    assert sorted(checks["json_columns"]["ids"]) == sorted([str(broken_account), str(split_part)])
+    assert checks["parent_ids"]["ids"] == [str(split_part)]
+    assert checks["migrations"]["ids"] == ["016_attachments.sql"]
+    assert result.data["fixes"] == {}
+
+    fixed = await service.verify(fix=True)
+
+    assert fixed.data["fixes"] == {"json_columns": 2, "parent_ids": 1}
+    checks = {check["name"]: check for check in fixed.data["checks"]}
+    assert checks["json_columns"]["passed"] and checks["parent_ids"]["passed"]
+    assert not checks["fingerprints"]["passed"] and not checks["migrations"]["passed"]
+    rows = (
+        await repository.execute_query(
+            f"SELECT tags, parent_transaction_id FROM sys_transactions "
+            f"WHERE transaction_id = '{split_part}'"
+        )
+    ).data["rows"]
+    assert rows == [(["food"], None)]
+
+
+@pytest.mark.asyncio
+async def test_apply_default_tags_backfills_and_keeps_existing_tags(repository):
+    """Test that stored transactions gain the default tags next to rule-applied ones."""
+    account = (await repository.get_accounts()).data[0]
+    first, second = (await repository.get_transactions_by_account(account.id)).data
+    # As the auto-tag rules would leave them
+    await repository.bulk_update_transaction_tags(
+        {first.id: ["coffee", "business"], second.id: ["coffee"]}
+    )
+    service = MaintenanceService(repository)
+
+    no_tags = await service.apply_default_tags(account.id)
+    assert not no_tags.success
+
+    business = account.model_copy(update={"default_tags": ("business",)})
+    await repository.update_account_by_id(business)
+    preview = await service.apply_default_tags(account.id, dry_run=True)
+    assert preview.data["updated"] == 1
+    assert preview.data["unchanged"] == 1
+
+    result = await service.apply_default_tags(account.id)
+
+    assert result.success
+    assert result.data["updated"] == 1
+    stored = (await repository.get_transactions_by_account(account.id)).data
+    tags = {tx.id: tx.tags for tx in stored}
+    assert tags == {first.id: ("coffee", "business"), second.id: ("coffee", "business")}
+    assert (await service.apply_default_tags(account.id)).data["updated"] == 0
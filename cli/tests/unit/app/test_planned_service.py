@@ -0,0 +1,195 @@
+"""Unit tests for PlannedService and the planned-transaction schedule and matching."""
+
+from datetime import date, datetime, timezone
+from decimal import Decimal
+from uuid import uuid4
+
+import pytest
+import pytest_asyncio
+
+from treeline.app.account_service import AccountService
+from treeline.app.planned_service import (
+    PlannedService,
+    advance_due,
+    first_due_on_or_after,
+    matches_planned,
+)
+from treeline.domain import BalanceSnapshot, PlannedFrequency, PlannedTransaction
+
+from tests.support.factories import make_account, make_transaction
+
+
+def _planned(**fields) -> PlannedTransaction:
+    now = datetime.now(timezone.utc)
+    defaults = {
+        "id": uuid4(),
+        "account_id": uuid4(),
+        "description": "Rent",
+        "amount": Decimal("-1800.00"),
+        "frequency": PlannedFrequency.MONTHLY,
+        "day_of_month": 1,
+        "next_due": date(2024, 3, 1),
+        "created_at": now,
+        "updated_at": now,
+    }
+    return PlannedTransaction(**{**defaults, **fields})
+
+
+def test_monthly_schedule_keeps_its_day_across_short_months():
+    """Test that an item on the 31st falls on each month's last day and returns to the 31st."""
+    due = date(2024, 1, 31)
+    dues = []
+    for _ in range(4):
+        due = advance_due(due, PlannedFrequency.MONTHLY, 31)
+        dues.append(due)
+
+    assert dues == [date(2024, 2, 29), date(2024, 3, 31), date(2024, 4, 30), date(2024, 5, 31)]
+    assert advance_due(date(2024, 11, 30), PlannedFrequency.QUARTERLY, 30) == date(2025, 2, 28)
+    assert advance_due(date(2024, 2, 29), PlannedFrequency.YEARLY, 29) == date(2025, 2, 28)
+    assert advance_due(date(2024, 12, 28), PlannedFrequency.WEEKLY) == date(2025, 1, 4)
+    assert advance_due(date(2024, 12, 28), PlannedFrequency.BIWEEKLY) == date(2025, 1, 11)
+    assert advance_due(date(2024, 12, 28), PlannedFrequency.ONCE) is None
+    assert first_due_on_or_after(date(2024, 2, 15), 31) == date(2024, 2, 29)
+    assert first_due_on_or_after(date(2024, 1, 31), 1) == date(2024, 2, 1)
+
+
+def test_matching_checks_amount_date_and_description():
+    """Test that a match needs the amount within 5%, the date within 3 days and a similar payee."""
+    planned = _planned()
+    due = planned.next_due
+
+    def tx(amount="-1800.00", description="ACH RENT PAYMENT OAKWOOD", on=due):
+        return make_transaction(planned.account_id, amount, on, description=description)
+
+    assert matches_planned(planned, due, tx())
+    assert matches_planned(planned, due, tx(amount="-1890.00", on=date(2024, 3, 4)))
+    assert matches_planned(planned, due, tx(description="rent pymt"))
+    assert not matches_planned(planned, due, tx(amount="-1891.00"))
+    assert not matches_planned(planned, due, tx(amount="1800.00"))
+    assert not matches_planned(planned, due, tx(on=date(2024, 2, 25)))
+    assert not matches_planned(planned, due, tx(description="Whole Foods"))
+    other_account = make_transaction(uuid4(), "-1800.00", due, description="Rent")
+    assert not matches_planned(planned, due, other_account)
+
+
+@pytest_asyncio.fixture
+async def workspace(repository):
+    """Fresh database with a checking account holding 2,000.00 on 2024-03-01."""
+    now = datetime.now(timezone.utc)
+    account = make_account(account_type="depository")
+    await repository.add_account(account)
+    await repository.add_balance(
+        BalanceSnapshot(
+            id=uuid4(),
+            account_id=account.id,
+            balance=Decimal("2000.00"),
+            snapshot_time=datetime(2024, 3, 1, 12, 0),
+            created_at=now,
+            updated_at=now,
+        )
+    )
+    return PlannedService(repository, AccountService(repository)), account
+
+
+@pytest.mark.asyncio
+async def test_satisfy_advances_next_due_and_removes_one_off_items(workspace):
+    """Test that synced matches advance recurring items, catch up, and finish one-off items."""
+    service, account = workspace
+    rent = (
+        await service.upsert("Checking", "Rent", Decimal("-1800"), "monthly", date(2024, 1, 31))
+    ).data
+    gym = (
+        await service.upsert("checking", "Gym", Decimal("-40"), "once", date(2024, 2, 10))
+    ).data
+    assert rent.day_of_month == 31
+    assert gym.day_of_month is None
+
+    january = make_transaction(account.id, "-1800.00", date(2024, 1, 31), description="RENT OAKWOOD")
+    february = make_transaction(account.id, "-1800.00", date(2024, 3, 1), description="RENT OAKWOOD")
+    gym_fee = make_transaction(account.id, "-39.00", date(2024, 2, 12), description="GYM MEMBERSHIP")
+
+    preview = await service.satisfy([january, february, gym_fee], dry_run=True)
+    assert len(preview.data) == 3
+    assert [p.next_due for p in (await service.list_planned()).data] == [
+        date(2024, 1, 31),
+        date(2024, 2, 10),
+    ]
+
+    result = await service.satisfy([january, february, gym_fee])
+
+    assert result.success, result.error
+    assert [(e["description"], e["due"], e["next_due"]) for e in result.data] == [
+        ("Rent", date(2024, 1, 31), date(2024, 2, 29)),
+        ("Rent", date(2024, 2, 29), date(2024, 3, 31)),
+        ("Gym", date(2024, 2, 10), None),
+    ]
+    (remaining,) = (await service.list_planned()).data
+    assert remaining.id == rent.id
+    assert remaining.next_due == date(2024, 3, 31)
+    assert remaining.last_transaction_id == february.id
+
+    # February's payment is too early to count toward the March 31 occurrence
+    assert (await service.satisfy([february])).data == []
+
+
+@pytest.mark.asyncio
+async def test_upcoming_projects_balances_and_flags_negatives(workspace):
+    """Test that upcoming walks occurrences in order from today's balance."""
+    service, _ = workspace
+    await service.upsert(
+        "Checking", "Rent", Decimal("-1800"), "monthly", day_of_month=1, today=date(2024, 3, 2)
+    )
+    await service.upsert("Checking", "Payroll", Decimal("1500"), "biweekly", date(2024, 3, 8))
+    await service.upsert("Checking", "Phone", Decimal("-90"), "monthly", date(2024, 2, 28))
+
+    result = await service.upcoming(days=30, today=date(2024, 3, 2))
+
+    assert result.success, result.error
+    items = [(i["description"], i["due"], i["projected_balance"]) for i in result.data["items"]]
+    assert items == [
+        ("Phone", date(2024, 2, 28), Decimal("1910.00")),
+        ("Payroll", date(2024, 3, 8), Decimal("3410.00")),
+        ("Payroll", date(2024, 3, 22), Decimal("4910.00")),
+        ("Phone", date(2024, 3, 28), Decimal("4820.00")),
+        ("Rent", date(2024, 4, 1), Decimal("3020.00")),
+    ]
+    assert result.data["items"][0]["overdue"]
+    assert not any(i["projected_negative"] for i in result.data["items"])
+
+    await service.upsert("Checking", "Car insurance", Decimal("-3500"), "yearly", date(2024, 3, 5))
+    items = (await service.upcoming(days=30, today=date(2024, 3, 2))).data["items"]
+    flagged = [(i["description"], i["projected_balance"]) for i in items if i["projected_negative"]]
+    assert flagged == [("Car insurance", Decimal("-1590.00")), ("Payroll", Decimal("-90.00"))]
+
+
+@pytest.mark.asyncio
+async def test_upsert_validates_and_replaces_by_id(workspace):
+    """Test that bad input is rejected and --id replaces an item in place."""
+    service, _ = workspace
+
+    assert "Invalid frequency" in (
+        await service.upsert("Checking", "Rent", Decimal("-1800"), "fortnightly", date(2024, 3, 1))
+    ).error
+    zero = await service.upsert("Checking", "Rent", Decimal("0"), "monthly", date(2024, 3, 1))
+    assert not zero.success
+    unknown = await service.upsert("Savings", "Rent", Decimal("-1"), "monthly", date(2024, 3, 1))
+    assert not unknown.success
+    no_due = await service.upsert("Checking", "Rent", Decimal("-1"), "weekly", day_of_month=1)
+    assert not no_due.success
+
+    rent = (
+        await service.upsert("Checking", "Rent", Decimal("-1800"), "monthly", date(2024, 3, 1))
+    ).data
+    updated = await service.upsert(
+        "Checking", "Rent", Decimal("-1850"), "monthly", date(2024, 4, 1), planned_id=str(rent.id)[:8]
+    )
+
+    assert updated.success, updated.error
+    (stored,) = (await service.list_planned()).data
+    assert stored.id == rent.id
+    assert stored.amount == Decimal("-1850.00")
+    assert stored.next_due == date(2024, 4, 1)
+
+    removed = await service.remove(str(rent.id)[:8])
+    assert removed.data.id == rent.id
+    assert (await service.list_planned()).data == []
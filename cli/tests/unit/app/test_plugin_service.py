@@ -0,0 +1,66 @@
+"""Unit tests for PluginService release lookups through the injected HTTP client."""
+
+from treeline.app.plugin_service import HTTPX_MISSING, PluginService
+
+RELEASE = {
+    "tag_name": "v1.2.0",
+    "assets": [
+        {"name": "manifest.json", "browser_download_url": "https://example.com/manifest.json"}
+    ],
+}
+MANIFEST = {"id": "budget-plus", "version": "1.2.0"}
+
+
+class FakeResponse:
+    def __init__(self, data):
+        self.status_code = 200
+        self._data = data
+
+    def raise_for_status(self):
+        pass
+
+    def json(self):
+        return self._data
+
+
+class FakeClient:
+    """Answers the GitHub release API and asset URLs without a network."""
+
+    def __init__(self, requested):
+        self.requested = requested
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, *exc):
+        return False
+
+    def get(self, url, headers=None):
+        self.requested.append(url)
+        return FakeResponse(MANIFEST if url.endswith("manifest.json") else RELEASE)
+
+
+def test_fetch_manifest_uses_the_injected_client(tmp_path):
+    """Test that releases are fetched through the factory the container provides."""
+    requested = []
+    service = PluginService(tmp_path, lambda timeout, **options: FakeClient(requested))
+
+    result = service.fetch_manifest("https://github.com/acme/budget-plus")
+
+    assert result.success, result.error
+    assert result.data == {
+        "manifest": MANIFEST,
+        "version": "v1.2.0",
+        "source": "github:acme/budget-plus",
+    }
+    assert requested == [
+        "https://api.github.com/repos/acme/budget-plus/releases/latest",
+        "https://example.com/manifest.json",
+    ]
+
+
+def test_fetch_manifest_without_a_client_factory(tmp_path):
+    """Test that a build without httpx explains why GitHub installs are unavailable."""
+    result = PluginService(tmp_path).fetch_manifest("https://github.com/acme/budget-plus")
+
+    assert result.error == HTTPX_MISSING
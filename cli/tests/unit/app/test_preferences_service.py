@@ -0,0 +1,43 @@
+"""Unit tests for locale-aware amount and date formatting."""
+
+from datetime import date
+from decimal import Decimal
+
+from treeline.app.preferences_service import format_currency, format_date, format_number
+from treeline.locales import resolve_locale
+
+
+def test_format_en_us():
+    """Test US separators, prefix symbol and month-first dates."""
+    assert format_number(Decimal("1234567.89"), locale="en-US") == "1,234,567.89"
+    assert format_currency(Decimal("1234567.89"), "USD", locale="en-US") == "$1,234,567.89"
+    assert format_currency(Decimal("-42.5"), "USD", locale="en-US") == "-$42.50"
+    assert format_date(date(2024, 3, 31), locale="en-US") == "03/31/2024"
+
+
+def test_format_de_de():
+    """Test German separators, trailing symbol and day-first dates."""
+    assert format_number(Decimal("1234567.89"), locale="de-DE") == "1.234.567,89"
+    assert format_currency(Decimal("1234567.89"), "EUR", locale="de-DE") == "1.234.567,89 €"
+    assert format_currency(Decimal("-42.5"), "EUR", locale="de-DE") == "-42,50 €"
+    assert format_date(date(2024, 3, 31), locale="de-DE") == "31.03.2024"
+
+
+def test_format_fr_uses_comma_decimal():
+    """Test that French amounts use comma decimals."""
+    assert format_number(Decimal("1234567.89"), locale="fr-FR") == "1.234.567,89"
+
+
+def test_unknown_locale_falls_back_to_en_us():
+    """Test that an unsupported locale formats like en-US."""
+    assert resolve_locale("xx-YY") == "en-US"
+    assert resolve_locale("C") == "en-US"
+    assert resolve_locale(None) == "en-US"
+    assert format_currency(Decimal("1234.5"), "USD", locale="xx-YY") == "$1,234.50"
+    assert format_date(date(2024, 3, 31), locale="xx-YY") == "03/31/2024"
+
+
+def test_resolve_posix_locale_names():
+    """Test that LC_ALL-style names are normalized."""
+    assert resolve_locale("de_DE.UTF-8") == "de-DE"
+    assert resolve_locale("en_GB") == "en-GB"
@@ -0,0 +1,554 @@
+"""Unit tests for ReportService and the shared Classifier."""
+
+import csv
+import io
+import json
+from datetime import date, datetime, timezone
+from decimal import Decimal
+from uuid import uuid4
+
+import pytest
+
+from treeline.app.classifier import EXPENSE, INCOME, TRANSFER, Classifier
+from treeline.app.report_csv import render_register_csv
+from treeline.app.report_html import render_monthly_html
+from treeline.app.report_service import ReportService, _comparison, merchant_key
+from treeline.app.transaction_service import TransactionService
+from treeline.smoothing import Smoothing
+from treeline.commands.report import json_serializer
+from treeline.commands.report_markdown import render_digest_markdown
+from treeline.domain import TransactionStatus
+from treeline.infra.demo import DemoDataProvider
+
+from tests.support.factories import make_account, make_snapshot, make_transaction
+
+
+def test_classifier_rules():
+    """Test income, expense and transfer classification."""
+    classifier = Classifier()
+
+    def tx(amount, *tags):
+        return make_transaction(uuid4(), amount, tags=tags)
+
+    assert classifier.classify(tx("3000"), "checking") == INCOME
+    assert classifier.classify(tx("-50"), "checking") == EXPENSE
+    assert classifier.classify(tx("-750", "transfer"), "checking") == TRANSFER
+    assert classifier.classify(tx("2500"), "credit") == TRANSFER
+    assert classifier.classify(tx("-900", "Payment"), "checking") == TRANSFER
+
+
+def test_classifier_expense_category_uses_top_level_tag():
+    """Test that expenses group by the first tag's top-level part."""
+    classifier = Classifier()
+    coffee = make_transaction(uuid4(), "-5", tags=["food:coffee", "work"])
+
+    assert classifier.expense_category(coffee) == "food"
+    assert classifier.expense_category(make_transaction(uuid4(), "-5")) == "uncategorized"
+
+
+def test_account_averages_use_months_open():
+    """Test that an account opened mid-period averages over its open months only."""
+    checking = make_account("Checking", account_type="checking")
+    card = make_account("Credit", account_type="credit", opened_at=date(2024, 3, 10))
+    expenses = {checking.id: Decimal("600.00"), card.id: Decimal("400.00")}
+    months = ["2024-01", "2024-02", "2024-03", "2024-04", "2024-05", "2024-06"]
+
+    entries = ReportService._account_averages(
+        [checking, card], expenses, months, date(2024, 1, 1), date(2024, 6, 30)
+    )
+
+    assert [(e["account"], e["months_open"], e["average_monthly_expenses"]) for e in entries] == [
+        ("Checking", 6, Decimal("100.00")),
+        ("Credit", 4, Decimal("100.00")),
+    ]
+
+
+@pytest.mark.asyncio
+async def test_cashflow_statement(repository):
+    """Test monthly totals, transfer exclusion and split handling."""
+    checking = make_account("Checking", account_type="checking")
+    credit = make_account("Credit", account_type="credit")
+    for account in (checking, credit):
+        await repository.add_account(account)
+
+    parent = make_transaction(credit.id, "-100", tags=["shopping"])
+    await repository.bulk_upsert_transactions(
+        [
+            make_transaction(checking.id, "4000", tags=["income"]),
+            make_transaction(checking.id, "-1500", tags=["housing"]),
+            make_transaction(checking.id, "-1000", tags=["payment"]),
+            make_transaction(credit.id, "1000"),
+            parent,
+            make_transaction(credit.id, "-60", tags=["groceries"], parent_transaction_id=parent.id),
+            make_transaction(credit.id, "-40", tags=["household"], parent_transaction_id=parent.id),
+            make_transaction(checking.id, "-200", tags=["housing"], day=date(2024, 8, 2)),
+        ]
+    )
+
+    result = await ReportService(repository).cashflow(date(2024, 7, 1), date(2024, 8, 31))
+
+    assert result.success
+    july, august = result.data["months"]
+    assert july["month"] == "2024-07"
+    assert july["income"] == Decimal("4000")
+    assert july["expenses"] == {
+        "groceries": Decimal("60"),
+        "household": Decimal("40"),
+        "housing": Decimal("1500"),
+    }
+    assert july["net_savings"] == Decimal("2400")
+    assert july["savings_rate"] == Decimal("60.0")
+
+    assert august["income"] == Decimal("0")
+    assert august["savings_rate"] is None
+
+    totals = result.data["totals"]
+    assert totals["total_expenses"] == Decimal("1800")
+    assert totals["net_savings"] == Decimal("2200")
+
+
+@pytest.mark.asyncio
+async def test_spending_by_tag_counts_every_tag_and_skips_transfers(repository):
+    """Test that each tag of an expense counts, and payments and split parents don't."""
+    checking = make_account("Checking", account_type="checking")
+    credit = make_account("Credit", account_type="credit")
+    for account in (checking, credit):
+        await repository.add_account(account)
+
+    parent = make_transaction(credit.id, "-100", tags=["shopping"])
+    await repository.bulk_upsert_transactions(
+        [
+            make_transaction(checking.id, "4000", tags=["income"]),
+            make_transaction(checking.id, "-1000", tags=["payment"]),
+            make_transaction(credit.id, "1000"),
+            make_transaction(checking.id, "-30.25"),
+            parent,
+            make_transaction(credit.id, "-60", tags=["groceries", "household"], parent_transaction_id=parent.id),
+            make_transaction(credit.id, "-40", tags=["household"], parent_transaction_id=parent.id),
+        ]
+    )
+    service = ReportService(repository)
+
+    result = await service.spending_by_tag(date(2024, 7, 1), date(2024, 7, 31))
+
+    assert result.success, result.error
+    assert [(e["tag"], e["total"], e["transaction_count"]) for e in result.data["tags"]] == [
+        ("household", Decimal("100"), 2),
+        ("groceries", Decimal("60"), 1),
+        (None, Decimal("30.25"), 1),
+    ]
+    assert {e["currency"] for e in result.data["tags"]} == {"USD"}
+
+    checking_only = await service.spending_by_tag(
+        date(2024, 7, 1), date(2024, 7, 31), account_id=checking.id
+    )
+    assert [(e["tag"], e["total"]) for e in checking_only.data["tags"]] == [(None, Decimal("30.25"))]
+
+
+@pytest.mark.asyncio
+async def test_totals_unchanged_by_splitting(repository):
+    """Test that splitting a transaction (and restoring its parent) leaves every total alone."""
+    checking = make_account("Checking", account_type="checking")
+    await repository.add_account(checking)
+    purchase = make_transaction(checking.id, "-100", tags=["shopping"])
+    await repository.bulk_upsert_transactions(
+        [make_transaction(checking.id, "-25", tags=["food"]), purchase]
+    )
+    service = ReportService(repository)
+
+    async def totals():
+        cashflow = await service.cashflow(date(2024, 7, 1), date(2024, 7, 31))
+        merchants = await service.merchants(date(2024, 7, 1), date(2024, 7, 31))
+        view = await repository.execute_query("SELECT SUM(amount) FROM transactions")
+        return (
+            cashflow.data["totals"]["total_expenses"],
+            merchants.data["total_expenses"],
+            Decimal(str(view.data["rows"][0][0])),
+        )
+
+    before = await totals()
+
+    # Split the way the app does: soft-delete the parent, add the parts as children
+    await repository.execute_write_query(
+        f"UPDATE sys_transactions SET deleted_at = now() WHERE transaction_id = '{purchase.id}'"
+    )
+    parts = [
+        make_transaction(checking.id, "-70", tags=["groceries"], parent_transaction_id=purchase.id),
+        make_transaction(checking.id, "-30", tags=["household"], parent_transaction_id=purchase.id),
+    ]
+    await repository.bulk_upsert_transactions(parts)
+
+    assert await totals() == before
+    children = await repository.get_children(purchase.id)
+    assert sorted(tx.id for tx in children.data) == sorted(tx.id for tx in parts)
+
+    # A parent brought back while its parts are live is still counted once
+    await repository.execute_write_query(
+        f"UPDATE sys_transactions SET deleted_at = NULL WHERE transaction_id = '{purchase.id}'"
+    )
+    assert await totals() == before
+
+
+@pytest.mark.asyncio
+async def test_split_children_in_another_period_count_once(repository):
+    """Test that a live split parent is left out even when its parts fall in another period."""
+    checking = make_account("Checking", account_type="checking")
+    await repository.add_account(checking)
+    purchase = make_transaction(checking.id, "-100", day=date(2024, 6, 30))
+    parts = [
+        make_transaction(
+            checking.id, amount, day=date(2024, 7, 2), parent_transaction_id=purchase.id
+        )
+        for amount in ("-70", "-30")
+    ]
+    await repository.bulk_upsert_transactions([purchase, *parts])
+    service = ReportService(repository)
+
+    june = await service.cashflow(date(2024, 6, 1), date(2024, 6, 30))
+    july = await service.cashflow(date(2024, 7, 1), date(2024, 7, 31))
+    assert june.data["totals"]["total_expenses"] == Decimal("0")
+    assert july.data["totals"]["total_expenses"] == Decimal("100")
+
+    register = await service.register(checking.id, date(2024, 6, 1), date(2024, 6, 30))
+    assert register.data["rows"] == []
+    merchants = await service.merchants(date(2024, 6, 1), date(2024, 6, 30))
+    assert merchants.data["total_expenses"] == Decimal("0")
+
+
+@pytest.mark.asyncio
+async def test_cashflow_exclude_pending(repository):
+    """Test that pending transactions can be left out of the statement."""
+    checking = make_account("Checking", account_type="checking")
+    await repository.add_account(checking)
+    await repository.bulk_upsert_transactions(
+        [
+            make_transaction(checking.id, "-100", tags=["food"]),
+            make_transaction(checking.id, "-25", tags=["food"], status=TransactionStatus.PENDING),
+        ]
+    )
+
+    service = ReportService(repository)
+    included = await service.cashflow(date(2024, 7, 1), date(2024, 7, 31))
+    excluded = await service.cashflow(
+        date(2024, 7, 1), date(2024, 7, 31), include_pending=False
+    )
+
+    assert included.data["totals"]["total_expenses"] == Decimal("125")
+    assert excluded.data["totals"]["total_expenses"] == Decimal("100")
+
+
+@pytest.mark.asyncio
+async def test_cashflow_exclude_tag_drops_a_default_tagged_account(repository):
+    """Test that --exclude-tag leaves out transactions tagged by an account's default tags."""
+    personal = make_account("Checking", account_type="checking")
+    business = make_account("Business", account_type="depository", default_tags=("business",))
+    for account in (personal, business):
+        await repository.add_account(account)
+    await repository.bulk_upsert_transactions([make_transaction(personal.id, "-100", tags=["food"])])
+    entered = await TransactionService(repository).create_transaction(
+        business.id, Decimal("-40"), "Printer paper", date(2024, 7, 20)
+    )
+    assert entered.data.tags == ("business",)
+
+    service = ReportService(repository)
+    everything = await service.cashflow(date(2024, 7, 1), date(2024, 7, 31))
+    personal_only = await service.cashflow(
+        date(2024, 7, 1), date(2024, 7, 31), exclude_tags=["Business"]
+    )
+
+    assert everything.data["totals"]["total_expenses"] == Decimal("140")
+    assert personal_only.data["totals"]["total_expenses"] == Decimal("100")
+
+
+async def seed_demo_month(repository) -> dict:
+    """Load the demo accounts and their March 2024 transactions; return accounts by demo ID."""
+    provider = DemoDataProvider()
+    accounts = {a.external_ids["demo"]: a for a in (await provider.get_accounts()).data}
+    for account in accounts.values():
+        await repository.add_account(account)
+
+    demo_result = await provider.get_transactions(
+        datetime(2024, 3, 1, tzinfo=timezone.utc), datetime(2024, 3, 31, 23, 59, tzinfo=timezone.utc)
+    )
+    await repository.bulk_upsert_transactions(
+        [
+            tx.model_copy(update={"account_id": accounts[demo_id].id})
+            for demo_id, tx in demo_result.data
+        ]
+    )
+    return accounts
+
+
+@pytest.mark.asyncio
+async def test_balance_history_smooths_over_days_before_the_range(repository):
+    """Test that the first point's rolling average includes the day before --since."""
+    checking = make_account("Checking", account_type="checking")
+    await repository.add_account(checking)
+    await repository.add_balance(make_snapshot(checking.id, "100", date(2024, 6, 1)))
+    await repository.bulk_upsert_transactions(
+        [make_transaction(checking.id, "-10", day=date(2024, 6, d)) for d in (2, 3, 4)]
+    )
+
+    result = await ReportService(repository).balance_history(
+        checking.id, date(2024, 6, 3), date(2024, 6, 4), smoothing=Smoothing(2, "sma")
+    )
+
+    assert result.success
+    # Balances 90 (June 2), 80, 70: June 3 averages June 2 and 3
+    assert result.data["series"] == [
+        {"date": date(2024, 6, 3), "balance": Decimal("80"), "smoothed": Decimal("85.00")},
+        {"date": date(2024, 6, 4), "balance": Decimal("70"), "smoothed": Decimal("75.00")},
+    ]
+    assert result.data["trend"]["slope_per_month"] == Decimal("-304.37")
+
+
+@pytest.mark.asyncio
+async def test_cashflow_series_by_week_sums_the_daily_net(repository):
+    """Test the optional net flow series: income minus expenses, transfers left out."""
+    checking = make_account("Checking", account_type="checking")
+    await repository.add_account(checking)
+    await repository.bulk_upsert_transactions(
+        [
+            make_transaction(checking.id, "1000", tags=["income"], day=date(2024, 7, 1)),
+            make_transaction(checking.id, "-100", tags=["food"], day=date(2024, 7, 2)),
+            make_transaction(checking.id, "-500", tags=["transfer"], day=date(2024, 7, 3)),
+            make_transaction(checking.id, "-50", tags=["food"], day=date(2024, 7, 8)),
+        ]
+    )
+
+    result = await ReportService(repository).cashflow(
+        date(2024, 7, 1), date(2024, 7, 14), interval="week"
+    )
+
+    assert result.data["smoothing"] is None
+    assert result.data["series"] == [
+        {"date": date(2024, 7, 7), "net": Decimal("900")},
+        {"date": date(2024, 7, 14), "net": Decimal("-50")},
+    ]
+    assert result.data["totals"]["net_savings"] == Decimal("850")
+
+
+@pytest.mark.asyncio
+async def test_monthly_report_html_and_json_from_demo_data(repository):
+    """Test the monthly summary against the deterministic demo dataset."""
+    accounts = await seed_demo_month(repository)
+    checking = accounts["demo-checking-001"]
+    await repository.add_balance(make_snapshot(checking.id, "5000.00", date(2024, 2, 29)))
+    await repository.add_balance(make_snapshot(checking.id, "6250.50", date(2024, 3, 31)))
+
+    result = await ReportService(repository).monthly(2024, 3)
+
+    assert result.success, result.error
+    report = result.data
+    assert report["income"] == Decimal("11128.23")
+    assert report["total_expenses"] == Decimal("9737.73")
+    assert report["net_worth"]["change"] == Decimal("1250.50")
+    assert "Everyday Spending" not in report["net_worth"]["unknown_accounts"]
+    assert len(report["net_worth"]["unknown_accounts"]) == len(accounts) - 1
+
+    assert len(report["top_merchants"]) == 10
+    assert report["top_merchants"][0] == {
+        "merchant": "Online Payment - Rent", "amount": Decimal("2100.00"), "count": 1,
+    }
+    assert report["top_merchants"][1]["merchant"] == "Costco"
+    assert [tx["amount"] for tx in report["large_transactions"]][:3] == [
+        Decimal("4250.00"), Decimal("4250.00"), Decimal("-2100.00"),
+    ]
+    assert all("transfer" not in tx["tags"] for tx in report["large_transactions"])
+    shares = [entry["share"] for entry in report["spending_by_tag"]]
+    assert shares == sorted(shares, reverse=True)
+
+    html = render_monthly_html(report, "USD", "en-US")
+    for figure in ("$11,128.23", "$9,737.73", "$1,250.50", "$2,100.00", "$866.07"):
+        assert figure in html
+    assert "Trader Joe&#x27;s" in html
+    assert "<script" not in html
+    assert "http://" not in html and "https://" not in html
+
+    exported = json.loads(json.dumps(report, default=json_serializer))
+    assert exported["month"] == "2024-03"
+    assert exported["income"] == "11128.23"
+    assert exported["top_merchants"][0]["merchant"] == "Online Payment - Rent"
+
+
+@pytest.mark.asyncio
+async def test_digest_from_demo_data(repository):
+    """Test the digest numbers against the deterministic demo dataset."""
+    accounts = await seed_demo_month(repository)
+    checking = accounts["demo-checking-001"]
+    await repository.add_balance(make_snapshot(checking.id, "5000.00", date(2024, 3, 16)))
+    await repository.add_balance(make_snapshot(checking.id, "6250.50", date(2024, 3, 31)))
+    await repository.record_provider_warnings("demo", ["Connection needs attention"])
+
+    result = await ReportService(repository).digest(date(2024, 3, 17), date(2024, 3, 31))
+
+    assert result.success, result.error
+    digest = result.data
+    assert (digest["prior_start_date"], digest["prior_end_date"]) == (date(2024, 3, 2), date(2024, 3, 16))
+    assert digest["spent"] == {
+        "current": Decimal("3649.58"),
+        "prior": Decimal("3668.15"),
+        "change": Decimal("-18.57"),
+        "percent_change": Decimal("-0.5"),
+        "new": False,
+    }
+    assert [(c["category"], c["current"], c["percent_change"]) for c in digest["top_categories"]] == [
+        ("groceries", Decimal("1194.30"), Decimal("-3.9")),
+        ("dining", Decimal("942.03"), Decimal("4.9")),
+        ("shopping", Decimal("882.84"), Decimal("59.6")),
+    ]
+    assert digest["largest_transaction"]["description"] == "IKEA"
+    assert digest["largest_transaction"]["amount"] == Decimal("-235.50")
+    # The seeded transactions were added today, not during March 2024
+    assert digest["new_transactions"] == 0
+    assert [(c["account"], c["percent_change"]) for c in digest["balance_changes"]] == [
+        ("Everyday Spending", Decimal("25.0")),
+    ]
+    assert [w["message"] for w in digest["provider_warnings"]] == ["Connection needs attention"]
+
+    markdown = render_digest_markdown(digest, "USD", "en-US")
+    assert "- **Spent:** $3,649.58 (-0.5% vs $3,668.15 the period before)" in markdown
+    assert "| shopping | $882.84 | $553.29 | +59.6% |" in markdown
+    assert "- Everyday Spending: $5,000.00 → $6,250.50 (+25.0%)" in markdown
+    assert "- demo: Connection needs attention" in markdown
+
+    exported = json.loads(json.dumps(digest, default=json_serializer))
+    assert exported["spent"]["current"] == "3649.58"
+
+
+@pytest.mark.asyncio
+async def test_merchants_report_from_demo_data(repository):
+    """Test top merchants and their trend against the preceding period of the same length."""
+    await seed_demo_month(repository)
+
+    result = await ReportService(repository).merchants(date(2024, 3, 17), date(2024, 3, 31), top=5)
+
+    assert result.success, result.error
+    report = result.data
+    assert (report["prior_start_date"], report["prior_end_date"]) == (date(2024, 3, 2), date(2024, 3, 16))
+    # Same spending total as the digest for this period
+    assert report["total_expenses"] == Decimal("3649.58")
+    assert len(report["merchants"]) == 5
+    costco, safeway, whole_foods = report["merchants"][:3]
+    assert costco == {
+        "merchant": "Costco",
+        "amount": Decimal("431.74"),
+        "count": 2,
+        "average": Decimal("215.87"),
+        "prior_amount": Decimal("434.33"),
+        "change": Decimal("-2.59"),
+        "percent_change": Decimal("-0.6"),
+        "new": False,
+    }
+    assert (safeway["merchant"], safeway["count"], safeway["average"]) == ("Safeway", 3, Decimal("94.21"))
+    assert (safeway["prior_amount"], safeway["percent_change"]) == (Decimal("189.69"), Decimal("49.0"))
+    assert whole_foods["merchant"] == "Whole Foods Market"
+    ikea = report["merchants"][3]
+    assert (ikea["merchant"], ikea["new"], ikea["percent_change"]) == ("IKEA", True, None)
+    # Income and transfers are never merchants
+    assert all(m["amount"] > 0 for m in report["merchants"])
+
+
+def test_merchant_key_ignores_case_punctuation_and_store_numbers():
+    """Test that spellings of the same payee group together."""
+    assert merchant_key("STARBUCKS #1234") == merchant_key("Starbucks 5678") == "starbucks"
+    assert merchant_key("Trader Joe's") == merchant_key("TRADER JOE S") == "trader joe s"
+    assert merchant_key(None) == ""
+
+
+def test_digest_comparison_with_zero_prior_period():
+    """Test that spending with nothing the period before is "new", not a division error."""
+    assert _comparison(Decimal("50"), Decimal("0")) == {
+        "current": Decimal("50"),
+        "prior": Decimal("0"),
+        "change": Decimal("50"),
+        "percent_change": None,
+        "new": True,
+    }
+    assert _comparison(Decimal("0"), Decimal("0"))["new"] is False
+    assert _comparison(Decimal("0"), Decimal("40"))["percent_change"] == Decimal("-100.0")
+
+
+async def seed_register(repository):
+    """Checking account with a 1000.00 snapshot on June 30 and four July transactions."""
+    checking = make_account("Checking", account_type="checking")
+    await repository.add_account(checking)
+    await repository.add_balance(make_snapshot(checking.id, "1000.00", date(2024, 6, 30)))
+    transactions = [
+        make_transaction(checking.id, "-100.00", day=date(2024, 6, 20)),
+        make_transaction(checking.id, "2500.00", day=date(2024, 7, 1)),
+        make_transaction(checking.id, "-1200.00", day=date(2024, 7, 3)),
+        make_transaction(checking.id, "-45.67", day=date(2024, 7, 3)),
+        make_transaction(checking.id, "-19.99", day=date(2024, 7, 10)),
+    ]
+    transactions[3] = transactions[3].model_copy(update={"description": 'Joe\'s "Diner", Inc'})
+    await repository.bulk_upsert_transactions(transactions)
+    return checking
+
+
+@pytest.mark.asyncio
+async def test_register_running_balance(repository):
+    """Test debit/credit split and running balance from an exact snapshot."""
+    checking = await seed_register(repository)
+
+    result = await ReportService(repository).register(
+        checking.id, date(2024, 7, 1), date(2024, 7, 31)
+    )
+
+    assert result.success, result.error
+    register = result.data
+    assert register["opening_balance"] == Decimal("1000.00")
+    assert register["opening_balance_status"] == "snapshot"
+    assert register["estimated"] is False
+    assert [row["date"] for row in register["rows"]] == [
+        date(2024, 7, 1), date(2024, 7, 3), date(2024, 7, 3), date(2024, 7, 10)
+    ]
+    assert [(row["debit"], row["credit"]) for row in register["rows"]] == [
+        (None, Decimal("2500.00")),
+        (Decimal("1200.00"), None),
+        (Decimal("45.67"), None),
+        (Decimal("19.99"), None),
+    ]
+    assert [row["balance"] for row in register["rows"]] == [
+        Decimal("3500.00"), Decimal("2300.00"), Decimal("2254.33"), Decimal("2234.34")
+    ]
+    assert register["totals"] == {
+        "debits": Decimal("1265.66"),
+        "credits": Decimal("2500.00"),
+        "net": Decimal("1234.34"),
+        "closing_balance": Decimal("2234.34"),
+    }
+
+
+@pytest.mark.asyncio
+async def test_register_reconstructed_opening_balance_is_estimated(repository):
+    """Test that an opening balance reconstructed from another day is marked estimated."""
+    checking = await seed_register(repository)
+
+    result = await ReportService(repository).register(
+        checking.id, date(2024, 7, 2), date(2024, 7, 31)
+    )
+
+    register = result.data
+    # June 30 snapshot plus the July 1 deposit
+    assert register["opening_balance"] == Decimal("3500.00")
+    assert register["estimated"] is True
+    assert [row["balance"] for row in register["rows"]] == [
+        Decimal("2300.00"), Decimal("2254.33"), Decimal("2234.34")
+    ]
+
+
+@pytest.mark.asyncio
+async def test_register_csv(repository):
+    """Test the CSV layout, quoting and summary row."""
+    checking = await seed_register(repository)
+    result = await ReportService(repository).register(
+        checking.id, date(2024, 7, 2), date(2024, 7, 31)
+    )
+
+    rows = list(csv.reader(io.StringIO(render_register_csv(result.data))))
+
+    assert rows[0] == ["Date", "Description", "Debit", "Credit", "Balance"]
+    assert rows[1] == ["2024-07-02", "Opening balance (estimated)", "", "", "3500.00"]
+    assert rows[3] == ["2024-07-03", 'Joe\'s "Diner", Inc', "45.67", "", "2254.33"]
+    assert rows[-1] == ["2024-07-31", "Total (net change in Balance)", "1265.66", "0.00", "-1265.66"]
@@ -0,0 +1,188 @@
+"""Unit tests for grouping accounts by institution and finding stale accounts in status."""
+
+from datetime import date, datetime, timezone
+from decimal import Decimal
+from uuid import uuid4
+
+import pytest
+
+from treeline.app.status_service import (
+    StatusService,
+    find_stale_accounts,
+    group_accounts_by_institution,
+)
+from treeline.app.warning_service import WarningService
+
+from tests.support.factories import make_account, make_transaction
+
+
+def test_groups_sorted_with_subtotals_and_totals():
+    """Test case-insensitive grouping, "Other" last, and exact Decimal subtotals."""
+    savings = make_account("Savings", institution_name="Ally")
+    checking = make_account("checking", institution_name="ally")
+    card = make_account("Sapphire", institution_name="Chase")
+    cash = make_account("Cash")
+    euros = make_account("Girokonto", institution_name="Chase", currency="EUR")
+    balances = {
+        savings.id: Decimal("0.10"),
+        checking.id: Decimal("0.20"),
+        card.id: Decimal("-500.00"),
+        cash.id: Decimal("40.00"),
+        euros.id: Decimal("12.34"),
+    }
+
+    result = group_accounts_by_institution([cash, card, savings, euros, checking], balances)
+
+    assert [g["institution"] for g in result["groups"]] == ["Ally", "Chase", "Other"]
+    ally, chase, _ = result["groups"]
+    assert ally["account_ids"] == [str(checking.id), str(savings.id)]
+    assert ally["subtotals"] == {"USD": Decimal("0.30")}
+    assert chase["account_ids"] == [str(euros.id), str(card.id)]
+    assert chase["subtotals"] == {"USD": Decimal("-500.00"), "EUR": Decimal("12.34")}
+    assert result["totals"] == {"USD": Decimal("-459.70"), "EUR": Decimal("12.34")}
+
+
+def test_unknown_balances_are_counted_not_summed():
+    """Test that accounts without a balance are reported separately from the subtotal."""
+    known = make_account("Checking", institution_name="Ally")
+    unknown = make_account("Savings", institution_name="Ally")
+
+    (group,) = group_accounts_by_institution([known, unknown], {known.id: Decimal("5.00")})[
+        "groups"
+    ]
+
+    assert group["account_count"] == 2
+    assert group["subtotals"] == {"USD": Decimal("5.00")}
+    assert group["unknown_balances"] == 1
+
+
+def test_blank_institution_groups_under_other():
+    """Test that empty and whitespace institution names join the "Other" group."""
+    accounts = [
+        make_account("A", institution_name=""),
+        make_account("B", institution_name="  "),
+        make_account("C"),
+    ]
+
+    (group,) = group_accounts_by_institution(accounts, {})["groups"]
+
+    assert group["institution"] == "Other"
+    assert group["account_count"] == 3
+    assert group["subtotals"] == {}
+
+
+THRESHOLDS = {"synced": 7, "manual": 45}
+TODAY = date(2024, 6, 30)
+ADDED = datetime(2024, 1, 1, tzinfo=timezone.utc)
+
+
+def test_stale_accounts_use_synced_and_manual_thresholds():
+    """Test that synced accounts go stale after a week and manual ones only after longer."""
+    fresh = make_account("Fresh", external_ids={"simplefin": "A1"}, created_at=ADDED)
+    stale = make_account("Stale", external_ids={"simplefin": "A2"}, created_at=ADDED)
+    manual = make_account("Manual", created_at=ADDED)
+    old_manual = make_account("Old Manual", created_at=ADDED)
+    latest = {
+        fresh.id: (date(2024, 6, 28), date(2024, 6, 29)),
+        stale.id: (date(2024, 6, 20), date(2024, 6, 29)),
+        manual.id: (date(2024, 6, 1), date(2024, 6, 1)),
+        old_manual.id: (date(2024, 4, 1), date(2024, 6, 1)),
+    }
+
+    result = find_stale_accounts([fresh, stale, manual, old_manual], latest, THRESHOLDS, TODAY)
+
+    assert [entry["name"] for entry in result] == ["Old Manual", "Stale"]
+    old_entry, stale_entry = result
+    assert stale_entry["synced"] is True
+    assert stale_entry["threshold_days"] == 7
+    assert stale_entry["days_since_transaction"] == 10
+    assert stale_entry["reasons"] == ["no transactions in 10 days"]
+    assert old_entry["synced"] is False
+    assert old_entry["threshold_days"] == 45
+    assert old_entry["reasons"] == ["no transactions in 90 days"]
+
+
+def test_stale_accounts_measure_empty_accounts_from_when_added():
+    """Test that accounts without data are only stale once they've existed past the threshold."""
+    new = make_account("New", external_ids={"simplefin": "A1"})
+    empty = make_account("Empty", external_ids={"simplefin": "A2"}, created_at=ADDED)
+
+    (entry,) = find_stale_accounts([new, empty], {}, THRESHOLDS, date.today())
+
+    assert entry["name"] == "Empty"
+    assert entry["days_since_transaction"] is None
+    assert entry["days_since_snapshot"] is None
+    assert entry["reasons"][0].startswith("no transactions since it was added")
+    assert entry["reasons"][1].startswith("no balance since it was added")
+
+
+def test_stale_accounts_honor_type_thresholds_and_skip_closed():
+    """Test per-type thresholds and that closed accounts are never stale."""
+    brokerage = make_account(
+        "Brokerage",
+        account_type="Investment",
+        external_ids={"simplefin": "A1"},
+        created_at=ADDED,
+    )
+    closed = make_account(
+        "Closed",
+        external_ids={"simplefin": "A2"},
+        created_at=ADDED,
+        closed_at=date(2024, 3, 1),
+    )
+    latest = {
+        brokerage.id: (date(2024, 6, 10), date(2024, 6, 10)),
+        closed.id: (date(2024, 2, 1), date(2024, 2, 1)),
+    }
+
+    assert find_stale_accounts(
+        [brokerage, closed], latest, {**THRESHOLDS, "investment": 30}, TODAY
+    ) == []
+    (entry,) = find_stale_accounts([brokerage, closed], latest, THRESHOLDS, TODAY)
+    assert entry["name"] == "Brokerage"
+    assert entry["reasons"] == ["no transactions in 20 days", "no balance in 20 days"]
+
+
+@pytest.mark.asyncio
+async def test_status_order_does_not_depend_on_insert_order(repository, tmp_path, monkeypatch):
+    """Test accounts by name then ID, integrations by name, and sorted ID and option keys."""
+    monkeypatch.setenv("TREELINE_DIR", str(tmp_path))
+    first_id, second_id = sorted((uuid4() for _ in range(2)), key=str)
+    accounts = [
+        make_account("savings", id=second_id),
+        make_account("Checking", external_ids={"simplefin": "A1", "csv": "C1"}),
+        make_account("Savings", id=first_id),
+    ]
+    for account in accounts:
+        await repository.add_account(account)
+    await repository.upsert_integration("simplefin", {"setupToken": "t", "accessUrl": "u"})
+    await repository.upsert_integration("demo", {})
+
+    result = await StatusService(repository, WarningService(repository)).get_status()
+
+    assert [(a.name, a.id) for a in result.data["accounts"]] == [
+        ("Checking", accounts[1].id),
+        ("Savings", first_id),
+        ("savings", second_id),
+    ]
+    assert list(result.data["accounts"][0].external_ids) == ["csv", "simplefin"]
+    assert result.data["integration_names"] == ["demo", "simplefin"]
+    assert list(result.data["integrations"][1]["integrationOptions"]) == ["accessUrl", "setupToken"]
+
+
+@pytest.mark.asyncio
+async def test_archived_accounts_are_left_out_of_status(repository, tmp_path, monkeypatch):
+    """Test that archived accounts are counted separately and their transactions kept."""
+    monkeypatch.setenv("TREELINE_DIR", str(tmp_path))
+    active = make_account("Checking")
+    archived = make_account("Old Savings", archived_at=datetime.now(timezone.utc))
+    for account in (active, archived):
+        await repository.add_account(account)
+    await repository.add_transaction(make_transaction(archived.id, "-5.00", date.today()))
+
+    result = await StatusService(repository, WarningService(repository)).get_status()
+
+    assert [a.id for a in result.data["accounts"]] == [active.id]
+    assert result.data["total_accounts"] == 1
+    assert result.data["archived_accounts"] == 1
+    assert result.data["total_transactions"] == 1
@@ -0,0 +1,274 @@
+"""Unit tests for the pure sync steps (account matching, mapping, dedup, balance checks)."""
+
+from datetime import date, datetime, timedelta, timezone
+from decimal import Decimal
+from uuid import uuid4
+
+from treeline.app.sync_steps import (
+    backfill_range,
+    find_duplicate_account_candidates,
+    find_unexplained_balance_changes,
+    is_duplicate_overlap,
+    match_accounts,
+    map_transactions,
+    month_windows,
+    partition_new,
+    previous_month_end,
+)
+from treeline.domain import Account, BalanceSnapshot, Transaction
+
+from tests.support.factories import make_account, make_transaction
+
+
+def _account(name: str, ext_id: str | None = None, provider: str = "simplefin", **fields) -> Account:
+    return make_account(name, external_ids={provider: ext_id} if ext_id else {}, **fields)
+
+
+def _provider_transaction(ext_id: str, amount: str = "-12.50") -> Transaction:
+    return make_transaction(
+        uuid4(),  # Placeholder until mapped
+        amount,
+        date(2024, 3, 1),
+        description="Coffee",
+        external_ids={"simplefin": ext_id},
+    )
+
+
+def test_renamed_account_keeps_id_type_and_nickname():
+    """Test that a provider-side rename updates the name but keeps user edits."""
+    existing = _account("Checking", "acc-1", account_type="depository", nickname="Bills")
+    discovered = _account("Everyday Checking", "acc-1", balance=Decimal("100"))
+
+    result = match_accounts([discovered], [existing], "simplefin")
+
+    assert result.success
+    (matched,) = result.data.accounts
+    assert matched.id == existing.id
+    assert matched.name == "Everyday Checking"
+    assert matched.account_type == "depository"
+    assert matched.nickname == "Bills"
+    assert matched.balance == Decimal("100")
+    assert result.data.new_accounts == []
+
+
+def test_unmatched_and_id_less_accounts_are_new():
+    """Test that accounts without a stored counterpart or external ID are new."""
+    existing = _account("Checking", "acc-1")
+    savings = _account("Savings", "acc-2")
+    manual = _account("Cash")
+
+    result = match_accounts([savings, manual], [existing], "simplefin")
+
+    assert result.success
+    assert result.data.accounts == [savings, manual]
+    assert result.data.new_accounts == [savings, manual]
+
+
+def test_duplicate_discovered_external_id_is_an_error():
+    """Test that two discovered accounts sharing an external ID fail instead of last-wins."""
+    first = _account("Checking", "acc-1")
+    second = _account("Checking (joint)", "acc-1")
+
+    result = match_accounts([first, second], [], "simplefin")
+
+    assert not result.success
+    assert "acc-1" in result.error
+    assert "Checking (joint)" in result.error
+
+
+def test_match_uses_the_given_provider_key():
+    """Test that matching works for providers other than SimpleFIN."""
+    existing = _account("Brokerage", "brk-9", provider="demo")
+    discovered = _account("Brokerage Account", "brk-9", provider="demo")
+    other_provider = _account("Brokerage", "brk-9", provider="simplefin")
+
+    result = match_accounts([discovered], [existing, other_provider], "demo")
+
+    assert result.success
+    assert result.data.accounts[0].id == existing.id
+    assert result.data.new_accounts == []
+
+    # The same external ID under a different provider key is not a match
+    result = match_accounts([discovered], [other_provider], "demo")
+    assert result.data.new_accounts == [discovered]
+
+
+def test_map_transactions_reports_unmapped_accounts():
+    """Test that transactions for unknown provider accounts are returned, not dropped."""
+    account_id = uuid4()
+    known = _provider_transaction("tx-1")
+    orphan = _provider_transaction("tx-2")
+
+    outcome = map_transactions(
+        [("acc-1", known), ("acc-missing", orphan)], {"acc-1": account_id}
+    )
+
+    assert [tx.account_id for tx in outcome.transactions] == [account_id]
+    assert outcome.unmapped == [("acc-missing", orphan)]
+
+
+def test_map_transactions_recomputes_fingerprint_and_skips_balances_only():
+    """Test that mapped transactions get a fresh fingerprint and balances-only accounts are skipped."""
+    account_id = uuid4()
+    tx = _provider_transaction("tx-1")
+    csv_tx = _provider_transaction("tx-3").model_copy(update={"account_id": account_id})
+
+    outcome = map_transactions(
+        [("acc-1", tx), ("acc-2", _provider_transaction("tx-2")), csv_tx],
+        {"acc-1": account_id, "acc-2": uuid4()},
+        skip_account_ids={"acc-2"},
+    )
+
+    mapped, passthrough = outcome.transactions
+    assert mapped.account_id == account_id
+    assert mapped.external_ids["fingerprint"] != tx.external_ids["fingerprint"]
+    assert passthrough is csv_tx
+    assert outcome.unmapped == []
+
+
+def test_partition_new_skips_existing_external_ids():
+    """Test that already-stored transactions are skipped and the rest are new."""
+    stored = _provider_transaction("tx-1")
+    fresh = _provider_transaction("tx-2")
+    no_ext_id = _provider_transaction("tx-3").model_copy(update={"external_ids": {}})
+
+    new, skipped = partition_new(
+        [_provider_transaction("tx-1"), fresh, no_ext_id], {"tx-1": stored}, "simplefin"
+    )
+
+    assert new == [fresh, no_ext_id]
+    assert len(skipped) == 1
+    assert skipped[0].external_ids["simplefin"] == "tx-1"
+
+
+def _snapshot(account: Account, balance: str, day: date) -> BalanceSnapshot:
+    now = datetime.now(timezone.utc)
+    return BalanceSnapshot(
+        id=uuid4(),
+        account_id=account.id,
+        balance=Decimal(balance),
+        snapshot_time=datetime.combine(day, datetime.min.time(), tzinfo=timezone.utc),
+        created_at=now,
+        updated_at=now,
+    )
+
+
+def _posted(account: Account, amount: str, day: date) -> Transaction:
+    return make_transaction(account.id, amount, day, description="Purchase")
+
+
+def test_balance_change_beyond_known_transactions_is_reported():
+    """Test that a -300.00 move with -120.00 of transactions leaves -180.00 unexplained."""
+    checking = _account("Checking", "acc-1", nickname="Bills", balance=Decimal("700.00"))
+    savings = _account("Savings", "acc-2", balance=Decimal("2050.00"))
+    previous = {
+        checking.id: _snapshot(checking, "1000.00", date(2024, 3, 1)),
+        savings.id: _snapshot(savings, "2000.00", date(2024, 3, 1)),
+    }
+    transactions = [
+        _posted(checking, "-100.00", date(2024, 3, 1)),  # Before the window: already in the snapshot
+        _posted(checking, "-120.00", date(2024, 3, 2)),
+        _posted(checking, "-999.00", date(2024, 3, 4)),  # After the balance date
+        _posted(savings, "50.00", date(2024, 3, 3)),
+    ]
+
+    changes = find_unexplained_balance_changes(
+        [checking, savings],
+        {checking.id: date(2024, 3, 3), savings.id: date(2024, 3, 3)},
+        previous,
+        transactions,
+        tolerance=Decimal("0.01"),
+    )
+
+    (change,) = changes
+    assert change.account_id == checking.id
+    assert change.account_name == "Bills"
+    assert change.balance_change == Decimal("-300.00")
+    assert change.transactions_total == Decimal("-120.00")
+    assert change.unexplained_delta == Decimal("-180.00")
+    assert (change.previous_date, change.balance_date) == (date(2024, 3, 1), date(2024, 3, 3))
+
+
+def test_balance_check_tolerance_and_skipped_accounts():
+    """Test the tolerance, and that accounts without history or a balance are skipped."""
+    rounding = _account("Rounding", "acc-1", balance=Decimal("99.99"))
+    new = _account("New", "acc-2", balance=Decimal("5.00"))
+    no_balance = _account("No balance", "acc-3")
+    same_day = _account("Same day", "acc-4", balance=Decimal("1.00"))
+    previous = {
+        rounding.id: _snapshot(rounding, "100.00", date(2024, 3, 1)),
+        no_balance.id: _snapshot(no_balance, "100.00", date(2024, 3, 1)),
+        same_day.id: _snapshot(same_day, "500.00", date(2024, 3, 3)),
+    }
+    balance_dates = {account.id: date(2024, 3, 3) for account in (rounding, new, no_balance, same_day)}
+
+    changes = find_unexplained_balance_changes(
+        [rounding, new, no_balance, same_day], balance_dates, previous, [], tolerance=Decimal("0.01")
+    )
+
+    assert changes == []
+
+
+def test_previous_month_end():
+    """Test that the month-end is the last day of the last month that has ended."""
+    assert previous_month_end(date(2024, 4, 3)) == date(2024, 3, 31)
+    assert previous_month_end(date(2024, 4, 1)) == date(2024, 3, 31)
+    # The last day of a month is not its month-end until the month is over
+    assert previous_month_end(date(2024, 3, 31)) == date(2024, 2, 29)
+    assert previous_month_end(date(2025, 1, 15)) == date(2024, 12, 31)
+
+
+def test_backfill_range_counts_the_current_month():
+    """Test that a backfill starts on a month's first day and includes today."""
+    assert backfill_range(date(2024, 4, 10), 1) == (date(2024, 4, 1), date(2024, 4, 11))
+    assert backfill_range(date(2024, 4, 10), 24) == (date(2022, 5, 1), date(2024, 4, 11))
+    assert backfill_range(date(2025, 1, 31), 2) == (date(2024, 12, 1), date(2025, 2, 1))
+
+
+def test_month_windows_split_on_calendar_months():
+    """Test that windows are whole months, oldest first, with a partial last month."""
+    windows = month_windows(date(2024, 1, 1), date(2024, 3, 16))
+
+    assert windows == [
+        (date(2024, 1, 1), date(2024, 2, 1)),
+        (date(2024, 2, 1), date(2024, 3, 1)),
+        (date(2024, 3, 1), date(2024, 3, 16)),
+    ]
+    assert month_windows(date(2024, 12, 20), date(2025, 1, 5)) == [
+        (date(2024, 12, 20), date(2025, 1, 1)),
+        (date(2025, 1, 1), date(2025, 1, 5)),
+    ]
+    assert month_windows(date(2024, 3, 1), date(2024, 3, 1)) == []
+
+
+def test_duplicate_account_candidates_span_integrations():
+    """Test that same-institution, same-name accounts pair up only across integrations."""
+    bank = {"institution_domain": "chase.com", "currency": "USD"}
+    older = _account("Total Checking", "ACT-1", **bank)
+    newer = _account("TOTAL CHECKING.", "lf-9", provider="lunchflow", **bank)
+    newer = newer.model_copy(update={"created_at": older.created_at + timedelta(days=30)})
+    same_provider = _account("Total Checking", "ACT-2", **bank)
+    other_currency = _account(
+        "Total Checking", "lf-10", provider="lunchflow", institution_domain="chase.com", currency="CAD"
+    )
+    other_bank = _account(
+        "Total Checking", "lf-11", provider="lunchflow", institution_domain="wellsfargo.com"
+    )
+    manual = _account("Total Checking", **bank)
+
+    pairs = find_duplicate_account_candidates(
+        [newer, same_provider, other_currency, other_bank, manual, older]
+    )
+
+    pair_ids = {(a.id, b.id) for a, b in pairs}
+    assert (older.id, newer.id) in pair_ids
+    assert (older.id, same_provider.id) not in pair_ids
+    assert all(other_currency.id not in ids and other_bank.id not in ids for ids in pair_ids)
+    assert all(manual.id not in ids for ids in pair_ids)
+
+
+def test_duplicate_overlap_threshold():
+    """Test that a few shared transactions, or a small share of them, are not duplicates."""
+    assert is_duplicate_overlap({"a": 40, "b": 10, "shared": 9})
+    assert not is_duplicate_overlap({"a": 2, "b": 2, "shared": 2})
+    assert not is_duplicate_overlap({"a": 100, "b": 100, "shared": 20})
@@ -0,0 +1,85 @@
+"""Unit tests for TaggingService."""
+
+from datetime import date
+
+import pytest
+import pytest_asyncio
+
+from treeline.app.tagging_service import TaggingService
+from treeline.domain import Transaction
+
+from tests.support.factories import make_transaction
+
+
+@pytest_asyncio.fixture
+async def workspace(repository, account_id):
+    """Create a fresh database with an account and two transactions."""
+    transactions = [
+        make_transaction(account_id, amount, date(2024, 6, 1), description=description)
+        for amount, description in (("-42.00", "Bistro"), ("-18.50", "Taqueria"))
+    ]
+    await repository.bulk_upsert_transactions(transactions)
+    return TaggingService(repository), transactions
+
+
+async def stored_tags(service: TaggingService, transaction: Transaction):
+    result = await service.repository.execute_query(
+        f"SELECT tags FROM sys_transactions WHERE transaction_id = '{transaction.id}'"
+    )
+    return result.data["rows"][0][0]
+
+
+@pytest.mark.asyncio
+async def test_variant_spellings_are_stored_identically(workspace):
+    """Test that visually different tag lists are written as the same array."""
+    service, (bistro, taqueria) = workspace
+
+    await service.update_transaction_tags(bistro.id, ["Dining ", "#Date Night", "dining"])
+    await service.update_transaction_tags(taqueria.id, ["DINING", "  date   night"])
+
+    assert await stored_tags(service, bistro) == ["dining", "date night"]
+    assert await stored_tags(service, taqueria) == ["dining", "date night"]
+
+
+@pytest.mark.asyncio
+async def test_normalize_stored_tags_reports_then_rewrites(workspace):
+    """Test that tags written before normalization are merged, and only with apply."""
+    service, (bistro, taqueria) = workspace
+    await service.repository.bulk_update_transaction_tags(
+        {bistro.id: ["Dining ", "travel"], taqueria.id: ["DINING", "dining"]}
+    )
+
+    dry_run = await service.normalize_stored_tags(dry_run=True)
+
+    assert dry_run.success, dry_run.error
+    assert dry_run.data["transactions"] == 2
+    assert dry_run.data["tags"] == [
+        {"tag": "dining", "variants": ["DINING", "Dining "], "transactions": 2}
+    ]
+    assert await stored_tags(service, bistro) == ["Dining ", "travel"]
+
+    applied = await service.normalize_stored_tags(dry_run=False)
+
+    assert applied.data["transactions"] == 2
+    assert await stored_tags(service, bistro) == ["dining", "travel"]
+    assert await stored_tags(service, taqueria) == ["dining"]
+    again = await service.normalize_stored_tags(dry_run=True)
+    assert again.data == {"dry_run": True, "transactions": 0, "tags": []}
+
+
+@pytest.mark.asyncio
+async def test_replace_tag_rewrites_a_provider_category(workspace):
+    """Test that a newly mapped category is replaced in place, merging with existing tags."""
+    service, (bistro, taqueria) = workspace
+    await service.repository.bulk_update_transaction_tags(
+        {bistro.id: ["food_and_drink_coffee", "travel"], taqueria.id: ["coffee", "food_and_drink_coffee"]}
+    )
+
+    result = await service.replace_tag("FOOD_AND_DRINK_COFFEE", "coffee")
+
+    assert result.success, result.error
+    assert result.data == 2
+    assert await stored_tags(service, bistro) == ["coffee", "travel"]
+    assert await stored_tags(service, taqueria) == ["coffee"]
+    counts = await service.count_tags(["coffee", "food_and_drink_coffee"])
+    assert counts.data == {"coffee": 2}
@@ -0,0 +1,278 @@
+"""Unit tests for transaction status, edits, deletes and split listing in TransactionService."""
+
+from datetime import date
+from decimal import Decimal
+from uuid import uuid4
+
+import pytest
+
+from treeline.app.transaction_service import TransactionService, nest_split_parts
+from treeline.domain import TransactionStatus
+
+from tests.support.factories import make_transaction
+
+
+def test_status_defaults_to_cleared():
+    """Test that transactions are cleared unless stated otherwise."""
+    assert make_transaction(uuid4(), "-5").status == TransactionStatus.CLEARED
+
+
+@pytest.mark.asyncio
+async def test_create_pending_and_clear(repository, account_id):
+    """Test entering a pending transaction and clearing it later."""
+    service = TransactionService(repository)
+    created = await service.create_transaction(
+        account_id, Decimal("-42.00"), "Farmers market", date(2024, 7, 15),
+        status=TransactionStatus.PENDING,
+    )
+    assert created.success
+
+    pending = await service.list_transactions(status=TransactionStatus.PENDING)
+    assert [tx.id for tx in pending.data] == [created.data.id]
+
+    cleared = await service.set_status([str(created.data.id)[:8]], TransactionStatus.CLEARED)
+    assert cleared.success
+    assert cleared.data == [created.data.id]
+
+    pending = await service.list_transactions(status=TransactionStatus.PENDING)
+    assert pending.data == []
+    stored = await repository.get_transaction_by_id(created.data.id)
+    assert stored.data.status == TransactionStatus.CLEARED
+
+
+@pytest.mark.asyncio
+async def test_set_status_rejects_unknown_ids(repository, account_id):
+    """Test that one bad ID leaves every transaction unchanged."""
+    transaction = make_transaction(account_id, "-10", status=TransactionStatus.PENDING)
+    await repository.bulk_upsert_transactions([transaction])
+
+    service = TransactionService(repository)
+    result = await service.set_status(
+        [str(transaction.id), str(uuid4())], TransactionStatus.CLEARED
+    )
+
+    assert not result.success
+    stored = await repository.get_transaction_by_id(transaction.id)
+    assert stored.data.status == TransactionStatus.PENDING
+
+
+@pytest.mark.asyncio
+async def test_sync_upsert_keeps_manual_status(repository, account_id):
+    """Test that re-syncing a transaction does not undo a manual status change."""
+    transaction = make_transaction(account_id, "-10", status=TransactionStatus.PENDING)
+    await repository.bulk_upsert_transactions([transaction])
+    await repository.update_transactions_status([transaction.id], TransactionStatus.RECONCILED)
+
+    await repository.bulk_upsert_transactions([transaction])
+
+    stored = await repository.get_transaction_by_id(transaction.id)
+    assert stored.data.status == TransactionStatus.RECONCILED
+
+
+@pytest.mark.asyncio
+async def test_delete_hides_transactions_until_restored(repository, account_id):
+    """Test that deleted transactions drop out of lists and come back with restore."""
+    kept = make_transaction(account_id, "-10")
+    bogus = make_transaction(account_id, "-999")
+    await repository.bulk_upsert_transactions([kept, bogus])
+    service = TransactionService(repository)
+
+    deleted = await service.delete([str(bogus.id)[:8]])
+    assert deleted.success
+    assert deleted.data == [bogus.id]
+
+    listed = await service.list_transactions()
+    assert [tx.id for tx in listed.data] == [kept.id]
+    stored = await repository.get_transaction_by_id(bogus.id)
+    assert stored.data.deleted_at is not None
+
+    # Deleting again changes nothing
+    again = await service.delete([str(bogus.id)])
+    assert again.success
+    assert again.data == []
+
+    restored = await service.delete([str(bogus.id)], restore=True)
+    assert restored.success
+    assert restored.data == [bogus.id]
+    listed = await service.list_transactions()
+    assert {tx.id for tx in listed.data} == {kept.id, bogus.id}
+
+
+@pytest.mark.asyncio
+async def test_delete_rejects_unknown_ids(repository, account_id):
+    """Test that one bad ID leaves every transaction in place."""
+    transaction = make_transaction(account_id, "-10")
+    await repository.bulk_upsert_transactions([transaction])
+
+    service = TransactionService(repository)
+    result = await service.delete([str(transaction.id), str(uuid4())])
+
+    assert not result.success
+    stored = await repository.get_transaction_by_id(transaction.id)
+    assert stored.data.deleted_at is None
+
+
+@pytest.mark.asyncio
+async def test_restore_refuses_split_parent_with_active_parts(repository, account_id):
+    """Test that a split parent isn't restored alongside the parts that replaced it."""
+    parent = make_transaction(account_id, "-100")
+    part = make_transaction(account_id, "-100", parent_transaction_id=parent.id)
+    await repository.bulk_upsert_transactions([parent, part])
+    service = TransactionService(repository)
+    await service.delete([str(parent.id)])
+
+    result = await service.delete([str(parent.id)], restore=True)
+
+    assert not result.success
+    assert "split" in result.error
+    stored = await repository.get_transaction_by_id(parent.id)
+    assert stored.data.deleted_at is not None
+
+
+@pytest.mark.asyncio
+async def test_edit_recomputes_fingerprint(repository, account_id):
+    """Test that an edit saves the new values, bumps updated_at and refingerprints."""
+    transaction = make_transaction(account_id, "-5.50", description="COFEE SHOP")
+    await repository.bulk_upsert_transactions([transaction])
+    service = TransactionService(repository)
+
+    result = await service.edit(
+        str(transaction.id)[:8],
+        amount=Decimal("-5.05"),
+        description="Coffee shop",
+        transaction_date=date(2024, 7, 14),
+    )
+
+    assert result.success
+    before, after = result.data
+    assert (before.amount, before.description) == (Decimal("-5.50"), "COFEE SHOP")
+    stored = (await repository.get_transaction_by_id(transaction.id)).data
+    assert stored.external_ids == after.external_ids
+    assert (stored.amount, stored.description) == (Decimal("-5.05"), "Coffee shop")
+    assert (stored.transaction_date, stored.posted_date) == (date(2024, 7, 14), date(2024, 7, 15))
+    assert stored.updated_at > transaction.updated_at
+    corrected = make_transaction(
+        account_id, "-5.05", description="Coffee shop", transaction_date=date(2024, 7, 14)
+    )
+    assert stored.external_ids["fingerprint"] == corrected.external_ids["fingerprint"]
+
+
+@pytest.mark.asyncio
+async def test_edit_keeps_v2_fingerprints_unique(repository, account_id):
+    """Test that an edit making two rows identical gives the edited one the next occurrence."""
+    first = make_transaction(account_id, "-3", description="Parking").with_fingerprint(2)
+    second = make_transaction(account_id, "-4", description="Parking").with_fingerprint(2)
+    await repository.bulk_upsert_transactions([first, second])
+
+    result = await TransactionService(repository).edit(str(second.id), amount=Decimal("-3"))
+
+    assert result.success
+    _, after = result.data
+    assert after.external_ids["fingerprint_version"] == "2"
+    assert after.external_ids["fingerprint"] == first.compute_fingerprint(2, occurrence=1)
+
+
+@pytest.mark.asyncio
+async def test_edit_rejects_unknown_ids_and_empty_edits(repository, account_id):
+    """Test that an edit with nothing to change or no such transaction fails."""
+    transaction = make_transaction(account_id, "-10")
+    await repository.bulk_upsert_transactions([transaction])
+    service = TransactionService(repository)
+
+    unknown = await service.edit(str(uuid4()), amount=Decimal("-1"))
+    empty = await service.edit(str(transaction.id))
+
+    assert not unknown.success
+    assert "No transaction matches ID" in unknown.error
+    assert not empty.success
+    assert "Nothing to change" in empty.error
+    stored = await repository.get_transaction_by_id(transaction.id)
+    assert stored.data.amount == Decimal("-10")
+
+
+def test_nest_split_parts_groups_parts_under_parent():
+    """Test that parts follow their parent, fetched or listed, at the first part's position."""
+    account_id = uuid4()
+    before = make_transaction(account_id, "-1")
+    split = make_transaction(account_id, "-100")
+    first = make_transaction(account_id, "-70", parent_transaction_id=split.id)
+    after = make_transaction(account_id, "-2")
+    second = make_transaction(account_id, "-30", parent_transaction_id=split.id)
+    orphan = make_transaction(account_id, "-5", parent_transaction_id=uuid4())
+
+    rows = nest_split_parts([before, first, after, second, orphan], {split.id: split})
+
+    assert [(tx.id, depth) for tx, depth in rows] == [
+        (before.id, 0),
+        (split.id, 0),
+        (first.id, 1),
+        (second.id, 1),
+        (after.id, 0),
+        (orphan.id, 0),
+    ]
+
+    listed = nest_split_parts([first, split, second], {})
+    assert [(tx.id, depth) for tx, depth in listed] == [
+        (split.id, 0),
+        (first.id, 1),
+        (second.id, 1),
+    ]
+
+
+@pytest.mark.asyncio
+async def test_find_by_external_id_only_matches_the_given_provider(repository, account_id):
+    """Test that a provider's lookup ignores the same value under other keys."""
+    synced = make_transaction(account_id, "-5", external_ids={"simplefin": "TRN-1"})
+    imported = make_transaction(account_id, "-5", external_ids={"csv": "TRN-1"})
+    await repository.bulk_upsert_transactions([synced, imported])
+    service = TransactionService(repository)
+
+    result = await service.find_by_external_id("simplefin", "TRN-1")
+
+    assert result.success, result.error
+    assert [(tx.id, key) for tx, key in result.data] == [(synced.id, "simplefin")]
+
+
+@pytest.mark.asyncio
+async def test_find_by_external_id_any_reports_the_matched_key(repository, account_id):
+    """Test that --provider any searches every key and says which one matched."""
+    synced = make_transaction(account_id, "-5", external_ids={"simplefin": "TRN-1"})
+    imported = make_transaction(account_id, "-7", external_ids={"csv": "TRN-1"})
+    other = make_transaction(account_id, "-9", external_ids={"lunchflow": "TRN-10"})
+    await repository.bulk_upsert_transactions([synced, imported, other])
+    service = TransactionService(repository)
+
+    result = await service.find_by_external_id("any", "TRN-1")
+
+    assert result.success, result.error
+    assert sorted((key, tx.id) for tx, key in result.data) == sorted(
+        [("csv", imported.id), ("simplefin", synced.id)]
+    )
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize("value", ["50%_off", "a\\b", "it's \"quoted\"", "trn-%"])
+async def test_find_by_external_id_matches_special_characters_exactly(
+    repository, account_id, value
+):
+    """Test that LIKE wildcards, quotes and backslashes are compared literally."""
+    target = make_transaction(account_id, "-5", external_ids={"simplefin": value})
+    decoy = make_transaction(account_id, "-5", external_ids={"simplefin": "trn-12"})
+    await repository.bulk_upsert_transactions([target, decoy])
+    service = TransactionService(repository)
+
+    for provider in ("simplefin", "any"):
+        result = await service.find_by_external_id(provider, value)
+        assert result.success, result.error
+        assert [tx.id for tx, _ in result.data] == [target.id]
+
+
+@pytest.mark.asyncio
+async def test_find_by_external_id_not_found_has_a_kind(repository, account_id):
+    """Test that no match fails with context kind "not_found"."""
+    service = TransactionService(repository)
+
+    result = await service.find_by_external_id("any", "TRN-404")
+
+    assert not result.success
+    assert result.context["kind"] == "not_found"
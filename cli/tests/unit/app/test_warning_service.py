@@ -0,0 +1,51 @@
+"""Unit tests for WarningService."""
+
+import pytest
+
+from treeline.app.warning_service import WarningService
+
+
+@pytest.fixture
+def warning_service(repository):
+    """Create a WarningService backed by a fresh database."""
+    return WarningService(repository)
+
+
+@pytest.mark.asyncio
+async def test_repeated_warning_is_deduplicated(warning_service):
+    """Test that the same message across syncs bumps last_seen instead of duplicating."""
+    message = "Connection to Chase may need attention"
+    await warning_service.record_sync_warnings("simplefin", [message])
+    await warning_service.record_sync_warnings("simplefin", [message])
+
+    result = await warning_service.list_warnings()
+
+    assert result.success
+    assert len(result.data) == 1
+    assert result.data[0].message == message
+    assert result.data[0].last_seen >= result.data[0].first_seen
+
+
+@pytest.mark.asyncio
+async def test_clean_sync_resolves_warnings(warning_service):
+    """Test that a clean sync resolves open warnings for that integration only."""
+    await warning_service.record_sync_warnings("simplefin", ["Reauthenticate"])
+    await warning_service.record_sync_warnings("demo", ["Demo warning"])
+
+    await warning_service.record_sync_warnings("simplefin", [])
+
+    open_result = await warning_service.list_warnings()
+    assert [w.integration_name for w in open_result.data] == ["demo"]
+
+    all_result = await warning_service.list_warnings(include_resolved=True)
+    resolved = [w for w in all_result.data if w.integration_name == "simplefin"]
+    assert resolved[0].resolved_at is not None
+
+
+@pytest.mark.asyncio
+async def test_resolve_unknown_warning_fails(warning_service):
+    """Test that resolving a bad or unknown ID fails cleanly."""
+    assert not (await warning_service.resolve_warning("not-a-uuid")).success
+    assert not (
+        await warning_service.resolve_warning("00000000-0000-0000-0000-000000000000")
+    ).success
@@ -0,0 +1,140 @@
+"""Unit tests for the year-in-review summary."""
+
+import json
+from datetime import date, datetime, timezone
+from decimal import Decimal
+
+import pytest
+
+from treeline.app.report_html import render_year_review_html
+from treeline.app.report_service import ReportService
+from treeline.app.year_review_service import YearReviewService
+from treeline.commands.report import json_serializer
+from treeline.infra.demo import DemoDataProvider
+
+from tests.support.factories import make_snapshot
+
+
+async def seed_demo_year(repository) -> dict:
+    """Load the demo accounts and their 2024 transactions; return accounts by demo ID."""
+    provider = DemoDataProvider()
+    accounts = {a.external_ids["demo"]: a for a in (await provider.get_accounts()).data}
+    for account in accounts.values():
+        await repository.add_account(account)
+
+    demo_result = await provider.get_transactions(
+        datetime(2024, 1, 1, tzinfo=timezone.utc), datetime(2024, 12, 31, 23, 59, tzinfo=timezone.utc)
+    )
+    await repository.bulk_upsert_transactions(
+        [
+            tx.model_copy(update={"account_id": accounts[demo_id].id})
+            for demo_id, tx in demo_result.data
+        ]
+    )
+    return accounts
+
+
+def make_service(repository) -> YearReviewService:
+    return YearReviewService(repository, ReportService(repository))
+
+
+@pytest.mark.asyncio
+async def test_year_review_of_demo_year(repository):
+    """Test the headline figures of the review against the deterministic demo year."""
+    accounts = await seed_demo_year(repository)
+    checking = accounts["demo-checking-001"]
+    for day, balance in (
+        (date(2023, 12, 31), "5000.00"),
+        (date(2024, 3, 31), "6250.50"),
+        (date(2024, 6, 30), "5900.00"),
+        (date(2024, 9, 30), "7100.25"),
+        (date(2024, 12, 31), "8000.00"),
+    ):
+        await repository.add_balance(make_snapshot(checking.id, balance, day))
+
+    result = await make_service(repository).review(2024, today=date(2025, 6, 1))
+
+    assert result.success, result.error
+    review = result.data
+    assert review["enough_data"] and not review["year_to_date"]
+    assert review["income"] == Decimal("133538.76")
+    assert review["total_expenses"] == Decimal("122453.38")
+    assert review["net_savings"] == Decimal("11085.38")
+    assert review["savings_rate"] == Decimal("8.3")
+    assert review["net_worth"]["change"] == Decimal("3000.00")
+    assert [(q["quarter"], q["change"]) for q in review["quarters"]] == [
+        ("Q1", Decimal("1250.50")),
+        ("Q2", Decimal("-350.50")),
+        ("Q3", Decimal("1200.25")),
+        ("Q4", Decimal("899.75")),
+    ]
+    assert review["most_expensive_month"]["month"] == "2024-10"
+    assert review["most_expensive_month"]["total_expenses"] == Decimal("10899.50")
+    assert review["biggest_expense"]["description"] == "Online Payment - Rent"
+    assert review["biggest_expense"]["date"] == date(2024, 1, 1)
+
+    # The same numbers as the reports it is built from
+    merchants = (await ReportService(repository).merchants(date(2024, 1, 1), date(2024, 12, 31))).data
+    assert review["top_merchants"][0]["merchant"] == merchants["merchants"][0]["merchant"]
+    assert [m["amount"] for m in review["top_merchants"]] == [
+        m["amount"] for m in merchants["merchants"][:10]
+    ]
+    assert review["top_tags"][0] == {
+        "tag": "groceries", "amount": Decimal("30448.06"), "share": Decimal("24.9"),
+    }
+
+    subscriptions = review["subscriptions"]
+    assert subscriptions["total"] == Decimal("39295.62")
+    netflix = next(c for c in subscriptions["charges"] if c["merchant"] == "Netflix")
+    assert (netflix["months"], netflix["typical_amount"], netflix["total"]) == (
+        12, Decimal("15.99"), Decimal("191.88"),
+    )
+    # Weekly and every-few-days spending isn't recurring
+    assert not any(c["merchant"] in ("Costco", "Starbucks") for c in subscriptions["charges"])
+
+    highlights = review["highlights"]
+    assert highlights["most_spending"]["account"] == "Cashback Card"
+    assert highlights["largest_increase"]["account"] == "Everyday Spending"
+    assert highlights["largest_decrease"] is None
+
+    html = render_year_review_html(review, "USD", "en-US")
+    for figure in ("$133,538.76", "$122,453.38", "$3,000.00", "$39,295.62", "2024-10"):
+        assert figure in html
+    assert "<script" not in html
+    assert "http://" not in html and "https://" not in html
+
+    exported = json.loads(json.dumps(review, default=json_serializer))
+    assert exported["income"] == "133538.76"
+
+
+@pytest.mark.asyncio
+async def test_current_year_runs_to_today(repository):
+    """Test that a year still in progress stops at today and skips quarters not yet started."""
+    await seed_demo_year(repository)
+
+    result = await make_service(repository).review(2024, today=date(2024, 5, 10))
+
+    review = result.data
+    assert review["year_to_date"]
+    assert review["end_date"] == date(2024, 5, 10)
+    assert [q["quarter"] for q in review["quarters"]] == ["Q1", "Q2"]
+    assert review["quarters"][1]["end_date"] == date(2024, 5, 10)
+    assert review["income"] == Decimal("50037.92")
+
+
+@pytest.mark.asyncio
+async def test_year_without_data_says_so(repository):
+    """Test that an empty year gives a not-enough-data result rather than zeros."""
+    await seed_demo_year(repository)
+    service = make_service(repository)
+
+    result = await service.review(2023, today=date(2025, 1, 5))
+
+    assert result.success
+    assert result.data["enough_data"] is False
+    assert "Not enough data for 2023" in result.data["message"]
+    assert "income" not in result.data
+    html = render_year_review_html(result.data, "USD", "en-US")
+    assert "Not enough data for 2023" in html
+
+    assert not (await service.review(2026, today=date(2025, 1, 5))).success
@@ -0,0 +1,23 @@
+"""Fixtures shared by the unit tests."""
+
+import pytest_asyncio
+
+from treeline.infra.duckdb import DuckDBRepository
+
+from tests.support.factories import make_account
+
+
+@pytest_asyncio.fixture
+async def repository(tmp_path):
+    """A fresh, migrated database in a temporary directory."""
+    repository = DuckDBRepository(str(tmp_path / "treeline.duckdb"))
+    await repository.ensure_schema_upgraded()
+    return repository
+
+
+@pytest_asyncio.fixture
+async def account_id(repository):
+    """Add a "Checking" account to the database and return its ID."""
+    account = make_account()
+    await repository.add_account(account)
+    return account.id
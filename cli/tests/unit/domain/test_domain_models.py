@@ -1,5 +1,7 @@
 from __future__ import annotations
 
+import json
+import random
 from datetime import date, datetime, timedelta, timezone
 from decimal import Decimal
 from uuid import UUID, uuid4
@@ -7,7 +9,18 @@ from uuid import UUID, uuid4
 import pytest
 from pydantic import ValidationError
 
-from treeline.domain import Account, BalanceSnapshot, Transaction
+from treeline.domain import (
+    MAX_AMOUNT,
+    Account,
+    BalanceSnapshot,
+    Transaction,
+    amount_in_range,
+    apply_default_tags,
+    money_json,
+    assign_fingerprints,
+    normalize_tag,
+    normalize_tags,
+)
 
 
 def _tz_now() -> datetime:
@@ -63,6 +76,32 @@ def test_account_fields_align_with_schema() -> None:
         )
 
 
+def test_account_open_dates() -> None:
+    account = Account(
+        id=uuid4(),
+        name="Old Card",
+        opened_at=date(2022, 3, 1),
+        closed_at=date(2024, 6, 30),
+        created_at=_tz_now(),
+        updated_at=_tz_now(),
+    )
+
+    assert not account.is_open_on(date(2022, 2, 28))
+    assert account.is_open_on(date(2022, 3, 1))
+    assert account.is_open_on(date(2024, 6, 30))
+    assert not account.is_open_on(date(2024, 7, 1))
+
+    with pytest.raises(ValidationError):
+        Account(
+            id=uuid4(),
+            name="Old Card",
+            opened_at=date(2024, 6, 30),
+            closed_at=date(2022, 3, 1),
+            created_at=_tz_now(),
+            updated_at=_tz_now(),
+        )
+
+
 def test_transaction_normalizes_amount_and_tags() -> None:
     account_id: UUID = uuid4()
     transaction = Transaction(
@@ -100,6 +139,41 @@ def test_transaction_normalizes_amount_and_tags() -> None:
     assert zero_amount_transaction.amount == Decimal("0")
 
 
+@pytest.mark.parametrize(
+    "variant",
+    ["dining out", "Dining Out", "  DINING   OUT ", "#dining out", "# Dining\tOut", "dining\u00a0out"],
+)
+def test_normalize_tag_merges_variants(variant: str) -> None:
+    assert normalize_tag(variant) == "dining out"
+
+
+def _random_tag(rng: random.Random) -> str:
+    alphabet = "aAbBéÉ#  \t-_"
+    return "".join(rng.choice(alphabet) for _ in range(rng.randint(0, 8)))
+
+
+def test_normalize_tag_is_idempotent() -> None:
+    rng = random.Random(20240401)
+    for _ in range(2000):
+        tag = _random_tag(rng)
+        normalized = normalize_tag(tag)
+        assert normalize_tag(normalized) == normalized
+        assert normalized == normalized.strip().lower()
+        assert "  " not in normalized
+
+
+def test_normalize_tags_dedupes_and_drops_empty() -> None:
+    rng = random.Random(7)
+    for _ in range(500):
+        tags = [_random_tag(rng) for _ in range(rng.randint(0, 6))]
+        normalized = normalize_tags(tags)
+        assert normalize_tags(normalized) == normalized
+        assert len(set(normalized)) == len(normalized)
+        assert "" not in normalized
+
+    assert normalize_tags([" Dining ", "#dining", "", "Fuel", None]) == ["dining", "fuel"]
+
+
 def test_balance_snapshot_requires_timezone_aware_datetime() -> None:
     """Test that created_at/updated_at require timezone but snapshot_time is naive (local)."""
     account_id = uuid4()
@@ -366,3 +440,173 @@ def test_transaction_dedup_key_respects_sign() -> None:
     assert (
         tx_purchase.external_ids["fingerprint"] != tx_refund.external_ids["fingerprint"]
     )
+
+
+def _coffee(account_id: UUID) -> Transaction:
+    tx_date = date(2025, 10, 4)
+    return Transaction(
+        id=uuid4(),
+        account_id=account_id,
+        amount=Decimal("-4.50"),
+        description="Coffee",
+        transaction_date=tx_date,
+        posted_date=tx_date,
+        created_at=_tz_now(),
+        updated_at=_tz_now(),
+    )
+
+
+def test_fingerprint_v2_keeps_identical_rows_distinct() -> None:
+    """Test that v2 indexes identical rows within a batch so both survive dedup."""
+    account_id = uuid4()
+    first, second = _coffee(account_id), _coffee(account_id)
+    assert first.external_ids["fingerprint"] == second.external_ids["fingerprint"]
+
+    v2_first, v2_second = assign_fingerprints([first, second], 2)
+
+    assert v2_first.external_ids["fingerprint"] != v2_second.external_ids["fingerprint"]
+    assert v2_first.fingerprint_version == 2
+    assert v2_first.external_ids["fingerprint_version"] == "2"
+
+    # Re-fingerprinting the same batch is deterministic
+    again = assign_fingerprints([first, second], 2)
+    assert [tx.external_ids["fingerprint"] for tx in again] == [
+        v2_first.external_ids["fingerprint"],
+        v2_second.external_ids["fingerprint"],
+    ]
+
+
+def test_fingerprint_v1_is_default_and_unversioned() -> None:
+    """Test that v1 stays the default and round-trips from v2."""
+    tx = _coffee(uuid4())
+    assert tx.fingerprint_version == 1
+    assert "fingerprint_version" not in tx.external_ids
+
+    back_to_v1 = tx.with_fingerprint(2).with_fingerprint(1)
+    assert back_to_v1.external_ids == tx.external_ids
+
+
+def test_fingerprint_unsupported_version() -> None:
+    """Test that unknown fingerprint versions are rejected."""
+    with pytest.raises(ValueError):
+        assign_fingerprints([_coffee(uuid4())], 3)
+
+
+def test_default_tags_merge_without_duplicates() -> None:
+    """Test that account default tags follow a transaction's own tags, each once."""
+    hsa = Account(
+        id=uuid4(),
+        name="HSA",
+        default_tags=["Health", "#medical", "health"],
+        created_at=_tz_now(),
+        updated_at=_tz_now(),
+    )
+    other_account = uuid4()
+    assert hsa.default_tags == ("health", "medical")
+
+    # "reimbursable" as a rule or the user would have added it
+    tagged = _coffee(hsa.id).model_copy(update={"tags": ("reimbursable", "health")})
+    untagged = _coffee(hsa.id)
+    elsewhere = _coffee(other_account)
+
+    result = apply_default_tags([tagged, untagged, elsewhere], [hsa])
+
+    assert [tx.tags for tx in result] == [
+        ("reimbursable", "health", "medical"),
+        ("health", "medical"),
+        (),
+    ]
+    assert result[2] is elsewhere
+    # Applying again changes nothing
+    assert apply_default_tags(result, [hsa]) == result
+
+
+def test_amount_range_matches_the_money_columns() -> None:
+    assert MAX_AMOUNT == Decimal("9999999999999999.99")
+    assert amount_in_range(Decimal("10000000000000.00"))  # Past the old DECIMAL(15,2)
+    assert amount_in_range(MAX_AMOUNT)
+    assert amount_in_range(-MAX_AMOUNT)
+    assert not amount_in_range(MAX_AMOUNT + Decimal("0.01"))
+    assert not amount_in_range(Decimal("-1E+17"))
+    assert not amount_in_range(Decimal("Infinity"))
+    assert not amount_in_range(Decimal("NaN"))
+
+
+def _coffee(amount) -> Transaction:
+    return Transaction(
+        id=uuid4(),
+        account_id=uuid4(),
+        amount=amount,
+        description="Coffee",
+        transaction_date=date(2025, 3, 1),
+        posted_date=date(2025, 3, 1),
+        created_at=_tz_now(),
+        updated_at=_tz_now(),
+    )
+
+
+def test_money_serializes_as_strings_with_two_decimal_places() -> None:
+    account = Account(
+        id=uuid4(),
+        name="Checking",
+        balance=Decimal("1200"),
+        created_at=_tz_now(),
+        updated_at=_tz_now(),
+    )
+    snapshot = BalanceSnapshot(
+        id=uuid4(),
+        account_id=account.id,
+        balance=Decimal("-0.5"),
+        snapshot_time=datetime(2025, 3, 1, 12, 0),
+        created_at=_tz_now(),
+        updated_at=_tz_now(),
+    )
+
+    assert _coffee(Decimal("-4.5")).model_dump(mode="json")["amount"] == "-4.50"
+    assert account.model_dump(mode="json")["balance"] == "1200.00"
+    assert snapshot.model_dump(mode="json")["balance"] == "-0.50"
+    assert account.model_copy(update={"balance": None}).model_dump(mode="json")["balance"] is None
+    # Python-mode dumps keep the Decimal
+    assert _coffee(Decimal("-4.5")).model_dump()["amount"] == Decimal("-4.5")
+
+
+def test_money_accepts_strings_and_numbers() -> None:
+    assert _coffee("-4.50").amount == Decimal("-4.50")
+    assert _coffee(-4.5).amount == Decimal("-4.5")
+    assert _coffee(12).amount == Decimal("12")
+
+
+def test_largest_amount_survives_a_json_round_trip() -> None:
+    """17 significant digits is past what a JavaScript number holds exactly."""
+    transaction = _coffee(-MAX_AMOUNT)
+
+    encoded = transaction.model_dump_json()
+    assert json.loads(encoded)["amount"] == "-9999999999999999.99"
+    assert Transaction.model_validate_json(encoded).amount == -MAX_AMOUNT
+
+
+def test_json_numbers_context_writes_amounts_as_numbers(monkeypatch) -> None:
+    transaction = _coffee(Decimal("-4.50"))
+
+    assert transaction.model_dump(mode="json", context={"json_numbers": True})["amount"] == -4.5
+    # Without the context (e.g. JSON stored in the database) amounts stay exact,
+    # whatever the environment says
+    monkeypatch.setenv("TREELINE_JSON_NUMBERS", "1")
+    assert json.loads(transaction.model_dump_json())["amount"] == "-4.50"
+
+
+def test_money_uses_the_currency_minor_units() -> None:
+    account = Account(
+        id=uuid4(),
+        name="Yucho",
+        currency="jpy",
+        balance=Decimal("1200000"),
+        created_at=_tz_now(),
+        updated_at=_tz_now(),
+    )
+
+    assert account.model_dump(mode="json")["balance"] == "1200000"
+    assert money_json(Decimal("-450"), "KRW") == "-450"
+    assert money_json(Decimal("3"), "EUR") == "3.00"
+    assert money_json(Decimal("3")) == "3.00"
+    assert _coffee(Decimal("-450")).model_dump(mode="json", context={"currency": "JPY"})["amount"] == "-450"
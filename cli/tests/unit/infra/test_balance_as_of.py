@@ -0,0 +1,190 @@
+"""Unit tests for DuckDBRepository.get_balance_as_of and its agreement with backfill."""
+
+from datetime import date, datetime, timezone
+from decimal import Decimal
+from uuid import uuid4
+
+import pytest
+
+from treeline.app.backfill_service import BackfillService
+from treeline.domain import BalanceSnapshot, Transaction
+
+from tests.support.factories import make_account, make_transaction
+
+
+async def add_snapshot(repository, account_id, day: date, balance: str) -> None:
+    now = datetime.now(timezone.utc)
+    await repository.add_balance(
+        BalanceSnapshot(
+            id=uuid4(),
+            account_id=account_id,
+            balance=Decimal(balance),
+            snapshot_time=datetime(day.year, day.month, day.day, 23, 59, 59),
+            created_at=now,
+            updated_at=now,
+            source="sync",
+        )
+    )
+
+
+async def add_transactions(repository, account_id, *entries: tuple[date, str]) -> None:
+    await repository.bulk_upsert_transactions(
+        [make_transaction(account_id, amount, day) for day, amount in entries]
+    )
+
+
+@pytest.mark.asyncio
+async def test_exact_snapshot_hit(repository, account_id):
+    """Test that a snapshot on the date is returned as-is."""
+    await add_snapshot(repository, account_id, date(2024, 3, 31), "1500.00")
+    await add_transactions(repository, account_id, (date(2024, 3, 31), "-20.00"))
+
+    result = await repository.get_balance_as_of(account_id, date(2024, 3, 31))
+
+    assert result.success
+    assert result.data["status"] == "snapshot"
+    assert result.data["balance"] == Decimal("1500.00")
+    assert result.data["reconstructed"] is False
+
+
+@pytest.mark.asyncio
+async def test_forward_reconstruction_from_earlier_snapshot(repository, account_id):
+    """Test adding the transactions after an earlier snapshot."""
+    await add_snapshot(repository, account_id, date(2024, 3, 1), "1000.00")
+    await add_transactions(
+        repository,
+        account_id,
+        (date(2024, 3, 1), "-99.00"),  # already in the snapshot
+        (date(2024, 3, 2), "-50.00"),
+        (date(2024, 3, 3), "200.00"),
+        (date(2024, 3, 10), "-75.00"),  # after the target date
+    )
+
+    result = await repository.get_balance_as_of(account_id, date(2024, 3, 4))
+
+    assert result.success
+    assert result.data["status"] == "reconstructed"
+    assert result.data["balance"] == Decimal("1150.00")
+    assert result.data["transactions_applied"] == 2
+    assert result.data["anchor"]["balance"] == Decimal("1000.00")
+
+
+@pytest.mark.asyncio
+async def test_backward_reconstruction_from_later_snapshot(repository, account_id):
+    """Test subtracting the transactions up to a later snapshot."""
+    await add_transactions(
+        repository,
+        account_id,
+        (date(2024, 2, 28), "-10.00"),
+        (date(2024, 3, 1), "-40.00"),
+        (date(2024, 3, 2), "500.00"),
+    )
+    await add_snapshot(repository, account_id, date(2024, 3, 2), "2000.00")
+
+    result = await repository.get_balance_as_of(account_id, date(2024, 2, 29))
+
+    assert result.success
+    assert result.data["status"] == "reconstructed"
+    assert result.data["balance"] == Decimal("1540.00")
+    assert result.data["transactions_applied"] == 2
+
+
+@pytest.mark.asyncio
+async def test_unknown_before_any_data(repository, account_id):
+    """Test that a date before any history is unknown, not zero."""
+    await add_snapshot(repository, account_id, date(2024, 3, 1), "1000.00")
+    await add_transactions(repository, account_id, (date(2024, 3, 1), "-10.00"))
+
+    result = await repository.get_balance_as_of(account_id, date(2023, 12, 31))
+
+    assert result.success
+    assert result.data["status"] == "unknown"
+    assert result.data["balance"] is None
+    assert result.data["anchor"] is None
+
+
+@pytest.mark.asyncio
+async def test_unknown_without_snapshots(repository, account_id):
+    """Test that transactions alone don't produce a balance."""
+    await add_transactions(repository, account_id, (date(2024, 3, 1), "-10.00"))
+
+    result = await repository.get_balance_as_of(account_id, date(2024, 3, 5))
+
+    assert result.success
+    assert result.data["status"] == "unknown"
+    assert result.data["balance"] is None
+
+
+@pytest.mark.asyncio
+async def test_balances_for_several_accounts_and_dates(repository, account_id):
+    """Test that the batched lookup matches get_balance_as_of for every pair."""
+    savings = make_account("Savings")
+    await repository.add_account(savings)
+    await add_snapshot(repository, account_id, date(2024, 3, 1), "1000.00")
+    await add_snapshot(repository, savings.id, date(2024, 3, 10), "500.00")
+    await add_transactions(
+        repository, account_id, (date(2024, 3, 2), "-50.00"), (date(2024, 3, 9), "20.00")
+    )
+    await add_transactions(
+        repository, savings.id, (date(2024, 3, 3), "30.00"), (date(2024, 3, 5), "100.00")
+    )
+    days = [date(2024, 2, 1), date(2024, 3, 4), date(2024, 3, 10)]
+
+    result = await repository.get_balances_as_of([account_id, savings.id], days)
+
+    assert result.success, result.error
+    balances = {
+        (key, day): entry["balance"]
+        for key, by_date in result.data.items()
+        for day, entry in by_date.items()
+    }
+    assert balances == {
+        (account_id, date(2024, 2, 1)): None,
+        (account_id, date(2024, 3, 4)): Decimal("950.00"),
+        (account_id, date(2024, 3, 10)): Decimal("970.00"),
+        (savings.id, date(2024, 2, 1)): None,
+        (savings.id, date(2024, 3, 4)): Decimal("400.00"),
+        (savings.id, date(2024, 3, 10)): Decimal("500.00"),
+    }
+    for (key, day), balance in balances.items():
+        assert (await repository.get_balance_as_of(key, day)).data["balance"] == balance
+
+
+@pytest.mark.asyncio
+async def test_backfill_agrees_with_balance_as_of(repository, account_id):
+    """Test that backfilled snapshots are the balances get_balance_as_of reconstructs."""
+    def tx(day: date, amount: str, **fields) -> Transaction:
+        return make_transaction(account_id, amount, day, **fields)
+
+    deleted = tx(date(2024, 3, 8), "-5.00")
+    parent = tx(date(2024, 3, 7), "-100.00")
+    await repository.bulk_upsert_transactions(
+        [
+            tx(date(2024, 3, 2), "-10.00"),
+            tx(date(2024, 3, 5), "-20.00"),
+            tx(date(2024, 3, 5), "200.00"),
+            parent,
+            tx(date(2024, 3, 7), "-60.00", parent_transaction_id=parent.id),
+            tx(date(2024, 3, 7), "-40.00", parent_transaction_id=parent.id),
+            deleted,
+            tx(date(2024, 3, 10), "-1.00"),
+            tx(date(2024, 3, 12), "-15.00"),
+        ]
+    )
+    await repository.delete_transactions([deleted.id])
+    await add_snapshot(repository, account_id, date(2024, 3, 10), "1000.00")
+    days = [date(2024, 3, 2), date(2024, 3, 5), date(2024, 3, 7), date(2024, 3, 12)]
+    expected = {}
+    for day in days:
+        expected[day] = (await repository.get_balance_as_of(account_id, day)).data["balance"]
+
+    result = await BackfillService(repository).backfill_balances()
+
+    assert result.success, result.error
+    snapshots = (await repository.get_balance_snapshots(account_id)).data
+    backfilled = {s.snapshot_time.date(): s.balance for s in snapshots if s.source == "backfill"}
+    assert backfilled == expected
+    assert backfilled[date(2024, 3, 7)] == Decimal("1001.00")
+    assert backfilled[date(2024, 3, 5)] == Decimal("1101.00")
+    assert backfilled[date(2024, 3, 2)] == Decimal("921.00")
+    assert backfilled[date(2024, 3, 12)] == Decimal("985.00")
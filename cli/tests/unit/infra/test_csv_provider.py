@@ -1,5 +1,6 @@
 """Unit tests for CSVProvider."""
 
+import json
 import tempfile
 from datetime import date, datetime, timezone
 from decimal import Decimal
@@ -8,7 +9,12 @@ from pathlib import Path
 import pytest
 
 from treeline.domain import Ok, Fail
-from treeline.infra.csv import CSVProvider
+from treeline.infra.csv import CSVProvider, load_presets, match_preset
+
+# Shared with the desktop app's Rust port of column detection (detect_csv_columns)
+DETECTION_FIXTURES = Path(__file__).parents[2] / "fixtures" / "csv_detection"
+# One export per bundled institution preset, also checked by the desktop app
+PRESET_FIXTURES = Path(__file__).parents[2] / "fixtures" / "csv_presets"
 
 
 @pytest.mark.asyncio
@@ -575,6 +581,35 @@ def test_detect_columns_with_currency_suffix():
         Path(csv_path).unlink()
 
 
+@pytest.mark.asyncio
+async def test_currency_column_values_are_kept_uppercased():
+    """Test that a row's currency is passed on for ImportService, and blank cells are not."""
+    provider = CSVProvider()
+
+    csv_content = """Date,Description,Amount,Currency
+2024-10-01,Dividend,12.50,cad
+2024-10-02,Commission,-9.99,
+"""
+
+    with tempfile.NamedTemporaryFile(mode="w", suffix=".csv", delete=False) as f:
+        f.write(csv_content)
+        csv_path = f.name
+
+    try:
+        mapping = {"date": "Date", "description": "Description", "amount": "Amount", "currency": "Currency"}
+        result = await provider.get_transactions(
+            start_date=datetime.min,
+            end_date=datetime.now(),
+            provider_settings={"file_path": csv_path, "column_mapping": mapping},
+        )
+
+        assert result.success
+        assert result.data[0].external_ids["currency"] == "CAD"
+        assert "currency" not in result.data[1].external_ids
+    finally:
+        Path(csv_path).unlink()
+
+
 def test_detect_columns_with_extra_words():
     """Test column detection with extra words like 'Transaction Amount'."""
     provider = CSVProvider()
@@ -745,3 +780,277 @@ async def test_get_transactions_with_debit_negative_flag():
         assert transactions[2].amount == Decimal("2500.00")  # Paycheck
     finally:
         Path(csv_path).unlink()
+
+
+@pytest.mark.asyncio
+async def test_get_transactions_records_csv_source():
+    """Test that each transaction records the file hash, line and raw row."""
+    import hashlib
+    import json
+
+    provider = CSVProvider()
+
+    csv_content = """Date,Description,Amount
+2024-10-01,"Coffee
+at Starbucks",-5.50
+not-a-date,Skipped,1.00
+2024-10-03,Salary Deposit,2500.00
+"""
+
+    with tempfile.NamedTemporaryFile(mode="w", suffix=".csv", delete=False) as f:
+        f.write(csv_content)
+        csv_path = f.name
+
+    try:
+        result = await provider.get_transactions(
+            start_date=datetime.min,
+            end_date=datetime.max,
+            provider_account_ids=[],
+            provider_settings={
+                "file_path": csv_path,
+                "column_mapping": {"date": "Date", "description": "Description", "amount": "Amount"},
+                "import_id": "batch-1",
+            },
+        )
+
+        assert result.success
+        sources = [json.loads(tx.external_ids["csv_source"]) for tx in result.data]
+        expected_hash = hashlib.sha256(Path(csv_path).read_bytes()).hexdigest()
+
+        assert [source["line"] for source in sources] == [2, 5]
+        assert all(source["file_sha256"] == expected_hash for source in sources)
+        assert all(source["import_id"] == "batch-1" for source in sources)
+        assert sources[1]["row"] == "2024-10-03,Salary Deposit,2500.00"
+    finally:
+        Path(csv_path).unlink()
+
+
+def test_detection_report_explains_mapping():
+    """Test that the report lists every header with the pattern it matched."""
+    provider = CSVProvider()
+
+    csv_content = """Transaction Date,Merchant,Debit,Credit,Account Number
+2024-10-01,Coffee Shop,5.50,
+2024-10-02,Refund,,10.00
+"""
+
+    with tempfile.NamedTemporaryFile(mode="w", suffix=".csv", delete=False) as f:
+        f.write(csv_content)
+        csv_path = f.name
+
+    try:
+        result = provider.detection_report(csv_path)
+
+        assert result.success
+        report = result.data
+        assert report.delimiter == ","
+        assert report.encoding == "utf-8"
+        assert report.header_row == 0
+        assert report.column_mapping == provider.detect_columns(csv_path).data
+
+        headers = {match.header: match for match in report.headers}
+        assert (headers["Transaction Date"].pattern, headers["Transaction Date"].used) == ("date", True)
+        assert (headers["Merchant"].field, headers["Merchant"].pattern) == ("description", "merchant")
+        assert headers["Debit"].used and headers["Credit"].used
+        assert headers["Account Number"].pattern is None
+        assert headers["Account Number"].used is False
+    finally:
+        Path(csv_path).unlink()
+
+
+def test_detection_report_date_format_evidence():
+    """Test that ambiguous and inconsistently parsed dates are reported."""
+    provider = CSVProvider()
+
+    csv_content = """Date,Description,Amount
+01/10/2024,Coffee,-5.50
+13/10/2024,Grocery,-45.00
+"""
+
+    with tempfile.NamedTemporaryFile(mode="w", suffix=".csv", delete=False) as f:
+        f.write(csv_content)
+        csv_path = f.name
+
+    try:
+        report = provider.detection_report(csv_path).data
+
+        assert report.date_samples == ["01/10/2024", "13/10/2024"]
+        assert report.date_format_candidates == ["%d/%m/%Y"]
+        assert report.date_format == "%d/%m/%Y"
+        # The first row is still read month-first, which is the misfire to surface
+        assert report.date_format_mixed is True
+        assert report.rows[0].fields["date"].parsed == "2024-01-10"
+    finally:
+        Path(csv_path).unlink()
+
+
+def test_detection_report_debit_verdict_and_parsed_rows():
+    """Test the debit sign verdict and the raw to parsed values of sampled rows."""
+    provider = CSVProvider()
+
+    csv_content = """Date,Description,Debit,Credit
+2024-10-01,Coffee,5.50,
+2024-10-02,Grocery,"1,045.00",
+2024-10-03,Paycheck,,2500.00
+2024-10-04,Rent,1500.00,
+"""
+
+    with tempfile.NamedTemporaryFile(mode="w", suffix=".csv", delete=False) as f:
+        f.write(csv_content)
+        csv_path = f.name
+
+    try:
+        report = provider.detection_report(csv_path, debit_negative=True).data
+
+        assert report.negate_debits is True
+        assert report.debit_samples == ["5.50", "1,045.00", "1500.00"]
+
+        assert [row.line for row in report.rows] == [2, 3, 4]
+        grocery = report.rows[1]
+        assert grocery.fields["debit"].raw == "1,045.00"
+        assert grocery.fields["debit"].parsed == "1045.00"
+        assert grocery.amount == Decimal("-1045.00")
+        assert report.rows[2].amount == Decimal("2500.00")
+    finally:
+        Path(csv_path).unlink()
+
+
+def test_detection_report_with_given_mapping():
+    """Test that a given mapping is explained instead of the detected one."""
+    provider = CSVProvider()
+
+    csv_content = """When,What,How Much
+2024-10-01,Coffee,-5.50
+oops,Broken,1.00
+"""
+
+    with tempfile.NamedTemporaryFile(mode="w", suffix=".csv", delete=False) as f:
+        f.write(csv_content)
+        csv_path = f.name
+
+    try:
+        mapping = {"date": "When", "description": "What", "amount": "How Much"}
+        report = provider.detection_report(csv_path, column_mapping=mapping).data
+
+        assert report.column_mapping == mapping
+        assert all(match.used for match in report.headers)
+        assert report.negate_debits is None
+        assert report.rows[0].amount == Decimal("-5.50")
+        assert report.rows[1].fields["date"].parsed is None
+        assert report.rows[1].error == "Failed to parse date: oops"
+    finally:
+        Path(csv_path).unlink()
+
+
+def test_detect_columns_matches_shared_fixtures():
+    """Test detection against the fixtures the desktop app's port is also checked against."""
+    provider = CSVProvider()
+    expected = json.loads((DETECTION_FIXTURES / "expected.json").read_text())
+
+    assert sorted(expected) == sorted(p.name for p in DETECTION_FIXTURES.glob("*.csv"))
+    for name, mapping in expected.items():
+        result = provider.detect_columns(str(DETECTION_FIXTURES / name))
+
+        assert result.success
+        assert {k: v for k, v in result.data.items() if v} == mapping, name
+
+
+@pytest.mark.asyncio
+async def test_presets_resolve_and_import_their_fixtures():
+    """Test that each preset's export gets the preset's mapping and signs, and imports correctly."""
+    provider = CSVProvider()
+    expected = json.loads((PRESET_FIXTURES / "expected.json").read_text())
+
+    assert sorted(expected) == sorted(p.name for p in PRESET_FIXTURES.glob("*.csv"))
+    assert sorted(e["preset"] for e in expected.values()) == sorted(p.id for p in load_presets())
+    for name, case in expected.items():
+        path = str(PRESET_FIXTURES / name)
+
+        preset = provider.detect_preset(path).data
+        assert preset.id == case["preset"], name
+        assert (preset.flip_signs, preset.debit_negative) == (
+            case["flip_signs"], case["debit_negative"]
+        ), name
+        assert provider.detect_columns(path).data == case["column_mapping"], name
+
+        result = await provider.get_transactions(
+            start_date=datetime.min,
+            end_date=datetime.max,
+            provider_settings={
+                "file_path": path,
+                "column_mapping": preset.column_mapping,
+                "flip_signs": preset.flip_signs,
+                "debit_negative": preset.debit_negative,
+            },
+        )
+        assert result.success, name
+        assert [
+            [tx.transaction_date.isoformat(), tx.description, str(tx.amount)]
+            for tx in result.data
+        ] == case["rows"], name
+
+
+def test_preset_match_ignores_header_order_case_and_bom():
+    """Test that a preset matches the same header names in another order or case only."""
+    headers = ["\ufeffstatus", "DESCRIPTION", "Date", "Credit", "Debit"]
+
+    preset = match_preset(headers)
+
+    assert preset.id == "citi_credit_card"
+    assert preset.column_mapping == {
+        "date": "Date", "description": "DESCRIPTION", "debit": "Debit", "credit": "Credit",
+    }
+    # An extra column is a different format
+    assert match_preset(headers + ["Memo"]) is None
+
+
+def test_detection_report_names_the_preset():
+    """Test that the report says which preset was applied, and not for a given mapping."""
+    provider = CSVProvider()
+    path = str(PRESET_FIXTURES / "capital_one.csv")
+
+    report = provider.detection_report(path).data
+
+    assert report.preset.name == "Capital One export"
+    assert report.column_mapping == report.preset.column_mapping
+    headers = {match.header: match for match in report.headers}
+    assert headers["Debit"].pattern == "preset:capital_one" and headers["Debit"].used
+    assert not headers["Card No."].used
+    # Parsed with the preset's debit convention
+    assert report.rows[0].amount == Decimal("-45.10")
+
+    given = provider.detection_report(
+        path, {"date": "Posted Date", "description": "Description", "debit": "Debit"}
+    ).data
+    assert given.preset is None
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize(
+    "content, encoding, delimiter",
+    [
+        ("Date;Description;Amount\n2024-10-01;Café;-5.50\n", "utf-8-sig", ";"),
+        ("Date;Description;Amount\n2024-10-01;Café;-5.50\n", "latin-1", ";"),
+        ("Date\tDescription\tAmount\n2024-10-01\tCafé\t-5.50\n", "utf-8", "\t"),
+    ],
+)
+async def test_detection_report_sniffs_encoding_and_delimiter(
+    tmp_path, content, encoding, delimiter
+):
+    """Test that a BOM, a non-UTF-8 export and other delimiters are detected and imported."""
+    provider = CSVProvider()
+    csv_path = tmp_path / "export.csv"
+    csv_path.write_bytes(content.encode(encoding))
+
+    report = provider.detection_report(csv_path).data
+
+    assert (report.encoding, report.delimiter) == (encoding, delimiter)
+    assert [match.header for match in report.headers] == ["Date", "Description", "Amount"]
+    assert report.rows[0].amount == Decimal("-5.50")
+
+    result = await provider.get_transactions(
+        datetime(2024, 1, 1, tzinfo=timezone.utc),
+        datetime(2024, 12, 31, tzinfo=timezone.utc),
+        provider_settings={"file_path": str(csv_path), "column_mapping": report.column_mapping},
+    )
+    assert [(tx.description, tx.amount) for tx in result.data] == [("Café", Decimal("-5.50"))]
@@ -0,0 +1,105 @@
+"""Unit tests for the description search column and its query rewrite."""
+
+import time
+from datetime import datetime, timedelta, timezone
+
+import pytest
+
+from treeline.infra.demo import DemoDataProvider
+
+from tests.support.factories import make_transaction
+
+BENCHMARK_ROWS = 500_000
+
+# The search as written before description_search existed
+UNINDEXED_CLAUSE = "(LOWER(description) LIKE ?)"
+
+
+def matching_ids(repository, where_sql: str, params: list) -> list:
+    conn = repository._get_connection(read_only=True)
+    rows = conn.execute(
+        f"SELECT transaction_id FROM sys_transactions WHERE {where_sql} ORDER BY transaction_id",
+        params,
+    ).fetchall()
+    conn.close()
+    return [row[0] for row in rows]
+
+
+def best_time(repository, where_sql: str, params: list, runs: int = 5) -> float:
+    """Fastest of several runs, so one slow run doesn't decide the comparison."""
+    conn = repository._get_connection(read_only=True)
+    timings = []
+    for _ in range(runs):
+        started = time.perf_counter()
+        conn.execute(f"SELECT transaction_id FROM sys_transactions WHERE {where_sql}", params).fetchall()
+        timings.append(time.perf_counter() - started)
+    conn.close()
+    return min(timings)
+
+
+@pytest.mark.asyncio
+async def test_search_is_case_insensitive_for_tokens_and_phrases(repository, account_id):
+    """Test that single-token and multi-word searches both ignore case."""
+    netflix = make_transaction(account_id, description="NETFLIX.COM Subscription")
+    whole_foods = make_transaction(account_id, description="Whole Foods Market")
+    await repository.bulk_upsert_transactions([netflix, whole_foods])
+
+    token_result = await repository.get_transactions_filtered(search="Netflix")
+    phrase_result = await repository.get_transactions_filtered(search="whole FOODS")
+
+    assert [tx.id for tx in token_result.data] == [netflix.id]
+    assert [tx.id for tx in phrase_result.data] == [whole_foods.id]
+
+
+@pytest.mark.asyncio
+async def test_rows_without_search_column_are_found_and_rebuilt(repository, account_id):
+    """Test that rows written outside the repository still match, and rebuild fills them in."""
+    tx = make_transaction(account_id, description="Trader Joe's")
+    await repository.add_transaction(tx)
+    conn = repository._get_connection()
+    conn.execute("UPDATE sys_transactions SET description_search = NULL")
+    conn.close()
+
+    result = await repository.get_transactions_filtered(search="trader")
+    assert [found.id for found in result.data] == [tx.id]
+
+    rebuilt = await repository.rebuild_search_index()
+    assert rebuilt.data == 1
+    assert (await repository.rebuild_search_index()).data == 0
+
+
+@pytest.mark.asyncio
+async def test_indexed_search_is_faster_with_identical_results(repository, account_id):
+    """Benchmark the rewritten search against the LOWER() scan over 500k demo rows."""
+    end = datetime.now(timezone.utc)
+    demo_result = await DemoDataProvider().get_transactions(end - timedelta(days=365), end)
+    descriptions = sorted({tx.description for _, tx in demo_result.data})
+
+    conn = repository._get_connection()
+    conn.execute(
+        """
+        INSERT INTO sys_transactions (
+            transaction_id, account_id, amount, description,
+            transaction_date, posted_date, tags, external_ids
+        )
+        SELECT
+            uuid()::VARCHAR, ?, -1.00, list_element(?::VARCHAR[], (i % ?)::INTEGER + 1),
+            DATE '2024-01-01' + (i % 365)::INTEGER, DATE '2024-01-01' + (i % 365)::INTEGER,
+            [], '{}'
+        FROM range(?) r(i)
+        """,
+        [str(account_id), descriptions, len(descriptions), BENCHMARK_ROWS],
+    )
+    conn.close()
+    assert (await repository.rebuild_search_index()).data == BENCHMARK_ROWS
+
+    for search in ("Netflix", "starbucks", "market"):
+        indexed_sql, indexed_params = repository._description_search_clause(search)
+        unindexed_params = [f"%{search.lower()}%"]
+
+        indexed_ids = matching_ids(repository, indexed_sql, indexed_params)
+        assert indexed_ids == matching_ids(repository, UNINDEXED_CLAUSE, unindexed_params)
+
+        indexed_time = best_time(repository, indexed_sql, indexed_params)
+        unindexed_time = best_time(repository, UNINDEXED_CLAUSE, unindexed_params)
+        assert indexed_time < unindexed_time / 2, (search, indexed_time, unindexed_time)
@@ -0,0 +1,317 @@
+"""Unit tests for DuckDBRepository lookups, balance snapshots and queries."""
+
+from datetime import date, datetime, timedelta, timezone
+from decimal import Decimal
+from uuid import uuid4
+
+import pytest
+
+from tests.support.factories import make_account, make_snapshot, make_transaction
+
+
+@pytest.mark.asyncio
+async def test_external_id_lookup_matches_provider_key(repository, account_id):
+    """Test that a lookup only matches the requested provider's key."""
+    simplefin_tx = make_transaction(account_id, external_ids={"simplefin": "TRN-1"})
+    await repository.bulk_upsert_transactions([simplefin_tx])
+
+    result = await repository.get_transactions_by_external_ids("simplefin", ["TRN-1"])
+
+    assert result.success
+    assert [tx.id for tx in result.data] == [simplefin_tx.id]
+
+
+@pytest.mark.asyncio
+async def test_external_id_lookup_ignores_other_keys_with_colliding_values(repository, account_id):
+    """Test that values stored under other keys are never returned.
+
+    The old lookup matched the value anywhere in the serialized JSON, so a
+    CSV row whose fingerprint (or any other ID) equalled or contained a
+    SimpleFIN ID came back as a SimpleFIN match and sync skipped the new
+    transaction.
+    """
+    await repository.bulk_upsert_transactions(
+        [
+            make_transaction(account_id, external_ids={"csv": "TRN-1"}),
+            make_transaction(account_id, external_ids={"fingerprint": "TRN-1"}),
+            make_transaction(account_id, external_ids={"simplefin": "TRN-10"}),
+        ]
+    )
+
+    result = await repository.get_transactions_by_external_ids("simplefin", ["TRN-1"])
+
+    assert result.success
+    assert result.data == []
+
+
+@pytest.mark.asyncio
+async def test_external_id_lookup_with_no_ids(repository):
+    """Test that an empty ID list returns no transactions."""
+    result = await repository.get_transactions_by_external_ids("simplefin", [])
+
+    assert result.success
+    assert result.data == []
+
+
+@pytest.mark.asyncio
+async def test_execute_query_timeout_cancels_query(repository):
+    """Test that a query past its timeout is interrupted and marked cancelled."""
+    result = await repository.execute_query(
+        "SELECT SUM(a.range * b.range) FROM range(100000000) a, range(100000000) b",
+        timeout_secs=0.5,
+    )
+
+    assert not result.success
+    assert result.error == "Query cancelled after 0.5s"
+    assert result.context == {"cancelled": True}
+
+    quick = await repository.execute_query("SELECT 42", timeout_secs=0.5)
+    assert quick.data["rows"] == [(42,)]
+
+
+@pytest.mark.asyncio
+async def test_add_balance_upserts_on_snapshot_id(repository, account_id):
+    """Test that re-adding a snapshot ID overwrites it instead of failing or duplicating."""
+    created_at = datetime.now(timezone.utc) - timedelta(days=1)
+    snapshot = make_snapshot(account_id, created_at=created_at, updated_at=created_at)
+    await repository.add_balance(snapshot)
+    (original,) = (await repository.get_balance_snapshots(account_id=account_id)).data
+
+    replay = snapshot.model_copy(
+        update={"balance": Decimal("120.00"), "updated_at": datetime.now(timezone.utc)}
+    )
+    assert (await repository.bulk_add_balances([replay])).success
+
+    (stored,) = (await repository.get_balance_snapshots(account_id=account_id)).data
+    assert stored.balance == Decimal("120.00")
+    assert stored.created_at == original.created_at
+    assert stored.updated_at > original.updated_at
+
+
+@pytest.mark.asyncio
+async def test_update_balance_snapshot(repository, account_id):
+    """Test that editing a snapshot changes its balance and updated_at but keeps created_at."""
+    created_at = datetime.now(timezone.utc) - timedelta(days=1)
+    snapshot = make_snapshot(account_id, created_at=created_at, updated_at=created_at)
+    await repository.add_balance(snapshot)
+    (original,) = (await repository.get_balance_snapshots(account_id=account_id)).data
+
+    result = await repository.update_balance_snapshot(snapshot.id, Decimal("-42.10"))
+
+    assert result.success, result.error
+    assert result.data.id == snapshot.id
+    assert result.data.balance == Decimal("-42.10")
+    assert result.data.created_at == original.created_at
+    assert result.data.updated_at > original.updated_at
+    assert result.data.source == "manual"
+
+    (stored,) = (await repository.get_balance_snapshots(account_id=account_id)).data
+    assert stored.balance == Decimal("-42.10")
+    assert stored.created_at == original.created_at
+
+
+@pytest.mark.asyncio
+async def test_update_missing_balance_snapshot_fails(repository):
+    """Test that editing an unknown snapshot ID is a not-found error."""
+    missing_id = uuid4()
+
+    result = await repository.update_balance_snapshot(missing_id, Decimal("1.00"))
+
+    assert not result.success
+    assert result.error == f"Balance snapshot not found: {missing_id}"
+
+
+@pytest.mark.asyncio
+async def test_transaction_overlap_matches_date_and_amount_once(repository, account_id):
+    """Test that shared transactions are counted across accounts, each matching at most once."""
+    now = datetime.now(timezone.utc)
+    other = make_account()
+    await repository.add_account(other)
+
+    def tx(account_id, day, amount, **fields):
+        return make_transaction(account_id, amount, date(2024, 5, day), **fields)
+
+    await repository.bulk_upsert_transactions(
+        [
+            # Shared: same day and amount, different descriptions
+            tx(account_id, 1, "-12.50"),
+            tx(other.id, 1, "-12.50", description="COFFEE SHOP 123"),
+            tx(account_id, 2, "-40.00"),
+            tx(other.id, 2, "-40.00"),
+            # Two on one side, one on the other: one match
+            tx(account_id, 3, "-5.00"),
+            tx(account_id, 3, "-5.00"),
+            tx(other.id, 3, "-5.00"),
+            # Not shared
+            tx(account_id, 4, "-8.00"),
+            tx(other.id, 4, "-8.01"),
+            tx(other.id, 5, "-8.00"),
+            # Deleted transactions don't count
+            tx(account_id, 6, "-99.00"),
+            tx(other.id, 6, "-99.00", deleted_at=now),
+        ]
+    )
+
+    result = await repository.get_transaction_overlap(account_id, other.id)
+
+    assert result.success, result.error
+    assert result.data == {"a": 7, "b": 6, "shared": 3}
+
+
+def insert_raw(repository, sql: str) -> None:
+    conn = repository._get_connection()
+    conn.execute(sql)
+    conn.close()
+
+
+@pytest.mark.asyncio
+async def test_unreadable_account_row_is_skipped_and_reported(repository, account_id):
+    """Test that a corrupted account row is named in row_errors instead of failing the list."""
+    insert_raw(repository, "INSERT INTO sys_accounts (account_id, name) VALUES ('not-a-uuid', 'Broken')")
+
+    result = await repository.get_accounts()
+
+    assert result.success, result.error
+    assert [account.id for account in result.data] == [account_id]
+    (row_error,) = result.context["row_errors"]
+    assert "sys_accounts (account_id 'not-a-uuid')" in row_error
+
+
+@pytest.mark.asyncio
+async def test_unreadable_transaction_row_is_skipped_and_reported(repository, account_id):
+    """Test that a corrupted transaction is reported, never shown with a made-up ID."""
+    good = make_transaction(account_id)
+    await repository.bulk_upsert_transactions([good])
+    insert_raw(
+        repository,
+        f"""
+        INSERT INTO sys_transactions (transaction_id, account_id, amount, transaction_date, posted_date)
+        VALUES ('tx-garbage', '{account_id}', -1.00, '2024-05-02', '2024-05-02')
+        """,
+    )
+
+    result = await repository.get_transactions_by_account(account_id)
+
+    assert result.success, result.error
+    assert [tx.id for tx in result.data] == [good.id]
+    (row_error,) = result.context["row_errors"]
+    assert "sys_transactions (transaction_id 'tx-garbage')" in row_error
+
+
+@pytest.mark.asyncio
+async def test_unreadable_balance_snapshot_row_is_skipped_and_reported(repository, account_id):
+    """Test that a corrupted snapshot is reported and the readable ones are still returned."""
+    snapshot = make_snapshot(account_id)
+    await repository.add_balance(snapshot)
+    insert_raw(
+        repository,
+        f"""
+        INSERT INTO sys_balance_snapshots (snapshot_id, account_id, balance, snapshot_time)
+        VALUES ('snap-garbage', '{account_id}', 5.00, '2024-05-31 12:00:00')
+        """,
+    )
+
+    result = await repository.get_balance_snapshots(account_id=account_id)
+
+    assert result.success, result.error
+    assert [s.id for s in result.data] == [snapshot.id]
+    (row_error,) = result.context["row_errors"]
+    assert "sys_balance_snapshots (snapshot_id 'snap-garbage')" in row_error
+
+
+@pytest.mark.asyncio
+async def test_readable_rows_report_no_row_errors(repository, account_id):
+    """Test that a clean read has no row_errors context."""
+    result = await repository.get_accounts()
+
+    assert result.success
+    assert result.context is None
+
+
+@pytest.mark.asyncio
+async def test_unreadable_account_by_id_fails_naming_the_row(repository):
+    """Test that a single-row read of a corrupted account fails with its table and key."""
+    broken_id = uuid4()
+    insert_raw(
+        repository,
+        f"""
+        INSERT INTO sys_accounts (account_id, name, external_ids)
+        VALUES ('{broken_id}', 'Broken', '"not an object"')
+        """,
+    )
+
+    result = await repository.get_account_by_id(broken_id)
+
+    assert not result.success
+    assert f"sys_accounts (account_id '{broken_id}')" in result.error
+
+
+# Columns of the views before migration 018 added the computed columns
+PRE_018_VIEW_COLUMNS = {
+    "transactions": [
+        "transaction_id", "account_id", "amount", "description", "transaction_date",
+        "posted_date", "tags", "parent_transaction_id", "status", "account_name",
+        "account_type", "currency", "institution_name",
+    ],
+    "accounts": [
+        "account_id", "name", "nickname", "account_type", "currency", "balance",
+        "external_ids", "institution_name", "institution_url", "institution_domain",
+        "created_at", "updated_at", "opened_at", "closed_at",
+    ],
+}
+
+
+@pytest.mark.asyncio
+async def test_views_expose_month_display_name_and_current_balance(repository):
+    """Test the computed view columns: month, year, display_name, is_deleted, current_balance."""
+    checking = make_account("CHASE CHK 1234", nickname="Checking")
+    savings = make_account("Savings")
+    for account in (checking, savings):
+        await repository.add_account(account)
+    await repository.bulk_upsert_transactions(
+        [
+            make_transaction(checking.id, day=date(2024, 5, 1)),
+            make_transaction(savings.id, day=date(2024, 5, 1)),
+        ]
+    )
+    older = make_snapshot(checking.id, "100.00", date(2024, 5, 1))
+    newer = make_snapshot(checking.id, "250.00", date(2024, 6, 1))
+    for snapshot in (newer, older):
+        await repository.add_balance(snapshot)
+
+    transactions = await repository.execute_query(
+        "SELECT month, year, nickname, display_name, is_deleted FROM transactions "
+        "ORDER BY display_name"
+    )
+    accounts = await repository.execute_query(
+        "SELECT name, balance, current_balance FROM accounts ORDER BY name"
+    )
+
+    assert [tuple(row) for row in transactions.data["rows"]] == [
+        ("2024-05", 2024, "Checking", "Checking", False),
+        ("2024-05", 2024, None, "Savings", False),
+    ]
+    assert [tuple(row) for row in accounts.data["rows"]] == [
+        ("CHASE CHK 1234", None, Decimal("250.00")),
+        ("Savings", None, None),
+    ]
+
+
+@pytest.mark.asyncio
+async def test_queries_written_before_computed_view_columns_still_work(repository, account_id):
+    """Test that existing view columns keep their names and order and old queries run unchanged."""
+    await repository.bulk_upsert_transactions([make_transaction(account_id)])
+
+    columns = (await repository.get_view_columns()).data
+    for view, old_columns in PRE_018_VIEW_COLUMNS.items():
+        assert columns[view][: len(old_columns)] == old_columns, view
+
+    for sql in (
+        "SELECT transaction_date, description, amount FROM transactions ORDER BY transaction_date DESC LIMIT 20",
+        "SELECT account_name, SUM(amount) FROM transactions GROUP BY account_name",
+        "SELECT t.description, a.name FROM transactions t JOIN accounts a USING (account_id)",
+        "SELECT name, nickname, balance FROM accounts",
+    ):
+        result = await repository.execute_query(sql)
+        assert result.success, (sql, result.error)
+        assert len(result.data["rows"]) == 1, sql
@@ -0,0 +1,115 @@
+"""Unit tests for the shared provider HTTP client."""
+
+import asyncio
+import json
+import tempfile
+from pathlib import Path
+
+import pytest
+
+from treeline.infra.http import USER_AGENT, OfflineError, build_async_client, build_client
+
+
+@pytest.fixture
+def network_env(monkeypatch):
+    """Isolated settings dir with no proxy or offline variables inherited from the shell."""
+    for name in (
+        "TREELINE_OFFLINE",
+        "HTTP_PROXY",
+        "HTTPS_PROXY",
+        "ALL_PROXY",
+        "NO_PROXY",
+        "http_proxy",
+        "https_proxy",
+        "all_proxy",
+        "no_proxy",
+    ):
+        monkeypatch.delenv(name, raising=False)
+    with tempfile.TemporaryDirectory() as tmpdir:
+        monkeypatch.setenv("TREELINE_DIR", tmpdir)
+        yield Path(tmpdir)
+
+
+async def start_mock_proxy():
+    """Start a forwarding proxy on localhost that records each request head."""
+    requests = []
+
+    async def handle(reader, writer):
+        head = []
+        while True:
+            line = await reader.readline()
+            if line in (b"\r\n", b""):
+                break
+            head.append(line.decode().rstrip("\r\n"))
+        requests.append(head)
+        writer.write(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok")
+        await writer.drain()
+        writer.close()
+
+    server = await asyncio.start_server(handle, "127.0.0.1", 0)
+    port = server.sockets[0].getsockname()[1]
+    return server, f"http://127.0.0.1:{port}", requests
+
+
+@pytest.mark.asyncio
+async def test_env_proxy_is_used(network_env, monkeypatch):
+    """Test that HTTP_PROXY from the environment routes provider requests."""
+    server, proxy_url, requests = await start_mock_proxy()
+    monkeypatch.setenv("HTTP_PROXY", proxy_url)
+
+    async with server:
+        async with build_async_client(5.0) as client:
+            response = await client.get("http://bank.example/accounts")
+
+    assert response.text == "ok"
+    assert len(requests) == 1
+    assert requests[0][0] == "GET http://bank.example/accounts HTTP/1.1"
+    assert f"User-Agent: {USER_AGENT}" in requests[0]
+
+
+@pytest.mark.asyncio
+async def test_no_proxy_bypasses_env_proxy(network_env, monkeypatch):
+    """Test that hosts listed in NO_PROXY skip the environment proxy."""
+    server, proxy_url, requests = await start_mock_proxy()
+    monkeypatch.setenv("HTTP_PROXY", "http://127.0.0.1:9")
+    monkeypatch.setenv("NO_PROXY", "127.0.0.1")
+
+    async with server:
+        async with build_async_client(5.0) as client:
+            # The mock proxy doubles as a plain server for the direct request
+            response = await client.get(f"{proxy_url}/accounts")
+
+    assert response.text == "ok"
+    assert requests[0][0] == "GET /accounts HTTP/1.1"
+
+
+@pytest.mark.asyncio
+async def test_settings_proxy_overrides_env(network_env, monkeypatch):
+    """Test that network.proxy in settings.json takes precedence over HTTP_PROXY."""
+    server, proxy_url, requests = await start_mock_proxy()
+    monkeypatch.setenv("HTTP_PROXY", "http://127.0.0.1:9")
+    (network_env / "settings.json").write_text(json.dumps({"network": {"proxy": proxy_url}}))
+
+    async with server:
+        async with build_async_client(5.0) as client:
+            response = await client.get("http://bank.example/accounts")
+
+    assert response.text == "ok"
+    assert requests[0][0] == "GET http://bank.example/accounts HTTP/1.1"
+
+
+def test_user_agent_includes_version(network_env):
+    """Test that clients identify as treeline with the installed version."""
+    with build_client() as client:
+        assert client.headers["User-Agent"] == USER_AGENT
+    assert USER_AGENT.startswith("treeline/")
+
+
+def test_offline_mode_short_circuits(network_env, monkeypatch):
+    """Test that no client is created while offline mode is on."""
+    monkeypatch.setenv("TREELINE_OFFLINE", "1")
+
+    with pytest.raises(OfflineError):
+        build_async_client()
+    with pytest.raises(OfflineError):
+        build_client()
@@ -0,0 +1,197 @@
+"""Unit tests for DuckDBRepository write failures.
+
+Failed writes must come back as Fail with context (or raise in strict mode),
+never as a silent success with missing data.
+"""
+
+from datetime import date, datetime, timezone
+from decimal import Decimal
+from uuid import uuid4
+
+import duckdb
+import pytest
+
+from treeline.domain import BalanceSnapshot
+from treeline.infra.duckdb import MIGRATIONS_DIR, DuckDBRepository, StrictWriteError
+
+from tests.support.factories import make_account, make_transaction
+
+
+@pytest.fixture
+def db_path(repository):
+    """Path to the conftest database."""
+    return str(repository.db_path)
+
+
+@pytest.fixture
+def lenient_repository(db_path):
+    """The database with strict writes off, whatever the environment says."""
+    return DuckDBRepository(db_path, strict_writes=False)
+
+
+@pytest.fixture
+def strict_repository(db_path):
+    return DuckDBRepository(db_path, strict_writes=True)
+
+
+def count_rows(db_path: str, table: str) -> int:
+    conn = duckdb.connect(db_path)
+    try:
+        return conn.execute(f"SELECT COUNT(*) FROM {table}").fetchone()[0]
+    finally:
+        conn.close()
+
+
+@pytest.mark.asyncio
+async def test_transaction_for_unknown_account_fails_with_context(lenient_repository, db_path):
+    """Test that a foreign key violation names the transaction and writes nothing."""
+    account = make_account()
+    await lenient_repository.add_account(account)
+    good = make_transaction(account.id)
+    orphan = make_transaction(uuid4())
+
+    result = await lenient_repository.bulk_upsert_transactions([good, orphan])
+
+    assert not result.success
+    assert f"transaction {orphan.id}" in result.error
+    # All or nothing: the valid transaction was rolled back too
+    assert count_rows(db_path, "sys_transactions") == 0
+
+
+@pytest.mark.asyncio
+async def test_strict_mode_raises_on_failed_write(strict_repository, db_path):
+    """Test that strict mode raises instead of returning Fail."""
+    orphan = make_transaction(uuid4())
+
+    with pytest.raises(StrictWriteError, match=f"transaction {orphan.id}"):
+        await strict_repository.bulk_upsert_transactions([orphan])
+
+    assert count_rows(db_path, "sys_transactions") == 0
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize("settings", [{"balance": float("nan")}, {"since": date(2024, 1, 1)}])
+async def test_upsert_integration_rejects_invalid_json_settings(
+    lenient_repository, db_path, settings
+):
+    """Test that settings that aren't valid JSON fail instead of being stored."""
+    result = await lenient_repository.upsert_integration("simplefin", settings)
+
+    assert not result.success
+    assert "simplefin" in result.error
+    assert count_rows(db_path, "sys_integrations") == 0
+
+
+@pytest.mark.asyncio
+async def test_migration_fails_when_it_cannot_be_recorded(lenient_repository, db_path):
+    """Test that a migration whose sys_migrations record fails is reported and rolled back."""
+    latest = sorted(MIGRATIONS_DIR.glob("*.sql"))[-1].name
+    # Make sys_migrations unwritable (a view) and forget the latest migration
+    conn = duckdb.connect(db_path)
+    conn.execute("CREATE TABLE applied AS SELECT * FROM sys_migrations WHERE migration_name != ?", [latest])
+    conn.execute("DROP TABLE sys_migrations")
+    conn.execute("CREATE VIEW sys_migrations AS SELECT * FROM applied")
+    conn.close()
+
+    result = await lenient_repository.ensure_schema_upgraded()
+
+    assert not result.success
+    assert latest in result.error
+    conn = duckdb.connect(db_path)
+    try:
+        recorded = conn.execute("SELECT migration_name FROM applied").fetchall()
+    finally:
+        conn.close()
+    assert (latest,) not in recorded
+
+
+@pytest.mark.asyncio
+async def test_update_missing_account_fails(lenient_repository):
+    """Test that updating an account that doesn't exist isn't reported as success."""
+    account = make_account()
+
+    result = await lenient_repository.update_account_by_id(account)
+
+    assert not result.success
+    assert str(account.id) in result.error
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize(
+    "amount",
+    ["9999999999999.99", "10000000000000.00", "9999999999999999.99", "-9999999999999999.99"],
+)
+async def test_amounts_up_to_the_widened_maximum_are_stored_exactly(
+    lenient_repository, db_path, amount
+):
+    """Test that amounts at and past the old DECIMAL(15,2) maximum round-trip to the cent."""
+    account = make_account()
+    await lenient_repository.add_account(account)
+
+    result = await lenient_repository.bulk_upsert_transactions(
+        [make_transaction(account.id, amount)]
+    )
+
+    assert result.success, result.error
+    conn = duckdb.connect(db_path)
+    try:
+        stored = conn.execute("SELECT amount FROM sys_transactions").fetchone()[0]
+    finally:
+        conn.close()
+    assert stored == Decimal(amount)
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize("amount", ["10000000000000000.00", "-9999999999999999.995"])
+async def test_amounts_beyond_the_maximum_are_rejected_by_name(lenient_repository, db_path, amount):
+    """Test that an amount too large to store fails naming the record and writes nothing."""
+    account = make_account()
+    await lenient_repository.add_account(account)
+    too_large = make_transaction(account.id, amount)
+
+    result = await lenient_repository.bulk_upsert_transactions(
+        [make_transaction(account.id), too_large]
+    )
+
+    assert not result.success
+    assert result.context == {
+        "kind": "amount_out_of_range",
+        "record": "transaction",
+        "id": str(too_large.id),
+        "amount": amount,
+        "max": "9999999999999999.99",
+    }
+    assert count_rows(db_path, "sys_transactions") == 0
+
+    now = datetime.now(timezone.utc)
+    snapshot = BalanceSnapshot(
+        id=uuid4(),
+        account_id=account.id,
+        balance=Decimal(amount),
+        snapshot_time=datetime(2024, 5, 1, 12, 0),
+        created_at=now,
+        updated_at=now,
+    )
+    result = await lenient_repository.add_balance(snapshot)
+
+    assert not result.success
+    assert result.context["id"] == str(snapshot.id)
+    assert f"Balance snapshot {snapshot.id}" in result.error
+    assert count_rows(db_path, "sys_balance_snapshots") == 0
+
+
+def test_every_money_column_is_widened(db_path):
+    """Test that no money column, including the budget plugin's, is left narrower."""
+    conn = duckdb.connect(db_path)
+    try:
+        narrow = conn.execute(
+            """
+            SELECT table_name || '.' || column_name
+            FROM information_schema.columns
+            WHERE data_type LIKE 'DECIMAL%' AND data_type <> 'DECIMAL(18,2)'
+              AND table_name LIKE 'sys_%'
+            """
+        ).fetchall()
+    finally:
+        conn.close()
+    assert narrow == []
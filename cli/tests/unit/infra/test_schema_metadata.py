@@ -0,0 +1,37 @@
+"""Cross-check the schema descriptions against a freshly migrated database."""
+
+import tempfile
+from pathlib import Path
+
+import duckdb
+import pytest
+
+from treeline.infra.duckdb import DuckDBRepository
+from treeline.infra.schema_metadata import SCHEMA_METADATA
+
+
+@pytest.mark.asyncio
+async def test_metadata_matches_migrated_schema():
+    """Test that every table and column is described, and nothing described is missing."""
+    with tempfile.TemporaryDirectory() as tmpdir:
+        db_path = Path(tmpdir) / "treeline.duckdb"
+        assert (await DuckDBRepository(str(db_path)).ensure_schema_upgraded()).success
+
+        conn = duckdb.connect(str(db_path), read_only=True)
+        rows = conn.execute(
+            """
+            SELECT table_name, column_name FROM information_schema.columns
+            WHERE table_schema = 'main'
+            ORDER BY table_name, ordinal_position
+            """
+        ).fetchall()
+        conn.close()
+
+    actual: dict[str, list[str]] = {}
+    for table, column in rows:
+        actual.setdefault(table, []).append(column)
+
+    assert set(SCHEMA_METADATA) == set(actual)
+    for table, columns in actual.items():
+        assert set(SCHEMA_METADATA[table]["columns"]) == set(columns), table
+        assert SCHEMA_METADATA[table]["description"], table
@@ -0,0 +1,118 @@
+"""Unit tests for the write lock file shared by import, sync and backfill."""
+
+import asyncio
+import json
+import multiprocessing
+import os
+import tempfile
+import time
+from pathlib import Path
+
+import pytest
+
+from treeline.abstractions import write_phase
+from treeline.infra.write_lock import FileWriteLock, write_lock_path
+
+
+@pytest.fixture
+def lock_path():
+    with tempfile.TemporaryDirectory() as tmpdir:
+        yield write_lock_path(Path(tmpdir) / "treeline.duckdb")
+
+
+def hold_lock(path: str, hold_seconds: float, held) -> None:
+    """Take the lock in a child process and report when it was held."""
+
+    async def run():
+        lock = FileWriteLock(Path(path), wait_seconds=10)
+        result = await lock.acquire("import")
+        started = time.time()
+        await asyncio.sleep(hold_seconds)
+        ended = time.time()
+        lock.release()
+        return result.success, started, ended
+
+    held.put(asyncio.run(run()))
+
+
+def test_each_database_has_its_own_lock_file(tmp_path):
+    """Test that the demo and real databases don't share a lock."""
+    real = write_lock_path(tmp_path / "treeline.duckdb")
+    demo = write_lock_path(tmp_path / "demo.duckdb")
+
+    assert real == tmp_path / "treeline.duckdb.write.lock"
+    assert real != demo
+
+
+@pytest.mark.asyncio
+async def test_lock_file_names_the_holder_and_is_removed_on_release(lock_path):
+    """Test that the lock file holds our pid and operation until released."""
+    lock = FileWriteLock(lock_path, wait_seconds=0)
+
+    async with lock.held("import") as result:
+        assert result.success
+        holder = json.loads(lock_path.read_text())
+        assert (holder["pid"], holder["operation"]) == (os.getpid(), "import")
+
+    assert not lock_path.exists()
+
+
+@pytest.mark.asyncio
+async def test_nested_write_phases_share_the_lock(lock_path):
+    """Test that the lock is reentrant and held until the outermost phase ends."""
+    lock = FileWriteLock(lock_path, wait_seconds=0)
+
+    async with write_phase(lock, "sync") as outer:
+        async with write_phase(lock, "sync") as inner:
+            assert outer.success and inner.success
+        assert lock_path.exists()
+
+    assert not lock_path.exists()
+
+
+@pytest.mark.asyncio
+async def test_lock_left_by_a_dead_process_is_taken_over(lock_path):
+    """Test that a lock whose process is gone doesn't block the next writer."""
+    lock_path.write_text(json.dumps({"pid": 999999999, "operation": "sync"}))
+    lock = FileWriteLock(lock_path, wait_seconds=0)
+
+    result = await lock.acquire("import")
+
+    assert result.success
+    assert json.loads(lock_path.read_text())["pid"] == os.getpid()
+    lock.release()
+
+
+@pytest.mark.asyncio
+async def test_live_holder_times_out_naming_the_operation(lock_path):
+    """Test that waiting on a live holder gives up with kind write_locked."""
+    holder = FileWriteLock(lock_path, wait_seconds=0)
+    await holder.acquire("sync")
+
+    result = await FileWriteLock(lock_path, wait_seconds=0.2).acquire("import")
+
+    assert not result.success
+    assert result.context["kind"] == "write_locked"
+    assert f"(sync, pid {os.getpid()})" in result.error
+    assert "--wait-lock" in result.error
+    holder.release()
+    assert not lock_path.exists()
+
+
+def test_two_processes_take_turns(lock_path):
+    """Test that a second process waits until the first releases the lock."""
+    context = multiprocessing.get_context("spawn")
+    held = context.Queue()
+    processes = [
+        context.Process(target=hold_lock, args=(str(lock_path), 0.5, held)) for _ in range(2)
+    ]
+    for process in processes:
+        process.start()
+    intervals = sorted(held.get(timeout=30) for _ in processes)
+    for process in processes:
+        process.join(timeout=30)
+
+    assert all(success for success, _, _ in intervals)
+    (_, _, first_ended), (_, second_started, _) = intervals
+    assert first_ended <= second_started
+    assert not lock_path.exists()
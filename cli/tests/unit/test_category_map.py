@@ -0,0 +1,63 @@
+"""Unit tests for the provider category to tag map."""
+
+import json
+from uuid import uuid4
+
+import pytest
+
+from treeline.category_map import DEFAULT_CATEGORY_MAP, load_category_map, save_category_map
+
+from tests.support.factories import make_transaction
+
+
+def test_missing_map_is_seeded_with_the_defaults(tmp_path):
+    """Test that the first load writes the default mappings to disk."""
+    path = tmp_path / "category_map.json"
+
+    category_map = load_category_map(path)
+
+    assert category_map.mappings == DEFAULT_CATEGORY_MAP
+    assert json.loads(path.read_text()) == {
+        "mappings": dict(sorted(DEFAULT_CATEGORY_MAP.items())),
+        "unmapped": [],
+    }
+
+
+def test_user_overrides_are_kept_across_loads(tmp_path):
+    """Test that set and remove are saved, normalized, and win over the defaults."""
+    path = tmp_path / "category_map.json"
+    category_map = load_category_map(path)
+
+    assert category_map.set_mapping(" FOOD_AND_DRINK_COFFEE", "#Cafe") == "coffee"
+    assert category_map.remove_mapping("Restaurants") == "dining"
+    save_category_map(category_map, path)
+
+    reloaded = load_category_map(path)
+    assert reloaded.tag_for("food_and_drink_coffee") == "cafe"
+    assert reloaded.tag_for("Restaurants") == "restaurants"
+
+
+def test_unmapped_categories_pass_through_lowercase(tmp_path):
+    """Test that unknown categories become lowercase tags and are reported."""
+    category_map = load_category_map(tmp_path / "category_map.json")
+
+    mapped, unmapped = category_map.apply(
+        [
+            make_transaction(uuid4(), tags=["FOOD_AND_DRINK_COFFEE"]),
+            make_transaction(uuid4(), tags=["Pet Supplies"]),
+            make_transaction(uuid4()),
+        ]
+    )
+
+    assert [tx.tags for tx in mapped] == [("coffee",), ("pet supplies",), ()]
+    assert unmapped == {"pet supplies"}
+
+
+def test_invalid_map_file_is_not_overwritten(tmp_path):
+    """Test that a hand-edited file with a typo is reported rather than replaced."""
+    path = tmp_path / "category_map.json"
+    path.write_text('{"mappings": {"restaurants": "dining",}}')
+
+    with pytest.raises(ValueError, match="Invalid category map"):
+        load_category_map(path)
+    assert path.read_text() == '{"mappings": {"restaurants": "dining",}}'
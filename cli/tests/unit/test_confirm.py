@@ -0,0 +1,62 @@
+"""Unit tests for confirmation prompts."""
+
+import io
+
+from treeline.confirm import (
+    CONFIRMATION_REQUIRED,
+    INPUT_REQUIRED,
+    Confirm,
+    deletion_action,
+)
+
+
+def make_confirm(answer: str = "", **kwargs) -> tuple[Confirm, io.StringIO]:
+    writer = io.StringIO()
+    options = {"yes": False, "no_input": False, "is_tty": True, **kwargs}
+    return Confirm(reader=io.StringIO(answer), writer=writer, **options), writer
+
+
+def test_asks_with_standard_wording():
+    """Test the prompt text and that only y/yes confirm."""
+    confirm, writer = make_confirm("y\n")
+
+    result = confirm.ask(deletion_action(3))
+
+    assert result.success and result.data is True
+    assert writer.getvalue() == "This will permanently delete 3 rows. Continue? [y/N] "
+    for answer in ("\n", "n\n", "yep\n", ""):
+        assert make_confirm(answer)[0].ask("Go").data is False
+    assert make_confirm(" YES \n")[0].ask("Go").data is True
+
+
+def test_non_tty_stdin_fails_with_confirmation_required():
+    """Test that without a terminal nothing is read and the error kind is reported."""
+    confirm, writer = make_confirm("y\n", is_tty=False)
+
+    result = confirm.ask("This will delete ALL backups")
+
+    assert not result.success
+    assert result.context == {"error_kind": CONFIRMATION_REQUIRED}
+    assert "--yes" in result.error
+    assert writer.getvalue() == ""
+
+
+def test_no_input_fails_and_yes_wins():
+    """Test --no-input on a terminal, and that --yes confirms even without one."""
+    assert make_confirm("y\n", no_input=True)[0].ask("Go").context == {
+        "error_kind": CONFIRMATION_REQUIRED
+    }
+
+    confirm, writer = make_confirm(yes=True, is_tty=False)
+    assert confirm.ask("Go").data is True
+    assert writer.getvalue() == ""
+
+
+def test_require_input():
+    """Test that value prompts are refused without a terminal, even with --yes."""
+    assert make_confirm()[0].require_input("Setup token").success
+
+    result = make_confirm(yes=True, is_tty=False)[0].require_input("Setup token")
+    assert not result.success
+    assert result.context == {"error_kind": INPUT_REQUIRED}
+    assert result.error.startswith("Setup token is required")
@@ -0,0 +1,81 @@
+"""Unit tests for keeping real integrations and the demo database apart."""
+
+import asyncio
+import tempfile
+from pathlib import Path
+
+import pytest
+from typer.testing import CliRunner
+
+from treeline import cli
+from treeline.commands import demo, setup
+from treeline.infra.duckdb import DuckDBRepository
+
+runner = CliRunner()
+
+
+@pytest.fixture
+def treeline_dir(monkeypatch):
+    """Point treeline at an empty directory, with a fresh container per test."""
+    with tempfile.TemporaryDirectory() as tmpdir:
+        monkeypatch.setenv("TREELINE_DIR", tmpdir)
+        monkeypatch.delenv("TREELINE_DEMO_MODE", raising=False)
+        cli.reset_container()
+        yield Path(tmpdir)
+        cli.reset_container()
+
+
+def output_text(result) -> str:
+    """Command output with rich's line wrapping undone."""
+    return " ".join(result.output.split())
+
+
+def test_setup_is_refused_in_demo_mode(treeline_dir, monkeypatch):
+    """Test that tl setup simplefin won't store real credentials in the demo database."""
+    monkeypatch.setenv("TREELINE_DEMO_MODE", "1")
+    calls = []
+    monkeypatch.setattr(setup, "_setup_simplefin", lambda *args: calls.append(args))
+
+    result = runner.invoke(cli.app, ["setup", "simplefin", "--token", "FAKE-TOKEN"])
+
+    assert result.exit_code == 1
+    assert "Demo mode is on" in output_text(result)
+    assert "--allow-demo-db" in output_text(result)
+    assert calls == []
+
+
+def test_allow_demo_db_sets_up_anyway(treeline_dir, monkeypatch):
+    """Test that --allow-demo-db goes ahead with setup in demo mode."""
+    monkeypatch.setenv("TREELINE_DEMO_MODE", "1")
+    calls = []
+    monkeypatch.setattr(setup, "_setup_simplefin", lambda *args: calls.append(args))
+
+    result = runner.invoke(
+        cli.app, ["setup", "simplefin", "--token", "FAKE-TOKEN", "--allow-demo-db"]
+    )
+
+    assert result.exit_code == 0, result.output
+    assert len(calls) == 1
+
+
+def test_demo_on_warns_about_real_integrations(treeline_dir, monkeypatch):
+    """Test that tl demo on says so when the real database has integrations."""
+    monkeypatch.setattr(demo, "_setup_demo_data", lambda *args: None)
+    real = DuckDBRepository(str(treeline_dir / "treeline.duckdb"))
+    asyncio.run(real.ensure_schema_upgraded())
+    asyncio.run(real.upsert_integration("simplefin", {"accessUrl": "https://u:p@bridge.example"}))
+
+    result = runner.invoke(cli.app, ["demo", "on"])
+
+    assert result.exit_code == 0, result.output
+    assert "Your real database has integrations configured (simplefin)" in output_text(result)
+
+
+def test_demo_on_without_real_integrations_does_not_warn(treeline_dir, monkeypatch):
+    """Test that a fresh install switches to demo mode without the warning."""
+    monkeypatch.setattr(demo, "_setup_demo_data", lambda *args: None)
+
+    result = runner.invoke(cli.app, ["demo", "on"])
+
+    assert result.exit_code == 0, result.output
+    assert "integrations configured" not in output_text(result)
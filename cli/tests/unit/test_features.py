@@ -0,0 +1,38 @@
+"""Unit tests for optional provider features."""
+
+import tempfile
+
+import pytest
+
+from treeline.app.container import Container
+from treeline.features import disabled_error, enabled_features, is_enabled
+
+
+def test_features_are_enabled_by_default(monkeypatch):
+    """Test that both providers are available in a full install."""
+    monkeypatch.delenv("TREELINE_DISABLED_FEATURES", raising=False)
+
+    assert enabled_features() == ["simplefin", "demo"]
+    assert is_enabled("csv")
+
+
+def test_disabled_features_env(monkeypatch):
+    """Test that TREELINE_DISABLED_FEATURES turns features off, case-insensitively."""
+    monkeypatch.setenv("TREELINE_DISABLED_FEATURES", " SimpleFIN , demo")
+
+    assert not is_enabled("simplefin")
+    assert not is_enabled("demo")
+    assert enabled_features() == []
+    assert disabled_error("SimpleFIN") == "treeline was built without simplefin support"
+
+
+def test_container_leaves_out_disabled_providers(monkeypatch):
+    """Test that a disabled provider is missing from the registry and can't be fetched."""
+    monkeypatch.setenv("TREELINE_DISABLED_FEATURES", "simplefin")
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        container = Container(tmpdir)
+
+        assert set(container.provider_registry()) == {"demo", "csv"}
+        with pytest.raises(ValueError, match="built without simplefin support"):
+            container.get_integration_provider("simplefin")
@@ -0,0 +1,41 @@
+"""Unit tests for amounts in --json output."""
+
+from datetime import date, datetime, timezone
+from decimal import Decimal
+from uuid import uuid4
+
+from treeline.commands.json_output import amount_json, decimal_json, model_json
+from treeline.domain import Transaction
+
+
+def _coffee() -> Transaction:
+    now = datetime.now(timezone.utc)
+    return Transaction(
+        id=uuid4(),
+        account_id=uuid4(),
+        amount=Decimal("-4.5"),
+        description="Coffee",
+        transaction_date=date(2025, 3, 1),
+        posted_date=date(2025, 3, 1),
+        created_at=now,
+        updated_at=now,
+    )
+
+
+def test_amounts_are_exact_strings_by_default(monkeypatch):
+    """Test that amounts are strings in the currency's decimal places."""
+    monkeypatch.delenv("TREELINE_JSON_NUMBERS", raising=False)
+
+    assert model_json(_coffee())["amount"] == "-4.50"
+    assert model_json(_coffee(), currency="JPY")["amount"] == "-4"
+    assert amount_json(Decimal("12"), "USD") == "12.00"
+    assert decimal_json(Decimal("12.5")) == "12.5"
+
+
+def test_json_numbers_flag_writes_amounts_as_numbers(monkeypatch):
+    """Test that --json-numbers brings back floats for the deprecated output."""
+    monkeypatch.setenv("TREELINE_JSON_NUMBERS", "1")
+
+    assert model_json(_coffee())["amount"] == -4.5
+    assert amount_json(Decimal("3")) == 3.0
+    assert decimal_json(Decimal("12.5")) == 12.5
@@ -0,0 +1,107 @@
+"""Unit tests for period string parsing."""
+
+from datetime import date
+
+import pytest
+
+from treeline.period import parse_period, resolve_date_range
+
+SUNDAY = 6
+
+
+def test_explicit_year_quarter_month():
+    """Test absolute periods."""
+    assert parse_period("2024") == (date(2024, 1, 1), date(2024, 12, 31))
+    assert parse_period("2024-Q3") == (date(2024, 7, 1), date(2024, 9, 30))
+    assert parse_period("2024-q4") == (date(2024, 10, 1), date(2024, 12, 31))
+    assert parse_period("2024-07") == (date(2024, 7, 1), date(2024, 7, 31))
+
+
+def test_february_leap_years():
+    """Test that February ends on the right day in leap and non-leap years."""
+    assert parse_period("2024-02") == (date(2024, 2, 1), date(2024, 2, 29))
+    assert parse_period("2023-02") == (date(2023, 2, 1), date(2023, 2, 28))
+    assert parse_period("1900-02") == (date(1900, 2, 1), date(1900, 2, 28))
+    assert parse_period("2000-02") == (date(2000, 2, 1), date(2000, 2, 29))
+    assert parse_period("2024-Q1") == (date(2024, 1, 1), date(2024, 3, 31))
+
+
+def test_iso_week():
+    """Test ISO weeks, including week 1 starting in the previous year."""
+    assert parse_period("2024-W01") == (date(2024, 1, 1), date(2024, 1, 7))
+    assert parse_period("2021-W01") == (date(2021, 1, 4), date(2021, 1, 10))
+    assert parse_period("2020-W53") == (date(2020, 12, 28), date(2021, 1, 3))
+
+
+def test_last_month_across_year_boundary():
+    """Test last-month in January resolves to December of the previous year."""
+    assert parse_period("last-month", today=date(2025, 1, 15)) == (
+        date(2024, 12, 1),
+        date(2024, 12, 31),
+    )
+    assert parse_period("last-month", today=date(2024, 3, 31)) == (
+        date(2024, 2, 1),
+        date(2024, 2, 29),
+    )
+
+
+def test_this_and_last_quarter():
+    """Test quarter aliases, including last-quarter in Q1."""
+    assert parse_period("this-quarter", today=date(2024, 8, 10)) == (
+        date(2024, 7, 1),
+        date(2024, 9, 30),
+    )
+    assert parse_period("last-quarter", today=date(2025, 2, 1)) == (
+        date(2024, 10, 1),
+        date(2024, 12, 31),
+    )
+
+
+def test_this_and_last_year():
+    """Test year aliases."""
+    today = date(2024, 6, 1)
+    assert parse_period("this-year", today=today) == (date(2024, 1, 1), date(2024, 12, 31))
+    assert parse_period("last-year", today=today) == (date(2023, 1, 1), date(2023, 12, 31))
+
+
+def test_ytd_and_mtd():
+    """Test to-date periods, including ytd on January 1st."""
+    assert parse_period("ytd", today=date(2024, 5, 20)) == (date(2024, 1, 1), date(2024, 5, 20))
+    assert parse_period("ytd", today=date(2025, 1, 1)) == (date(2025, 1, 1), date(2025, 1, 1))
+    assert parse_period("mtd", today=date(2024, 5, 20)) == (date(2024, 5, 1), date(2024, 5, 20))
+
+
+def test_last_n_days_includes_today():
+    """Test last-Nd is N days ending today, across a leap day."""
+    assert parse_period("last-90d", today=date(2024, 3, 31)) == (date(2024, 1, 2), date(2024, 3, 31))
+    assert parse_period("last-1d", today=date(2024, 3, 1)) == (date(2024, 3, 1), date(2024, 3, 1))
+    assert parse_period("last-2d", today=date(2024, 3, 1)) == (date(2024, 2, 29), date(2024, 3, 1))
+
+
+def test_week_respects_week_start():
+    """Test this-week/last-week with Monday and Sunday week starts."""
+    wednesday = date(2024, 1, 3)
+    assert parse_period("this-week", today=wednesday) == (date(2024, 1, 1), date(2024, 1, 7))
+    assert parse_period("this-week", today=wednesday, week_start=SUNDAY) == (
+        date(2023, 12, 31),
+        date(2024, 1, 6),
+    )
+    assert parse_period("last-week", today=wednesday) == (date(2023, 12, 25), date(2023, 12, 31))
+
+
+@pytest.mark.parametrize(
+    "value", ["", "2024-13", "2024-Q5", "2024-W54", "last-0d", "next-month", "july"]
+)
+def test_invalid_periods(value):
+    """Test that unrecognized periods raise ValueError."""
+    with pytest.raises(ValueError):
+        parse_period(value, today=date(2024, 1, 1))
+
+
+def test_resolve_date_range_rejects_both_forms():
+    """Test that --period and --since/--until are mutually exclusive."""
+    with pytest.raises(ValueError, match="--period"):
+        resolve_date_range("ytd", date(2024, 1, 1), None)
+
+    assert resolve_date_range(None, date(2024, 1, 1), None) == (date(2024, 1, 1), None)
+    assert resolve_date_range("2024-07", None, None) == (date(2024, 7, 1), date(2024, 7, 31))
@@ -0,0 +1,74 @@
+"""Unit tests for rolling averages, downsampling and trends of daily series."""
+
+from datetime import date, timedelta
+from decimal import Decimal
+
+import pytest
+
+from treeline.smoothing import Smoothing, downsample, linear_trend, smooth
+
+
+def decimals(*values) -> list[Decimal]:
+    return [Decimal(str(v)) for v in values]
+
+
+def test_sma_averages_what_there_is_during_warm_up():
+    """Test SMA against hand-computed values, including the first window_days - 1 days."""
+    assert smooth(decimals(10, 20, 30, 40, 50), Smoothing(3, "sma")) == decimals(
+        "10.00", "15.00", "20.00", "30.00", "40.00"
+    )
+    # (1 + 2 + 2) / 3 rounds to cents
+    assert smooth(decimals(1, 2, 2, 5), Smoothing(3, "sma")) == decimals(
+        "1.00", "1.50", "1.67", "3.00"
+    )
+    assert smooth([], Smoothing(30, "sma")) == []
+
+
+def test_ema_starts_from_the_first_value_and_rounds_half_even():
+    """Test EMA (alpha = 2 / (3 + 1) = 0.5) against hand-computed values."""
+    # 10, 0.5*20 + 0.5*10 = 15, 22.5, 31.25, 40.625
+    assert smooth(decimals(10, 20, 30, 40, 50), Smoothing(3, "ema")) == decimals(
+        "10.00", "15.00", "22.50", "31.25", "40.62"
+    )
+
+
+def test_smoothing_options_are_parsed_and_validated():
+    """Test the kind:days form and its limits."""
+    assert Smoothing.parse("EMA:7") == Smoothing(window_days=7, kind="ema")
+    assert Smoothing.parse("sma:30").to_json() == {"window_days": 30, "kind": "sma"}
+    for text in ("sma", "wma:3", "sma:0", "sma:366", "sma:ten"):
+        with pytest.raises(ValueError):
+            Smoothing.parse(text)
+
+
+def test_linear_trend_slope_and_projections():
+    """Test the least-squares slope per month and the values 30 and 90 days out."""
+    start = date(2024, 6, 1)
+    points = [(start + timedelta(days=i), Decimal(100 + 10 * i)) for i in range(3)]
+
+    trend = linear_trend(points)
+
+    # 10 a day over an average month of 30.436875 days
+    assert trend["slope_per_month"] == Decimal("304.37")
+    assert trend["projected_30d"] == Decimal("420.00")
+    assert trend["projected_90d"] == Decimal("1020.00")
+    assert trend["end_date"] == date(2024, 6, 3)
+    assert linear_trend(points[:1]) is None
+
+
+def test_downsample_keeps_the_last_day_and_sums_flows():
+    """Test that buckets keep their last day's values, except flows, which are totalled."""
+    points = [
+        {"date": date(2024, 1, 30), "net": Decimal("1"), "smoothed": Decimal("1.00")},
+        {"date": date(2024, 1, 31), "net": Decimal("2"), "smoothed": Decimal("1.50")},
+        {"date": date(2024, 2, 1), "net": Decimal("3"), "smoothed": Decimal("2.50")},
+        {"date": date(2024, 2, 2), "net": Decimal("4"), "smoothed": Decimal("3.50")},
+    ]
+
+    assert downsample(points, "month", sum_keys=("net",)) == [
+        {"date": date(2024, 1, 31), "net": Decimal("3"), "smoothed": Decimal("1.50")},
+        {"date": date(2024, 2, 2), "net": Decimal("7"), "smoothed": Decimal("3.50")},
+    ]
+    # 2024-01-30 is a Tuesday; weeks starting Sunday put Jan 30 - Feb 2 together
+    assert downsample(points, "week", week_start=6) == [points[-1]]
+    assert downsample(points, "day") == points
@@ -0,0 +1,107 @@
+"""Unit tests for tl sync exit codes when some or all integrations fail."""
+
+import asyncio
+import json
+import re
+import tempfile
+
+import pytest
+from typer.testing import CliRunner
+
+from treeline import cli
+from treeline.commands.sync import EXIT_SYNC_PARTIAL
+from treeline.domain import Fail
+from treeline.infra.demo import DemoDataProvider
+
+runner = CliRunner()
+
+
+class UnreachableProvider(DemoDataProvider):
+    """Provider whose bank never answers."""
+
+    async def fetch(self, start_date, end_date, provider_settings={}):
+        return Fail("Bank is unreachable")
+
+
+class UntypedProvider(DemoDataProvider):
+    """Provider whose accounts come without an account type."""
+
+    def _generate_demo_accounts(self):
+        return [
+            account.model_copy(update={"account_type": None})
+            for account in super()._generate_demo_accounts()
+        ]
+
+
+@pytest.fixture
+def configure_integrations(monkeypatch):
+    """Set up a fresh database with the given integrations configured."""
+    with tempfile.TemporaryDirectory() as tmpdir:
+        monkeypatch.setenv("TREELINE_DIR", tmpdir)
+        monkeypatch.delenv("TREELINE_DEMO_MODE", raising=False)
+        cli.reset_container()
+
+        def configure(*names: str) -> None:
+            cli.ensure_treeline_initialized()
+            container = cli.get_container()
+            container._instances["provider_registry"] = {
+                "demo": DemoDataProvider(),
+                "unreachable": UnreachableProvider(),
+                "untyped": UntypedProvider(),
+            }
+            for name in names:
+                asyncio.run(container.repository().upsert_integration(name, {}))
+
+        yield configure
+        cli.reset_container()
+
+
+@pytest.mark.parametrize(
+    "integrations, exit_code, overall_status",
+    [
+        (["demo"], 0, "ok"),
+        (["demo", "unreachable"], EXIT_SYNC_PARTIAL, "partial"),
+        (["unreachable"], 1, "failed"),
+    ],
+)
+def test_sync_exit_code_and_status(configure_integrations, integrations, exit_code, overall_status):
+    """Test that tl sync only exits 0 when every integration synced."""
+    configure_integrations(*integrations)
+
+    result = runner.invoke(cli.app, ["sync", "--json"])
+
+    assert result.exit_code == exit_code, result.output
+    data = json.loads(result.output)
+    assert data["overall_status"] == overall_status
+    statuses = {entry["integration"]: entry["status"] for entry in data["results"]}
+    assert statuses == {
+        name: "ok" if name == "demo" else "failed" for name in integrations
+    }
+    if "unreachable" in integrations:
+        (failed,) = [entry for entry in data["results"] if entry["status"] == "failed"]
+        assert failed["error"] == "Bank is unreachable"
+
+
+def test_partial_failure_is_summarized_last(configure_integrations):
+    """Test that the text output ends with the failed integration, not "Sync completed!"."""
+    configure_integrations("demo", "unreachable")
+
+    result = runner.invoke(cli.app, ["sync"])
+
+    assert result.exit_code == EXIT_SYNC_PARTIAL
+    # Rich wraps long lines, so compare with whitespace collapsed
+    text = " ".join(result.output.split())
+    assert text.endswith("1 of 2 integration(s) failed (unreachable)")
+    assert "Sync completed!" not in result.output
+
+
+def test_untyped_accounts_are_listed_by_name_and_short_id(configure_integrations):
+    """Test that the new-account warning shows names and short IDs, never full UUIDs."""
+    configure_integrations("untyped")
+
+    result = runner.invoke(cli.app, ["sync"])
+
+    assert result.exit_code == 0, result.output
+    assert "New accounts need an account type" in result.output
+    assert re.search(r"Primary Checking \([0-9a-f]{8}\.\.\.\)", result.output)
+    assert not re.search(r"[0-9a-f]{8}-[0-9a-f]{4}-", result.output)
@@ -0,0 +1,87 @@
+"""Unit tests for terminal-fitted tables."""
+
+import io
+
+import pytest
+from rich.console import Console
+from rich.table import Table
+
+from treeline.tables import TableLayout, add_column, print_table, project_columns, resolve_layout
+
+COLUMNS = ["date", "description", "amount"]
+ROWS = [
+    ["2024-06-01", "AMAZON MKTPLACE PMTS AMZN.COM/BILL WA ORDER 113-4471", "-42.17"],
+    ["2024-06-02", "Coffee", "-4.75"],
+]
+
+
+def make_console(width: int = 48, terminal: bool = True) -> Console:
+    return Console(file=io.StringIO(), width=width, force_terminal=terminal, color_system=None)
+
+
+def render(console: Console, layout: TableLayout) -> str:
+    table = Table(show_header=True)
+    for column in COLUMNS:
+        add_column(table, layout, column)
+    for row in ROWS:
+        table.add_row(*row)
+    print_table(console, table, layout)
+    return console.file.getvalue()
+
+
+def test_long_cells_are_cut_at_the_column_cap():
+    """Test that a capped column ends in an ellipsis and rows stay on one line."""
+    console = make_console()
+
+    output = render(console, resolve_layout(console, max_col_width=20))
+
+    assert output == (
+        "┏━━━━━━━━━━━━┳━━━━━━━━━━━━━━━━━━━━━━┳━━━━━━━━┓\n"
+        "┃ date       ┃ description          ┃ amount ┃\n"
+        "┡━━━━━━━━━━━━╇━━━━━━━━━━━━━━━━━━━━━━╇━━━━━━━━┩\n"
+        "│ 2024-06-01 │ AMAZON MKTPLACE PMT… │ -42.17 │\n"
+        "│ 2024-06-02 │ Coffee               │ -4.75  │\n"
+        "└────────────┴──────────────────────┴────────┘\n"
+    )
+
+
+def test_narrow_terminal_cuts_the_widest_columns_first():
+    """Test that short columns keep their width when the table must shrink."""
+    console = make_console(width=30)
+
+    output = render(console, resolve_layout(console))
+
+    assert output == (
+        "┏━━━━━━━━━┳━━━━━━━━━┳━━━━━━━━┓\n"
+        "┃ date    ┃ descri… ┃ amount ┃\n"
+        "┡━━━━━━━━━╇━━━━━━━━━╇━━━━━━━━┩\n"
+        "│ 2024-0… │ AMAZON… │ -42.17 │\n"
+        "│ 2024-0… │ Coffee  │ -4.75  │\n"
+        "└─────────┴─────────┴────────┘\n"
+    )
+
+
+@pytest.mark.parametrize("terminal, wide", [(False, False), (True, True)])
+def test_piped_or_wide_output_is_never_truncated(terminal, wide):
+    """Test that the table keeps its natural width beyond the console width."""
+    console = make_console(width=30, terminal=terminal)
+
+    output = render(console, resolve_layout(console, wide=wide))
+
+    assert output.splitlines()[3] == (
+        "│ 2024-06-01 │ AMAZON MKTPLACE PMTS AMZN.COM/BILL WA ORDER 113-4471 │ -42.17 │"
+    )
+
+
+def test_columns_are_projected_in_the_order_given():
+    """Test --columns picks columns case-insensitively and rejects unknown names."""
+    layout = resolve_layout(make_console(), columns="Amount, date")
+
+    columns, rows = project_columns(COLUMNS, ROWS, layout.columns)
+
+    assert columns == ["amount", "date"]
+    assert rows == [["-42.17", "2024-06-01"], ["-4.75", "2024-06-02"]]
+    with pytest.raises(ValueError, match="Unknown column: payee"):
+        project_columns(COLUMNS, ROWS, ["date", "payee"])
+    with pytest.raises(ValueError, match="not both"):
+        resolve_layout(make_console(), max_col_width=10, wide=True)
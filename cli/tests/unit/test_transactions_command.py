@@ -0,0 +1,42 @@
+"""Unit tests for tl transactions edit argument handling."""
+
+import json
+import tempfile
+
+import pytest
+from typer.testing import CliRunner
+
+from treeline import cli
+
+runner = CliRunner()
+
+
+@pytest.fixture
+def treeline_dir(monkeypatch):
+    """Point treeline at an empty directory, with a fresh container per test."""
+    with tempfile.TemporaryDirectory() as tmpdir:
+        monkeypatch.setenv("TREELINE_DIR", tmpdir)
+        monkeypatch.delenv("TREELINE_DEMO_MODE", raising=False)
+        cli.reset_container()
+        yield tmpdir
+        cli.reset_container()
+
+
+@pytest.mark.parametrize(
+    "args, error",
+    [
+        (["--amount", "12,50"], "Invalid --amount: 12,50"),
+        (["--amount", "nan"], "Invalid --amount: nan"),
+        (["--date", "2025-02-30"], "Invalid --date: 2025-02-30"),
+        (["--posted-date", "03/02/2025"], "Invalid --posted-date: 03/02/2025"),
+        (["--description", "Coffee"], "No transaction matches ID: 3f2a9c1e"),
+    ],
+)
+def test_edit_reports_bad_input(treeline_dir, args, error):
+    """Test that unparsable values and unknown IDs fail with a clear error."""
+    result = runner.invoke(cli.app, ["transactions", "edit", "3f2a9c1e", *args, "--json"])
+
+    assert result.exit_code == 1
+    data = json.loads(result.output)
+    assert data["success"] is False
+    assert data["error"].startswith(error)
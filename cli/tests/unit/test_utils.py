@@ -0,0 +1,60 @@
+"""Unit tests for resolving user-supplied file paths."""
+
+import sys
+from pathlib import Path
+
+import pytest
+
+from treeline.utils import resolve_user_path
+
+windows_only = pytest.mark.skipif(sys.platform != "win32", reason="Windows path semantics")
+
+
+@pytest.fixture
+def statement(tmp_path):
+    """A file whose name has spaces and non-ASCII characters."""
+    path = tmp_path / "Kontoauszug März 2024 – Giro.csv"
+    path.write_text("Date,Description,Amount\n")
+    return path
+
+
+def test_existing_path_is_used_as_given(statement):
+    """Test that spaces and unicode in an existing path pass through unchanged."""
+    assert resolve_user_path(str(statement)) == statement
+    assert resolve_user_path(statement) == statement
+
+
+def test_whitespace_and_quotes_are_dropped(statement):
+    """Test pasted paths with trailing whitespace or surrounding quotes."""
+    assert resolve_user_path(f"{statement}  \n") == statement
+    assert resolve_user_path(f'"{statement}"') == statement
+    assert resolve_user_path(f"  '{statement}' ") == statement
+
+
+def test_file_url_is_percent_decoded(statement):
+    """Test that a file:// URL resolves to the file it names."""
+    url = statement.as_uri()
+    assert "%20" in url
+    assert resolve_user_path(url) == statement
+    assert resolve_user_path(f" {url}\n") == statement
+
+
+def test_missing_path_keeps_its_name(tmp_path):
+    """Test that a path that doesn't exist still resolves, for the not-found error."""
+    missing = tmp_path / "nope.csv"
+    assert resolve_user_path(f"{missing} ") == missing
+    assert not resolve_user_path(f"{missing} ").exists()
+
+
+@windows_only
+def test_windows_drive_letter_url_and_quotes(statement):
+    """Test file:///C:/... URLs and cmd.exe's literal single quotes on Windows."""
+    assert resolve_user_path(f"file:///{statement.as_posix()}") == statement
+    assert resolve_user_path(f"'{statement}'") == statement
+    assert str(resolve_user_path(f"'{statement}'")).startswith(statement.drive)
+
+
+@windows_only
+def test_windows_unc_url():
+    """Test that file://server/share URLs become UNC paths."""
+    assert resolve_user_path("file://server/share/stmt%201.csv") == Path(r"\\server\share\stmt 1.csv")
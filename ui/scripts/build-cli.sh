@@ -30,6 +30,9 @@ fi
 # Install PyInstaller if needed
 uv pip install pyinstaller --quiet
 
+# Embed build metadata for `tl about`
+echo "{\"git_commit\": \"$(git rev-parse --short=12 HEAD 2>/dev/null)\", \"build_date\": \"$(date -u +%Y-%m-%d)\"}" > src/treeline/_build_info.json
+
 # Build binary
 echo "Building CLI binary with PyInstaller..."
 uv run pyinstaller --onefile --name tl --hidden-import=treeline --collect-all treeline src/treeline/cli.py --distpath dist --workpath build --specpath . -y
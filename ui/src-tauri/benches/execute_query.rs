@@ -0,0 +1,33 @@
+//! Arrow to JSON conversion behind the execute_query command.
+//!
+//! `cargo bench` for the full run, `cargo bench -- --quick` for CI.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use duckdb::Connection;
+use treeline_lib::bench_support::{execute_query_json, seed_wide_table};
+
+const WIDE_ROWS: usize = 100_000;
+
+fn bench_execute_query_wide(c: &mut Criterion) {
+    let dir = std::env::temp_dir().join(format!("treeline-bench-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("bench.duckdb");
+    let _ = std::fs::remove_file(&path);
+
+    let conn = Connection::open(&path).unwrap();
+    seed_wide_table(&conn, WIDE_ROWS).unwrap();
+
+    c.bench_function("bench_execute_query_wide", |b| {
+        b.iter(|| execute_query_json(&conn, "SELECT * FROM bench_wide").unwrap())
+    });
+
+    drop(conn);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = bench_execute_query_wide
+}
+criterion_main!(benches);
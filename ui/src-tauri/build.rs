@@ -1,3 +1,24 @@
+use std::path::Path;
+use std::process::Command;
+
 fn main() {
+    // Build metadata for get_about_info; empty when not built from a git checkout
+    let commit = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default();
+    println!("cargo:rustc-env=TREELINE_GIT_COMMIT={}", commit);
+    println!(
+        "cargo:rustc-env=TREELINE_BUILD_DATE={}",
+        chrono::Utc::now().format("%Y-%m-%d")
+    );
+    // Only watch HEAD when it exists; a missing path would rebuild every time
+    if Path::new("../../.git/HEAD").exists() {
+        println!("cargo:rerun-if-changed=../../.git/HEAD");
+    }
+
     tauri_build::build()
 }
@@ -7,7 +7,7 @@
 
 import { invoke } from "@tauri-apps/api/core";
 import { convertFileSrc } from "@tauri-apps/api/core";
-import { registry, themeManager, getDisabledPlugins } from "../sdk";
+import { registry, themeManager, getDisabledPlugins, startHostSession } from "../sdk";
 import type { Plugin, PluginContext } from "../sdk";
 
 // Import core plugins
@@ -95,6 +95,9 @@ async function loadExternalPlugins(): Promise<LoadedExternalPlugin[]> {
  * Initialize all plugins (core + external)
  */
 export async function initializePlugins(): Promise<void> {
+  // Claim the host session before any plugin code can
+  await startHostSession();
+
   // Load external plugins (with discovered manifest info)
   const loadedExternalPlugins = await loadExternalPlugins();
 
@@ -5,7 +5,7 @@
  * Rules are stored in DuckDB (sys_plugin_transactions_rules table).
  */
 
-import { executeQuery } from "../../../sdk";
+import { executeQuery, setTransactionTags, type TransactionTags } from "../../../sdk";
 import type {
   TagRule,
   RuleCondition,
@@ -537,6 +537,29 @@ export function describeRule(rule: TagRule): string {
   return conditionDescs.join(logic);
 }
 
+/**
+ * Merge tags into each (transaction_id, tags) row and save the rows that change.
+ * Returns the number of transactions whose stored tags changed.
+ */
+async function addTagsToRows(rows: unknown[][], tags: string[]): Promise<number> {
+  const existing = new Map(rows.map((row) => [row[0] as string, (row[1] as string[]) || []]));
+  const updates: TransactionTags[] = [];
+  for (const [transactionId, existingTags] of existing) {
+    // Merge tags (avoid duplicates)
+    const newTags = [...new Set([...existingTags, ...tags])];
+    if (newTags.length !== existingTags.length) {
+      updates.push({ transactionId, tags: newTags });
+    }
+  }
+
+  // Tags are stored normalized, so "Dining" added to ["dining"] is no change
+  const stored = await setTransactionTags(updates);
+  return stored.filter((update) => {
+    const before = existing.get(update.transactionId) ?? [];
+    return update.tags.length !== before.length || !update.tags.every((t) => before.includes(t));
+  }).length;
+}
+
 /**
  * Apply a rule's tags to all matching transactions in the database
  * Returns the number of transactions updated
@@ -560,30 +583,7 @@ export async function applyRuleToExisting(rule: TagRule): Promise<number> {
       return 0;
     }
 
-    // Update each transaction to add the rule's tags
-    // Note: Must update sys_transactions (base table), not the transactions view
-    let updatedCount = 0;
-    for (const row of result.rows) {
-      const transactionId = row[0] as string;
-      const existingTags = (row[1] as string[]) || [];
-
-      // Merge tags (avoid duplicates)
-      const newTags = [...new Set([...existingTags, ...rule.tags])];
-
-      // Only update if tags actually changed
-      if (newTags.length !== existingTags.length || !newTags.every((t) => existingTags.includes(t))) {
-        const tagList = newTags.map((t) => `'${t.replace(/'/g, "''")}'`).join(", ");
-        await executeQuery(
-          `UPDATE sys_transactions
-           SET tags = [${tagList}]
-           WHERE transaction_id = '${transactionId}'`,
-          { readonly: false }
-        );
-        updatedCount++;
-      }
-    }
-
-    return updatedCount;
+    return await addTagsToRows(result.rows, rule.tags);
   } catch (e) {
     console.error("Failed to apply rule to existing transactions:", e);
     throw e;
@@ -611,30 +611,7 @@ export async function applyTagsToMatching(sqlCondition: string, tags: string[]):
       return 0;
     }
 
-    // Update each transaction to add the tags
-    // Note: Must update sys_transactions (base table), not the transactions view
-    let updatedCount = 0;
-    for (const row of result.rows) {
-      const transactionId = row[0] as string;
-      const existingTags = (row[1] as string[]) || [];
-
-      // Merge tags (avoid duplicates)
-      const newTags = [...new Set([...existingTags, ...tags])];
-
-      // Only update if tags actually changed
-      if (newTags.length !== existingTags.length || !newTags.every((t) => existingTags.includes(t))) {
-        const tagList = newTags.map((t) => `'${t.replace(/'/g, "''")}'`).join(", ");
-        await executeQuery(
-          `UPDATE sys_transactions
-           SET tags = [${tagList}]
-           WHERE transaction_id = '${transactionId}'`,
-          { readonly: false }
-        );
-        updatedCount++;
-      }
-    }
-
-    return updatedCount;
+    return await addTagsToRows(result.rows, tags);
   } catch (e) {
     console.error("Failed to apply tags to matching transactions:", e);
     throw e;
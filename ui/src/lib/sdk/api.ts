@@ -4,6 +4,38 @@
 
 import { invoke } from "@tauri-apps/api/core";
 
+export interface ProviderWarning {
+  id: string;
+  integration_name: string;
+  message: string;
+  first_seen: string;
+  last_seen: string;
+  resolved_at: string | null;
+}
+
+export interface IntegrationSummary {
+  /** Integration name used by sync, e.g. "simplefin" */
+  name: string;
+  /** User-set label, e.g. "Chase + Ally via SimpleFIN" */
+  display_name: string | null;
+  notes: string | null;
+}
+
+export interface StaleAccount {
+  id: string;
+  name: string;
+  /** Whether the account has a provider ID (synced accounts go stale sooner) */
+  synced: boolean;
+  threshold_days: number;
+  latest_transaction_date: string | null;
+  latest_snapshot_date: string | null;
+  /** Null when the account has never had one */
+  days_since_transaction: number | null;
+  days_since_snapshot: number | null;
+  /** e.g. "no transactions in 12 days" */
+  reasons: string[];
+}
+
 export interface StatusResponse {
   total_accounts: number;
   total_transactions: number;
@@ -13,7 +45,15 @@ export interface StatusResponse {
   earliest_date: string | null;
   latest_date: string | null;
   accounts: unknown[];
-  integrations: unknown[];
+  integrations: IntegrationSummary[];
+  provider_warnings: ProviderWarning[];
+  /** Open accounts with no recent transactions or balances */
+  stale_accounts: StaleAccount[];
+  /** Integrations with unresolved provider warnings */
+  warning_integrations: string[];
+  untyped_accounts: { id: string; name: string }[];
+  /** Present (true) only when the data is the simulated demo database */
+  demo_mode?: boolean;
 }
 
 /**
@@ -36,7 +76,11 @@ export async function getStatus(): Promise<StatusResponse> {
     earliest_date: response.earliest_date || null,
     latest_date: response.latest_date || null,
     accounts: response.accounts || [],
-    integrations: response.integrations || []
+    integrations: response.integrations || [],
+    provider_warnings: response.provider_warnings || [],
+    stale_accounts: response.stale_accounts || [],
+    warning_integrations: response.warning_integrations || [],
+    untyped_accounts: response.untyped_accounts || []
   };
 }
 
@@ -46,23 +90,79 @@ export interface QueryResult {
   row_count: number;
 }
 
+/** What a write-enabled statement would do, as classified by the backend */
+export interface StatementClassification {
+  /** Leading keyword, lowercased: "update", "delete", "drop", "select", ... */
+  kind: string;
+  /** Tables the statement names */
+  tables: string[];
+  writes: boolean;
+  /** Names sys_integrations or sys_migrations */
+  touches_protected: boolean;
+}
+
+/** Returned by execute_query instead of running a write that needs confirming */
+export interface WriteConfirmation {
+  requires_confirmation: true;
+  classification: StatementClassification;
+  /**
+   * One-time token that runs the same query; null in safe mode. For plugins
+   * the backend asks the user before the token's write runs.
+   */
+  token: string | null;
+  expires_in_secs: number;
+}
+
 export interface ExecuteQueryOptions {
   readonly?: boolean;
+  /** Cancel the query after this many seconds (unset or 0 = no limit) */
+  timeoutSecs?: number;
+  /** The plugin running the query; unset for the app's own statements */
+  pluginId?: string;
+}
+
+/** Proves to the backend that a query comes from the app, not a plugin */
+let hostToken: string | null = null;
+
+/**
+ * Claim the host session. Call once per page load, before any plugin code
+ * runs: the backend only hands the token out once.
+ */
+export async function startHostSession(): Promise<void> {
+  hostToken = await invoke<string>("take_host_session");
+}
+
+function isWriteConfirmation(response: unknown): response is WriteConfirmation {
+  return (response as WriteConfirmation | null)?.requires_confirmation === true;
 }
 
 /**
  * Execute a SQL query against the DuckDB database
  * @param query SQL query string
  * @param options.readonly If true (default), opens read-only connection. Set to false for writes.
+ * @param options.timeoutSecs Cancel the query after this many seconds ("Query cancelled after Ns")
+ * @param options.pluginId The plugin running the query; the user is asked before its writes run
  */
 export async function executeQuery(query: string, options: ExecuteQueryOptions = {}): Promise<QueryResult> {
-  const { readonly = true } = options;
+  const { readonly = true, timeoutSecs, pluginId } = options;
+  const caller = pluginId === undefined ? { hostToken } : { pluginId };
 
   try {
-    const jsonString = await invoke<string>("execute_query", { query, readonly });
+    let response = JSON.parse(await invoke<string>("execute_query", { query, readonly, timeoutSecs, ...caller }));
 
-    // Parse JSON string from Rust backend
-    const response = JSON.parse(jsonString);
+    // Writes run in two steps: the backend classifies the statement and
+    // hands back a token. Redeeming it runs the statement, after the
+    // backend has asked the user when a plugin is the caller
+    if (isWriteConfirmation(response)) {
+      const { classification, token } = response;
+      if (token === null) {
+        const what = `${classification.kind.toUpperCase()} on ${classification.tables.join(", ") || "the database"}`;
+        throw new Error(`${what} needs confirmation, which isn't available in safe mode`);
+      }
+      response = JSON.parse(
+        await invoke<string>("execute_query", { query, readonly, timeoutSecs, confirmationToken: token, ...caller }),
+      );
+    }
 
     return {
       columns: response.columns || [],
@@ -77,3 +177,241 @@ export async function executeQuery(query: string, options: ExecuteQueryOptions =
     throw e;
   }
 }
+
+// ============================================================================
+// Versioned data API - stable response shapes for plugins
+// ============================================================================
+
+// Amounts are decimal strings, not numbers, so large ones arrive exactly:
+// a JavaScript number rounds past 15-17 significant digits.
+
+/** transactions.v1.list row */
+export interface TransactionV1 {
+  id: string;
+  accountId: string;
+  accountName: string | null;
+  /** YYYY-MM-DD */
+  date: string;
+  postedDate: string;
+  description: string | null;
+  /** Decimal string ("-4.50"), signed; negative for outflows */
+  amount: string;
+  currency: string | null;
+  tags: string[];
+  status: "pending" | "cleared" | "reconciled";
+  /** For split parts, the transaction that was split */
+  parentId: string | null;
+}
+
+/** accounts.v1.list row */
+export interface AccountV1 {
+  id: string;
+  name: string;
+  nickname: string | null;
+  accountType: string | null;
+  currency: string;
+  institutionName: string | null;
+  /** Latest balance snapshot as a decimal string, or null if there is none */
+  balance: string | null;
+  balanceAsOf: string | null;
+  openedAt: string | null;
+  closedAt: string | null;
+}
+
+/**
+ * reports.v1.spendingByTag row, from `tl report tags`: income, transfers and
+ * card payments aren't spending. A transaction with several tags counts toward each.
+ */
+export interface SpendingByTagV1 {
+  /** null for untagged spending */
+  tag: string | null;
+  currency: string | null;
+  /** Positive total spent, as a decimal string */
+  total: string;
+  transactionCount: number;
+}
+
+/** Endpoint name -> accepted params and row type. Dates are YYYY-MM-DD. */
+export interface ApiEndpoints {
+  "transactions.v1.list": {
+    params: { accountId?: string; status?: string; since?: string; until?: string; limit?: number };
+    row: TransactionV1;
+  };
+  "accounts.v1.list": {
+    params: Record<string, never>;
+    row: AccountV1;
+  };
+  "reports.v1.spendingByTag": {
+    params: { since?: string; until?: string; accountId?: string };
+    row: SpendingByTagV1;
+  };
+}
+
+export type ApiEndpoint = keyof ApiEndpoints;
+
+/** Error returned by apiQuery (the Rust ApiError) */
+export type ApiQueryError =
+  | { code: "unknownEndpoint"; endpoint: string; supported: string[] }
+  | { code: "invalidParam"; param: string; message: string }
+  | { code: "queryFailed"; message: string };
+
+/**
+ * Query a versioned data endpoint. Unlike executeQuery, each endpoint's
+ * response shape stays the same when the database schema changes; breaking
+ * changes ship as a new ".v2" endpoint.
+ * @throws ApiQueryError
+ */
+export async function apiQuery<E extends ApiEndpoint>(
+  endpoint: E,
+  params: ApiEndpoints[E]["params"] = {} as ApiEndpoints[E]["params"]
+): Promise<ApiEndpoints[E]["row"][]> {
+  const response = await invoke<{ endpoint: E; data: ApiEndpoints[E]["row"][] }>("api_query", {
+    endpoint,
+    params,
+  });
+  return response.data;
+}
+
+export interface SchemaColumn {
+  name: string;
+  /** DuckDB type, e.g. "VARCHAR" or "DECIMAL(18,2)" */
+  type: string;
+  description: string | null;
+}
+
+export interface SchemaTable {
+  name: string;
+  kind: "view" | "table";
+  description: string | null;
+  columns: SchemaColumn[];
+  /** Example SQL queries */
+  examples: string[];
+}
+
+/**
+ * Get every table and view (including sys_* tables) with column names,
+ * types and descriptions, e.g. for query editor autocomplete
+ */
+export async function getSchema(): Promise<SchemaTable[]> {
+  const jsonString = await invoke<string>("get_schema");
+  return JSON.parse(jsonString).tables as SchemaTable[];
+}
+
+export interface QuerySnippet {
+  name: string;
+  sql: string;
+  description: string;
+  /** Ships with the app; save a copy under a new name to change it */
+  builtin: boolean;
+}
+
+/**
+ * List saved query snippets (~/.treeline/snippets.json), built-in ones first.
+ * The same snippets run from the CLI with `tl query --snippet NAME`.
+ */
+export async function listQuerySnippets(): Promise<QuerySnippet[]> {
+  return invoke<QuerySnippet[]>("list_query_snippets");
+}
+
+/**
+ * Save SQL as a snippet, replacing the user snippet with the same name.
+ * Built-in names are refused.
+ */
+export async function saveQuerySnippet(
+  name: string,
+  sql: string,
+  description?: string
+): Promise<QuerySnippet> {
+  return invoke<QuerySnippet>("save_query_snippet", { name, sql, description });
+}
+
+/**
+ * Delete a user snippet (built-in snippets can't be deleted)
+ */
+export async function deleteQuerySnippet(name: string): Promise<void> {
+  await invoke("delete_query_snippet", { name });
+}
+
+export interface CliAboutInfo {
+  version: string;
+  git_commit: string | null;
+  build_date: string | null;
+  python: string;
+  os: string;
+  arch: string;
+  demo_mode: boolean;
+  /** settings.json in use, or null when running on defaults */
+  config_path: string | null;
+  database: {
+    path: string;
+    backend: string;
+    exists: boolean;
+    size_bytes: number | null;
+    encrypted: boolean;
+    /** null when the database is missing or encrypted and locked */
+    data_version: number | null;
+    supported_data_version: number;
+    migrations_applied: number | null;
+    latest_migration: string | null;
+  };
+}
+
+export interface AboutInfo {
+  app_version: string;
+  git_commit: string | null;
+  build_date: string;
+  os: string;
+  arch: string;
+  /** `tl about --json` from the bundled CLI */
+  cli: CliAboutInfo | null;
+  /** Why `cli` is missing */
+  cli_error: string | null;
+}
+
+/**
+ * Get app and CLI build details, database version and platform for the
+ * About dialog and bug reports. Contains no credentials.
+ */
+export async function getAboutInfo(): Promise<AboutInfo> {
+  return invoke<AboutInfo>("get_about_info");
+}
+
+export interface RecentLogs {
+  ui_log: string;
+  ui_lines: string[];
+  /** The CLI's newest log file, if it has written one */
+  cli_log: string | null;
+  cli_lines: string[];
+}
+
+/**
+ * Get the tail of the app's log (ui.log) and the CLI's newest log.
+ * Secrets are redacted before they are logged.
+ *
+ * @param lines - Lines per log (default 200)
+ * @param levelFilter - Minimum level: "debug", "info", "warning" or "error"
+ */
+export async function getRecentLogs(lines?: number, levelFilter?: string): Promise<RecentLogs> {
+  return invoke<RecentLogs>("get_recent_logs", { lines, levelFilter });
+}
+
+/**
+ * Open the log directory (~/.treeline/logs) in the file manager
+ */
+export async function openLogsDir(): Promise<void> {
+  await invoke("open_logs_dir");
+}
+
+/**
+ * Build and platform details plus recent logs, as text to paste into an issue
+ */
+export async function collectDiagnostics(): Promise<string> {
+  const [about, logs] = await Promise.all([getAboutInfo(), getRecentLogs()]);
+  return [
+    JSON.stringify(about, null, 2),
+    `--- ${logs.ui_log}`,
+    ...logs.ui_lines,
+    `--- ${logs.cli_log ?? "CLI log (none yet)"}`,
+    ...logs.cli_lines,
+  ].join("\n");
+}
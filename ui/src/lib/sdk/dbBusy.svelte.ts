@@ -0,0 +1,74 @@
+/**
+ * Database busy store - tracks whether a write holds the database writer
+ *
+ * The backend serializes writes and emits db-busy / db-idle. Quick writes
+ * finish before BUSY_DISPLAY_DELAY_MS, so only longer ones (sync, import)
+ * mark the database as busy in the UI.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+const BUSY_DISPLAY_DELAY_MS = 300;
+
+export interface DbBusyState {
+  busy: boolean;
+  /** What holds the writer, e.g. "query" or "sync" */
+  operation: string | null;
+  /** Writes waiting their turn */
+  waiting: number;
+}
+
+class DbBusyStore {
+  private _busy = $state(false);
+  private _operation = $state<string | null>(null);
+  private timer: ReturnType<typeof setTimeout> | null = null;
+  private started = false;
+
+  /** True while a long-running write holds the database; grey out write actions */
+  get busy(): boolean {
+    return this._busy;
+  }
+
+  get operation(): string | null {
+    return this._operation;
+  }
+
+  /**
+   * Start listening for writer events. Safe to call more than once.
+   */
+  async start(): Promise<void> {
+    if (this.started) return;
+    this.started = true;
+
+    await listen<DbBusyState>("db-busy", (event) => this.update(event.payload));
+    await listen<DbBusyState>("db-idle", (event) => this.update(event.payload));
+
+    try {
+      this.update(await invoke<DbBusyState>("get_db_busy_state"));
+    } catch (e) {
+      console.error("Failed to get database busy state:", e);
+    }
+  }
+
+  private update(state: DbBusyState): void {
+    if (this.timer) {
+      clearTimeout(this.timer);
+      this.timer = null;
+    }
+
+    if (!state.busy) {
+      this._busy = false;
+      this._operation = null;
+      return;
+    }
+
+    this.timer = setTimeout(() => {
+      this._busy = true;
+      this._operation = state.operation;
+      this.timer = null;
+    }, BUSY_DISPLAY_DELAY_MS);
+  }
+}
+
+export const dbBusyStore = new DbBusyStore();
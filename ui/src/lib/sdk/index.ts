@@ -28,8 +28,42 @@ export type { PluginSDK, PluginTablePermissions } from "./public";
 export { registry } from "./registry";
 
 // API
-export { getStatus, executeQuery } from "./api";
-export type { StatusResponse, QueryResult, ExecuteQueryOptions } from "./api";
+export {
+  getStatus,
+  executeQuery,
+  startHostSession,
+  apiQuery,
+  getSchema,
+  listQuerySnippets,
+  saveQuerySnippet,
+  deleteQuerySnippet,
+  getAboutInfo,
+  getRecentLogs,
+  openLogsDir,
+  collectDiagnostics,
+} from "./api";
+export type {
+  StatusResponse,
+  IntegrationSummary,
+  ProviderWarning,
+  StaleAccount,
+  QueryResult,
+  ExecuteQueryOptions,
+  StatementClassification,
+  WriteConfirmation,
+  ApiEndpoint,
+  ApiEndpoints,
+  ApiQueryError,
+  TransactionV1,
+  AccountV1,
+  SpendingByTagV1,
+  SchemaTable,
+  SchemaColumn,
+  QuerySnippet,
+  AboutInfo,
+  CliAboutInfo,
+  RecentLogs,
+} from "./api";
 
 // Theme
 export { themeManager, themes } from "./theme";
@@ -50,9 +84,11 @@ export {
   readPluginState,
   writePluginState,
   runSync,
+  onSyncProgress,
   isSyncNeeded,
   getDemoMode,
   setDemoMode,
+  onDemoModeChanged,
   enableDemo,
   disableDemo,
   // Plugin enable/disable
@@ -65,16 +101,63 @@ export {
   // CSV Import
   pickCsvFile,
   getCsvHeaders,
+  detectCsvColumns,
   importCsvPreview,
   importCsvExecute,
+  importFromClipboardPreview,
+  importFromClipboardExecute,
+  ImportMappingError,
+  ImportFileError,
+  // Accounts
+  createAccount,
+  updateAccount,
+  bootstrapManualAccount,
+  AccountValidationError,
+  onAccountsChanged,
+  balanceAsOf,
+  listAccounts,
+  // Reports
+  spendingByMerchant,
+  getBalanceHistory,
+  getCashFlow,
+  // Transactions
+  listTransactions,
+  setTransactionStatus,
+  setTransactionTags,
+  attachFileToTransaction,
+  listAttachments,
+  openAttachment,
+  // Undo
+  getUndoStack,
+  undoLastChange,
+  onTransactionsChanged,
+  // Planned transactions
+  listPlanned,
+  upsertPlanned,
+  // Duplicate review
+  listDuplicateCandidates,
+  dismissDuplicate,
+  mergeDuplicate,
   // Integrations
   setupSimplefin,
+  testIntegration,
+  // Deep links
+  getPendingDeepLinkSetup,
+  onDeepLinkSetup,
+  confirmDeepLinkSetup,
+  dismissDeepLinkSetup,
   // Integration Account Settings
   getIntegrationSettings,
   updateIntegrationAccountSetting,
   // Community Plugins
   installPlugin,
   uninstallPlugin,
+  // Database compatibility
+  checkDbCompatibility,
+  checkViews,
+  repairViews,
+  // Onboarding
+  getOnboardingState,
   // Encryption
   getEncryptionStatus,
   tryAutoUnlock,
@@ -86,11 +169,67 @@ export type {
   Settings,
   AppSettings,
   SyncResult,
+  SyncProgressEvent,
+  BudgetAlert,
   ImportColumnMapping,
+  CsvColumnDetection,
+  CsvPreset,
+  ColumnMappingError,
+  ImportFileErrorKind,
+  CsvDetectionReport,
   ImportPreviewResult,
   ImportExecuteResult,
+  ForeignCurrencyMode,
+  CurrencySkippedRow,
+  ClipboardImportPreviewResult,
+  CreateAccountOptions,
+  CreateAccountResult,
+  UpdateAccountOptions,
+  UpdateAccountResult,
+  BootstrapAccountOptions,
+  BootstrapAccountResult,
+  AccountFieldError,
+  BalanceAsOf,
+  AccountSummary,
+  AccountGroup,
+  AccountListing,
+  MerchantSpending,
+  MerchantReport,
+  MerchantReportOptions,
+  SeriesSmoothing,
+  SeriesInterval,
+  SeriesOptions,
+  SeriesTrend,
+  BalanceHistory,
+  BalanceHistoryPoint,
+  CashFlowOptions,
+  CashFlowMonth,
+  CashFlowPoint,
+  CashFlowReport,
+  TransactionStatus,
+  TransactionTags,
+  TransactionFilters,
+  TransactionRecord,
+  TransactionAttachment,
+  UndoChange,
+  UndoEntry,
+  PlannedFrequency,
+  PlannedTransaction,
+  PlannedTransactionInput,
+  DuplicateKind,
+  DuplicateStatus,
+  DuplicateCandidate,
+  DuplicateReviewItem,
+  DuplicateMergeResult,
+  IntegrationTestResult,
+  DeepLinkSetup,
   PluginInstallResult,
   EncryptionStatus,
+  DbCompatibility,
+  ViewDrift,
+  ViewCheck,
+  ViewRepair,
+  OnboardingState,
 } from "./settings";
 
 // Toast notifications
@@ -101,6 +240,10 @@ export type { Toast, ToastOptions, ToastType } from "./toast.svelte";
 export { activityStore, withActivity } from "./activity.svelte";
 export type { Activity } from "./activity.svelte";
 
+// Database writer state (grey out writes while a long write runs)
+export { dbBusyStore } from "./dbBusy.svelte";
+export type { DbBusyState } from "./dbBusy.svelte";
+
 // Platform utilities
 export { isMac, modKey, formatShortcut } from "./platform";
 
@@ -5,7 +5,13 @@
  * It's passed to external plugin views via props.
  */
 
-import { executeQuery, type QueryResult } from "./api";
+import {
+  apiQuery,
+  executeQuery,
+  type ApiEndpoint,
+  type ApiEndpoints,
+  type QueryResult,
+} from "./api";
 import { showToast, toast } from "./toast.svelte";
 import { themeManager } from "./theme";
 import { registry } from "./registry";
@@ -31,9 +37,27 @@ import {
   getUserCurrencySymbol,
 } from "../shared/currencyStore.svelte";
 
+/** Plugin queries are cancelled after this long so a runaway query can't hang the app */
+const PLUGIN_QUERY_TIMEOUT_SECS = 30;
+
 // Re-export types for plugin authors
 export type { Plugin, PluginManifest, PluginContext, PluginPermissions } from "./types";
-export type { QueryResult } from "./api";
+export type {
+  QueryResult,
+  ApiEndpoint,
+  ApiEndpoints,
+  ApiQueryError,
+  TransactionV1,
+  AccountV1,
+  SpendingByTagV1,
+} from "./api";
+
+/** Views each API endpoint reads, checked against the plugin's read permissions */
+const API_ENDPOINT_TABLES: Record<ApiEndpoint, string[]> = {
+  "transactions.v1.list": ["transactions"],
+  "accounts.v1.list": ["accounts", "balance_snapshots"],
+  "reports.v1.spendingByTag": ["transactions"],
+};
 
 /**
  * Full permissions object for a plugin
@@ -59,6 +83,15 @@ export interface PluginSDK {
    */
   execute: (sql: string) => Promise<{ rowsAffected: number }>;
 
+  /**
+   * Query a versioned data endpoint (e.g. "transactions.v1.list"). Prefer
+   * this to query(): the response shape doesn't change with the schema.
+   */
+  api: <E extends ApiEndpoint>(
+    endpoint: E,
+    params?: ApiEndpoints[E]["params"]
+  ) => Promise<ApiEndpoints[E]["row"][]>;
+
   /**
    * Show a toast notification
    */
@@ -165,7 +198,7 @@ export function createPluginSDK(pluginId: string, permissions: PluginTablePermis
     // Database - read-only queries (with table restriction)
     query: async <T = Record<string, any>>(sql: string): Promise<T[]> => {
       validateReadQuery(sql, pluginId, allowedReadTables);
-      const result = await executeQuery(sql, { readonly: true });
+      const result = await executeQuery(sql, { readonly: true, timeoutSecs: PLUGIN_QUERY_TIMEOUT_SECS });
       return result.rows as T[];
     },
 
@@ -173,10 +206,20 @@ export function createPluginSDK(pluginId: string, permissions: PluginTablePermis
     execute: async (sql: string): Promise<{ rowsAffected: number }> => {
       // Validate that query only targets allowed tables
       validateWriteQuery(sql, pluginId, effectiveWriteTables, permissions.create ?? []);
-      const result = await executeQuery(sql, { readonly: false });
+      const result = await executeQuery(sql, {
+        readonly: false,
+        timeoutSecs: PLUGIN_QUERY_TIMEOUT_SECS,
+        pluginId,
+      });
       return { rowsAffected: result.rows.length };
     },
 
+    // Versioned data API (needs read permission on the views it reads)
+    api: async <E extends ApiEndpoint>(endpoint: E, params?: ApiEndpoints[E]["params"]) => {
+      validateReadTables(API_ENDPOINT_TABLES[endpoint] ?? [], pluginId, allowedReadTables);
+      return apiQuery(endpoint, params);
+    },
+
     // Toast notifications
     toast: {
       show: (message: string, description?: string) => showToast(message, "info", description),
@@ -293,12 +336,19 @@ function extractReadTables(sql: string): string[] {
  * Use "*" in allowedTables to allow reading any table.
  */
 function validateReadQuery(sql: string, pluginId: string, allowedTables: string[]): void {
+  validateReadTables(extractReadTables(sql), pluginId, allowedTables);
+}
+
+/**
+ * Validate that every table is allowed for reads.
+ * Throws an error naming the first table the plugin may not read.
+ */
+function validateReadTables(referencedTables: string[], pluginId: string, allowedTables: string[]): void {
   // Wildcard allows all reads
   if (allowedTables.includes("*")) {
     return;
   }
 
-  const referencedTables = extractReadTables(sql);
   const normalizedAllowed = allowedTables.map(t => t.toLowerCase());
 
   for (const table of referencedTables) {
@@ -10,6 +10,8 @@
  */
 
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import { executeQuery } from "./api";
 
 /**
  * App-level settings structure
@@ -24,6 +26,11 @@ export interface AppSettings {
   sidebarCollapsed?: boolean;
   hideDemoBanner?: boolean;
   currency?: string;
+  /**
+   * Refuse plugin writes that need confirmation instead of asking: true for
+   * every plugin, or a list of plugin IDs. Read by the backend.
+   */
+  pluginSafeMode?: boolean | string[];
 }
 
 /**
@@ -236,11 +243,11 @@ export async function disablePlugin(pluginId: string): Promise<void> {
  * Read plugin state (runtime state, not user settings)
  */
 export async function readPluginState<T>(pluginId: string): Promise<T | null> {
-  const jsonString = await invoke<string>("read_plugin_state", { pluginId });
-  if (jsonString === "null") {
-    return null;
+  const result = await invoke<{ data: T | null; recovered: boolean }>("read_plugin_state", { pluginId });
+  if (result.recovered) {
+    console.warn(`Plugin state for ${pluginId} was corrupt; restored from backup`);
   }
-  return JSON.parse(jsonString) as T;
+  return result.data;
 }
 
 /**
@@ -266,10 +273,41 @@ export interface SyncResult {
       discovered: number;
       new: number;
       skipped: number;
+      /** Transactions for provider accounts that matched no account */
+      unmapped: number;
+      /** Unmapped transactions kept for adoption (sync.keep_unmapped) */
+      quarantined: number;
     };
     provider_warnings?: string[];
+    /** Set when the integration was not contacted (e.g. "offline") */
+    skipped?: string;
     error?: string;
+    status: "ok" | "failed" | "skipped";
   }>;
+  /**
+   * "partial" when some integrations failed and others synced. A sync where
+   * none synced rejects instead, so runSync never resolves with "failed".
+   */
+  overall_status: "ok" | "partial" | "failed";
+  /** Expense categories this sync pushed (further) over budget (budgets.alerts) */
+  budget_alerts?: BudgetAlert[];
+  /** True when nothing was written (sync --dry-run) */
+  dry_run: boolean;
+}
+
+export interface BudgetAlert {
+  category_id: string;
+  category: string;
+  /** Budget month (YYYY-MM) */
+  month: string;
+  /** Amounts are decimal strings */
+  expected: string;
+  actual: string;
+  over_by: string;
+  /** Spending added by this sync */
+  sync_delta: string;
+  /** e.g. "groceries: $612 of $500 (over by $112, +$87 from this sync)" */
+  message: string;
 }
 
 export interface RunSyncOptions {
@@ -285,7 +323,7 @@ export async function runSync(options: RunSyncOptions = {}): Promise<SyncResult>
   const result = JSON.parse(jsonString) as SyncResult;
 
   // Update lastSyncDate on success (but not for dry runs)
-  if (!dryRun) {
+  if (!dryRun && !result.dry_run) {
     const today = new Date().toISOString().split("T")[0];
     await setAppSetting("lastSyncDate", today);
   }
@@ -293,6 +331,21 @@ export async function runSync(options: RunSyncOptions = {}): Promise<SyncResult>
   return result;
 }
 
+/** A step of a running sync, as printed by `tl sync --json-stream` */
+export type SyncProgressEvent =
+  | { event: "integration_start"; name: string }
+  | { event: "accounts_fetched"; name: string; count: number }
+  | { event: "transactions_inserted"; name: string; new: number; skipped: number };
+
+/**
+ * Listen for progress while runSync is running
+ */
+export async function onSyncProgress(
+  callback: (event: SyncProgressEvent) => void
+): Promise<UnlistenFn> {
+  return listen<SyncProgressEvent>("sync-progress", (event) => callback(event.payload));
+}
+
 /**
  * Check if sync is needed (based on lastSyncDate)
  */
@@ -331,10 +384,20 @@ export async function setDemoMode(enabled: boolean): Promise<void> {
 }
 
 /**
- * Enable demo mode via CLI (sets up demo integration and syncs demo data)
+ * Listen for demo mode being turned on or off, e.g. to show a persistent badge
+ */
+export async function onDemoModeChanged(
+  callback: (enabled: boolean) => void
+): Promise<UnlistenFn> {
+  return listen<boolean>("demo-mode-changed", (event) => callback(event.payload));
+}
+
+/**
+ * Enable demo mode via CLI (sets up demo integration and syncs demo data).
+ * Pass fresh=true to rebuild the demo database from scratch.
  */
-export async function enableDemo(): Promise<void> {
-  await invoke("enable_demo");
+export async function enableDemo(fresh: boolean = false): Promise<void> {
+  await invoke("enable_demo", { fresh });
   // CLI modifies settings.json directly, so invalidate our cache
   invalidateSettingsCache();
 }
@@ -372,24 +435,189 @@ export interface ImportColumnMapping {
   descriptionColumn?: string;
   debitColumn?: string;
   creditColumn?: string;
+  /** Per-row ISO currency code, for files mixing currencies */
+  currencyColumn?: string;
+}
+
+/**
+ * What happens to rows whose currency column differs from the account's
+ * currency: "skip" them (the default), "keep" them unconverted and tagged
+ * fx:<code>, or "convert" them with the settings.json exchange_rates table.
+ * The original amount of kept and converted rows is stored in
+ * external_ids.original_amount.
+ */
+export type ForeignCurrencyMode = "skip" | "keep" | "convert";
+
+/** A row left out of an import because of its currency */
+export interface CurrencySkippedRow {
+  line: number | null;
+  date: string;
+  description: string;
+  amount: string;
+  currency: string;
+  reason: string;
+}
+
+/** A column mapping field that doesn't fit the file */
+export interface ColumnMappingError {
+  field: keyof ImportColumnMapping;
+  /** "notFound": no such column (names are case-sensitive); "conflict": amount with debit/credit */
+  error: "notFound" | "conflict";
+  /** Closest column name, for "notFound" */
+  suggestion?: string;
+  message: string;
+}
+
+/**
+ * Thrown by importCsvPreview/importCsvExecute when the column mapping
+ * doesn't fit the file. The CLI is not run.
+ */
+export class ImportMappingError extends Error {
+  readonly errors: ColumnMappingError[];
+
+  constructor(errors: ColumnMappingError[]) {
+    super(errors.map((e) => e.message).join("; "));
+    this.name = "ImportMappingError";
+    this.errors = errors;
+  }
+}
+
+/**
+ * Why a file can't be imported at all:
+ * - "file_not_found" / "file_unreadable"
+ * - "file_too_large": over import.max_file_mb (200 MB by default)
+ * - "wrong_file_type": an XLSX, PDF or HTML file; see `detectedType`
+ */
+export type ImportFileErrorKind =
+  | "file_not_found"
+  | "file_unreadable"
+  | "file_too_large"
+  | "wrong_file_type";
+
+/**
+ * Thrown by detectCsvColumns, importCsvPreview and importCsvExecute when the
+ * file itself can't be imported. The message says what the file is and what
+ * to do instead.
+ */
+export class ImportFileError extends Error {
+  readonly kind: ImportFileErrorKind;
+  readonly detectedType: "xlsx" | "pdf" | "html" | null;
+
+  constructor(message: string, kind: ImportFileErrorKind, detectedType: "xlsx" | "pdf" | "html" | null) {
+    super(message);
+    this.name = "ImportFileError";
+    this.kind = kind;
+    this.detectedType = detectedType;
+  }
+}
+
+/**
+ * Invoke an import command, turning mapping validation errors into
+ * ImportMappingError and file errors into ImportFileError
+ */
+async function invokeImport<T = string>(command: string, args: Record<string, unknown>): Promise<T> {
+  try {
+    return await invoke<T>(command, args);
+  } catch (e) {
+    if (typeof e === "string") {
+      let parsed: {
+        error?: string;
+        mappingErrors?: ColumnMappingError[];
+        kind?: ImportFileErrorKind;
+        detectedType?: "xlsx" | "pdf" | "html" | null;
+      } | null = null;
+      try {
+        parsed = JSON.parse(e);
+      } catch {
+        // Not a mapping or file error
+      }
+      if (parsed?.mappingErrors) {
+        throw new ImportMappingError(parsed.mappingErrors);
+      }
+      if (parsed?.kind && parsed.error) {
+        throw new ImportFileError(parsed.error, parsed.kind, parsed.detectedType ?? null);
+      }
+    }
+    throw e;
+  }
+}
+
+export interface CsvDetectionReport {
+  file_path: string;
+  delimiter: string;
+  encoding: string;
+  header_row: number;
+  /** Every header with the detection pattern it matched (null if unmatched) */
+  headers: Array<{
+    header: string;
+    field: string | null;
+    pattern: string | null;
+    used: boolean;
+  }>;
+  column_mapping: Record<string, string | null>;
+  date_samples: string[];
+  /** strftime formats that parse every sampled date */
+  date_format_candidates: string[];
+  date_format: string | null;
+  /** True when rows would be read with different date formats */
+  date_format_mixed: boolean;
+  debit_samples: string[];
+  negate_debits: boolean | null;
+  rows: Array<{
+    line: number;
+    fields: Record<string, { column: string; raw: string; parsed: string | null }>;
+    amount: string | null;
+    error: string | null;
+  }>;
+  /** Institution preset the detected mapping came from */
+  preset: {
+    id: string;
+    name: string;
+    headers: string[];
+    column_mapping: Record<string, string>;
+    flip_signs: boolean;
+    debit_negative: boolean;
+  } | null;
 }
 
 export interface ImportPreviewResult {
   file: string;
   flip_signs: boolean;
   debit_negative: boolean;
+  /** Set when columns were detected from an institution preset (not given) */
+  preset?: CsvDetectionReport["preset"];
   preview: Array<{
     date: string;
     description: string | null;
-    amount: number;
+    /** Decimal string with two decimal places */
+    amount: string;
   }>;
+  /** How the columns were detected and the first rows parsed */
+  detection?: CsvDetectionReport;
+  /** e.g. amounts that look inverted for the account; never blocks the import */
+  warnings: string[];
 }
 
 export interface ImportExecuteResult {
+  /** Batch ID recorded in the import history */
+  import_id: string | null;
+  file_sha256: string | null;
   discovered: number;
   imported: number;
   skipped: number;
+  /** Skipped rows that matched a stored row on a nearby date (import.fuzzy_dedup_days) */
+  skipped_fuzzy: number;
   fingerprints_checked: number;
+  /** Rows by currency disposition; null when no row had a currency */
+  foreign_currency: {
+    mode: ForeignCurrencyMode;
+    matched: number;
+    skipped: number;
+    kept: number;
+    converted: number;
+  } | null;
+  currency_skipped: CurrencySkippedRow[];
+  warnings: string[];
 }
 
 /**
@@ -407,6 +635,39 @@ export async function getCsvHeaders(filePath: string): Promise<string[]> {
   return invoke<string[]>("get_csv_headers", { filePath });
 }
 
+/** Institution export format recognized from a CSV file's headers */
+export interface CsvPreset {
+  id: string;
+  /** e.g. "Chase Credit Card export" */
+  name: string;
+  flipSigns: boolean;
+  debitNegative: boolean;
+}
+
+export interface CsvColumnDetection {
+  /** Mapping the CLI would detect for this file */
+  mapping: ImportColumnMapping;
+  /** Set when the mapping came from an institution preset */
+  preset: CsvPreset | null;
+  headers: string[];
+  /** Up to three non-empty values per column, in header order */
+  samples: string[][];
+}
+
+/**
+ * Detect the column mapping of a CSV file from its header and first rows.
+ * Doesn't run the CLI, so it's fast enough to call as soon as a file is picked.
+ */
+export async function detectCsvColumns(
+  filePath: string,
+  delimiter?: string
+): Promise<CsvColumnDetection> {
+  return invokeImport<CsvColumnDetection>("detect_csv_columns", {
+    filePath,
+    delimiter: delimiter ?? null,
+  });
+}
+
 /**
  * Preview CSV import (detect columns, show first few transactions)
  */
@@ -417,7 +678,7 @@ export async function importCsvPreview(
   flipSigns: boolean = false,
   debitNegative: boolean = false
 ): Promise<ImportPreviewResult> {
-  const jsonString = await invoke<string>("import_csv_preview", {
+  const jsonString = await invokeImport("import_csv_preview", {
     filePath,
     accountId,
     dateColumn: columnMapping.dateColumn || null,
@@ -425,6 +686,7 @@ export async function importCsvPreview(
     descriptionColumn: columnMapping.descriptionColumn || null,
     debitColumn: columnMapping.debitColumn || null,
     creditColumn: columnMapping.creditColumn || null,
+    currencyColumn: columnMapping.currencyColumn || null,
     flipSigns,
     debitNegative,
   });
@@ -439,9 +701,11 @@ export async function importCsvExecute(
   accountId: string,
   columnMapping: ImportColumnMapping = {},
   flipSigns: boolean = false,
-  debitNegative: boolean = false
+  debitNegative: boolean = false,
+  force: boolean = false,
+  foreignCurrency?: ForeignCurrencyMode
 ): Promise<ImportExecuteResult> {
-  const jsonString = await invoke<string>("import_csv_execute", {
+  const jsonString = await invokeImport("import_csv_execute", {
     filePath,
     accountId,
     dateColumn: columnMapping.dateColumn || null,
@@ -449,12 +713,784 @@ export async function importCsvExecute(
     descriptionColumn: columnMapping.descriptionColumn || null,
     debitColumn: columnMapping.debitColumn || null,
     creditColumn: columnMapping.creditColumn || null,
+    currencyColumn: columnMapping.currencyColumn || null,
+    flipSigns,
+    debitNegative,
+    foreignCurrency: foreignCurrency ?? null,
+    force,
+  });
+  return JSON.parse(jsonString) as ImportExecuteResult;
+}
+
+export interface ClipboardImportPreviewResult extends ImportPreviewResult {
+  /** Pass to importFromClipboardExecute to import the previewed rows */
+  clipboardToken: string;
+}
+
+/** Column options for the clipboard import commands */
+function clipboardImportOptions(
+  columnMapping: ImportColumnMapping,
+  flipSigns: boolean,
+  debitNegative: boolean
+): Record<string, unknown> {
+  return {
+    dateColumn: columnMapping.dateColumn || null,
+    amountColumn: columnMapping.amountColumn || null,
+    descriptionColumn: columnMapping.descriptionColumn || null,
+    debitColumn: columnMapping.debitColumn || null,
+    creditColumn: columnMapping.creditColumn || null,
+    currencyColumn: columnMapping.currencyColumn || null,
     flipSigns,
     debitNegative,
+  };
+}
+
+/**
+ * Preview importing the table on the clipboard (e.g. rows copied from a bank's
+ * website). Tab- and comma-separated text are accepted, up to 5 MB.
+ */
+export async function importFromClipboardPreview(
+  accountId: string,
+  columnMapping: ImportColumnMapping = {},
+  flipSigns: boolean = false,
+  debitNegative: boolean = false
+): Promise<ClipboardImportPreviewResult> {
+  const jsonString = await invokeImport("import_from_clipboard_preview", {
+    accountId,
+    options: clipboardImportOptions(columnMapping, flipSigns, debitNegative),
+  });
+  return JSON.parse(jsonString) as ClipboardImportPreviewResult;
+}
+
+/**
+ * Import the clipboard rows saved by importFromClipboardPreview
+ */
+export async function importFromClipboardExecute(
+  clipboardToken: string,
+  accountId: string,
+  columnMapping: ImportColumnMapping = {},
+  flipSigns: boolean = false,
+  debitNegative: boolean = false,
+  force: boolean = false
+): Promise<ImportExecuteResult> {
+  const jsonString = await invokeImport("import_from_clipboard_execute", {
+    clipboardToken,
+    accountId,
+    options: clipboardImportOptions(columnMapping, flipSigns, debitNegative),
+    force,
   });
   return JSON.parse(jsonString) as ImportExecuteResult;
 }
 
+// ============================================================================
+// Accounts
+// ============================================================================
+
+export interface CreateAccountOptions {
+  name: string;
+  accountType?: string | null;
+  currency?: string | null;
+  institution?: string | null;
+  nickname?: string | null;
+  openingBalance?: string | null;
+  asOf?: string | null;
+}
+
+export interface CreateAccountResult {
+  success: boolean;
+  account: {
+    id: string;
+    name: string;
+    nickname: string | null;
+    account_type: string | null;
+    currency: string;
+    institution_name: string | null;
+  };
+}
+
+/**
+ * Create a manual account (optionally with an opening balance snapshot)
+ */
+export async function createAccount(options: CreateAccountOptions): Promise<CreateAccountResult> {
+  const jsonString = await invoke<string>("create_account", {
+    name: options.name,
+    accountType: options.accountType || null,
+    currency: options.currency || null,
+    institution: options.institution || null,
+    nickname: options.nickname || null,
+    openingBalance: options.openingBalance || null,
+    asOf: options.asOf || null,
+  });
+  return JSON.parse(jsonString) as CreateAccountResult;
+}
+
+export interface UpdateAccountOptions {
+  /** YYYY-MM-DD; null clears it, undefined leaves it unchanged */
+  openedAt?: string | null;
+  /** YYYY-MM-DD; reports count the balance as zero after it. null clears it */
+  closedAt?: string | null;
+  /**
+   * Tags added to every new transaction in the account; [] clears them.
+   * Stored transactions keep theirs (see `tl maintenance apply-default-tags`)
+   */
+  defaultTags?: string[];
+  /** Name to show instead of the provider's; "" clears it, undefined leaves it */
+  nickname?: string;
+}
+
+export interface UpdateAccountResult {
+  success: boolean;
+  account: CreateAccountResult["account"] & {
+    opened_at: string | null;
+    closed_at: string | null;
+    default_tags: string[];
+  };
+  /** e.g. transactions dated after the closed date (closing still succeeds) */
+  warnings: string[];
+}
+
+function dateArg(value: string | null | undefined): string | null {
+  if (value === undefined) return null;
+  return value === null ? "none" : value;
+}
+
+/**
+ * Set when an account was opened or closed, its default tags or nickname.
+ * The edit can be reverted with undoLastChange().
+ */
+export async function updateAccount(
+  accountId: string,
+  options: UpdateAccountOptions
+): Promise<UpdateAccountResult> {
+  const jsonString = await invoke<string>("update_account", {
+    accountId,
+    openedAt: dateArg(options.openedAt),
+    closedAt: dateArg(options.closedAt),
+    defaultTags: options.defaultTags ?? null,
+    nickname: options.nickname ?? null,
+  });
+  return JSON.parse(jsonString) as UpdateAccountResult;
+}
+
+export interface BootstrapAccountOptions {
+  name: string;
+  accountType?: string | null;
+  /** 3-letter code; defaults to USD */
+  currency?: string | null;
+  /** Decimal string, e.g. "1250.00" */
+  openingBalance: string;
+  /** YYYY-MM-DD; defaults to today */
+  asOf?: string | null;
+}
+
+export interface BootstrapAccountResult {
+  account: CreateAccountResult["account"];
+  /** Onboarding state after the account was created */
+  onboarding: OnboardingState;
+}
+
+/** A new account form field that failed validation */
+export interface AccountFieldError {
+  field: "name" | "currency" | "openingBalance" | "asOf";
+  message: string;
+}
+
+/**
+ * Thrown by bootstrapManualAccount when a form field is invalid, as
+ * `tl accounts create` checks it. Nothing is written.
+ */
+export class AccountValidationError extends Error {
+  readonly errors: AccountFieldError[];
+
+  constructor(errors: AccountFieldError[]) {
+    super(errors.map((e) => e.message).join("; "));
+    this.name = "AccountValidationError";
+    this.errors = errors;
+  }
+}
+
+/**
+ * Create the first manual account together with its opening balance, for
+ * the empty state. The account and snapshot are written together, and
+ * "accounts-changed" is emitted afterwards.
+ */
+export async function bootstrapManualAccount(
+  options: BootstrapAccountOptions
+): Promise<BootstrapAccountResult> {
+  try {
+    return await invoke<BootstrapAccountResult>("bootstrap_manual_account", {
+      name: options.name,
+      accountType: options.accountType || null,
+      currency: options.currency || null,
+      openingBalance: options.openingBalance,
+      asOf: options.asOf || null,
+    });
+  } catch (e) {
+    if (typeof e === "string") {
+      let parsed: { fieldErrors?: AccountFieldError[] } | null = null;
+      try {
+        parsed = JSON.parse(e);
+      } catch {
+        // Not a validation error
+      }
+      if (parsed?.fieldErrors) {
+        throw new AccountValidationError(parsed.fieldErrors);
+      }
+    }
+    throw e;
+  }
+}
+
+/**
+ * Listen for accounts created or changed from the app
+ */
+export async function onAccountsChanged(callback: () => void): Promise<UnlistenFn> {
+  return listen("accounts-changed", () => callback());
+}
+
+export interface BalanceAsOf {
+  account_id: string;
+  account_name: string;
+  currency: string;
+  as_of: string;
+  /** Decimal string, or null when there is no data for the account at that date */
+  balance: string | null;
+  status: "snapshot" | "reconstructed" | "unknown";
+  reconstructed: boolean;
+  anchor: {
+    snapshot_time: string;
+    balance: string;
+    source: string | null;
+  } | null;
+  transactions_applied: number;
+}
+
+/**
+ * Get end-of-day balances on a past date (YYYY-MM-DD).
+ * Returns one entry for the given account, or one per account when omitted.
+ */
+export async function balanceAsOf(date: string, accountId?: string): Promise<BalanceAsOf[]> {
+  const jsonString = await invoke<string>("balance_as_of", {
+    date,
+    accountId: accountId || null,
+  });
+  return JSON.parse(jsonString).balances as BalanceAsOf[];
+}
+
+export interface AccountSummary {
+  id: string;
+  name: string;
+  institution_name: string | null;
+  currency: string;
+  /** Latest snapshot balance as a Decimal string, or null when unknown */
+  balance: string | null;
+}
+
+export interface AccountGroup {
+  /** Institution name; accounts without one are grouped under "Other" */
+  institution: string;
+  /** Accounts in the group, sorted by name */
+  account_ids: string[];
+  account_count: number;
+  /** Decimal-string subtotal per currency code */
+  subtotals: Record<string, string>;
+  /** Accounts in the group with no known balance (not included in subtotals) */
+  unknown_balances: number;
+}
+
+export interface AccountListing {
+  accounts: AccountSummary[];
+  /** Sorted by institution name, "Other" last */
+  groups: AccountGroup[];
+  /** Decimal-string grand total per currency code */
+  totals: Record<string, string>;
+}
+
+/**
+ * List accounts with their latest balances, grouped by institution
+ */
+export async function listAccounts(): Promise<AccountListing> {
+  const response = JSON.parse(await invoke<string>("list_accounts"));
+  return {
+    accounts: response.accounts || [],
+    groups: response.groups || [],
+    totals: response.totals || {},
+  };
+}
+
+// ============================================================================
+// Reports
+// ============================================================================
+
+export interface MerchantSpending {
+  /** Most common spelling of the merchant's description in the period */
+  merchant: string;
+  /** Decimal string, positive for spending */
+  amount: string;
+  count: number;
+  /** Average ticket, Decimal string */
+  average: string;
+  /** Spending in the preceding period, Decimal string */
+  prior_amount: string;
+  change: string;
+  /** Decimal string, or null without prior spending */
+  percent_change: string | null;
+  /** True when there was no spending at the merchant in the preceding period */
+  new: boolean;
+}
+
+export interface MerchantReport {
+  start_date: string;
+  end_date: string;
+  prior_start_date: string;
+  prior_end_date: string;
+  total_expenses: string;
+  /** Largest first */
+  merchants: MerchantSpending[];
+}
+
+export interface MerchantReportOptions {
+  /** Period string such as "last-month" or "2024-Q3"; year to date by default */
+  period?: string;
+  /** YYYY-MM-DD, instead of period */
+  since?: string;
+  until?: string;
+  /** Number of merchants (default 20) */
+  top?: number;
+}
+
+/**
+ * Top merchants by spending, with the change from the preceding period of
+ * the same length. Income and transfers are excluded.
+ */
+export async function spendingByMerchant(options: MerchantReportOptions = {}): Promise<MerchantReport> {
+  const jsonString = await invoke<string>("spending_by_merchant", {
+    period: options.period ?? null,
+    since: options.since ?? null,
+    until: options.until ?? null,
+    top: options.top ?? null,
+  });
+  return JSON.parse(jsonString) as MerchantReport;
+}
+
+/** Rolling average computed by the CLI over every day of the series */
+export interface SeriesSmoothing {
+  window_days: number;
+  /** Simple or exponential (alpha = 2 / (window_days + 1)) */
+  kind: "sma" | "ema";
+}
+
+export type SeriesInterval = "day" | "week" | "month";
+
+export interface SeriesOptions {
+  /** Period string such as "last-year"; year to date by default */
+  period?: string;
+  /** YYYY-MM-DD, instead of period */
+  since?: string;
+  until?: string;
+  /** One point per day (default), week or month */
+  interval?: SeriesInterval;
+  /** Adds "smoothed" to each point, computed before points are reduced to the interval */
+  smoothing?: SeriesSmoothing;
+}
+
+/** Least-squares line through the daily values in the range */
+export interface SeriesTrend {
+  start_date: string;
+  end_date: string;
+  /** Decimal strings, rounded half-even to cents */
+  slope_per_month: string;
+  projected_30d: string;
+  projected_90d: string;
+}
+
+export interface BalanceHistoryPoint {
+  /** The day, or the last day of the week or month the point covers */
+  date: string;
+  /** End-of-day balance, Decimal string */
+  balance: string;
+  smoothed?: string;
+}
+
+export interface BalanceHistory {
+  account_id: string;
+  account: string;
+  currency: string;
+  start_date: string;
+  end_date: string;
+  smoothing: SeriesSmoothing | null;
+  interval: SeriesInterval;
+  series: BalanceHistoryPoint[];
+  /** null with fewer than two days */
+  trend: SeriesTrend | null;
+}
+
+/**
+ * End-of-day balances of an account, optionally smoothed, with a trend line
+ */
+export async function getBalanceHistory(
+  accountId: string,
+  options: SeriesOptions = {}
+): Promise<BalanceHistory> {
+  const jsonString = await invoke<string>("balance_history", {
+    accountId,
+    period: options.period ?? null,
+    since: options.since ?? null,
+    until: options.until ?? null,
+    interval: options.interval ?? null,
+    smoothing: options.smoothing ?? null,
+  });
+  return JSON.parse(jsonString) as BalanceHistory;
+}
+
+export interface CashFlowPoint {
+  date: string;
+  /** Income minus expenses (transfers excluded) over the point's days, Decimal string */
+  net: string;
+  smoothed?: string;
+}
+
+export interface CashFlowOptions extends SeriesOptions {
+  /** Leave out transactions with any of these tags */
+  excludeTags?: string[];
+}
+
+export interface CashFlowMonth {
+  month?: string;
+  income: string;
+  /** Decimal strings by category, positive for spending */
+  expenses: Record<string, string>;
+  total_expenses: string;
+  net_savings: string;
+  /** Percent, or null without income */
+  savings_rate: string | null;
+}
+
+export interface CashFlowReport {
+  start_date: string;
+  end_date: string;
+  months: CashFlowMonth[];
+  totals: CashFlowMonth;
+  /** Present when interval or smoothing was given */
+  smoothing?: SeriesSmoothing | null;
+  interval?: SeriesInterval;
+  series?: CashFlowPoint[];
+  trend?: SeriesTrend | null;
+}
+
+/**
+ * Monthly cash-flow statement; with interval or smoothing, also the net
+ * flow as a series with a trend line
+ */
+export async function getCashFlow(options: CashFlowOptions = {}): Promise<CashFlowReport> {
+  const jsonString = await invoke<string>("cash_flow", {
+    period: options.period ?? null,
+    since: options.since ?? null,
+    until: options.until ?? null,
+    excludeTags: options.excludeTags ?? null,
+    interval: options.interval ?? null,
+    smoothing: options.smoothing ?? null,
+  });
+  return JSON.parse(jsonString) as CashFlowReport;
+}
+
+// ============================================================================
+// Transactions
+// ============================================================================
+
+export type TransactionStatus = "pending" | "cleared" | "reconciled";
+
+export interface TransactionFilters {
+  accountId?: string | null;
+  status?: TransactionStatus | null;
+  /** YYYY-MM-DD, inclusive */
+  since?: string | null;
+  /** YYYY-MM-DD, inclusive */
+  until?: string | null;
+  limit?: number | null;
+}
+
+export interface TransactionRecord {
+  id: string;
+  account_id: string;
+  amount: string;
+  description: string | null;
+  transaction_date: string;
+  posted_date: string;
+  tags: string[];
+  status: TransactionStatus;
+  parent_transaction_id: string | null;
+}
+
+/**
+ * List non-deleted transactions, newest first
+ */
+export async function listTransactions(filters: TransactionFilters = {}): Promise<TransactionRecord[]> {
+  const jsonString = await invoke<string>("list_transactions", {
+    accountId: filters.accountId || null,
+    status: filters.status || null,
+    since: filters.since || null,
+    until: filters.until || null,
+    limit: filters.limit ?? null,
+  });
+  return JSON.parse(jsonString).transactions as TransactionRecord[];
+}
+
+/**
+ * Set the status of one or more transactions (e.g. clear pending ones)
+ */
+export async function setTransactionStatus(
+  transactionIds: string[],
+  status: TransactionStatus
+): Promise<string[]> {
+  const jsonString = await invoke<string>("set_transaction_status", { transactionIds, status });
+  return JSON.parse(jsonString).transaction_ids as string[];
+}
+
+export interface TransactionTags {
+  transactionId: string;
+  tags: string[];
+}
+
+/**
+ * Replace the tags of one or more transactions.
+ * Tags are stored trimmed and lowercase without a leading '#' (like `tl tag`);
+ * returns the tags as stored.
+ */
+export async function setTransactionTags(updates: TransactionTags[]): Promise<TransactionTags[]> {
+  if (updates.length === 0) return [];
+  return invoke<TransactionTags[]>("set_transaction_tags", { updates });
+}
+
+// ============================================================================
+// Undo
+// ============================================================================
+
+/** Values a change overwrote, written back by undoLastChange() */
+export type UndoChange =
+  | {
+      kind: "tags";
+      transactions: Array<{
+        transactionId: string;
+        /** null when the transaction had no tags column value */
+        tags: string[] | null;
+        /** updated_at right after the change */
+        changedAt: string;
+      }>;
+    }
+  | {
+      kind: "account";
+      account: {
+        accountId: string;
+        name: string;
+        nickname: string | null;
+        openedAt: string | null;
+        closedAt: string | null;
+        /** JSON array, as stored */
+        defaultTags: string | null;
+        changedAt: string;
+      };
+    };
+
+export interface UndoEntry {
+  id: number;
+  /** e.g. "Edit tags of 3 transaction(s)" */
+  description: string;
+  change: UndoChange;
+}
+
+/**
+ * Tag and account edits made in the app that can be undone, newest first.
+ * Kept for the last 100 edits, until the app restarts or switches between
+ * the demo and real databases.
+ */
+export async function getUndoStack(): Promise<UndoEntry[]> {
+  return invoke<UndoEntry[]>("get_undo_stack");
+}
+
+/**
+ * Revert the most recent edit and return it. Fails with "Nothing to undo",
+ * or with a "row changed since" error (the edit is then dropped) when the
+ * rows were changed again afterwards, e.g. by a sync.
+ * "transactions-changed" or "accounts-changed" is emitted afterwards.
+ */
+export async function undoLastChange(): Promise<UndoEntry> {
+  return invoke<UndoEntry>("undo_last_change");
+}
+
+/**
+ * Listen for transactions rewritten by undo
+ */
+export async function onTransactionsChanged(callback: () => void): Promise<UnlistenFn> {
+  return listen("transactions-changed", () => callback());
+}
+
+export interface TransactionAttachment {
+  id: string;
+  transaction_id: string;
+  filename: string;
+  /** Relative to the attachments directory */
+  stored_path: string;
+  sha256: string;
+  size_bytes: number;
+  created_at: string;
+  /** Absolute path of the stored copy */
+  path: string;
+}
+
+/**
+ * Pick a file and attach it to a transaction. The file is copied into
+ * ~/.treeline/attachments. Returns null if the picker was cancelled.
+ */
+export async function attachFileToTransaction(
+  transactionId: string
+): Promise<TransactionAttachment | null> {
+  const jsonString = await invoke<string | null>("attach_file_to_transaction", { transactionId });
+  return jsonString ? (JSON.parse(jsonString).attachment as TransactionAttachment) : null;
+}
+
+/**
+ * List a transaction's attachments
+ */
+export async function listAttachments(transactionId: string): Promise<TransactionAttachment[]> {
+  const jsonString = await invoke<string>("list_attachments", { transactionId });
+  return JSON.parse(jsonString).attachments as TransactionAttachment[];
+}
+
+/**
+ * Open an attachment with the system's default app
+ */
+export async function openAttachment(attachmentId: string): Promise<void> {
+  await invoke<void>("open_attachment", { attachmentId });
+}
+
+// ============================================================================
+// Planned transactions
+// ============================================================================
+
+export type PlannedFrequency = "once" | "weekly" | "biweekly" | "monthly" | "quarterly" | "yearly";
+
+export interface PlannedTransaction {
+  id: string;
+  account_id: string;
+  description: string;
+  /** Decimal string, negative for bills */
+  amount: string;
+  frequency: PlannedFrequency;
+  /** Day monthly, quarterly and yearly items fall on (clamped to short months) */
+  day_of_month: number | null;
+  /** YYYY-MM-DD; advanced when a matching transaction syncs in */
+  next_due: string;
+  /** Added by recurring-transaction detection rather than by hand */
+  autogenerated: boolean;
+  last_transaction_id: string | null;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface PlannedTransactionInput {
+  /** ID (or ID prefix) of the item to replace; omit to add one */
+  id?: string;
+  /** Account ID, name or nickname */
+  account: string;
+  description: string;
+  /** Decimal string, negative for bills */
+  amount: string;
+  frequency: PlannedFrequency;
+  /** YYYY-MM-DD; may be omitted for monthly items when dayOfMonth is set */
+  nextDue?: string;
+  dayOfMonth?: number;
+}
+
+/**
+ * List planned transactions, soonest due first
+ */
+export async function listPlanned(): Promise<PlannedTransaction[]> {
+  const jsonString = await invoke<string>("list_planned");
+  return JSON.parse(jsonString).planned as PlannedTransaction[];
+}
+
+/**
+ * Add a planned transaction, or replace the one with `planned.id`
+ */
+export async function upsertPlanned(planned: PlannedTransactionInput): Promise<PlannedTransaction> {
+  const jsonString = await invoke<string>("upsert_planned", {
+    planned: {
+      id: planned.id ?? null,
+      account: planned.account,
+      description: planned.description,
+      amount: planned.amount,
+      frequency: planned.frequency,
+      nextDue: planned.nextDue ?? null,
+      dayOfMonth: planned.dayOfMonth ?? null,
+    },
+  });
+  return JSON.parse(jsonString).planned as PlannedTransaction;
+}
+
+// ============================================================================
+// Duplicate review
+// ============================================================================
+
+export type DuplicateKind = "transaction" | "account";
+
+export type DuplicateStatus = "open" | "dismissed" | "merged";
+
+export interface DuplicateCandidate {
+  id: string;
+  kind: DuplicateKind;
+  /** Transaction or account kept when the pair is merged */
+  keep_id: string;
+  duplicate_id: string;
+  /** How alike the pair is, from 0 to 1 */
+  score: number;
+  status: DuplicateStatus;
+  created_at: string;
+  resolved_at: string | null;
+}
+
+export interface DuplicateReviewItem {
+  candidate: DuplicateCandidate;
+  /** Transaction or account for each side, or null if it has since been removed */
+  keep: Record<string, unknown> | null;
+  duplicate: Record<string, unknown> | null;
+}
+
+export interface DuplicateMergeResult {
+  candidate: string;
+  kind: DuplicateKind;
+  kept: string;
+  removed: string;
+  /** Account pairs only: integrations that stopped syncing the duplicate */
+  integrations?: string[];
+}
+
+/**
+ * List possible duplicates, oldest first (open ones unless a status is given)
+ */
+export async function listDuplicateCandidates(
+  status: DuplicateStatus | "all" = "open"
+): Promise<DuplicateReviewItem[]> {
+  const jsonString = await invoke<string>("list_duplicate_candidates", { status });
+  return JSON.parse(jsonString).candidates as DuplicateReviewItem[];
+}
+
+/**
+ * Mark a pair as not a duplicate; detecting it again won't reopen it
+ */
+export async function dismissDuplicate(id: string): Promise<DuplicateCandidate> {
+  const jsonString = await invoke<string>("dismiss_duplicate", { id });
+  return JSON.parse(jsonString).candidate as DuplicateCandidate;
+}
+
+/**
+ * Merge a pair into the side that is kept
+ */
+export async function mergeDuplicate(id: string): Promise<DuplicateMergeResult> {
+  const jsonString = await invoke<string>("merge_duplicate", { id });
+  return JSON.parse(jsonString) as DuplicateMergeResult;
+}
+
 // ============================================================================
 // Integrations
 // ============================================================================
@@ -466,6 +1502,70 @@ export async function setupSimplefin(token: string): Promise<string> {
   return invoke<string>("setup_simplefin", { token });
 }
 
+/**
+ * Integration setup received via a treeline://setup/... deep link.
+ * The token stays in the backend; confirm or dismiss by id.
+ */
+export interface DeepLinkSetup {
+  id: number;
+  integration: string;
+  /** Host the setup token will be claimed from */
+  claim_host: string;
+}
+
+/**
+ * Setup link waiting for confirmation (e.g. one that launched the app)
+ */
+export async function getPendingDeepLinkSetup(): Promise<DeepLinkSetup | null> {
+  return invoke<DeepLinkSetup | null>("get_pending_deep_link_setup");
+}
+
+/**
+ * Listen for setup links as they become ready to confirm.
+ * Links are shown one at a time; the next arrives after confirm or dismiss.
+ */
+export async function onDeepLinkSetup(
+  callback: (setup: DeepLinkSetup) => void
+): Promise<UnlistenFn> {
+  return listen<DeepLinkSetup>("deep-link-setup", (event) => callback(event.payload));
+}
+
+/**
+ * Set up the integration from a deep link the user confirmed
+ */
+export async function confirmDeepLinkSetup(id: number): Promise<string> {
+  return invoke<string>("confirm_deep_link_setup", { id });
+}
+
+/**
+ * Discard a deep link the user declined
+ */
+export async function dismissDeepLinkSetup(id: number): Promise<void> {
+  await invoke("dismiss_deep_link_setup", { id });
+}
+
+/**
+ * Result of an integration connection check.
+ * On failure, `kind` is a short error kind (e.g. "unauthorized", "payment_required", "timeout").
+ */
+export interface IntegrationTestResult {
+  success: boolean;
+  integration: string;
+  account_count?: number;
+  http_status?: number | null;
+  warnings?: string[];
+  error?: string;
+  kind?: string;
+}
+
+/**
+ * Check an integration's credentials without syncing or writing anything
+ */
+export async function testIntegration(name: string): Promise<IntegrationTestResult> {
+  const jsonString = await invoke<string>("test_integration", { name });
+  return JSON.parse(jsonString) as IntegrationTestResult;
+}
+
 // ============================================================================
 // Integration Account Settings
 // ============================================================================
@@ -515,10 +1615,10 @@ export async function updateIntegrationAccountSetting(
 
   // Write back to database
   const settingsJson = JSON.stringify(settings).replace(/'/g, "''"); // Escape single quotes for SQL
-  await invoke<string>("execute_query", {
-    query: `UPDATE sys_integrations SET integration_settings = '${settingsJson}' WHERE integration_name = '${integrationName}'`,
-    readonly: false,
-  });
+  await executeQuery(
+    `UPDATE sys_integrations SET integration_settings = '${settingsJson}' WHERE integration_name = '${integrationName}'`,
+    { readonly: false },
+  );
 }
 
 // ============================================================================
@@ -557,6 +1657,82 @@ export async function uninstallPlugin(pluginId: string): Promise<{ success: bool
   return JSON.parse(jsonString);
 }
 
+// ============================================================================
+// Database Compatibility
+// ============================================================================
+
+export interface DbCompatibility {
+  compatible: boolean;
+  data_version: number | null;
+  supported_data_version: number;
+  min_app_version: string | null;
+}
+
+/**
+ * Check whether the database was written by a newer Treeline
+ * (called on app startup, before rendering any views)
+ */
+export async function checkDbCompatibility(): Promise<DbCompatibility> {
+  return invoke<DbCompatibility>("check_db_compatibility");
+}
+
+export interface ViewDrift {
+  view: string;
+  /** The view doesn't exist at all */
+  missing: boolean;
+  missing_columns: string[];
+  unexpected_columns: string[];
+}
+
+export interface ViewCheck {
+  ok: boolean;
+  drift: ViewDrift[];
+}
+
+export interface ViewRepair {
+  repaired: string[];
+  remaining_drift: ViewDrift[];
+}
+
+/**
+ * Check the views (transactions, accounts, ...) against the expected schema.
+ * Views drift when they're dropped or replaced by hand in the query editor.
+ */
+export async function checkViews(): Promise<ViewCheck> {
+  const result = await invoke<string>("check_views");
+  return JSON.parse(result);
+}
+
+/**
+ * Recreate the views from the schema migrations (tables and data are untouched)
+ */
+export async function repairViews(): Promise<ViewRepair> {
+  const result = await invoke<string>("repair_views");
+  return JSON.parse(result);
+}
+
+// ============================================================================
+// Onboarding
+// ============================================================================
+
+export interface OnboardingState {
+  db_initialized: boolean;
+  has_accounts: boolean;
+  has_transactions: boolean;
+  integrations: string[];
+  untyped_account_count: number;
+  demo_mode: boolean;
+  last_sync_at: string | null;
+}
+
+/**
+ * Get everything the onboarding flow needs in one cheap call
+ * (safe to poll after every onboarding action)
+ */
+export async function getOnboardingState(): Promise<OnboardingState> {
+  return invoke<OnboardingState>("get_onboarding_state");
+}
+
 // ============================================================================
 // Encryption
 // ============================================================================